@@ -0,0 +1,94 @@
+// fetch-ocr-models.mjs — Download the OCR models bundled into the installer
+// Usage: node scripts/fetch-ocr-models.mjs [--update]
+//
+// Models are downloaded into src-tauri/resources/ocr/models/ and verified against
+// src-tauri/resources/ocr/models.sha256. Files that are already present with the
+// pinned hash are not downloaded again. Models without a pinned hash are skipped
+// with a warning (the build succeeds without OCR); a hash mismatch fails the build.
+// `--update` records the hashes of freshly downloaded models instead; run it once
+// after changing the model URLs and commit the checksum file.
+
+import { createHash } from "node:crypto";
+import { existsSync } from "node:fs";
+import { readFile, rename, rm, writeFile } from "node:fs/promises";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const MODEL_BASE_URL = "https://ocrs-models.s3-accelerate.amazonaws.com";
+const MODELS = ["text-detection.rten", "text-recognition.rten"];
+
+const root = dirname(dirname(fileURLToPath(import.meta.url)));
+const ocrDir = join(root, "src-tauri", "resources", "ocr");
+const modelDir = join(ocrDir, "models");
+const checksumPath = join(ocrDir, "models.sha256");
+const update = process.argv.includes("--update");
+
+async function readChecksums() {
+  if (!existsSync(checksumPath)) return new Map();
+  const lines = (await readFile(checksumPath, "utf8")).split(/\r?\n/);
+  const entries = lines
+    .map((line) => line.trim())
+    .filter((line) => line && !line.startsWith("#"))
+    .map((line) => {
+      const [hash, name] = line.split(/\s+/);
+      return [name, hash.toLowerCase()];
+    });
+  return new Map(entries);
+}
+
+async function sha256(path) {
+  return createHash("sha256").update(await readFile(path)).digest("hex");
+}
+
+async function download(name) {
+  const url = `${MODEL_BASE_URL}/${name}`;
+  console.log(`Downloading ${url} ...`);
+  const response = await fetch(url);
+  if (!response.ok) {
+    throw new Error(`Failed to download ${name}: HTTP ${response.status}`);
+  }
+  const part = join(modelDir, `${name}.part`);
+  await writeFile(part, Buffer.from(await response.arrayBuffer()));
+  return part;
+}
+
+const checksums = await readChecksums();
+let failed = false;
+
+for (const name of MODELS) {
+  const target = join(modelDir, name);
+  const expected = checksums.get(name);
+
+  if (expected && existsSync(target) && (await sha256(target)) === expected) {
+    console.log(`${name}: up to date`);
+    continue;
+  }
+
+  if (!expected && !update) {
+    console.warn(
+      `${name}: no pinned SHA-256 in ${checksumPath}, skipped; OCR will be unavailable in this build ` +
+        "(run with --update to record it)",
+    );
+    continue;
+  }
+
+  const part = await download(name);
+  const actual = await sha256(part);
+  if (expected && actual !== expected) {
+    await rm(part);
+    console.error(`${name}: SHA-256 mismatch (expected ${expected}, got ${actual})`);
+    failed = true;
+    continue;
+  }
+  await rename(part, target);
+  checksums.set(name, actual);
+  console.log(`${name}: ${actual}`);
+}
+
+if (update && !failed) {
+  const lines = MODELS.map((name) => `${checksums.get(name)}  ${name}`);
+  await writeFile(checksumPath, `${lines.join("\n")}\n`);
+  console.log(`Updated ${checksumPath}`);
+}
+
+if (failed) process.exit(1);
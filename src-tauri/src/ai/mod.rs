@@ -0,0 +1,285 @@
+//! AI 操作：对单个条目或多个条目的合并内容运行提示词模板（总结、改写、解释代码等）
+//!
+//! 模板保存在设置项 `ai_templates`（JSON 数组），未配置时使用内置模板。提示词中的
+//! `{{text}}` 替换为条目文本，不含占位符时文本追加在提示词之后。接口与代理配置默认沿用
+//! OpenAI 翻译的设置，可用 `ai_endpoint` / `ai_api_key` / `ai_model` 单独覆盖。
+
+pub mod openai;
+
+use openai::{ChatConfig, ChatMessage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 设置项：自定义模板列表
+pub const TEMPLATES_SETTING: &str = "ai_templates";
+/// 提示词中的文本占位符
+pub const TEXT_PLACEHOLDER: &str = "{{text}}";
+/// 多个条目合并时的分隔
+pub const MERGE_SEPARATOR: &str = "\n\n---\n\n";
+/// 单次请求输入上限（字符），避免误选超大条目
+pub const MAX_INPUT_CHARS: usize = 100_000;
+
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+const MAX_TEMPLATES: usize = 64;
+
+/// 提示词模板
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiTemplate {
+    pub id: String,
+    pub name: String,
+    /// 系统提示词（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub prompt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+}
+
+impl AiTemplate {
+    fn builtin(id: &str, name: &str, prompt: &str, temperature: f32) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            system: None,
+            prompt: prompt.to_string(),
+            temperature: Some(temperature),
+        }
+    }
+
+    pub fn temperature(&self) -> f32 {
+        self.temperature.unwrap_or(DEFAULT_TEMPERATURE)
+    }
+
+    /// 生成请求消息；`texts` 为各条目文本，按顺序合并
+    pub fn render(&self, texts: &[String]) -> Vec<ChatMessage> {
+        let text = texts.join(MERGE_SEPARATOR);
+        let prompt = if self.prompt.contains(TEXT_PLACEHOLDER) {
+            self.prompt.replace(TEXT_PLACEHOLDER, &text)
+        } else {
+            format!("{}\n\n{text}", self.prompt.trim_end())
+        };
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system) = self.system.as_deref().filter(|s| !s.trim().is_empty()) {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(prompt));
+        messages
+    }
+}
+
+/// 内置模板
+pub fn default_templates() -> Vec<AiTemplate> {
+    vec![
+        AiTemplate::builtin(
+            "summarize",
+            "总结",
+            "Summarize the following content concisely, in the same language as the content. Output only the summary.\n\n{{text}}",
+            0.3,
+        ),
+        AiTemplate::builtin(
+            "rewrite",
+            "改写润色",
+            "Rewrite the following text to be clearer and more fluent while keeping its meaning and language. Output only the rewritten text.\n\n{{text}}",
+            0.7,
+        ),
+        AiTemplate::builtin(
+            "explain_code",
+            "解释代码",
+            "Explain what the following code does, step by step, then point out potential issues.\n\n```\n{{text}}\n```",
+            0.3,
+        ),
+        AiTemplate::builtin(
+            "extract_todos",
+            "提取待办",
+            "Extract all action items / TODOs from the following content as a Markdown checklist (`- [ ] ...`). Output only the list; output nothing if there are none.\n\n{{text}}",
+            0.2,
+        ),
+        AiTemplate::builtin(
+            "generate_regex",
+            "生成正则",
+            "Write a regular expression that matches strings like the following examples. Output the regex on the first line, then a one-line explanation.\n\n{{text}}",
+            0.2,
+        ),
+    ]
+}
+
+/// 解析设置中的模板；未配置或内容无效时返回内置模板
+pub fn parse_templates(raw: Option<&str>) -> Vec<AiTemplate> {
+    raw.filter(|s| !s.trim().is_empty())
+        .and_then(|s| serde_json::from_str::<Vec<AiTemplate>>(s).ok())
+        .filter(|templates| validate_templates(templates).is_ok())
+        .unwrap_or_else(default_templates)
+}
+
+pub fn validate_templates(templates: &[AiTemplate]) -> Result<(), String> {
+    if templates.len() > MAX_TEMPLATES {
+        return Err(format!("模板数量超过上限 {MAX_TEMPLATES}"));
+    }
+    let mut ids = HashSet::new();
+    for template in templates {
+        let id = template.id.as_str();
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("模板 ID 无效: {id}"));
+        }
+        if !ids.insert(id) {
+            return Err(format!("模板 ID 重复: {id}"));
+        }
+        if template.name.trim().is_empty() {
+            return Err(format!("模板 {id} 缺少名称"));
+        }
+        if template.prompt.trim().is_empty() {
+            return Err(format!("模板 {id} 缺少提示词"));
+        }
+        if let Some(t) = template.temperature
+            && !(0.0..=2.0).contains(&t)
+        {
+            return Err(format!("模板 {id} 的 temperature 需在 0~2 之间"));
+        }
+    }
+    Ok(())
+}
+
+/// AI 操作的接口与代理配置
+#[derive(Debug, Clone, Default)]
+pub struct AiSettings {
+    pub chat: ChatConfig,
+    pub proxy_mode: String,
+    pub proxy_url: String,
+}
+
+impl AiSettings {
+    /// 从设置读取：`ai_*` 优先，为空时沿用 OpenAI 翻译配置
+    pub fn load(get: impl Fn(&str) -> Option<String>) -> Self {
+        let pick = |own: &str, fallback: &str| {
+            get(own)
+                .filter(|v| !v.trim().is_empty())
+                .or_else(|| get(fallback))
+                .unwrap_or_default()
+        };
+        Self {
+            chat: ChatConfig {
+                endpoint: pick("ai_endpoint", "translate_openai_endpoint"),
+                api_key: pick("ai_api_key", "translate_openai_api_key"),
+                model: pick("ai_model", "translate_openai_model"),
+            },
+            proxy_mode: get("translate_proxy_mode").unwrap_or_else(|| "system".to_string()),
+            proxy_url: get("translate_proxy_url").unwrap_or_default(),
+        }
+    }
+
+    /// 使用官方接口时必须配置密钥；自定义（本地）接口可以不填
+    pub fn validate(&self) -> Result<(), String> {
+        if self.chat.base_url() == openai::DEFAULT_ENDPOINT && self.chat.api_key.is_empty() {
+            return Err("AI:INVALID_CONFIG:API_KEY".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn render_merges_items_into_placeholder() {
+        let template = AiTemplate {
+            id: "t".into(),
+            name: "T".into(),
+            system: Some("You are terse.".into()),
+            prompt: "Summarize:\n{{text}}\nEnd.".into(),
+            temperature: None,
+        };
+        let messages = template.render(&["a".into(), "b".into()]);
+        assert_eq!(messages[0], ChatMessage::system("You are terse."));
+        assert_eq!(
+            messages[1],
+            ChatMessage::user("Summarize:\na\n\n---\n\nb\nEnd.")
+        );
+        assert_eq!(template.temperature(), DEFAULT_TEMPERATURE);
+
+        let template = AiTemplate {
+            system: None,
+            prompt: "Explain this  \n".into(),
+            ..template
+        };
+        assert_eq!(
+            template.render(&["x".into()]),
+            [ChatMessage::user("Explain this\n\nx")]
+        );
+    }
+
+    #[test]
+    fn parse_falls_back_to_defaults() {
+        assert_eq!(parse_templates(None), default_templates());
+        assert_eq!(parse_templates(Some("not json")), default_templates());
+        assert_eq!(
+            parse_templates(Some(
+                r#"[{"id":"a","name":"A","prompt":"p"},{"id":"a","name":"B","prompt":"q"}]"#
+            )),
+            default_templates()
+        );
+        let parsed = parse_templates(Some(
+            r#"[{"id":"sql","name":"SQL","prompt":"Format {{text}}","temperature":0}]"#,
+        ));
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].temperature(), 0.0);
+        assert!(validate_templates(&default_templates()).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_templates() {
+        let ok = AiTemplate::builtin("ok", "OK", "p", 0.5);
+        assert!(validate_templates(std::slice::from_ref(&ok)).is_ok());
+        for bad in [
+            AiTemplate {
+                id: "a b".into(),
+                ..ok.clone()
+            },
+            AiTemplate {
+                name: " ".into(),
+                ..ok.clone()
+            },
+            AiTemplate {
+                prompt: "".into(),
+                ..ok.clone()
+            },
+            AiTemplate {
+                temperature: Some(3.0),
+                ..ok.clone()
+            },
+        ] {
+            assert!(validate_templates(&[bad]).is_err());
+        }
+    }
+
+    #[test]
+    fn settings_fall_back_to_translate_config() {
+        let values: HashMap<&str, &str> = [
+            ("translate_openai_endpoint", "http://localhost:11434/v1"),
+            ("translate_openai_model", "qwen2.5"),
+            ("ai_model", "llama3"),
+            ("ai_endpoint", ""),
+            ("translate_proxy_mode", "none"),
+        ]
+        .into_iter()
+        .collect();
+        let settings = AiSettings::load(|k| values.get(k).map(|v| v.to_string()));
+        assert_eq!(settings.chat.endpoint, "http://localhost:11434/v1");
+        assert_eq!(settings.chat.model, "llama3");
+        assert_eq!(settings.proxy_mode, "none");
+        // 本地接口无需密钥
+        assert!(settings.validate().is_ok());
+
+        let settings = AiSettings::load(|_| None);
+        assert_eq!(settings.proxy_mode, "system");
+        assert_eq!(
+            settings.validate().unwrap_err(),
+            "AI:INVALID_CONFIG:API_KEY"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! OpenAI 兼容接口（`/chat/completions`）客户端，翻译与 AI 操作共用
+//!
+//! 兼容 OpenAI、Ollama、LM Studio、vLLM 等实现：流式请求按 SSE 逐行解析
+//! `data: {"choices":[{"delta":{"content":"..."}}]}`，服务端忽略 `stream` 直接返回完整
+//! JSON 时按非流式结果处理。
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.openai.com/v1";
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// 接口配置；`endpoint` / `model` 为空时使用默认值
+#[derive(Debug, Clone, Default)]
+pub struct ChatConfig {
+    pub endpoint: String,
+    pub api_key: String,
+    pub model: String,
+}
+
+impl ChatConfig {
+    pub fn base_url(&self) -> &str {
+        let endpoint = self.endpoint.trim();
+        if endpoint.is_empty() {
+            DEFAULT_ENDPOINT
+        } else {
+            endpoint.trim_end_matches('/')
+        }
+    }
+
+    pub fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url())
+    }
+
+    pub fn model(&self) -> &str {
+        let model = self.model.trim();
+        if model.is_empty() {
+            DEFAULT_MODEL
+        } else {
+            model
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChatMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: "system",
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user",
+            content: content.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ChatError {
+    /// 连接 / 发送失败
+    Request(String),
+    /// 读取响应体失败
+    Read(String),
+    /// 非 2xx 状态码
+    Provider { status: String, body: String },
+    /// 响应不是合法 JSON
+    Parse(String),
+    /// JSON 结构不符合 chat/completions 格式
+    InvalidFormat,
+    /// 被调用方取消
+    Cancelled,
+}
+
+impl ChatError {
+    /// 转为 `<prefix>:<CODE>[:detail]` 形式的结构化错误码，由前端本地化
+    pub fn code(&self, prefix: &str) -> String {
+        match self {
+            Self::Request(e) => format!("{prefix}:REQUEST_FAILED:{e}"),
+            Self::Read(e) => format!("{prefix}:READ_RESPONSE_FAILED:{e}"),
+            Self::Provider { status, body } => {
+                format!("{prefix}:PROVIDER_ERROR:openai:{status}:{body}")
+            }
+            Self::Parse(e) => format!("{prefix}:PARSE_RESPONSE_FAILED:{e}"),
+            Self::InvalidFormat => format!("{prefix}:INVALID_FORMAT"),
+            Self::Cancelled => format!("{prefix}:CANCELLED"),
+        }
+    }
+}
+
+fn send(
+    client: &reqwest::blocking::Client,
+    config: &ChatConfig,
+    messages: &[ChatMessage],
+    temperature: f32,
+    stream: bool,
+) -> Result<reqwest::blocking::Response, ChatError> {
+    let body = json!({
+        "model": config.model(),
+        "messages": messages,
+        "temperature": temperature,
+        "stream": stream,
+    });
+    let mut request = client.post(config.completions_url()).json(&body);
+    // 本地服务（Ollama 等）通常无需密钥
+    if !config.api_key.is_empty() {
+        request = request.header("Authorization", format!("Bearer {}", config.api_key));
+    }
+    let resp = request
+        .send()
+        .map_err(|e| ChatError::Request(e.to_string()))?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().map_err(|e| ChatError::Read(e.to_string()))?;
+        return Err(ChatError::Provider {
+            status: status.to_string(),
+            body,
+        });
+    }
+    Ok(resp)
+}
+
+fn message_content(val: &Value) -> Result<String, ChatError> {
+    val["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or(ChatError::InvalidFormat)
+}
+
+/// 非流式请求，返回完整回复（已去除首尾空白）
+pub fn chat(
+    client: &reqwest::blocking::Client,
+    config: &ChatConfig,
+    messages: &[ChatMessage],
+    temperature: f32,
+) -> Result<String, ChatError> {
+    let text = send(client, config, messages, temperature, false)?
+        .text()
+        .map_err(|e| ChatError::Read(e.to_string()))?;
+    let val: Value = serde_json::from_str(&text).map_err(|e| ChatError::Parse(e.to_string()))?;
+    message_content(&val)
+}
+
+/// 流式请求：每收到一段增量调用 `on_delta`，返回完整回复（已去除首尾空白）。
+/// `cancel` 置位后在下一段数据到达时中止并返回 [`ChatError::Cancelled`]。
+pub fn chat_stream(
+    client: &reqwest::blocking::Client,
+    config: &ChatConfig,
+    messages: &[ChatMessage],
+    temperature: f32,
+    cancel: &AtomicBool,
+    on_delta: &mut dyn FnMut(&str),
+) -> Result<String, ChatError> {
+    let resp = send(client, config, messages, temperature, true)?;
+    let is_event_stream = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+    if !is_event_stream {
+        let mut text = String::new();
+        BufReader::new(resp)
+            .read_to_string(&mut text)
+            .map_err(|e| ChatError::Read(e.to_string()))?;
+        let val: Value =
+            serde_json::from_str(&text).map_err(|e| ChatError::Parse(e.to_string()))?;
+        let content = message_content(&val)?;
+        on_delta(&content);
+        return Ok(content);
+    }
+    read_event_stream(BufReader::new(resp), cancel, on_delta).map(|s| s.trim().to_string())
+}
+
+/// 解析 SSE 数据行，拼接 `choices[0].delta.content`
+fn read_event_stream(
+    reader: impl BufRead,
+    cancel: &AtomicBool,
+    on_delta: &mut dyn FnMut(&str),
+) -> Result<String, ChatError> {
+    let mut output = String::new();
+    for line in reader.lines() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(ChatError::Cancelled);
+        }
+        let line = line.map_err(|e| ChatError::Read(e.to_string()))?;
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            continue;
+        };
+        if data == "[DONE]" {
+            break;
+        }
+        let val: Value = serde_json::from_str(data).map_err(|e| ChatError::Parse(e.to_string()))?;
+        if let Some(message) = val["error"]["message"].as_str() {
+            return Err(ChatError::Provider {
+                status: "stream".to_string(),
+                body: message.to_string(),
+            });
+        }
+        if let Some(delta) = val["choices"][0]["delta"]["content"].as_str()
+            && !delta.is_empty()
+        {
+            output.push_str(delta);
+            on_delta(delta);
+        }
+    }
+    if cancel.load(Ordering::Relaxed) {
+        return Err(ChatError::Cancelled);
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// 本地 OpenAI 兼容替身：接受一次请求，返回给定响应并交回请求原文
+    fn stand_in(
+        status: &str,
+        content_type: &str,
+        body: String,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/v1", listener.local_addr().unwrap());
+        let status = status.to_string();
+        let content_type = content_type.to_string();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = v.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            head + &String::from_utf8(request_body).unwrap()
+        });
+        (endpoint, handle)
+    }
+
+    fn client() -> reqwest::blocking::Client {
+        reqwest::blocking::Client::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+    }
+
+    fn config(endpoint: String, api_key: &str) -> ChatConfig {
+        ChatConfig {
+            endpoint,
+            api_key: api_key.to_string(),
+            model: "local-model".to_string(),
+        }
+    }
+
+    #[test]
+    fn config_defaults() {
+        let config = ChatConfig::default();
+        assert_eq!(
+            config.completions_url(),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert_eq!(config.model(), DEFAULT_MODEL);
+        let config = ChatConfig {
+            endpoint: "http://localhost:11434/v1/".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.completions_url(),
+            "http://localhost:11434/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn chat_returns_message_content() {
+        let (endpoint, server) = stand_in(
+            "200 OK",
+            "application/json",
+            r#"{"choices":[{"message":{"role":"assistant","content":"  hi there \n"}}]}"#.into(),
+        );
+        let messages = [ChatMessage::system("be brief"), ChatMessage::user("hello")];
+        let reply = chat(&client(), &config(endpoint, "sk-test"), &messages, 0.3).unwrap();
+        assert_eq!(reply, "hi there");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions"));
+        assert!(request.contains("authorization: Bearer sk-test"));
+        let body: Value = serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["model"], "local-model");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["messages"][1]["content"], "hello");
+    }
+
+    #[test]
+    fn chat_stream_collects_deltas() {
+        let sse = [
+            r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#,
+            "",
+            r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#,
+            "",
+            ": keep-alive",
+            r#"data: {"choices":[{"delta":{"content":"lo"}}]}"#,
+            "",
+            "data: [DONE]",
+            "",
+        ]
+        .join("\n");
+        let (endpoint, server) = stand_in("200 OK", "text/event-stream", sse);
+        let mut deltas = Vec::new();
+        let reply = chat_stream(
+            &client(),
+            &config(endpoint, ""),
+            &[ChatMessage::user("hi")],
+            0.7,
+            &AtomicBool::new(false),
+            &mut |d| deltas.push(d.to_string()),
+        )
+        .unwrap();
+        assert_eq!(reply, "Hello");
+        assert_eq!(deltas, ["Hel", "lo"]);
+
+        let request = server.join().unwrap();
+        assert!(!request.to_ascii_lowercase().contains("authorization:"));
+        assert!(request.contains(r#""stream":true"#));
+    }
+
+    #[test]
+    fn chat_stream_accepts_non_streaming_reply() {
+        let (endpoint, server) = stand_in(
+            "200 OK",
+            "application/json",
+            r#"{"choices":[{"message":{"content":"whole"}}]}"#.into(),
+        );
+        let mut deltas = Vec::new();
+        let reply = chat_stream(
+            &client(),
+            &config(endpoint, ""),
+            &[ChatMessage::user("hi")],
+            0.7,
+            &AtomicBool::new(false),
+            &mut |d| deltas.push(d.to_string()),
+        )
+        .unwrap();
+        assert_eq!(reply, "whole");
+        assert_eq!(deltas, ["whole"]);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn provider_error_keeps_status_and_body() {
+        let (endpoint, server) = stand_in(
+            "401 Unauthorized",
+            "application/json",
+            r#"{"error":{"message":"bad key"}}"#.into(),
+        );
+        let err = chat(
+            &client(),
+            &config(endpoint, "x"),
+            &[ChatMessage::user("hi")],
+            0.3,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.code("AI"),
+            r#"AI:PROVIDER_ERROR:openai:401 Unauthorized:{"error":{"message":"bad key"}}"#
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn event_stream_cancel_and_errors() {
+        let cancel = AtomicBool::new(true);
+        let input = "data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\n";
+        assert!(matches!(
+            read_event_stream(input.as_bytes(), &cancel, &mut |_| {}),
+            Err(ChatError::Cancelled)
+        ));
+
+        let cancel = AtomicBool::new(false);
+        let input = "data: {\"error\":{\"message\":\"overloaded\"}}\n";
+        let err = read_event_stream(input.as_bytes(), &cancel, &mut |_| {}).unwrap_err();
+        assert_eq!(err.code("AI"), "AI:PROVIDER_ERROR:openai:stream:overloaded");
+
+        let input = "data: not json\n";
+        assert!(matches!(
+            read_event_stream(input.as_bytes(), &cancel, &mut |_| {}),
+            Err(ChatError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+//! 极简 HTTP/1.1 解析与响应（仅服务本机脚本调用，每个连接处理一个请求）
+
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// 请求行 + 头部总长度上限
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+/// 请求体上限
+pub(super) const MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+#[derive(Debug)]
+pub(super) struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// 头部名不区分大小写
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 解析失败时直接返回给客户端的状态码与说明
+#[derive(Debug)]
+pub(super) struct HttpError {
+    pub status: u16,
+    pub message: &'static str,
+}
+
+impl HttpError {
+    fn new(status: u16, message: &'static str) -> Self {
+        Self { status, message }
+    }
+}
+
+pub(super) fn read_request<R: Read>(stream: R) -> Result<Request, HttpError> {
+    let mut reader = BufReader::new(stream);
+    let mut head_bytes = 0usize;
+    let mut next_line = |reader: &mut BufReader<R>| -> Result<String, HttpError> {
+        let mut line = String::new();
+        let n = reader
+            .by_ref()
+            .take((MAX_HEAD_BYTES - head_bytes) as u64 + 1)
+            .read_line(&mut line)
+            .map_err(|_| HttpError::new(400, "malformed request"))?;
+        head_bytes += n;
+        if head_bytes > MAX_HEAD_BYTES {
+            return Err(HttpError::new(431, "request header too large"));
+        }
+        if n == 0 {
+            return Err(HttpError::new(400, "unexpected end of request"));
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    };
+
+    let request_line = next_line(&mut reader)?;
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(HttpError::new(400, "malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(HttpError::new(505, "unsupported HTTP version"));
+    }
+
+    let mut headers = Vec::new();
+    loop {
+        let line = next_line(&mut reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(HttpError::new(400, "malformed header"));
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, parse_query(query)),
+        None => (target, Vec::new()),
+    };
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        headers,
+        body: Vec::new(),
+    };
+
+    if request.header("Transfer-Encoding").is_some() {
+        return Err(HttpError::new(501, "chunked request body is not supported"));
+    }
+    if let Some(length) = request.header("Content-Length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| HttpError::new(400, "invalid Content-Length"))?;
+        if length > MAX_BODY_BYTES {
+            return Err(HttpError::new(413, "request body too large"));
+        }
+        request.body = vec![0; length];
+        reader
+            .read_exact(&mut request.body)
+            .map_err(|_| HttpError::new(400, "incomplete request body"))?;
+    }
+    Ok(request)
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let decode = |s: &str| {
+                urlencoding::decode(&s.replace('+', " "))
+                    .map(|s| s.into_owned())
+                    .unwrap_or_else(|_| s.to_string())
+            };
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        431 => "Request Header Fields Too Large",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        505 => "HTTP Version Not Supported",
+        _ => "Internal Server Error",
+    }
+}
+
+pub(super) fn write_response<W: Write>(
+    stream: &mut W,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        reason_phrase(status),
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// 以 `{ "error": "..." }` 形式返回错误
+pub(super) fn write_error<W: Write>(
+    stream: &mut W,
+    status: u16,
+    message: &str,
+) -> std::io::Result<()> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    write_response(stream, status, "application/json", body.as_bytes())
+}
+
+/// SSE 响应头（之后连接保持打开，持续写入事件）
+pub(super) fn write_event_stream_head<W: Write>(stream: &mut W) -> std::io::Result<()> {
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    stream.flush()
+}
+
+/// 写入一条 SSE 事件（`data` 为单行 JSON）
+pub(super) fn write_event<W: Write>(
+    stream: &mut W,
+    event: &str,
+    data: &str,
+) -> std::io::Result<()> {
+    write!(stream, "event: {event}\ndata: {data}\n\n")?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_with_query_and_body() {
+        let raw = b"POST /v1/rpc?token=a%20b&x HTTP/1.1\r\nHost: localhost\r\nContent-Length: 4\r\n\r\nbodyEXTRA";
+        let request = read_request(&raw[..]).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/rpc");
+        assert_eq!(request.query_param("token"), Some("a b"));
+        assert_eq!(request.query_param("x"), Some(""));
+        assert_eq!(request.header("content-length"), Some("4"));
+        assert_eq!(request.body, b"body");
+    }
+
+    #[test]
+    fn rejects_oversized_and_malformed_requests() {
+        let raw = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(read_request(raw.as_bytes()).unwrap_err().status, 413);
+
+        let raw = format!(
+            "GET / HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_HEAD_BYTES)
+        );
+        assert_eq!(read_request(raw.as_bytes()).unwrap_err().status, 431);
+
+        assert_eq!(
+            read_request(&b"GARBAGE\r\n\r\n"[..]).unwrap_err().status,
+            400
+        );
+    }
+}
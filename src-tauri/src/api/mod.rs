@@ -0,0 +1,720 @@
+//! 本地脚本 API（可选开启）
+//!
+//! 监听 `127.0.0.1:<port>` 或 Unix socket，供启动器、编辑器插件等本机工具读写剪贴板历史：
+//!
+//! - `GET  /v1/health`：存活检查（无需令牌）
+//! - `GET  /v1/schema`：请求 / 响应的 JSON Schema
+//! - `POST /v1/rpc`：JSON-RPC 2.0，方法见 [`rpc::METHODS`]
+//! - `GET  /v1/events`：Server-Sent Events，推送 `item.added` / `item.updated` /
+//!   `item.deleted` / `group.created`
+//!
+//! 除 health 外均需 `Authorization: Bearer <token>`（事件流也可用 `?token=`，
+//! 方便无法自定义请求头的 EventSource 客户端）。令牌随安装生成，可在设置中重置。
+
+mod http;
+mod rpc;
+
+use crate::database::{ClipboardItem, ClipboardRepository, Database, Group};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// API 版本（路径前缀 `/v1`）；不兼容变更时递增
+pub const API_VERSION: u32 = 1;
+
+/// 请求 / 响应 JSON Schema
+const SCHEMA: &str = include_str!("schema.v1.json");
+
+/// 监听线程轮询间隔（非阻塞 accept，便于及时停止）
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 普通请求的读写超时
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+/// 事件流空闲多久发送一次保活注释
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// 事件流检查停止标志的间隔
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 同时处理的连接上限（含事件流），超出返回 503
+const MAX_CONNECTIONS: usize = 32;
+
+/// 由宿主实现的操作（需要访问剪贴板 / 窗口，测试中可替换）
+pub trait ApiHost: Send + Sync {
+    /// 按捕获流程新增条目（去重、规则、钩子均生效）并通知事件流，返回条目 ID（None = 已存在或被丢弃）
+    fn add(&self, content: AddContent) -> Result<Option<i64>, String>;
+    /// 将条目粘贴到当前活动窗口
+    fn paste(&self, item: &ClipboardItem, plain: bool) -> Result<(), String>;
+    /// RPC 修改了条目 / 分组后调用：通知界面刷新并推送到事件流
+    fn notify(&self, event: ApiEvent);
+}
+
+/// 数据变更事件（事件流的 `event:` 名见 [`ApiEvent::name`]）
+#[derive(Debug, Clone)]
+pub enum ApiEvent {
+    ItemAdded(i64),
+    /// 置顶、移动分组等
+    ItemUpdated(i64),
+    ItemDeleted(i64),
+    GroupCreated(Group),
+}
+
+impl ApiEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ItemAdded(_) => "item.added",
+            Self::ItemUpdated(_) => "item.updated",
+            Self::ItemDeleted(_) => "item.deleted",
+            Self::GroupCreated(_) => "group.created",
+        }
+    }
+}
+
+/// `items.add` 参数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddContent {
+    #[serde(default)]
+    pub text: String,
+    /// 可选 HTML 版本（`text` 作为纯文本回退）
+    #[serde(default)]
+    pub html: Option<String>,
+    /// 目标分组（None = 默认分组）
+    #[serde(default)]
+    pub group_id: Option<i64>,
+}
+
+pub struct ApiContext {
+    pub db: Database,
+    pub token: String,
+    pub host: Arc<dyn ApiHost>,
+}
+
+/// 监听地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bind {
+    /// 仅监听回环地址；端口 0 = 由系统分配
+    Tcp(u16),
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+/// 运行中的 API 服务；drop 时停止
+pub struct ApiServer {
+    running: Arc<AtomicBool>,
+    endpoint: String,
+    #[cfg(unix)]
+    socket_path: Option<std::path::PathBuf>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApiServer {
+    pub fn start(bind: Bind, ctx: ApiContext) -> std::io::Result<Self> {
+        let (listener, endpoint) = match &bind {
+            Bind::Tcp(port) => {
+                let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, *port))?;
+                listener.set_nonblocking(true)?;
+                let endpoint = format!("http://{}", listener.local_addr()?);
+                (Listener::Tcp(listener), endpoint)
+            }
+            #[cfg(unix)]
+            Bind::Unix(path) => {
+                use std::os::unix::fs::PermissionsExt;
+                // 清理上次异常退出残留的 socket 文件
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                let listener = std::os::unix::net::UnixListener::bind(path)?;
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+                listener.set_nonblocking(true)?;
+                (Listener::Unix(listener), format!("unix:{}", path.display()))
+            }
+        };
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread = {
+            let running = running.clone();
+            let ctx = Arc::new(ctx);
+            std::thread::Builder::new()
+                .name("api-server".into())
+                .spawn(move || accept_loop(listener, ctx, running))?
+        };
+        info!("Local API listening on {}", endpoint);
+
+        Ok(Self {
+            running,
+            endpoint,
+            #[cfg(unix)]
+            socket_path: match bind {
+                Bind::Unix(path) => Some(path),
+                Bind::Tcp(_) => None,
+            },
+            thread: Some(thread),
+        })
+    }
+
+    /// 监听地址（`http://127.0.0.1:port` 或 `unix:/path`）
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        #[cfg(unix)]
+        if let Some(path) = &self.socket_path {
+            let _ = std::fs::remove_file(path);
+        }
+        info!("Local API stopped ({})", self.endpoint);
+    }
+}
+
+/// 事件流订阅者
+static SUBSCRIBERS: Mutex<Vec<mpsc::Sender<ApiEvent>>> = Mutex::new(Vec::new());
+
+/// 推送事件给所有事件流订阅者
+pub fn publish(event: ApiEvent) {
+    let mut subscribers = SUBSCRIBERS.lock();
+    if !subscribers.is_empty() {
+        subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+/// 通知事件流订阅者有新条目入库（捕获 worker 与 `save_generated_item`，含 `items.add`）
+pub fn publish_item_added(id: i64) {
+    publish(ApiEvent::ItemAdded(id));
+}
+
+/// 生成新的访问令牌
+pub fn generate_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener),
+}
+
+enum Connection {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixStream),
+}
+
+impl Connection {
+    /// 监听器为非阻塞模式，accept 出的连接需切回阻塞并设置超时
+    fn prepare(&self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(s) => {
+                s.set_nonblocking(false)?;
+                s.set_read_timeout(Some(IO_TIMEOUT))?;
+                s.set_write_timeout(Some(IO_TIMEOUT))
+            }
+            #[cfg(unix)]
+            Self::Unix(s) => {
+                s.set_nonblocking(false)?;
+                s.set_read_timeout(Some(IO_TIMEOUT))?;
+                s.set_write_timeout(Some(IO_TIMEOUT))
+            }
+        }
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.read(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.write(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(s) => s.flush(),
+            #[cfg(unix)]
+            Self::Unix(s) => s.flush(),
+        }
+    }
+}
+
+fn accept_loop(listener: Listener, ctx: Arc<ApiContext>, running: Arc<AtomicBool>) {
+    let active = Arc::new(AtomicUsize::new(0));
+    while running.load(Ordering::SeqCst) {
+        let accepted = match &listener {
+            Listener::Tcp(l) => l.accept().map(|(s, _)| Connection::Tcp(s)),
+            #[cfg(unix)]
+            Listener::Unix(l) => l.accept().map(|(s, _)| Connection::Unix(s)),
+        };
+        let mut conn = match accepted {
+            Ok(conn) => conn,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                warn!("Local API accept failed: {}", e);
+                std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            }
+        };
+        if let Err(e) = conn.prepare() {
+            debug!("Local API connection setup failed: {}", e);
+            continue;
+        }
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            let _ = http::write_error(&mut conn, 503, "too many connections");
+            continue;
+        }
+
+        let ctx = ctx.clone();
+        let running = running.clone();
+        let guard_active = active.clone();
+        let spawned = std::thread::Builder::new()
+            .name("api-conn".into())
+            .spawn(move || {
+                handle_connection(conn, &ctx, &running);
+                guard_active.fetch_sub(1, Ordering::SeqCst);
+            });
+        if let Err(e) = spawned {
+            active.fetch_sub(1, Ordering::SeqCst);
+            warn!("Failed to spawn local API connection thread: {}", e);
+        }
+    }
+}
+
+fn handle_connection(mut conn: Connection, ctx: &ApiContext, running: &AtomicBool) {
+    let request = match http::read_request(&mut conn) {
+        Ok(request) => request,
+        Err(e) => {
+            let _ = http::write_error(&mut conn, e.status, e.message);
+            return;
+        }
+    };
+    debug!("Local API {} {}", request.method, request.path);
+
+    let result = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/v1/health") => {
+            let body = serde_json::json!({ "status": "ok", "version": API_VERSION });
+            http::write_response(
+                &mut conn,
+                200,
+                "application/json",
+                body.to_string().as_bytes(),
+            )
+        }
+        (_, "/v1/health" | "/v1/schema" | "/v1/rpc" | "/v1/events")
+            if !is_authorized(&request, &ctx.token) =>
+        {
+            http::write_error(&mut conn, 401, "missing or invalid token")
+        }
+        ("GET", "/v1/schema") => {
+            http::write_response(&mut conn, 200, "application/schema+json", SCHEMA.as_bytes())
+        }
+        ("POST", "/v1/rpc") => match rpc::handle(ctx, &request.body) {
+            Some(response) => http::write_response(
+                &mut conn,
+                200,
+                "application/json",
+                response.to_string().as_bytes(),
+            ),
+            None => http::write_response(&mut conn, 204, "application/json", b""),
+        },
+        ("GET", "/v1/events") => stream_events(&mut conn, ctx, running),
+        (_, "/v1/health" | "/v1/schema" | "/v1/rpc" | "/v1/events") => {
+            http::write_error(&mut conn, 405, "method not allowed")
+        }
+        _ => http::write_error(&mut conn, 404, "not found"),
+    };
+    if let Err(e) = result {
+        debug!("Local API write failed: {}", e);
+    }
+}
+
+fn is_authorized(request: &http::Request, token: &str) -> bool {
+    let bearer = request
+        .header("Authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    let provided = match bearer {
+        Some(provided) => Some(provided),
+        // EventSource 无法设置请求头，事件流允许通过查询参数传递
+        None if request.path == "/v1/events" => request.query_param("token"),
+        None => None,
+    };
+    provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 事件流：先发 `ready`，之后每个变更发一条事件。新增 / 更新的数据为完整条目 JSON，
+/// 删除为 `{"id": ...}`，新分组为分组 JSON
+fn stream_events(
+    conn: &mut Connection,
+    ctx: &ApiContext,
+    running: &AtomicBool,
+) -> std::io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    SUBSCRIBERS.lock().push(tx);
+
+    http::write_event_stream_head(conn)?;
+    let ready = serde_json::json!({ "version": API_VERSION });
+    http::write_event(conn, "ready", &ready.to_string())?;
+
+    let repo = ClipboardRepository::new(&ctx.db);
+    let mut idle = Duration::ZERO;
+    while running.load(Ordering::SeqCst) {
+        match rx.recv_timeout(EVENT_POLL_INTERVAL) {
+            Ok(event) => {
+                idle = Duration::ZERO;
+                let data = match &event {
+                    ApiEvent::ItemAdded(id) | ApiEvent::ItemUpdated(id) => {
+                        // 条目可能已被规则 / 清理删除，此时跳过
+                        let Ok(Some(item)) = repo.get_by_id(*id) else {
+                            continue;
+                        };
+                        serde_json::to_string(&item)
+                    }
+                    ApiEvent::ItemDeleted(id) => Ok(serde_json::json!({ "id": id }).to_string()),
+                    ApiEvent::GroupCreated(group) => serde_json::to_string(group),
+                };
+                http::write_event(conn, event.name(), &data.unwrap_or_default())?;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                idle += EVENT_POLL_INTERVAL;
+                if idle >= KEEPALIVE_INTERVAL {
+                    idle = Duration::ZERO;
+                    conn.write_all(b": keepalive\n\n")?;
+                    conn.flush()?;
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ContentType, NewClipboardItem};
+    use serde_json::{Value, json};
+    use std::io::{BufRead, BufReader};
+
+    const TOKEN: &str = "test-token";
+
+    /// 测试宿主：直接写库，记录粘贴调用
+    #[derive(Default)]
+    struct TestHost {
+        db: Option<Database>,
+        pasted: Mutex<Vec<(i64, bool)>>,
+    }
+
+    impl ApiHost for TestHost {
+        fn add(&self, content: AddContent) -> Result<Option<i64>, String> {
+            let repo = ClipboardRepository::new(self.db.as_ref().unwrap());
+            let hash = blake3::hash(content.text.as_bytes()).to_hex().to_string();
+            if repo
+                .exists_by_hash(&hash, content.group_id)
+                .map_err(|e| e.to_string())?
+            {
+                return Ok(None);
+            }
+            let id = repo
+                .insert(NewClipboardItem {
+                    content_type: ContentType::Text,
+                    preview: Some(content.text.clone()),
+                    text_content: Some(content.text),
+                    content_hash: hash.clone(),
+                    semantic_hash: hash,
+                    group_id: content.group_id,
+                    ..Default::default()
+                })
+                .map_err(|e| e.to_string())?;
+            publish_item_added(id);
+            Ok(Some(id))
+        }
+
+        fn paste(&self, item: &ClipboardItem, plain: bool) -> Result<(), String> {
+            self.pasted.lock().push((item.id, plain));
+            Ok(())
+        }
+
+        fn notify(&self, event: ApiEvent) {
+            publish(event);
+        }
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!("ec_api_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(format!(
+            "{name}_{}",
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn start_server(bind: Bind) -> (ApiServer, Arc<TestHost>) {
+        let db = Database::new(temp_path("api.db")).unwrap();
+        let host = Arc::new(TestHost {
+            db: Some(db.clone()),
+            ..Default::default()
+        });
+        let ctx = ApiContext {
+            db,
+            token: TOKEN.to_string(),
+            host: host.clone(),
+        };
+        (ApiServer::start(bind, ctx).unwrap(), host)
+    }
+
+    fn connect(server: &ApiServer) -> TcpStream {
+        let addr = server.endpoint().trim_start_matches("http://");
+        let stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream
+    }
+
+    /// 发送请求并读取完整响应，返回 (状态码, body)
+    fn send<S: Read + Write>(
+        mut stream: S,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> (u16, String) {
+        let auth = token
+            .map(|t| format!("Authorization: Bearer {t}\r\n"))
+            .unwrap_or_default();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\n{auth}Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, b)| b.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    fn call(server: &ApiServer, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let (status, body) = send(
+            connect(server),
+            "POST",
+            "/v1/rpc",
+            Some(TOKEN),
+            &request.to_string(),
+        );
+        assert_eq!(status, 200, "{body}");
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn requires_token_except_health() {
+        let (server, _) = start_server(Bind::Tcp(0));
+        let (status, body) = send(connect(&server), "GET", "/v1/health", None, "");
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["version"], 1);
+
+        let (status, _) = send(connect(&server), "POST", "/v1/rpc", None, "{}");
+        assert_eq!(status, 401);
+        let (status, _) = send(connect(&server), "POST", "/v1/rpc", Some("wrong"), "{}");
+        assert_eq!(status, 401);
+        let (status, _) = send(connect(&server), "GET", "/v1/schema", Some(TOKEN), "");
+        assert_eq!(status, 200);
+        let (status, _) = send(connect(&server), "GET", "/v1/rpc", Some(TOKEN), "");
+        assert_eq!(status, 405);
+        let (status, _) = send(connect(&server), "GET", "/v2/rpc", Some(TOKEN), "");
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn schema_is_valid_json_and_lists_all_methods() {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let methods = &schema["$defs"]["methods"]["properties"];
+        for method in rpc::METHODS {
+            assert!(methods.get(*method).is_some(), "schema missing {method}");
+        }
+    }
+
+    #[test]
+    fn item_operations_round_trip() {
+        let (server, host) = start_server(Bind::Tcp(0));
+
+        let added = call(&server, "items.add", json!({ "text": "hello api" }));
+        let id = added["result"]["id"].as_i64().unwrap();
+        // 重复内容按去重处理，返回 null
+        let again = call(&server, "items.add", json!({ "text": "hello api" }));
+        assert!(again["result"]["id"].is_null());
+        call(&server, "items.add", json!({ "text": "other" }));
+
+        let listed = call(&server, "items.list", json!({ "limit": 10 }));
+        assert_eq!(listed["result"].as_array().unwrap().len(), 2);
+        let found = call(&server, "items.search", json!({ "query": "hello" }));
+        assert_eq!(found["result"][0]["id"], id);
+        let item = call(&server, "items.get", json!({ "id": id }));
+        assert_eq!(item["result"]["text_content"], "hello api");
+
+        let pinned = call(&server, "items.pin", json!({ "id": id, "pinned": true }));
+        assert_eq!(pinned["result"]["pinned"], true);
+        let still = call(&server, "items.pin", json!({ "id": id, "pinned": true }));
+        assert_eq!(still["result"]["pinned"], true);
+        let toggled = call(&server, "items.pin", json!({ "id": id }));
+        assert_eq!(toggled["result"]["pinned"], false);
+
+        let group = call(&server, "groups.create", json!({ "name": "Work" }));
+        let group_id = group["result"]["id"].as_i64().unwrap();
+        call(
+            &server,
+            "items.move",
+            json!({ "id": id, "group_id": group_id }),
+        );
+        let groups = call(&server, "groups.list", Value::Null);
+        assert_eq!(groups["result"][0]["item_count"], 1);
+        let in_group = call(&server, "items.list", json!({ "group_id": group_id }));
+        assert_eq!(in_group["result"][0]["id"], id);
+
+        call(&server, "items.paste", json!({ "id": id, "plain": true }));
+        assert_eq!(*host.pasted.lock(), vec![(id, true)]);
+
+        call(&server, "items.delete", json!({ "id": id }));
+        let missing = call(&server, "items.get", json!({ "id": id }));
+        assert_eq!(missing["error"]["code"], -32001);
+    }
+
+    #[test]
+    fn reports_json_rpc_errors() {
+        let (server, _) = start_server(Bind::Tcp(0));
+        let unknown = call(&server, "items.explode", Value::Null);
+        assert_eq!(unknown["error"]["code"], -32601);
+        let bad_params = call(&server, "items.get", json!({ "id": "x" }));
+        assert_eq!(bad_params["error"]["code"], -32602);
+        let empty_search = call(&server, "items.search", json!({ "query": " " }));
+        assert_eq!(empty_search["error"]["code"], -32602);
+        let bad_group = call(&server, "items.add", json!({ "text": "a", "group_id": 99 }));
+        assert_eq!(bad_group["error"]["code"], -32001);
+
+        let (_, body) = send(
+            connect(&server),
+            "POST",
+            "/v1/rpc",
+            Some(TOKEN),
+            "{not json",
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap()["error"]["code"],
+            -32700
+        );
+
+        // 批量请求：通知（无 id）不产生响应
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "api.version" },
+            { "jsonrpc": "2.0", "method": "api.version" },
+        ]);
+        let (_, body) = send(
+            connect(&server),
+            "POST",
+            "/v1/rpc",
+            Some(TOKEN),
+            &batch.to_string(),
+        );
+        let responses: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(responses.as_array().unwrap().len(), 1);
+        assert_eq!(responses[0]["result"]["version"], API_VERSION);
+    }
+
+    #[test]
+    fn event_stream_pushes_new_items() {
+        let (server, _) = start_server(Bind::Tcp(0));
+        let mut stream = connect(&server);
+        write!(
+            stream,
+            "GET /v1/events?token={TOKEN} HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut next_event = || {
+            let (mut event, mut data) = (String::new(), String::new());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if let Some(name) = line.strip_prefix("event: ") {
+                    event = name.to_string();
+                } else if let Some(payload) = line.strip_prefix("data: ") {
+                    data = payload.to_string();
+                } else if line.is_empty() && !event.is_empty() {
+                    return (event, data);
+                }
+            }
+        };
+        assert_eq!(next_event().0, "ready");
+
+        let added = call(&server, "items.add", json!({ "text": "streamed" }));
+        let id = added["result"]["id"].clone();
+        // 并行测试共用订阅列表，跳过其他测试的事件
+        let mut wait_for = |name: &str, matches: &dyn Fn(&Value) -> bool| loop {
+            let (event, data) = next_event();
+            let data: Value = serde_json::from_str(&data).unwrap();
+            if event == name && matches(&data) {
+                return data;
+            }
+        };
+        let item = wait_for("item.added", &|item| item["text_content"] == "streamed");
+        assert_eq!(item["id"], id);
+
+        call(&server, "items.pin", json!({ "id": id, "pinned": true }));
+        let item = wait_for("item.updated", &|item| item["id"] == id);
+        assert_eq!(item["is_pinned"], true);
+        let group = call(&server, "groups.create", json!({ "name": "Streamed" }));
+        wait_for("group.created", &|g| g["id"] == group["result"]["id"]);
+        call(&server, "items.delete", json!({ "id": id }));
+        wait_for("item.deleted", &|data| data["id"] == id);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn serves_over_unix_socket() {
+        let path = temp_path("api.sock");
+        let (server, _) = start_server(Bind::Unix(path.clone()));
+        assert_eq!(server.endpoint(), format!("unix:{}", path.display()));
+        let stream = std::os::unix::net::UnixStream::connect(&path).unwrap();
+        let request = json!({ "jsonrpc": "2.0", "id": 7, "method": "api.version" });
+        let (status, body) = send(stream, "POST", "/v1/rpc", Some(TOKEN), &request.to_string());
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["id"], 7);
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,337 @@
+//! JSON-RPC 2.0 方法分发（`POST /v1/rpc`，支持单个请求与批量数组）
+
+use super::{AddContent, ApiContext, ApiEvent};
+use crate::database::{
+    ClipboardItem, ClipboardRepository, GroupRepository, QueryOptions, TagRepository,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+/// 列表默认 / 最大返回条数
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+/// 所有方法名（`api.version` 返回，便于客户端探测能力）
+pub(super) const METHODS: &[&str] = &[
+    "api.version",
+    "items.list",
+    "items.search",
+    "items.get",
+    "items.add",
+    "items.paste",
+    "items.pin",
+    "items.delete",
+    "items.move",
+    "groups.list",
+    "groups.create",
+];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// 应用内部错误（数据库、剪贴板访问等）
+const SERVER_ERROR: i64 = -32000;
+/// 条目 / 分组不存在
+const NOT_FOUND: i64 = -32001;
+
+#[derive(Debug)]
+pub(super) struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(what: &str, id: i64) -> Self {
+        Self::new(NOT_FOUND, format!("{what} not found: {id}"))
+    }
+}
+
+impl From<rusqlite::Error> for RpcError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::new(SERVER_ERROR, e.to_string())
+    }
+}
+
+type RpcResult = Result<Value, RpcError>;
+
+/// 处理请求体，返回响应 JSON；全部为通知（无 id）时返回 None
+pub(super) fn handle(ctx: &ApiContext, body: &[u8]) -> Option<Value> {
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(value) => value,
+        Err(e) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, e.to_string()),
+            ));
+        }
+    };
+    match request {
+        Value::Array(batch) if batch.is_empty() => Some(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "empty batch"),
+        )),
+        Value::Array(batch) => {
+            let responses: Vec<Value> = batch
+                .into_iter()
+                .filter_map(|request| handle_single(ctx, request))
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        request => handle_single(ctx, request),
+    }
+}
+
+fn handle_single(ctx: &ApiContext, request: Value) -> Option<Value> {
+    #[derive(Deserialize)]
+    struct Envelope {
+        jsonrpc: String,
+        method: String,
+        #[serde(default)]
+        params: Value,
+        id: Option<Value>,
+    }
+
+    let id_hint = request.get("id").cloned().unwrap_or(Value::Null);
+    let envelope: Envelope = match serde_json::from_value(request) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            return Some(error_response(
+                id_hint,
+                RpcError::new(INVALID_REQUEST, e.to_string()),
+            ));
+        }
+    };
+    if envelope.jsonrpc != "2.0" {
+        return Some(error_response(
+            id_hint,
+            RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+
+    let result = dispatch(ctx, &envelope.method, envelope.params);
+    // 通知（无 id）不返回响应
+    let id = envelope.id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // 省略 params 等同于空对象
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn dispatch(ctx: &ApiContext, method: &str, params: Value) -> RpcResult {
+    match method {
+        "api.version" => Ok(json!({
+            "version": super::API_VERSION,
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "methods": METHODS,
+        })),
+        "items.list" => list_items(ctx, parse_params(params)?, false),
+        "items.search" => list_items(ctx, parse_params(params)?, true),
+        "items.get" => {
+            let IdParams { id } = parse_params(params)?;
+            Ok(serde_json::to_value(load_item(ctx, id)?).unwrap_or_default())
+        }
+        "items.add" => add_item(ctx, parse_params(params)?),
+        "items.paste" => {
+            let PasteParams { id, plain } = parse_params(params)?;
+            let item = load_item(ctx, id)?;
+            ctx.host
+                .paste(&item, plain)
+                .map_err(|e| RpcError::new(SERVER_ERROR, e))?;
+            Ok(Value::Null)
+        }
+        "items.pin" => {
+            let PinParams { id, pinned } = parse_params(params)?;
+            let repo = ClipboardRepository::new(&ctx.db);
+            let item = load_item(ctx, id)?;
+            let pinned = match pinned {
+                Some(pinned) if pinned == item.is_pinned => pinned,
+                _ => {
+                    let pinned = repo.toggle_pin(id)?;
+                    ctx.host.notify(ApiEvent::ItemUpdated(id));
+                    pinned
+                }
+            };
+            Ok(json!({ "pinned": pinned }))
+        }
+        "items.delete" => {
+            let IdParams { id } = parse_params(params)?;
+            let item = load_item(ctx, id)?;
+            ClipboardRepository::new(&ctx.db).delete(id)?;
+            crate::clipboard::cleanup_deleted_assets(
+                &item.image_path.into_iter().collect::<Vec<_>>(),
+                &item.file_payload.into_iter().collect::<Vec<_>>(),
+            );
+            ctx.host.notify(ApiEvent::ItemDeleted(id));
+            Ok(Value::Null)
+        }
+        "items.move" => {
+            let MoveParams { id, group_id } = parse_params(params)?;
+            load_item(ctx, id)?;
+            let groups = GroupRepository::new(&ctx.db);
+            if let Some(group_id) = group_id
+                && !groups.exists(group_id)?
+            {
+                return Err(RpcError::not_found("group", group_id));
+            }
+            groups.move_item_to_group(id, group_id)?;
+            ctx.host.notify(ApiEvent::ItemUpdated(id));
+            Ok(Value::Null)
+        }
+        "groups.list" => {
+            let groups = GroupRepository::new(&ctx.db).list_with_count()?;
+            Ok(serde_json::to_value(groups).unwrap_or_default())
+        }
+        "groups.create" => {
+            let CreateGroupParams { name, color } = parse_params(params)?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    "group name must not be empty",
+                ));
+            }
+            let group = GroupRepository::new(&ctx.db).create(name, color.as_deref())?;
+            let value = serde_json::to_value(&group).unwrap_or_default();
+            ctx.host.notify(ApiEvent::GroupCreated(group));
+            Ok(value)
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdParams {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListParams {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    content_type: Option<String>,
+    #[serde(default)]
+    pinned_only: bool,
+    #[serde(default)]
+    favorite_only: bool,
+    #[serde(default)]
+    group_id: Option<i64>,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default)]
+    tag_ids: Option<Vec<i64>>,
+    #[serde(default)]
+    limit: Option<i64>,
+    #[serde(default)]
+    offset: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PasteParams {
+    id: i64,
+    /// 以纯文本粘贴
+    #[serde(default)]
+    plain: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PinParams {
+    id: i64,
+    /// 目标状态；省略时切换
+    #[serde(default)]
+    pinned: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveParams {
+    id: i64,
+    /// None = 移回默认分组
+    group_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateGroupParams {
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+fn load_item(ctx: &ApiContext, id: i64) -> Result<ClipboardItem, RpcError> {
+    let item = ClipboardRepository::new(&ctx.db)
+        .get_by_id(id)?
+        .ok_or_else(|| RpcError::not_found("item", id))?;
+    let mut items = [item];
+    TagRepository::new(&ctx.db).attach_tags(&mut items)?;
+    let [item] = items;
+    Ok(item)
+}
+
+fn list_items(ctx: &ApiContext, params: ListParams, search: bool) -> RpcResult {
+    let query = params.query.filter(|q| !q.trim().is_empty());
+    if search && query.is_none() {
+        return Err(RpcError::new(INVALID_PARAMS, "query must not be empty"));
+    }
+    let options = QueryOptions {
+        search: query,
+        content_type: params.content_type,
+        pinned_only: params.pinned_only,
+        favorite_only: params.favorite_only,
+        group_id: params.group_id,
+        subtype: params.subtype,
+        tag_ids: params.tag_ids,
+        limit: Some(params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)),
+        offset: params.offset.map(|o| o.max(0)),
+    };
+    let mut items = ClipboardRepository::new(&ctx.db).list(options)?;
+    TagRepository::new(&ctx.db).attach_tags(&mut items)?;
+    Ok(serde_json::to_value(items).unwrap_or_default())
+}
+
+fn add_item(ctx: &ApiContext, content: AddContent) -> RpcResult {
+    if content.text.is_empty() && content.html.as_deref().is_none_or(str::is_empty) {
+        return Err(RpcError::new(INVALID_PARAMS, "text must not be empty"));
+    }
+    if let Some(group_id) = content.group_id
+        && !GroupRepository::new(&ctx.db).exists(group_id)?
+    {
+        return Err(RpcError::not_found("group", group_id));
+    }
+    let id = ctx
+        .host
+        .add(content)
+        .map_err(|e| RpcError::new(SERVER_ERROR, e))?;
+    // id 为 null 表示内容已存在（按去重策略处理）或被捕获钩子丢弃
+    Ok(json!({ "id": id }))
+}
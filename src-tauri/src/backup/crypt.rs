@@ -0,0 +1,274 @@
+//! 备份加密：PBKDF2-HMAC-SHA256 由密码派生密钥，AES-256-GCM 分块加密。
+//!
+//! 文件格式：`MAGIC | 迭代次数 (u32 LE) | salt (16) | nonce 前缀 (8)`，随后为若干块
+//! `末块标记 (u8) | 明文长度 (u32 LE) | 密文 + tag (16)`。nonce = 前缀 ‖ 块序号 (u32 BE)，
+//! 末块标记作为附加数据参与认证，截断、重排或篡改均会导致解密失败。
+
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+pub const MAGIC: &[u8; 8] = b"ECBAKv1\0";
+const ITERATIONS: u32 = 200_000;
+/// 解密时接受的迭代次数范围，防止损坏或伪造的头部导致弱密钥或长时间卡住
+const MIN_ITERATIONS: u32 = 10_000;
+const MAX_ITERATIONS: u32 = ITERATIONS * 10;
+const SALT_LEN: usize = 16;
+const PREFIX_LEN: usize = 8;
+const TAG_LEN: usize = 16;
+const CHUNK_SIZE: usize = 1024 * 1024;
+/// 解密时允许的最大块长度，防止损坏的长度字段导致过量分配
+const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+const WRONG_PASSWORD: &str = "密码错误或备份文件已损坏";
+
+/// 文件是否为加密备份
+pub fn is_encrypted(path: &Path) -> io::Result<bool> {
+    let mut magic = [0u8; MAGIC.len()];
+    let mut file = File::open(path)?;
+    Ok(read_full(&mut file, &mut magic)? == magic.len() && &magic == MAGIC)
+}
+
+/// 加密 `src` 写入 `dst`，每处理一块回调一次已处理的明文字节数
+pub fn encrypt_file(
+    src: &Path,
+    dst: &Path,
+    password: &str,
+    on_progress: impl FnMut(u64),
+) -> Result<(), String> {
+    let reader = BufReader::new(File::open(src).map_err(|e| e.to_string())?);
+    let mut writer = BufWriter::new(File::create(dst).map_err(|e| e.to_string())?);
+    encrypt(
+        reader,
+        &mut writer,
+        password,
+        ITERATIONS,
+        CHUNK_SIZE,
+        on_progress,
+    )?;
+    writer
+        .into_inner()
+        .map_err(|e| e.to_string())?
+        .sync_all()
+        .map_err(|e| e.to_string())
+}
+
+/// 解密 `src` 写入 `dst`，密码错误或内容被篡改时返回错误
+pub fn decrypt_file(
+    src: &Path,
+    dst: &Path,
+    password: &str,
+    on_progress: impl FnMut(u64),
+) -> Result<(), String> {
+    let reader = BufReader::new(File::open(src).map_err(|e| e.to_string())?);
+    let mut writer = BufWriter::new(File::create(dst).map_err(|e| e.to_string())?);
+    decrypt(reader, &mut writer, password, on_progress)?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey, String> {
+    let iterations = NonZeroU32::new(iterations).ok_or(WRONG_PASSWORD)?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| "初始化密钥失败")?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn nonce(prefix: &[u8; PREFIX_LEN], counter: u32) -> Nonce {
+    let mut bytes = [0u8; NONCE_LEN];
+    bytes[..PREFIX_LEN].copy_from_slice(prefix);
+    bytes[PREFIX_LEN..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::assume_unique_for_key(bytes)
+}
+
+/// 读满 `buf` 或直到 EOF，返回读取的字节数
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn encrypt(
+    mut reader: impl Read,
+    writer: &mut impl Write,
+    password: &str,
+    iterations: u32,
+    chunk_size: usize,
+    mut on_progress: impl FnMut(u64),
+) -> Result<(), String> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut prefix = [0u8; PREFIX_LEN];
+    rng.fill(&mut salt).map_err(|_| "生成随机数失败")?;
+    rng.fill(&mut prefix).map_err(|_| "生成随机数失败")?;
+    let key = derive_key(password, &salt, iterations)?;
+
+    let io_err = |e: io::Error| e.to_string();
+    writer.write_all(MAGIC).map_err(io_err)?;
+    writer
+        .write_all(&iterations.to_le_bytes())
+        .map_err(io_err)?;
+    writer.write_all(&salt).map_err(io_err)?;
+    writer.write_all(&prefix).map_err(io_err)?;
+
+    let mut buf = vec![0u8; chunk_size];
+    let mut done = 0u64;
+    for counter in 0u32.. {
+        let n = read_full(&mut reader, &mut buf).map_err(io_err)?;
+        let last = n < chunk_size;
+        let mut block = Vec::with_capacity(n + TAG_LEN);
+        block.extend_from_slice(&buf[..n]);
+        key.seal_in_place_append_tag(nonce(&prefix, counter), Aad::from([last as u8]), &mut block)
+            .map_err(|_| "加密失败")?;
+        writer.write_all(&[last as u8]).map_err(io_err)?;
+        writer
+            .write_all(&(n as u32).to_le_bytes())
+            .map_err(io_err)?;
+        writer.write_all(&block).map_err(io_err)?;
+        done += n as u64;
+        on_progress(done);
+        if last {
+            return Ok(());
+        }
+    }
+    Err("备份文件过大".into())
+}
+
+fn decrypt(
+    mut reader: impl Read,
+    writer: &mut impl Write,
+    password: &str,
+    mut on_progress: impl FnMut(u64),
+) -> Result<(), String> {
+    let io_err = |e: io::Error| e.to_string();
+    let mut header = [0u8; MAGIC.len() + 4 + SALT_LEN + PREFIX_LEN];
+    if read_full(&mut reader, &mut header).map_err(io_err)? != header.len()
+        || &header[..MAGIC.len()] != MAGIC
+    {
+        return Err("不是有效的加密备份".into());
+    }
+    let (_, rest) = header.split_at(MAGIC.len());
+    let (iterations, rest) = rest.split_at(4);
+    let (salt, prefix) = rest.split_at(SALT_LEN);
+    let iterations = u32::from_le_bytes(iterations.try_into().unwrap_or_default());
+    if !(MIN_ITERATIONS..=MAX_ITERATIONS).contains(&iterations) {
+        return Err("不是有效的加密备份".into());
+    }
+    let prefix: [u8; PREFIX_LEN] = prefix.try_into().map_err(|_| WRONG_PASSWORD)?;
+    let key = derive_key(password, salt, iterations)?;
+
+    let mut done = 0u64;
+    for counter in 0u32.. {
+        let mut chunk_header = [0u8; 5];
+        if read_full(&mut reader, &mut chunk_header).map_err(io_err)? != chunk_header.len() {
+            return Err("备份文件不完整".into());
+        }
+        let last = match chunk_header[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(WRONG_PASSWORD.into()),
+        };
+        let len = u32::from_le_bytes(chunk_header[1..].try_into().unwrap_or_default()) as usize;
+        if len > MAX_CHUNK_SIZE {
+            return Err(WRONG_PASSWORD.into());
+        }
+        let mut block = vec![0u8; len + TAG_LEN];
+        if read_full(&mut reader, &mut block).map_err(io_err)? != block.len() {
+            return Err("备份文件不完整".into());
+        }
+        let plain = key
+            .open_in_place(nonce(&prefix, counter), Aad::from([last as u8]), &mut block)
+            .map_err(|_| WRONG_PASSWORD)?;
+        writer.write_all(plain).map_err(io_err)?;
+        done += plain.len() as u64;
+        on_progress(done);
+        if last {
+            let mut trailing = [0u8; 1];
+            if read_full(&mut reader, &mut trailing).map_err(io_err)? != 0 {
+                return Err(WRONG_PASSWORD.into());
+            }
+            return Ok(());
+        }
+    }
+    Err(WRONG_PASSWORD.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encrypt_bytes(data: &[u8], password: &str, chunk_size: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        encrypt(data, &mut out, password, MIN_ITERATIONS, chunk_size, |_| {}).unwrap();
+        out
+    }
+
+    fn decrypt_bytes(data: &[u8], password: &str) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        decrypt(data, &mut out, password, |_| {})?;
+        Ok(out)
+    }
+
+    #[test]
+    fn round_trips_across_chunks() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        for chunk_size in [64, 1000, 10_000, 20_000] {
+            let encrypted = encrypt_bytes(&data, "secret", chunk_size);
+            assert_eq!(&encrypted[..MAGIC.len()], MAGIC);
+            assert_eq!(decrypt_bytes(&encrypted, "secret").unwrap(), data);
+        }
+        let empty = encrypt_bytes(&[], "secret", 64);
+        assert!(decrypt_bytes(&empty, "secret").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_wrong_password_tampering_and_truncation() {
+        let data = vec![7u8; 300];
+        let encrypted = encrypt_bytes(&data, "secret", 100);
+        assert!(decrypt_bytes(&encrypted, "wrong").is_err());
+
+        let mut tampered = encrypted.clone();
+        let last = tampered.len() - 20;
+        tampered[last] ^= 1;
+        assert!(decrypt_bytes(&tampered, "secret").is_err());
+
+        // 300 字节按 100 分块后末块为空块：去掉末块不能当作完整文件接受
+        let truncated = &encrypted[..encrypted.len() - (1 + 4 + TAG_LEN)];
+        assert!(decrypt_bytes(truncated, "secret").is_err());
+
+        let mut extended = encrypted.clone();
+        extended.push(0);
+        assert!(decrypt_bytes(&extended, "secret").is_err());
+        assert!(decrypt_bytes(b"PK\x03\x04", "secret").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_iterations() {
+        let encrypted = encrypt_bytes(b"data", "secret", 64);
+        for iterations in [0, MIN_ITERATIONS - 1, MAX_ITERATIONS + 1, u32::MAX] {
+            let mut forged = encrypted.clone();
+            forged[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&iterations.to_le_bytes());
+            assert_eq!(
+                decrypt_bytes(&forged, "secret").unwrap_err(),
+                "不是有效的加密备份"
+            );
+        }
+    }
+}
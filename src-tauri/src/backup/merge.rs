@@ -0,0 +1,537 @@
+//! 合并导入：只读打开备份中的数据库，把当前历史中没有的条目（content_hash 与 semantic_hash 均不存在）
+//! 追加到当前数据库，保留收藏、置顶、时间与标签。分组按名称对应（不存在时新建），
+//! 引用的媒体文件存入 blob 存储，无需重启。`dry_run` 只统计不写入。
+
+use super::{DB_ENTRY, MEDIA_DIRS, sanitize_zip_relative_path};
+use crate::blobs::{self, BlobKind, BlobStore, StoredBlob};
+use crate::clipboard::file_clipboard::{decode_payload, encode_payload};
+use crate::database::{ClipboardItem, ClipboardRepository, Database, GroupRepository};
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// 合并时发现的冲突（均保留当前数据）
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// `other_group`（条目已存在于其他分组）/ `state_differs`（收藏或置顶状态不同）/
+    /// `media_missing`（备份缺少图片，跳过该条目）/ `group_color`（同名分组颜色不同）
+    pub kind: &'static str,
+    /// 备份中的条目 ID（`group_color` 时为分组 ID）
+    pub backup_id: i64,
+    pub preview: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeReport {
+    pub dry_run: bool,
+    pub backup_items: usize,
+    /// 新增（dry run 时为将新增）的条目数
+    pub imported: usize,
+    /// 当前已存在而跳过的条目数
+    pub duplicates: usize,
+    /// 因媒体缺失跳过的条目数
+    pub skipped: usize,
+    pub groups_created: Vec<String>,
+    pub groups_matched: Vec<String>,
+    pub media_files: usize,
+    pub media_bytes: u64,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+struct BackupGroup {
+    id: i64,
+    name: String,
+    color: Option<String>,
+    parent_id: Option<i64>,
+}
+
+/// 合并 `backup_dir`（已解压并校验的备份）中的条目；`store` 为 None 时只统计（dry run）
+pub fn merge(
+    db: &Database,
+    store: Option<&BlobStore>,
+    backup_dir: &Path,
+) -> Result<MergeReport, String> {
+    let conn = Connection::open_with_flags(
+        backup_dir.join(DB_ENTRY),
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("打开备份数据库失败: {e}"))?;
+    let groups = read_groups(&conn).map_err(|e| format!("读取备份分组失败: {e}"))?;
+    let items = read_items(&conn).map_err(|e| format!("读取备份条目失败: {e}"))?;
+    drop(conn);
+
+    let mut report = MergeReport {
+        dry_run: store.is_none(),
+        backup_items: items.len(),
+        ..Default::default()
+    };
+    let group_map = map_groups(db, &groups, store.is_some(), &mut report)?;
+    let repo = ClipboardRepository::new(db);
+    let mut seen = HashSet::new();
+    let mut to_insert = Vec::new();
+
+    for mut item in items {
+        let existing = repo
+            .find_by_hashes(&item.content_hash, &item.semantic_hash)
+            .map_err(|e| e.to_string())?;
+        let target_group = item.group_id.and_then(|id| group_map.get(&id).copied());
+        if let Some(existing) = existing {
+            report.duplicates += 1;
+            // 目标分组在 dry run 中尚未创建时无法比较
+            let comparable = item.group_id.is_none() || target_group.is_some();
+            if comparable && existing.group_id != target_group {
+                report.conflicts.push(conflict("other_group", &item));
+            }
+            if existing.is_favorite != item.is_favorite || existing.is_pinned != item.is_pinned {
+                report.conflicts.push(conflict("state_differs", &item));
+            }
+            continue;
+        }
+        // 备份内部的重复条目（不同分组的相同内容）只导入第一条
+        if seen.contains(&item.content_hash) || seen.contains(&item.semantic_hash) {
+            report.duplicates += 1;
+            continue;
+        }
+        if !import_media(store, backup_dir, &mut item, &mut report) {
+            report.skipped += 1;
+            report.conflicts.push(conflict("media_missing", &item));
+            continue;
+        }
+        seen.insert(item.content_hash.clone());
+        seen.insert(item.semantic_hash.clone());
+        item.group_id = target_group;
+        item.derived_from = None;
+        to_insert.push(item);
+    }
+
+    report.imported = match store {
+        Some(_) => repo.merge_items(&to_insert).map_err(|e| e.to_string())?,
+        None => to_insert.len(),
+    };
+    if store.is_some() {
+        info!(
+            "Merged backup: {} imported, {} duplicates, {} skipped",
+            report.imported, report.duplicates, report.skipped
+        );
+    }
+    Ok(report)
+}
+
+fn conflict(kind: &'static str, item: &ClipboardItem) -> MergeConflict {
+    MergeConflict {
+        kind,
+        backup_id: item.id,
+        preview: item.preview.clone(),
+    }
+}
+
+/// 备份分组 ID → 当前分组 ID（dry run 时将新建的分组不在表中）
+fn map_groups(
+    db: &Database,
+    groups: &[BackupGroup],
+    create: bool,
+    report: &mut MergeReport,
+) -> Result<HashMap<i64, i64>, String> {
+    let group_repo = GroupRepository::new(db);
+    let current: HashMap<String, (i64, Option<String>)> = group_repo
+        .list_with_count()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|g| (g.name, (g.id, g.color)))
+        .collect();
+
+    let mut map = HashMap::new();
+    let mut created = Vec::new();
+    for group in groups {
+        if let Some((id, color)) = current.get(&group.name) {
+            map.insert(group.id, *id);
+            report.groups_matched.push(group.name.clone());
+            if *color != group.color {
+                report.conflicts.push(MergeConflict {
+                    kind: "group_color",
+                    backup_id: group.id,
+                    preview: Some(group.name.clone()),
+                });
+            }
+            continue;
+        }
+        report.groups_created.push(group.name.clone());
+        if create {
+            let new = group_repo
+                .create(&group.name, group.color.as_deref())
+                .map_err(|e| format!("创建分组 {} 失败: {e}", group.name))?;
+            map.insert(group.id, new.id);
+            created.push((new.id, group.parent_id));
+        }
+    }
+    // 新建分组按备份中的层级挂到对应的父分组下
+    for (id, parent) in created {
+        let Some(parent_id) = parent.and_then(|p| map.get(&p).copied()) else {
+            continue;
+        };
+        if let Err(e) = group_repo.set_parent(id, Some(parent_id)) {
+            warn!("Failed to restore parent of merged group {}: {}", id, e);
+        }
+    }
+    Ok(map)
+}
+
+/// 把条目引用的媒体文件存入 blob 存储并改写路径；图片条目缺少图片时返回 false。
+/// 缺失的图标与 staging 副本直接去掉引用（原始文件路径仍在）
+fn import_media(
+    store: Option<&BlobStore>,
+    backup_dir: &Path,
+    item: &mut ClipboardItem,
+    report: &mut MergeReport,
+) -> bool {
+    let mut copy = |path: &str, kind: BlobKind| -> Option<String> {
+        let src = locate_media(backup_dir, path)?;
+        let size = path_size(&src);
+        let stored = match store {
+            Some(store) => match copy_media(store, &src, path, kind) {
+                Ok(stored) => stored.path.to_string_lossy().to_string(),
+                Err(e) => {
+                    warn!("Failed to import media {:?}: {}", src, e);
+                    return None;
+                }
+            },
+            None => path.to_string(),
+        };
+        report.media_files += 1;
+        report.media_bytes += size;
+        Some(stored)
+    };
+
+    if let Some(path) = item.image_path.take().filter(|p| !p.is_empty()) {
+        item.image_path = copy(&path, BlobKind::Image);
+        if item.image_path.is_none() && item.content_type == "image" {
+            return false;
+        }
+    }
+    if let Some(path) = item.source_app_icon.take().filter(|p| !p.is_empty()) {
+        item.source_app_icon = copy(&path, BlobKind::Icon);
+    }
+    let Some(mut payload) = decode_payload(item.file_payload.as_deref()) else {
+        return true;
+    };
+    if !payload.staged.is_empty() {
+        payload
+            .staged
+            .retain_mut(|file| match copy(&file.staged, BlobKind::File) {
+                Some(path) => {
+                    file.staged = path;
+                    true
+                }
+                None => false,
+            });
+        item.file_payload = Some(encode_payload(&payload));
+    }
+    true
+}
+
+/// 媒体文件在备份中的位置：blob 对象按 `blobs/<h2>/<hash>/<名称>`，旧版目录按最后一个媒体目录名截取，
+/// 旧版相对图片路径相对于 `images/`
+fn locate_media(backup_dir: &Path, path: &str) -> Option<PathBuf> {
+    let parts: Vec<&str> = path.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+    let rel = if blobs::hash_from_path(path).is_some() {
+        parts[parts.len() - 4..].join("/")
+    } else if let Some(i) = parts.iter().rposition(|p| MEDIA_DIRS.contains(p)) {
+        parts[i..].join("/")
+    } else if !path.starts_with(['/', '\\']) && !path.contains(':') {
+        format!("images/{}", parts.join("/"))
+    } else {
+        return None;
+    };
+    let full = backup_dir.join(sanitize_zip_relative_path(&rel)?);
+    full.exists().then_some(full)
+}
+
+fn copy_media(store: &BlobStore, src: &Path, path: &str, kind: BlobKind) -> io::Result<StoredBlob> {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+    if !src.is_dir() {
+        return store.put_file(src, name, kind);
+    }
+    // 目录快照：对象哈希由快照内容决定，沿用备份中的对象哈希
+    let hash = blobs::hash_from_path(path)
+        .ok_or_else(|| io::Error::other("directory snapshot outside blob store"))?;
+    let tmp = store.temp_path()?;
+    copy_dir(src, &tmp)?;
+    store.put_dir(&tmp, hash, path_size(src), name)
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)?.flatten() {
+        let target = dst.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| path_size(&e.path())).sum())
+            .unwrap_or(0)
+    } else {
+        fs::metadata(path).map_or(0, |m| m.len())
+    }
+}
+
+fn table_columns(conn: &Connection, table: &str) -> rusqlite::Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    stmt.query_map([], |row| row.get::<_, String>(1))?.collect()
+}
+
+fn read_groups(conn: &Connection) -> rusqlite::Result<Vec<BackupGroup>> {
+    let columns = table_columns(conn, "groups")?;
+    if columns.is_empty() {
+        return Ok(Vec::new());
+    }
+    let parent = if columns.contains("parent_id") {
+        "parent_id"
+    } else {
+        "NULL"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, color, {parent} FROM groups ORDER BY sort_order, id"
+    ))?;
+    stmt.query_map([], |row| {
+        Ok(BackupGroup {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            color: row.get(2)?,
+            parent_id: row.get(3)?,
+        })
+    })?
+    .collect()
+}
+
+/// 条目列及备份缺少该列（旧版本）时的默认值
+const ITEM_COLUMNS: &[(&str, &str)] = &[
+    ("id", "0"),
+    ("content_type", "'text'"),
+    ("text_content", "NULL"),
+    ("html_content", "NULL"),
+    ("rtf_content", "NULL"),
+    ("image_path", "NULL"),
+    ("file_paths", "NULL"),
+    ("file_payload", "NULL"),
+    ("content_hash", "''"),
+    ("semantic_hash", "content_hash"),
+    ("preview", "NULL"),
+    ("byte_size", "0"),
+    ("image_width", "NULL"),
+    ("image_height", "NULL"),
+    ("is_pinned", "0"),
+    ("is_favorite", "0"),
+    ("favorite_order", "0"),
+    ("sort_order", "0"),
+    ("created_at", "datetime('now', 'localtime')"),
+    ("updated_at", "datetime('now', 'localtime')"),
+    ("access_count", "0"),
+    ("last_accessed_at", "NULL"),
+    ("char_count", "NULL"),
+    ("source_app_name", "NULL"),
+    ("source_app_icon", "NULL"),
+    ("group_id", "NULL"),
+    ("ocr_text", "NULL"),
+    ("subtypes", "NULL"),
+    ("expires_at", "NULL"),
+    ("derived_from", "NULL"),
+    ("derivation", "NULL"),
+    ("staging_state", "NULL"),
+    ("media_missing", "0"),
+];
+
+/// 读取备份条目（旧到新），兼容缺少新列的旧版备份，并附加标签名
+fn read_items(conn: &Connection) -> rusqlite::Result<Vec<ClipboardItem>> {
+    let columns = table_columns(conn, "clipboard_items")?;
+    let select = ITEM_COLUMNS
+        .iter()
+        .map(|(name, default)| {
+            if columns.contains(*name) {
+                (*name).to_string()
+            } else {
+                format!("{default} AS {name}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {select} FROM clipboard_items ORDER BY created_at, id"
+    ))?;
+    let mut items: Vec<ClipboardItem> = stmt
+        .query_map([], ClipboardRepository::row_to_item)?
+        .collect::<rusqlite::Result<_>>()?;
+
+    if !table_columns(conn, "item_tags")?.is_empty() {
+        let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT it.item_id, t.name FROM item_tags it JOIN tags t ON t.id = it.tag_id",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?)))?;
+        for row in rows {
+            let (id, name) = row?;
+            tags.entry(id).or_default().push(name);
+        }
+        for item in &mut items {
+            item.tags = tags.remove(&item.id);
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ContentType, TagRepository};
+    use crate::test_support::{item, temp_dir};
+
+    fn all_items(db: &Database) -> Vec<ClipboardItem> {
+        let conn = db.write_connection();
+        let conn = conn.lock();
+        let mut stmt = conn
+            .prepare("SELECT * FROM clipboard_items ORDER BY id")
+            .unwrap();
+        stmt.query_map([], ClipboardRepository::row_to_item)
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn locates_media_inside_backup() {
+        let dir = temp_dir("locate");
+        let hash = "ab".repeat(32);
+        let object = dir.join("blobs").join("ab").join(&hash).join("image.png");
+        fs::create_dir_all(object.parent().unwrap()).unwrap();
+        fs::write(&object, b"png").unwrap();
+        fs::create_dir_all(dir.join("images")).unwrap();
+        fs::write(dir.join("images").join("old.png"), b"old").unwrap();
+
+        let blob_path =
+            format!("C:\\Users\\me\\AppData\\ElegantClipboard\\blobs\\ab\\{hash}\\image.png");
+        assert_eq!(locate_media(&dir, &blob_path), Some(object));
+        assert_eq!(
+            locate_media(&dir, "/home/me/.local/share/ec/images/old.png"),
+            Some(dir.join("images").join("old.png"))
+        );
+        assert_eq!(
+            locate_media(&dir, "old.png"),
+            Some(dir.join("images").join("old.png"))
+        );
+        assert_eq!(locate_media(&dir, "/elsewhere/missing.png"), None);
+        assert_eq!(locate_media(&dir, "images/../../secret"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn merges_new_items_with_groups_media_and_conflicts() {
+        let root = temp_dir("merge");
+        // 备份：一个分组、三条文本（其中一条当前已有）、一张图片、一张缺失的图片
+        let backup_dir = root.join("backup");
+        let backup_media = root.join("backup_data");
+        {
+            let db = Database::new(backup_dir.join(DB_ENTRY)).unwrap();
+            let repo = ClipboardRepository::new(&db);
+            let groups = GroupRepository::new(&db);
+            let work = groups.create("Work", Some("#ff0000")).unwrap();
+            let notes = groups.create("Notes", None).unwrap();
+            groups.set_parent(notes.id, Some(work.id)).unwrap();
+
+            let mut shared = item(ContentType::Text, "shared");
+            shared.group_id = Some(work.id);
+            let shared = repo.insert(shared).unwrap();
+            repo.toggle_favorite(shared).unwrap();
+            let mut note = item(ContentType::Text, "meeting notes");
+            note.group_id = Some(notes.id);
+            let note = repo.insert(note).unwrap();
+            let tag = TagRepository::new(&db).create("todo", None).unwrap();
+            TagRepository::new(&db)
+                .tag_items(&[note], &[tag.id])
+                .unwrap();
+            repo.insert(item(ContentType::Text, "plain")).unwrap();
+
+            let store = BlobStore::new(&db, backup_media.join(blobs::DIR_NAME));
+            let png = root.join("shot.png");
+            fs::write(&png, b"png bytes").unwrap();
+            let stored = store.put_file(&png, "image.png", BlobKind::Image).unwrap();
+            let mut image = item(ContentType::Image, "shot");
+            image.image_path = Some(stored.path.to_string_lossy().to_string());
+            repo.insert(image).unwrap();
+            let mut lost = item(ContentType::Image, "lost");
+            lost.image_path = Some("/gone/images/lost.png".into());
+            repo.insert(lost).unwrap();
+        }
+        // 解压后的备份目录结构：clipboard.db + blobs/
+        copy_dir(
+            &backup_media.join(blobs::DIR_NAME),
+            &backup_dir.join(blobs::DIR_NAME),
+        )
+        .unwrap();
+
+        let current_dir = root.join("current");
+        let db = Database::new(current_dir.join(DB_ENTRY)).unwrap();
+        let repo = ClipboardRepository::new(&db);
+        let existing_work = GroupRepository::new(&db).create("Work", None).unwrap();
+        repo.insert(item(ContentType::Text, "shared")).unwrap();
+        let store = BlobStore::new(&db, current_dir.join(blobs::DIR_NAME));
+
+        let preview = merge(&db, None, &backup_dir).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.backup_items, 5);
+        assert_eq!(preview.imported, 3);
+        assert_eq!(preview.duplicates, 1);
+        assert_eq!(preview.skipped, 1);
+        assert_eq!(preview.groups_matched, vec!["Work".to_string()]);
+        assert_eq!(preview.groups_created, vec!["Notes".to_string()]);
+        assert_eq!(preview.media_files, 1);
+        let kinds: Vec<&str> = preview.conflicts.iter().map(|c| c.kind).collect();
+        assert!(kinds.contains(&"group_color"));
+        assert!(kinds.contains(&"other_group"));
+        assert!(kinds.contains(&"state_differs"));
+        assert!(kinds.contains(&"media_missing"));
+        assert_eq!(all_items(&db).len(), 1);
+
+        let report = merge(&db, Some(&store), &backup_dir).unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.imported, 3);
+        let items = all_items(&db);
+        assert_eq!(items.len(), 4);
+
+        let groups = GroupRepository::new(&db).list_with_count().unwrap();
+        let notes = groups.iter().find(|g| g.name == "Notes").unwrap();
+        assert_eq!(notes.parent_id, Some(existing_work.id));
+        let note = items
+            .iter()
+            .find(|i| i.text_content.as_deref() == Some("meeting notes"))
+            .unwrap();
+        assert_eq!(note.group_id, Some(notes.id));
+        let mut with_tags = vec![note.clone()];
+        TagRepository::new(&db).attach_tags(&mut with_tags).unwrap();
+        assert_eq!(
+            with_tags[0].tags.as_deref(),
+            Some(&["todo".to_string()][..])
+        );
+
+        let image = items.iter().find(|i| i.content_type == "image").unwrap();
+        let image_path = PathBuf::from(image.image_path.as_deref().unwrap());
+        assert!(image_path.starts_with(current_dir.join(blobs::DIR_NAME)));
+        assert_eq!(fs::read(&image_path).unwrap(), b"png bytes");
+
+        // 再次合并不会重复导入
+        let again = merge(&db, Some(&store), &backup_dir).unwrap();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.duplicates, 4);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
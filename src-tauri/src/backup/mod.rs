@@ -0,0 +1,795 @@
+//! 数据备份：流式写入 ZIP（按块复制，内存占用与文件大小无关）、按条件导出部分数据、
+//! 逐条目 BLAKE3 校验和清单（导入时校验）与可选的密码加密（见 [`crypt`]）。
+
+pub mod crypt;
+pub mod merge;
+pub mod open_export;
+
+use crate::clipboard::file_clipboard;
+use rusqlite::{Connection, ToSql, params_from_iter};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::warn;
+
+pub const DB_ENTRY: &str = "clipboard.db";
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+const MANIFEST_VERSION: u32 = 1;
+/// 完整备份包含的媒体目录
+const MEDIA_DIRS: [&str; 4] = ["images", "icons", "staged", crate::blobs::DIR_NAME];
+/// 导入时先解压到该目录，校验通过后再移动到位
+const IMPORT_STAGING_DIR: &str = "import.tmp";
+const COPY_BUF_SIZE: usize = 64 * 1024;
+const CANCELLED_MSG: &str = "用户取消了操作";
+
+/// SQL 条件（AND 连接）与对应参数
+type Conditions = (Vec<String>, Vec<Box<dyn ToSql>>);
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 请求取消正在进行的导出 / 导入（在下一个数据块处生效）
+pub fn cancel() {
+    CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// 开始新的导出 / 导入前清除取消标记
+pub fn reset_cancel() {
+    CANCELLED.store(false, Ordering::SeqCst);
+}
+
+/// 已请求取消时返回错误（导入其他工具的历史时逐条检查）
+pub fn check_cancelled() -> Result<(), String> {
+    if CANCELLED.load(Ordering::SeqCst) {
+        return Err(CANCELLED_MSG.into());
+    }
+    Ok(())
+}
+
+/// 导出范围，全部为空时导出所有数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExportFilter {
+    /// 分组（`null` = 默认分组），未设置时不限分组
+    pub group_ids: Option<Vec<Option<i64>>>,
+    /// 创建时间范围（`YYYY-MM-DD` 或 `YYYY-MM-DD HH:MM:SS`，含端点）
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub content_types: Option<Vec<String>>,
+    pub favorites_only: bool,
+}
+
+impl ExportFilter {
+    pub fn is_empty(&self) -> bool {
+        self.group_ids.is_none()
+            && self.date_from.is_none()
+            && self.date_to.is_none()
+            && self.content_types.is_none()
+            && !self.favorites_only
+    }
+
+    /// 条目需满足的条件（AND）与参数
+    fn conditions(&self) -> Result<Conditions, String> {
+        let mut conditions = Vec::new();
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(group_ids) = &self.group_ids {
+            let ids: Vec<i64> = group_ids.iter().flatten().copied().collect();
+            let mut any = Vec::new();
+            if group_ids.contains(&None) {
+                any.push("group_id IS NULL".to_string());
+            }
+            if !ids.is_empty() {
+                any.push(format!("group_id IN ({})", placeholders(ids.len())));
+                params.extend(ids.into_iter().map(|id| Box::new(id) as Box<dyn ToSql>));
+            }
+            conditions.push(if any.is_empty() {
+                "0".to_string()
+            } else {
+                format!("({})", any.join(" OR "))
+            });
+        }
+        if let Some(from) = &self.date_from {
+            conditions.push("created_at >= ?".to_string());
+            params.push(Box::new(normalize_date(from, false)?));
+        }
+        if let Some(to) = &self.date_to {
+            conditions.push("created_at <= ?".to_string());
+            params.push(Box::new(normalize_date(to, true)?));
+        }
+        if let Some(types) = &self.content_types {
+            if types.is_empty() {
+                conditions.push("0".to_string());
+            } else {
+                conditions.push(format!("content_type IN ({})", placeholders(types.len())));
+                params.extend(types.iter().map(|t| Box::new(t.clone()) as Box<dyn ToSql>));
+            }
+        }
+        if self.favorites_only {
+            conditions.push("is_favorite = 1".to_string());
+        }
+        Ok((conditions, params))
+    }
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(",")
+}
+
+/// 日期补全为与 `created_at` 相同的格式；仅日期时起点取 00:00:00，终点取 23:59:59
+fn normalize_date(value: &str, end: bool) -> Result<String, String> {
+    let value = value.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = if end { "23:59:59" } else { "00:00:00" };
+        return Ok(format!("{date} {time}"));
+    }
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .map_err(|_| format!("无效的日期: {value}"))
+}
+
+/// 在数据库副本上删除导出范围外的条目、分组、翻译缓存与不再被引用的 blob 记录，
+/// 并 VACUUM 使删除的内容不残留在文件中。返回保留的条目数
+pub fn prune_copy(db_path: &Path, filter: &ExportFilter) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("打开数据库副本失败: {e}"))?;
+    let count = |conn: &Connection| {
+        conn.query_row("SELECT COUNT(*) FROM clipboard_items", [], |row| row.get(0))
+            .map_err(|e| e.to_string())
+    };
+    if filter.is_empty() {
+        return count(&conn);
+    }
+
+    let (conditions, params) = filter.conditions()?;
+    let sql = format!(
+        "DELETE FROM clipboard_items WHERE NOT ({})",
+        conditions.join(" AND ")
+    );
+    let run = || -> rusqlite::Result<()> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute(&sql, params_from_iter(params.iter()))?;
+        if let Some(group_ids) = &filter.group_ids {
+            let ids: Vec<i64> = group_ids.iter().flatten().copied().collect();
+            conn.execute(
+                &format!(
+                    "DELETE FROM groups WHERE id NOT IN ({})",
+                    placeholders(ids.len())
+                ),
+                params_from_iter(ids.iter()),
+            )?;
+        }
+        conn.execute_batch(
+            "DELETE FROM translation_cache;
+             DELETE FROM blobs WHERE hash NOT IN (SELECT hash FROM item_blobs);
+             VACUUM;",
+        )
+    };
+    run().map_err(|e| format!("筛选导出数据失败: {e}"))?;
+    count(&conn)
+}
+
+/// 备份中的一个媒体文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaFile {
+    /// ZIP 内路径（相对数据目录，`/` 分隔）
+    pub entry: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// 备份包含的媒体文件：完整导出为全部媒体目录，部分导出仅包含副本中剩余条目引用的文件
+pub fn collect_media(
+    data_dir: &Path,
+    db_copy: &Path,
+    filter: &ExportFilter,
+) -> Result<Vec<MediaFile>, String> {
+    let mut files = BTreeMap::new();
+    if filter.is_empty() {
+        for dir in MEDIA_DIRS {
+            walk(&data_dir.join(dir), dir, &mut files);
+        }
+    } else {
+        for path in referenced_paths(db_copy).map_err(|e| e.to_string())? {
+            let p = Path::new(&path);
+            // 旧版相对图片路径相对于 `images/`
+            let resolved = if p.is_absolute() {
+                p.to_path_buf()
+            } else {
+                data_dir.join("images").join(p)
+            };
+            match resolved.strip_prefix(data_dir) {
+                Ok(rel) => walk(&resolved, &entry_name(rel), &mut files),
+                Err(_) => warn!("Skipping media outside data dir: {}", path),
+            }
+        }
+    }
+    Ok(files
+        .into_iter()
+        .map(|(entry, path)| {
+            let size = fs::metadata(&path).map_or(0, |m| m.len());
+            MediaFile { entry, path, size }
+        })
+        .collect())
+}
+
+fn referenced_paths(db_path: &Path) -> rusqlite::Result<Vec<String>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(
+        "SELECT image_path, source_app_icon, file_payload FROM clipboard_items \
+         WHERE image_path IS NOT NULL OR source_app_icon IS NOT NULL OR file_payload IS NOT NULL",
+    )?;
+    let mut paths = Vec::new();
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let image: Option<String> = row.get(0)?;
+        let icon: Option<String> = row.get(1)?;
+        let payload: Option<String> = row.get(2)?;
+        paths.extend(image.into_iter().chain(icon).filter(|p| !p.is_empty()));
+        paths.extend(file_clipboard::staged_paths_from_payload(
+            payload.as_deref(),
+        ));
+    }
+    Ok(paths)
+}
+
+fn entry_name(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 收集文件或目录（递归）下的所有文件
+fn walk(path: &Path, entry: &str, files: &mut BTreeMap<String, PathBuf>) {
+    if path.is_dir() {
+        let Ok(read_dir) = fs::read_dir(path) else {
+            return;
+        };
+        for child in read_dir.flatten() {
+            let name = format!("{}/{}", entry, child.file_name().to_string_lossy());
+            walk(&child.path(), &name, files);
+        }
+    } else if path.is_file() {
+        files.insert(entry.to_string(), path.to_path_buf());
+    }
+}
+
+/// 进度：`phase` 为 `export` / `encrypt` / `decrypt` / `import`，单位为字节
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferProgress {
+    pub phase: &'static str,
+    pub done: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size: u64,
+    pub blake3: String,
+}
+
+/// 备份清单，作为最后一个条目写入
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Manifest {
+    pub version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    /// 导出范围（完整导出时为空）
+    pub filter: ExportFilter,
+    pub items: i64,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn new(filter: ExportFilter, items: i64) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            filter,
+            items,
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// 按块复制并计算 BLAKE3，返回 `(字节数, 哈希)`；每块检查一次取消标记
+fn copy_hashed(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    on_chunk: &mut impl FnMut(u64),
+) -> Result<(u64, String), String> {
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0u8; COPY_BUF_SIZE];
+    let mut total = 0u64;
+    loop {
+        check_cancelled()?;
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.to_string()),
+        };
+        hasher.update(&buf[..n]);
+        writer.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+        total += n as u64;
+        on_chunk(n as u64);
+    }
+    Ok((total, hasher.finalize().to_hex().to_string()))
+}
+
+/// 流式写入备份：数据库副本、媒体文件，最后写入清单。返回写入的清单
+pub fn write_archive<W: Write + Seek>(
+    out: W,
+    db_copy: &Path,
+    media: &[MediaFile],
+    mut manifest: Manifest,
+    on_progress: &mut impl FnMut(TransferProgress),
+) -> Result<Manifest, String> {
+    use zip::write::SimpleFileOptions;
+
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let db_size = fs::metadata(db_copy).map_or(0, |m| m.len());
+    let total = db_size + media.iter().map(|m| m.size).sum::<u64>();
+    let mut done = 0u64;
+    let mut zip = zip::ZipWriter::new(out);
+
+    let sources = std::iter::once((DB_ENTRY, db_copy, db_size)).chain(
+        media
+            .iter()
+            .map(|m| (m.entry.as_str(), m.path.as_path(), m.size)),
+    );
+    for (entry, path, expected_size) in sources {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if entry == DB_ENTRY => return Err(format!("读取数据库副本失败: {e}")),
+            // 导出期间被清理的媒体文件：跳过而不是让整个导出失败
+            Err(e) => {
+                warn!("Skipping unreadable media {:?}: {}", path, e);
+                continue;
+            }
+        };
+        // 超过 4 GB 的条目需要 ZIP64
+        let options = options.large_file(expected_size >= u64::from(u32::MAX));
+        zip.start_file(entry, options).map_err(|e| e.to_string())?;
+        let (size, hash) = copy_hashed(&mut file, &mut zip, &mut |n| {
+            done += n;
+            on_progress(TransferProgress {
+                phase: "export",
+                done,
+                total,
+            });
+        })
+        .map_err(|e| {
+            if e == CANCELLED_MSG {
+                e
+            } else {
+                format!("写入 {entry} 失败: {e}")
+            }
+        })?;
+        manifest.entries.push(ManifestEntry {
+            path: entry.to_string(),
+            size,
+            blake3: hash,
+        });
+    }
+
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_ENTRY, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(manifest)
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractSummary {
+    pub files: u32,
+    /// 备份带有清单且全部条目校验通过（旧版备份无清单时为 false）
+    pub verified: bool,
+    pub manifest: Option<Manifest>,
+}
+
+/// 解压备份到 `data_dir`：先解压到临时目录并按清单逐条校验大小与哈希，
+/// 全部通过后再移动到位；数据库写为 `clipboard.db.import`，重启时替换。
+pub fn extract_archive<R: Read + Seek>(
+    reader: R,
+    data_dir: &Path,
+    on_progress: &mut impl FnMut(TransferProgress),
+) -> Result<ExtractSummary, String> {
+    let staging = data_dir.join(IMPORT_STAGING_DIR);
+    let result = unpack_archive(reader, &staging, on_progress).and_then(|(files, manifest)| {
+        move_into_place(&staging, data_dir, &files).map(|()| (files.len(), manifest))
+    });
+    let _ = fs::remove_dir_all(&staging);
+    let (files, manifest) = result?;
+    Ok(ExtractSummary {
+        files: files as u32,
+        verified: manifest.is_some(),
+        manifest,
+    })
+}
+
+/// 解压备份到空目录 `dest` 并按清单校验（合并导入直接读取该目录），返回解压出的相对路径；
+/// 失败时 `dest` 可能残留部分文件，由调用方删除
+pub fn unpack_archive<R: Read + Seek>(
+    reader: R,
+    dest: &Path,
+    on_progress: &mut impl FnMut(TransferProgress),
+) -> Result<(Vec<PathBuf>, Option<Manifest>), String> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| format!("无效的 ZIP 文件: {e}"))?;
+    if archive.index_for_name(DB_ENTRY).is_none() {
+        return Err("ZIP 文件中未找到 clipboard.db，不是有效的备份文件".to_string());
+    }
+    let manifest: Option<Manifest> = match archive.by_name(MANIFEST_ENTRY) {
+        Ok(mut entry) => {
+            let mut json = Vec::new();
+            entry.read_to_end(&mut json).map_err(|e| e.to_string())?;
+            Some(serde_json::from_slice(&json).map_err(|e| format!("备份清单无效: {e}"))?)
+        }
+        Err(_) => {
+            warn!("Backup has no manifest, skipping checksum verification");
+            None
+        }
+    };
+
+    let _ = fs::remove_dir_all(dest);
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    let files = extract_to_staging(&mut archive, dest, manifest.as_ref(), on_progress)?;
+    Ok((files, manifest))
+}
+
+fn extract_to_staging<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    staging: &Path,
+    manifest: Option<&Manifest>,
+    on_progress: &mut impl FnMut(TransferProgress),
+) -> Result<Vec<PathBuf>, String> {
+    let mut expected: HashMap<&str, &ManifestEntry> = manifest
+        .map(|m| m.entries.iter().map(|e| (e.path.as_str(), e)).collect())
+        .unwrap_or_default();
+    let total: u64 = (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok().map(|f| f.size()))
+        .sum();
+    let mut done = 0u64;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.name().to_string();
+        if name == MANIFEST_ENTRY || entry.is_dir() {
+            continue;
+        }
+        let Some(rel_path) = sanitize_zip_relative_path(&name) else {
+            warn!("Skipping unsafe zip entry path: {name}");
+            continue;
+        };
+        // 跳过临时数据库文件，仅导入 clipboard.db 和资产目录
+        if rel_path.ends_with("clipboard.db-wal") || rel_path.ends_with("clipboard.db-shm") {
+            continue;
+        }
+        let wanted = match manifest {
+            Some(_) => Some(
+                expected
+                    .remove(name.as_str())
+                    .ok_or_else(|| format!("备份包含清单外的文件: {name}"))?,
+            ),
+            None => None,
+        };
+
+        let out_path = staging.join(&rel_path);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = File::create(&out_path).map_err(|e| format!("写入 {name} 失败: {e}"))?;
+        let (size, hash) = copy_hashed(&mut entry, &mut out, &mut |n| {
+            done += n;
+            on_progress(TransferProgress {
+                phase: "import",
+                done,
+                total,
+            });
+        })?;
+        if wanted.is_some_and(|w| w.size != size || w.blake3 != hash) {
+            return Err(format!("校验失败，备份文件已损坏: {name}"));
+        }
+        files.push(rel_path);
+    }
+
+    if let Some(missing) = expected.keys().next() {
+        return Err(format!("备份不完整，缺少文件: {missing}"));
+    }
+    Ok(files)
+}
+
+fn move_into_place(staging: &Path, data_dir: &Path, files: &[PathBuf]) -> Result<(), String> {
+    for rel_path in files {
+        let src = staging.join(rel_path);
+        let dst = if rel_path == Path::new(DB_ENTRY) {
+            data_dir.join("clipboard.db.import")
+        } else {
+            data_dir.join(rel_path)
+        };
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if dst.is_file() {
+            let _ = fs::remove_file(&dst);
+        }
+        if fs::rename(&src, &dst).is_err() {
+            fs::copy(&src, &dst).map_err(|e| format!("写入 {dst:?} 失败: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// 将 ZIP 条目名转为安全的相对路径（拒绝绝对路径与路径穿越）
+pub fn sanitize_zip_relative_path(name: &str) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let raw = Path::new(name);
+    if raw.is_absolute() {
+        return None;
+    }
+
+    let mut clean = PathBuf::new();
+    for component in raw.components() {
+        match component {
+            Component::Normal(seg) => clean.push(seg),
+            Component::CurDir => {}
+            // 拒绝根/前缀/父目录防止路径穿越
+            Component::RootDir | Component::Prefix(_) | Component::ParentDir => return None,
+        }
+    }
+
+    if clean.as_os_str().is_empty() {
+        return None;
+    }
+
+    Some(clean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ClipboardRepository, ContentType, Database};
+    use crate::test_support::{item, temp_dir};
+    use std::io::Cursor;
+
+    fn write_to_vec(db: &Path, media: &[MediaFile]) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        write_archive(
+            &mut out,
+            db,
+            media,
+            Manifest::new(ExportFilter::default(), 0),
+            &mut |_| {},
+        )
+        .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn normalizes_dates() {
+        assert_eq!(
+            normalize_date("2024-03-01", false).unwrap(),
+            "2024-03-01 00:00:00"
+        );
+        assert_eq!(
+            normalize_date(" 2024-03-01 ", true).unwrap(),
+            "2024-03-01 23:59:59"
+        );
+        assert_eq!(
+            normalize_date("2024-03-01 08:30:00", true).unwrap(),
+            "2024-03-01 08:30:00"
+        );
+        assert!(normalize_date("03/01/2024", false).is_err());
+    }
+
+    #[test]
+    fn prunes_copy_and_collects_referenced_media() {
+        let dir = temp_dir("prune");
+        let db_path = dir.join("clipboard.db");
+        let image = dir.join("images").join("kept.png");
+        fs::create_dir_all(image.parent().unwrap()).unwrap();
+        fs::write(&image, b"png").unwrap();
+        fs::write(dir.join("images").join("other.png"), b"other").unwrap();
+        {
+            let db = Database::new(db_path.clone()).unwrap();
+            let repo = ClipboardRepository::new(&db);
+            let group = crate::database::GroupRepository::new(&db)
+                .create("Work", None)
+                .unwrap();
+            let fav = repo.insert(item(ContentType::Text, "favorite")).unwrap();
+            repo.toggle_favorite(fav).unwrap();
+            let mut shot = item(ContentType::Image, "shot");
+            shot.image_path = Some(image.to_string_lossy().to_string());
+            shot.group_id = Some(group.id);
+            let shot = repo.insert(shot).unwrap();
+            repo.toggle_favorite(shot).unwrap();
+            repo.insert(item(ContentType::Url, "https://example.com"))
+                .unwrap();
+            repo.insert(item(ContentType::Text, "secret")).unwrap();
+        }
+
+        let everything = ExportFilter::default();
+        assert_eq!(prune_copy(&db_path, &everything).unwrap(), 4);
+        let all_media = collect_media(&dir, &db_path, &everything).unwrap();
+        assert_eq!(all_media.len(), 2);
+
+        let filter = ExportFilter {
+            favorites_only: true,
+            date_from: Some("2000-01-01".into()),
+            ..Default::default()
+        };
+        assert_eq!(prune_copy(&db_path, &filter).unwrap(), 2);
+        let media = collect_media(&dir, &db_path, &filter).unwrap();
+        assert_eq!(media.len(), 1);
+        assert_eq!(media[0].entry, "images/kept.png");
+        assert_eq!(media[0].size, 3);
+
+        let only_work = ExportFilter {
+            group_ids: Some(vec![None]),
+            content_types: Some(vec!["image".into()]),
+            ..Default::default()
+        };
+        assert_eq!(prune_copy(&db_path, &only_work).unwrap(), 0);
+        let conn = Connection::open(&db_path).unwrap();
+        let groups: i64 = conn
+            .query_row("SELECT COUNT(*) FROM groups", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(groups, 0);
+        drop(conn);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn archive_round_trip_verifies_checksums() {
+        let dir = temp_dir("roundtrip");
+        let db = dir.join("copy.db");
+        fs::write(&db, b"sqlite bytes").unwrap();
+        let blob = dir.join("blob.bin");
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 253) as u8).collect();
+        fs::write(&blob, &data).unwrap();
+        let media = vec![MediaFile {
+            entry: "blobs/ab/abcd/blob.bin".into(),
+            path: blob,
+            size: data.len() as u64,
+        }];
+
+        let mut last = None;
+        let mut out = Cursor::new(Vec::new());
+        let manifest = write_archive(
+            &mut out,
+            &db,
+            &media,
+            Manifest::new(ExportFilter::default(), 1),
+            &mut |p| last = Some(p),
+        )
+        .unwrap();
+        assert_eq!(manifest.entries.len(), 2);
+        assert_eq!(
+            manifest.entries[1].blake3,
+            blake3::hash(&data).to_hex().to_string()
+        );
+        let last = last.unwrap();
+        assert_eq!(last.done, last.total);
+
+        let target = dir.join("data");
+        let summary = extract_archive(Cursor::new(out.into_inner()), &target, &mut |_| {}).unwrap();
+        assert_eq!(summary.files, 2);
+        assert!(summary.verified);
+        assert_eq!(
+            fs::read(target.join("clipboard.db.import")).unwrap(),
+            b"sqlite bytes"
+        );
+        assert_eq!(
+            fs::read(target.join("blobs/ab/abcd/blob.bin")).unwrap(),
+            data
+        );
+        assert!(!target.join(IMPORT_STAGING_DIR).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_rejects_corrupted_entries_without_touching_data() {
+        use zip::write::SimpleFileOptions;
+
+        let dir = temp_dir("corrupt");
+        let db = dir.join("copy.db");
+        fs::write(&db, b"original").unwrap();
+        let good = write_to_vec(&db, &[]);
+
+        // 用原清单重新打包被篡改的数据库
+        let mut archive = zip::ZipArchive::new(Cursor::new(good)).unwrap();
+        let mut manifest = Vec::new();
+        archive
+            .by_name(MANIFEST_ENTRY)
+            .unwrap()
+            .read_to_end(&mut manifest)
+            .unwrap();
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file(DB_ENTRY, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"tampered").unwrap();
+        zip.start_file(MANIFEST_ENTRY, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&manifest).unwrap();
+        let tampered = zip.finish().unwrap().into_inner();
+
+        let target = dir.join("data");
+        let err = extract_archive(Cursor::new(tampered), &target, &mut |_| {}).unwrap_err();
+        assert!(err.contains("校验失败"), "{err}");
+        assert!(!target.join("clipboard.db.import").exists());
+        assert!(!target.join(IMPORT_STAGING_DIR).exists());
+
+        // 没有清单的旧版备份照常导入，但不标记为已校验
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file(DB_ENTRY, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"legacy").unwrap();
+        let legacy = zip.finish().unwrap().into_inner();
+        let summary = extract_archive(Cursor::new(legacy), &target, &mut |_| {}).unwrap();
+        assert!(!summary.verified);
+        assert_eq!(summary.files, 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn normal_relative_path() {
+        assert_eq!(
+            sanitize_zip_relative_path("images/screenshot.png"),
+            Some(PathBuf::from("images/screenshot.png"))
+        );
+    }
+
+    #[test]
+    fn simple_filename() {
+        assert_eq!(
+            sanitize_zip_relative_path("clipboard.db"),
+            Some(PathBuf::from("clipboard.db"))
+        );
+    }
+
+    #[test]
+    fn rejects_parent_dir_traversal() {
+        assert_eq!(sanitize_zip_relative_path("../etc/passwd"), None);
+        assert_eq!(sanitize_zip_relative_path("images/../../secret"), None);
+    }
+
+    #[test]
+    fn rejects_absolute_path() {
+        assert_eq!(sanitize_zip_relative_path("/etc/passwd"), None);
+        assert_eq!(sanitize_zip_relative_path("C:\\Windows\\system32"), None);
+    }
+
+    #[test]
+    fn rejects_empty_path() {
+        assert_eq!(sanitize_zip_relative_path(""), None);
+    }
+
+    #[test]
+    fn rejects_dot_only() {
+        assert_eq!(sanitize_zip_relative_path("."), None);
+        assert_eq!(sanitize_zip_relative_path("./"), None);
+    }
+
+    #[test]
+    fn strips_current_dir_prefix() {
+        assert_eq!(
+            sanitize_zip_relative_path("./images/test.png"),
+            Some(PathBuf::from("images/test.png"))
+        );
+    }
+
+    #[test]
+    fn nested_path_ok() {
+        assert_eq!(
+            sanitize_zip_relative_path("a/b/c/d.txt"),
+            Some(PathBuf::from("a/b/c/d.txt"))
+        );
+    }
+}
@@ -0,0 +1,677 @@
+//! 开放格式导出：JSON Lines（每行一个 `ClipboardItem`，媒体按相对路径引用）、
+//! 按分组的 Markdown 笔记，以及内嵌缩略图的单文件 HTML 归档。
+//! 条目按列表视图的 [`QueryOptions`] 筛选，不依赖应用即可阅读或交给其他工具处理。
+
+use crate::database::{
+    ClipboardItem, ClipboardRepository, Database, GroupRepository, QueryOptions, TagRepository,
+};
+use crate::utils::escape_html;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
+
+/// Markdown 导出中的媒体目录（相对于导出目录）
+const MEDIA_DIR: &str = "media";
+/// HTML 归档中内嵌缩略图的最大边长
+const THUMBNAIL_SIZE: u32 = 320;
+const DEFAULT_GROUP_NAME: &str = "默认分组";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenFormat {
+    /// `<名称>.jsonl` + 同名 `<名称>_media/` 目录
+    Jsonl,
+    /// 导出目录下每个分组一个 `.md` 文件 + `media/` 目录
+    Markdown,
+    /// 单个 `.html` 文件，图片以缩略图内嵌
+    Html,
+}
+
+impl OpenFormat {
+    /// 导出文件的扩展名；Markdown 导出为目录
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Jsonl => Some("jsonl"),
+            Self::Markdown => None,
+            Self::Html => Some("html"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenExportSummary {
+    pub items: usize,
+    /// 复制的媒体文件数（HTML 为内嵌的缩略图数）
+    pub media: usize,
+    /// 写入的文件（Markdown 为各分组的笔记）
+    pub files: Vec<PathBuf>,
+}
+
+/// 按列表视图的筛选条件查询完整条目（含标签）。
+/// `group_ids` 指定多个分组时逐个分组查询（`None` = 默认分组）；标签筛选本身跨分组，此时忽略
+pub fn query_items(
+    db: &Database,
+    options: &QueryOptions,
+    group_ids: Option<&[Option<i64>]>,
+) -> Result<Vec<ClipboardItem>, String> {
+    let repo = ClipboardRepository::new(db);
+    let by_tag = options.tag_ids.as_ref().is_some_and(|ids| !ids.is_empty());
+    let mut items = match group_ids {
+        Some(group_ids) if !by_tag => {
+            let mut items = Vec::new();
+            for &group_id in group_ids {
+                let options = QueryOptions {
+                    group_id,
+                    ..options.clone()
+                };
+                items.extend(repo.list_full(&options).map_err(|e| e.to_string())?);
+            }
+            items
+        }
+        _ => repo.list_full(options).map_err(|e| e.to_string())?,
+    };
+    TagRepository::new(db)
+        .attach_tags(&mut items)
+        .map_err(|e| e.to_string())?;
+    Ok(items)
+}
+
+/// 写入导出：`dest` 为 JSONL / HTML 文件路径或 Markdown 导出目录。每写完一条回调 `(已完成, 总数)`
+pub fn export(
+    db: &Database,
+    format: OpenFormat,
+    items: &[ClipboardItem],
+    dest: &Path,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<OpenExportSummary, String> {
+    let groups: HashMap<i64, String> = GroupRepository::new(db)
+        .list_with_count()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|g| (g.id, g.name))
+        .collect();
+    let mut progress = |done| {
+        super::check_cancelled()?;
+        on_progress(done, items.len());
+        Ok::<(), String>(())
+    };
+    match format {
+        OpenFormat::Jsonl => write_jsonl(items, dest, &mut progress),
+        OpenFormat::Markdown => write_markdown(items, &groups, dest, &mut progress),
+        OpenFormat::Html => write_html(items, &groups, dest, &mut progress),
+    }
+    .inspect_err(|_| {
+        // 写入失败或取消时不留下不完整的单文件导出
+        if format.extension().is_some() {
+            let _ = fs::remove_file(dest);
+        }
+    })
+}
+
+type Progress<'a> = dyn FnMut(usize) -> Result<(), String> + 'a;
+
+/// 把引用的媒体文件复制到导出目录，同一源文件只复制一次
+struct MediaCopier {
+    dir: PathBuf,
+    /// 写入导出文件的相对路径前缀
+    prefix: String,
+    copied: HashMap<String, String>,
+}
+
+impl MediaCopier {
+    fn new(dir: PathBuf, prefix: String) -> Self {
+        Self {
+            dir,
+            prefix,
+            copied: HashMap::new(),
+        }
+    }
+
+    /// 复制后返回相对路径；源文件不存在时返回 `None`
+    fn copy(&mut self, src: &str, name: &str) -> Result<Option<String>, String> {
+        if let Some(rel) = self.copied.get(src) {
+            return Ok(Some(rel.clone()));
+        }
+        let src_path = Path::new(src);
+        if !src_path.is_file() {
+            return Ok(None);
+        }
+        let file_name = match src_path.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{name}.{ext}"),
+            None => name.to_string(),
+        };
+        fs::create_dir_all(&self.dir).map_err(|e| format!("创建媒体目录失败: {e}"))?;
+        fs::copy(src_path, self.dir.join(&file_name))
+            .map_err(|e| format!("复制媒体文件失败: {e}"))?;
+        let rel = format!("{}/{file_name}", self.prefix);
+        self.copied.insert(src.to_string(), rel.clone());
+        Ok(Some(rel))
+    }
+
+    fn count(&self) -> usize {
+        self.copied.len()
+    }
+}
+
+fn create_file(path: &Path) -> Result<BufWriter<File>, String> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("创建文件失败: {e}"))
+}
+
+fn io_err(e: std::io::Error) -> String {
+    format!("写入文件失败: {e}")
+}
+
+fn write_jsonl(
+    items: &[ClipboardItem],
+    dest: &Path,
+    progress: &mut Progress,
+) -> Result<OpenExportSummary, String> {
+    let stem = dest
+        .file_stem()
+        .map_or_else(|| "export".into(), |s| s.to_string_lossy().into_owned());
+    let media_name = format!("{stem}_media");
+    let mut media = MediaCopier::new(dest.with_file_name(&media_name), media_name);
+    let mut out = create_file(dest)?;
+    for (index, item) in items.iter().enumerate() {
+        let mut item = item.clone();
+        if let Some(path) = item.image_path.take() {
+            item.image_path = media.copy(&path, &item.id.to_string())?;
+        }
+        if let Some(path) = item.source_app_icon.take() {
+            let name = Path::new(&path).file_stem().map_or_else(
+                || format!("icon_{}", item.id),
+                |s| s.to_string_lossy().into(),
+            );
+            item.source_app_icon = media.copy(&path, &format!("icon_{name}"))?;
+        }
+        // 文件 payload 含本机 staging 路径，离开本机无意义
+        item.file_payload = None;
+        let line = serde_json::to_string(&item).map_err(|e| e.to_string())?;
+        writeln!(out, "{line}").map_err(io_err)?;
+        progress(index + 1)?;
+    }
+    out.flush().map_err(io_err)?;
+    Ok(OpenExportSummary {
+        items: items.len(),
+        media: media.count(),
+        files: vec![dest.to_path_buf()],
+    })
+}
+
+/// 分组显示名称（默认分组或已删除的分组显示为“默认分组”）
+fn group_name(groups: &HashMap<i64, String>, group_id: Option<i64>) -> &str {
+    group_id
+        .and_then(|id| groups.get(&id))
+        .map_or(DEFAULT_GROUP_NAME, String::as_str)
+}
+
+/// 按分组归类，保持条目在查询结果中的顺序；分组按首次出现的顺序排列
+fn by_group<'a>(
+    items: &'a [ClipboardItem],
+    groups: &'a HashMap<i64, String>,
+) -> Vec<(&'a str, Vec<&'a ClipboardItem>)> {
+    let mut sections: Vec<(&str, Vec<&ClipboardItem>)> = Vec::new();
+    for item in items {
+        let name = group_name(groups, item.group_id);
+        match sections.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, list)) => list.push(item),
+            None => sections.push((name, vec![item])),
+        }
+    }
+    sections
+}
+
+fn file_paths(item: &ClipboardItem) -> Vec<String> {
+    item.file_paths
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default()
+}
+
+/// 子类型中的代码语言（`lang:rust`）
+fn code_language(item: &ClipboardItem) -> &str {
+    item.subtypes
+        .as_deref()
+        .and_then(|s| s.split(',').find_map(|t| t.strip_prefix("lang:")))
+        .unwrap_or("")
+}
+
+fn type_label(content_type: &str) -> &str {
+    match content_type {
+        "text" => "文本",
+        "url" => "链接",
+        "html" => "HTML",
+        "rtf" => "RTF",
+        "image" => "图片",
+        "files" => "文件",
+        other => other,
+    }
+}
+
+/// 文件名中不允许的字符替换为 `_`
+fn safe_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.');
+    if cleaned.is_empty() {
+        "_".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// 代码块围栏：比内容中最长的连续反引号多一个，至少三个
+fn fence_for(text: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn write_markdown(
+    items: &[ClipboardItem],
+    groups: &HashMap<i64, String>,
+    dir: &Path,
+    progress: &mut Progress,
+) -> Result<OpenExportSummary, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("创建导出目录失败: {e}"))?;
+    let mut media = MediaCopier::new(dir.join(MEDIA_DIR), MEDIA_DIR.to_string());
+    let mut files = Vec::new();
+    let mut done = 0;
+    for (name, section) in by_group(items, groups) {
+        let mut file_name = format!("{}.md", safe_file_name(name));
+        // 不同分组清理后重名时加序号
+        let mut suffix = 2;
+        while files.iter().any(|f: &PathBuf| f.ends_with(&file_name)) {
+            file_name = format!("{} ({suffix}).md", safe_file_name(name));
+            suffix += 1;
+        }
+        let path = dir.join(&file_name);
+        let mut doc = format!("# {name}\n\n");
+        for item in section {
+            write_markdown_item(&mut doc, item, &mut media)?;
+            done += 1;
+            progress(done)?;
+        }
+        fs::write(&path, doc).map_err(io_err)?;
+        files.push(path);
+    }
+    Ok(OpenExportSummary {
+        items: items.len(),
+        media: media.count(),
+        files,
+    })
+}
+
+fn write_markdown_item(
+    doc: &mut String,
+    item: &ClipboardItem,
+    media: &mut MediaCopier,
+) -> Result<(), String> {
+    let _ = writeln!(
+        doc,
+        "## {} · {}\n",
+        item.created_at,
+        type_label(&item.content_type)
+    );
+    let mut meta = Vec::new();
+    if item.is_pinned {
+        meta.push("置顶".to_string());
+    }
+    if item.is_favorite {
+        meta.push("收藏".to_string());
+    }
+    if let Some(app) = item.source_app_name.as_deref().filter(|a| !a.is_empty()) {
+        meta.push(format!("来源：{app}"));
+    }
+    if let Some(tags) = item.tags.as_deref().filter(|t| !t.is_empty()) {
+        let tags: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+        meta.push(tags.join(" "));
+    }
+    if !meta.is_empty() {
+        let _ = writeln!(doc, "*{}*\n", meta.join(" · "));
+    }
+
+    match item.content_type.as_str() {
+        "url" => {
+            let url = item.text_content.as_deref().unwrap_or_default().trim();
+            let _ = writeln!(doc, "<{url}>");
+        }
+        "image" => {
+            let rel = match item.image_path.as_deref() {
+                Some(path) => media.copy(path, &item.id.to_string())?,
+                None => None,
+            };
+            match rel {
+                Some(rel) => {
+                    let _ = writeln!(doc, "![图片]({rel})");
+                }
+                None => doc.push_str("*（图片文件缺失）*\n"),
+            }
+            if let Some(ocr) = item.ocr_text.as_deref().filter(|t| !t.trim().is_empty()) {
+                doc.push('\n');
+                for line in ocr.lines() {
+                    let _ = writeln!(doc, "> {line}");
+                }
+            }
+        }
+        "files" => {
+            for path in file_paths(item) {
+                let _ = writeln!(doc, "- `{path}`");
+            }
+        }
+        _ => {
+            let text = item
+                .text_content
+                .as_deref()
+                .or(item.preview.as_deref())
+                .unwrap_or_default();
+            let fence = fence_for(text);
+            let _ = writeln!(
+                doc,
+                "{fence}{}\n{}\n{fence}",
+                code_language(item),
+                text.trim_end()
+            );
+        }
+    }
+    doc.push('\n');
+    Ok(())
+}
+
+/// 图片缩略图（PNG data URI）；文件缺失或无法解码时返回 `None`
+fn thumbnail_data_uri(path: &str) -> Option<String> {
+    let image = image::open(path).ok()?;
+    let thumbnail = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    } else {
+        image
+    };
+    let mut png = Vec::new();
+    thumbnail
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .ok()?;
+    Some(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    ))
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,-apple-system,'Segoe UI',sans-serif;\
+max-width:880px;margin:2rem auto;padding:0 1rem;color:#222;background:#fafafa}\
+nav a{margin-right:.75rem}h2{margin-top:2rem;border-bottom:1px solid #ddd;padding-bottom:.25rem}\
+article{background:#fff;border:1px solid #e3e3e3;border-radius:8px;padding:.75rem 1rem;margin:.75rem 0}\
+.meta{font-size:.8rem;color:#777;margin-bottom:.5rem}.tag{background:#eef;border-radius:4px;padding:0 .3rem;margin-left:.3rem}\
+pre{white-space:pre-wrap;word-break:break-word;margin:0;font-family:ui-monospace,Consolas,monospace;font-size:.85rem}\
+img{max-width:100%;border-radius:4px}ul{margin:0;padding-left:1.25rem}\
+@media (prefers-color-scheme:dark){body{background:#1e1e1e;color:#ddd}article{background:#262626;border-color:#333}\
+h2{border-color:#333}.tag{background:#334}}";
+
+fn write_html(
+    items: &[ClipboardItem],
+    groups: &HashMap<i64, String>,
+    dest: &Path,
+    progress: &mut Progress,
+) -> Result<OpenExportSummary, String> {
+    let sections = by_group(items, groups);
+    let mut out = create_file(dest)?;
+    let mut thumbnails = 0;
+    let mut done = 0;
+
+    let mut head = format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>ElegantClipboard 导出</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+         <h1>ElegantClipboard 导出</h1>\n<p class=\"meta\">导出于 {}，共 {} 条</p>\n<nav>",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        items.len()
+    );
+    for (index, (name, section)) in sections.iter().enumerate() {
+        let _ = write!(
+            head,
+            "<a href=\"#g{index}\">{} ({})</a>",
+            escape_html(name),
+            section.len()
+        );
+    }
+    head.push_str("</nav>\n");
+    out.write_all(head.as_bytes()).map_err(io_err)?;
+
+    for (index, (name, section)) in sections.iter().enumerate() {
+        let mut html = format!("<h2 id=\"g{index}\">{}</h2>\n", escape_html(name));
+        for item in section {
+            thumbnails += usize::from(write_html_item(&mut html, item));
+            done += 1;
+            progress(done)?;
+        }
+        out.write_all(html.as_bytes()).map_err(io_err)?;
+    }
+    out.write_all(b"</body>\n</html>\n").map_err(io_err)?;
+    out.flush().map_err(io_err)?;
+    Ok(OpenExportSummary {
+        items: items.len(),
+        media: thumbnails,
+        files: vec![dest.to_path_buf()],
+    })
+}
+
+/// 写入一条条目，返回是否内嵌了缩略图。HTML 内容只输出纯文本，不执行原始标记
+fn write_html_item(html: &mut String, item: &ClipboardItem) -> bool {
+    let mut meta = format!(
+        "{} · {}",
+        escape_html(&item.created_at),
+        type_label(&item.content_type)
+    );
+    if item.is_pinned {
+        meta.push_str(" · 置顶");
+    }
+    if item.is_favorite {
+        meta.push_str(" · 收藏");
+    }
+    if let Some(app) = item.source_app_name.as_deref().filter(|a| !a.is_empty()) {
+        let _ = write!(meta, " · {}", escape_html(app));
+    }
+    for tag in item.tags.as_deref().unwrap_or_default() {
+        let _ = write!(meta, "<span class=\"tag\">#{}</span>", escape_html(tag));
+    }
+    let _ = write!(html, "<article><div class=\"meta\">{meta}</div>");
+
+    let mut embedded = false;
+    match item.content_type.as_str() {
+        "url" => {
+            let url = escape_html(item.text_content.as_deref().unwrap_or_default().trim());
+            let _ = write!(html, "<a href=\"{url}\">{url}</a>");
+        }
+        "image" => match item.image_path.as_deref().and_then(thumbnail_data_uri) {
+            Some(uri) => {
+                let _ = write!(html, "<img src=\"{uri}\" alt=\"图片\">");
+                embedded = true;
+            }
+            None => html.push_str("<p class=\"meta\">（图片文件缺失）</p>"),
+        },
+        "files" => {
+            html.push_str("<ul>");
+            for path in file_paths(item) {
+                let _ = write!(html, "<li><code>{}</code></li>", escape_html(&path));
+            }
+            html.push_str("</ul>");
+        }
+        _ => {
+            let text = item
+                .text_content
+                .as_deref()
+                .or(item.preview.as_deref())
+                .unwrap_or_default();
+            let _ = write!(html, "<pre>{}</pre>", escape_html(text));
+        }
+    }
+    html.push_str("</article>\n");
+    embedded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ContentType, NewClipboardItem};
+    use crate::test_support::temp_dir;
+
+    fn text(content: &str, hash: &str, group_id: Option<i64>) -> NewClipboardItem {
+        NewClipboardItem {
+            content_type: ContentType::Text,
+            text_content: Some(content.to_string()),
+            preview: Some(content.to_string()),
+            content_hash: hash.to_string(),
+            semantic_hash: hash.to_string(),
+            byte_size: content.len() as i64,
+            group_id,
+            ..Default::default()
+        }
+    }
+
+    /// 默认分组：一条文本、一张图片；“Work / 项目”分组：一条含反引号的代码
+    fn seed(dir: &Path) -> (Database, i64) {
+        let db = Database::new(dir.join("clipboard.db")).unwrap();
+        let work = GroupRepository::new(&db)
+            .create("Work / 项目", None)
+            .unwrap()
+            .id;
+        let repo = ClipboardRepository::new(&db);
+        repo.insert(text("hello <world>", "h1", None)).unwrap();
+        let png = dir.join("source.png");
+        image::RgbImage::from_pixel(640, 320, image::Rgb([0, 128, 255]))
+            .save(&png)
+            .unwrap();
+        repo.insert(NewClipboardItem {
+            content_type: ContentType::Image,
+            image_path: Some(png.to_string_lossy().into_owned()),
+            content_hash: "h2".into(),
+            semantic_hash: "h2".into(),
+            image_width: Some(640),
+            image_height: Some(320),
+            ..Default::default()
+        })
+        .unwrap();
+        repo.insert(NewClipboardItem {
+            subtypes: Some("code,lang:rust".into()),
+            ..text("let s = \"```\";", "h3", Some(work))
+        })
+        .unwrap();
+        (db, work)
+    }
+
+    #[test]
+    fn queries_with_list_filters_across_groups() {
+        let dir = temp_dir("query");
+        let (db, work) = seed(&dir);
+
+        let default_only = query_items(&db, &QueryOptions::default(), None).unwrap();
+        assert_eq!(default_only.len(), 2);
+        // 完整内容（列表查询不含 text_content）
+        assert!(
+            default_only
+                .iter()
+                .any(|i| i.text_content.as_deref() == Some("hello <world>"))
+        );
+
+        let options = QueryOptions {
+            content_type: Some("text".into()),
+            ..Default::default()
+        };
+        let texts = query_items(&db, &options, Some(&[None, Some(work)])).unwrap();
+        assert_eq!(texts.len(), 2);
+        assert!(texts.iter().all(|i| i.content_type == "text"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_jsonl_with_relative_media() {
+        let dir = temp_dir("jsonl");
+        let (db, work) = seed(&dir);
+        let items = query_items(&db, &QueryOptions::default(), Some(&[None, Some(work)])).unwrap();
+        let dest = dir.join("out").join("history.jsonl");
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let mut progress = Vec::new();
+        let summary = export(&db, OpenFormat::Jsonl, &items, &dest, |done, total| {
+            progress.push((done, total))
+        })
+        .unwrap();
+        assert_eq!((summary.items, summary.media), (3, 1));
+        assert_eq!(progress.last(), Some(&(3, 3)));
+
+        let lines: Vec<ClipboardItem> = fs::read_to_string(&dest)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        let image = lines.iter().find(|i| i.content_type == "image").unwrap();
+        let rel = image.image_path.as_deref().unwrap();
+        assert_eq!(rel, format!("history_media/{}.png", image.id));
+        assert!(dest.parent().unwrap().join(rel).is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_markdown_notebook_per_group() {
+        let dir = temp_dir("markdown");
+        let (db, work) = seed(&dir);
+        let items = query_items(&db, &QueryOptions::default(), Some(&[None, Some(work)])).unwrap();
+        let out = dir.join("notes");
+
+        let summary = export(&db, OpenFormat::Markdown, &items, &out, |_, _| {}).unwrap();
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!(summary.media, 1);
+
+        let default = fs::read_to_string(out.join("默认分组.md")).unwrap();
+        assert!(default.starts_with("# 默认分组\n"));
+        assert!(default.contains("```\nhello <world>\n```"));
+        assert!(default.contains("![图片](media/"));
+        // 分组名中的 `/` 不能出现在文件名里；代码块围栏长于内容中的反引号
+        let work_doc = fs::read_to_string(out.join("Work _ 项目.md")).unwrap();
+        assert!(work_doc.starts_with("# Work / 项目\n"));
+        assert!(work_doc.contains("````rust\nlet s = \"```\";\n````"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_self_contained_html_with_thumbnails() {
+        let dir = temp_dir("html");
+        let (db, work) = seed(&dir);
+        let items = query_items(&db, &QueryOptions::default(), Some(&[None, Some(work)])).unwrap();
+        let dest = dir.join("archive.html");
+
+        let summary = export(&db, OpenFormat::Html, &items, &dest, |_, _| {}).unwrap();
+        assert_eq!(summary.media, 1);
+        let html = fs::read_to_string(&dest).unwrap();
+        assert!(html.contains("<pre>hello &lt;world&gt;</pre>"));
+        assert!(html.contains("Work / 项目"));
+        let start = html.find("data:image/png;base64,").unwrap() + "data:image/png;base64,".len();
+        let end = start + html[start..].find('"').unwrap();
+        let png = base64::engine::general_purpose::STANDARD
+            .decode(&html[start..end])
+            .unwrap();
+        let thumbnail = image::load_from_memory(&png).unwrap();
+        assert_eq!(
+            (thumbnail.width(), thumbnail.height()),
+            (THUMBNAIL_SIZE, 160)
+        );
+        // 不引用任何外部资源
+        assert!(!html.contains("src=\"http") && !html.contains("src=\"file"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,835 @@
+//! 存储完整性检查：对照数据库引用扫描 blob 存储、旧版媒体目录与截图临时目录，
+//! 报告孤儿文件、悬空引用与哈希不一致，可选修复。
+//!
+//! 修复内容：删除孤儿文件；哈希不一致的对象按实际内容重新存入并改写条目路径；
+//! 清除缺失的图标与 staging 副本引用；图片缺失的条目标记 `media_missing`；按 `item_blobs` 校正引用数。
+
+use super::{BlobKind, BlobStore, ORPHAN_GRACE_MINUTES, hash_file, hash_from_path};
+use crate::clipboard::{dir_snapshot, file_clipboard};
+use crate::database::{BlobRecord, BlobRepository, ClipboardItem, ClipboardRepository, Database};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// 迁入 blob 存储前的媒体目录
+const LEGACY_DIRS: &[&str] = &["images", "icons", "staged"];
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct CheckOptions {
+    /// 执行修复（否则只报告）
+    #[serde(default)]
+    pub repair: bool,
+    /// 重新计算每个 blob 的内容哈希（较慢）
+    #[serde(default)]
+    pub verify_hashes: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckProgress {
+    /// `items` / `objects` / `legacy` / `repair`
+    pub phase: &'static str,
+    pub done: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Orphan {
+    pub path: String,
+    pub size: u64,
+    /// `blob`（未登记或已释放的对象）/ `temp`（中断写入的临时文件）/ `legacy`（旧版目录）/ `capture`
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DanglingRef {
+    pub item_id: i64,
+    /// `image` / `icon` / `staged`
+    pub field: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HashMismatch {
+    pub hash: String,
+    pub path: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepairSummary {
+    pub orphans_deleted: usize,
+    pub bytes_freed: u64,
+    pub rehashed: usize,
+    pub refs_cleared: usize,
+    pub items_marked_missing: usize,
+    pub ref_counts_fixed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub checked_at: String,
+    pub duration_ms: u64,
+    pub objects: usize,
+    pub items: usize,
+    pub orphans: Vec<Orphan>,
+    pub orphan_bytes: u64,
+    pub dangling: Vec<DanglingRef>,
+    pub mismatches: Vec<HashMismatch>,
+    pub ref_count_drift: usize,
+    pub hashes_verified: bool,
+    pub repair: Option<RepairSummary>,
+}
+
+/// 执行检查（同一时间只允许一个检查运行）；`on_progress` 在每个条目/对象处理后回调
+pub fn run(
+    db: &Database,
+    store: &BlobStore,
+    data_dir: &Path,
+    options: CheckOptions,
+    mut on_progress: impl FnMut(CheckProgress),
+) -> Result<CheckReport, String> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("Storage check is already running".to_string());
+    }
+    let started = Instant::now();
+    let result = Checker::new(db, store, data_dir).run(options, &mut on_progress);
+    RUNNING.store(false, Ordering::SeqCst);
+
+    let mut report = result?;
+    report.duration_ms = started.elapsed().as_millis() as u64;
+    info!(
+        "Storage check finished in {}ms: {} orphan(s), {} dangling ref(s), {} hash mismatch(es)",
+        report.duration_ms,
+        report.orphans.len(),
+        report.dangling.len(),
+        report.mismatches.len()
+    );
+    Ok(report)
+}
+
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+struct Checker<'a> {
+    repo: ClipboardRepository,
+    blob_repo: BlobRepository,
+    store: &'a BlobStore,
+    data_dir: &'a Path,
+    grace: Duration,
+}
+
+impl<'a> Checker<'a> {
+    fn new(db: &Database, store: &'a BlobStore, data_dir: &'a Path) -> Self {
+        Self {
+            repo: ClipboardRepository::new(db),
+            blob_repo: BlobRepository::new(db),
+            store,
+            data_dir,
+            grace: Duration::from_secs(ORPHAN_GRACE_MINUTES as u64 * 60),
+        }
+    }
+
+    fn run(
+        &self,
+        options: CheckOptions,
+        on_progress: &mut impl FnMut(CheckProgress),
+    ) -> Result<CheckReport, String> {
+        let items = self.repo.query_media_items().map_err(|e| e.to_string())?;
+        let records: HashMap<String, BlobRecord> = self
+            .blob_repo
+            .list_all(ORPHAN_GRACE_MINUTES)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|r| (r.hash.clone(), r))
+            .collect();
+
+        let mut report = CheckReport {
+            checked_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            items: items.len(),
+            hashes_verified: options.verify_hashes,
+            ..Default::default()
+        };
+
+        let referenced = self.check_items(&items, &mut report, on_progress);
+        let mut blob_orphans = self.check_objects(&records, options, &mut report, on_progress);
+        self.check_legacy(&referenced, &mut report, on_progress);
+        report.ref_count_drift = self.blob_repo.count_ref_drift().unwrap_or(0);
+        report.orphans.append(&mut blob_orphans);
+        report.orphan_bytes = report.orphans.iter().map(|o| o.size).sum();
+
+        if options.repair {
+            report.repair = Some(self.repair(&items, &records, &report, on_progress));
+        }
+        Ok(report)
+    }
+
+    /// 旧版相对图片路径相对于 `images/`
+    fn resolve(&self, path: &str) -> PathBuf {
+        let p = Path::new(path);
+        if p.is_absolute() {
+            p.to_path_buf()
+        } else {
+            self.data_dir.join("images").join(p)
+        }
+    }
+
+    /// 检查条目引用的媒体文件是否存在，返回被引用的路径集合
+    fn check_items(
+        &self,
+        items: &[ClipboardItem],
+        report: &mut CheckReport,
+        on_progress: &mut impl FnMut(CheckProgress),
+    ) -> HashSet<PathBuf> {
+        let mut referenced = HashSet::new();
+        for (index, item) in items.iter().enumerate() {
+            for (field, path) in media_refs(item) {
+                let resolved = self.resolve(&path);
+                if !resolved.exists() {
+                    report.dangling.push(DanglingRef {
+                        item_id: item.id,
+                        field: field.to_string(),
+                        path,
+                    });
+                }
+                referenced.insert(resolved);
+            }
+            on_progress(CheckProgress {
+                phase: "items",
+                done: index + 1,
+                total: items.len(),
+            });
+        }
+        referenced
+    }
+
+    /// 扫描 blob 根目录：未登记或已释放的对象、过期临时文件为孤儿；可选校验内容哈希
+    fn check_objects(
+        &self,
+        records: &HashMap<String, BlobRecord>,
+        options: CheckOptions,
+        report: &mut CheckReport,
+        on_progress: &mut impl FnMut(CheckProgress),
+    ) -> Vec<Orphan> {
+        let mut orphans = Vec::new();
+        let mut objects = Vec::new();
+        for entry in read_dir_paths(self.store.root()) {
+            let name = file_name(&entry);
+            let is_prefix = entry.is_dir() && name.len() == 2;
+            if !is_prefix {
+                self.push_if_stale(&mut orphans, &entry, "temp");
+                continue;
+            }
+            for object in read_dir_paths(&entry) {
+                let hash = file_name(&object);
+                if object.is_dir() && hash.len() == 64 && hash.starts_with(&name) {
+                    objects.push((hash, object));
+                } else {
+                    self.push_if_stale(&mut orphans, &object, "temp");
+                }
+            }
+        }
+
+        report.objects = objects.len();
+        for (index, (hash, dir)) in objects.iter().enumerate() {
+            match records.get(hash) {
+                // 未登记的对象可能正在存入（登记晚于本次读取记录），同样按宽限期判断
+                None => self.push_if_stale(&mut orphans, dir, "blob"),
+                Some(record) if record.ref_count <= 0 && !record.recent => {
+                    orphans.push(orphan(dir, "blob"));
+                }
+                Some(record) => {
+                    for path in read_dir_paths(dir) {
+                        if is_temp_name(&file_name(&path)) {
+                            self.push_if_stale(&mut orphans, &path, "temp");
+                        }
+                    }
+                    if options.verify_hashes {
+                        report.mismatches.extend(verify_object(hash, dir, record));
+                    }
+                }
+            }
+            on_progress(CheckProgress {
+                phase: "objects",
+                done: index + 1,
+                total: objects.len(),
+            });
+        }
+        orphans
+    }
+
+    /// 旧版目录中未被引用的文件，以及过期的截图临时文件
+    fn check_legacy(
+        &self,
+        referenced: &HashSet<PathBuf>,
+        report: &mut CheckReport,
+        on_progress: &mut impl FnMut(CheckProgress),
+    ) {
+        let capture_dir = self.data_dir.join("images").join(super::CAPTURE_DIR);
+        for (index, dir) in LEGACY_DIRS.iter().enumerate() {
+            let mut stack = vec![self.data_dir.join(dir)];
+            while let Some(current) = stack.pop() {
+                for path in read_dir_paths(&current) {
+                    if path == capture_dir {
+                        continue;
+                    }
+                    if path.is_dir() {
+                        stack.push(path);
+                    } else if !referenced.contains(&path) {
+                        self.push_if_stale(&mut report.orphans, &path, "legacy");
+                    }
+                }
+            }
+            on_progress(CheckProgress {
+                phase: "legacy",
+                done: index + 1,
+                total: LEGACY_DIRS.len() + 1,
+            });
+        }
+        for path in read_dir_paths(&capture_dir) {
+            self.push_if_stale(&mut report.orphans, &path, "capture");
+        }
+        on_progress(CheckProgress {
+            phase: "legacy",
+            done: LEGACY_DIRS.len() + 1,
+            total: LEGACY_DIRS.len() + 1,
+        });
+    }
+
+    /// 超过宽限期未修改的文件才算孤儿，避免误删正在写入的临时文件；
+    /// 目录按其中最新的文件计（空目录按自身）
+    fn push_if_stale(&self, orphans: &mut Vec<Orphan>, path: &Path, reason: &str) {
+        let modified = |p: &Path| std::fs::symlink_metadata(p).and_then(|m| m.modified()).ok();
+        let newest = if path.is_dir() {
+            read_dir_paths(path)
+                .iter()
+                .filter_map(|p| modified(p))
+                .max()
+                .or_else(|| modified(path))
+        } else {
+            modified(path)
+        };
+        let stale = newest
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age >= self.grace);
+        if stale {
+            orphans.push(orphan(path, reason));
+        }
+    }
+
+    fn repair(
+        &self,
+        items: &[ClipboardItem],
+        records: &HashMap<String, BlobRecord>,
+        report: &CheckReport,
+        on_progress: &mut impl FnMut(CheckProgress),
+    ) -> RepairSummary {
+        let mut summary = RepairSummary::default();
+        let total = items.len() + report.mismatches.len() + report.orphans.len();
+        let mut done = 0;
+        let mut step = || {
+            done += 1;
+            on_progress(CheckProgress {
+                phase: "repair",
+                done,
+                total,
+            });
+        };
+
+        // 悬空引用：清除图标与 staging 副本引用，图片缺失时标记条目
+        let dangling: HashMap<i64, Vec<&DanglingRef>> =
+            report.dangling.iter().fold(HashMap::new(), |mut map, d| {
+                map.entry(d.item_id).or_default().push(d);
+                map
+            });
+        for item in items {
+            let refs = dangling
+                .get(&item.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            match self.repair_item(item, refs) {
+                Ok((cleared, marked)) => {
+                    summary.refs_cleared += cleared;
+                    summary.items_marked_missing += usize::from(marked);
+                }
+                Err(e) => warn!("Failed to repair media refs of item {}: {}", item.id, e),
+            }
+            step();
+        }
+
+        // 哈希不一致：按实际内容重新存入，条目改指新对象，旧对象引用归零后被清理
+        let mut rehoused: HashMap<&str, HashMap<String, String>> = HashMap::new();
+        for mismatch in &report.mismatches {
+            let Some(kind) = records
+                .get(&mismatch.hash)
+                .and_then(|r| BlobKind::parse(&r.kind))
+                .filter(|k| *k != BlobKind::Dir)
+            else {
+                step();
+                continue;
+            };
+            let path = Path::new(&mismatch.path);
+            match self.store.put_file(path, &file_name(path), kind) {
+                Ok(blob) => {
+                    rehoused
+                        .entry(mismatch.hash.as_str())
+                        .or_default()
+                        .insert(file_name(path), blob.path.to_string_lossy().to_string());
+                    summary.rehashed += 1;
+                }
+                Err(e) => warn!("Failed to re-hash {}: {}", mismatch.path, e),
+            }
+            step();
+        }
+        for (hash, targets) in &rehoused {
+            self.repoint_items(hash, targets);
+        }
+
+        for item in items {
+            let _ = self.repo.resync_blob_refs(item.id);
+        }
+        summary.ref_counts_fixed = self.blob_repo.fix_ref_counts().unwrap_or(0);
+
+        for orphan in &report.orphans {
+            if self.delete_orphan(orphan) {
+                summary.orphans_deleted += 1;
+                summary.bytes_freed += orphan.size;
+            }
+            step();
+        }
+        self.store.sweep();
+        summary
+    }
+
+    /// 返回（清除的引用数，是否新标记为媒体缺失）
+    fn repair_item(
+        &self,
+        item: &ClipboardItem,
+        refs: &[&DanglingRef],
+    ) -> Result<(usize, bool), rusqlite::Error> {
+        let missing =
+            |field: &str, path: &str| refs.iter().any(|d| d.field == field && d.path == path);
+        let image_missing = item
+            .image_path
+            .as_deref()
+            .is_some_and(|p| missing("image", p));
+        let icon = item
+            .source_app_icon
+            .as_deref()
+            .filter(|p| !missing("icon", p));
+        let icon_cleared = icon.is_none() && item.source_app_icon.is_some();
+
+        let mut payload = file_clipboard::decode_payload(item.file_payload.as_deref());
+        let mut staged_cleared = 0;
+        if let Some(payload) = payload.as_mut() {
+            let before = payload.staged.len();
+            payload.staged.retain(|s| !missing("staged", &s.staged));
+            staged_cleared = before - payload.staged.len();
+        }
+
+        if icon_cleared || staged_cleared > 0 {
+            let payload = payload
+                .filter(|_| staged_cleared > 0)
+                .map(|p| file_clipboard::encode_payload(&p));
+            self.repo.update_item_media_paths(
+                item.id,
+                item.image_path.as_deref(),
+                payload.as_deref().or(item.file_payload.as_deref()),
+                icon,
+            )?;
+            // 副本已丢失，可通过重试 staging 重新复制
+            if staged_cleared > 0 && item.staging_state.is_some() {
+                self.repo.set_staging_state(item.id, Some("partial"))?;
+            }
+        }
+        if image_missing != item.media_missing {
+            self.repo.set_media_missing(item.id, image_missing)?;
+        }
+        Ok((
+            usize::from(icon_cleared) + staged_cleared,
+            image_missing && !item.media_missing,
+        ))
+    }
+
+    /// 把引用旧对象的条目路径改为重新存入后的对象路径（按文件名对应）
+    fn repoint_items(&self, old_hash: &str, targets: &HashMap<String, String>) {
+        let ids = self
+            .blob_repo
+            .referencing_items(old_hash)
+            .unwrap_or_default();
+        let rewrite = |path: &str| -> Option<String> {
+            (hash_from_path(path) == Some(old_hash))
+                .then(|| targets.get(&file_name(Path::new(path))).cloned())
+                .flatten()
+        };
+        for id in ids {
+            let Ok(Some(item)) = self.repo.get_by_id(id) else {
+                continue;
+            };
+            let image = item.image_path.as_deref().and_then(rewrite);
+            let icon = item.source_app_icon.as_deref().and_then(rewrite);
+            let mut payload = file_clipboard::decode_payload(item.file_payload.as_deref());
+            let mut payload_changed = false;
+            for staged in payload.iter_mut().flat_map(|p| p.staged.iter_mut()) {
+                if let Some(target) = rewrite(&staged.staged) {
+                    staged.staged = target;
+                    payload_changed = true;
+                }
+            }
+            if image.is_none() && icon.is_none() && !payload_changed {
+                continue;
+            }
+            let payload = payload
+                .filter(|_| payload_changed)
+                .map(|p| file_clipboard::encode_payload(&p));
+            if let Err(e) = self.repo.update_item_media_paths(
+                id,
+                image.as_deref().or(item.image_path.as_deref()),
+                payload.as_deref().or(item.file_payload.as_deref()),
+                icon.as_deref().or(item.source_app_icon.as_deref()),
+            ) {
+                warn!("Failed to repoint media of item {}: {}", id, e);
+            }
+        }
+    }
+
+    /// 删除前再次确认 blob 未被引用（检查期间可能重新存入）
+    fn delete_orphan(&self, orphan: &Orphan) -> bool {
+        let path = Path::new(&orphan.path);
+        if orphan.reason == "blob" {
+            let hash = file_name(path);
+            let unreferenced = match self.blob_repo.ref_count(&hash) {
+                Ok(None) => true,
+                Ok(Some(_)) => matches!(
+                    self.blob_repo
+                        .delete_unreferenced(&hash, ORPHAN_GRACE_MINUTES),
+                    Ok(true)
+                ),
+                Err(_) => false,
+            };
+            if !unreferenced {
+                return false;
+            }
+        }
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        match removed {
+            Ok(()) => {
+                if orphan.reason == "blob"
+                    && let Some(prefix_dir) = path.parent()
+                {
+                    let _ = std::fs::remove_dir(prefix_dir);
+                }
+                true
+            }
+            Err(e) => {
+                warn!("Failed to delete orphan {}: {}", orphan.path, e);
+                false
+            }
+        }
+    }
+}
+
+/// 条目引用的媒体路径：`(字段, 路径)`
+fn media_refs(item: &ClipboardItem) -> Vec<(&'static str, String)> {
+    let mut refs = Vec::new();
+    if let Some(path) = item.image_path.as_deref().filter(|p| !p.is_empty()) {
+        refs.push(("image", path.to_string()));
+    }
+    if let Some(path) = item.source_app_icon.as_deref().filter(|p| !p.is_empty()) {
+        refs.push(("icon", path.to_string()));
+    }
+    for path in file_clipboard::staged_paths_from_payload(item.file_payload.as_deref()) {
+        refs.push(("staged", path));
+    }
+    refs
+}
+
+/// 校验对象目录内每个文件（或目录快照）的内容哈希
+fn verify_object(hash: &str, dir: &Path, record: &BlobRecord) -> Vec<HashMismatch> {
+    let mut mismatches = Vec::new();
+    for path in read_dir_paths(dir) {
+        let name = file_name(&path);
+        if is_temp_name(&name) {
+            continue;
+        }
+        let actual = if path.is_dir() {
+            // ZIP 快照解压出的文件夹不参与校验
+            if record.kind != BlobKind::Dir.as_str() || dir.join(format!("{name}.zip")).is_file() {
+                continue;
+            }
+            dir_snapshot::tree_hash(&path)
+        } else {
+            hash_file(&path).map(|(actual, _)| actual)
+        };
+        match actual {
+            Ok(actual) if actual != hash => mismatches.push(HashMismatch {
+                hash: hash.to_string(),
+                path: path.to_string_lossy().to_string(),
+                actual,
+            }),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to hash {:?}: {}", path, e),
+        }
+    }
+    mismatches
+}
+
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn is_temp_name(name: &str) -> bool {
+    name.starts_with('.') && name.ends_with(".tmp")
+}
+
+fn orphan(path: &Path, reason: &str) -> Orphan {
+    Orphan {
+        path: path.to_string_lossy().to_string(),
+        size: path_size(path),
+        reason: reason.to_string(),
+    }
+}
+
+fn path_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    read_dir_paths(path).iter().map(|p| path_size(p)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::object_path;
+    use super::super::tests::setup;
+    use super::*;
+    use crate::database::{ContentType, NewClipboardItem};
+
+    /// 检查全局互斥，测试串行执行
+    static SERIAL: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+
+    /// 把文件修改时间推到宽限期之前
+    fn age(path: &Path) {
+        let old = std::time::SystemTime::now() - Duration::from_secs(2 * 3600);
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+    }
+
+    fn image_item(path: &Path, hash: &str) -> NewClipboardItem {
+        NewClipboardItem {
+            content_type: ContentType::Image,
+            image_path: Some(path.to_string_lossy().to_string()),
+            content_hash: hash.to_string(),
+            semantic_hash: hash.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_and_repairs_storage_problems() {
+        let _serial = SERIAL.lock();
+        let (dir, db, store) = setup("repair");
+        let repo = ClipboardRepository::new(&db);
+
+        // 正常条目 + 内容被篡改的条目 + 图片已丢失的条目
+        let good = store
+            .put_bytes(b"good", "image.png", BlobKind::Image)
+            .unwrap();
+        let tampered = store
+            .put_bytes(b"tampered", "image.png", BlobKind::Image)
+            .unwrap();
+        std::fs::write(&tampered.path, b"changed on disk").unwrap();
+        let good_id = repo.insert(image_item(&good.path, "g")).unwrap();
+        let tampered_id = repo.insert(image_item(&tampered.path, "t")).unwrap();
+        let lost_id = repo
+            .insert(image_item(&dir.join("images").join("lost.png"), "l"))
+            .unwrap();
+
+        // 未登记的对象、过期临时文件、旧版目录中未被引用的文件
+        let stray = object_path(store.root(), &"ab".repeat(32), "x.bin");
+        std::fs::create_dir_all(stray.parent().unwrap()).unwrap();
+        std::fs::write(&stray, b"stray").unwrap();
+        age(&stray);
+        // 刚写入、尚未登记的对象（存入进行中）不算孤儿
+        let writing = object_path(store.root(), &"cd".repeat(32), "y.bin");
+        std::fs::create_dir_all(writing.parent().unwrap()).unwrap();
+        std::fs::write(&writing, b"writing").unwrap();
+        let temp = store.root().join(".deadbeef.tmp");
+        std::fs::write(&temp, b"tmp").unwrap();
+        age(&temp);
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        let legacy = dir.join("icons").join("old.png");
+        std::fs::write(&legacy, b"old").unwrap();
+        age(&legacy);
+        let fresh = store.root().join(".fresh.tmp");
+        std::fs::write(&fresh, b"writing").unwrap();
+
+        let report = run(
+            &db,
+            &store,
+            &dir,
+            CheckOptions {
+                repair: false,
+                verify_hashes: true,
+            },
+            |_| {},
+        )
+        .unwrap();
+        let mut reasons: Vec<_> = report.orphans.iter().map(|o| o.reason.as_str()).collect();
+        reasons.sort_unstable();
+        assert_eq!(reasons, vec!["blob", "legacy", "temp"]);
+        assert_eq!(report.dangling.len(), 1);
+        assert_eq!(report.dangling[0].item_id, lost_id);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].hash, tampered.hash);
+        assert!(report.repair.is_none());
+        assert!(stray.exists(), "check-only run changes nothing");
+
+        let mut phases = HashSet::new();
+        let report = run(
+            &db,
+            &store,
+            &dir,
+            CheckOptions {
+                repair: true,
+                verify_hashes: true,
+            },
+            |p| {
+                phases.insert(p.phase);
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            phases,
+            HashSet::from(["items", "objects", "legacy", "repair"])
+        );
+        let summary = report.repair.unwrap();
+        assert_eq!(summary.orphans_deleted, 3);
+        assert_eq!(summary.rehashed, 1);
+        assert_eq!(summary.items_marked_missing, 1);
+        assert!(!stray.exists() && !temp.exists() && !legacy.exists());
+        assert!(fresh.exists(), "files still being written are kept");
+        assert!(
+            writing.exists(),
+            "unregistered objects within the grace period are kept"
+        );
+        assert!(good.path.exists());
+
+        let repaired = repo.get_by_id(tampered_id).unwrap().unwrap();
+        let new_path = repaired.image_path.unwrap();
+        assert_ne!(new_path, tampered.path.to_string_lossy());
+        assert_eq!(
+            hash_from_path(&new_path),
+            Some(blake3::hash(b"changed on disk").to_hex().as_str())
+        );
+        assert!(!tampered.path.exists(), "old object is swept");
+        assert!(repo.get_by_id(lost_id).unwrap().unwrap().media_missing);
+        assert!(!repo.get_by_id(good_id).unwrap().unwrap().media_missing);
+
+        // 修复后再次检查无问题（缺失的图片仍报告，但不再重复标记）
+        let again = run(
+            &db,
+            &store,
+            &dir,
+            CheckOptions {
+                repair: true,
+                verify_hashes: true,
+            },
+            |_| {},
+        )
+        .unwrap();
+        assert!(again.orphans.is_empty());
+        assert!(again.mismatches.is_empty());
+        assert_eq!(again.ref_count_drift, 0);
+        assert_eq!(again.repair.unwrap().items_marked_missing, 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clears_missing_staged_copies_and_icons() {
+        let _serial = SERIAL.lock();
+        let (dir, db, store) = setup("staged");
+        let repo = ClipboardRepository::new(&db);
+        let kept = store.put_bytes(b"kept", "a.txt", BlobKind::File).unwrap();
+        let payload = file_clipboard::FilePayload {
+            staged: vec![
+                file_clipboard::StagedFile {
+                    original: "/gone/a.txt".into(),
+                    staged: kept.path.to_string_lossy().to_string(),
+                    size: 4,
+                    snapshot: None,
+                },
+                file_clipboard::StagedFile {
+                    original: "/gone/b.txt".into(),
+                    staged: dir.join("missing.txt").to_string_lossy().to_string(),
+                    size: 1,
+                    snapshot: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let id = repo
+            .insert(NewClipboardItem {
+                content_type: ContentType::Files,
+                file_paths: Some(vec!["/gone/a.txt".into(), "/gone/b.txt".into()]),
+                file_payload: Some(file_clipboard::encode_payload(&payload)),
+                source_app_icon: Some(dir.join("icon.png").to_string_lossy().to_string()),
+                content_hash: "f".into(),
+                semantic_hash: "f".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        repo.set_staging_state(id, Some("done")).unwrap();
+
+        let report = run(
+            &db,
+            &store,
+            &dir,
+            CheckOptions {
+                repair: true,
+                verify_hashes: false,
+            },
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(report.dangling.len(), 2);
+        assert_eq!(report.repair.unwrap().refs_cleared, 2);
+
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.source_app_icon, None);
+        assert_eq!(item.staging_state.as_deref(), Some("partial"));
+        assert_eq!(
+            file_clipboard::staged_paths_from_payload(item.file_payload.as_deref()),
+            vec![kept.path.to_string_lossy().to_string()]
+        );
+        assert!(!item.media_missing);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,776 @@
+//! 内容寻址 blob 存储：图片、应用图标与 staging 文件按 BLAKE3 哈希存放，相同内容只存一份。
+//!
+//! 布局为 `blobs/<hash 前 2 位>/<hash>/<文件名>`。目录按内容寻址，目录内保留文件名，
+//! 粘贴 staging 文件时目标程序看到的仍是原文件名；同一内容以不同文件名存入时在目录内建硬链接。
+//!
+//! 引用计数保存在 SQLite：条目写入媒体路径时记录到 `item_blobs`，由触发器维护 `blobs.ref_count`；
+//! 条目删除时级联释放。引用数归零的 blob 由 [`BlobStore::sweep`] 从磁盘删除。
+
+pub mod fsck;
+
+use crate::clipboard::file_clipboard;
+use crate::database::{BlobRepository, ClipboardRepository, Database, SettingsRepository};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::{debug, info, warn};
+
+/// 数据目录下的 blob 根目录名
+pub const DIR_NAME: &str = "blobs";
+/// 新捕获图片在 blob 目录内的文件名
+pub const IMAGE_NAME: &str = "image.png";
+
+const MIGRATION_SETTING: &str = "_migration_blob_store";
+/// 截图临时目录（位于 `images/` 下）
+pub(crate) const CAPTURE_DIR: &str = "captures";
+/// 存入后一直未被引用的 blob（如条目写入失败）保留的时长
+const ORPHAN_GRACE_MINUTES: i64 = 60;
+const COPY_CHUNK_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobKind {
+    Image,
+    Icon,
+    File,
+    /// 目录快照（逐文件复制的目录树或 ZIP 压缩包）
+    Dir,
+}
+
+impl BlobKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Image => "image",
+            Self::Icon => "icon",
+            Self::File => "file",
+            Self::Dir => "dir",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "image" => Some(Self::Image),
+            "icon" => Some(Self::Icon),
+            "file" => Some(Self::File),
+            "dir" => Some(Self::Dir),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredBlob {
+    pub hash: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+static STORE: OnceLock<BlobStore> = OnceLock::new();
+
+/// 初始化进程内共享的 blob 存储（供删除条目后的清理使用）
+pub fn init(db: &Database, data_dir: &Path) -> &'static BlobStore {
+    STORE.get_or_init(|| BlobStore::new(db, data_dir.join(DIR_NAME)))
+}
+
+pub fn global() -> Option<&'static BlobStore> {
+    STORE.get()
+}
+
+/// blob 对象在 `root` 下的路径
+pub fn object_path(root: &Path, hash: &str, name: &str) -> PathBuf {
+    root.join(&hash[..2.min(hash.len())]).join(hash).join(name)
+}
+
+/// 从 blob 对象路径中解析哈希（`…/blobs/<h2>/<hash>/<name>`，兼容 Windows / Unix 分隔符）
+pub fn hash_from_path(path: &str) -> Option<&str> {
+    let mut parts = path.rsplit(['/', '\\']);
+    let _name = parts.next().filter(|n| !n.is_empty())?;
+    let hash = parts.next()?;
+    let prefix = parts.next()?;
+    let root = parts.next()?;
+    let valid = root == DIR_NAME
+        && hash.len() == 64
+        && hash.bytes().all(|b| b.is_ascii_hexdigit())
+        && hash.starts_with(prefix)
+        && prefix.len() == 2;
+    valid.then_some(hash)
+}
+
+/// 文件名去掉路径分隔符等非法字符，空名回退为 `blob`
+pub(crate) fn safe_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match cleaned.trim_matches('.') {
+        "" => "blob".to_string(),
+        _ => cleaned,
+    }
+}
+
+/// 复制被进度回调中止（如取消 staging）时的错误，用 [`is_copy_cancelled`] 判断
+#[derive(Debug)]
+pub struct CopyCancelled;
+
+impl std::fmt::Display for CopyCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("copy cancelled")
+    }
+}
+
+impl std::error::Error for CopyCancelled {}
+
+/// 错误是否为 [`CopyCancelled`]（与系统调用被中断的 `ErrorKind::Interrupted` 区分）
+pub fn is_copy_cancelled(err: &io::Error) -> bool {
+    err.get_ref()
+        .is_some_and(|inner| inner.is::<CopyCancelled>())
+}
+
+/// 边复制边计算哈希（单次读取），每写入一块回调已写入字节数；回调返回 false 时中止，
+/// 返回 [`CopyCancelled`]
+pub(crate) fn copy_hashed(
+    reader: &mut impl io::Read,
+    writer: &mut impl io::Write,
+    mut on_progress: impl FnMut(u64) -> bool,
+) -> io::Result<(String, u64)> {
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0u8; COPY_CHUNK_BYTES];
+    let mut size = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        writer.write_all(&buf[..n])?;
+        size += n as u64;
+        if !on_progress(size) {
+            return Err(io::Error::other(CopyCancelled));
+        }
+    }
+    writer.flush()?;
+    Ok((hasher.finalize().to_hex().to_string(), size))
+}
+
+/// 删除临时文件或临时目录
+fn remove_temp(path: &Path) {
+    if path.is_dir() {
+        let _ = std::fs::remove_dir_all(path);
+    } else {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn hash_file(path: &Path) -> io::Result<(String, u64)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let size = io::copy(&mut file, &mut hasher)?;
+    Ok((hasher.finalize().to_hex().to_string(), size))
+}
+
+pub struct BlobStore {
+    root: PathBuf,
+    repo: BlobRepository,
+}
+
+impl BlobStore {
+    pub fn new(db: &Database, root: PathBuf) -> Self {
+        std::fs::create_dir_all(&root).ok();
+        Self {
+            root,
+            repo: BlobRepository::new(db),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 复制文件存入（源文件保留）
+    pub fn put_file(&self, src: &Path, name: &str, kind: BlobKind) -> io::Result<StoredBlob> {
+        self.put_file_with_progress(src, name, kind, |_| true)
+    }
+
+    /// 边复制边计算哈希（单次读取），见 [`copy_hashed`]
+    pub fn put_file_with_progress(
+        &self,
+        src: &Path,
+        name: &str,
+        kind: BlobKind,
+        on_progress: impl FnMut(u64) -> bool,
+    ) -> io::Result<StoredBlob> {
+        let tmp = self.temp_path()?;
+        let copied = std::fs::File::open(src).and_then(|mut reader| {
+            let mut writer = std::fs::File::create(&tmp)?;
+            copy_hashed(&mut reader, &mut writer, on_progress)
+        });
+        let result = copied.and_then(|(hash, size)| {
+            self.store(&hash, size, name, kind, |dst| std::fs::rename(&tmp, dst))
+        });
+        // 内容已存在（未用到临时文件）或出错时清理
+        let _ = std::fs::remove_file(&tmp);
+        result
+    }
+
+    /// 存入调用方在 [`Self::temp_path`] 准备好的目录，`hash` 由调用方按目录内容计算；
+    /// 临时目录被移走或删除
+    pub fn put_dir(&self, tmp: &Path, hash: &str, size: u64, name: &str) -> io::Result<StoredBlob> {
+        let result = self.store(hash, size, name, BlobKind::Dir, |dst| {
+            std::fs::rename(tmp, dst)
+        });
+        remove_temp(tmp);
+        result
+    }
+
+    /// blob 根目录下的临时路径（与对象同盘，可直接 rename）
+    pub fn temp_path(&self) -> io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.root)?;
+        Ok(self
+            .root
+            .join(format!(".{}.tmp", uuid::Uuid::new_v4().simple())))
+    }
+
+    /// 移动文件存入（源文件被移走或删除），用于捕获的临时图片等临时文件
+    pub fn put_moved(&self, src: &Path, name: &str, kind: BlobKind) -> io::Result<StoredBlob> {
+        let (hash, size) = hash_file(src)?;
+        let blob = self.store(&hash, size, name, kind, |tmp| {
+            std::fs::rename(src, tmp).or_else(|_| std::fs::copy(src, tmp).map(|_| ()))
+        })?;
+        if src.exists() {
+            let _ = std::fs::remove_file(src);
+        }
+        Ok(blob)
+    }
+
+    pub fn put_bytes(&self, data: &[u8], name: &str, kind: BlobKind) -> io::Result<StoredBlob> {
+        let hash = blake3::hash(data).to_hex().to_string();
+        self.store(&hash, data.len() as u64, name, kind, |tmp| {
+            std::fs::write(tmp, data)
+        })
+    }
+
+    /// 从流读入并校验内容哈希（如同步下载的媒体），哈希不符时不存入，
+    /// 返回 [`io::ErrorKind::InvalidData`]
+    pub fn put_verified(
+        &self,
+        reader: &mut impl io::Read,
+        expected_hash: &str,
+        name: &str,
+        kind: BlobKind,
+    ) -> io::Result<StoredBlob> {
+        let tmp = self.temp_path()?;
+        let copied = std::fs::File::create(&tmp)
+            .and_then(|mut writer| copy_hashed(reader, &mut writer, |_| true));
+        let result = copied.and_then(|(hash, size)| {
+            if hash != expected_hash {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("hash mismatch: expected {expected_hash}, got {hash}"),
+                ));
+            }
+            self.store(&hash, size, name, kind, |dst| std::fs::rename(&tmp, dst))
+        });
+        let _ = std::fs::remove_file(&tmp);
+        result
+    }
+
+    /// 写入对象：内容已存在时只补建文件名（硬链接，失败时复制）
+    fn store(
+        &self,
+        hash: &str,
+        size: u64,
+        name: &str,
+        kind: BlobKind,
+        write: impl FnOnce(&Path) -> io::Result<()>,
+    ) -> io::Result<StoredBlob> {
+        let name = safe_name(name);
+        // 先登记（重置释放标记），避免与并发的 sweep 竞争
+        self.repo
+            .register(hash, kind.as_str(), &name, size)
+            .map_err(io::Error::other)?;
+
+        let path = object_path(&self.root, hash, &name);
+        if !path.exists() {
+            let dir = path.parent().unwrap_or(&self.root);
+            std::fs::create_dir_all(dir)?;
+            let existing = std::fs::read_dir(dir)?
+                .flatten()
+                .map(|e| e.path())
+                .find(|p| p.is_file());
+            match existing {
+                Some(existing) => {
+                    if std::fs::hard_link(&existing, &path).is_err() {
+                        std::fs::copy(&existing, &path)?;
+                    }
+                }
+                None => {
+                    let tmp = self.temp_path()?;
+                    if let Err(e) = write(&tmp).and_then(|()| std::fs::rename(&tmp, &path)) {
+                        remove_temp(&tmp);
+                        return Err(e);
+                    }
+                }
+            }
+            debug!("Stored blob {} as {:?}", &hash[..12.min(hash.len())], path);
+        }
+        Ok(StoredBlob {
+            hash: hash.to_string(),
+            path,
+            size,
+        })
+    }
+
+    /// 按登记的文件名查找对象（图标缓存按 `<cache_key>.png` 命中）
+    pub fn find_by_name(&self, kind: BlobKind, name: &str) -> Option<PathBuf> {
+        let hash = self.repo.find_by_name(kind.as_str(), name).ok()??;
+        let path = object_path(&self.root, &hash, name);
+        path.is_file().then_some(path)
+    }
+
+    /// 删除引用数为 0 的 blob，返回删除数
+    pub fn sweep(&self) -> usize {
+        let candidates = match self.repo.list_sweepable(ORPHAN_GRACE_MINUTES) {
+            Ok(candidates) => candidates,
+            Err(e) => {
+                warn!("Failed to list unreferenced blobs: {}", e);
+                return 0;
+            }
+        };
+        let mut removed = 0;
+        for hash in candidates {
+            if !matches!(
+                self.repo.delete_unreferenced(&hash, ORPHAN_GRACE_MINUTES),
+                Ok(true)
+            ) {
+                continue;
+            }
+            let dir = object_path(&self.root, &hash, "_");
+            if let Some(dir) = dir.parent() {
+                match std::fs::remove_dir_all(dir) {
+                    Ok(()) => removed += 1,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => warn!("Failed to delete blob {:?}: {}", dir, e),
+                }
+                if let Some(prefix_dir) = dir.parent() {
+                    let _ = std::fs::remove_dir(prefix_dir);
+                }
+            }
+        }
+        if removed > 0 {
+            debug!("Swept {} unreferenced blob(s)", removed);
+        }
+        removed
+    }
+
+    /// 把旧版 `images/`、`icons/`、`staged/` 中被条目引用的文件迁入 blob 存储并改写条目路径（只执行一次）
+    pub fn migrate_legacy(&self, db: &Database, data_dir: &Path) -> usize {
+        let settings = SettingsRepository::new(db);
+        if settings.get(MIGRATION_SETTING).ok().flatten().as_deref() == Some("done") {
+            return 0;
+        }
+        let repo = ClipboardRepository::new(db);
+        let items = match repo.query_media_items() {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("Blob migration skipped, failed to query items: {}", e);
+                return 0;
+            }
+        };
+        let images_dir = data_dir.join("images");
+
+        // 同一旧文件可能被多个条目引用（图标、同路径 staging），只复制一次；
+        // 旧文件在所有引用它的条目都改指 blob 后才删除，数据库更新失败时保留原文件
+        let mut copied: HashMap<String, Option<(PathBuf, String)>> = HashMap::new();
+        let mut migrate = |path: &str, name: &str, kind: BlobKind| -> Option<String> {
+            if hash_from_path(path).is_some() {
+                return None;
+            }
+            if let Some(entry) = copied.get(path) {
+                return entry.as_ref().map(|(_, target)| target.clone());
+            }
+            let src = if Path::new(path).is_absolute() {
+                PathBuf::from(path)
+            } else {
+                images_dir.join(path)
+            };
+            let target = src
+                .is_file()
+                .then(|| self.put_file(&src, name, kind))
+                .and_then(|result| {
+                    result
+                        .inspect_err(|e| warn!("Failed to migrate {} into blob store: {}", path, e))
+                        .ok()
+                })
+                .map(|blob| blob.path.to_string_lossy().to_string());
+            copied.insert(path.to_string(), target.clone().map(|t| (src, t)));
+            target
+        };
+
+        let mut migrated = 0;
+        let mut keep: HashSet<String> = HashSet::new();
+        for item in items {
+            let mut sources = Vec::new();
+            let image = item.image_path.as_deref().and_then(|p| {
+                let ext = Path::new(p)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("png");
+                let target = migrate(p, &format!("image.{ext}"), BlobKind::Image)?;
+                sources.push(p.to_string());
+                Some(target)
+            });
+            let icon = item.source_app_icon.as_deref().and_then(|p| {
+                let name = Path::new(p).file_name()?.to_string_lossy().to_string();
+                let target = migrate(p, &name, BlobKind::Icon)?;
+                sources.push(p.to_string());
+                Some(target)
+            });
+            let mut payload = file_clipboard::decode_payload(item.file_payload.as_deref());
+            let mut payload_changed = false;
+            for staged in payload.iter_mut().flat_map(|p| p.staged.iter_mut()) {
+                let name = Path::new(&staged.original)
+                    .file_name()
+                    .or_else(|| Path::new(&staged.staged).file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if let Some(target) = migrate(&staged.staged, &name, BlobKind::File) {
+                    sources.push(std::mem::replace(&mut staged.staged, target));
+                    payload_changed = true;
+                }
+            }
+            if image.is_none() && icon.is_none() && !payload_changed {
+                continue;
+            }
+            let payload = match payload.filter(|_| payload_changed) {
+                Some(payload) => Some(file_clipboard::encode_payload(&payload)),
+                None => item.file_payload.clone(),
+            };
+            if let Err(e) = repo.update_item_media_paths(
+                item.id,
+                image.as_deref().or(item.image_path.as_deref()),
+                payload.as_deref(),
+                icon.as_deref().or(item.source_app_icon.as_deref()),
+            ) {
+                warn!("Failed to update media paths of item {}: {}", item.id, e);
+                keep.extend(sources);
+                continue;
+            }
+            migrated += 1;
+        }
+
+        for (path, entry) in copied {
+            if let Some((src, _)) = entry.filter(|_| !keep.contains(&path)) {
+                let _ = std::fs::remove_file(src);
+            }
+        }
+
+        for dir in ["staged", "icons"] {
+            remove_empty_dirs(&data_dir.join(dir));
+        }
+        // images/captures 仍是捕获临时目录（可能正在使用），只清理其余旧图片子目录
+        if let Ok(entries) = std::fs::read_dir(&images_dir) {
+            for entry in entries.flatten() {
+                if entry.file_name() != CAPTURE_DIR && entry.path().is_dir() {
+                    remove_empty_dirs(&entry.path());
+                }
+            }
+        }
+        let _ = settings.set(MIGRATION_SETTING, "done");
+        if migrated > 0 {
+            info!("Migrated media of {} item(s) into blob store", migrated);
+        }
+        migrated
+    }
+}
+
+/// 自底向上删除空目录（非空目录保留）
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.path().is_dir() {
+            remove_empty_dirs(&entry.path());
+        }
+    }
+    let _ = std::fs::remove_dir(dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::NewClipboardItem;
+    use crate::test_support::temp_dir;
+
+    /// 临时目录中的数据库与 blob 存储（供 blobs 下各模块的测试共用）
+    pub(super) fn setup(name: &str) -> (PathBuf, Database, BlobStore) {
+        let dir = temp_dir(name);
+        let db = Database::new(dir.join("clipboard.db")).unwrap();
+        let store = BlobStore::new(&db, dir.join(DIR_NAME));
+        (dir, db, store)
+    }
+
+    #[test]
+    fn parses_hash_from_object_paths() {
+        let hash = "ab".to_string() + &"0".repeat(62);
+        let unix = object_path(Path::new("/data/blobs"), &hash, "a.txt");
+        assert_eq!(hash_from_path(&unix.to_string_lossy()), Some(hash.as_str()));
+        let windows = format!("D:\\data\\blobs\\ab\\{hash}\\report.zip");
+        assert_eq!(hash_from_path(&windows), Some(hash.as_str()));
+
+        assert_eq!(hash_from_path("D:\\data\\images\\abc.png"), None);
+        assert_eq!(
+            hash_from_path(&format!("/data/blobs/cd/{hash}/a.txt")),
+            None
+        );
+        assert_eq!(
+            hash_from_path(&format!("/data/other/ab/{hash}/a.txt")),
+            None
+        );
+        assert_eq!(safe_name("..\\evil:name"), ".._evil_name");
+        assert_eq!(safe_name(".."), "blob");
+    }
+
+    #[test]
+    fn same_content_is_stored_once_and_keeps_names() {
+        let (dir, _db, store) = setup("dedup");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        std::fs::write(&a, b"same bytes").unwrap();
+        std::fs::write(&b, b"same bytes").unwrap();
+
+        let first = store.put_file(&a, "a.txt", BlobKind::File).unwrap();
+        let second = store.put_file(&b, "b.txt", BlobKind::File).unwrap();
+        let third = store
+            .put_bytes(b"same bytes", "a.txt", BlobKind::File)
+            .unwrap();
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(first.path, third.path);
+        assert_eq!(second.path.file_name().unwrap(), "b.txt");
+        assert_eq!(std::fs::read(&second.path).unwrap(), b"same bytes");
+        assert!(a.exists(), "put_file keeps the source");
+
+        let moved = store.put_moved(&b, "b.txt", BlobKind::File).unwrap();
+        assert_eq!(moved.path, second.path);
+        assert!(!b.exists(), "put_moved consumes the source");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn verified_put_rejects_hash_mismatch() {
+        let (dir, _db, store) = setup("verified");
+        let hash = blake3::hash(b"media").to_hex().to_string();
+
+        let blob = store
+            .put_verified(&mut &b"media"[..], &hash, "image.png", BlobKind::Image)
+            .unwrap();
+        assert_eq!(blob.hash, hash);
+        assert_eq!(std::fs::read(&blob.path).unwrap(), b"media");
+
+        let other = blake3::hash(b"other").to_hex().to_string();
+        let err = store
+            .put_verified(&mut &b"tampered"[..], &other, "image.png", BlobKind::Image)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(!object_path(store.root(), &other, "image.png").exists());
+        assert_eq!(store.repo.ref_count(&other).unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cancelled_copy_leaves_no_object() {
+        let (dir, _db, store) = setup("cancel");
+        let src = dir.join("big.bin");
+        std::fs::write(&src, vec![7u8; COPY_CHUNK_BYTES * 2 + 10]).unwrap();
+
+        let mut calls = 0;
+        let err = store
+            .put_file_with_progress(&src, "big.bin", BlobKind::File, |_| {
+                calls += 1;
+                calls < 2
+            })
+            .unwrap_err();
+        assert!(is_copy_cancelled(&err));
+        let leftovers: Vec<_> = std::fs::read_dir(store.root()).unwrap().flatten().collect();
+        assert!(leftovers.is_empty(), "temp file and object are removed");
+
+        let mut reported = 0;
+        let blob = store
+            .put_file_with_progress(&src, "big.bin", BlobKind::File, |n| {
+                reported = n;
+                true
+            })
+            .unwrap();
+        assert_eq!(reported, blob.size);
+        assert_eq!(blob.hash, hash_file(&src).unwrap().0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sweep_removes_only_released_blobs() {
+        let (dir, db, store) = setup("sweep");
+        let repo = ClipboardRepository::new(&db);
+        let kept = store
+            .put_bytes(b"kept", IMAGE_NAME, BlobKind::Image)
+            .unwrap();
+        let released = store
+            .put_bytes(b"released", IMAGE_NAME, BlobKind::Image)
+            .unwrap();
+        let pending = store
+            .put_bytes(b"pending", IMAGE_NAME, BlobKind::Image)
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for blob in [&kept, &released] {
+            ids.push(
+                repo.insert(NewClipboardItem {
+                    content_type: crate::database::ContentType::Image,
+                    image_path: Some(blob.path.to_string_lossy().to_string()),
+                    content_hash: blob.hash.clone(),
+                    semantic_hash: blob.hash.clone(),
+                    ..Default::default()
+                })
+                .unwrap(),
+            );
+        }
+        repo.delete(ids[1]).unwrap();
+
+        assert_eq!(store.sweep(), 1);
+        assert!(kept.path.exists());
+        assert!(!released.path.exists());
+        assert!(!released.path.parent().unwrap().exists());
+        // 刚存入、尚未被条目引用的 blob 在宽限期内保留
+        assert!(pending.path.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn migrates_legacy_directories() {
+        let (dir, db, store) = setup("migrate");
+        let repo = ClipboardRepository::new(&db);
+        std::fs::create_dir_all(dir.join("images").join("2023")).unwrap();
+        std::fs::create_dir_all(dir.join("images").join(CAPTURE_DIR)).unwrap();
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        std::fs::create_dir_all(dir.join("staged").join("1234abcd")).unwrap();
+        let image = dir.join("images").join("0123.png");
+        let icon = dir.join("icons").join("key123.png");
+        let staged = dir
+            .join("staged")
+            .join("1234abcd")
+            .join("9f9f9f9f_report.txt");
+        std::fs::write(&image, b"png").unwrap();
+        std::fs::write(&icon, b"icon").unwrap();
+        std::fs::write(&staged, b"report").unwrap();
+        let icon_str = icon.to_string_lossy().to_string();
+
+        let image_id = repo
+            .insert(NewClipboardItem {
+                content_type: crate::database::ContentType::Image,
+                image_path: Some(image.to_string_lossy().to_string()),
+                source_app_icon: Some(icon_str.clone()),
+                content_hash: "h1".into(),
+                semantic_hash: "h1".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        let payload = file_clipboard::FilePayload {
+            staged: vec![file_clipboard::StagedFile {
+                original: "C:\\docs\\report.txt".into(),
+                staged: staged.to_string_lossy().to_string(),
+                size: 6,
+                snapshot: None,
+            }],
+            ..Default::default()
+        };
+        let files_id = repo
+            .insert(NewClipboardItem {
+                content_type: crate::database::ContentType::Files,
+                file_paths: Some(vec!["C:\\docs\\report.txt".into()]),
+                file_payload: Some(file_clipboard::encode_payload(&payload)),
+                source_app_icon: Some(icon_str),
+                content_hash: "h2".into(),
+                semantic_hash: "h2".into(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(store.migrate_legacy(&db, &dir), 2);
+        assert_eq!(store.migrate_legacy(&db, &dir), 0, "runs only once");
+
+        let image_item = repo.get_by_id(image_id).unwrap().unwrap();
+        let image_path = image_item.image_path.unwrap();
+        assert!(hash_from_path(&image_path).is_some());
+        assert_eq!(std::fs::read(&image_path).unwrap(), b"png");
+        assert!(!image.exists());
+
+        let files_item = repo.get_by_id(files_id).unwrap().unwrap();
+        assert_eq!(files_item.source_app_icon, image_item.source_app_icon);
+        assert_eq!(
+            store.find_by_name(BlobKind::Icon, "key123.png").as_deref(),
+            files_item.source_app_icon.as_deref().map(Path::new)
+        );
+        let staged_path =
+            file_clipboard::staged_paths_from_payload(files_item.file_payload.as_deref());
+        assert!(staged_path[0].ends_with("report.txt"));
+        assert_eq!(std::fs::read(&staged_path[0]).unwrap(), b"report");
+        assert!(!dir.join("staged").exists());
+        assert!(!dir.join("icons").exists());
+        assert!(!dir.join("images").join("2023").exists());
+        assert!(dir.join("images").join(CAPTURE_DIR).exists());
+
+        // 两个条目共享图标：删除一个后仍被引用
+        repo.delete(image_id).unwrap();
+        store.sweep();
+        assert!(Path::new(files_item.source_app_icon.as_deref().unwrap()).exists());
+        assert!(!Path::new(&image_path).exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_migration_keeps_legacy_files() {
+        let (dir, db, store) = setup("migrate_fail");
+        let repo = ClipboardRepository::new(&db);
+        std::fs::create_dir_all(dir.join("images")).unwrap();
+        let image = dir.join("images").join("0456.png");
+        std::fs::write(&image, b"png").unwrap();
+        let image_str = image.to_string_lossy().to_string();
+        let id = repo
+            .insert(NewClipboardItem {
+                content_type: crate::database::ContentType::Image,
+                image_path: Some(image_str.clone()),
+                content_hash: "h1".into(),
+                semantic_hash: "h1".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        db.write_connection()
+            .lock()
+            .execute_batch(
+                "CREATE TRIGGER fail_update BEFORE UPDATE ON clipboard_items \
+                 BEGIN SELECT RAISE(ABORT, 'read-only'); END;",
+            )
+            .unwrap();
+
+        assert_eq!(store.migrate_legacy(&db, &dir), 0);
+        assert!(image.exists(), "legacy file survives a failed update");
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.image_path.as_deref(), Some(image_str.as_str()));
+        assert_eq!(std::fs::read(&image).unwrap(), b"png");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
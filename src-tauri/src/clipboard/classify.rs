@@ -0,0 +1,654 @@
+//! 文本内容子类型识别（颜色、JSON、代码、邮箱、电话、路径、UUID、IP、算式）
+//!
+//! 结果以逗号分隔写入 `subtypes` 列，如 `color`、`code,lang:rust`，
+//! 供前端渲染色块/代码高亮，并可通过 `QueryOptions::subtype` 过滤。
+
+use std::net::{IpAddr, SocketAddr};
+
+pub(crate) const SUBTYPE_COLOR: &str = "color";
+pub(crate) const SUBTYPE_JSON: &str = "json";
+pub(crate) const SUBTYPE_CODE: &str = "code";
+pub(crate) const SUBTYPE_EMAIL: &str = "email";
+pub(crate) const SUBTYPE_PHONE: &str = "phone";
+pub(crate) const SUBTYPE_PATH: &str = "path";
+pub(crate) const SUBTYPE_UUID: &str = "uuid";
+pub(crate) const SUBTYPE_IP: &str = "ip";
+pub(crate) const SUBTYPE_MATH: &str = "math";
+/// 代码语言标签前缀（`lang:rust`）
+pub(crate) const LANG_PREFIX: &str = "lang:";
+
+/// 超过该长度的文本只做代码/JSON 判断的前缀采样，避免大文本拖慢捕获
+const MAX_SCAN_BYTES: usize = 64 * 1024;
+/// 单行类型（颜色、邮箱、路径等）的最大长度
+const MAX_SINGLE_LINE_LEN: usize = 1024;
+
+/// 识别文本子类型，返回逗号分隔的标签串；无匹配返回 None
+pub(crate) fn classify_text(text: &str) -> Option<String> {
+    let tags = classify_tags(text);
+    (!tags.is_empty()).then(|| tags.join(","))
+}
+
+fn classify_tags(text: &str) -> Vec<String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+
+    let single_line = !trimmed.contains('\n') && trimmed.len() <= MAX_SINGLE_LINE_LEN;
+    if single_line {
+        let single: Option<&str> = if is_color(trimmed) {
+            Some(SUBTYPE_COLOR)
+        } else if is_uuid(trimmed) {
+            Some(SUBTYPE_UUID)
+        } else if is_ip(trimmed) {
+            Some(SUBTYPE_IP)
+        } else if is_email(trimmed) {
+            Some(SUBTYPE_EMAIL)
+        } else if is_phone(trimmed) {
+            Some(SUBTYPE_PHONE)
+        } else if is_path(trimmed) {
+            Some(SUBTYPE_PATH)
+        } else if is_math(trimmed) {
+            Some(SUBTYPE_MATH)
+        } else {
+            None
+        };
+        if let Some(tag) = single {
+            return vec![tag.to_string()];
+        }
+    }
+
+    if trimmed.len() <= MAX_SCAN_BYTES && is_json(trimmed) {
+        return vec![SUBTYPE_JSON.to_string()];
+    }
+
+    let sample = truncate_at_char_boundary(trimmed, MAX_SCAN_BYTES);
+    if let Some(lang) = guess_code_language(sample) {
+        let mut tags = vec![SUBTYPE_CODE.to_string()];
+        if let Some(lang) = lang {
+            tags.push(format!("{LANG_PREFIX}{lang}"));
+        }
+        return tags;
+    }
+
+    Vec::new()
+}
+
+fn truncate_at_char_boundary(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+// ============ 颜色 ============
+
+fn is_color(text: &str) -> bool {
+    if let Some(hex) = text.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    let lower = text.to_ascii_lowercase();
+    for (func, is_hsl) in [
+        ("rgba", false),
+        ("rgb", false),
+        ("hsla", true),
+        ("hsl", true),
+    ] {
+        if let Some(args) = lower
+            .strip_prefix(func)
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return is_color_args(args, is_hsl);
+        }
+    }
+    false
+}
+
+/// 校验 `rgb()/hsl()` 参数：3 或 4 个分量，支持逗号或空格（CSS4 `/ alpha`）分隔
+fn is_color_args(args: &str, is_hsl: bool) -> bool {
+    let parts: Vec<&str> = args
+        .split([',', ' ', '/'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    if !(3..=4).contains(&parts.len()) {
+        return false;
+    }
+    parts.iter().enumerate().all(|(i, part)| {
+        let (num, unit) = split_number_unit(part);
+        let Ok(value) = num.parse::<f64>() else {
+            return false;
+        };
+        match (i, is_hsl) {
+            (3, _) => match unit {
+                "%" => (0.0..=100.0).contains(&value),
+                "" => (0.0..=1.0).contains(&value),
+                _ => false,
+            },
+            (0, true) => matches!(unit, "" | "deg") && (0.0..=360.0).contains(&value),
+            (_, true) => unit == "%" && (0.0..=100.0).contains(&value),
+            (_, false) => match unit {
+                "%" => (0.0..=100.0).contains(&value),
+                "" => (0.0..=255.0).contains(&value),
+                _ => false,
+            },
+        }
+    })
+}
+
+fn split_number_unit(part: &str) -> (&str, &str) {
+    let idx = part
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(part.len());
+    part.split_at(idx)
+}
+
+// ============ 标识符 / 网络 ============
+
+fn is_uuid(text: &str) -> bool {
+    let inner = text
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .unwrap_or(text);
+    let groups: Vec<&str> = inner.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn is_ip(text: &str) -> bool {
+    text.parse::<IpAddr>().is_ok() || text.parse::<SocketAddr>().is_ok()
+}
+
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+    let local = local.strip_prefix("mailto:").unwrap_or(local);
+    if local.is_empty() || local.len() > 64 || domain.contains('@') {
+        return false;
+    }
+    if !local
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "._%+-".contains(c))
+    {
+        return false;
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|l| {
+            !l.is_empty()
+                && !l.starts_with('-')
+                && !l.ends_with('-')
+                && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+/// 电话号码：`+` 国际号码、11 位国内手机号，或带分隔符分组的 7-15 位号码
+fn is_phone(text: &str) -> bool {
+    if !text
+        .chars()
+        .all(|c| c.is_ascii_digit() || " -().+".contains(c))
+    {
+        return false;
+    }
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    if !(7..=15).contains(&digits) {
+        return false;
+    }
+    if text[1..].contains('+') || looks_like_date(text) {
+        return false;
+    }
+    if text.starts_with('+') {
+        return true;
+    }
+    if digits == text.len() {
+        return digits == 11 && text.starts_with('1');
+    }
+    // 带分隔符：至少两个数字组，且不含小数点形式（避免与数字/版本号混淆）
+    let groups = text
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|g| !g.is_empty())
+        .count();
+    groups >= 2 && !text.contains('.')
+}
+
+fn looks_like_date(text: &str) -> bool {
+    let groups: Vec<&str> = text.split(['-', '.', '/']).collect();
+    groups.len() == 3 && groups[0].len() == 4 && groups[1].len() <= 2 && groups[2].len() <= 2
+}
+
+// ============ 路径 ============
+
+fn is_path(text: &str) -> bool {
+    let text = text.trim_matches('"');
+    let bytes = text.as_bytes();
+    // Windows 盘符路径：C:\ 或 C:/
+    if bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/')
+    {
+        return !text[2..].contains(['<', '>', '|', '"', '*', '?', ':']);
+    }
+    // UNC 路径：\\server\share
+    if let Some(rest) = text.strip_prefix("\\\\") {
+        return rest.split('\\').filter(|s| !s.is_empty()).count() >= 2;
+    }
+    // Unix 绝对路径 / 家目录路径（至少两段，排除 `//` 开头的协议相对 URL）
+    let rest = text
+        .strip_prefix("~/")
+        .or_else(|| text.strip_prefix('/').filter(|r| !r.starts_with('/')));
+    rest.is_some_and(|r| {
+        !r.is_empty()
+            && !r.contains("//")
+            && (text.starts_with('~') || r.contains('/') || !r.contains(' '))
+            && r.chars().next().is_some_and(|c| !c.is_whitespace())
+    })
+}
+
+// ============ 算式 ============
+
+fn is_math(text: &str) -> bool {
+    if !text
+        .chars()
+        .all(|c| c.is_ascii_digit() || " +-*/%^().,×÷=".contains(c))
+    {
+        return false;
+    }
+    let expr = text.trim_end_matches(['=', ' ']);
+    if expr.contains('=') || looks_like_date(expr) {
+        return false;
+    }
+
+    let mut depth = 0i32;
+    let mut numbers = 0;
+    let mut operators = 0;
+    let mut in_number = false;
+    for c in expr.chars() {
+        match c {
+            '0'..='9' | '.' | ',' => {
+                if !in_number {
+                    numbers += 1;
+                    in_number = true;
+                }
+            }
+            '(' => {
+                depth += 1;
+                in_number = false;
+            }
+            ')' => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+                in_number = false;
+            }
+            ' ' => in_number = false,
+            _ => {
+                operators += 1;
+                in_number = false;
+            }
+        }
+    }
+    depth == 0 && numbers >= 2 && operators >= 1
+}
+
+// ============ JSON ============
+
+fn is_json(text: &str) -> bool {
+    let first = text.as_bytes()[0];
+    let last = text.as_bytes()[text.len() - 1];
+    let wrapped = (first == b'{' && last == b'}') || (first == b'[' && last == b']');
+    wrapped && text.len() > 2 && serde_json::from_str::<serde::de::IgnoredAny>(text).is_ok()
+}
+
+// ============ 代码 ============
+
+/// 各语言的特征片段（命中越多得分越高）
+const LANGUAGE_HINTS: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "fn ",
+            "let mut ",
+            "impl ",
+            "pub fn ",
+            "use std::",
+            "#[derive",
+            "&mut ",
+            "match ",
+            "-> ",
+            "Option<",
+            "Result<",
+            "println!",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "def ", "import ", "from ", "self.", "elif ", "print(", "__init__", "None", "True:",
+            "lambda ",
+        ],
+    ),
+    (
+        "typescript",
+        &[
+            ": string",
+            ": number",
+            "interface ",
+            "export type ",
+            "as const",
+            ": boolean",
+            "implements ",
+            "readonly ",
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            "function ",
+            "const ",
+            "=> ",
+            "console.log",
+            "require(",
+            "export ",
+            "let ",
+            "document.",
+            "await ",
+            "===",
+        ],
+    ),
+    (
+        "go",
+        &[
+            "func ",
+            "package ",
+            ":= ",
+            "fmt.",
+            "go func",
+            "chan ",
+            "defer ",
+            "err != nil",
+        ],
+    ),
+    (
+        "java",
+        &[
+            "public class ",
+            "System.out",
+            "private ",
+            "public static void",
+            "@Override",
+            "import java.",
+            "new ",
+            "extends ",
+        ],
+    ),
+    (
+        "csharp",
+        &[
+            "using System",
+            "namespace ",
+            "public class ",
+            "Console.Write",
+            "var ",
+            "async Task",
+            "get; set;",
+        ],
+    ),
+    (
+        "cpp",
+        &[
+            "#include",
+            "std::",
+            "int main",
+            "printf(",
+            "nullptr",
+            "template<",
+            "cout <<",
+            "->",
+        ],
+    ),
+    (
+        "sql",
+        &[
+            "SELECT ",
+            "FROM ",
+            "WHERE ",
+            "INSERT INTO",
+            "UPDATE ",
+            "CREATE TABLE",
+            "JOIN ",
+            "GROUP BY",
+            "ORDER BY",
+        ],
+    ),
+    (
+        "shell",
+        &[
+            "#!/bin/", "echo ", "sudo ", "export ", "fi\n", "then\n", "$(", "apt ", "| grep",
+        ],
+    ),
+    (
+        "css",
+        &[
+            "px;",
+            "color:",
+            "margin:",
+            "padding:",
+            "display:",
+            "font-",
+            "@media",
+            "!important",
+        ],
+    ),
+];
+
+/// 代码判定：基于结构特征 + 关键词打分。
+///
+/// 返回 `None` 表示不是代码；`Some(None)` 表示是代码但语言不确定。
+fn guess_code_language(text: &str) -> Option<Option<&'static str>> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    // 结构特征：以 ; { } 结尾的行、缩进行
+    let structural = lines
+        .iter()
+        .filter(|l| {
+            let t = l.trim_end();
+            t.ends_with(';') || t.ends_with('{') || t.ends_with('}') || t.ends_with(':')
+        })
+        .count();
+    let indented = lines
+        .iter()
+        .filter(|l| l.starts_with("    ") || l.starts_with('\t'))
+        .count();
+
+    let mut best: Option<(&'static str, usize)> = None;
+    for (lang, hints) in LANGUAGE_HINTS {
+        let score = hints.iter().filter(|h| text.contains(*h)).count();
+        if score > best.map_or(0, |(_, s)| s) {
+            best = Some((lang, score));
+        }
+    }
+
+    // Shebang / #include 等强特征直接判定
+    if text.starts_with("#!") {
+        return Some(Some("shell"));
+    }
+    if text.starts_with("#include") {
+        return Some(Some("cpp"));
+    }
+    if text.starts_with("<?php") {
+        return Some(Some("php"));
+    }
+    if is_markup(text) {
+        return Some(Some("html"));
+    }
+
+    let structure_ratio = (structural + indented) as f64 / lines.len() as f64;
+    match best {
+        Some((lang, score)) if score >= 3 => Some(Some(lang)),
+        Some((lang, score)) if score >= 2 && (lines.len() == 1 || structure_ratio >= 0.3) => {
+            Some(Some(lang))
+        }
+        _ if lines.len() >= 3 && structure_ratio >= 0.6 => Some(None),
+        _ => None,
+    }
+}
+
+fn is_markup(text: &str) -> bool {
+    text.starts_with('<')
+        && text.ends_with('>')
+        && text.contains("</")
+        && text
+            .as_bytes()
+            .get(1)
+            .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'!')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::classify_text;
+
+    fn tags(text: &str) -> String {
+        classify_text(text).unwrap_or_default()
+    }
+
+    #[test]
+    fn detects_hex_colors() {
+        assert_eq!(tags("#fff"), "color");
+        assert_eq!(tags("#1E90FF"), "color");
+        assert_eq!(tags("#1e90ff80"), "color");
+        assert_eq!(tags("#12345"), "");
+        assert_eq!(tags("#ggg"), "");
+    }
+
+    #[test]
+    fn detects_functional_colors() {
+        assert_eq!(tags("rgb(255, 0, 128)"), "color");
+        assert_eq!(tags("rgba(0,0,0,0.5)"), "color");
+        assert_eq!(tags("rgb(255 0 0 / 50%)"), "color");
+        assert_eq!(tags("hsl(210, 50%, 40%)"), "color");
+        assert_eq!(tags("HSLA(210deg 50% 40% / 0.3)"), "color");
+        assert_eq!(tags("rgb(300, 0, 0)"), "");
+        assert_eq!(tags("hsl(210, 50, 40)"), "");
+    }
+
+    #[test]
+    fn detects_json() {
+        assert_eq!(tags(r#"{"name": "clip", "count": 3}"#), "json");
+        assert_eq!(tags("[1, 2, 3]"), "json");
+        assert_eq!(tags("{\n  \"a\": [true, null]\n}"), "json");
+        assert_eq!(tags("{not json}"), "");
+        assert_eq!(tags("[]"), "");
+    }
+
+    #[test]
+    fn detects_code_with_language() {
+        let rust = "fn main() {\n    let mut x = 1;\n    println!(\"{}\", x);\n}";
+        assert_eq!(tags(rust), "code,lang:rust");
+
+        let python = "def add(a, b):\n    return a + b\n\nprint(add(1, 2))";
+        assert_eq!(tags(python), "code,lang:python");
+
+        let sql = "SELECT id, name FROM users WHERE age > 18 ORDER BY name";
+        assert_eq!(tags(sql), "code,lang:sql");
+
+        let go = "package main\n\nfunc main() {\n    x := 1\n    fmt.Println(x)\n}";
+        assert_eq!(tags(go), "code,lang:go");
+
+        assert_eq!(tags("#!/bin/sh\necho hi"), "code,lang:shell");
+        assert_eq!(
+            tags("<div class=\"a\"><span>x</span></div>"),
+            "code,lang:html"
+        );
+    }
+
+    #[test]
+    fn prose_is_not_code() {
+        assert_eq!(tags("Let me know when you are free tomorrow."), "");
+        assert_eq!(tags("今天天气不错，我们去公园散步吧。"), "");
+        assert_eq!(tags("First line\nSecond line\nThird line"), "");
+    }
+
+    #[test]
+    fn detects_email() {
+        assert_eq!(tags("someone@example.com"), "email");
+        assert_eq!(tags("first.last+tag@mail.example.co.uk"), "email");
+        assert_eq!(tags("mailto:me@example.org"), "email");
+        assert_eq!(tags("not an @email.com"), "");
+        assert_eq!(tags("user@localhost"), "");
+    }
+
+    #[test]
+    fn detects_phone_numbers() {
+        assert_eq!(tags("+86 138 0013 8000"), "phone");
+        assert_eq!(tags("13800138000"), "phone");
+        assert_eq!(tags("(555) 123-4567"), "phone");
+        assert_eq!(tags("555-123-4567"), "phone");
+        assert_eq!(tags("2024-01-15"), "");
+        assert_eq!(tags("1234567"), "");
+    }
+
+    #[test]
+    fn detects_paths() {
+        assert_eq!(tags(r"C:\Users\me\Documents\report.docx"), "path");
+        assert_eq!(tags("D:/projects/app"), "path");
+        assert_eq!(tags(r"\\server\share\file.txt"), "path");
+        assert_eq!(tags("/usr/local/bin"), "path");
+        assert_eq!(tags("~/Downloads/My File.pdf"), "path");
+        assert_eq!(tags("\"C:\\Program Files\\App\\app.exe\""), "path");
+        assert_eq!(tags("//cdn.example.com/lib.js"), "");
+        assert_eq!(tags("and/or"), "");
+    }
+
+    #[test]
+    fn detects_uuid() {
+        assert_eq!(tags("550e8400-e29b-41d4-a716-446655440000"), "uuid");
+        assert_eq!(tags("{550E8400-E29B-41D4-A716-446655440000}"), "uuid");
+        assert_eq!(tags("550e8400-e29b-41d4-a716"), "");
+    }
+
+    #[test]
+    fn detects_ip_addresses() {
+        assert_eq!(tags("192.168.1.1"), "ip");
+        assert_eq!(tags("10.0.0.1:8080"), "ip");
+        assert_eq!(tags("::1"), "ip");
+        assert_eq!(tags("2001:db8::ff00:42:8329"), "ip");
+        assert_eq!(tags("[::1]:443"), "ip");
+        assert_eq!(tags("999.1.1.1"), "");
+    }
+
+    #[test]
+    fn detects_math_expressions() {
+        assert_eq!(tags("3 * (4 + 2)"), "math");
+        assert_eq!(tags("1024/8"), "math");
+        assert_eq!(tags("2^10 - 1 ="), "math");
+        assert_eq!(tags("12 × 3 ÷ 4"), "math");
+        assert_eq!(tags("(1+2"), "");
+        assert_eq!(tags("42"), "");
+        assert_eq!(tags("1 + 1 = 2"), "");
+    }
+
+    #[test]
+    fn empty_and_plain_text_have_no_tags() {
+        assert_eq!(classify_text(""), None);
+        assert_eq!(classify_text("   \n "), None);
+        assert_eq!(classify_text("hello"), None);
+    }
+}
@@ -0,0 +1,479 @@
+//! 收集模式：开启期间连续复制的文本/HTML 或文件合并为同一条记录
+//!
+//! 会话类型由第一段内容决定（文本或文件），其他类型（含图片）照常入库。
+//! 关闭收集模式时由命令层把合并结果写回系统剪贴板。
+
+use super::file_clipboard::{self, FilePayload, StagedFile};
+use super::{
+    ClipboardHandler, classify_text, dedup::normalize_semantic_text, semantic_hash_from_text,
+};
+use crate::database::{ContentType, NewClipboardItem};
+use crate::utils::escape_html;
+use blake3::Hasher;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub(crate) const SEPARATOR_SETTING: &str = "collect_separator";
+pub(crate) const ORDER_SETTING: &str = "collect_order";
+pub(crate) const DEDUP_SETTING: &str = "collect_dedup";
+
+const DEFAULT_SEPARATOR: &str = "\n";
+
+/// 新片段的拼接位置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectOrder {
+    /// 追加到末尾
+    #[default]
+    Append,
+    /// 插入到开头（最新的在最前）
+    Prepend,
+}
+
+/// 收集模式配置，开启会话时从设置读取
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectOptions {
+    pub separator: String,
+    pub order: CollectOrder,
+    /// 跳过与已收集片段重复的文本（文件路径始终去重）
+    pub dedup: bool,
+}
+
+impl Default for CollectOptions {
+    fn default() -> Self {
+        Self {
+            separator: DEFAULT_SEPARATOR.to_string(),
+            order: CollectOrder::Append,
+            dedup: true,
+        }
+    }
+}
+
+impl CollectOptions {
+    pub fn from_settings(settings: &HashMap<String, Option<String>>) -> Self {
+        let get = |key: &str| settings.get(key).cloned().flatten();
+        Self {
+            // 允许空分隔符（直接拼接），仅未设置时使用默认值
+            separator: get(SEPARATOR_SETTING).unwrap_or_else(|| DEFAULT_SEPARATOR.to_string()),
+            order: match get(ORDER_SETTING).as_deref() {
+                Some("prepend") => CollectOrder::Prepend,
+                _ => CollectOrder::Append,
+            },
+            dedup: get(DEDUP_SETTING).is_none_or(|v| v != "false"),
+        }
+    }
+}
+
+/// 会话接受的内容类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FragmentKind {
+    Text,
+    Files,
+}
+
+/// 一次捕获贡献的片段
+#[derive(Debug, Clone)]
+pub(crate) enum Fragment {
+    Text {
+        text: String,
+        html: Option<String>,
+    },
+    Files {
+        paths: Vec<String>,
+        staged: Vec<StagedFile>,
+    },
+}
+
+impl Fragment {
+    fn kind(&self) -> FragmentKind {
+        match self {
+            Self::Text { .. } => FragmentKind::Text,
+            Self::Files { .. } => FragmentKind::Files,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PushOutcome {
+    Added,
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Session {
+    options: CollectOptions,
+    item_id: Option<i64>,
+    /// 合并条目所属分组，固定为首段内容的分组
+    group_id: Option<i64>,
+    kind: Option<FragmentKind>,
+    fragments: usize,
+    /// 按最终顺序排列的文本片段（文本, HTML）
+    texts: Vec<(String, Option<String>)>,
+    /// 按最终顺序排列的文件路径
+    paths: Vec<String>,
+    staged: Vec<StagedFile>,
+}
+
+impl Session {
+    fn new(options: CollectOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn accepts(&self, kind: FragmentKind) -> bool {
+        self.kind.is_none_or(|k| k == kind)
+    }
+
+    fn insert_at<T>(order: CollectOrder, list: &mut Vec<T>, value: T) {
+        match order {
+            CollectOrder::Append => list.push(value),
+            CollectOrder::Prepend => list.insert(0, value),
+        }
+    }
+
+    fn push(&mut self, fragment: Fragment) -> PushOutcome {
+        self.kind = Some(fragment.kind());
+        let order = self.options.order;
+        match fragment {
+            Fragment::Text { text, html } => {
+                if self.options.dedup {
+                    let normalized = normalize_semantic_text(&text);
+                    if self
+                        .texts
+                        .iter()
+                        .any(|(t, _)| normalize_semantic_text(t) == normalized)
+                    {
+                        return PushOutcome::Duplicate;
+                    }
+                }
+                Self::insert_at(order, &mut self.texts, (text, html));
+            }
+            Fragment::Files { paths, staged } => {
+                let new_paths: Vec<String> = paths
+                    .into_iter()
+                    .filter(|p| !self.paths.contains(p))
+                    .fold(Vec::new(), |mut acc, p| {
+                        if !acc.contains(&p) {
+                            acc.push(p);
+                        }
+                        acc
+                    });
+                if new_paths.is_empty() {
+                    return PushOutcome::Duplicate;
+                }
+                match order {
+                    CollectOrder::Append => self.paths.extend(new_paths.iter().cloned()),
+                    CollectOrder::Prepend => {
+                        self.paths.splice(0..0, new_paths.iter().cloned());
+                    }
+                }
+                self.staged.extend(
+                    staged
+                        .into_iter()
+                        .filter(|s| new_paths.contains(&s.original)),
+                );
+            }
+        }
+        self.fragments += 1;
+        PushOutcome::Added
+    }
+
+    fn combined_text(&self) -> String {
+        self.texts
+            .iter()
+            .map(|(t, _)| t.as_str())
+            .collect::<Vec<_>>()
+            .join(&self.options.separator)
+    }
+
+    /// 任一片段含 HTML 时生成合并 HTML，纯文本片段转义后参与拼接
+    fn combined_html(&self) -> Option<String> {
+        if self.texts.iter().all(|(_, html)| html.is_none()) {
+            return None;
+        }
+        let separator = escape_html(&self.options.separator).replace('\n', "<br>");
+        let parts: Vec<String> = self
+            .texts
+            .iter()
+            .map(|(text, html)| match html {
+                Some(html) => html.clone(),
+                None => escape_html(text).replace('\n', "<br>"),
+            })
+            .collect();
+        Some(parts.join(&separator))
+    }
+
+    /// 生成合并后的条目内容
+    fn build_item(&self) -> NewClipboardItem {
+        let mut hasher = Hasher::new();
+        let mut item = match self.kind {
+            Some(FragmentKind::Files) => {
+                hasher.update(b"files:");
+                for path in &self.paths {
+                    hasher.update(path.as_bytes());
+                    hasher.update(b"|");
+                }
+                let byte_size = self
+                    .paths
+                    .iter()
+                    .filter_map(|p| std::fs::metadata(p).ok())
+                    .filter(std::fs::Metadata::is_file)
+                    .map(|m| m.len() as i64)
+                    .sum();
+                let payload = FilePayload {
+                    staged: self.staged.clone(),
+                    ..Default::default()
+                };
+                NewClipboardItem {
+                    content_type: ContentType::Files,
+                    file_paths: Some(self.paths.clone()),
+                    file_payload: Some(file_clipboard::encode_payload(&payload)),
+                    preview: Some(file_clipboard::file_entry_preview(&self.paths, &[])),
+                    byte_size,
+                    ..Default::default()
+                }
+            }
+            _ => {
+                let text = self.combined_text();
+                let html = self.combined_html();
+                match &html {
+                    Some(html) => {
+                        hasher.update(b"html:");
+                        hasher.update(html.as_bytes());
+                    }
+                    None => {
+                        hasher.update(b"text:");
+                        hasher.update(text.as_bytes());
+                    }
+                }
+                NewClipboardItem {
+                    content_type: if html.is_some() {
+                        ContentType::Html
+                    } else {
+                        ContentType::Text
+                    },
+                    preview: Some(ClipboardHandler::create_preview(&text)),
+                    byte_size: html.as_ref().map_or(text.len(), String::len) as i64,
+                    char_count: Some(text.chars().count() as i64),
+                    subtypes: classify_text(&text),
+                    html_content: html,
+                    text_content: Some(text),
+                    ..Default::default()
+                }
+            }
+        };
+        item.content_hash = hasher.finalize().to_hex().to_string();
+        item.semantic_hash = item
+            .text_content
+            .as_deref()
+            .and_then(semantic_hash_from_text)
+            .unwrap_or_else(|| item.content_hash.clone());
+        item.group_id = self.group_id;
+        item
+    }
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// 收集模式状态快照
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CollectState {
+    pub active: bool,
+    /// 合并条目 ID（尚未收集到内容时为 None）
+    pub item_id: Option<i64>,
+    pub fragments: usize,
+}
+
+pub(crate) fn is_active() -> bool {
+    SESSION.lock().is_some()
+}
+
+/// 当前会话是否接受该类别的片段
+pub(crate) fn accepts(kind: FragmentKind) -> bool {
+    SESSION
+        .lock()
+        .as_ref()
+        .is_some_and(|session| session.accepts(kind))
+}
+
+pub(crate) fn state() -> CollectState {
+    match SESSION.lock().as_ref() {
+        Some(session) => CollectState {
+            active: true,
+            item_id: session.item_id,
+            fragments: session.fragments,
+        },
+        None => CollectState::default(),
+    }
+}
+
+/// 开启收集模式；已开启时返回 false
+pub(crate) fn start(options: CollectOptions) -> bool {
+    let mut guard = SESSION.lock();
+    if guard.is_some() {
+        return false;
+    }
+    *guard = Some(Session::new(options));
+    true
+}
+
+/// 结束收集模式，返回最终状态（`active` 为 false）；未开启时返回 None
+pub(crate) fn finish() -> Option<CollectState> {
+    SESSION.lock().take().map(|session| CollectState {
+        active: false,
+        item_id: session.item_id,
+        fragments: session.fragments,
+    })
+}
+
+/// 将片段并入当前会话并通过 `store` 持久化合并结果。
+///
+/// `store` 接收已有合并条目 ID（首段为 None）与合并后的内容，返回条目 ID。
+/// 未开启或类别不匹配时返回 None，调用方按普通捕获处理；
+/// 重复片段返回 `Some(Ok(None))`。持久化失败时回滚本次片段。
+pub(crate) fn collect<F>(
+    fragment: Fragment,
+    group_id: Option<i64>,
+    store: F,
+) -> Option<Result<Option<i64>, String>>
+where
+    F: FnOnce(Option<i64>, NewClipboardItem) -> Result<i64, String>,
+{
+    let mut guard = SESSION.lock();
+    let session = guard.as_mut()?;
+    if !session.accepts(fragment.kind()) {
+        return None;
+    }
+
+    let backup = session.clone();
+    if session.push(fragment) == PushOutcome::Duplicate {
+        return Some(Ok(None));
+    }
+    // 合并条目固定在首段所属分组
+    if session.item_id.is_none() {
+        session.group_id = group_id;
+    }
+    let item = session.build_item();
+    Some(match store(session.item_id, item) {
+        Ok(id) => {
+            session.item_id = Some(id);
+            Ok(Some(id))
+        }
+        Err(e) => {
+            *session = backup;
+            Err(e)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(t: &str) -> Fragment {
+        Fragment::Text {
+            text: t.to_string(),
+            html: None,
+        }
+    }
+
+    fn session(separator: &str, order: CollectOrder, dedup: bool) -> Session {
+        Session::new(CollectOptions {
+            separator: separator.to_string(),
+            order,
+            dedup,
+        })
+    }
+
+    #[test]
+    fn options_read_from_settings() {
+        let mut settings = HashMap::new();
+        assert_eq!(
+            CollectOptions::from_settings(&settings),
+            CollectOptions::default()
+        );
+
+        settings.insert(SEPARATOR_SETTING.to_string(), Some(", ".to_string()));
+        settings.insert(ORDER_SETTING.to_string(), Some("prepend".to_string()));
+        settings.insert(DEDUP_SETTING.to_string(), Some("false".to_string()));
+        let options = CollectOptions::from_settings(&settings);
+        assert_eq!(options.separator, ", ");
+        assert_eq!(options.order, CollectOrder::Prepend);
+        assert!(!options.dedup);
+    }
+
+    #[test]
+    fn text_fragments_join_in_configured_order() {
+        let mut s = session(" | ", CollectOrder::Append, true);
+        s.push(text("a"));
+        s.push(text("b"));
+        assert_eq!(s.combined_text(), "a | b");
+
+        let mut s = session("\n", CollectOrder::Prepend, true);
+        s.push(text("a"));
+        s.push(text("b"));
+        assert_eq!(s.combined_text(), "b\na");
+    }
+
+    #[test]
+    fn duplicate_fragments_follow_dedup_option() {
+        let mut s = session("\n", CollectOrder::Append, true);
+        assert_eq!(s.push(text("same")), PushOutcome::Added);
+        assert_eq!(s.push(text("same \r\n")), PushOutcome::Duplicate);
+        assert_eq!(s.fragments, 1);
+
+        let mut s = session("\n", CollectOrder::Append, false);
+        s.push(text("same"));
+        assert_eq!(s.push(text("same")), PushOutcome::Added);
+        assert_eq!(s.combined_text(), "same\nsame");
+    }
+
+    #[test]
+    fn html_is_built_when_any_fragment_has_html() {
+        let mut s = session("\n", CollectOrder::Append, true);
+        s.push(text("a < b"));
+        assert!(s.combined_html().is_none());
+        s.push(Fragment::Text {
+            text: "bold".into(),
+            html: Some("<b>bold</b>".into()),
+        });
+        assert_eq!(s.combined_html().unwrap(), "a &lt; b<br><b>bold</b>");
+
+        s.group_id = Some(3);
+        let item = s.build_item();
+        assert_eq!(item.content_type, ContentType::Html);
+        assert_eq!(item.text_content.as_deref(), Some("a < b\nbold"));
+        assert_eq!(item.group_id, Some(3));
+        assert!(!item.content_hash.is_empty());
+    }
+
+    #[test]
+    fn file_paths_are_merged_without_duplicates() {
+        let mut s = session("\n", CollectOrder::Append, false);
+        assert!(s.accepts(FragmentKind::Files));
+        s.push(Fragment::Files {
+            paths: vec!["/a".into(), "/b".into()],
+            staged: vec![],
+        });
+        assert!(!s.accepts(FragmentKind::Text));
+        s.push(Fragment::Files {
+            paths: vec!["/b".into(), "/c".into()],
+            staged: vec![],
+        });
+        assert_eq!(
+            s.push(Fragment::Files {
+                paths: vec!["/a".into()],
+                staged: vec![],
+            }),
+            PushOutcome::Duplicate
+        );
+        assert_eq!(s.paths, vec!["/a", "/b", "/c"]);
+
+        let item = s.build_item();
+        assert_eq!(item.content_type, ContentType::Files);
+        assert_eq!(item.preview.as_deref(), Some("3 files"));
+    }
+}
@@ -1,3 +1,4 @@
+use base64::Engine;
 use blake3::Hasher;
 
 const ZERO_WIDTH_CHARS: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
@@ -245,6 +246,75 @@ pub(crate) fn normalize_rtf_for_hash(rtf_bytes: &[u8]) -> Vec<u8> {
     out.into_bytes()
 }
 
+/// 图片 content_hash 的前缀（图片字节由调用方按块写入 hasher）
+pub(crate) const IMAGE_HASH_PREFIX: &[u8] = b"image:";
+
+/// 计算 content_hash 的内容。捕获与导入共用同一规则，两边的相同内容才能去重
+pub(crate) enum HashInput<'a> {
+    Text(&'a str),
+    Html(&'a str),
+    /// 存储形式的 RTF（`b64:` 前缀的原始字节或 RTF 文本）
+    Rtf(&'a str),
+    Files {
+        paths: &'a [String],
+        /// 虚拟文件（FileGroupDescriptor）的文件名，仅在没有路径时计入
+        descriptor_names: &'a [String],
+        /// 原始 CF_HDROP
+        hdrop: Option<&'a [u8]>,
+    },
+}
+
+pub(crate) fn content_hash(input: HashInput<'_>) -> String {
+    let mut hasher = Hasher::new();
+    match input {
+        HashInput::Text(text) => {
+            if let Some(canonical) = canonical_url_text(text) {
+                hasher.update(b"url:");
+                hasher.update(canonical.as_bytes());
+            } else {
+                hasher.update(b"text:");
+                hasher.update(text.as_bytes());
+            }
+        }
+        HashInput::Html(html) => {
+            hasher.update(b"html:");
+            hasher.update(html.as_bytes());
+        }
+        HashInput::Rtf(rtf) => {
+            hasher.update(b"rtf:");
+            // Decode b64, strip volatile fields (rsid, datastore…), hash cleaned bytes.
+            match rtf
+                .strip_prefix("b64:")
+                .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+            {
+                Some(raw) => hasher.update(&normalize_rtf_for_hash(&raw)),
+                None => hasher.update(rtf.as_bytes()),
+            };
+        }
+        HashInput::Files {
+            paths,
+            descriptor_names,
+            hdrop,
+        } => {
+            hasher.update(b"files:");
+            let names = if paths.is_empty() {
+                descriptor_names
+            } else {
+                paths
+            };
+            for name in names {
+                hasher.update(name.as_bytes());
+                hasher.update(b"|");
+            }
+            if let Some(raw) = hdrop {
+                hasher.update(b"hdrop:");
+                hasher.update(raw);
+            }
+        }
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
 pub(crate) fn compute_semantic_hash(
     content_type: &str,
     text_content: Option<&str>,
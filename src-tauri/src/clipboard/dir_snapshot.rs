@@ -0,0 +1,484 @@
+//! 目录快照：复制文件夹时把目录树 staging 进 blob 存储，原目录移动或删除后粘贴仍能还原出真实文件夹。
+//!
+//! 两种格式：`copy` 逐文件复制为 `blobs/<h2>/<hash>/<目录名>/…`，粘贴时直接使用；
+//! `archive` 压缩为 `blobs/<h2>/<hash>/<目录名>.zip`，首次粘贴时解压到同一 blob 目录。
+//! 快照受总大小与条目数上限约束（超出时放弃整个目录），忽略列表中的名称不进入快照。
+
+use super::file_clipboard::StagedFile;
+use crate::blobs::{self, BlobKind, BlobStore, StoredBlob};
+use crate::database::{Database, SettingsRepository};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// 快照格式：`off`（默认）/ `copy` / `archive`
+pub const MODE_SETTING: &str = "folder_snapshot_mode";
+pub const MAX_MB_SETTING: &str = "folder_snapshot_max_mb";
+pub const MAX_ENTRIES_SETTING: &str = "folder_snapshot_max_entries";
+/// 逗号或换行分隔的名称；`*` 开头表示后缀匹配（如 `*.log`）
+pub const IGNORE_SETTING: &str = "folder_snapshot_ignore";
+
+const DEFAULT_MAX_BYTES: u64 = 200 * 1024 * 1024;
+const DEFAULT_MAX_ENTRIES: usize = 5000;
+const DEFAULT_IGNORE: &[&str] = &["node_modules", ".git", "target"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotFormat {
+    /// 逐文件复制的目录树
+    Copy,
+    /// ZIP 压缩包，粘贴时解压
+    Archive,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotOptions {
+    /// None = 不为目录创建快照
+    pub format: Option<SnapshotFormat>,
+    pub max_bytes: u64,
+    /// 文件与子目录总数上限
+    pub max_entries: usize,
+    pub ignore: Vec<String>,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            format: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ignore: DEFAULT_IGNORE.iter().map(|s| (*s).to_string()).collect(),
+        }
+    }
+}
+
+impl SnapshotOptions {
+    /// 从设置读取；上限未设置或为 0 时使用默认值，忽略列表设置为空字符串时不忽略任何名称
+    pub fn load(db: &Database) -> Self {
+        let keys = [
+            MODE_SETTING,
+            MAX_MB_SETTING,
+            MAX_ENTRIES_SETTING,
+            IGNORE_SETTING,
+        ];
+        let batch = SettingsRepository::new(db).get_batch(&keys);
+        let get = |key: &str| batch.get(key).and_then(|v| v.as_deref());
+        let defaults = Self::default();
+
+        Self {
+            format: match get(MODE_SETTING) {
+                Some("copy") => Some(SnapshotFormat::Copy),
+                Some("archive") => Some(SnapshotFormat::Archive),
+                _ => None,
+            },
+            max_bytes: get(MAX_MB_SETTING)
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|mb| *mb > 0)
+                .map_or(defaults.max_bytes, |mb| mb.saturating_mul(1024 * 1024)),
+            max_entries: get(MAX_ENTRIES_SETTING)
+                .and_then(|s| s.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_entries),
+            ignore: get(IGNORE_SETTING).map_or(defaults.ignore, parse_ignore_list),
+        }
+    }
+}
+
+pub fn parse_ignore_list(raw: &str) -> Vec<String> {
+    raw.split([',', ';', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_ignored(name: &str, ignore: &[String]) -> bool {
+    ignore
+        .iter()
+        .any(|pattern| match pattern.strip_prefix('*') {
+            Some(suffix) => name
+                .to_ascii_lowercase()
+                .ends_with(&suffix.to_ascii_lowercase()),
+            None => name.eq_ignore_ascii_case(pattern),
+        })
+}
+
+/// 目录扫描结果，相对路径以 `/` 分隔并排序
+#[derive(Debug, Default)]
+pub struct DirScan {
+    /// 子目录（含空目录）
+    pub dirs: Vec<String>,
+    pub files: Vec<(String, u64)>,
+    pub total_bytes: u64,
+    /// 被忽略的条目数（忽略列表、符号链接、非 UTF-8 名称）
+    pub skipped: usize,
+}
+
+/// 扫描目录树（不跟随符号链接），超出大小或条目数上限时返回错误
+pub fn scan(root: &Path, options: &SnapshotOptions) -> io::Result<DirScan> {
+    let mut scan = DirScan::default();
+    let mut stack = vec![String::new()];
+    while let Some(rel) = stack.pop() {
+        for entry in std::fs::read_dir(root.join(&rel))? {
+            let entry = entry?;
+            let Ok(name) = entry.file_name().into_string() else {
+                scan.skipped += 1;
+                continue;
+            };
+            if is_ignored(&name, &options.ignore) {
+                scan.skipped += 1;
+                continue;
+            }
+            let child = if rel.is_empty() {
+                name
+            } else {
+                format!("{rel}/{name}")
+            };
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                scan.dirs.push(child.clone());
+                stack.push(child);
+            } else if file_type.is_file() {
+                let size = entry.metadata()?.len();
+                scan.total_bytes += size;
+                scan.files.push((child, size));
+            } else {
+                scan.skipped += 1;
+                continue;
+            }
+            if scan.dirs.len() + scan.files.len() > options.max_entries
+                || scan.total_bytes > options.max_bytes
+            {
+                return Err(io::Error::other(format!(
+                    "folder exceeds snapshot limits ({} entries / {} bytes)",
+                    options.max_entries, options.max_bytes
+                )));
+            }
+        }
+    }
+    scan.dirs.sort();
+    scan.files.sort();
+    Ok(scan)
+}
+
+/// 把扫描过的目录存入 blob 存储；`on_progress` 回调已复制的源文件字节数，返回 false 时中止
+pub fn stage_dir(
+    store: &BlobStore,
+    path: &str,
+    format: SnapshotFormat,
+    scan: &DirScan,
+    on_progress: impl FnMut(u64) -> bool,
+) -> io::Result<StagedFile> {
+    let root = Path::new(path);
+    let name = blobs::safe_name(
+        &root
+            .file_name()
+            .map_or_else(|| "folder".to_string(), |n| n.to_string_lossy().to_string()),
+    );
+    let blob = match format {
+        SnapshotFormat::Copy => snapshot_copy(store, root, &name, scan, on_progress),
+        SnapshotFormat::Archive => snapshot_archive(store, root, &name, scan, on_progress),
+    }?;
+    debug!(
+        "Snapshotted folder {} ({} files, {} skipped)",
+        path,
+        scan.files.len(),
+        scan.skipped
+    );
+    Ok(StagedFile {
+        original: path.to_string(),
+        staged: blob.path.to_string_lossy().to_string(),
+        size: scan.total_bytes,
+        snapshot: Some(format),
+    })
+}
+
+fn tree_dir_line(rel: &str) -> String {
+    format!("d {rel}\n")
+}
+
+fn tree_file_line(rel: &str, hash: &str) -> String {
+    format!("f {rel} {hash}\n")
+}
+
+/// 按快照规则重新计算已存目录树的哈希（存储检查用）
+pub fn tree_hash(root: &Path) -> io::Result<String> {
+    let options = SnapshotOptions {
+        max_bytes: u64::MAX,
+        max_entries: usize::MAX,
+        ignore: Vec::new(),
+        ..Default::default()
+    };
+    let scan = scan(root, &options)?;
+    let mut tree = blake3::Hasher::new();
+    for dir in &scan.dirs {
+        tree.update(tree_dir_line(dir).as_bytes());
+    }
+    for (rel, _) in &scan.files {
+        let mut file = std::fs::File::open(root.join(rel))?;
+        let mut hasher = blake3::Hasher::new();
+        io::copy(&mut file, &mut hasher)?;
+        tree.update(tree_file_line(rel, &hasher.finalize().to_hex()).as_bytes());
+    }
+    Ok(tree.finalize().to_hex().to_string())
+}
+
+/// 逐文件复制到临时目录，哈希按（相对路径, 文件内容哈希）列表计算，相同目录树只存一份
+fn snapshot_copy(
+    store: &BlobStore,
+    root: &Path,
+    name: &str,
+    scan: &DirScan,
+    mut on_progress: impl FnMut(u64) -> bool,
+) -> io::Result<StoredBlob> {
+    let tmp = store.temp_path()?;
+    let copied = (|| {
+        let mut tree = blake3::Hasher::new();
+        std::fs::create_dir_all(&tmp)?;
+        for dir in &scan.dirs {
+            std::fs::create_dir_all(tmp.join(dir))?;
+            tree.update(tree_dir_line(dir).as_bytes());
+        }
+        let mut done = 0u64;
+        for (rel, _) in &scan.files {
+            let mut reader = std::fs::File::open(root.join(rel))?;
+            let mut writer = std::fs::File::create(tmp.join(rel))?;
+            let base = done;
+            let (hash, size) =
+                blobs::copy_hashed(&mut reader, &mut writer, |n| on_progress(base + n))?;
+            done += size;
+            tree.update(tree_file_line(rel, &hash).as_bytes());
+        }
+        Ok((tree.finalize().to_hex().to_string(), done))
+    })();
+    match copied {
+        Ok((hash, size)) => store.put_dir(&tmp, &hash, size, name),
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&tmp);
+            Err(e)
+        }
+    }
+}
+
+/// 压缩为 `<目录名>.zip`（条目以目录名为根，时间戳固定，相同目录得到相同压缩包）
+fn snapshot_archive(
+    store: &BlobStore,
+    root: &Path,
+    name: &str,
+    scan: &DirScan,
+    mut on_progress: impl FnMut(u64) -> bool,
+) -> io::Result<StoredBlob> {
+    let tmp = store.temp_path()?;
+    let written = (|| -> io::Result<()> {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&tmp)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(zip::DateTime::default());
+        zip.add_directory(format!("{name}/"), options)?;
+        for dir in &scan.dirs {
+            zip.add_directory(format!("{name}/{dir}/"), options)?;
+        }
+        let mut done = 0u64;
+        for (rel, size) in &scan.files {
+            zip.start_file(
+                format!("{name}/{rel}"),
+                options.large_file(*size > u64::from(u32::MAX)),
+            )?;
+            let mut reader = std::fs::File::open(root.join(rel))?;
+            let base = done;
+            let (_, size) = blobs::copy_hashed(&mut reader, &mut zip, |n| on_progress(base + n))?;
+            done += size;
+        }
+        zip.finish()?;
+        Ok(())
+    })();
+    let result =
+        written.and_then(|()| store.put_moved(&tmp, &format!("{name}.zip"), BlobKind::Dir));
+    let _ = std::fs::remove_file(&tmp);
+    result
+}
+
+/// 还原 ZIP 快照：首次调用时解压到压缩包所在的 blob 目录，返回还原出的文件夹路径
+pub fn restore_archive(archive: &Path) -> io::Result<PathBuf> {
+    let (Some(dir), Some(name)) = (archive.parent(), archive.file_stem()) else {
+        return Err(io::Error::other("invalid snapshot archive path"));
+    };
+    let target = dir.join(name);
+    if target.is_dir() {
+        return Ok(target);
+    }
+    let tmp = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+    let extracted = (|| -> io::Result<()> {
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(archive)?)?;
+        zip.extract(&tmp)?;
+        std::fs::rename(tmp.join(name), &target)
+    })();
+    let _ = std::fs::remove_dir_all(&tmp);
+    match extracted {
+        Ok(()) => {
+            debug!("Restored folder snapshot {:?}", target);
+            Ok(target)
+        }
+        // 并发还原时另一方已完成
+        Err(_) if target.is_dir() => Ok(target),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::file_clipboard;
+    use crate::test_support::temp_dir;
+
+    /// project/{a.txt, src/lib.rs, empty/, node_modules/x.js, .git/HEAD}
+    fn make_project(dir: &Path) -> PathBuf {
+        let project = dir.join("project");
+        for sub in ["src", "empty", "node_modules", ".git"] {
+            std::fs::create_dir_all(project.join(sub)).unwrap();
+        }
+        std::fs::write(project.join("a.txt"), b"alpha").unwrap();
+        std::fs::write(project.join("src").join("lib.rs"), b"fn main() {}").unwrap();
+        std::fs::write(project.join("node_modules").join("x.js"), b"x").unwrap();
+        std::fs::write(project.join(".git").join("HEAD"), b"ref").unwrap();
+        project
+    }
+
+    fn options(format: SnapshotFormat) -> SnapshotOptions {
+        SnapshotOptions {
+            format: Some(format),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scan_honors_ignore_list_and_limits() {
+        let dir = temp_dir("scan");
+        let project = make_project(&dir);
+
+        let scan = scan(&project, &options(SnapshotFormat::Copy)).unwrap();
+        assert_eq!(scan.dirs, vec!["empty".to_string(), "src".to_string()]);
+        assert_eq!(
+            scan.files,
+            vec![("a.txt".to_string(), 5), ("src/lib.rs".to_string(), 12)]
+        );
+        assert_eq!(scan.total_bytes, 17);
+        assert_eq!(scan.skipped, 2);
+
+        let small = SnapshotOptions {
+            max_bytes: 10,
+            ..options(SnapshotFormat::Copy)
+        };
+        assert!(super::scan(&project, &small).is_err());
+        let few = SnapshotOptions {
+            max_entries: 2,
+            ..options(SnapshotFormat::Copy)
+        };
+        assert!(super::scan(&project, &few).is_err());
+
+        assert_eq!(
+            parse_ignore_list("node_modules, *.LOG\n.git;"),
+            vec!["node_modules", "*.LOG", ".git"]
+        );
+        assert!(is_ignored("debug.log", &parse_ignore_list("*.LOG")));
+        assert!(!is_ignored("log", &parse_ignore_list("*.log")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn snapshots_restore_real_folders() {
+        let dir = temp_dir("stage");
+        let project = make_project(&dir);
+        let path = project.to_string_lossy().to_string();
+        let db = Database::new(dir.join("clipboard.db")).unwrap();
+        let store = BlobStore::new(&db, dir.join(blobs::DIR_NAME));
+
+        let mut restored = Vec::new();
+        for format in [SnapshotFormat::Copy, SnapshotFormat::Archive] {
+            let scan = scan(&project, &options(format)).unwrap();
+            let mut reported = 0;
+            let staged = stage_dir(&store, &path, format, &scan, |n| {
+                reported = n;
+                true
+            })
+            .unwrap();
+            assert_eq!(reported, 17);
+            assert_eq!(staged.size, 17);
+            assert_eq!(staged.snapshot, Some(format));
+            assert!(blobs::hash_from_path(&staged.staged).is_some());
+
+            let folder = match format {
+                SnapshotFormat::Copy => PathBuf::from(&staged.staged),
+                SnapshotFormat::Archive => {
+                    assert!(staged.staged.ends_with("project.zip"));
+                    // 原文件夹已删除时，只读解析返回压缩包而不解压，粘贴时才还原
+                    let missing = dir.join("gone").to_string_lossy().to_string();
+                    let payload = file_clipboard::FilePayload {
+                        staged: vec![StagedFile {
+                            original: missing.clone(),
+                            ..staged.clone()
+                        }],
+                        ..Default::default()
+                    };
+                    let originals = [missing];
+                    assert_eq!(
+                        file_clipboard::resolve_paths(&originals, Some(&payload)),
+                        vec![staged.staged.clone()]
+                    );
+                    assert!(!Path::new(&staged.staged).with_extension("").exists());
+                    let restored = file_clipboard::restore_paths(&originals, Some(&payload));
+                    let folder = restore_archive(Path::new(&staged.staged)).unwrap();
+                    assert_eq!(restored, vec![folder.to_string_lossy().to_string()]);
+                    assert_eq!(restore_archive(Path::new(&staged.staged)).unwrap(), folder);
+                    folder
+                }
+            };
+            assert_eq!(folder.file_name().unwrap(), "project");
+            if format == SnapshotFormat::Copy {
+                assert_eq!(
+                    blobs::hash_from_path(&staged.staged),
+                    Some(tree_hash(&folder).unwrap().as_str())
+                );
+            }
+            restored.push(folder);
+
+            // 相同目录再次快照时复用同一对象
+            let again = stage_dir(&store, &path, format, &scan, |_| true).unwrap();
+            assert_eq!(again.staged, staged.staged);
+        }
+
+        for folder in restored {
+            assert_eq!(std::fs::read(folder.join("a.txt")).unwrap(), b"alpha");
+            assert_eq!(
+                std::fs::read(folder.join("src").join("lib.rs")).unwrap(),
+                b"fn main() {}"
+            );
+            assert!(folder.join("empty").is_dir());
+            assert!(!folder.join("node_modules").exists());
+            assert!(!folder.join(".git").exists());
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cancelled_snapshot_leaves_no_object() {
+        let dir = temp_dir("cancel");
+        let project = make_project(&dir);
+        let db = Database::new(dir.join("clipboard.db")).unwrap();
+        let store = BlobStore::new(&db, dir.join(blobs::DIR_NAME));
+
+        for format in [SnapshotFormat::Copy, SnapshotFormat::Archive] {
+            let scan = scan(&project, &options(format)).unwrap();
+            let err = stage_dir(&store, &project.to_string_lossy(), format, &scan, |_| false)
+                .unwrap_err();
+            assert!(blobs::is_copy_cancelled(&err));
+        }
+        let leftovers: Vec<_> = std::fs::read_dir(store.root()).unwrap().flatten().collect();
+        assert!(leftovers.is_empty(), "temp files are removed");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
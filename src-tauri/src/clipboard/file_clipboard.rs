@@ -1,5 +1,7 @@
 //! Windows 文件剪贴板：捕获/还原 CF_HDROP 与伴生格式，支持文件内容 staging。
 
+use super::dir_snapshot::{self, SnapshotFormat};
+use crate::blobs::{BlobKind, BlobStore};
 use base64::Engine;
 use clipboard_rs::{Clipboard as ClipboardTrait, ClipboardContext, ContentFormat};
 use serde::{Deserialize, Serialize};
@@ -36,6 +38,9 @@ pub struct StagedFile {
     pub original: String,
     pub staged: String,
     pub size: u64,
+    /// 目录快照格式，普通文件为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<SnapshotFormat>,
 }
 
 const FILE_EXTRA_FORMATS: &[&str] = &[
@@ -146,12 +151,9 @@ fn capture_extra_formats(ctx: &ClipboardContext) -> Vec<(String, Vec<u8>)> {
     out
 }
 
-pub fn build_payload(
-    capture: &FileCaptureData,
-    staged_dir: &Path,
-    max_stage_bytes: u64,
-) -> FilePayload {
-    let mut payload = FilePayload {
+/// 构建不含 staging 副本的 payload（文件副本由后台 staging 队列补写）
+pub fn build_payload(capture: &FileCaptureData) -> FilePayload {
+    FilePayload {
         hdrop_b64: capture
             .hdrop_raw
             .as_ref()
@@ -165,54 +167,57 @@ pub fn build_payload(
             })
             .collect(),
         staged: Vec::new(),
-    };
-
-    if capture.paths.is_empty() {
-        return payload;
     }
+}
 
+/// 需要 staging 的文件及大小：仅绝对路径的普通文件，且不超过大小上限（0 = 默认 50MB）；
+/// `include_dirs` 时目录也加入（大小记为 0，由快照扫描确定）
+pub fn stageable_files(
+    paths: &[String],
+    max_stage_bytes: u64,
+    include_dirs: bool,
+) -> Vec<(String, u64)> {
     let stage_limit = if max_stage_bytes > 0 {
         max_stage_bytes
     } else {
         DEFAULT_MAX_STAGE_BYTES
     };
-    std::fs::create_dir_all(staged_dir).ok();
-
-    for path in &capture.paths {
-        let src = Path::new(path);
-        if !src.is_file() {
-            continue;
-        }
-        let Ok(meta) = std::fs::metadata(src) else {
-            continue;
-        };
-        let size = meta.len();
-        if size > stage_limit {
-            debug!("Skip staging large file {} ({} bytes)", path, size);
-            continue;
-        }
-
-        let file_name = src.file_name().and_then(|n| n.to_str()).unwrap_or("file");
-        let path_hash = &blake3::hash(path.as_bytes()).to_hex()[..8];
-        let staged_path = staged_dir.join(format!("{path_hash}_{file_name}"));
-        if staged_path.exists() {
-            payload.staged.push(StagedFile {
-                original: path.clone(),
-                staged: staged_path.to_string_lossy().to_string(),
-                size,
-            });
-            continue;
-        }
-        if std::fs::copy(src, &staged_path).is_ok() {
-            payload.staged.push(StagedFile {
-                original: path.clone(),
-                staged: staged_path.to_string_lossy().to_string(),
-                size,
-            });
-        }
-    }
+    paths
+        .iter()
+        .filter(|path| Path::new(path).is_absolute())
+        .filter_map(|path| {
+            let meta = std::fs::metadata(path).ok()?;
+            if meta.is_dir() && include_dirs {
+                return Some((path.clone(), 0));
+            }
+            if !meta.is_file() {
+                return None;
+            }
+            if meta.len() > stage_limit {
+                debug!("Skip staging large file {} ({} bytes)", path, meta.len());
+                return None;
+            }
+            Some((path.clone(), meta.len()))
+        })
+        .collect()
+}
 
-    payload
+/// 把单个文件复制进 blob 存储；`on_progress` 返回 false 时中止
+pub fn stage_file(
+    store: &BlobStore,
+    path: &str,
+    size: u64,
+    on_progress: impl FnMut(u64) -> bool,
+) -> std::io::Result<StagedFile> {
+    let src = Path::new(path);
+    let file_name = src.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let blob = store.put_file_with_progress(src, file_name, BlobKind::File, on_progress)?;
+    Ok(StagedFile {
+        original: path.to_string(),
+        staged: blob.path.to_string_lossy().to_string(),
+        size,
+        snapshot: None,
+    })
 }
 
 pub fn parse_file_paths(raw: Option<&str>) -> Vec<String> {
@@ -330,14 +335,19 @@ pub fn file_entry_preview(paths: &[String], extra: &[(String, Vec<u8>)]) -> Stri
     }
 }
 
+/// 原路径不存在时回退到 staging 副本（只读：ZIP 目录快照返回压缩包路径，不解压）
 pub fn resolve_paths(paths: &[String], payload: Option<&FilePayload>) -> Vec<String> {
-    let staged_map: std::collections::HashMap<&str, &str> = payload
-        .map(|p| {
-            p.staged
-                .iter()
-                .map(|s| (s.original.as_str(), s.staged.as_str()))
-                .collect()
-        })
+    resolve_with(paths, payload, false)
+}
+
+/// 粘贴用的路径：同 [`resolve_paths`]，ZIP 目录快照在此首次解压为文件夹
+pub fn restore_paths(paths: &[String], payload: Option<&FilePayload>) -> Vec<String> {
+    resolve_with(paths, payload, true)
+}
+
+fn resolve_with(paths: &[String], payload: Option<&FilePayload>, restore: bool) -> Vec<String> {
+    let staged_map: std::collections::HashMap<&str, &StagedFile> = payload
+        .map(|p| p.staged.iter().map(|s| (s.original.as_str(), s)).collect())
         .unwrap_or_default();
 
     paths
@@ -348,7 +358,13 @@ pub fn resolve_paths(paths: &[String], payload: Option<&FilePayload>) -> Vec<Str
                 return path.clone();
             }
             if let Some(staged) = staged_map.get(path.as_str()) {
-                return (*staged).to_string();
+                if restore && staged.snapshot == Some(SnapshotFormat::Archive) {
+                    match dir_snapshot::restore_archive(Path::new(&staged.staged)) {
+                        Ok(folder) => return folder.to_string_lossy().to_string(),
+                        Err(e) => warn!("Failed to restore folder snapshot of {}: {}", path, e),
+                    }
+                }
+                return staged.staged.clone();
             }
             path.clone()
         })
@@ -362,7 +378,7 @@ pub fn write_files_to_clipboard(
 ) -> Result<(), String> {
     let paths = parse_file_paths(file_paths);
     let payload = decode_payload(file_payload);
-    let resolved = resolve_paths(&paths, payload.as_ref());
+    let resolved = restore_paths(&paths, payload.as_ref());
 
     if resolved.is_empty() && payload.as_ref().is_none_or(|p| p.hdrop_b64.is_none()) {
         return Err("Item has no file paths".to_string());
@@ -420,12 +436,13 @@ fn decode_b64(raw: Option<&String>) -> Option<Vec<u8>> {
     base64::engine::general_purpose::STANDARD.decode(raw).ok()
 }
 
+/// 合并粘贴的文件列表（去重，ZIP 目录快照解压为文件夹）
 pub fn merge_file_paths(items: &[(Option<&str>, Option<&str>)]) -> Vec<String> {
     let mut merged = Vec::new();
     for (paths_json, payload_raw) in items {
         let paths = parse_file_paths(*paths_json);
         let payload = decode_payload(*payload_raw);
-        let resolved = resolve_paths(&paths, payload.as_ref());
+        let resolved = restore_paths(&paths, payload.as_ref());
         for path in resolved {
             if !merged.iter().any(|p| p == &path) {
                 merged.push(path);
@@ -451,6 +468,7 @@ mod tests {
                 original: "C:\\a.txt".into(),
                 staged: "D:\\staged\\a.txt".into(),
                 size: 3,
+                snapshot: None,
             }],
         };
         let json = encode_payload(&payload);
@@ -558,6 +576,7 @@ mod tests {
                 original: "C:\\missing.txt".into(),
                 staged: "D:\\staged\\missing.txt".into(),
                 size: 1,
+                snapshot: None,
             }],
             ..Default::default()
         };
@@ -573,11 +592,13 @@ mod tests {
                     original: "C:\\a.txt".into(),
                     staged: "D:\\staged\\a.txt".into(),
                     size: 1,
+                    snapshot: None,
                 },
                 StagedFile {
                     original: "C:\\b.txt".into(),
                     staged: "D:\\staged\\b.txt".into(),
                     size: 2,
+                    snapshot: None,
                 },
             ],
             ..Default::default()
@@ -1,13 +1,20 @@
+use super::collect::{self, Fragment, FragmentKind};
 use super::file_clipboard::{self, FileCaptureData};
+use super::hooks::{self, CaptureHook, HookGroup, HookInput};
+use super::quota;
+use super::retention::{self, DEFAULT_AUTO_CLEANUP_DAYS, RetentionPolicy};
+use super::rules::{self, CompiledRule, RuleSubject};
 use super::source_app::{self, SourceAppInfo};
+use super::staging;
 use super::{
-    canonical_url_text, compute_semantic_hash, is_url, normalize_rtf_for_hash,
-    semantic_hash_from_text,
+    HashInput, IMAGE_HASH_PREFIX, canonical_url_text, classify_text, compute_semantic_hash,
+    content_hash, is_url, semantic_hash_from_text,
 };
+use crate::blobs::{self, BlobKind, BlobStore};
 use crate::database::{
-    ClipboardRepository, ContentType, Database, NewClipboardItem, SettingsRepository,
+    CaptureRule, ClipboardRepository, ContentType, Database, GroupRepository, NewClipboardItem,
+    RuleRepository, SettingsRepository, TagRepository,
 };
-use base64::Engine;
 use blake3::Hasher;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -19,7 +26,6 @@ use crate::file_preview_limits::{
 const DEFAULT_MAX_CONTENT_SIZE: usize = 1_048_576;
 const MAX_PREVIEW_LENGTH: usize = 200;
 const DEFAULT_MAX_HISTORY_COUNT: i64 = 0;
-const DEFAULT_AUTO_CLEANUP_DAYS: i64 = 30;
 
 /// 通配符匹配（支持 * 和 ?，不区分大小写，O(n) 空间）
 fn wildcard_match(pattern: &str, text: &str) -> bool {
@@ -53,7 +59,12 @@ fn wildcard_match(pattern: &str, text: &str) -> bool {
 
 /// 检查来源应用是否匹配过滤规则
 /// 支持通配符模式和普通子串匹配，匹配目标：应用名、进程名、进程路径
-fn matches_app_filter(filter: &str, app_name: &str, exe_name: &str, exe_path: &str) -> bool {
+pub(super) fn matches_app_filter(
+    filter: &str,
+    app_name: &str,
+    exe_name: &str,
+    exe_path: &str,
+) -> bool {
     let filter = filter.trim();
     if filter.is_empty() {
         return false;
@@ -148,6 +159,14 @@ struct ContentHashes {
     semantic_hash: String,
 }
 
+/// [`ClipboardHandler::capture`] 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Captured {
+    pub id: i64,
+    /// false 表示未新建条目：去重命中（置顶已有条目）或并入了收集模式的合并条目
+    pub inserted: bool,
+}
+
 /// 处理剪贴板内容所需的设置，批量读取避免多次数据库查询
 struct ProcessSettings {
     max_content_size: usize,
@@ -156,6 +175,8 @@ struct ProcessSettings {
     max_history_count: i64,
     auto_cleanup_days: i64,
     max_image_size_kb: u64,
+    ocr_enabled: bool,
+    capture_hooks: Vec<CaptureHook>,
 }
 
 /// 剪贴板变更热路径所需的设置，单次批量查询
@@ -242,8 +263,13 @@ impl ClipChangeSettings {
 pub struct ClipboardHandler {
     repository: ClipboardRepository,
     settings_repo: SettingsRepository,
-    images_path: PathBuf,
-    icons_path: PathBuf,
+    group_repo: GroupRepository,
+    rule_repo: RuleRepository,
+    tag_repo: TagRepository,
+    /// 已编译的捕获规则缓存：(规则版本号, 规则列表)
+    rules_cache: parking_lot::Mutex<(u64, Vec<CompiledRule>)>,
+    /// 图片、图标与 staging 文件的内容寻址存储
+    blobs: BlobStore,
     /// 内存级去重：最近一次成功处理的内容哈希，防止快速连续事件绕过 DB dedup
     last_content_hash: parking_lot::Mutex<String>,
 }
@@ -252,15 +278,20 @@ impl ClipboardHandler {
     pub fn new(db: &Database, images_path: PathBuf) -> Self {
         std::fs::create_dir_all(&images_path).ok();
 
-        // 图标目录与图片目录同级
-        let icons_path = images_path.parent().unwrap_or(&images_path).join("icons");
-        std::fs::create_dir_all(&icons_path).ok();
+        // blob 目录与图片目录同级
+        let blobs_root = images_path
+            .parent()
+            .unwrap_or(&images_path)
+            .join(blobs::DIR_NAME);
 
         Self {
             repository: ClipboardRepository::new(db),
             settings_repo: SettingsRepository::new(db),
-            images_path,
-            icons_path,
+            group_repo: GroupRepository::new(db),
+            rule_repo: RuleRepository::new(db),
+            tag_repo: TagRepository::new(db),
+            rules_cache: parking_lot::Mutex::new((0, Vec::new())),
+            blobs: BlobStore::new(db, blobs_root),
             last_content_hash: parking_lot::Mutex::new(String::new()),
         }
     }
@@ -272,8 +303,10 @@ impl ClipboardHandler {
             "dedup_strategy",
             "text_dedup_mode",
             "max_history_count",
-            "auto_cleanup_days",
+            retention::AUTO_CLEANUP_SETTING,
             "max_image_size_kb",
+            "ocr_enabled",
+            hooks::CAPTURE_HOOKS_SETTING,
         ];
         let batch = self.settings_repo.get_batch(&keys);
 
@@ -302,7 +335,7 @@ impl ClipboardHandler {
             .unwrap_or(DEFAULT_MAX_HISTORY_COUNT);
 
         let auto_cleanup_days = batch
-            .get("auto_cleanup_days")
+            .get(retention::AUTO_CLEANUP_SETTING)
             .and_then(|v| v.as_deref())
             .and_then(|s| s.parse::<i64>().ok())
             .unwrap_or(DEFAULT_AUTO_CLEANUP_DAYS);
@@ -313,6 +346,17 @@ impl ClipboardHandler {
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(DEFAULT_MAX_IMAGE_SIZE_KB);
 
+        let ocr_enabled = batch
+            .get("ocr_enabled")
+            .and_then(|v| v.as_deref())
+            .is_none_or(|v| v != "false");
+
+        let capture_hooks = hooks::parse_hooks(
+            batch
+                .get(hooks::CAPTURE_HOOKS_SETTING)
+                .and_then(|v| v.as_deref()),
+        );
+
         ProcessSettings {
             max_content_size,
             dedup_strategy,
@@ -320,6 +364,152 @@ impl ClipboardHandler {
             max_history_count,
             auto_cleanup_days,
             max_image_size_kb,
+            ocr_enabled,
+            capture_hooks,
+        }
+    }
+
+    /// 构造传给捕获钩子的条目描述
+    fn hook_input(
+        content: &ClipboardContent,
+        source: Option<&SourceAppInfo>,
+        group_id: Option<i64>,
+    ) -> HookInput {
+        let mut input = HookInput {
+            version: HookInput::VERSION,
+            content_type: String::new(),
+            text: None,
+            file_paths: None,
+            image_path: None,
+            source_app_name: source.map(|s| s.app_name.clone()),
+            source_exe_path: source.map(|s| s.exe_path.clone()),
+            group_id,
+        };
+        match content {
+            ClipboardContent::Text(text) => {
+                input.content_type = if is_url(text) { "url" } else { "text" }.to_string();
+                input.text = Some(text.clone());
+            }
+            ClipboardContent::Html { text, .. } => {
+                input.content_type = "html".to_string();
+                input.text.clone_from(text);
+            }
+            ClipboardContent::Rtf { text, .. } => {
+                input.content_type = "rtf".to_string();
+                input.text.clone_from(text);
+            }
+            ClipboardContent::ImageFile(capture) => {
+                input.content_type = "image".to_string();
+                input.image_path = Some(capture.temp_path.to_string_lossy().into_owned());
+            }
+            ClipboardContent::Files(files) => {
+                input.content_type = "files".to_string();
+                input.file_paths = Some(files.paths.clone());
+            }
+        }
+        input
+    }
+
+    /// 应用钩子改写后的文本；富文本改写后不再与原格式一致，降级为纯文本
+    fn apply_hook_text(content: &mut ClipboardContent, text: String) {
+        match content {
+            ClipboardContent::Text(current) => *current = text,
+            ClipboardContent::Html { .. } | ClipboardContent::Rtf { .. } => {
+                *content = ClipboardContent::Text(text);
+            }
+            ClipboardContent::ImageFile(_) | ClipboardContent::Files(_) => {
+                warn!("Capture hook returned text for non-text content, ignoring");
+            }
+        }
+    }
+
+    /// 将钩子指定的分组解析为 group_id；分组不存在时保持原分组
+    fn resolve_hook_group(&self, target: HookGroup) -> Option<i64> {
+        let resolved = match &target {
+            HookGroup::Id(id) => self.group_repo.exists(*id).map(|ok| ok.then_some(*id)),
+            HookGroup::Name(name) => self.group_repo.find_id_by_name(name),
+        };
+        match resolved {
+            Ok(Some(id)) => Some(id),
+            Ok(None) => {
+                warn!(
+                    "Capture hook group {:?} not found, keeping current group",
+                    target
+                );
+                None
+            }
+            Err(e) => {
+                warn!("Failed to resolve capture hook group {:?}: {}", target, e);
+                None
+            }
+        }
+    }
+
+    /// 匹配捕获规则，返回首个命中的规则（规则变更后自动重新加载）
+    fn match_capture_rule(
+        &self,
+        content: &ClipboardContent,
+        source: Option<&SourceAppInfo>,
+    ) -> Option<CaptureRule> {
+        let mut cache = self.rules_cache.lock();
+        let version = rules::rules_version();
+        if cache.0 != version {
+            match self.rule_repo.list() {
+                Ok(list) => *cache = (version, rules::compile_rules(list)),
+                Err(e) => {
+                    warn!("Failed to load capture rules: {}", e);
+                    return None;
+                }
+            }
+        }
+        if cache.1.is_empty() {
+            return None;
+        }
+
+        let (content_type, text, file_paths) = match content {
+            ClipboardContent::Text(text) => {
+                let kind = if is_url(text) { "url" } else { "text" };
+                (kind, Some(text.as_str()), None)
+            }
+            ClipboardContent::Html { text, .. } => ("html", text.as_deref(), None),
+            ClipboardContent::Rtf { text, .. } => ("rtf", text.as_deref(), None),
+            ClipboardContent::ImageFile(_) => ("image", None, None),
+            ClipboardContent::Files(files) => ("files", None, Some(files.paths.as_slice())),
+        };
+        let subject = RuleSubject {
+            content_type,
+            text,
+            file_paths,
+            source_app_name: source.map(|s| s.app_name.as_str()),
+            source_exe_path: source.map(|s| s.exe_path.as_str()),
+            minute_of_day: rules::current_minute_of_day(),
+        };
+        rules::first_match(&cache.1, &subject).cloned()
+    }
+
+    /// 应用规则的置顶 / 过期效果
+    fn apply_rule_effects(&self, id: i64, rule: Option<&CaptureRule>) {
+        let Some(rule) = rule else {
+            return;
+        };
+        if !rule.pin && rule.ttl_minutes.is_none() {
+            return;
+        }
+        if let Err(e) = self
+            .repository
+            .apply_rule_effects(id, rule.pin, rule.ttl_minutes)
+        {
+            warn!("Failed to apply capture rule '{}': {}", rule.name, e);
+        }
+    }
+
+    /// 钩子追加的标签作为普通标签关联（可用于标签筛选）
+    fn apply_hook_tags(&self, id: i64, tags: &[String]) {
+        if tags.is_empty() {
+            return;
+        }
+        if let Err(e) = self.tag_repo.tag_item_by_names(id, tags) {
+            warn!("Failed to tag item {} from capture hooks: {}", id, e);
         }
     }
 
@@ -381,6 +571,35 @@ impl ClipboardHandler {
         source: Option<SourceAppInfo>,
         group_id: Option<i64>,
     ) -> Result<Option<i64>, String> {
+        Ok(self.capture(content, source, group_id)?.map(|c| c.id))
+    }
+
+    /// 同 [`Self::process`]，并区分新建条目与沿用已有条目
+    pub fn capture(
+        &self,
+        content: ClipboardContent,
+        source: Option<SourceAppInfo>,
+        group_id: Option<i64>,
+    ) -> Result<Option<Captured>, String> {
+        self.capture_with(content, source, group_id, true)
+    }
+
+    /// 保存应用生成的内容（译文、AI / 插件结果、API 新增）：不并入收集模式的合并条目
+    pub fn capture_generated(
+        &self,
+        content: ClipboardContent,
+        group_id: Option<i64>,
+    ) -> Result<Option<Captured>, String> {
+        self.capture_with(content, None, group_id, false)
+    }
+
+    fn capture_with(
+        &self,
+        mut content: ClipboardContent,
+        source: Option<SourceAppInfo>,
+        mut group_id: Option<i64>,
+        collectable: bool,
+    ) -> Result<Option<Captured>, String> {
         // 批量读取所有设置，单次数据库查询替代 5-6 次独立查询
         let settings = self.get_process_settings();
         let max_content_size = settings.max_content_size;
@@ -400,8 +619,43 @@ impl ClipboardHandler {
             }
         }
 
+        // 捕获规则：按顺序匹配首条规则，路由到目标分组（须在去重之前，去重按分组进行）
+        let matched_rule = self.match_capture_rule(&content, source.as_ref());
+        if let Some(rule) = &matched_rule {
+            debug!("Capture rule '{}' matched", rule.name);
+            if rule.target_group_id.is_some() {
+                group_id = rule.target_group_id;
+            }
+        }
+
+        // 用户捕获钩子：可丢弃、改写文本、指定分组或追加标签（须在哈希与去重之前）
+        let mut hook_tags = Vec::new();
+        if !settings.capture_hooks.is_empty() {
+            let outcome = hooks::run_hooks(
+                &settings.capture_hooks,
+                Self::hook_input(&content, source.as_ref(), group_id),
+            );
+            if outcome.dropped {
+                info!("Clipboard content dropped by capture hook");
+                return Ok(None);
+            }
+            if let Some(text) = outcome.text {
+                if text.trim().is_empty() {
+                    info!("Capture hook cleared the text, dropping content");
+                    return Ok(None);
+                }
+                Self::apply_hook_text(&mut content, text);
+            }
+            if let Some(target) = outcome.group {
+                group_id = self.resolve_hook_group(target).or(group_id);
+            }
+            hook_tags = outcome.tags;
+        }
+
         let hashes = self.calculate_hashes(&content)?;
-        let dedup = &settings.dedup_strategy;
+        // 分组可覆盖全局去重策略
+        let group_dedup = group_id.and_then(|id| self.group_repo.dedup_strategy(id).ok().flatten());
+        let dedup = group_dedup.as_ref().unwrap_or(&settings.dedup_strategy);
         let text_like = Self::is_text_like_content(&content);
         let text_dedup_mode = &settings.text_dedup_mode;
         let text_use_strict = text_like && text_dedup_mode == "strict";
@@ -417,6 +671,18 @@ impl ClipboardHandler {
             last.clone_from(&hashes.content_hash);
         }
 
+        // 收集模式：文本类与文件内容并入同一条合并记录，不新建条目
+        if collectable
+            && collect::is_active()
+            && let Some(result) =
+                self.try_collect(&content, &hashes, group_id, settings.max_image_size_kb)
+        {
+            return Ok(result?.map(|id| Captured {
+                id,
+                inserted: false,
+            }));
+        }
+
         if dedup != "always_new"
             && if text_like {
                 if text_use_strict {
@@ -476,8 +742,16 @@ impl ClipboardHandler {
                         .refresh_rich_fields(id, &refreshed)
                         .map_err(|e| e.to_string())?;
                 }
+                // 规则的置顶 / TTL 只作用于新捕获，避免重复复制让已有条目变为会过期；
+                // 分组已在去重前路由，这里只补充钩子标签
+                if let Some(id) = id {
+                    self.apply_hook_tags(id, &hook_tags);
+                }
 
-                return Ok(id);
+                return Ok(id.map(|id| Captured {
+                    id,
+                    inserted: false,
+                }));
             }
         }
 
@@ -485,7 +759,7 @@ impl ClipboardHandler {
             Some(ref info) => {
                 let icon_path = source_app::extract_and_cache_icon(
                     &info.exe_path,
-                    &self.icons_path,
+                    &self.blobs,
                     &info.icon_cache_key,
                 );
                 (Some(info.app_name.clone()), icon_path)
@@ -519,8 +793,7 @@ impl ClipboardHandler {
                 }
                 ClipboardContent::Files(files) => {
                     let files = std::mem::take(files);
-                    // staging 使用 file_clipboard 内置 50MB 上限，与文本长度限制无关
-                    self.process_files(files, &hashes, 0, settings.max_image_size_kb)?
+                    self.process_files(files, &hashes, settings.max_image_size_kb)?
                 }
             }
         };
@@ -529,12 +802,29 @@ impl ClipboardHandler {
         item.source_app_icon = source_app_icon;
         item.group_id = group_id;
 
+        // 文本子类型识别（颜色、代码、JSON 等），URL 已单独成类
+        if matches!(
+            item.content_type,
+            ContentType::Text | ContentType::Html | ContentType::Rtf
+        ) {
+            item.subtypes = item.text_content.as_deref().and_then(classify_text);
+        }
+
         let log_type = format!("{:?}", item.content_type);
+        let quota_type = item.content_type.as_str();
         let log_size = item.byte_size;
         let log_source = item
             .source_app_name
             .clone()
             .unwrap_or_else(|| "unknown".to_string());
+        let ocr_image_path = match item.content_type {
+            ContentType::Image if settings.ocr_enabled => item.image_path.clone(),
+            _ => None,
+        };
+        let stage_paths = match item.content_type {
+            ContentType::Files => item.file_paths.clone(),
+            _ => None,
+        };
 
         let id = self.repository.insert(item).map_err(|e| e.to_string())?;
         info!(
@@ -542,42 +832,127 @@ impl ClipboardHandler {
             id, log_type, log_size, log_source
         );
 
-        // 执行最大历史数限制，清理旧图片
-        if settings.max_history_count > 0 {
-            match self
-                .repository
-                .enforce_max_count(settings.max_history_count, group_id)
-            {
-                Ok((deleted, image_paths, file_payloads)) => {
-                    super::cleanup_deleted_assets(&image_paths, &file_payloads);
-                    if deleted > 0 {
-                        debug!("Enforced max count: removed {} old items", deleted);
-                    }
+        self.apply_rule_effects(id, matched_rule.as_ref());
+        self.apply_hook_tags(id, &hook_tags);
+
+        // 图片 OCR 在后台线程异步执行，不阻塞捕获
+        if let Some(image_path) = ocr_image_path {
+            crate::ocr::enqueue(id, image_path);
+        }
+
+        // 文件副本由后台队列复制，完成后补写 payload
+        // staging 使用 file_clipboard 内置 50MB 上限，与文本长度限制无关
+        if let Some(paths) = stage_paths {
+            staging::enqueue(id, &paths, 0);
+        }
+
+        // 执行最大历史数限制，清理旧图片（分组设置优先，由仓库层解析）
+        match self
+            .repository
+            .enforce_max_count(settings.max_history_count, group_id)
+        {
+            Ok((deleted, image_paths, file_payloads)) => {
+                super::cleanup_deleted_assets(&image_paths, &file_payloads);
+                if deleted > 0 {
+                    debug!("Enforced max count: removed {} old items", deleted);
                 }
-                Err(e) => warn!("Failed to enforce max history count: {}", e),
             }
+            Err(e) => warn!("Failed to enforce max history count: {}", e),
         }
 
-        // 自动清理超过指定天数的旧记录
-        if settings.auto_cleanup_days > 0 {
-            match self
-                .repository
-                .delete_older_than(settings.auto_cleanup_days, group_id)
-            {
-                Ok((deleted, image_paths, file_payloads)) => {
-                    super::cleanup_deleted_assets(&image_paths, &file_payloads);
-                    if deleted > 0 {
-                        info!(
-                            "Auto-cleanup: removed {} items older than {} days",
-                            deleted, settings.auto_cleanup_days
-                        );
-                    }
+        // 按字节的配额（类型、分组、总量），新条目自身不被淘汰
+        let quota_settings = quota::QuotaSettings::load(&self.settings_repo);
+        quota::enforce(&self.repository, &quota_settings, id, quota_type, group_id);
+
+        // 按保留评分自动清理旧记录（分组设置的保留天数优先，由仓库层解析）
+        let retention_policy = RetentionPolicy::load(&self.settings_repo);
+        match self.repository.delete_older_than(
+            settings.auto_cleanup_days,
+            group_id,
+            &retention_policy,
+        ) {
+            Ok((deleted, image_paths, file_payloads)) => {
+                super::cleanup_deleted_assets(&image_paths, &file_payloads);
+                if deleted > 0 {
+                    info!(
+                        "Auto-cleanup: removed {} old items (group: {:?})",
+                        deleted, group_id
+                    );
                 }
-                Err(e) => warn!("Failed to auto-cleanup old items: {}", e),
             }
+            Err(e) => warn!("Failed to auto-cleanup old items: {}", e),
         }
 
-        Ok(Some(id))
+        Ok(Some(Captured { id, inserted: true }))
+    }
+
+    /// 将内容并入收集模式的合并条目；返回 None 表示会话不接受该内容，按普通捕获继续处理
+    fn try_collect(
+        &self,
+        content: &ClipboardContent,
+        hashes: &ContentHashes,
+        group_id: Option<i64>,
+        max_image_size_kb: u64,
+    ) -> Option<Result<Option<i64>, String>> {
+        let mut stage_paths: Option<Vec<String>> = None;
+        let fragment = match content {
+            ClipboardContent::Text(text) if collect::accepts(FragmentKind::Text) => {
+                Fragment::Text {
+                    text: text.clone(),
+                    html: None,
+                }
+            }
+            ClipboardContent::Html { html, text, .. } if collect::accepts(FragmentKind::Text) => {
+                Fragment::Text {
+                    text: text.clone()?,
+                    html: Some(html.clone()),
+                }
+            }
+            ClipboardContent::Rtf { text, .. } if collect::accepts(FragmentKind::Text) => {
+                Fragment::Text {
+                    text: text.clone()?,
+                    html: None,
+                }
+            }
+            ClipboardContent::Files(files)
+                if !files.paths.is_empty() && collect::accepts(FragmentKind::Files) =>
+            {
+                let item = match self.process_files(files.clone(), hashes, max_image_size_kb) {
+                    Ok(item) => item,
+                    Err(e) => return Some(Err(e)),
+                };
+                stage_paths = item.file_paths.clone();
+                Fragment::Files {
+                    paths: item.file_paths.unwrap_or_default(),
+                    staged: file_clipboard::decode_payload(item.file_payload.as_deref())
+                        .map(|payload| payload.staged)
+                        .unwrap_or_default(),
+                }
+            }
+            _ => return None,
+        };
+
+        let result = collect::collect(fragment, group_id, |existing, item| {
+            if let Some(id) = existing
+                && self
+                    .repository
+                    .replace_collected_content(id, &item)
+                    .map_err(|e| e.to_string())?
+            {
+                return Ok(id);
+            }
+            // 首段或合并条目已被删除：新建合并条目
+            self.repository.insert(item).map_err(|e| e.to_string())
+        })?;
+        if let (Ok(Some(id)), Some(paths)) = (&result, stage_paths) {
+            staging::enqueue(*id, &paths, 0);
+        }
+        match &result {
+            Ok(Some(id)) => info!("Collect mode: merged fragment into item {}", id),
+            Ok(None) => debug!("Collect mode: duplicate fragment skipped"),
+            Err(e) => warn!("Collect mode: failed to store fragment: {}", e),
+        }
+        Some(result)
     }
 
     fn get_content_size(&self, content: &ClipboardContent) -> usize {
@@ -628,62 +1003,30 @@ impl ClipboardHandler {
     }
 
     fn calculate_hash(&self, content: &ClipboardContent) -> Result<String, String> {
-        let mut hasher = Hasher::new();
-
-        match content {
-            ClipboardContent::Text(text) => {
-                if let Some(canonical) = canonical_url_text(text) {
-                    hasher.update(b"url:");
-                    hasher.update(canonical.as_bytes());
-                } else {
-                    hasher.update(b"text:");
-                    hasher.update(text.as_bytes());
-                }
-            }
-            ClipboardContent::Html { html, .. } => {
-                hasher.update(b"html:");
-                hasher.update(html.as_bytes());
-            }
-            ClipboardContent::Rtf { rtf, .. } => {
-                hasher.update(b"rtf:");
-                // Decode b64, strip volatile fields (rsid, datastore…), hash cleaned bytes.
-                if let Some(b64) = rtf.strip_prefix("b64:") {
-                    if let Ok(raw) =
-                        base64::engine::general_purpose::STANDARD.decode(b64.as_bytes())
-                    {
-                        let cleaned = normalize_rtf_for_hash(&raw);
-                        hasher.update(&cleaned);
-                    } else {
-                        hasher.update(rtf.as_bytes());
-                    }
-                } else {
-                    hasher.update(rtf.as_bytes());
-                }
-            }
+        let input = match content {
+            ClipboardContent::Text(text) => HashInput::Text(text),
+            ClipboardContent::Html { html, .. } => HashInput::Html(html),
+            ClipboardContent::Rtf { rtf, .. } => HashInput::Rtf(rtf),
             ClipboardContent::ImageFile(capture) => {
-                hasher.update(b"image:");
+                let mut hasher = Hasher::new();
+                hasher.update(IMAGE_HASH_PREFIX);
                 hash_file_into_hasher(&mut hasher, &capture.temp_path)?;
+                return Ok(hasher.finalize().to_hex().to_string());
             }
             ClipboardContent::Files(files) => {
-                hasher.update(b"files:");
-                for file in &files.paths {
-                    hasher.update(file.as_bytes());
-                    hasher.update(b"|");
-                }
-                if files.paths.is_empty() {
-                    for name in file_clipboard::descriptor_file_names(&files.extra_formats) {
-                        hasher.update(name.as_bytes());
-                        hasher.update(b"|");
-                    }
-                }
-                if let Some(ref raw) = files.hdrop_raw {
-                    hasher.update(b"hdrop:");
-                    hasher.update(raw);
-                }
+                let descriptor_names = if files.paths.is_empty() {
+                    file_clipboard::descriptor_file_names(&files.extra_formats)
+                } else {
+                    Vec::new()
+                };
+                return Ok(content_hash(HashInput::Files {
+                    paths: &files.paths,
+                    descriptor_names: &descriptor_names,
+                    hdrop: files.hdrop_raw.as_deref(),
+                }));
             }
-        }
-
-        Ok(hasher.finalize().to_hex().to_string())
+        };
+        Ok(content_hash(input))
     }
 
     fn process_text(
@@ -779,7 +1122,7 @@ impl ClipboardHandler {
         })
     }
 
-    /// 处理图片：将 watcher 写入的临时 PNG rename 到 hash 命名路径
+    /// 处理图片：将 watcher 写入的临时 PNG 移入 blob 存储
     fn process_image_file(
         &self,
         capture: ImageCapture,
@@ -789,10 +1132,6 @@ impl ClipboardHandler {
         let image_width = i64::from(capture.width);
         let image_height = i64::from(capture.height);
 
-        let filename = format!("{}.png", &hashes.content_hash[..32]);
-        let image_path = self.images_path.join(&filename);
-        let image_path_str = image_path.to_string_lossy().to_string();
-
         debug!(
             "Processing image: {}x{}, {} bytes, hash={}",
             image_width,
@@ -801,13 +1140,15 @@ impl ClipboardHandler {
             &hashes.content_hash[..32]
         );
 
-        if image_path.exists() {
-            let _ = std::fs::remove_file(&capture.temp_path);
-        } else if let Err(e) = std::fs::rename(&capture.temp_path, &image_path) {
-            let _ = std::fs::remove_file(&capture.temp_path);
-            return Err(format!("Failed to save image: {e}"));
-        }
-        debug!("Saved image to {:?}", image_path);
+        let blob = self
+            .blobs
+            .put_moved(&capture.temp_path, blobs::IMAGE_NAME, BlobKind::Image)
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&capture.temp_path);
+                format!("Failed to save image: {e}")
+            })?;
+        let image_path_str = blob.path.to_string_lossy().to_string();
+        debug!("Saved image to {:?}", blob.path);
 
         Ok(NewClipboardItem {
             content_type: ContentType::Image,
@@ -826,7 +1167,6 @@ impl ClipboardHandler {
         &self,
         capture: FileCaptureData,
         hashes: &ContentHashes,
-        max_stage_bytes: usize,
         max_image_size_kb: u64,
     ) -> Result<NewClipboardItem, String> {
         debug!("Processing {} file(s)", capture.paths.len());
@@ -859,13 +1199,7 @@ impl ClipboardHandler {
 
         let preview = file_clipboard::file_entry_preview(&capture.paths, &capture.extra_formats);
 
-        let staged_dir = self
-            .images_path
-            .parent()
-            .unwrap_or(&self.images_path)
-            .join("staged")
-            .join(&hashes.content_hash[..8.min(hashes.content_hash.len())]);
-        let payload = file_clipboard::build_payload(&capture, &staged_dir, max_stage_bytes as u64);
+        let payload = file_clipboard::build_payload(&capture);
         let file_payload = Some(file_clipboard::encode_payload(&payload));
 
         Ok(NewClipboardItem {
@@ -880,7 +1214,7 @@ impl ClipboardHandler {
         })
     }
 
-    fn create_preview(text: &str) -> String {
+    pub(crate) fn create_preview(text: &str) -> String {
         let trimmed = text.trim();
         if let Some((idx, _)) = trimmed.char_indices().nth(MAX_PREVIEW_LENGTH) {
             format!("{}...", &trimmed[..idx])
@@ -0,0 +1,676 @@
+//! 捕获钩子：新剪贴板内容入库前依次调用用户配置的外部命令
+//!
+//! 协议：条目以 JSON 写入钩子的 stdin，钩子在 stdout 输出 JSON 判定：
+//!
+//! ```json
+//! { "action": "accept" | "drop" | "modify", "text": "...", "group": "工作", "group_id": 3, "tags": ["ticket"] }
+//! ```
+//!
+//! 所有字段均可省略；stdout 为空视为 accept。给出 `text` 即替换文本内容。
+//! 钩子按配置顺序串行执行，后一个钩子看到的是前一个修改后的内容。
+//!
+//! 沙箱限制：清空环境变量（仅保留 PATH 等基础变量）、工作目录固定为临时目录、
+//! 超时强制结束（连同钩子派生的子进程）、单次捕获的钩子总耗时上限、stdin/stdout 大小上限；
+//! 失败时按 `on_error` 放行或丢弃。
+//! 进程运行逻辑（[`run_sandboxed`]）同时供插件使用。
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// 设置项：钩子列表（JSON 数组）
+pub(crate) const CAPTURE_HOOKS_SETTING: &str = "capture_hooks";
+
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+const MAX_TIMEOUT_MS: u64 = 10_000;
+/// 单次捕获所有钩子的总耗时上限，超出后剩余钩子按失败处理
+const MAX_TOTAL_TIMEOUT_MS: u64 = 10_000;
+/// 最多执行的钩子数量，防止配置失误拖慢每次捕获
+const MAX_HOOKS: usize = 8;
+/// 写入 stdin 的 JSON 上限，超过则跳过钩子
+const MAX_INPUT_BYTES: usize = 4 * 1024 * 1024;
+/// stdout 读取上限，超过视为失败
+const MAX_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
+/// stderr 仅保留前若干字节用于日志
+const MAX_STDERR_BYTES: usize = 4 * 1024;
+/// 透传给钩子进程的环境变量白名单
+const ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "PATHEXT",
+    "SystemRoot",
+    "SYSTEMROOT",
+    "COMSPEC",
+    "TEMP",
+    "TMP",
+    "TMPDIR",
+    "HOME",
+    "USERPROFILE",
+    "LANG",
+    "LC_ALL",
+];
+
+/// 钩子执行失败时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookErrorPolicy {
+    /// 失败时放行（默认，避免脚本问题导致剪贴板记录丢失）
+    #[default]
+    Accept,
+    /// 失败时丢弃本条内容
+    Drop,
+}
+
+/// 单个钩子配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureHook {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// 仅对这些类型生效（text/html/rtf/image/files），空 = 全部
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    #[serde(default)]
+    pub on_error: HookErrorPolicy,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+impl CaptureHook {
+    fn applies_to(&self, content_type: &str) -> bool {
+        self.content_types.is_empty() || self.content_types.iter().any(|t| t == content_type)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.clamp(1, MAX_TIMEOUT_MS))
+    }
+}
+
+/// 解析设置中的钩子列表，过滤禁用项；格式错误时返回空列表
+pub(crate) fn parse_hooks(raw: Option<&str>) -> Vec<CaptureHook> {
+    let Some(raw) = raw.filter(|s| !s.trim().is_empty()) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<Vec<CaptureHook>>(raw) {
+        Ok(hooks) => hooks
+            .into_iter()
+            .filter(|h| h.enabled && !h.command.trim().is_empty())
+            .take(MAX_HOOKS)
+            .collect(),
+        Err(e) => {
+            warn!(
+                "Invalid {} setting, hooks disabled: {}",
+                CAPTURE_HOOKS_SETTING, e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// 写入钩子 stdin 的条目描述
+#[derive(Debug, Clone, Serialize)]
+pub struct HookInput {
+    /// 协议版本
+    pub version: u32,
+    pub content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_paths: Option<Vec<String>>,
+    /// 图片临时文件路径（钩子运行期间有效）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_app_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_exe_path: Option<String>,
+    pub group_id: Option<i64>,
+}
+
+impl HookInput {
+    pub const VERSION: u32 = 1;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum HookAction {
+    #[default]
+    Accept,
+    Drop,
+    Modify,
+}
+
+/// 钩子 stdout 判定
+#[derive(Debug, Clone, Default, Deserialize)]
+struct HookVerdict {
+    #[serde(default)]
+    action: HookAction,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    group_id: Option<i64>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// 分组指定方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookGroup {
+    Id(i64),
+    Name(String),
+}
+
+/// 钩子链执行结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookOutcome {
+    pub dropped: bool,
+    /// 替换后的文本（None = 未修改）
+    pub text: Option<String>,
+    pub group: Option<HookGroup>,
+    pub tags: Vec<String>,
+}
+
+/// 依次执行钩子；任一钩子丢弃即停止
+pub(crate) fn run_hooks(hooks: &[CaptureHook], input: HookInput) -> HookOutcome {
+    run_hooks_within(hooks, input, Duration::from_millis(MAX_TOTAL_TIMEOUT_MS))
+}
+
+/// 在总耗时 `budget` 内依次执行钩子，每个钩子的超时不超过剩余时间
+fn run_hooks_within(hooks: &[CaptureHook], mut input: HookInput, budget: Duration) -> HookOutcome {
+    let deadline = Instant::now() + budget;
+    let mut outcome = HookOutcome::default();
+    for hook in hooks.iter().filter(|h| h.applies_to(&input.content_type)) {
+        let started = Instant::now();
+        let remaining = deadline.saturating_duration_since(started);
+        let verdict = if remaining.is_zero() {
+            Err("time budget for capture hooks exhausted".to_string())
+        } else {
+            serde_json::to_vec(&input)
+                .map_err(|e| e.to_string())
+                .and_then(|payload| {
+                    if payload.len() > MAX_INPUT_BYTES {
+                        return Err(format!("input too large ({} bytes)", payload.len()));
+                    }
+                    run_hook(hook, &payload, hook.timeout().min(remaining))
+                })
+        };
+        let verdict = match verdict {
+            Ok(v) => v,
+            Err(e) => {
+                warn!("Capture hook '{}' failed: {}", hook.name, e);
+                if hook.on_error == HookErrorPolicy::Drop {
+                    outcome.dropped = true;
+                    return outcome;
+                }
+                continue;
+            }
+        };
+        debug!(
+            "Capture hook '{}' -> {:?} in {:?}",
+            hook.name,
+            verdict.action,
+            started.elapsed()
+        );
+
+        if verdict.action == HookAction::Drop {
+            outcome.dropped = true;
+            return outcome;
+        }
+        if let Some(text) = verdict.text {
+            input.text = Some(text.clone());
+            outcome.text = Some(text);
+        }
+        if let Some(id) = verdict.group_id {
+            input.group_id = Some(id);
+            outcome.group = Some(HookGroup::Id(id));
+        } else if let Some(name) = verdict.group.filter(|n| !n.trim().is_empty()) {
+            outcome.group = Some(HookGroup::Name(name.trim().to_string()));
+        }
+        for tag in verdict.tags {
+            // 标签以逗号分隔存储，去掉标签内的逗号
+            let tag = tag.replace(',', " ").trim().to_string();
+            if !tag.is_empty() && !outcome.tags.contains(&tag) {
+                outcome.tags.push(tag);
+            }
+        }
+    }
+    outcome
+}
+
+/// 运行单个钩子进程并解析 stdout
+fn run_hook(hook: &CaptureHook, payload: &[u8], timeout: Duration) -> Result<HookVerdict, String> {
+    let stdout = run_sandboxed(
+        &SandboxedCommand {
+            command: &hook.command,
+            args: &hook.args,
+            current_dir: &std::env::temp_dir(),
+            marker_env: "ELEGANT_CLIPBOARD_HOOK",
+            timeout,
+        },
+        payload,
+    )?;
+    parse_verdict(&stdout)
+}
+
+/// 受限运行的外部命令（捕获钩子与插件共用）
+pub(crate) struct SandboxedCommand<'a> {
+    pub command: &'a str,
+    pub args: &'a [String],
+    pub current_dir: &'a Path,
+    /// 设为 `1` 的环境变量，便于脚本识别调用方
+    pub marker_env: &'a str,
+    pub timeout: Duration,
+}
+
+/// 子进程及其派生的进程：Unix 下为独立进程组，Windows 下归入 Job 对象，超时时整体结束
+struct ProcessTree {
+    child: Child,
+    #[cfg(target_os = "windows")]
+    job: Option<windows::Win32::Foundation::HANDLE>,
+}
+
+impl ProcessTree {
+    fn spawn(cmd: &mut Command) -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+        let child = cmd.spawn()?;
+
+        // 进程启动后才能加入 Job，之前派生的进程（极短窗口）不受管理
+        #[cfg(target_os = "windows")]
+        let job = unsafe {
+            use std::os::windows::io::AsRawHandle;
+            use windows::Win32::Foundation::{CloseHandle, HANDLE};
+            use windows::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW};
+            use windows::core::PCWSTR;
+
+            match CreateJobObjectW(None, PCWSTR::null()) {
+                Ok(job) => match AssignProcessToJobObject(job, HANDLE(child.as_raw_handle())) {
+                    Ok(()) => Some(job),
+                    Err(e) => {
+                        debug!("Failed to assign process to job object: {}", e);
+                        let _ = CloseHandle(job);
+                        None
+                    }
+                },
+                Err(e) => {
+                    debug!("Failed to create job object: {}", e);
+                    None
+                }
+            }
+        };
+
+        Ok(Self {
+            child,
+            #[cfg(target_os = "windows")]
+            job,
+        })
+    }
+
+    /// 强制结束整棵进程树并回收子进程
+    fn kill(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: 仅向 spawn 时以子进程 pid 创建的进程组发送信号
+        unsafe {
+            libc::kill(-(self.child.id() as libc::pid_t), libc::SIGKILL);
+        }
+        #[cfg(target_os = "windows")]
+        if let Some(job) = self.job {
+            // SAFETY: job 句柄在 Drop 前一直有效
+            unsafe {
+                let _ = windows::Win32::System::JobObjects::TerminateJobObject(job, 1);
+            }
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        // 未设置 KILL_ON_JOB_CLOSE：正常结束时插件启动的外部程序不受影响
+        if let Some(job) = self.job.take() {
+            unsafe {
+                let _ = windows::Win32::Foundation::CloseHandle(job);
+            }
+        }
+    }
+}
+
+/// 清空环境运行命令：payload 写入 stdin，返回 stdout；超时、非零退出、输出超限均视为失败
+pub(crate) fn run_sandboxed(spec: &SandboxedCommand, payload: &[u8]) -> Result<Vec<u8>, String> {
+    let mut cmd = Command::new(spec.command);
+    cmd.args(spec.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(spec.current_dir)
+        .env_clear();
+    for key in ENV_ALLOWLIST {
+        if let Some(value) = std::env::var_os(key) {
+            cmd.env(key, value);
+        }
+    }
+    cmd.env(spec.marker_env, "1");
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut tree =
+        ProcessTree::spawn(&mut cmd).map_err(|e| format!("spawn '{}': {e}", spec.command))?;
+    let child = &mut tree.child;
+
+    // stdin/stdout/stderr 各自在线程中读写，避免管道缓冲区满导致互相阻塞
+    let mut stdin = child.stdin.take().ok_or("stdin unavailable")?;
+    let payload = payload.to_vec();
+    let writer = std::thread::spawn(move || {
+        let _ = stdin.write_all(&payload);
+    });
+
+    let stdout = child.stdout.take().ok_or("stdout unavailable")?;
+    let (out_tx, out_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut limited = stdout.take(MAX_OUTPUT_BYTES as u64 + 1);
+        let result = limited.read_to_end(&mut buf).map(|_| buf);
+        // 超限部分直接丢弃，让子进程正常退出后再判定为输出过大
+        let _ = std::io::copy(&mut limited.into_inner(), &mut std::io::sink());
+        let _ = out_tx.send(result);
+    });
+
+    let stderr = child.stderr.take().ok_or("stderr unavailable")?;
+    let (err_tx, err_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut limited = stderr.take(MAX_STDERR_BYTES as u64);
+        let _ = limited.read_to_end(&mut buf);
+        // 丢弃剩余输出，保证子进程不会因 stderr 写满而卡住
+        let _ = std::io::copy(&mut limited.into_inner(), &mut std::io::sink());
+        let _ = err_tx.send(buf);
+    });
+
+    let deadline = Instant::now() + spec.timeout;
+    let status = loop {
+        match tree.child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                tree.kill();
+                return Err(format!("timed out after {:?}", spec.timeout));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(5)),
+            Err(e) => {
+                tree.kill();
+                return Err(format!("wait failed: {e}"));
+            }
+        }
+    };
+    let _ = writer.join();
+
+    // 子进程已退出；若其派生的后台进程仍持有管道，最多再等待到超时
+    let remaining = deadline
+        .saturating_duration_since(Instant::now())
+        .max(Duration::from_millis(50));
+    let stdout = match out_rx.recv_timeout(remaining) {
+        Ok(result) => result.map_err(|e| format!("read stdout: {e}"))?,
+        Err(_) => {
+            tree.kill();
+            return Err("stdout not closed".to_string());
+        }
+    };
+
+    if !status.success() {
+        let stderr = err_rx
+            .recv_timeout(Duration::from_millis(50))
+            .unwrap_or_default();
+        return Err(format!(
+            "exited with {status}: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        ));
+    }
+    if stdout.len() > MAX_OUTPUT_BYTES {
+        return Err(format!("output exceeds {MAX_OUTPUT_BYTES} bytes"));
+    }
+    Ok(stdout)
+}
+
+fn parse_verdict(stdout: &[u8]) -> Result<HookVerdict, String> {
+    let text = std::str::from_utf8(stdout).map_err(|e| format!("output is not UTF-8: {e}"))?;
+    if text.trim().is_empty() {
+        return Ok(HookVerdict::default());
+    }
+    serde_json::from_str(text.trim()).map_err(|e| format!("invalid verdict JSON: {e}"))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn script(name: &str, body: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ec_hooks_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{name}.sh"));
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        path
+    }
+
+    fn hook(name: &str, body: &str) -> CaptureHook {
+        CaptureHook {
+            name: name.to_string(),
+            command: "sh".to_string(),
+            args: vec![script(name, body).to_string_lossy().into_owned()],
+            enabled: true,
+            timeout_ms: 3000,
+            content_types: Vec::new(),
+            on_error: HookErrorPolicy::Accept,
+        }
+    }
+
+    fn text_input(text: &str) -> HookInput {
+        HookInput {
+            version: HookInput::VERSION,
+            content_type: "text".to_string(),
+            text: Some(text.to_string()),
+            file_paths: None,
+            image_path: None,
+            source_app_name: Some("Editor".to_string()),
+            source_exe_path: None,
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn accept_leaves_item_untouched() {
+        let hooks = [hook(
+            "accept",
+            r#"cat >/dev/null; echo '{"action":"accept"}'"#,
+        )];
+        assert_eq!(
+            run_hooks(&hooks, text_input("hello")),
+            HookOutcome::default()
+        );
+    }
+
+    #[test]
+    fn empty_output_is_accept() {
+        let hooks = [hook("silent", "cat >/dev/null")];
+        assert_eq!(
+            run_hooks(&hooks, text_input("hello")),
+            HookOutcome::default()
+        );
+    }
+
+    #[test]
+    fn drop_based_on_stdin_content() {
+        let body =
+            r#"if grep -q 'TICKET-[0-9]'; then echo '{"action":"drop"}'; else echo '{}'; fi"#;
+        let hooks = [hook("ticket", body)];
+        assert!(run_hooks(&hooks, text_input("see TICKET-42")).dropped);
+        assert!(!run_hooks(&hooks, text_input("nothing here")).dropped);
+    }
+
+    #[test]
+    fn modify_text_group_and_tags() {
+        let body = r#"cat >/dev/null; echo '{"action":"modify","text":"cleaned","group":"Work","tags":["a"," a ","b"]}'"#;
+        let outcome = run_hooks(&[hook("modify", body)], text_input("dirty?utm_source=x"));
+        assert!(!outcome.dropped);
+        assert_eq!(outcome.text.as_deref(), Some("cleaned"));
+        assert_eq!(outcome.group, Some(HookGroup::Name("Work".to_string())));
+        assert_eq!(outcome.tags, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn chained_hooks_see_previous_modification() {
+        let first = hook("first", r#"cat >/dev/null; echo '{"text":"step-one"}'"#);
+        let second = hook(
+            "second",
+            r#"if grep -q step-one; then echo '{"group_id":7}'; else echo '{"action":"drop"}'; fi"#,
+        );
+        let outcome = run_hooks(&[first, second], text_input("original"));
+        assert!(!outcome.dropped);
+        assert_eq!(outcome.text.as_deref(), Some("step-one"));
+        assert_eq!(outcome.group, Some(HookGroup::Id(7)));
+    }
+
+    #[test]
+    fn timeout_kills_hook_and_applies_policy() {
+        let mut slow = hook("slow", "sleep 5");
+        slow.timeout_ms = 200;
+
+        let started = Instant::now();
+        let outcome = run_hooks(std::slice::from_ref(&slow), text_input("x"));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(!outcome.dropped);
+
+        slow.on_error = HookErrorPolicy::Drop;
+        assert!(run_hooks(&[slow], text_input("x")).dropped);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn timeout_kills_spawned_children() {
+        let pid_file = std::env::temp_dir().join(format!(
+            "ec_hooks_test_{}_grandchild.pid",
+            std::process::id()
+        ));
+        let body = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+        let mut spawner = hook("spawner", &body);
+        spawner.timeout_ms = 300;
+        assert!(!run_hooks(&[spawner], text_input("x")).dropped);
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let _ = std::fs::remove_file(&pid_file);
+        // 被结束的孙进程可能暂时是僵尸进程，等待其不再处于运行状态
+        let alive = || {
+            std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()))
+                .is_ok_and(|stat| !stat.contains(") Z "))
+        };
+        let started = Instant::now();
+        while alive() && started.elapsed() < Duration::from_secs(2) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(), "grandchild {} still running", pid.trim());
+    }
+
+    #[test]
+    fn total_budget_caps_hook_chain() {
+        let slow = hook("budget_slow", "sleep 5");
+        let second = hook("budget_second", r#"cat >/dev/null; echo '{"text":"ran"}'"#);
+
+        let started = Instant::now();
+        let outcome =
+            run_hooks_within(&[slow, second], text_input("x"), Duration::from_millis(300));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(!outcome.dropped);
+        assert_eq!(outcome.text, None);
+    }
+
+    #[test]
+    fn oversized_output_is_rejected() {
+        let big = hook("big", "cat >/dev/null; head -c 5000000 /dev/zero");
+        assert!(
+            run_hook(&big, b"{}", big.timeout())
+                .unwrap_err()
+                .contains("exceeds")
+        );
+    }
+
+    #[test]
+    fn invalid_output_and_exit_code_are_errors() {
+        let garbage = hook("garbage", "cat >/dev/null; echo not-json");
+        assert!(
+            run_hook(&garbage, b"{}", garbage.timeout())
+                .unwrap_err()
+                .contains("invalid verdict")
+        );
+
+        let failing = hook("failing", "cat >/dev/null; echo boom >&2; exit 3");
+        let err = run_hook(&failing, b"{}", failing.timeout()).unwrap_err();
+        assert!(err.contains("boom"), "{err}");
+    }
+
+    #[test]
+    fn environment_is_cleared() {
+        let body = r#"cat >/dev/null
+if [ -n "$CARGO_MANIFEST_DIR" ] || [ "$ELEGANT_CLIPBOARD_HOOK" != "1" ]; then
+  echo '{"action":"drop"}'
+else
+  echo '{}'
+fi"#;
+        assert!(!run_hooks(&[hook("env", body)], text_input("x")).dropped);
+    }
+
+    #[test]
+    fn content_type_filter_skips_hook() {
+        let mut files_only = hook("files_only", r#"echo '{"action":"drop"}'"#);
+        files_only.content_types = vec!["files".to_string()];
+        assert!(!run_hooks(&[files_only], text_input("x")).dropped);
+    }
+
+    #[test]
+    fn missing_command_falls_back_to_policy() {
+        let mut missing = hook("missing", "");
+        missing.command = "/nonexistent/ec-hook".to_string();
+        missing.args.clear();
+        assert!(!run_hooks(std::slice::from_ref(&missing), text_input("x")).dropped);
+        missing.on_error = HookErrorPolicy::Drop;
+        assert!(run_hooks(&[missing], text_input("x")).dropped);
+    }
+
+    #[test]
+    fn parse_hooks_filters_disabled_and_invalid() {
+        let raw = r#"[
+            {"name":"a","command":"sh"},
+            {"name":"b","command":"sh","enabled":false},
+            {"name":"c","command":"  "}
+        ]"#;
+        let hooks = parse_hooks(Some(raw));
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name, "a");
+        assert_eq!(hooks[0].timeout_ms, DEFAULT_TIMEOUT_MS);
+        assert!(parse_hooks(Some("{broken")).is_empty());
+        assert!(parse_hooks(None).is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 //! 合并粘贴：聚合多条记录的文本/富文本/文件格式后一次性写入剪贴板。
 
 use super::file_clipboard::{
-    decode_payload, merge_file_paths, resolve_item_paths, write_payload_extras,
+    decode_payload, merge_file_paths, parse_file_paths, restore_paths, write_payload_extras,
 };
 use super::format_write::{item_alt_text, rich_contents_summary};
 use super::rtf_storage;
@@ -108,7 +108,11 @@ fn extract_merge_text(item: &ClipboardItem) -> Option<String> {
         return Some(text.clone());
     }
     if item.content_type == "files" {
-        let resolved = resolve_item_paths(item.file_paths.as_deref(), item.file_payload.as_deref());
+        let paths = parse_file_paths(item.file_paths.as_deref());
+        let resolved = restore_paths(
+            &paths,
+            decode_payload(item.file_payload.as_deref()).as_ref(),
+        );
         if !resolved.is_empty() {
             return Some(resolved.join("\n"));
         }
@@ -152,6 +156,14 @@ mod tests {
             source_app_name: None,
             source_app_icon: None,
             group_id: None,
+            ocr_text: None,
+            subtypes: None,
+            expires_at: None,
+            derived_from: None,
+            derivation: None,
+            staging_state: None,
+            media_missing: false,
+            tags: None,
             files_valid: None,
         }
     }
@@ -1,14 +1,24 @@
+mod classify;
+pub(crate) mod collect;
 mod dedup;
+pub(crate) mod dir_snapshot;
 pub(crate) mod file_clipboard;
 pub(crate) mod format_write;
 mod handler;
+pub(crate) mod hooks;
 pub(crate) mod merge_paste;
 mod monitor;
+pub(crate) mod quota;
+pub(crate) mod retention;
 pub(crate) mod rtf_storage;
+pub(crate) mod rules;
 pub mod source_app;
+pub(crate) mod staging;
+pub(crate) mod text_diff;
 
+pub(crate) use classify::classify_text;
 pub(crate) use dedup::{
-    canonical_url_text, compute_semantic_hash, is_url, normalize_rtf_for_hash,
+    HashInput, IMAGE_HASH_PREFIX, canonical_url_text, compute_semantic_hash, content_hash, is_url,
     semantic_hash_from_text,
 };
 pub use handler::*;
@@ -71,11 +81,24 @@ fn cleanup_staged_files(paths: &[String]) -> usize {
 }
 
 /// 删除条目关联的图片与 staging 文件。
+///
+/// blob 存储中的文件可能被其他条目共享，不直接删除，改为清理引用数归零的 blob；
+/// 旧版目录中的文件仍按路径删除。
 pub fn cleanup_deleted_assets(image_paths: &[String], file_payloads: &[String]) {
-    cleanup_image_files(image_paths);
+    let is_legacy = |p: &String| crate::blobs::hash_from_path(p).is_none();
+    let images: Vec<String> = image_paths
+        .iter()
+        .filter(|p| is_legacy(p))
+        .cloned()
+        .collect();
+    cleanup_image_files(&images);
     let staged: Vec<String> = file_payloads
         .iter()
         .flat_map(|p| file_clipboard::staged_paths_from_payload(Some(p.as_str())))
+        .filter(is_legacy)
         .collect();
     cleanup_staged_files(&staged);
+    if let Some(store) = crate::blobs::global() {
+        store.sweep();
+    }
 }
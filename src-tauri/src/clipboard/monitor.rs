@@ -228,6 +228,10 @@ impl ClipboardMonitor {
                 Ok(Ok(Some(id))) => {
                     debug!("Processed clipboard item: {}", id);
                     let _ = app_handle.emit("clipboard-updated", id);
+                    crate::api::publish_item_added(id);
+                    if super::collect::is_active() {
+                        let _ = app_handle.emit("collect-mode-changed", super::collect::state());
+                    }
                 }
                 Ok(Ok(None)) => {
                     debug!("Clipboard content already exists");
@@ -251,6 +255,11 @@ impl ClipboardMonitor {
         info!("Clipboard worker thread stopped");
     }
 
+    /// 当前剪贴板处理器（`init` 之前为 None）
+    pub fn handler(&self) -> Option<Arc<ClipboardHandler>> {
+        self.handler.read().clone()
+    }
+
     /// 暂停监控（递增暂停计数，支持多个并发暂停）
     pub fn pause(&self) {
         let count = self.pause_count.fetch_add(1, Ordering::SeqCst);
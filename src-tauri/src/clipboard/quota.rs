@@ -0,0 +1,135 @@
+//! 按字节的存储配额
+//!
+//! 占用按条目的数据库内容与引用的媒体文件计算（共享的 blob 按引用数均摊），分内容类型、分组与总量三级，
+//! 超出时按淘汰策略删除非置顶、非收藏条目。新条目写入与文件 staging 完成后执行。
+
+use crate::database::{ClipboardRepository, EvictionPolicy, QuotaScope, SettingsRepository};
+use tracing::{info, warn};
+
+/// 总容量上限（MB，0 = 不限制）
+pub const TOTAL_SETTING: &str = "quota_total_mb";
+/// 每个分组的容量上限（MB，0 = 不限制），分组设置的配额优先
+pub const GROUP_SETTING: &str = "quota_group_mb";
+/// 淘汰策略：oldest / least_accessed / largest
+pub const POLICY_SETTING: &str = "quota_eviction_policy";
+
+/// 可设置配额的内容类型，设置项为 `quota_<类型>_mb`
+const CONTENT_TYPES: [&str; 6] = ["text", "url", "html", "rtf", "image", "files"];
+
+fn type_setting(content_type: &str) -> String {
+    format!("quota_{content_type}_mb")
+}
+
+/// 配额设置（单位 MB，0 = 不限制）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuotaSettings {
+    pub total_mb: i64,
+    pub group_mb: i64,
+    /// 按内容类型的上限，未设置的类型不在列表中
+    pub type_mb: Vec<(&'static str, i64)>,
+    pub policy: EvictionPolicy,
+}
+
+impl QuotaSettings {
+    pub fn load(settings: &SettingsRepository) -> Self {
+        let type_keys: Vec<String> = CONTENT_TYPES.iter().map(|t| type_setting(t)).collect();
+        let mut keys = vec![TOTAL_SETTING, GROUP_SETTING, POLICY_SETTING];
+        keys.extend(type_keys.iter().map(String::as_str));
+        let batch = settings.get_batch(&keys);
+        Self::from_values(|key| batch.get(key).and_then(|v| v.as_deref()))
+    }
+
+    fn from_values<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let mb = |key: &str| {
+            get(key)
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(0)
+        };
+        Self {
+            total_mb: mb(TOTAL_SETTING),
+            group_mb: mb(GROUP_SETTING),
+            type_mb: CONTENT_TYPES
+                .iter()
+                .map(|t| (*t, mb(&type_setting(t))))
+                .filter(|(_, limit)| *limit > 0)
+                .collect(),
+            policy: get(POLICY_SETTING)
+                .and_then(EvictionPolicy::parse)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn type_limit(&self, content_type: &str) -> i64 {
+        self.type_mb
+            .iter()
+            .find(|(t, _)| *t == content_type)
+            .map_or(0, |(_, limit)| *limit)
+    }
+}
+
+/// 对条目所在的类型、分组与总量执行配额（`item_id` 自身不被淘汰），返回删除数
+pub fn enforce(
+    repo: &ClipboardRepository,
+    settings: &QuotaSettings,
+    item_id: i64,
+    content_type: &str,
+    group_id: Option<i64>,
+) -> i64 {
+    let scopes = [
+        (
+            QuotaScope::ContentType(content_type),
+            settings.type_limit(content_type),
+        ),
+        (QuotaScope::Group(group_id), settings.group_mb),
+        (QuotaScope::Total, settings.total_mb),
+    ];
+    let mut deleted = 0;
+    let mut image_paths = Vec::new();
+    let mut file_payloads = Vec::new();
+    for (scope, limit_mb) in scopes {
+        match repo.enforce_byte_quota(scope, limit_mb, settings.policy, Some(item_id)) {
+            Ok((count, images, payloads)) => {
+                deleted += count;
+                image_paths.extend(images);
+                file_payloads.extend(payloads);
+            }
+            Err(e) => warn!("Failed to enforce byte quota {:?}: {}", scope, e),
+        }
+    }
+    if deleted > 0 {
+        super::cleanup_deleted_assets(&image_paths, &file_payloads);
+        info!(
+            "Byte quota: removed {} item(s) ({:?})",
+            deleted, settings.policy
+        );
+    }
+    deleted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_limits_and_policy() {
+        let values = [
+            ("quota_total_mb", "2048"),
+            ("quota_image_mb", " 500 "),
+            ("quota_files_mb", "0"),
+            ("quota_text_mb", "-3"),
+            ("quota_eviction_policy", "largest"),
+        ];
+        let settings = QuotaSettings::from_values(|key| {
+            values.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+        });
+        assert_eq!(settings.total_mb, 2048);
+        assert_eq!(settings.group_mb, 0);
+        assert_eq!(settings.type_mb, vec![("image", 500)]);
+        assert_eq!(settings.type_limit("files"), 0);
+        assert_eq!(settings.policy, EvictionPolicy::Largest);
+
+        let defaults = QuotaSettings::from_values(|_| None);
+        assert_eq!(defaults, QuotaSettings::default());
+    }
+}
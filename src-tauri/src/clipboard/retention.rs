@@ -0,0 +1,198 @@
+//! 自动清理的保留评分
+//!
+//! 使用次数按最近使用时间指数衰减为「有效使用次数」：`access_count × 0.5^(闲置天数 / 半衰期)`。
+//! 有效使用次数达到保护阈值的条目不被自动清理；其余条目的保留天数为
+//! `基础天数 × (1 + 有效使用次数) ÷ (1 + 大小 / 参考大小)`，至少 1 天。
+//! 从未使用过的条目在不考虑大小时与按天数清理的结果一致。
+
+use crate::database::SettingsRepository;
+use serde::Serialize;
+
+/// 基础保留天数（0 = 不自动清理），分组设置的保留天数优先
+pub const AUTO_CLEANUP_SETTING: &str = "auto_cleanup_days";
+pub const DEFAULT_AUTO_CLEANUP_DAYS: i64 = 30;
+/// 使用次数的衰减半衰期（天，0 = 不衰减）
+pub const HALF_LIFE_SETTING: &str = "retention_half_life_days";
+/// 有效使用次数达到该值的条目不被自动清理（0 = 不保护）
+pub const PROTECT_USES_SETTING: &str = "retention_protect_uses";
+/// 大小参考值（MB）：条目每达到一次该大小，保留天数相应缩短（0 = 不考虑大小）
+pub const SIZE_REF_SETTING: &str = "retention_size_ref_mb";
+
+const DEFAULT_HALF_LIFE_DAYS: f64 = 14.0;
+const DEFAULT_PROTECT_USES: f64 = 5.0;
+
+/// 保留评分参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RetentionPolicy {
+    pub half_life_days: f64,
+    pub protect_uses: f64,
+    pub size_ref_mb: f64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            half_life_days: DEFAULT_HALF_LIFE_DAYS,
+            protect_uses: DEFAULT_PROTECT_USES,
+            size_ref_mb: 0.0,
+        }
+    }
+}
+
+/// 评分所需的条目统计
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageStats {
+    pub age_days: f64,
+    /// 距最近一次使用的天数（从未使用时为条目年龄）
+    pub idle_days: f64,
+    pub access_count: i64,
+    pub bytes: i64,
+}
+
+/// 单个条目的评分结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RetentionScore {
+    pub effective_uses: f64,
+    pub keep_days: f64,
+    pub protected: bool,
+}
+
+impl RetentionScore {
+    /// 条目年龄超过保留天数且未受保护时应被清理
+    pub fn expired(&self, age_days: f64) -> bool {
+        !self.protected && age_days > self.keep_days
+    }
+}
+
+impl RetentionPolicy {
+    pub fn load(settings: &SettingsRepository) -> Self {
+        let batch =
+            settings.get_batch(&[HALF_LIFE_SETTING, PROTECT_USES_SETTING, SIZE_REF_SETTING]);
+        Self::from_values(|key| batch.get(key).and_then(|v| v.as_deref()))
+    }
+
+    fn from_values<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let value = |key: &str, default: f64| {
+            get(key)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .unwrap_or(default)
+        };
+        Self {
+            half_life_days: value(HALF_LIFE_SETTING, DEFAULT_HALF_LIFE_DAYS),
+            protect_uses: value(PROTECT_USES_SETTING, DEFAULT_PROTECT_USES),
+            size_ref_mb: value(SIZE_REF_SETTING, 0.0),
+        }
+    }
+
+    /// 给定大小的条目的最短保留天数（即从未使用时的保留天数）：评分结果不低于此值，
+    /// 更新的条目无需评分
+    pub fn min_keep_days(&self, base_days: f64, bytes: i64) -> f64 {
+        (base_days / self.size_factor(bytes)).max(base_days.min(1.0))
+    }
+
+    fn size_factor(&self, bytes: i64) -> f64 {
+        if self.size_ref_mb > 0.0 {
+            1.0 + bytes.max(0) as f64 / (self.size_ref_mb * 1024.0 * 1024.0)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn score(&self, base_days: f64, stats: &UsageStats) -> RetentionScore {
+        let decay = if self.half_life_days > 0.0 {
+            0.5_f64.powf(stats.idle_days.max(0.0) / self.half_life_days)
+        } else {
+            1.0
+        };
+        let effective_uses = stats.access_count.max(0) as f64 * decay;
+        RetentionScore {
+            effective_uses,
+            keep_days: (base_days * (1.0 + effective_uses) / self.size_factor(stats.bytes))
+                .max(base_days.min(1.0)),
+            protected: self.protect_uses > 0.0 && effective_uses >= self.protect_uses,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(age_days: f64, idle_days: f64, access_count: i64, bytes: i64) -> UsageStats {
+        UsageStats {
+            age_days,
+            idle_days,
+            access_count,
+            bytes,
+        }
+    }
+
+    #[test]
+    fn unused_items_follow_base_days() {
+        let policy = RetentionPolicy::default();
+        let score = policy.score(30.0, &stats(31.0, 31.0, 0, 1024));
+        assert_eq!(score.keep_days, 30.0);
+        assert!(score.expired(31.0));
+        assert!(!policy.score(30.0, &stats(29.0, 29.0, 0, 0)).expired(29.0));
+    }
+
+    #[test]
+    fn usage_extends_and_protects_with_decay() {
+        let policy = RetentionPolicy::default();
+        // 昨天刚用过 2 次：保留期接近三倍
+        let recent = policy.score(30.0, &stats(40.0, 1.0, 2, 0));
+        assert!(recent.keep_days > 80.0);
+        assert!(!recent.expired(40.0));
+        // 同样次数但闲置两个半衰期后只剩四分之一
+        let stale = policy.score(30.0, &stats(40.0, 28.0, 2, 0));
+        assert!((stale.effective_uses - 0.5).abs() < 1e-9);
+        assert!(!stale.expired(40.0));
+        assert!(stale.expired(50.0));
+        // 每天都在用的条目受保护
+        let daily = policy.score(30.0, &stats(365.0, 0.5, 200, 0));
+        assert!(daily.protected);
+        assert!(!daily.expired(365.0));
+
+        let no_protect = RetentionPolicy {
+            protect_uses: 0.0,
+            ..policy
+        };
+        assert!(!no_protect.score(30.0, &stats(365.0, 0.5, 200, 0)).protected);
+    }
+
+    #[test]
+    fn large_items_expire_sooner() {
+        let policy = RetentionPolicy {
+            size_ref_mb: 10.0,
+            ..Default::default()
+        };
+        let screenshot = policy.score(30.0, &stats(7.0, 7.0, 0, 40 * 1024 * 1024));
+        assert_eq!(screenshot.keep_days, 6.0);
+        assert!(screenshot.expired(7.0));
+        // 保留期至少 1 天
+        let huge = policy.score(30.0, &stats(0.5, 0.5, 0, 10_000 * 1024 * 1024));
+        assert_eq!(huge.keep_days, 1.0);
+        assert_eq!(policy.min_keep_days(30.0, 40 * 1024 * 1024), 6.0);
+        assert_eq!(policy.min_keep_days(30.0, 10_000 * 1024 * 1024), 1.0);
+        assert_eq!(
+            RetentionPolicy::default().min_keep_days(30.0, 1 << 40),
+            30.0
+        );
+    }
+
+    #[test]
+    fn parses_settings() {
+        let values = [
+            ("retention_half_life_days", "7"),
+            ("retention_protect_uses", "-1"),
+            ("retention_size_ref_mb", "abc"),
+        ];
+        let policy = RetentionPolicy::from_values(|key| {
+            values.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+        });
+        assert_eq!(policy.half_life_days, 7.0);
+        assert_eq!(policy.protect_uses, DEFAULT_PROTECT_USES);
+        assert_eq!(policy.size_ref_mb, 0.0);
+    }
+}
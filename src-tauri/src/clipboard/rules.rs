@@ -0,0 +1,449 @@
+//! 捕获路由规则匹配
+//!
+//! 规则存储在 `capture_rules` 表，按 `sort_order` 依次匹配，首个命中的规则生效。
+//! 规则内所有已配置的条件需同时满足；未配置任何条件的规则不会命中。
+
+use super::handler::matches_app_filter;
+use crate::database::{CaptureRule, CaptureRuleInput, ClipboardItem};
+use regex::Regex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 规则版本号：规则增删改后递增，`ClipboardHandler` 据此刷新已编译缓存
+static RULES_VERSION: AtomicU64 = AtomicU64::new(1);
+
+/// 正则编译大小上限，防止病态表达式占用过多内存
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+pub(crate) fn rules_version() -> u64 {
+    RULES_VERSION.load(Ordering::Acquire)
+}
+
+/// 规则变更后调用，使处理器在下次捕获时重新加载规则
+pub(crate) fn invalidate_rules() {
+    RULES_VERSION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// 规则匹配的输入
+#[derive(Debug, Clone)]
+pub(crate) struct RuleSubject<'a> {
+    pub content_type: &'a str,
+    pub text: Option<&'a str>,
+    pub file_paths: Option<&'a [String]>,
+    pub source_app_name: Option<&'a str>,
+    pub source_exe_path: Option<&'a str>,
+    /// 当日分钟数（0..1440）
+    pub minute_of_day: u32,
+}
+
+/// 预编译的规则（正则、类型列表、时间窗口）
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledRule {
+    pub rule: CaptureRule,
+    regex: Option<Regex>,
+    content_types: Vec<String>,
+    window: Option<(u32, u32)>,
+}
+
+impl CompiledRule {
+    pub fn compile(rule: CaptureRule) -> Result<Self, String> {
+        let conditions = RuleConditions::parse(
+            rule.content_regex.as_deref(),
+            rule.content_types.as_deref(),
+            rule.time_start.as_deref(),
+            rule.time_end.as_deref(),
+        )?;
+        Ok(Self {
+            rule,
+            regex: conditions.regex,
+            content_types: conditions.content_types,
+            window: conditions.window,
+        })
+    }
+
+    pub fn matches(&self, subject: &RuleSubject<'_>) -> bool {
+        let source_app = non_empty(self.rule.source_app.as_deref());
+        if source_app.is_none()
+            && self.regex.is_none()
+            && self.content_types.is_empty()
+            && self.window.is_none()
+        {
+            return false;
+        }
+
+        if let Some(filter) = source_app {
+            let app_name = subject.source_app_name.unwrap_or("");
+            let exe_path = subject.source_exe_path.unwrap_or("");
+            let exe_name = std::path::Path::new(exe_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("");
+            if (app_name.is_empty() && exe_path.is_empty())
+                || !matches_app_filter(filter, app_name, exe_name, exe_path)
+            {
+                return false;
+            }
+        }
+
+        if !self.content_types.is_empty()
+            && !self.content_types.iter().any(|t| t == subject.content_type)
+        {
+            return false;
+        }
+
+        if let Some(regex) = &self.regex {
+            let text_hit = subject.text.is_some_and(|t| regex.is_match(t));
+            let path_hit = subject
+                .file_paths
+                .is_some_and(|paths| paths.iter().any(|p| regex.is_match(p)));
+            if !text_hit && !path_hit {
+                return false;
+            }
+        }
+
+        if let Some((start, end)) = self.window
+            && !in_window(subject.minute_of_day, start, end)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+struct RuleConditions {
+    regex: Option<Regex>,
+    content_types: Vec<String>,
+    window: Option<(u32, u32)>,
+}
+
+impl RuleConditions {
+    fn parse(
+        regex: Option<&str>,
+        content_types: Option<&str>,
+        time_start: Option<&str>,
+        time_end: Option<&str>,
+    ) -> Result<Self, String> {
+        let regex = non_empty(regex)
+            .map(|pattern| {
+                regex::RegexBuilder::new(pattern)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .build()
+                    .map_err(|e| format!("正则表达式无效: {e}"))
+            })
+            .transpose()?;
+
+        let content_types = content_types
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let window = match (non_empty(time_start), non_empty(time_end)) {
+            (None, None) => None,
+            (Some(start), Some(end)) => {
+                let start = parse_hhmm(start).ok_or_else(|| format!("开始时间无效: {start}"))?;
+                let end = parse_hhmm(end).ok_or_else(|| format!("结束时间无效: {end}"))?;
+                Some((start, end))
+            }
+            _ => return Err("时间窗口需同时设置开始与结束时间".to_string()),
+        };
+
+        Ok(Self {
+            regex,
+            content_types,
+            window,
+        })
+    }
+}
+
+/// 校验规则输入（正则、时间格式、TTL）
+pub(crate) fn validate_input(input: &CaptureRuleInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("规则名称不能为空".to_string());
+    }
+    if input.ttl_minutes.is_some_and(|m| m <= 0) {
+        return Err("过期时间必须大于 0 分钟".to_string());
+    }
+    RuleConditions::parse(
+        input.content_regex.as_deref(),
+        input.content_types.as_deref(),
+        input.time_start.as_deref(),
+        input.time_end.as_deref(),
+    )
+    .map(|_| ())
+}
+
+/// 编译规则列表（跳过禁用与无效规则）
+pub(crate) fn compile_rules(rules: Vec<CaptureRule>) -> Vec<CompiledRule> {
+    rules
+        .into_iter()
+        .filter(|r| r.enabled)
+        .filter_map(|rule| {
+            let name = rule.name.clone();
+            CompiledRule::compile(rule)
+                .map_err(|e| tracing::warn!("Skipping capture rule '{}': {}", name, e))
+                .ok()
+        })
+        .collect()
+}
+
+/// 返回首个命中的规则
+pub(crate) fn first_match<'a>(
+    rules: &'a [CompiledRule],
+    subject: &RuleSubject<'_>,
+) -> Option<&'a CaptureRule> {
+    rules.iter().find(|r| r.matches(subject)).map(|r| &r.rule)
+}
+
+/// 解析 `HH:MM` 为当日分钟数
+pub(crate) fn parse_hhmm(value: &str) -> Option<u32> {
+    let (h, m) = value.trim().split_once(':')?;
+    let h: u32 = h.parse().ok()?;
+    let m: u32 = m.parse().ok()?;
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
+/// 当前本地时间的当日分钟数
+pub(crate) fn current_minute_of_day() -> u32 {
+    use chrono::Timelike;
+    let now = chrono::Local::now();
+    now.hour() * 60 + now.minute()
+}
+
+/// 从 `YYYY-MM-DD HH:MM:SS` 时间戳取当日分钟数
+fn minute_of_day_from_timestamp(ts: &str) -> Option<u32> {
+    parse_hhmm(ts.split_once(' ')?.1.get(..5)?)
+}
+
+/// 时间窗口判断：`start <= now < end`，`start > end` 表示跨午夜，`start == end` 表示全天
+fn in_window(now: u32, start: u32, end: u32) -> bool {
+    if start == end {
+        true
+    } else if start < end {
+        (start..end).contains(&now)
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// 规则试运行：对历史条目逐一匹配，返回命中的条目 ID
+///
+/// 历史条目只保存来源应用名称，不含可执行文件路径，来源条件仅按名称匹配。
+pub(crate) fn dry_run(rule: &CompiledRule, items: &[ClipboardItem]) -> Vec<i64> {
+    items
+        .iter()
+        .filter(|item| {
+            let file_paths: Option<Vec<String>> = item
+                .file_paths
+                .as_deref()
+                .and_then(|p| serde_json::from_str(p).ok());
+            let subject = RuleSubject {
+                content_type: &item.content_type,
+                text: item.text_content.as_deref().or(item.ocr_text.as_deref()),
+                file_paths: file_paths.as_deref(),
+                source_app_name: item.source_app_name.as_deref(),
+                source_exe_path: None,
+                minute_of_day: minute_of_day_from_timestamp(&item.created_at).unwrap_or(0),
+            };
+            rule.matches(&subject)
+        })
+        .map(|item| item.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(f: impl FnOnce(&mut CaptureRule)) -> CompiledRule {
+        let mut rule = CaptureRule {
+            id: 1,
+            name: "test".to_string(),
+            enabled: true,
+            sort_order: 1,
+            source_app: None,
+            content_regex: None,
+            content_types: None,
+            time_start: None,
+            time_end: None,
+            target_group_id: Some(10),
+            pin: false,
+            ttl_minutes: None,
+            created_at: String::new(),
+        };
+        f(&mut rule);
+        CompiledRule::compile(rule).unwrap()
+    }
+
+    fn subject(text: &str) -> RuleSubject<'_> {
+        RuleSubject {
+            content_type: "text",
+            text: Some(text),
+            file_paths: None,
+            source_app_name: Some("Visual Studio Code"),
+            source_exe_path: Some(r"C:\Program Files\Code\Code.exe"),
+            minute_of_day: 10 * 60,
+        }
+    }
+
+    #[test]
+    fn rule_without_conditions_never_matches() {
+        assert!(!rule(|_| {}).matches(&subject("anything")));
+    }
+
+    #[test]
+    fn source_app_matches_name_or_exe() {
+        assert!(rule(|r| r.source_app = Some("code.exe".into())).matches(&subject("x")));
+        assert!(rule(|r| r.source_app = Some("Visual*".into())).matches(&subject("x")));
+        assert!(!rule(|r| r.source_app = Some("chrome".into())).matches(&subject("x")));
+
+        let mut anonymous = subject("x");
+        anonymous.source_app_name = None;
+        anonymous.source_exe_path = None;
+        assert!(!rule(|r| r.source_app = Some("code".into())).matches(&anonymous));
+    }
+
+    #[test]
+    fn regex_matches_text_or_file_paths() {
+        let r = rule(|r| r.content_regex = Some(r"^JIRA-\d+$".into()));
+        assert!(r.matches(&subject("JIRA-123")));
+        assert!(!r.matches(&subject("see JIRA-123")));
+
+        let paths = vec![r"D:\work\report.xlsx".to_string()];
+        let r = rule(|r| r.content_regex = Some(r"\.xlsx$".into()));
+        let files = RuleSubject {
+            content_type: "files",
+            text: None,
+            file_paths: Some(&paths),
+            ..subject("")
+        };
+        assert!(r.matches(&files));
+    }
+
+    #[test]
+    fn content_types_are_any_of() {
+        let r = rule(|r| r.content_types = Some("image, files".into()));
+        assert!(!r.matches(&subject("x")));
+        let image = RuleSubject {
+            content_type: "image",
+            ..subject("")
+        };
+        assert!(r.matches(&image));
+    }
+
+    #[test]
+    fn all_conditions_must_hold() {
+        let r = rule(|r| {
+            r.source_app = Some("code".into());
+            r.content_regex = Some("TODO".into());
+        });
+        assert!(r.matches(&subject("TODO: fix")));
+        assert!(!r.matches(&subject("done")));
+    }
+
+    #[test]
+    fn time_window_including_overnight() {
+        let day = rule(|r| {
+            r.time_start = Some("09:00".into());
+            r.time_end = Some("18:00".into());
+        });
+        let night = rule(|r| {
+            r.time_start = Some("22:00".into());
+            r.time_end = Some("06:00".into());
+        });
+        let at = |minute| RuleSubject {
+            minute_of_day: minute,
+            ..subject("x")
+        };
+        assert!(day.matches(&at(9 * 60)));
+        assert!(!day.matches(&at(18 * 60)));
+        assert!(night.matches(&at(23 * 60)));
+        assert!(night.matches(&at(5 * 60 + 59)));
+        assert!(!night.matches(&at(12 * 60)));
+
+        let all_day = rule(|r| {
+            r.time_start = Some("08:00".into());
+            r.time_end = Some("08:00".into());
+        });
+        assert!(all_day.matches(&at(0)));
+        assert!(all_day.matches(&at(8 * 60)));
+        assert!(all_day.matches(&at(23 * 60 + 59)));
+    }
+
+    #[test]
+    fn first_match_respects_order_and_skips_disabled() {
+        let mut first = rule(|r| r.content_regex = Some("a".into())).rule;
+        first.id = 1;
+        first.enabled = false;
+        let mut second = rule(|r| r.content_regex = Some("a".into())).rule;
+        second.id = 2;
+        let mut third = rule(|r| r.content_regex = Some(".".into())).rule;
+        third.id = 3;
+        let compiled = compile_rules(vec![first, second, third]);
+        assert_eq!(
+            first_match(&compiled, &subject("abc")).map(|r| r.id),
+            Some(2)
+        );
+        assert_eq!(
+            first_match(&compiled, &subject("xyz")).map(|r| r.id),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_input() {
+        let ok = CaptureRuleInput {
+            name: "ok".into(),
+            content_regex: Some(r"\d+".into()),
+            ..Default::default()
+        };
+        assert!(validate_input(&ok).is_ok());
+        for bad in [
+            CaptureRuleInput {
+                name: "  ".into(),
+                ..Default::default()
+            },
+            CaptureRuleInput {
+                name: "regex".into(),
+                content_regex: Some("(unclosed".into()),
+                ..Default::default()
+            },
+            CaptureRuleInput {
+                name: "time".into(),
+                time_start: Some("25:00".into()),
+                time_end: Some("26:00".into()),
+                ..Default::default()
+            },
+            CaptureRuleInput {
+                name: "half window".into(),
+                time_start: Some("08:00".into()),
+                ..Default::default()
+            },
+            CaptureRuleInput {
+                name: "ttl".into(),
+                ttl_minutes: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(validate_input(&bad).is_err(), "{}", bad.name);
+        }
+    }
+
+    #[test]
+    fn parse_time_helpers() {
+        assert_eq!(parse_hhmm("00:00"), Some(0));
+        assert_eq!(parse_hhmm("23:59"), Some(1439));
+        assert_eq!(parse_hhmm("24:00"), None);
+        assert_eq!(
+            minute_of_day_from_timestamp("2024-05-01 13:45:10"),
+            Some(825)
+        );
+        assert_eq!(minute_of_day_from_timestamp("garbage"), None);
+    }
+}
@@ -241,21 +241,28 @@ pub fn compute_icon_cache_key(exe_path: &str) -> String {
     hasher.finalize().to_hex()[..12].to_string()
 }
 
-/// 提取应用图标并缓存为 PNG，返回缓存文件路径
+/// 提取应用图标并缓存为 PNG（存入 blob 存储，按 `<cache_key>.png` 命中缓存），返回文件路径
 #[cfg(target_os = "windows")]
-pub fn extract_and_cache_icon(exe_path: &str, icons_dir: &Path, cache_key: &str) -> Option<String> {
-    let icon_path = icons_dir.join(format!("{cache_key}.png"));
-    if icon_path.exists() {
-        return Some(icon_path.to_string_lossy().to_string());
+pub fn extract_and_cache_icon(
+    exe_path: &str,
+    store: &crate::blobs::BlobStore,
+    cache_key: &str,
+) -> Option<String> {
+    use crate::blobs::BlobKind;
+
+    let name = format!("{cache_key}.png");
+    if let Some(path) = store.find_by_name(BlobKind::Icon, &name) {
+        return Some(path.to_string_lossy().to_string());
     }
 
-    std::fs::create_dir_all(icons_dir).ok()?;
     let png_data = extract_icon_png(exe_path)?;
-    if let Err(e) = std::fs::write(&icon_path, &png_data) {
-        warn!("Icon cache failed for {}: {}", exe_path, e);
-        return None;
+    match store.put_bytes(&png_data, &name, BlobKind::Icon) {
+        Ok(blob) => Some(blob.path.to_string_lossy().to_string()),
+        Err(e) => {
+            warn!("Icon cache failed for {}: {}", exe_path, e);
+            None
+        }
     }
-    Some(icon_path.to_string_lossy().to_string())
 }
 
 /// 通过 SHGetFileInfoW + GDI 提取 exe 图标为 PNG
@@ -0,0 +1,536 @@
+//! 文件 staging 后台队列
+//!
+//! 文件条目入库时只记录路径与剪贴板格式，文件副本由 `staging-worker` 线程复制进 blob 存储，
+//! 完成后补写 `file_payload`，避免大文件复制阻塞捕获。状态记录在 `staging_state` 列：
+//! `pending`（排队或进行中）→ `done` / `partial`（部分文件失败或被取消）/ `failed`。
+//! 开启目录快照时，复制的文件夹同样在此队列中按 [`dir_snapshot`] 的上限与忽略列表存入。
+
+use super::dir_snapshot;
+use super::file_clipboard;
+use super::quota;
+use crate::blobs::BlobStore;
+use crate::database::{ClipboardRepository, Database, SettingsRepository};
+use parking_lot::Mutex;
+use std::cell::Cell;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, info, warn};
+
+pub const STATE_PENDING: &str = "pending";
+pub const STATE_PARTIAL: &str = "partial";
+pub const STATE_DONE: &str = "done";
+pub const STATE_FAILED: &str = "failed";
+
+/// 进度事件：`{ id, file_index, file_count, bytes_done, bytes_total }`
+const PROGRESS_EVENT: &str = "file-staging-progress";
+/// 完成事件：`{ id, state }`
+const UPDATED_EVENT: &str = "file-staging-updated";
+/// 进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// staging 事件的接收方（应用中转发给 webview，测试中可替换）
+pub(crate) trait StagingHost: Send + Sync {
+    fn emit(&self, event: &str, payload: serde_json::Value);
+}
+
+impl StagingHost for AppHandle {
+    fn emit(&self, event: &str, payload: serde_json::Value) {
+        let _ = Emitter::emit(self, event, payload);
+    }
+}
+
+static QUEUE: OnceLock<Arc<StagingQueue>> = OnceLock::new();
+
+struct StagingJob {
+    item_id: i64,
+    files: Vec<(String, u64)>,
+}
+
+/// staging 队列：由单个 `staging-worker` 线程按入队顺序执行
+pub(crate) struct StagingQueue {
+    host: Arc<dyn StagingHost>,
+    db: Database,
+    store: Option<&'static BlobStore>,
+    progress_interval: Duration,
+    /// 排队或进行中的条目 ID（同一条目不重复入队）
+    active: Mutex<Vec<i64>>,
+    /// 已请求取消的条目 ID
+    cancelled: Mutex<Vec<i64>>,
+    tx: Sender<StagingJob>,
+}
+
+/// 初始化 staging 队列（应用启动时调用一次），并恢复上次退出时未完成的任务
+pub fn init(app: &AppHandle, db: Database) {
+    let queue = StagingQueue::start(
+        Arc::new(app.clone()),
+        db,
+        crate::blobs::global(),
+        PROGRESS_INTERVAL,
+    );
+    if QUEUE.set(queue.clone()).is_ok() {
+        queue.resume();
+    }
+}
+
+/// 将文件条目加入 staging 队列，返回是否入队（无可 staging 的文件时不入队）
+///
+/// `max_stage_bytes` 为单文件大小上限，0 = 默认 50MB。
+pub fn enqueue(item_id: i64, paths: &[String], max_stage_bytes: u64) -> bool {
+    QUEUE
+        .get()
+        .is_some_and(|queue| queue.enqueue(item_id, paths, max_stage_bytes))
+}
+
+/// 取消条目的 staging（排队中或进行中），已复制的文件保留
+pub fn cancel(item_id: i64) -> bool {
+    QUEUE.get().is_some_and(|queue| queue.cancel(item_id))
+}
+
+/// 排队或进行中的条目 ID
+pub fn active_items() -> Vec<i64> {
+    QUEUE
+        .get()
+        .map(|queue| queue.active_items())
+        .unwrap_or_default()
+}
+
+/// 按成功数判定最终状态
+fn final_state(staged: usize, total: usize) -> &'static str {
+    match staged {
+        0 => STATE_FAILED,
+        n if n >= total => STATE_DONE,
+        _ => STATE_PARTIAL,
+    }
+}
+
+impl StagingQueue {
+    /// 创建队列并启动后台线程；线程只持有弱引用，队列释放后随之退出
+    fn start(
+        host: Arc<dyn StagingHost>,
+        db: Database,
+        store: Option<&'static BlobStore>,
+        progress_interval: Duration,
+    ) -> Arc<Self> {
+        let (tx, rx) = std::sync::mpsc::channel::<StagingJob>();
+        let queue = Arc::new(Self {
+            host,
+            db,
+            store,
+            progress_interval,
+            active: Mutex::new(Vec::new()),
+            cancelled: Mutex::new(Vec::new()),
+            tx,
+        });
+        let weak = Arc::downgrade(&queue);
+        if let Err(e) = std::thread::Builder::new()
+            .name("staging-worker".into())
+            .spawn(move || {
+                while let Ok(job) = rx.recv() {
+                    let Some(queue) = weak.upgrade() else {
+                        break;
+                    };
+                    queue.run_job(&job);
+                    queue.active.lock().retain(|id| *id != job.item_id);
+                    queue.cancelled.lock().retain(|id| *id != job.item_id);
+                }
+            })
+        {
+            tracing::error!("Failed to spawn staging-worker thread: {}", e);
+        }
+        queue
+    }
+
+    /// 重新排队上次退出时仍为 `pending` 的条目
+    fn resume(&self) {
+        let repo = ClipboardRepository::new(&self.db);
+        let pending = repo.list_pending_staging().unwrap_or_default();
+        if !pending.is_empty() {
+            info!("Resuming file staging for {} item(s)", pending.len());
+        }
+        for (item_id, paths) in pending {
+            // 原文件已不存在，无法再复制
+            if !self.enqueue(item_id, &paths, 0) {
+                let _ = repo.apply_staged_files(item_id, &[], STATE_FAILED);
+            }
+        }
+    }
+
+    fn enqueue(&self, item_id: i64, paths: &[String], max_stage_bytes: u64) -> bool {
+        let include_dirs = dir_snapshot::SnapshotOptions::load(&self.db)
+            .format
+            .is_some();
+        let files = file_clipboard::stageable_files(paths, max_stage_bytes, include_dirs);
+        if files.is_empty() {
+            return false;
+        }
+        {
+            let mut active = self.active.lock();
+            if active.contains(&item_id) {
+                return false;
+            }
+            active.push(item_id);
+        }
+        self.cancelled.lock().retain(|id| *id != item_id);
+        let repo = ClipboardRepository::new(&self.db);
+        if let Err(e) = repo.set_staging_state(item_id, Some(STATE_PENDING)) {
+            warn!("Failed to mark item {} as staging: {}", item_id, e);
+        }
+        self.host.emit(
+            UPDATED_EVENT,
+            serde_json::json!({ "id": item_id, "state": STATE_PENDING }),
+        );
+        debug!(
+            "Queued {} file(s) of item {} for staging",
+            files.len(),
+            item_id
+        );
+        let _ = self.tx.send(StagingJob { item_id, files });
+        true
+    }
+
+    fn cancel(&self, item_id: i64) -> bool {
+        if !self.active.lock().contains(&item_id) {
+            return false;
+        }
+        let mut cancelled = self.cancelled.lock();
+        if !cancelled.contains(&item_id) {
+            cancelled.push(item_id);
+        }
+        true
+    }
+
+    fn active_items(&self) -> Vec<i64> {
+        self.active.lock().clone()
+    }
+
+    fn is_cancelled(&self, item_id: i64) -> bool {
+        self.cancelled.lock().contains(&item_id)
+    }
+
+    fn run_job(&self, job: &StagingJob) {
+        let repo = ClipboardRepository::new(&self.db);
+        let started = Instant::now();
+        let file_count = job.files.len();
+        // 目录大小在扫描后才计入
+        let bytes_total = Cell::new(job.files.iter().map(|(_, size)| size).sum::<u64>());
+        let emit_progress = |file_index: usize, bytes_done: u64| {
+            self.host.emit(
+                PROGRESS_EVENT,
+                serde_json::json!({
+                    "id": job.item_id,
+                    "file_index": file_index,
+                    "file_count": file_count,
+                    "bytes_done": bytes_done,
+                    "bytes_total": bytes_total.get(),
+                }),
+            );
+        };
+
+        let Some(store) = self.store else {
+            warn!("Blob store not initialized, staging skipped");
+            let _ = repo.apply_staged_files(job.item_id, &[], STATE_FAILED);
+            return;
+        };
+
+        let snapshot_options = dir_snapshot::SnapshotOptions::load(&self.db);
+        let mut staged = Vec::with_capacity(file_count);
+        let mut bytes_done = 0u64;
+        let mut last_emit = Instant::now();
+        emit_progress(0, 0);
+        for (index, (path, size)) in job.files.iter().enumerate() {
+            // 条目已删除时停止，已复制的 blob 由清理任务回收
+            if self.is_cancelled(job.item_id) || !matches!(repo.get_by_id(job.item_id), Ok(Some(_)))
+            {
+                debug!("Staging of item {} stopped", job.item_id);
+                break;
+            }
+            let base = bytes_done;
+            let on_progress = |written: u64| {
+                // 节流：每个间隔最多发送一次进度事件
+                if last_emit.elapsed() >= self.progress_interval {
+                    emit_progress(index, base + written);
+                    last_emit = Instant::now();
+                }
+                !self.is_cancelled(job.item_id)
+            };
+            let result = if Path::new(path).is_dir() {
+                match snapshot_options.format {
+                    Some(format) => dir_snapshot::scan(Path::new(path), &snapshot_options)
+                        .and_then(|scan| {
+                            bytes_total.set(bytes_total.get() + scan.total_bytes);
+                            dir_snapshot::stage_dir(store, path, format, &scan, on_progress)
+                        }),
+                    None => Err(std::io::Error::other("folder snapshots are disabled")),
+                }
+            } else {
+                file_clipboard::stage_file(store, path, *size, on_progress)
+            };
+            match result {
+                Ok(file) => {
+                    bytes_done += file.size;
+                    // 立即登记引用：长任务中先存入的 blob 不会因超过未引用宽限期被清理
+                    if let Err(e) = repo.apply_staged_files(
+                        job.item_id,
+                        std::slice::from_ref(&file),
+                        STATE_PENDING,
+                    ) {
+                        warn!(
+                            "Failed to record staged file of item {}: {}",
+                            job.item_id, e
+                        );
+                    }
+                    staged.push(file);
+                }
+                Err(e) if crate::blobs::is_copy_cancelled(&e) => break,
+                Err(e) => warn!("Failed to stage {}: {}", path, e),
+            }
+        }
+        emit_progress(staged.len(), bytes_done);
+
+        let state = final_state(staged.len(), file_count);
+        match repo.apply_staged_files(job.item_id, &staged, state) {
+            Ok(true) => {
+                info!(
+                    "Staged {}/{} file(s) of item {} in {:?}",
+                    staged.len(),
+                    file_count,
+                    job.item_id,
+                    started.elapsed()
+                );
+                self.host.emit(
+                    UPDATED_EVENT,
+                    serde_json::json!({ "id": job.item_id, "state": state }),
+                );
+                // 文件副本计入占用后再检查配额
+                if bytes_done > 0
+                    && let Ok(Some(item)) = repo.get_by_id(job.item_id)
+                {
+                    let settings = quota::QuotaSettings::load(&SettingsRepository::new(&self.db));
+                    quota::enforce(&repo, &settings, item.id, &item.content_type, item.group_id);
+                }
+            }
+            Ok(false) => debug!(
+                "Item {} changed during staging, result dropped",
+                job.item_id
+            ),
+            Err(e) => warn!(
+                "Failed to save staging result of item {}: {}",
+                job.item_id, e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ContentType, NewClipboardItem};
+    use crate::test_support::temp_dir;
+    use std::path::PathBuf;
+    use std::sync::Weak;
+
+    /// 记录事件；设置 `cancel_on_progress` 后，首个复制中的进度事件会取消对应条目
+    #[derive(Default)]
+    struct TestHost {
+        events: Mutex<Vec<(String, serde_json::Value)>>,
+        cancel_on_progress: OnceLock<Weak<StagingQueue>>,
+    }
+
+    impl StagingHost for TestHost {
+        fn emit(&self, event: &str, payload: serde_json::Value) {
+            if event == PROGRESS_EVENT
+                && payload["bytes_done"].as_u64().unwrap_or(0) > 0
+                && let Some(queue) = self.cancel_on_progress.get().and_then(Weak::upgrade)
+            {
+                queue.cancel(payload["id"].as_i64().unwrap());
+            }
+            self.events.lock().push((event.to_string(), payload));
+        }
+    }
+
+    impl TestHost {
+        fn states(&self, id: i64) -> Vec<String> {
+            self.events
+                .lock()
+                .iter()
+                .filter(|(event, payload)| event == UPDATED_EVENT && payload["id"] == id)
+                .map(|(_, payload)| payload["state"].as_str().unwrap().to_string())
+                .collect()
+        }
+    }
+
+    struct Fixture {
+        dir: PathBuf,
+        db: Database,
+        host: Arc<TestHost>,
+        queue: Arc<StagingQueue>,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let dir = temp_dir(name);
+            let db = Database::new(dir.join("clipboard.db")).unwrap();
+            let store: &'static BlobStore =
+                Box::leak(Box::new(BlobStore::new(&db, dir.join("blobs"))));
+            let host = Arc::new(TestHost::default());
+            let queue = StagingQueue::start(host.clone(), db.clone(), Some(store), Duration::ZERO);
+            Self {
+                dir,
+                db,
+                host,
+                queue,
+            }
+        }
+
+        fn file(&self, name: &str, len: usize) -> String {
+            let path = self.dir.join(name);
+            std::fs::write(&path, vec![b'x'; len]).unwrap();
+            path.to_string_lossy().to_string()
+        }
+
+        fn insert(&self, paths: &[String]) -> i64 {
+            ClipboardRepository::new(&self.db)
+                .insert(NewClipboardItem {
+                    content_type: ContentType::Files,
+                    file_paths: Some(paths.to_vec()),
+                    content_hash: uuid::Uuid::new_v4().to_string(),
+                    semantic_hash: uuid::Uuid::new_v4().to_string(),
+                    ..Default::default()
+                })
+                .unwrap()
+        }
+
+        fn wait_idle(&self) {
+            let started = Instant::now();
+            while !self.queue.active_items().is_empty() {
+                assert!(
+                    started.elapsed() < Duration::from_secs(10),
+                    "staging timed out"
+                );
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        fn item(&self, id: i64) -> (Option<String>, Vec<String>) {
+            let item = ClipboardRepository::new(&self.db)
+                .get_by_id(id)
+                .unwrap()
+                .unwrap();
+            let staged = file_clipboard::staged_paths_from_payload(item.file_payload.as_deref());
+            (item.staging_state, staged)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn final_state_reflects_staged_count() {
+        assert_eq!(final_state(0, 2), STATE_FAILED);
+        assert_eq!(final_state(1, 2), STATE_PARTIAL);
+        assert_eq!(final_state(2, 2), STATE_DONE);
+    }
+
+    #[test]
+    fn enqueue_stages_files_until_done() {
+        let fx = Fixture::new("staging_done");
+        let paths = vec![fx.file("a.txt", 10), fx.file("b.txt", 20)];
+        let id = fx.insert(&paths);
+
+        assert!(fx.queue.enqueue(id, &paths, 0));
+        fx.wait_idle();
+
+        let (state, staged) = fx.item(id);
+        assert_eq!(state.as_deref(), Some(STATE_DONE));
+        assert_eq!(staged.len(), 2);
+        assert!(staged.iter().all(|p| Path::new(p).is_file()));
+        assert_eq!(fx.host.states(id), vec![STATE_PENDING, STATE_DONE]);
+    }
+
+    #[test]
+    fn enqueue_skips_unstageable_and_duplicate_items() {
+        let fx = Fixture::new("staging_enqueue");
+        let missing = vec![fx.dir.join("missing.txt").to_string_lossy().to_string()];
+        let id = fx.insert(&missing);
+        assert!(!fx.queue.enqueue(id, &missing, 0));
+        assert!(!fx.queue.enqueue(id, &["relative.txt".to_string()], 0));
+        assert!(fx.host.states(id).is_empty());
+
+        fx.queue.active.lock().push(id);
+        let paths = vec![fx.file("a.txt", 10)];
+        assert!(!fx.queue.enqueue(id, &paths, 0), "already queued");
+    }
+
+    #[test]
+    fn missing_files_end_partial_or_failed() {
+        let fx = Fixture::new("staging_partial");
+        let present = fx.file("present.txt", 10);
+        let gone = fx.dir.join("gone.txt").to_string_lossy().to_string();
+        let partial = fx.insert(&[present.clone(), gone.clone()]);
+        let failed = fx.insert(std::slice::from_ref(&gone));
+
+        // 入队后原文件被删除：直接执行任务模拟
+        fx.queue.run_job(&StagingJob {
+            item_id: partial,
+            files: vec![(present, 10), (gone.clone(), 10)],
+        });
+        fx.queue.run_job(&StagingJob {
+            item_id: failed,
+            files: vec![(gone, 10)],
+        });
+
+        let (state, staged) = fx.item(partial);
+        assert_eq!(state.as_deref(), Some(STATE_PARTIAL));
+        assert_eq!(staged.len(), 1);
+        assert_eq!(fx.item(failed).0.as_deref(), Some(STATE_FAILED));
+    }
+
+    #[test]
+    fn cancel_stops_copy_in_progress() {
+        let fx = Fixture::new("staging_cancel");
+        let _ = fx.host.cancel_on_progress.set(Arc::downgrade(&fx.queue));
+        // 多个复制块，保证首个块之后仍有取消检查
+        let paths = vec![fx.file("big.bin", 3 * 1024 * 1024), fx.file("b.txt", 10)];
+        let id = fx.insert(&paths);
+
+        assert!(fx.queue.enqueue(id, &paths, 0));
+        fx.wait_idle();
+
+        let (state, staged) = fx.item(id);
+        assert_eq!(state.as_deref(), Some(STATE_FAILED));
+        assert!(staged.is_empty());
+        assert!(!fx.queue.cancel(id), "finished items cannot be cancelled");
+    }
+
+    #[test]
+    fn resume_requeues_pending_items() {
+        let fx = Fixture::new("staging_resume");
+        let repo = ClipboardRepository::new(&fx.db);
+        let paths = vec![fx.file("a.txt", 10)];
+        let resumable = fx.insert(&paths);
+        let gone = fx.insert(&[fx.dir.join("gone.txt").to_string_lossy().to_string()]);
+        let finished = fx.insert(&paths);
+        repo.set_staging_state(resumable, Some(STATE_PENDING))
+            .unwrap();
+        repo.set_staging_state(gone, Some(STATE_PENDING)).unwrap();
+        repo.set_staging_state(finished, Some(STATE_DONE)).unwrap();
+
+        fx.queue.resume();
+        fx.wait_idle();
+
+        assert_eq!(fx.item(resumable).0.as_deref(), Some(STATE_DONE));
+        assert_eq!(fx.item(resumable).1.len(), 1);
+        assert_eq!(fx.item(gone).0.as_deref(), Some(STATE_FAILED));
+        assert_eq!(fx.item(finished).0.as_deref(), Some(STATE_DONE));
+        assert!(
+            fx.item(finished).1.is_empty(),
+            "finished items are not requeued"
+        );
+    }
+}
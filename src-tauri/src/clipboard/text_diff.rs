@@ -0,0 +1,142 @@
+//! 行级文本差异：用于编辑历史的差异摘要与版本对比
+
+use serde::Serialize;
+
+/// 超过该规模（行数乘积）时不再求最长公共子序列，中间段整体视为删除 + 新增
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// 差异中的一行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// 计算 `old` → `new` 的行级差异
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // 先剥离公共前后缀，缩小 LCS 规模（编辑通常只改动局部）
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+    let mut out: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|t| line(DiffKind::Equal, t))
+        .collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS {
+        out.extend(old_mid.iter().map(|t| line(DiffKind::Delete, t)));
+        out.extend(new_mid.iter().map(|t| line(DiffKind::Insert, t)));
+    } else {
+        // lcs[i][j] = old_mid[i..] 与 new_mid[j..] 的 LCS 长度
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                out.push(line(DiffKind::Equal, old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+                out.push(line(DiffKind::Delete, old_mid[i]));
+                i += 1;
+            } else {
+                out.push(line(DiffKind::Insert, new_mid[j]));
+                j += 1;
+            }
+        }
+        out.extend(old_mid[i..].iter().map(|t| line(DiffKind::Delete, t)));
+        out.extend(new_mid[j..].iter().map(|t| line(DiffKind::Insert, t)));
+    }
+
+    out.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|t| line(DiffKind::Equal, t)),
+    );
+    out
+}
+
+/// 差异摘要，如 `+3 -1`（新增 / 删除行数）
+pub fn diff_summary(old: &str, new: &str) -> String {
+    let diff = diff_lines(old, new);
+    let added = diff.iter().filter(|l| l.kind == DiffKind::Insert).count();
+    let removed = diff.iter().filter(|l| l.kind == DiffKind::Delete).count();
+    format!("+{added} -{removed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(diff: &[DiffLine]) -> Vec<(DiffKind, &str)> {
+        diff.iter().map(|l| (l.kind, l.text.as_str())).collect()
+    }
+
+    #[test]
+    fn identical_text_is_all_equal() {
+        let diff = diff_lines("a\nb", "a\nb");
+        assert!(diff.iter().all(|l| l.kind == DiffKind::Equal));
+        assert_eq!(diff_summary("a\nb", "a\nb"), "+0 -0");
+    }
+
+    #[test]
+    fn changed_line_is_delete_then_insert() {
+        let diff = diff_lines("a\nb\nc", "a\nB\nc\nd");
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (DiffKind::Equal, "a"),
+                (DiffKind::Delete, "b"),
+                (DiffKind::Insert, "B"),
+                (DiffKind::Equal, "c"),
+                (DiffKind::Insert, "d"),
+            ]
+        );
+        assert_eq!(diff_summary("a\nb\nc", "a\nB\nc\nd"), "+2 -1");
+    }
+
+    #[test]
+    fn lcs_keeps_moved_context() {
+        let diff = diff_lines("x\na\nb\ny", "x\nb\na\ny");
+        let equal = diff.iter().filter(|l| l.kind == DiffKind::Equal).count();
+        assert_eq!(equal, 3);
+        assert_eq!(diff_summary("x\na\nb\ny", "x\nb\na\ny"), "+1 -1");
+    }
+
+    #[test]
+    fn empty_sides() {
+        assert_eq!(diff_summary("", "a\nb"), "+2 -0");
+        assert_eq!(diff_summary("a\nb", ""), "+0 -2");
+    }
+}
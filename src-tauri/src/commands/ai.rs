@@ -0,0 +1,370 @@
+//! AI 操作：对选中条目运行提示词模板，结果流式推送到结果窗口并保存为新条目
+//! （模板与接口配置见 `crate::ai`）
+
+use crate::ai::openai::{self, ChatError};
+use crate::ai::{self, AiSettings, AiTemplate};
+use crate::database::{ClipboardRepository, SettingsRepository};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager, State};
+use tracing::{info, warn};
+
+use super::AppState;
+
+const RESULT_WINDOW_LABEL: &str = "ai-result";
+/// 结果窗口进度事件（payload 为 [`AiRun`] 快照）
+const UPDATE_EVENT: &str = "ai-action-update";
+/// 流式输出时推送进度的最小间隔
+const EMIT_INTERVAL: Duration = Duration::from_millis(50);
+/// 单次请求总超时（本地模型生成较慢）
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AiRunStatus {
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+/// 一次 AI 操作的状态快照
+#[derive(Debug, Clone, Serialize)]
+pub struct AiRun {
+    pub run_id: u64,
+    pub template_id: String,
+    pub template_name: String,
+    pub source_ids: Vec<i64>,
+    pub source_chars: usize,
+    pub text: String,
+    pub status: AiRunStatus,
+    pub error: Option<String>,
+    /// 结果保存后的条目 ID
+    pub item_id: Option<i64>,
+    #[serde(skip)]
+    group_id: Option<i64>,
+}
+
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+/// 进行中操作的取消标记
+static CANCEL_FLAGS: LazyLock<Mutex<HashMap<u64, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 最近一次操作（结果窗口挂载时读取）
+static LAST_RUN: Mutex<Option<AiRun>> = Mutex::new(None);
+
+fn load_settings(state: &AppState) -> AiSettings {
+    let repo = SettingsRepository::new(&state.db);
+    AiSettings::load(|key| repo.get(key).ok().flatten())
+}
+
+fn load_templates(state: &AppState) -> Vec<AiTemplate> {
+    let raw = SettingsRepository::new(&state.db)
+        .get(ai::TEMPLATES_SETTING)
+        .ok()
+        .flatten();
+    ai::parse_templates(raw.as_deref())
+}
+
+fn build_client(settings: &AiSettings) -> Result<reqwest::blocking::Client, String> {
+    let builder = reqwest::blocking::Client::builder()
+        .connect_timeout(Duration::from_secs(15))
+        .timeout(REQUEST_TIMEOUT);
+    crate::proxy::apply_proxy(builder, &settings.proxy_mode, &settings.proxy_url)?
+        .build()
+        .map_err(|e| format!("AI:CREATE_CLIENT_FAILED:{e}"))
+}
+
+/// 更新最近一次操作（仅当 run_id 一致）并推送给结果窗口
+fn update_run(app: &tauri::AppHandle, run_id: u64, f: impl FnOnce(&mut AiRun)) {
+    let snapshot = {
+        let mut guard = LAST_RUN.lock();
+        let Some(run) = guard.as_mut().filter(|r| r.run_id == run_id) else {
+            return;
+        };
+        f(run);
+        run.clone()
+    };
+    let _ = app.emit_to(RESULT_WINDOW_LABEL, UPDATE_EVENT, &snapshot);
+}
+
+/// 列出提示词模板（未自定义时为内置模板）
+#[tauri::command]
+pub async fn list_ai_templates(state: State<'_, Arc<AppState>>) -> Result<Vec<AiTemplate>, String> {
+    Ok(load_templates(&state))
+}
+
+/// 保存提示词模板
+#[tauri::command]
+pub async fn save_ai_templates(
+    state: State<'_, Arc<AppState>>,
+    templates: Vec<AiTemplate>,
+) -> Result<(), String> {
+    ai::validate_templates(&templates)?;
+    let json = serde_json::to_string(&templates).map_err(|e| e.to_string())?;
+    SettingsRepository::new(&state.db)
+        .set(ai::TEMPLATES_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// 恢复内置模板
+#[tauri::command]
+pub async fn reset_ai_templates(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<AiTemplate>, String> {
+    SettingsRepository::new(&state.db)
+        .set(ai::TEMPLATES_SETTING, "")
+        .map_err(|e| e.to_string())?;
+    Ok(ai::default_templates())
+}
+
+/// 对条目运行模板；多个条目按给定顺序合并后作为输入。
+/// 立即返回 run_id，结果通过结果窗口的 `ai-action-update` 事件流式推送；
+/// `save_result` 默认 true，完成后保存为新条目（与首个条目同组）。
+#[tauri::command]
+pub async fn run_ai_action(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    item_ids: Vec<i64>,
+    template_id: String,
+    save_result: Option<bool>,
+) -> Result<u64, String> {
+    if item_ids.is_empty() {
+        return Err("AI:NO_ITEMS".to_string());
+    }
+    let state = state.inner().clone();
+    // 读库与构建阻塞客户端都在阻塞线程池中进行，不占用异步运行时
+    let PreparedRun {
+        template,
+        settings,
+        texts,
+        group_id,
+        source_chars,
+        client,
+    } = {
+        let state = state.clone();
+        let item_ids = item_ids.clone();
+        tokio::task::spawn_blocking(move || prepare_run(&state, &item_ids, &template_id))
+            .await
+            .map_err(|e| e.to_string())??
+    };
+
+    let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+    let cancel = Arc::new(AtomicBool::new(false));
+    CANCEL_FLAGS.lock().insert(run_id, cancel.clone());
+    // 新操作替换窗口中的旧操作，旧操作不再需要继续生成
+    if let Some(previous) = LAST_RUN.lock().replace(AiRun {
+        run_id,
+        template_id: template.id.clone(),
+        template_name: template.name.clone(),
+        source_ids: item_ids,
+        source_chars,
+        text: String::new(),
+        status: AiRunStatus::Running,
+        error: None,
+        item_id: None,
+        group_id,
+    }) {
+        cancel_run(previous.run_id);
+    }
+    open_result_window(&app, run_id)?;
+
+    std::thread::spawn(move || {
+        let messages = template.render(&texts);
+        let mut output = String::new();
+        let mut last_emit = Instant::now();
+        let result = openai::chat_stream(
+            &client,
+            &settings.chat,
+            &messages,
+            template.temperature(),
+            &cancel,
+            &mut |delta| {
+                output.push_str(delta);
+                if last_emit.elapsed() >= EMIT_INTERVAL {
+                    last_emit = Instant::now();
+                    let text = output.clone();
+                    update_run(&app, run_id, |run| run.text = text);
+                }
+            },
+        );
+        CANCEL_FLAGS.lock().remove(&run_id);
+        match result {
+            Ok(text) => {
+                info!(
+                    "AI action {} on {} chars -> {} chars",
+                    template.id,
+                    source_chars,
+                    text.len()
+                );
+                update_run(&app, run_id, |run| {
+                    run.text = text;
+                    run.status = AiRunStatus::Done;
+                });
+                if save_result.unwrap_or(true)
+                    && let Err(e) = save_run(&state, &app, run_id)
+                {
+                    warn!("Failed to save AI result: {}", e);
+                    update_run(&app, run_id, |run| run.error = Some(e));
+                }
+            }
+            Err(ChatError::Cancelled) => {
+                update_run(&app, run_id, |run| run.status = AiRunStatus::Cancelled);
+            }
+            Err(e) => {
+                let error = e.code("AI");
+                warn!("AI action {} failed: {}", template.id, error);
+                update_run(&app, run_id, |run| {
+                    run.status = AiRunStatus::Failed;
+                    run.error = Some(error);
+                });
+            }
+        }
+    });
+    Ok(run_id)
+}
+
+/// 运行前准备好的模板、配置与源文本
+struct PreparedRun {
+    template: AiTemplate,
+    settings: AiSettings,
+    texts: Vec<String>,
+    /// 首个源条目的分组（结果保存到同一分组）
+    group_id: Option<i64>,
+    source_chars: usize,
+    client: reqwest::blocking::Client,
+}
+
+fn prepare_run(
+    state: &AppState,
+    item_ids: &[i64],
+    template_id: &str,
+) -> Result<PreparedRun, String> {
+    let template = load_templates(state)
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("AI:TEMPLATE_NOT_FOUND:{template_id}"))?;
+    let settings = load_settings(state);
+    settings.validate()?;
+
+    let repo = ClipboardRepository::new(&state.db);
+    let mut texts = Vec::with_capacity(item_ids.len());
+    let mut group_id = None;
+    for (index, id) in item_ids.iter().enumerate() {
+        let item = repo
+            .get_by_id(*id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("条目 {id} 未找到"))?;
+        if index == 0 {
+            group_id = item.group_id;
+        }
+        // 图片 / 文件等无文本的条目跳过
+        if let Some(text) = item.text_content.filter(|t| !t.trim().is_empty()) {
+            texts.push(text);
+        }
+    }
+    if texts.is_empty() {
+        return Err("AI:NO_TEXT".to_string());
+    }
+    let source_chars: usize = texts.iter().map(|t| t.chars().count()).sum();
+    if source_chars > ai::MAX_INPUT_CHARS {
+        return Err(format!("AI:INPUT_TOO_LARGE:{}", ai::MAX_INPUT_CHARS));
+    }
+    let client = build_client(&settings)?;
+    Ok(PreparedRun {
+        template,
+        settings,
+        texts,
+        group_id,
+        source_chars,
+        client,
+    })
+}
+
+fn cancel_run(run_id: u64) -> bool {
+    CANCEL_FLAGS
+        .lock()
+        .get(&run_id)
+        .map(|flag| flag.store(true, Ordering::Relaxed))
+        .is_some()
+}
+
+/// 取消进行中的操作；操作已结束时返回 false
+#[tauri::command]
+pub async fn cancel_ai_action(run_id: u64) -> Result<bool, String> {
+    Ok(cancel_run(run_id))
+}
+
+/// 最近一次操作的快照（结果窗口挂载时调用）
+#[tauri::command]
+pub async fn get_ai_run() -> Result<Option<AiRun>, String> {
+    Ok(LAST_RUN.lock().clone())
+}
+
+fn save_run(state: &AppState, app: &tauri::AppHandle, run_id: u64) -> Result<Option<i64>, String> {
+    let (text, group_id) = {
+        let guard = LAST_RUN.lock();
+        let run = guard
+            .as_ref()
+            .filter(|r| r.run_id == run_id)
+            .ok_or("AI:RUN_NOT_FOUND")?;
+        if run.status != AiRunStatus::Done {
+            return Err("AI:RUN_NOT_FINISHED".to_string());
+        }
+        if run.item_id.is_some() {
+            return Ok(run.item_id);
+        }
+        (run.text.clone(), run.group_id)
+    };
+    if text.is_empty() {
+        return Err("AI:EMPTY_RESULT".to_string());
+    }
+    let item_id = super::save_generated_item(state, app, Some(text), None, group_id)?;
+    update_run(app, run_id, |run| run.item_id = item_id);
+    Ok(item_id)
+}
+
+/// 手动保存结果（`save_result = false` 时由结果窗口调用）
+#[tauri::command]
+pub async fn save_ai_result(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    run_id: u64,
+) -> Result<Option<i64>, String> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || save_run(&state, &app, run_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn open_result_window(app: &tauri::AppHandle, run_id: u64) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(RESULT_WINDOW_LABEL) {
+        if let Some(run) = LAST_RUN.lock().clone().filter(|r| r.run_id == run_id) {
+            let _ = window.emit(UPDATE_EVENT, &run);
+        }
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    tauri::WebviewWindowBuilder::new(
+        app,
+        RESULT_WINDOW_LABEL,
+        tauri::WebviewUrl::App("/ai-result".into()),
+    )
+    .title("AI")
+    .inner_size(560.0, 460.0)
+    .min_inner_size(360.0, 280.0)
+    .decorations(false)
+    .transparent(true)
+    .shadow(true)
+    .resizable(true)
+    .always_on_top(true)
+    .center()
+    .build()
+    .map_err(|e| format!("AI:CREATE_WINDOW_FAILED:{e}"))?;
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! 本地脚本 API 的开关、监听地址与令牌管理（服务实现见 `crate::api`）
+
+use crate::api::{AddContent, ApiContext, ApiEvent, ApiHost, ApiServer, Bind};
+use crate::database::{ClipboardItem, SettingsRepository};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{Emitter, State};
+use tracing::{info, warn};
+
+use super::AppState;
+
+const API_ENABLED_SETTING: &str = "api_enabled";
+const API_PORT_SETTING: &str = "api_port";
+/// Unix socket 路径（仅 Unix 平台生效，设置后不再监听 TCP）
+const API_SOCKET_SETTING: &str = "api_socket_path";
+const API_TOKEN_SETTING: &str = "api_token";
+const DEFAULT_API_PORT: u16 = 27_315;
+
+static SERVER: Mutex<Option<ApiServer>> = Mutex::new(None);
+
+/// 最近一次启动失败的原因（端口占用等），供设置页展示
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Serialize)]
+pub struct ApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    /// 实际监听地址（未运行时为 None）
+    pub endpoint: Option<String>,
+    pub port: u16,
+    pub socket_path: Option<String>,
+    pub token: String,
+    pub error: Option<String>,
+}
+
+/// API 宿主：新增条目走捕获处理流程，粘贴复用现有粘贴流程
+struct AppApiHost {
+    state: Arc<AppState>,
+    app: tauri::AppHandle,
+}
+
+impl ApiHost for AppApiHost {
+    fn add(&self, content: AddContent) -> Result<Option<i64>, String> {
+        super::save_generated_item(
+            &self.state,
+            &self.app,
+            Some(content.text),
+            content.html,
+            content.group_id,
+        )
+    }
+
+    fn paste(&self, item: &ClipboardItem, plain: bool) -> Result<(), String> {
+        if plain {
+            let text = crate::clipboard::format_write::item_plain_text(item)?;
+            super::clipboard::paste_plain_text_to_active_window(&self.state, &self.app, &text, true)
+        } else {
+            super::clipboard::paste_item_to_active_window(&self.state, &self.app, item, true)
+        }
+    }
+
+    fn notify(&self, event: ApiEvent) {
+        let emitted = match &event {
+            ApiEvent::ItemAdded(id) => self.app.emit("clipboard-updated", id),
+            ApiEvent::ItemUpdated(id) | ApiEvent::ItemDeleted(id) => {
+                self.app.emit("clipboard-items-changed", id)
+            }
+            ApiEvent::GroupCreated(group) => self.app.emit("groups-changed", group.id),
+        };
+        if let Err(e) = emitted {
+            warn!("Failed to emit API change: {}", e);
+        }
+        crate::api::publish(event);
+    }
+}
+
+fn token(settings: &SettingsRepository) -> String {
+    match settings.get(API_TOKEN_SETTING).ok().flatten() {
+        Some(token) if !token.is_empty() => token,
+        _ => {
+            let token = crate::api::generate_token();
+            if let Err(e) = settings.set(API_TOKEN_SETTING, &token) {
+                warn!("Failed to save API token: {}", e);
+            }
+            token
+        }
+    }
+}
+
+fn bind(settings: &SettingsRepository) -> Bind {
+    #[cfg(unix)]
+    if let Some(path) = settings
+        .get(API_SOCKET_SETTING)
+        .ok()
+        .flatten()
+        .filter(|p| !p.trim().is_empty())
+    {
+        return Bind::Unix(path.trim().into());
+    }
+    Bind::Tcp(
+        settings
+            .get_parsed(API_PORT_SETTING)
+            .unwrap_or(DEFAULT_API_PORT),
+    )
+}
+
+/// 按设置启动 / 停止服务（启动时与设置变更后调用）
+pub(crate) fn apply_settings(state: &Arc<AppState>, app: &tauri::AppHandle) {
+    let settings = SettingsRepository::new(&state.db);
+    let mut server = SERVER.lock();
+    // 先停止旧实例，释放端口 / socket
+    server.take();
+    *LAST_ERROR.lock() = None;
+    if !settings.get_bool(API_ENABLED_SETTING, false) {
+        return;
+    }
+
+    let ctx = ApiContext {
+        db: state.db.clone(),
+        token: token(&settings),
+        host: Arc::new(AppApiHost {
+            state: state.clone(),
+            app: app.clone(),
+        }),
+    };
+    match ApiServer::start(bind(&settings), ctx) {
+        Ok(started) => *server = Some(started),
+        Err(e) => {
+            warn!("Failed to start local API: {}", e);
+            *LAST_ERROR.lock() = Some(e.to_string());
+        }
+    }
+}
+
+fn status(state: &AppState) -> ApiStatus {
+    let settings = SettingsRepository::new(&state.db);
+    let endpoint = SERVER.lock().as_ref().map(|s| s.endpoint().to_string());
+    ApiStatus {
+        enabled: settings.get_bool(API_ENABLED_SETTING, false),
+        running: endpoint.is_some(),
+        endpoint,
+        port: settings
+            .get_parsed(API_PORT_SETTING)
+            .unwrap_or(DEFAULT_API_PORT),
+        socket_path: settings
+            .get(API_SOCKET_SETTING)
+            .ok()
+            .flatten()
+            .filter(|p| !p.is_empty()),
+        token: token(&settings),
+        error: LAST_ERROR.lock().clone(),
+    }
+}
+
+/// 获取 API 运行状态与令牌
+#[tauri::command]
+pub async fn get_api_status(state: State<'_, Arc<AppState>>) -> Result<ApiStatus, String> {
+    Ok(status(&state))
+}
+
+/// 开启 / 关闭本地 API
+#[tauri::command]
+pub async fn set_api_enabled(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    enabled: bool,
+) -> Result<ApiStatus, String> {
+    SettingsRepository::new(&state.db)
+        .set(API_ENABLED_SETTING, if enabled { "true" } else { "false" })
+        .map_err(|e| e.to_string())?;
+    apply_settings(&state, &app);
+    info!("Local API {}", if enabled { "enabled" } else { "disabled" });
+    Ok(status(&state))
+}
+
+/// 修改监听端口 / Unix socket 路径（运行中则立即重启）
+#[tauri::command]
+pub async fn update_api_bind(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    port: u16,
+    socket_path: Option<String>,
+) -> Result<ApiStatus, String> {
+    if port == 0 {
+        return Err("端口无效".to_string());
+    }
+    let settings = SettingsRepository::new(&state.db);
+    settings
+        .set(API_PORT_SETTING, &port.to_string())
+        .map_err(|e| e.to_string())?;
+    settings
+        .set(
+            API_SOCKET_SETTING,
+            socket_path.as_deref().map(str::trim).unwrap_or_default(),
+        )
+        .map_err(|e| e.to_string())?;
+    apply_settings(&state, &app);
+    Ok(status(&state))
+}
+
+/// 重新生成令牌（旧令牌立即失效）
+#[tauri::command]
+pub async fn regenerate_api_token(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<ApiStatus, String> {
+    SettingsRepository::new(&state.db)
+        .set(API_TOKEN_SETTING, &crate::api::generate_token())
+        .map_err(|e| e.to_string())?;
+    apply_settings(&state, &app);
+    Ok(status(&state))
+}
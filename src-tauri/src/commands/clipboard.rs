@@ -265,10 +265,12 @@ pub async fn get_clipboard_items(
     pinned_only: Option<bool>,
     favorite_only: Option<bool>,
     group_id: Option<i64>,
+    subtype: Option<String>,
+    tag_ids: Option<Vec<i64>>,
     limit: Option<i64>,
     offset: Option<i64>,
 ) -> Result<Vec<ClipboardItem>, String> {
-    use crate::database::QueryOptions;
+    use crate::database::{QueryOptions, TagRepository};
 
     let repo = ClipboardRepository::new(&state.db);
     let search_keyword = search.clone();
@@ -278,14 +280,20 @@ pub async fn get_clipboard_items(
         pinned_only: pinned_only.unwrap_or(false),
         favorite_only: favorite_only.unwrap_or(false),
         group_id,
+        subtype,
+        tag_ids,
         limit,
         offset,
     };
     let mut items = repo.list(options).map_err(|e| e.to_string())?;
+    TagRepository::new(&state.db)
+        .attach_tags(&mut items)
+        .map_err(|e| e.to_string())?;
     if let Some(ref keyword) = search_keyword {
         let keyword_lower = keyword.to_lowercase();
         for item in &mut items {
-            if let Some(ref text) = item.text_content {
+            // 图片条目无文本内容，命中时以 OCR 文字作为上下文片段
+            if let Some(text) = item.text_content.as_ref().or(item.ocr_text.as_ref()) {
                 let preview_has_match = item
                     .preview
                     .as_ref()
@@ -295,6 +303,7 @@ pub async fn get_clipboard_items(
                 }
             }
             item.text_content = None;
+            item.ocr_text = None;
         }
     }
     Ok(items)
@@ -307,7 +316,74 @@ pub async fn get_clipboard_item(
     id: i64,
 ) -> Result<Option<ClipboardItem>, String> {
     let repo = ClipboardRepository::new(&state.db);
-    repo.get_by_id(id).map_err(|e| e.to_string())
+    let Some(item) = repo.get_by_id(id).map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let mut items = [item];
+    crate::database::TagRepository::new(&state.db)
+        .attach_tags(&mut items)
+        .map_err(|e| e.to_string())?;
+    let [item] = items;
+    Ok(Some(item))
+}
+
+/// 获取条目的派生条目（如译文），新到旧排列
+#[tauri::command]
+pub async fn get_derived_items(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+) -> Result<Vec<ClipboardItem>, String> {
+    let mut items = ClipboardRepository::new(&state.db)
+        .list_derived(id)
+        .map_err(|e| e.to_string())?;
+    crate::database::TagRepository::new(&state.db)
+        .attach_tags(&mut items)
+        .map_err(|e| e.to_string())?;
+    Ok(items)
+}
+
+/// 取消文件条目的后台 staging，返回是否有进行中的任务
+#[tauri::command]
+pub async fn cancel_file_staging(id: i64) -> Result<bool, String> {
+    Ok(crate::clipboard::staging::cancel(id))
+}
+
+/// 重新 staging 文件条目（补全失败或被取消的文件），返回是否入队
+#[tauri::command]
+pub async fn retry_file_staging(state: State<'_, Arc<AppState>>, id: i64) -> Result<bool, String> {
+    let item = ClipboardRepository::new(&state.db)
+        .get_by_id(id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Item not found")?;
+    if item.content_type != "files" {
+        return Err("Only file items can be staged".to_string());
+    }
+    let staged = crate::clipboard::file_clipboard::decode_payload(item.file_payload.as_deref())
+        .map(|payload| payload.staged)
+        .unwrap_or_default();
+    // 仍有效的 staging 副本不再复制
+    let missing: Vec<String> =
+        crate::clipboard::file_clipboard::parse_file_paths(item.file_paths.as_deref())
+            .into_iter()
+            .filter(|path| {
+                !staged
+                    .iter()
+                    .any(|s| &s.original == path && std::path::Path::new(&s.staged).exists())
+            })
+            .collect();
+    if missing.is_empty() {
+        ClipboardRepository::new(&state.db)
+            .set_staging_state(id, Some(crate::clipboard::staging::STATE_DONE))
+            .map_err(|e| e.to_string())?;
+        return Ok(false);
+    }
+    Ok(crate::clipboard::staging::enqueue(id, &missing, 0))
+}
+
+/// 排队或进行中的文件 staging 条目 ID
+#[tauri::command]
+pub async fn get_active_file_staging() -> Result<Vec<i64>, String> {
+    Ok(crate::clipboard::staging::active_items())
 }
 
 /// 获取条目总数
@@ -318,6 +394,8 @@ pub async fn get_clipboard_count(
     pinned_only: Option<bool>,
     favorite_only: Option<bool>,
     group_id: Option<i64>,
+    subtype: Option<String>,
+    tag_ids: Option<Vec<i64>>,
 ) -> Result<i64, String> {
     use crate::database::QueryOptions;
 
@@ -327,6 +405,8 @@ pub async fn get_clipboard_count(
         pinned_only: pinned_only.unwrap_or(false),
         favorite_only: favorite_only.unwrap_or(false),
         group_id,
+        subtype,
+        tag_ids,
         ..Default::default()
     };
     repo.count(options).map_err(|e| e.to_string())
@@ -448,33 +528,36 @@ pub async fn clear_all_history(state: State<'_, Arc<AppState>>) -> Result<i64, S
     Ok(deleted)
 }
 
-/// 清空所有非固定/非收藏历史（同时删除图片文件），按分组
+/// 清空所有非固定/非收藏历史（同时删除图片文件），按分组或标签
 #[tauri::command]
 pub async fn clear_history(
     state: State<'_, Arc<AppState>>,
     group_id: Option<i64>,
     content_type: Option<String>,
+    tag_ids: Option<Vec<i64>>,
 ) -> Result<i64, String> {
     use tracing::info;
 
     let repo = ClipboardRepository::new(&state.db);
+    let tag_ids = tag_ids.unwrap_or_default();
     let image_paths = repo
-        .get_clearable_image_paths(group_id, content_type.as_deref())
+        .get_clearable_image_paths(group_id, content_type.as_deref(), &tag_ids)
         .unwrap_or_default();
     let file_payloads = repo
-        .get_clearable_file_payloads(group_id, content_type.as_deref())
+        .get_clearable_file_payloads(group_id, content_type.as_deref(), &tag_ids)
         .unwrap_or_default();
     let deleted = repo
-        .clear_history(group_id, content_type.as_deref())
+        .clear_history(group_id, content_type.as_deref(), &tag_ids)
         .map_err(|e| e.to_string())?;
     crate::clipboard::cleanup_deleted_assets(&image_paths, &file_payloads);
 
     info!(
-        "Cleared {} clipboard items ({} image files) (group: {:?}, content_type: {:?})",
+        "Cleared {} clipboard items ({} image files) (group: {:?}, content_type: {:?}, tags: {:?})",
         deleted,
         image_paths.len(),
         group_id,
-        content_type
+        content_type,
+        tag_ids
     );
     Ok(deleted)
 }
@@ -503,15 +586,16 @@ pub async fn update_text_content(
         debug!("Deleted empty item {}", id);
         Ok(true)
     } else {
+        let revision_limit = super::revisions::revision_limit(&state);
         if let Ok(Some(item)) = repo.get_by_id(id) {
             let payloads: Vec<String> = item.file_payload.map(|p| vec![p]).unwrap_or_default();
-            repo.update_text_content(id, &new_text)
+            repo.update_text_content(id, &new_text, revision_limit)
                 .map_err(|e| e.to_string())?;
             if !payloads.is_empty() {
                 crate::clipboard::cleanup_deleted_assets(&[], &payloads);
             }
         } else {
-            repo.update_text_content(id, &new_text)
+            repo.update_text_content(id, &new_text, revision_limit)
                 .map_err(|e| e.to_string())?;
         }
         debug!("Updated text content for item {}", id);
@@ -581,6 +665,30 @@ pub async fn paste_content_as_plain(
     Ok(())
 }
 
+/// 粘贴图片条目中 OCR 识别出的文字
+#[tauri::command]
+pub async fn paste_image_text(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    id: i64,
+    close_window: Option<bool>,
+) -> Result<(), String> {
+    let repo = ClipboardRepository::new(&state.db);
+    let item = repo
+        .get_by_id(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "条目未找到".to_string())?;
+
+    let text = item
+        .ocr_text
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "图片中未识别到文字".to_string())?;
+
+    paste_plain_text_to_active_window(&state, &app, &text, close_window.unwrap_or(true))?;
+    debug!("Pasted OCR text of item {}", id);
+    Ok(())
+}
+
 /// 将任意文本直接粘贴到当前活动窗口（用于表情、片段等功能）
 #[tauri::command]
 pub async fn paste_text_direct(
@@ -711,7 +819,7 @@ where
     })
 }
 
-fn paste_item_to_active_window(
+pub(super) fn paste_item_to_active_window(
     state: &Arc<AppState>,
     app: &tauri::AppHandle,
     item: &ClipboardItem,
@@ -724,7 +832,7 @@ fn paste_item_to_active_window(
 }
 
 /// 纯文本粘贴：写剪贴板 → 隐藏窗口 → 模拟 Ctrl+V
-fn paste_plain_text_to_active_window(
+pub(super) fn paste_plain_text_to_active_window(
     state: &Arc<AppState>,
     app: &tauri::AppHandle,
     text: &str,
@@ -0,0 +1,193 @@
+//! 收集模式：开启后连续复制的内容合并为一条记录，结束时写回系统剪贴板
+//!
+//! 可从托盘菜单、专用快捷键或前端切换，状态变化通过 `collect-mode-changed` 事件推送。
+
+use crate::clipboard::collect::{self, CollectOptions, CollectState};
+use crate::database::{ClipboardRepository, SettingsRepository};
+use std::sync::Arc;
+use tauri::{Emitter, Manager, State};
+use tracing::{debug, info, warn};
+
+use super::AppState;
+
+const COLLECT_SHORTCUT_SETTING: &str = "collect_shortcut";
+
+fn emit_changed(app: &tauri::AppHandle, state: &CollectState) {
+    let _ = app.emit("collect-mode-changed", state);
+    crate::tray::refresh_collect_label();
+}
+
+/// 开启收集模式（已开启时直接返回当前状态）
+pub(crate) fn start(state: &AppState, app: &tauri::AppHandle) -> CollectState {
+    let settings = SettingsRepository::new(&state.db).get_batch(&[
+        collect::SEPARATOR_SETTING,
+        collect::ORDER_SETTING,
+        collect::DEDUP_SETTING,
+    ]);
+    if collect::start(CollectOptions::from_settings(&settings)) {
+        info!("Collect mode started");
+        let snapshot = collect::state();
+        emit_changed(app, &snapshot);
+        snapshot
+    } else {
+        collect::state()
+    }
+}
+
+/// 结束收集模式，并将合并条目写入系统剪贴板
+pub(crate) fn finish(
+    state: &Arc<AppState>,
+    app: &tauri::AppHandle,
+) -> Result<CollectState, String> {
+    let Some(finished) = collect::finish() else {
+        return Ok(CollectState::default());
+    };
+    info!(
+        "Collect mode finished: item={:?}, fragments={}",
+        finished.item_id, finished.fragments
+    );
+    emit_changed(app, &finished);
+
+    let Some(id) = finished.item_id else {
+        return Ok(finished);
+    };
+    let Some(item) = ClipboardRepository::new(&state.db)
+        .get_by_id(id)
+        .map_err(|e| e.to_string())?
+    else {
+        debug!("Collected item {} was deleted, nothing to place", id);
+        return Ok(finished);
+    };
+    super::with_paused_monitor(state, || {
+        let mut clipboard = clipboard_rs::ClipboardContext::new()
+            .map_err(|e| format!("Failed to access clipboard: {e}"))?;
+        crate::clipboard::format_write::write_item_to_clipboard(&item, &mut clipboard)
+    })?;
+    debug!("Placed collected item {} on the clipboard", id);
+    Ok(finished)
+}
+
+/// 切换收集模式
+pub(crate) fn toggle(
+    state: &Arc<AppState>,
+    app: &tauri::AppHandle,
+) -> Result<CollectState, String> {
+    if collect::is_active() {
+        finish(state, app)
+    } else {
+        Ok(start(state, app))
+    }
+}
+
+/// 获取收集模式状态
+#[tauri::command]
+pub async fn get_collect_state() -> Result<CollectState, String> {
+    Ok(collect::state())
+}
+
+/// 开启收集模式
+#[tauri::command]
+pub async fn start_collect(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<CollectState, String> {
+    Ok(start(&state, &app))
+}
+
+/// 结束收集模式并把合并结果放到系统剪贴板
+#[tauri::command]
+pub async fn finish_collect(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<CollectState, String> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || finish(&state, &app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 切换收集模式
+#[tauri::command]
+pub async fn toggle_collect(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<CollectState, String> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || toggle(&state, &app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 注册收集模式快捷键
+pub fn register_collect_shortcut(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<Arc<AppState>>() else {
+        return;
+    };
+    let shortcut_str = match SettingsRepository::new(&state.db)
+        .get(COLLECT_SHORTCUT_SETTING)
+        .ok()
+        .flatten()
+    {
+        Some(s) if !s.is_empty() => s,
+        _ => return,
+    };
+
+    let registered = crate::hotkey::register(
+        &shortcut_str,
+        Arc::new(|app, key_state| {
+            if key_state != crate::hotkey::KeyState::Pressed {
+                return;
+            }
+            let Some(state) = app.try_state::<Arc<AppState>>() else {
+                return;
+            };
+            let state = state.inner().clone();
+            let app = app.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = toggle(&state, &app) {
+                    warn!("Failed to toggle collect mode: {}", e);
+                }
+            });
+        }),
+    );
+    if !registered {
+        warn!("Invalid collect shortcut format: {}", shortcut_str);
+    }
+}
+
+/// 注销收集模式快捷键
+pub fn unregister_collect_shortcut(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<Arc<AppState>>() else {
+        return;
+    };
+    if let Some(shortcut_str) = SettingsRepository::new(&state.db)
+        .get(COLLECT_SHORTCUT_SETTING)
+        .ok()
+        .flatten()
+        && !shortcut_str.is_empty()
+    {
+        crate::hotkey::unregister(&shortcut_str);
+    }
+}
+
+/// 更新收集模式快捷键（空字符串 = 关闭）
+#[tauri::command]
+pub async fn update_collect_shortcut(
+    app: tauri::AppHandle,
+    new_shortcut: String,
+) -> Result<(), String> {
+    let new_shortcut = new_shortcut.trim().to_string();
+    if !new_shortcut.is_empty() && !crate::shortcut_has_modifier(&new_shortcut) {
+        return Err("SHORTCUT:MODIFIER_REQUIRED".to_string());
+    }
+
+    unregister_collect_shortcut(&app);
+    let state = app.state::<Arc<AppState>>();
+    SettingsRepository::new(&state.db)
+        .set(COLLECT_SHORTCUT_SETTING, &new_shortcut)
+        .map_err(|e| format!("SHORTCUT:SAVE_FAILED:{e}"))?;
+    if !new_shortcut.is_empty() && !crate::shortcuts_are_disabled() {
+        register_collect_shortcut(&app);
+    }
+    Ok(())
+}
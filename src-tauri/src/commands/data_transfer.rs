@@ -1,62 +1,26 @@
+use crate::backup::merge::{self, MergeReport};
+use crate::backup::open_export::{self, OpenFormat};
+use crate::backup::{self, ExportFilter, Manifest, TransferProgress, crypt};
 use crate::commands::AppState;
 use crate::config::{self, AppConfig};
-use crate::database;
+use crate::database::{self, QueryOptions};
+use crate::importers::{self, ImportReport, ImportSource};
 use crate::utils::format_size;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// 导出 / 导入进度事件：`{ phase, done, total }`，每 100ms 最多一次
+const PROGRESS_EVENT: &str = "data-transfer-progress";
+/// 合并导入时备份的解压目录（位于数据目录下）
+const MERGE_STAGING_DIR: &str = "merge.tmp";
 
 fn chrono_timestamp() -> String {
     chrono::Local::now().format("%Y%m%d_%H%M%S").to_string()
 }
 
-fn add_dir_to_zip(
-    zip: &mut zip::ZipWriter<std::fs::File>,
-    dir: &std::path::Path,
-    prefix: &str,
-    options: zip::write::SimpleFileOptions,
-) -> Result<(), String> {
-    use std::io::Write;
-
-    if !dir.exists() || !dir.is_dir() {
-        return Ok(());
-    }
-
-    for entry in std::fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
-        let path = entry.path();
-        if path.is_file() {
-            let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
-            zip.start_file(&name, options).map_err(|e| e.to_string())?;
-            let buf = std::fs::read(&path).map_err(|e| format!("读取 {path:?} 失败: {e}"))?;
-            zip.write_all(&buf).map_err(|e| e.to_string())?;
-        }
-    }
-    Ok(())
-}
-
 /// 检测并应用待导入的 staging 数据库文件（clipboard.db.import → clipboard.db）。
-fn sanitize_zip_relative_path(name: &str) -> Option<std::path::PathBuf> {
-    use std::path::{Component, Path, PathBuf};
-
-    let raw = Path::new(name);
-    if raw.is_absolute() {
-        return None;
-    }
-
-    let mut clean = PathBuf::new();
-    for component in raw.components() {
-        match component {
-            Component::Normal(seg) => clean.push(seg),
-            Component::CurDir => {}
-            // 拒绝根/前缀/父目录防止路径穿越
-            Component::RootDir | Component::Prefix(_) | Component::ParentDir => return None,
-        }
-    }
-
-    if clean.as_os_str().is_empty() {
-        return None;
-    }
-
-    Some(clean)
-}
-
 pub(crate) fn apply_pending_import(db_path: &std::path::Path) {
     use std::fs;
 
@@ -177,6 +141,13 @@ pub fn cleanup_data_at_path(path: String) -> Result<(), String> {
         fs::remove_dir_all(&icons_dir).map_err(|e| format!("删除图标目录失败: {e}"))?;
     }
 
+    for dir in ["staged", crate::blobs::DIR_NAME] {
+        let dir = p.join(dir);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| format!("删除 {dir:?} 失败: {e}"))?;
+        }
+    }
+
     Ok(())
 }
 
@@ -208,17 +179,31 @@ pub fn migrate_data_to_path(new_path: String) -> Result<config::MigrationResult,
     Ok(result)
 }
 
+fn progress_emitter(app: &tauri::AppHandle) -> impl FnMut(TransferProgress) + '_ {
+    let mut last_emit: Option<Instant> = None;
+    move |progress| {
+        // 节流：每 100ms 最多发送一次，完成时总是发送
+        let due = last_emit.is_none_or(|t| t.elapsed() >= Duration::from_millis(100));
+        if due || progress.done >= progress.total {
+            let _ = app.emit(PROGRESS_EVENT, &progress);
+            last_emit = Some(Instant::now());
+        }
+    }
+}
+
+/// 导出数据：`filter` 限定导出范围（为空时导出全部），设置 `password` 时加密备份
 #[tauri::command]
 pub async fn export_data(
     app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Arc<AppState>>,
+    filter: Option<ExportFilter>,
+    password: Option<String>,
 ) -> Result<String, String> {
-    use std::fs::{self, File};
-    use std::io::Write;
+    use std::fs;
     use tauri_plugin_dialog::DialogExt;
-    use zip::ZipWriter;
-    use zip::write::SimpleFileOptions;
 
+    let filter = filter.unwrap_or_default();
+    let password = password.filter(|p| !p.is_empty());
     let config = AppConfig::load();
     let data_dir = config.get_data_dir();
 
@@ -237,180 +222,377 @@ pub async fn export_data(
     }
 
     let timestamp = chrono_timestamp();
-    let default_name = format!("ElegantClipboard_backup_{timestamp}.zip");
+    let (extension, filter_name) = if password.is_some() {
+        ("ecbackup", "加密备份")
+    } else {
+        ("zip", "ZIP 压缩文件")
+    };
+    let default_name = format!("ElegantClipboard_backup_{timestamp}.{extension}");
     let dest = app
         .dialog()
         .file()
         .set_title("导出数据")
         .set_file_name(&default_name)
-        .add_filter("ZIP 压缩文件", &["zip"])
+        .add_filter(filter_name, &[extension])
         .blocking_save_file();
 
     let dest_path = if let Some(p) = dest {
-        p.to_string()
+        PathBuf::from(p.to_string())
     } else {
         let _ = fs::remove_file(&export_db);
         return Err("用户取消了导出".to_string());
     };
 
-    let file = File::create(&dest_path).map_err(|e| format!("创建文件失败: {e}"))?;
-    let mut zip = ZipWriter::new(file);
-    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    tokio::task::spawn_blocking(move || {
+        let result = write_backup(
+            &app,
+            &data_dir,
+            &export_db,
+            &dest_path,
+            &filter,
+            password.as_deref(),
+        );
+        let _ = fs::remove_file(&export_db);
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 筛选数据库副本并流式写入备份；先写 `.part` 临时文件，完成（或加密）后再落到目标路径。
+/// 加密时明文临时文件写在数据目录，不落到用户选择的目录
+fn write_backup(
+    app: &tauri::AppHandle,
+    data_dir: &Path,
+    export_db: &Path,
+    dest: &Path,
+    filter: &ExportFilter,
+    password: Option<&str>,
+) -> Result<String, String> {
+    use std::fs::{self, File};
+    use std::io::BufWriter;
+
+    backup::reset_cancel();
+    let items = backup::prune_copy(export_db, filter)?;
+    let media = backup::collect_media(data_dir, export_db, filter)?;
 
-    zip.start_file("clipboard.db", options)
-        .map_err(|e| e.to_string())?;
-    zip.write_all(&fs::read(&export_db).map_err(|e| format!("读取数据库副本失败: {e}"))?)
-        .map_err(|e| e.to_string())?;
-    let _ = fs::remove_file(&export_db);
+    let part = match password {
+        Some(_) => data_dir.join("export.zip.part"),
+        None => PathBuf::from(format!("{}.part", dest.display())),
+    };
+    let mut on_progress = progress_emitter(app);
+    let manifest = Manifest::new(filter.clone(), items);
+    let result = File::create(&part)
+        .map_err(|e| format!("创建文件失败: {e}"))
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            backup::write_archive(&mut writer, export_db, &media, manifest, &mut on_progress)?;
+            writer
+                .into_inner()
+                .map_err(|e| e.to_string())?
+                .sync_all()
+                .map_err(|e| e.to_string())
+        })
+        .and_then(|()| match password {
+            Some(password) => {
+                let total = fs::metadata(&part).map_or(0, |m| m.len());
+                crypt::encrypt_file(&part, dest, password, |done| {
+                    on_progress(TransferProgress {
+                        phase: "encrypt",
+                        done,
+                        total,
+                    })
+                })?;
+                fs::remove_file(&part).map_err(|e| e.to_string())
+            }
+            None => fs::rename(&part, dest).map_err(|e| format!("保存文件失败: {e}")),
+        });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&part);
+        if password.is_some() {
+            let _ = fs::remove_file(dest);
+        }
+        return Err(e);
+    }
 
-    add_dir_to_zip(&mut zip, &data_dir.join("images"), "images", options)?;
+    let size = fs::metadata(dest).map_or(0, |m| m.len());
+    Ok(format!("导出成功 ({}，{items} 条记录)", format_size(size)))
+}
 
-    add_dir_to_zip(&mut zip, &data_dir.join("icons"), "icons", options)?;
+/// 开放格式导出：按列表视图的 `options` 筛选（`group_ids` 指定时逐个分组导出），
+/// 导出为 JSON Lines、按分组的 Markdown 笔记或单文件 HTML 归档
+#[tauri::command]
+pub async fn export_items(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Arc<AppState>>,
+    format: OpenFormat,
+    options: Option<QueryOptions>,
+    group_ids: Option<Vec<Option<i64>>>,
+) -> Result<String, String> {
+    use tauri_plugin_dialog::DialogExt;
 
-    add_dir_to_zip(&mut zip, &data_dir.join("staged"), "staged", options)?;
+    let options = options.unwrap_or_default();
+    let items = open_export::query_items(&state.db, &options, group_ids.as_deref())?;
+    if items.is_empty() {
+        return Err("没有符合条件的条目".to_string());
+    }
 
-    zip.finish().map_err(|e| e.to_string())?;
+    let timestamp = chrono_timestamp();
+    let dialog = app.dialog().file().set_title("导出数据");
+    let dest = match format.extension() {
+        Some(extension) => {
+            let filter_name = if format == OpenFormat::Jsonl {
+                "JSON Lines"
+            } else {
+                "HTML 文件"
+            };
+            dialog
+                .set_file_name(format!("ElegantClipboard_{timestamp}.{extension}"))
+                .add_filter(filter_name, &[extension])
+                .blocking_save_file()
+                .map(|p| PathBuf::from(p.to_string()))
+        }
+        // Markdown 导出为目录：在选择的文件夹下新建
+        None => dialog.blocking_pick_folder().map(|p| {
+            PathBuf::from(p.to_string()).join(format!("ElegantClipboard_notes_{timestamp}"))
+        }),
+    }
+    .ok_or("用户取消了导出")?;
+
+    let db = state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        backup::reset_cancel();
+        let mut on_progress = progress_emitter(&app);
+        let summary = open_export::export(&db, format, &items, &dest, |done, total| {
+            on_progress(TransferProgress {
+                phase: "items",
+                done: done as u64,
+                total: total as u64,
+            })
+        })?;
+        Ok(format!(
+            "导出成功（{} 条记录，{} 个媒体文件）",
+            summary.items, summary.media
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    let size = fs::metadata(&dest_path).map_or(0, |m| m.len());
-    Ok(format!("导出成功 ({})", format_size(size)))
+/// 选择的备份文件
+#[derive(Debug, Serialize)]
+pub struct BackupFileInfo {
+    pub path: String,
+    pub encrypted: bool,
 }
 
+/// 选择备份文件并检测是否加密（加密时前端先询问密码再调用 `import_data`）
 #[tauri::command]
-pub async fn import_data(app: tauri::AppHandle) -> Result<String, String> {
-    use std::fs::{self, File};
-    use std::io::Read;
+pub async fn pick_backup_file(app: tauri::AppHandle) -> Result<Option<BackupFileInfo>, String> {
     use tauri_plugin_dialog::DialogExt;
 
-    let config = AppConfig::load();
-    let data_dir = config.get_data_dir();
-
-    let src = app
+    let Some(src) = app
         .dialog()
         .file()
         .set_title("导入数据")
-        .add_filter("ZIP 压缩文件", &["zip"])
-        .blocking_pick_file();
+        .add_filter("备份文件", &["zip", "ecbackup"])
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+    let path = src.to_string();
+    let encrypted =
+        crypt::is_encrypted(Path::new(&path)).map_err(|e| format!("打开文件失败: {e}"))?;
+    Ok(Some(BackupFileInfo { path, encrypted }))
+}
 
-    let src_path = match src {
-        Some(p) => p.to_string(),
-        None => return Err("用户取消了导入".to_string()),
+/// 导入备份：未指定 `path` 时弹出文件选择框；加密备份需提供 `password`
+#[tauri::command]
+pub async fn import_data(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    password: Option<String>,
+) -> Result<String, String> {
+    let src_path = match path {
+        Some(path) => path,
+        None => match pick_backup_file(app.clone()).await? {
+            Some(info) => info.path,
+            None => return Err("用户取消了导入".to_string()),
+        },
     };
+    let data_dir = AppConfig::load().get_data_dir();
+    tokio::task::spawn_blocking(move || {
+        read_backup(&app, &data_dir, Path::new(&src_path), password.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    let file = File::open(&src_path).map_err(|e| format!("打开文件失败: {e}"))?;
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("无效的 ZIP 文件: {e}"))?;
+fn read_backup(
+    app: &tauri::AppHandle,
+    data_dir: &Path,
+    src: &Path,
+    password: Option<&str>,
+) -> Result<String, String> {
+    use std::fs::File;
+    use std::io::BufReader;
 
-    let has_db = (0..archive.len()).any(|i| {
-        archive
-            .by_index(i)
-            .is_ok_and(|f| f.name() == "clipboard.db")
-    });
-    if !has_db {
-        return Err("ZIP 文件中未找到 clipboard.db，不是有效的备份文件".to_string());
-    }
+    let mut on_progress = progress_emitter(app);
+    let summary = with_backup_zip(app, data_dir, src, password, |zip_path| {
+        let file = File::open(zip_path).map_err(|e| format!("打开文件失败: {e}"))?;
+        backup::extract_archive(BufReader::new(file), data_dir, &mut on_progress)
+    })?;
 
-    fs::create_dir_all(&data_dir).map_err(|e| e.to_string())?;
+    let verified = if summary.verified {
+        "，已通过校验"
+    } else {
+        ""
+    };
+    Ok(format!(
+        "导入成功，共恢复 {} 个文件{verified}，应用即将重启",
+        summary.files
+    ))
+}
 
-    let mut files_extracted = 0u32;
+/// 以 ZIP 形式读取备份：加密备份先解密到数据目录下的临时文件，`read` 完成后立即删除
+fn with_backup_zip<T>(
+    app: &tauri::AppHandle,
+    data_dir: &Path,
+    src: &Path,
+    password: Option<&str>,
+    read: impl FnOnce(&Path) -> Result<T, String>,
+) -> Result<T, String> {
+    use std::fs;
 
-    for i in 0..archive.len() {
-        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
-        let name = entry.name().to_string();
+    backup::reset_cancel();
+    fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    if !crypt::is_encrypted(src).map_err(|e| format!("打开文件失败: {e}"))? {
+        return read(src);
+    }
+    let password = password
+        .filter(|p| !p.is_empty())
+        .ok_or("该备份已加密，请输入密码")?;
+    let decrypted = data_dir.join("import.zip.part");
+    let total = fs::metadata(src).map_or(0, |m| m.len());
+    let mut on_decrypt = progress_emitter(app);
+    let result = crypt::decrypt_file(src, &decrypted, password, |done| {
+        on_decrypt(TransferProgress {
+            phase: "decrypt",
+            done,
+            total,
+        })
+    })
+    .and_then(|()| read(&decrypted));
+    let _ = fs::remove_file(&decrypted);
+    result
+}
 
-        let Some(rel_path) = sanitize_zip_relative_path(&name) else {
-            tracing::warn!("Skipping unsafe zip entry path: {name}");
-            continue;
-        };
+/// 合并导入备份：解压校验到临时目录后，把当前没有的条目追加到现有历史，无需重启。
+/// `dry_run` 时只返回统计与冲突，不写入任何数据
+#[tauri::command]
+pub async fn merge_import_data(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Arc<AppState>>,
+    path: String,
+    password: Option<String>,
+    dry_run: bool,
+) -> Result<MergeReport, String> {
+    use std::fs::{self, File};
+    use std::io::BufReader;
+
+    let db = state.db.clone();
+    let data_dir = AppConfig::load().get_data_dir();
+    let report = tokio::task::spawn_blocking(move || {
+        let unpacked = data_dir.join(MERGE_STAGING_DIR);
+        let mut on_progress = progress_emitter(&app);
+        let result = with_backup_zip(
+            &app,
+            &data_dir,
+            Path::new(&path),
+            password.as_deref(),
+            |zip_path| {
+                let file = File::open(zip_path).map_err(|e| format!("打开文件失败: {e}"))?;
+                backup::unpack_archive(BufReader::new(file), &unpacked, &mut on_progress)
+            },
+        )
+        .and_then(|_| {
+            let store = if dry_run {
+                None
+            } else {
+                Some(crate::blobs::global().ok_or("存储未初始化")?)
+            };
+            merge::merge(&db, store, &unpacked)
+        });
+        let _ = fs::remove_dir_all(&unpacked);
+        if !dry_run && result.as_ref().is_ok_and(|r| r.imported > 0) {
+            let _ = app.emit("history-imported", ());
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    Ok(report)
+}
 
-        // 跳过临时数据库文件，仅导入 clipboard.db 和资产目录
-        if rel_path.ends_with("clipboard.db-wal") || rel_path.ends_with("clipboard.db-shm") {
-            continue;
+/// 从其他剪贴板工具导入：未指定 `path` 时按来源弹出文件选择框（Windows 剪贴板历史无需文件）
+#[tauri::command]
+pub async fn import_from_app(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Arc<AppState>>,
+    source: ImportSource,
+    path: Option<String>,
+) -> Result<ImportReport, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let path = match path {
+        Some(path) => Some(PathBuf::from(path)),
+        None if source.needs_path() => {
+            let (name, extensions) = match source {
+                ImportSource::Ditto => ("Ditto 数据库", &["db"][..]),
+                ImportSource::CopyQ => ("CopyQ 标签页", &["dat"][..]),
+                ImportSource::Maccy => ("Maccy 数据库", &["sqlite"][..]),
+                _ => ("Clipy 片段", &["xml"][..]),
+            };
+            let picked = app
+                .dialog()
+                .file()
+                .set_title("导入其他剪贴板工具的数据")
+                .add_filter(name, extensions)
+                .blocking_pick_file()
+                .ok_or("用户取消了导入")?;
+            Some(PathBuf::from(picked.to_string()))
         }
-        let out_path = if rel_path == std::path::Path::new("clipboard.db") {
-            data_dir.join("clipboard.db.import")
-        } else {
-            data_dir.join(&rel_path)
-        };
-
-        if entry.is_dir() {
-            fs::create_dir_all(&out_path).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(parent) = out_path.parent() {
-                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-            }
-            let mut buf = Vec::new();
-            entry.read_to_end(&mut buf).map_err(|e| e.to_string())?;
-            fs::write(&out_path, &buf).map_err(|e| format!("写入 {name} 失败: {e}"))?;
-            files_extracted += 1;
+        None => None,
+    };
+    let db = state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        backup::reset_cancel();
+        let store = crate::blobs::global().ok_or("存储未初始化")?;
+        let clips = source.read(path.as_deref())?;
+        let mut on_progress = progress_emitter(&app);
+        let report = importers::import(&db, store, source, clips, |done, total| {
+            on_progress(TransferProgress {
+                phase: "external",
+                done: done as u64,
+                total: total as u64,
+            })
+        })?;
+        if report.imported > 0 {
+            let _ = app.emit("history-imported", ());
         }
-    }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    Ok(format!(
-        "导入成功，共恢复 {files_extracted} 个文件，应用即将重启"
-    ))
+/// 取消正在进行的导出 / 导入
+#[tauri::command]
+pub fn cancel_data_transfer() {
+    backup::cancel();
 }
 
 #[tauri::command]
 pub fn restart_app(app: tauri::AppHandle) {
     crate::admin_launch::perform_restart(&app);
 }
-
-#[cfg(test)]
-mod tests {
-    use super::sanitize_zip_relative_path;
-    use std::path::PathBuf;
-
-    #[test]
-    fn normal_relative_path() {
-        assert_eq!(
-            sanitize_zip_relative_path("images/screenshot.png"),
-            Some(PathBuf::from("images/screenshot.png"))
-        );
-    }
-
-    #[test]
-    fn simple_filename() {
-        assert_eq!(
-            sanitize_zip_relative_path("clipboard.db"),
-            Some(PathBuf::from("clipboard.db"))
-        );
-    }
-
-    #[test]
-    fn rejects_parent_dir_traversal() {
-        assert_eq!(sanitize_zip_relative_path("../etc/passwd"), None);
-        assert_eq!(sanitize_zip_relative_path("images/../../secret"), None);
-    }
-
-    #[test]
-    fn rejects_absolute_path() {
-        assert_eq!(sanitize_zip_relative_path("/etc/passwd"), None);
-        assert_eq!(sanitize_zip_relative_path("C:\\Windows\\system32"), None);
-    }
-
-    #[test]
-    fn rejects_empty_path() {
-        assert_eq!(sanitize_zip_relative_path(""), None);
-    }
-
-    #[test]
-    fn rejects_dot_only() {
-        assert_eq!(sanitize_zip_relative_path("."), None);
-        assert_eq!(sanitize_zip_relative_path("./"), None);
-    }
-
-    #[test]
-    fn strips_current_dir_prefix() {
-        assert_eq!(
-            sanitize_zip_relative_path("./images/test.png"),
-            Some(PathBuf::from("images/test.png"))
-        );
-    }
-
-    #[test]
-    fn nested_path_ok() {
-        assert_eq!(
-            sanitize_zip_relative_path("a/b/c/d.txt"),
-            Some(PathBuf::from("a/b/c/d.txt"))
-        );
-    }
-}
@@ -1,7 +1,7 @@
 use crate::clipboard::file_clipboard::{
     item_files_all_exist, parse_file_paths, resolve_item_paths,
 };
-use crate::database::ClipboardRepository;
+use crate::database::{ClipboardRepository, StorageUsage};
 use crate::file_preview_limits::{
     DEFAULT_MAX_IMAGE_SIZE_KB, is_too_large_for_preview, is_unc_path, preview_limit_bytes,
 };
@@ -319,9 +319,9 @@ pub async fn save_file_as(app: tauri::AppHandle, source_path: String) -> Result<
     }
 }
 
-/// 获取数据目录大小明细（数据库+图片）
+/// 获取数据目录大小明细（数据库、blob 存储、旧版目录）与按类型/分组的条目占用
 #[tauri::command]
-pub async fn get_data_size() -> Result<DataSizeInfo, String> {
+pub async fn get_data_size(state: State<'_, Arc<AppState>>) -> Result<DataSizeInfo, String> {
     let config = crate::config::AppConfig::load();
     let data_dir = config.get_data_dir();
 
@@ -334,6 +334,12 @@ pub async fn get_data_size() -> Result<DataSizeInfo, String> {
     let (images_size, images_count) = dir_size_and_count(&images_dir);
     let staged_dir = data_dir.join("staged");
     let (staged_size, staged_count) = dir_size_and_count(&staged_dir);
+    let blobs_dir = data_dir.join(crate::blobs::DIR_NAME);
+    let (blobs_size, blobs_count) = dir_size_and_count(&blobs_dir);
+
+    let usage = ClipboardRepository::new(&state.db)
+        .storage_usage()
+        .map_err(|e| e.to_string())?;
 
     Ok(DataSizeInfo {
         db_size,
@@ -341,7 +347,10 @@ pub async fn get_data_size() -> Result<DataSizeInfo, String> {
         images_count,
         staged_size,
         staged_count,
-        total_size: db_size + images_size + staged_size,
+        blobs_size,
+        blobs_count,
+        total_size: db_size + images_size + staged_size + blobs_size,
+        usage,
     })
 }
 
@@ -376,7 +385,11 @@ pub struct DataSizeInfo {
     pub images_count: u64,
     pub staged_size: u64,
     pub staged_count: u64,
+    pub blobs_size: u64,
+    pub blobs_count: u64,
     pub total_size: u64,
+    /// 配额统计口径的条目占用
+    pub usage: StorageUsage,
 }
 
 /// 获取文件详情
@@ -1,4 +1,4 @@
-use crate::database::{ClipboardRepository, Group, GroupRepository};
+use crate::database::{Group, GroupRepository, GroupSettings};
 use std::sync::Arc;
 use tauri::State;
 use tracing::debug;
@@ -12,15 +12,36 @@ pub async fn get_groups(state: State<'_, Arc<AppState>>) -> Result<Vec<Group>, S
     repo.list_with_count().map_err(|e| e.to_string())
 }
 
-/// 创建自定义分组，返回完整分组对象
+const DEDUP_STRATEGIES: &[&str] = &["ignore", "move_to_top", "always_new"];
+
+/// 校验父分组：须存在，且不能是分组自身或其子孙（避免循环）
+fn validate_parent(repo: &GroupRepository, id: Option<i64>, parent_id: i64) -> Result<(), String> {
+    if !repo.exists(parent_id).map_err(|e| e.to_string())? {
+        return Err(format!("分组不存在: {parent_id}"));
+    }
+    if let Some(id) = id
+        && repo
+            .is_self_or_descendant(id, parent_id)
+            .map_err(|e| e.to_string())?
+    {
+        return Err("不能将分组移动到自身或其子分组下".to_string());
+    }
+    Ok(())
+}
+
+/// 创建自定义分组（可指定父分组），返回完整分组对象
 #[tauri::command]
 pub async fn create_group(
     state: State<'_, Arc<AppState>>,
     name: String,
     color: Option<String>,
+    parent_id: Option<i64>,
 ) -> Result<Group, String> {
     let repo = GroupRepository::new(&state.db);
-    repo.create(&name, color.as_deref())
+    if let Some(parent_id) = parent_id {
+        validate_parent(&repo, None, parent_id)?;
+    }
+    repo.create_in(&name, color.as_deref(), parent_id)
         .map_err(|e| e.to_string())
 }
 
@@ -47,25 +68,67 @@ pub async fn update_group_color(
         .map_err(|e| e.to_string())
 }
 
-/// 删除分组（ON DELETE CASCADE 自动删除该分组的所有 clipboard_items）
+/// 删除分组，条目移至 `move_items_to`（None = 默认分组），子分组上移一级
 #[tauri::command]
-pub async fn delete_group(state: State<'_, Arc<AppState>>, id: i64) -> Result<(), String> {
-    let clipboard_repo = ClipboardRepository::new(&state.db);
-    let image_paths = clipboard_repo
-        .get_image_paths_by_group(id)
-        .map_err(|e| e.to_string())?;
-    let file_payloads = clipboard_repo
-        .get_file_payloads_by_group(id)
-        .map_err(|e| e.to_string())?;
-
+pub async fn delete_group(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    move_items_to: Option<i64>,
+) -> Result<(), String> {
     let repo = GroupRepository::new(&state.db);
-    repo.delete(id).map_err(|e| e.to_string())?;
+    if let Some(target) = move_items_to {
+        if target == id {
+            return Err("目标分组不能是被删除的分组".to_string());
+        }
+        if !repo.exists(target).map_err(|e| e.to_string())? {
+            return Err(format!("分组不存在: {target}"));
+        }
+    }
+    repo.delete(id, move_items_to).map_err(|e| e.to_string())?;
+    // 指向该分组的捕获规则已改指目标分组或级联删除
+    crate::clipboard::rules::invalidate_rules();
 
-    crate::clipboard::cleanup_deleted_assets(&image_paths, &file_payloads);
-    debug!("Deleted group {} ({} image files)", id, image_paths.len());
+    debug!("Deleted group {}, items moved to {:?}", id, move_items_to);
     Ok(())
 }
 
+/// 移动分组到父分组下（None = 顶层）
+#[tauri::command]
+pub async fn set_group_parent(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    parent_id: Option<i64>,
+) -> Result<(), String> {
+    let repo = GroupRepository::new(&state.db);
+    if let Some(parent_id) = parent_id {
+        validate_parent(&repo, Some(id), parent_id)?;
+    }
+    repo.set_parent(id, parent_id).map_err(|e| e.to_string())
+}
+
+/// 更新分组级设置（保留天数、最大条数、去重策略、同步开关、容量配额）
+#[tauri::command]
+pub async fn update_group_settings(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    settings: GroupSettings,
+) -> Result<(), String> {
+    if settings.retention_days.is_some_and(|d| d < 0)
+        || settings.max_count.is_some_and(|c| c < 0)
+        || settings.quota_mb.is_some_and(|mb| mb < 0)
+    {
+        return Err("保留天数、最大条数与容量配额不能为负数".to_string());
+    }
+    if let Some(strategy) = settings.dedup_strategy.as_deref()
+        && !DEDUP_STRATEGIES.contains(&strategy)
+    {
+        return Err(format!("未知的去重策略: {strategy}"));
+    }
+    GroupRepository::new(&state.db)
+        .update_settings(id, &settings)
+        .map_err(|e| e.to_string())
+}
+
 /// 将条目移动到指定分组（None = 移回默认分组）
 #[tauri::command]
 pub async fn move_item_to_group(
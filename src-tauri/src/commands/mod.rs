@@ -1,10 +1,20 @@
+pub mod ai;
+pub mod api;
 pub mod clipboard;
+pub mod collect;
 pub mod data_transfer;
 pub mod file_ops;
 pub mod groups;
+pub mod ocr;
+pub mod paste_queue;
+pub mod plugins;
 pub mod preview;
+pub mod revisions;
+pub mod rules;
 pub mod settings;
+pub mod storage;
 pub mod sync;
+pub mod tags;
 pub mod translate;
 pub mod window;
 pub mod window_utils;
@@ -231,3 +241,46 @@ pub(crate) fn open_path_in_explorer(path: &std::path::Path) -> Result<(), String
 
     Ok(())
 }
+
+/// 将生成的文本 / HTML 走捕获处理流程保存为新条目（去重、规则、钩子均生效）。
+/// 返回条目 ID：去重命中时为已有条目，被丢弃时为 None。
+pub(crate) fn save_generated_item(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    text: Option<String>,
+    html: Option<String>,
+    group_id: Option<i64>,
+) -> Result<Option<i64>, String> {
+    Ok(save_generated(state, app, text, html, group_id)?.map(|c| c.id))
+}
+
+/// 同 [`save_generated_item`]，并区分是否新建了条目
+pub(crate) fn save_generated(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    text: Option<String>,
+    html: Option<String>,
+    group_id: Option<i64>,
+) -> Result<Option<crate::clipboard::Captured>, String> {
+    use crate::clipboard::ClipboardContent;
+
+    let handler = state
+        .monitor
+        .handler()
+        .ok_or("clipboard handler not initialized")?;
+    let text = text.unwrap_or_default();
+    let content = match html.filter(|h| !h.is_empty()) {
+        Some(html) => ClipboardContent::Html {
+            html,
+            text: Some(text).filter(|t| !t.is_empty()),
+            rtf: None,
+        },
+        None => ClipboardContent::Text(text),
+    };
+    let captured = handler.capture_generated(content, group_id)?;
+    if let Some(captured) = captured {
+        let _ = app.emit("clipboard-updated", captured.id);
+        crate::api::publish_item_added(captured.id);
+    }
+    Ok(captured)
+}
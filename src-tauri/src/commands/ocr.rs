@@ -0,0 +1,21 @@
+use crate::ocr::BackfillSummary;
+
+/// OCR 模型是否已安装（前端据此决定是否显示相关入口）
+#[tauri::command]
+pub fn is_ocr_available() -> bool {
+    crate::ocr::is_available()
+}
+
+/// 为历史图片回填 OCR 文本，进度通过 `ocr-backfill-progress` 事件上报
+#[tauri::command]
+pub async fn ocr_backfill_images(app: tauri::AppHandle) -> Result<BackfillSummary, String> {
+    tokio::task::spawn_blocking(move || crate::ocr::backfill(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 取消正在进行的 OCR 回填
+#[tauri::command]
+pub fn cancel_ocr_backfill() {
+    crate::ocr::cancel_backfill();
+}
@@ -0,0 +1,350 @@
+//! 粘贴队列：依次粘贴多个条目（如逐项填写表单）
+//!
+//! 队列保存在后端内存中，窗口隐藏后仍保留；每次按下专用快捷键粘贴下一项并出队。
+//! 状态变化通过 `paste-queue-changed` 事件推送给前端。
+
+use crate::database::{ClipboardRepository, SettingsRepository};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tauri::{Emitter, Manager, State};
+use tracing::{debug, warn};
+
+use super::AppState;
+
+const PASTE_QUEUE_SHORTCUT_SETTING: &str = "paste_queue_shortcut";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueMode {
+    /// 先进先出：按加入顺序粘贴
+    #[default]
+    Fifo,
+    /// 后进先出：最后加入的先粘贴
+    Lifo,
+}
+
+/// 队列本体（与 Tauri 无关，便于测试）
+#[derive(Debug, Default)]
+struct PasteQueue {
+    mode: QueueMode,
+    /// 按加入顺序存放条目 ID
+    ids: VecDeque<i64>,
+}
+
+impl PasteQueue {
+    fn push(&mut self, ids: &[i64]) {
+        self.ids.extend(ids);
+    }
+
+    /// 取出下一项
+    fn pop_next(&mut self) -> Option<i64> {
+        match self.mode {
+            QueueMode::Fifo => self.ids.pop_front(),
+            QueueMode::Lifo => self.ids.pop_back(),
+        }
+    }
+
+    /// 粘贴失败时放回原位置
+    fn restore(&mut self, id: i64) {
+        match self.mode {
+            QueueMode::Fifo => self.ids.push_front(id),
+            QueueMode::Lifo => self.ids.push_back(id),
+        }
+    }
+
+    /// 移除指定条目的所有出现
+    fn remove(&mut self, id: i64) -> bool {
+        let before = self.ids.len();
+        self.ids.retain(|&x| x != id);
+        self.ids.len() != before
+    }
+
+    /// 按粘贴顺序列出 ID
+    fn upcoming(&self) -> Vec<i64> {
+        match self.mode {
+            QueueMode::Fifo => self.ids.iter().copied().collect(),
+            QueueMode::Lifo => self.ids.iter().rev().copied().collect(),
+        }
+    }
+}
+
+static QUEUE: Mutex<PasteQueue> = Mutex::new(PasteQueue {
+    mode: QueueMode::Fifo,
+    ids: VecDeque::new(),
+});
+
+/// 串行化队列粘贴，避免快捷键连按时并发操作剪贴板
+static PASTE_NEXT_LOCK: Mutex<()> = Mutex::new(());
+
+/// 队列中的条目摘要
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueEntry {
+    pub id: i64,
+    pub content_type: String,
+    pub preview: Option<String>,
+}
+
+/// 队列状态快照（`items` 按粘贴顺序排列）
+#[derive(Debug, Clone, Serialize)]
+pub struct PasteQueueState {
+    pub mode: QueueMode,
+    pub items: Vec<QueueEntry>,
+}
+
+fn snapshot(state: &AppState) -> PasteQueueState {
+    let (mode, ids) = {
+        let queue = QUEUE.lock();
+        (queue.mode, queue.upcoming())
+    };
+    let repo = ClipboardRepository::new(&state.db);
+    let items = ids
+        .into_iter()
+        .filter_map(|id| repo.get_by_id(id).ok().flatten())
+        .map(|item| QueueEntry {
+            id: item.id,
+            content_type: item.content_type,
+            preview: item.preview,
+        })
+        .collect();
+    PasteQueueState { mode, items }
+}
+
+fn emit_changed(app: &tauri::AppHandle, state: &AppState) -> PasteQueueState {
+    let snapshot = snapshot(state);
+    let _ = app.emit("paste-queue-changed", &snapshot);
+    snapshot
+}
+
+/// 粘贴队列中的下一项并出队；已被删除的条目自动跳过。队列为空时返回 `Ok(None)`
+pub(crate) fn paste_next(
+    state: &Arc<AppState>,
+    app: &tauri::AppHandle,
+) -> Result<Option<i64>, String> {
+    let _guard = PASTE_NEXT_LOCK.lock();
+    let repo = ClipboardRepository::new(&state.db);
+    let result = loop {
+        let Some(id) = QUEUE.lock().pop_next() else {
+            break Ok(None);
+        };
+        let item = match repo.get_by_id(id) {
+            Ok(Some(item)) => item,
+            Ok(None) => {
+                debug!("Paste queue: item {} no longer exists, skipping", id);
+                continue;
+            }
+            Err(e) => {
+                QUEUE.lock().restore(id);
+                break Err(e.to_string());
+            }
+        };
+        match super::clipboard::paste_item_to_active_window(state, app, &item, true) {
+            Ok(()) => {
+                debug!("Paste queue: pasted item {}", id);
+                break Ok(Some(id));
+            }
+            Err(e) => {
+                QUEUE.lock().restore(id);
+                break Err(e);
+            }
+        }
+    };
+    emit_changed(app, state);
+    result
+}
+
+/// 获取当前队列状态
+#[tauri::command]
+pub async fn get_paste_queue(state: State<'_, Arc<AppState>>) -> Result<PasteQueueState, String> {
+    Ok(snapshot(&state))
+}
+
+/// 将条目追加到队列（按传入顺序）
+#[tauri::command]
+pub async fn paste_queue_add(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    ids: Vec<i64>,
+) -> Result<PasteQueueState, String> {
+    QUEUE.lock().push(&ids);
+    Ok(emit_changed(&app, &state))
+}
+
+/// 从队列中移除条目
+#[tauri::command]
+pub async fn paste_queue_remove(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    id: i64,
+) -> Result<PasteQueueState, String> {
+    QUEUE.lock().remove(id);
+    Ok(emit_changed(&app, &state))
+}
+
+/// 清空队列
+#[tauri::command]
+pub async fn paste_queue_clear(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<PasteQueueState, String> {
+    QUEUE.lock().ids.clear();
+    Ok(emit_changed(&app, &state))
+}
+
+/// 切换队列模式（FIFO / LIFO）
+#[tauri::command]
+pub async fn paste_queue_set_mode(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    mode: QueueMode,
+) -> Result<PasteQueueState, String> {
+    QUEUE.lock().mode = mode;
+    Ok(emit_changed(&app, &state))
+}
+
+/// 粘贴队列下一项（前端按钮调用，快捷键走同一逻辑）
+#[tauri::command]
+pub async fn paste_queue_next(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+) -> Result<Option<i64>, String> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || paste_next(&state, &app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 注册粘贴队列快捷键
+pub fn register_paste_queue_shortcut(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<Arc<AppState>>() else {
+        return;
+    };
+    let shortcut_str = match SettingsRepository::new(&state.db)
+        .get(PASTE_QUEUE_SHORTCUT_SETTING)
+        .ok()
+        .flatten()
+    {
+        Some(s) if !s.is_empty() => s,
+        _ => return,
+    };
+
+    let registered = crate::hotkey::register(
+        &shortcut_str,
+        Arc::new(|app, key_state| {
+            // 松键后触发：修饰键已释放，模拟粘贴不会被组合成其他快捷键
+            if key_state != crate::hotkey::KeyState::Released {
+                return;
+            }
+            let Some(state) = app.try_state::<Arc<AppState>>() else {
+                return;
+            };
+            let state = state.inner().clone();
+            let app = app.clone();
+            std::thread::spawn(move || match paste_next(&state, &app) {
+                Ok(Some(_)) => {}
+                Ok(None) => debug!("Paste queue is empty"),
+                Err(e) => warn!("Paste queue paste failed: {}", e),
+            });
+        }),
+    );
+    if !registered {
+        warn!("Invalid paste queue shortcut format: {}", shortcut_str);
+    }
+}
+
+/// 注销粘贴队列快捷键
+pub fn unregister_paste_queue_shortcut(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<Arc<AppState>>() else {
+        return;
+    };
+    if let Some(shortcut_str) = SettingsRepository::new(&state.db)
+        .get(PASTE_QUEUE_SHORTCUT_SETTING)
+        .ok()
+        .flatten()
+        && !shortcut_str.is_empty()
+    {
+        crate::hotkey::unregister(&shortcut_str);
+    }
+}
+
+/// 更新粘贴队列快捷键（空字符串 = 关闭）
+#[tauri::command]
+pub async fn update_paste_queue_shortcut(
+    app: tauri::AppHandle,
+    new_shortcut: String,
+) -> Result<(), String> {
+    let new_shortcut = new_shortcut.trim().to_string();
+    if !new_shortcut.is_empty() && !crate::shortcut_has_modifier(&new_shortcut) {
+        return Err("SHORTCUT:MODIFIER_REQUIRED".to_string());
+    }
+
+    unregister_paste_queue_shortcut(&app);
+    let state = app.state::<Arc<AppState>>();
+    SettingsRepository::new(&state.db)
+        .set(PASTE_QUEUE_SHORTCUT_SETTING, &new_shortcut)
+        .map_err(|e| format!("SHORTCUT:SAVE_FAILED:{e}"))?;
+    if !new_shortcut.is_empty() && !crate::shortcuts_are_disabled() {
+        register_paste_queue_shortcut(&app);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(mode: QueueMode, ids: &[i64]) -> PasteQueue {
+        let mut q = PasteQueue {
+            mode,
+            ..Default::default()
+        };
+        q.push(ids);
+        q
+    }
+
+    #[test]
+    fn fifo_pastes_in_insertion_order() {
+        let mut q = queue(QueueMode::Fifo, &[1, 2, 3]);
+        assert_eq!(q.upcoming(), vec![1, 2, 3]);
+        assert_eq!(q.pop_next(), Some(1));
+        assert_eq!(q.pop_next(), Some(2));
+        assert_eq!(q.pop_next(), Some(3));
+        assert_eq!(q.pop_next(), None);
+    }
+
+    #[test]
+    fn lifo_pastes_latest_first() {
+        let mut q = queue(QueueMode::Lifo, &[1, 2, 3]);
+        assert_eq!(q.upcoming(), vec![3, 2, 1]);
+        assert_eq!(q.pop_next(), Some(3));
+        q.push(&[4]);
+        assert_eq!(q.pop_next(), Some(4));
+        assert_eq!(q.pop_next(), Some(2));
+    }
+
+    #[test]
+    fn restore_puts_item_back_at_the_head() {
+        for mode in [QueueMode::Fifo, QueueMode::Lifo] {
+            let mut q = queue(mode, &[1, 2, 3]);
+            let id = q.pop_next().unwrap();
+            q.restore(id);
+            assert_eq!(q.pop_next(), Some(id), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn switching_mode_keeps_items() {
+        let mut q = queue(QueueMode::Fifo, &[1, 2, 3]);
+        q.mode = QueueMode::Lifo;
+        assert_eq!(q.upcoming(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn remove_drops_every_occurrence() {
+        let mut q = queue(QueueMode::Fifo, &[1, 2, 1, 3]);
+        assert!(q.remove(1));
+        assert!(!q.remove(9));
+        assert_eq!(q.upcoming(), vec![2, 3]);
+    }
+}
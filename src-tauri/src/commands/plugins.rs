@@ -0,0 +1,177 @@
+//! 外部插件：列出条目可用操作、执行并保存结果（插件加载与协议见 `crate::plugins`）
+
+use crate::config::AppConfig;
+use crate::database::{ClipboardRepository, SettingsRepository};
+use crate::plugins::{self, ActionOutput, ActionResult, Plugin};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+use tracing::info;
+
+use super::AppState;
+
+fn plugins_dir() -> PathBuf {
+    AppConfig::load().get_data_dir().join("plugins")
+}
+
+fn load_plugins(state: &AppState) -> Vec<Plugin> {
+    let disabled = SettingsRepository::new(&state.db)
+        .get(plugins::DISABLED_PLUGINS_SETTING)
+        .ok()
+        .flatten();
+    plugins::discover(
+        &plugins_dir(),
+        &plugins::parse_disabled(disabled.as_deref()),
+    )
+}
+
+/// 条目可用的插件操作
+#[derive(Debug, Serialize)]
+pub struct ItemAction {
+    pub plugin_id: String,
+    pub plugin_name: String,
+    pub action_id: String,
+    pub title: String,
+    pub output: ActionOutput,
+}
+
+/// 插件执行结果
+#[derive(Debug, Serialize)]
+pub struct PluginRunResult {
+    pub output: ActionOutput,
+    /// 新条目 ID（output = new_item 且未被去重时）
+    pub item_id: Option<i64>,
+}
+
+/// 列出已安装插件（含禁用项）
+#[tauri::command]
+pub async fn list_plugins(state: State<'_, Arc<AppState>>) -> Result<Vec<Plugin>, String> {
+    Ok(load_plugins(&state))
+}
+
+/// 启用 / 禁用插件
+#[tauri::command]
+pub async fn set_plugin_enabled(
+    state: State<'_, Arc<AppState>>,
+    plugin_id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let settings = SettingsRepository::new(&state.db);
+    let raw = settings
+        .get(plugins::DISABLED_PLUGINS_SETTING)
+        .map_err(|e| e.to_string())?;
+    let mut disabled: Vec<String> = plugins::parse_disabled(raw.as_deref())
+        .into_iter()
+        .filter(|id| *id != plugin_id)
+        .collect();
+    if !enabled {
+        disabled.push(plugin_id);
+    }
+    disabled.sort();
+    let json = serde_json::to_string(&disabled).map_err(|e| e.to_string())?;
+    settings
+        .set(plugins::DISABLED_PLUGINS_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// 在文件管理器中打开插件目录（不存在时创建）
+#[tauri::command]
+pub async fn open_plugins_dir() -> Result<(), String> {
+    let dir = plugins_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建插件目录失败: {e}"))?;
+    super::open_path_in_explorer(&dir)
+}
+
+/// 列出适用于条目的插件操作
+#[tauri::command]
+pub async fn list_item_actions(
+    state: State<'_, Arc<AppState>>,
+    item_id: i64,
+) -> Result<Vec<ItemAction>, String> {
+    let item = ClipboardRepository::new(&state.db)
+        .get_by_id(item_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "条目未找到".to_string())?;
+    let plugins = load_plugins(&state);
+    Ok(plugins::actions_for(&plugins, &item)
+        .map(|(plugin, action)| ItemAction {
+            plugin_id: plugin.manifest.id.clone(),
+            plugin_name: plugin.manifest.name.clone(),
+            action_id: action.id.clone(),
+            title: action.title.clone(),
+            output: action.output,
+        })
+        .collect())
+}
+
+/// 执行插件操作；`output` 为空时使用清单中声明的去向
+#[tauri::command]
+pub async fn run_plugin_action(
+    state: State<'_, Arc<AppState>>,
+    app: tauri::AppHandle,
+    item_id: i64,
+    plugin_id: String,
+    action_id: String,
+    output: Option<ActionOutput>,
+) -> Result<PluginRunResult, String> {
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let item = ClipboardRepository::new(&state.db)
+            .get_by_id(item_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "条目未找到".to_string())?;
+        let plugins = load_plugins(&state);
+        let plugin = plugins
+            .iter()
+            .find(|p| p.manifest.id == plugin_id && p.enabled)
+            .ok_or_else(|| format!("插件未安装或已禁用: {plugin_id}"))?;
+        let action = plugin
+            .action(&action_id)
+            .filter(|a| a.applies_to(&item))
+            .ok_or_else(|| format!("操作不适用于该条目: {action_id}"))?;
+        let output = output.unwrap_or(action.output);
+
+        let result = plugins::run_action(plugin, &action_id, &item)?;
+        // 空结果既不能保存为条目也不应清空剪贴板
+        if result.is_empty() {
+            return Err(plugins::EMPTY_RESULT.to_string());
+        }
+        info!(
+            "Plugin action {}/{} on item {} -> {:?}",
+            plugin_id, action_id, item_id, output
+        );
+        let item_id = match output {
+            ActionOutput::NewItem => {
+                super::save_generated_item(&state, &app, result.text, result.html, item.group_id)?
+            }
+            ActionOutput::Clipboard => {
+                write_to_clipboard(&state, result)?;
+                None
+            }
+        };
+        Ok(PluginRunResult { output, item_id })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 结果直接写入系统剪贴板（暂停监控，不产生新条目）
+fn write_to_clipboard(state: &Arc<AppState>, result: ActionResult) -> Result<(), String> {
+    use clipboard_rs::{Clipboard, ClipboardContent as RsClipboardContent};
+
+    super::with_paused_monitor(state, || {
+        let clipboard = clipboard_rs::ClipboardContext::new()
+            .map_err(|e| format!("Failed to access clipboard: {e}"))?;
+        let mut contents = Vec::new();
+        if let Some(html) = result.html.filter(|h| !h.is_empty()) {
+            contents.push(RsClipboardContent::Html(html));
+        }
+        if let Some(text) = result.text.filter(|t| !t.is_empty()) {
+            contents.push(RsClipboardContent::Text(text));
+        }
+        clipboard
+            .set(contents)
+            .map_err(|e| format!("Failed to set clipboard: {e}"))
+    })
+}
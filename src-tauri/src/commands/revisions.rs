@@ -0,0 +1,88 @@
+use crate::clipboard::text_diff::{self, DiffLine};
+use crate::database::{ClipboardRepository, ItemRevision, SettingsRepository};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+
+use super::AppState;
+
+const MAX_REVISIONS_SETTING: &str = "max_revisions_per_item";
+const DEFAULT_MAX_REVISIONS: usize = 20;
+
+/// 单个条目保留的历史版本数（0 = 不保留）
+pub(super) fn revision_limit(state: &AppState) -> usize {
+    SettingsRepository::new(&state.db)
+        .get_parsed(MAX_REVISIONS_SETTING)
+        .unwrap_or(DEFAULT_MAX_REVISIONS)
+}
+
+/// 两个版本的对比结果
+#[derive(Debug, Serialize)]
+pub struct RevisionDiff {
+    pub summary: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// 获取条目的历史版本（新→旧）
+#[tauri::command]
+pub async fn list_item_revisions(
+    state: State<'_, Arc<AppState>>,
+    item_id: i64,
+) -> Result<Vec<ItemRevision>, String> {
+    ClipboardRepository::new(&state.db)
+        .list_revisions(item_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 对比两个历史版本；`to_revision_id` 为 None 时与条目当前内容对比
+#[tauri::command]
+pub async fn diff_item_revisions(
+    state: State<'_, Arc<AppState>>,
+    from_revision_id: i64,
+    to_revision_id: Option<i64>,
+) -> Result<RevisionDiff, String> {
+    let db = state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        let repo = ClipboardRepository::new(&db);
+        let revision_text = |id: i64| {
+            repo.get_revision_content(id)
+                .map_err(|e| e.to_string())?
+                .map(|revision| (revision.item_id, revision.text_content))
+                .ok_or_else(|| format!("版本不存在: {id}"))
+        };
+        let (item_id, old) = revision_text(from_revision_id)?;
+        let new = match to_revision_id {
+            Some(id) => {
+                let (other_item, text) = revision_text(id)?;
+                if other_item != item_id {
+                    return Err("只能对比同一条目的版本".to_string());
+                }
+                text
+            }
+            None => repo
+                .get_by_id(item_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "条目未找到".to_string())?
+                .text_content
+                .unwrap_or_default(),
+        };
+        Ok(RevisionDiff {
+            summary: text_diff::diff_summary(&old, &new),
+            lines: text_diff::diff_lines(&old, &new),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 恢复历史版本（当前内容先存为新版本），返回条目 ID
+#[tauri::command]
+pub async fn restore_item_revision(
+    state: State<'_, Arc<AppState>>,
+    revision_id: i64,
+) -> Result<i64, String> {
+    ClipboardRepository::new(&state.db)
+        .restore_revision(revision_id, revision_limit(&state))
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("版本不存在: {revision_id}"))
+}
@@ -0,0 +1,192 @@
+use crate::clipboard::rules::{self, CompiledRule};
+use crate::database::{
+    CaptureRule, CaptureRuleInput, ClipboardRepository, Database, GroupRepository, RuleRepository,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tracing::{info, warn};
+
+use super::AppState;
+
+/// 试运行默认扫描的历史条目数
+const DEFAULT_TEST_LIMIT: i64 = 500;
+const MAX_TEST_LIMIT: i64 = 5000;
+
+/// TTL 到期检查间隔
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static EXPIRY_TIMER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 规则试运行命中的条目
+#[derive(Debug, Serialize)]
+pub struct RuleTestMatch {
+    pub id: i64,
+    pub content_type: String,
+    pub preview: Option<String>,
+    pub source_app_name: Option<String>,
+    pub created_at: String,
+    pub group_id: Option<i64>,
+}
+
+/// 规则试运行结果
+#[derive(Debug, Serialize)]
+pub struct RuleTestResult {
+    pub scanned: usize,
+    pub matches: Vec<RuleTestMatch>,
+}
+
+fn validate(state: &AppState, input: &CaptureRuleInput) -> Result<(), String> {
+    rules::validate_input(input)?;
+    if let Some(group_id) = input.target_group_id
+        && !GroupRepository::new(&state.db)
+            .exists(group_id)
+            .map_err(|e| e.to_string())?
+    {
+        return Err(format!("分组不存在: {group_id}"));
+    }
+    Ok(())
+}
+
+/// 获取全部捕获规则（按执行顺序）
+#[tauri::command]
+pub async fn list_capture_rules(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<CaptureRule>, String> {
+    RuleRepository::new(&state.db)
+        .list()
+        .map_err(|e| e.to_string())
+}
+
+/// 新建捕获规则（追加到末尾）
+#[tauri::command]
+pub async fn create_capture_rule(
+    state: State<'_, Arc<AppState>>,
+    rule: CaptureRuleInput,
+) -> Result<CaptureRule, String> {
+    validate(&state, &rule)?;
+    let created = RuleRepository::new(&state.db)
+        .create(&rule)
+        .map_err(|e| e.to_string())?;
+    rules::invalidate_rules();
+    Ok(created)
+}
+
+/// 更新捕获规则
+#[tauri::command]
+pub async fn update_capture_rule(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    rule: CaptureRuleInput,
+) -> Result<(), String> {
+    validate(&state, &rule)?;
+    RuleRepository::new(&state.db)
+        .update(id, &rule)
+        .map_err(|e| e.to_string())?;
+    rules::invalidate_rules();
+    Ok(())
+}
+
+/// 删除捕获规则
+#[tauri::command]
+pub async fn delete_capture_rule(state: State<'_, Arc<AppState>>, id: i64) -> Result<(), String> {
+    RuleRepository::new(&state.db)
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+    rules::invalidate_rules();
+    Ok(())
+}
+
+/// 按给定 ID 顺序重排捕获规则
+#[tauri::command]
+pub async fn reorder_capture_rules(
+    state: State<'_, Arc<AppState>>,
+    ids: Vec<i64>,
+) -> Result<(), String> {
+    RuleRepository::new(&state.db)
+        .reorder(&ids)
+        .map_err(|e| e.to_string())?;
+    rules::invalidate_rules();
+    Ok(())
+}
+
+/// 规则试运行：用最近的历史条目测试规则（不保存、不修改任何条目）
+#[tauri::command]
+pub async fn test_capture_rule(
+    state: State<'_, Arc<AppState>>,
+    rule: CaptureRuleInput,
+    limit: Option<i64>,
+) -> Result<RuleTestResult, String> {
+    rules::validate_input(&rule)?;
+    let compiled = CompiledRule::compile(CaptureRule {
+        id: 0,
+        name: rule.name,
+        enabled: true,
+        sort_order: 0,
+        source_app: rule.source_app,
+        content_regex: rule.content_regex,
+        content_types: rule.content_types,
+        time_start: rule.time_start,
+        time_end: rule.time_end,
+        target_group_id: rule.target_group_id,
+        pin: rule.pin,
+        ttl_minutes: rule.ttl_minutes,
+        created_at: String::new(),
+    })?;
+    let limit = limit.unwrap_or(DEFAULT_TEST_LIMIT).clamp(1, MAX_TEST_LIMIT);
+    let db = state.db.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let items = ClipboardRepository::new(&db)
+            .list_recent_with_text(limit)
+            .map_err(|e| e.to_string())?;
+        let scanned = items.len();
+        let matched = rules::dry_run(&compiled, &items);
+        let matches = items
+            .into_iter()
+            .filter(|item| matched.contains(&item.id))
+            .map(|item| RuleTestMatch {
+                id: item.id,
+                content_type: item.content_type,
+                preview: item.preview,
+                source_app_name: item.source_app_name,
+                created_at: item.created_at,
+                group_id: item.group_id,
+            })
+            .collect();
+        Ok(RuleTestResult { scanned, matches })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 启动 TTL 到期清理线程（只启动一次）：每分钟删除到期条目，有删除时通知前端刷新列表
+pub fn start_expiry_timer(app: AppHandle, db: Database) {
+    if EXPIRY_TIMER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("rule-expiry".into())
+        .spawn(move || {
+            let repo = ClipboardRepository::new(&db);
+            loop {
+                match repo.delete_expired() {
+                    Ok((deleted, image_paths, file_payloads)) => {
+                        crate::clipboard::cleanup_deleted_assets(&image_paths, &file_payloads);
+                        if deleted > 0 {
+                            info!("Removed {} expired items", deleted);
+                            let _ = app.emit("clipboard-items-expired", deleted);
+                        }
+                    }
+                    Err(e) => warn!("Failed to delete expired items: {}", e),
+                }
+                std::thread::sleep(EXPIRY_CHECK_INTERVAL);
+            }
+        });
+    if let Err(e) = spawned {
+        EXPIRY_TIMER_STARTED.store(false, Ordering::SeqCst);
+        tracing::error!("Failed to spawn rule-expiry thread: {}", e);
+    }
+}
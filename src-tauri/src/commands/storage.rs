@@ -0,0 +1,178 @@
+//! 存储维护：完整性检查（手动检查 / 修复与定时检查）与自动清理预览
+
+use crate::blobs::fsck::{self, CheckOptions, CheckReport};
+use crate::clipboard::retention::{self, RetentionPolicy};
+use crate::config::AppConfig;
+use crate::database::{
+    ClipboardRepository, Database, GroupRepository, RetentionCandidate, SettingsRepository,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tracing::{info, warn};
+
+use super::AppState;
+
+/// 进度事件：`{ phase, done, total }`，每 100ms 最多一次
+const PROGRESS_EVENT: &str = "storage-check-progress";
+/// 完成事件：载荷为检查报告
+const FINISHED_EVENT: &str = "storage-check-finished";
+
+/// 定时检查间隔（天，0 = 关闭）
+const INTERVAL_DAYS_SETTING: &str = "storage_check_interval_days";
+/// 定时检查时是否自动修复
+const AUTO_REPAIR_SETTING: &str = "storage_check_auto_repair";
+const LAST_RUN_SETTING: &str = "_storage_check_last_run";
+const LAST_REPORT_SETTING: &str = "_storage_check_last_report";
+
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 上次检查结果与当前运行状态
+#[derive(Debug, Serialize)]
+pub struct StorageCheckStatus {
+    pub running: bool,
+    pub last_report: Option<CheckReport>,
+}
+
+fn run_check(app: &AppHandle, db: &Database, options: CheckOptions) -> Result<CheckReport, String> {
+    let store = crate::blobs::global().ok_or("Blob store not initialized")?;
+    let data_dir = AppConfig::load().get_data_dir();
+    let mut last_emit: Option<Instant> = None;
+    let report = fsck::run(db, store, &data_dir, options, |progress| {
+        // 节流：每 100ms 最多发送一次，阶段结束时总是发送
+        let due = last_emit.is_none_or(|t| t.elapsed() >= Duration::from_millis(100));
+        if due || progress.done >= progress.total {
+            let _ = app.emit(PROGRESS_EVENT, &progress);
+            last_emit = Some(Instant::now());
+        }
+    })?;
+
+    let settings = SettingsRepository::new(db);
+    let _ = settings.set(
+        LAST_RUN_SETTING,
+        &chrono::Utc::now().timestamp().to_string(),
+    );
+    match serde_json::to_string(&report) {
+        Ok(json) => {
+            let _ = settings.set(LAST_REPORT_SETTING, &json);
+        }
+        Err(e) => warn!("Failed to save storage check report: {}", e),
+    }
+    let _ = app.emit(FINISHED_EVENT, &report);
+    Ok(report)
+}
+
+/// 检查媒体文件与数据库引用是否一致；`repair` 为 true 时同时修复
+#[tauri::command]
+pub async fn check_storage(
+    state: State<'_, Arc<AppState>>,
+    app: AppHandle,
+    repair: bool,
+    verify_hashes: bool,
+) -> Result<CheckReport, String> {
+    let db = state.db.clone();
+    let options = CheckOptions {
+        repair,
+        verify_hashes,
+    };
+    tokio::task::spawn_blocking(move || run_check(&app, &db, options))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 获取上次检查的报告与是否正在检查
+#[tauri::command]
+pub async fn get_storage_check_status(
+    state: State<'_, Arc<AppState>>,
+) -> Result<StorageCheckStatus, String> {
+    let last_report = SettingsRepository::new(&state.db)
+        .get(LAST_REPORT_SETTING)
+        .map_err(|e| e.to_string())?
+        .and_then(|json| serde_json::from_str(&json).ok());
+    Ok(StorageCheckStatus {
+        running: fsck::is_running(),
+        last_report,
+    })
+}
+
+/// 下一次自动清理将删除的条目（旧→新）
+#[derive(Debug, Serialize)]
+pub struct CleanupPreview {
+    pub base_days: i64,
+    pub policy: RetentionPolicy,
+    pub items: Vec<RetentionCandidate>,
+    pub total_bytes: i64,
+}
+
+/// 按当前保留设置预览各分组下一次自动清理会删除的条目
+#[tauri::command]
+pub async fn preview_retention_cleanup(
+    state: State<'_, Arc<AppState>>,
+) -> Result<CleanupPreview, String> {
+    let db = state.db.clone();
+    tokio::task::spawn_blocking(move || {
+        let settings = SettingsRepository::new(&db);
+        let base_days = settings
+            .get_parsed(retention::AUTO_CLEANUP_SETTING)
+            .unwrap_or(retention::DEFAULT_AUTO_CLEANUP_DAYS);
+        let policy = RetentionPolicy::load(&settings);
+        let groups = GroupRepository::new(&db)
+            .list_with_count()
+            .map_err(|e| e.to_string())?;
+        let repo = ClipboardRepository::new(&db);
+        let mut items = Vec::new();
+        for group_id in std::iter::once(None).chain(groups.iter().map(|g| Some(g.id))) {
+            items.extend(
+                repo.retention_expired(base_days, group_id, &policy)
+                    .map_err(|e| e.to_string())?,
+            );
+        }
+        items.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(CleanupPreview {
+            base_days,
+            policy,
+            total_bytes: items.iter().map(|c| c.bytes).sum(),
+            items,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 启动定时检查线程（只启动一次）：启动 10 分钟后开始，每小时判断是否到期
+pub fn start_scheduled_check(app: AppHandle, db: Database) {
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("storage-check".into())
+        .spawn(move || {
+            std::thread::sleep(Duration::from_secs(600));
+            loop {
+                let settings = SettingsRepository::new(&db);
+                let interval_days: i64 = settings.get_parsed(INTERVAL_DAYS_SETTING).unwrap_or(0);
+                let last_run: i64 = settings.get_parsed(LAST_RUN_SETTING).unwrap_or(0);
+                let now = chrono::Utc::now().timestamp();
+                if interval_days > 0 && now - last_run >= interval_days * 86_400 {
+                    let options = CheckOptions {
+                        repair: settings.get_bool(AUTO_REPAIR_SETTING, false),
+                        verify_hashes: false,
+                    };
+                    info!(
+                        "Running scheduled storage check (repair: {})",
+                        options.repair
+                    );
+                    if let Err(e) = run_check(&app, &db, options) {
+                        warn!("Scheduled storage check failed: {}", e);
+                    }
+                }
+                std::thread::sleep(Duration::from_secs(3600));
+            }
+        });
+    if let Err(e) = spawned {
+        SCHEDULER_STARTED.store(false, Ordering::SeqCst);
+        tracing::error!("Failed to spawn storage-check thread: {}", e);
+    }
+}
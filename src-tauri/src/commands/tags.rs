@@ -0,0 +1,87 @@
+use crate::database::{Tag, TagRepository};
+use std::sync::Arc;
+use tauri::State;
+
+use super::AppState;
+
+fn normalize_tag_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("标签名称不能为空".to_string());
+    }
+    Ok(name)
+}
+
+/// 获取所有标签（含条目数）
+#[tauri::command]
+pub async fn get_tags(state: State<'_, Arc<AppState>>) -> Result<Vec<Tag>, String> {
+    let repo = TagRepository::new(&state.db);
+    repo.list_with_count().map_err(|e| e.to_string())
+}
+
+/// 创建标签，返回完整标签对象
+#[tauri::command]
+pub async fn create_tag(
+    state: State<'_, Arc<AppState>>,
+    name: String,
+    color: Option<String>,
+) -> Result<Tag, String> {
+    let repo = TagRepository::new(&state.db);
+    repo.create(normalize_tag_name(&name)?, color.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 重命名标签
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    name: String,
+) -> Result<(), String> {
+    let repo = TagRepository::new(&state.db);
+    repo.rename(id, normalize_tag_name(&name)?)
+        .map_err(|e| e.to_string())
+}
+
+/// 更新标签颜色（传 None 清除颜色）
+#[tauri::command]
+pub async fn update_tag_color(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    color: Option<String>,
+) -> Result<(), String> {
+    let repo = TagRepository::new(&state.db);
+    repo.update_color(id, color.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 删除标签（条目保留，仅解除关联）
+#[tauri::command]
+pub async fn delete_tag(state: State<'_, Arc<AppState>>, id: i64) -> Result<(), String> {
+    let repo = TagRepository::new(&state.db);
+    repo.delete(id).map_err(|e| e.to_string())
+}
+
+/// 为一个或多个条目添加标签，返回新增关联数
+#[tauri::command]
+pub async fn tag_items(
+    state: State<'_, Arc<AppState>>,
+    item_ids: Vec<i64>,
+    tag_ids: Vec<i64>,
+) -> Result<usize, String> {
+    let repo = TagRepository::new(&state.db);
+    repo.tag_items(&item_ids, &tag_ids)
+        .map_err(|e| e.to_string())
+}
+
+/// 移除一个或多个条目的标签，返回移除关联数
+#[tauri::command]
+pub async fn untag_items(
+    state: State<'_, Arc<AppState>>,
+    item_ids: Vec<i64>,
+    tag_ids: Vec<i64>,
+) -> Result<usize, String> {
+    let repo = TagRepository::new(&state.db);
+    repo.untag_items(&item_ids, &tag_ids)
+        .map_err(|e| e.to_string())
+}
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use tauri::{Emitter, Manager, State};
 
 use super::AppState;
+use crate::ai::openai::ChatMessage;
 use crate::database;
+use crate::translation::{self, Provider, glossary};
 
 fn ensure_translate_available(db: &crate::database::Database) -> Result<(), String> {
     let repo = database::SettingsRepository::new(db);
@@ -100,18 +102,80 @@ fn translate_microsoft(
         .ok_or_else(|| "TRANSLATE:INVALID_FORMAT".to_string())
 }
 
+/// DeepLX 流式翻译时单次请求的段落合并上限（字节）
+const DEEPLX_CHUNK_BYTES: usize = 1500;
+
 /// DeepLX 翻译（自定义接口地址）
+///
+/// DeepLX 没有流式接口：流式模式下按段落分批请求，每完成一批推送一次已译内容。
 fn translate_deeplx(
     client: &reqwest::blocking::Client,
     text: &str,
     from: &str,
     to: &str,
     endpoint: &str,
+    on_partial: Option<&mut dyn FnMut(&str)>,
 ) -> Result<String, String> {
     let endpoint = endpoint.trim();
     if endpoint.is_empty() {
         return Err("TRANSLATE:INVALID_CONFIG:DEEPLX_ENDPOINT".to_string());
     }
+    let Some(on_partial) = on_partial else {
+        return deeplx_request(client, text, from, to, endpoint);
+    };
+    let (chunks, separator) = split_paragraph_chunks(text, DEEPLX_CHUNK_BYTES);
+    let mut output = String::new();
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        if index > 0 {
+            output.push_str(separator);
+        }
+        if chunk.trim().is_empty() {
+            output.push_str(chunk);
+        } else {
+            output.push_str(&deeplx_request(client, chunk, from, to, endpoint)?);
+        }
+        on_partial(&output);
+    }
+    Ok(output)
+}
+
+/// 按空行把文本切成若干段落组（每组不超过 `max_bytes`，单段超长时独占一组），
+/// 返回各组与组间分隔符；各组以分隔符拼接即为原文。
+fn split_paragraph_chunks(text: &str, max_bytes: usize) -> (Vec<&str>, &'static str) {
+    let separator = if text.contains("\r\n") {
+        "\r\n\r\n"
+    } else {
+        "\n\n"
+    };
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0;
+    let mut chunk_end = None;
+    let mut paragraph_start = 0;
+    let boundaries = text
+        .match_indices(separator)
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()));
+    for paragraph_end in boundaries {
+        if let Some(end) = chunk_end
+            && paragraph_end - chunk_start > max_bytes
+        {
+            chunks.push(&text[chunk_start..end]);
+            chunk_start = paragraph_start;
+        }
+        chunk_end = Some(paragraph_end);
+        paragraph_start = paragraph_end + separator.len();
+    }
+    chunks.push(&text[chunk_start..]);
+    (chunks, separator)
+}
+
+fn deeplx_request(
+    client: &reqwest::blocking::Client,
+    text: &str,
+    from: &str,
+    to: &str,
+    endpoint: &str,
+) -> Result<String, String> {
     let source_lang = if from == "auto" { "" } else { from };
     let body = serde_json::json!({
         "text": text,
@@ -275,20 +339,15 @@ fn translate_openai(
     endpoint: &str,
     api_key: &str,
     model: &str,
+    on_partial: Option<&mut dyn FnMut(&str)>,
 ) -> Result<String, String> {
     if api_key.is_empty() {
         return Err("TRANSLATE:INVALID_CONFIG:API_KEY".to_string());
     }
-    let base = if endpoint.is_empty() {
-        "https://api.openai.com/v1"
-    } else {
-        endpoint.trim_end_matches('/')
-    };
-    let url = format!("{base}/chat/completions");
-    let model_id = if model.is_empty() {
-        "gpt-4o-mini"
-    } else {
-        model
+    let config = crate::ai::openai::ChatConfig {
+        endpoint: endpoint.to_string(),
+        api_key: api_key.to_string(),
+        model: model.to_string(),
     };
     let from_desc = if from == "auto" {
         "auto-detected language"
@@ -298,32 +357,120 @@ fn translate_openai(
     let system_prompt = format!(
         "You are a professional translator. Translate the following text from {from_desc} to {to}. Only output the translation, no explanations.",
     );
-    let body = serde_json::json!({
-        "model": model_id,
-        "messages": [
-            { "role": "system", "content": system_prompt },
-            { "role": "user", "content": text },
-        ],
-        "temperature": 0.3,
-    });
-    let resp = client
-        .post(&url)
-        .header("Authorization", format!("Bearer {api_key}"))
-        .json(&body)
-        .send()
-        .map_err(|e| format!("TRANSLATE:REQUEST_FAILED:{e}"))?;
-    let val = parse_response(resp, "openai")?;
-    val["choices"][0]["message"]["content"]
-        .as_str()
-        .map(|s| s.trim().to_string())
-        .ok_or_else(|| "TRANSLATE:INVALID_FORMAT".to_string())
+    let messages = [ChatMessage::system(system_prompt), ChatMessage::user(text)];
+    let result = match on_partial {
+        Some(on_partial) => {
+            let mut output = String::new();
+            crate::ai::openai::chat_stream(
+                client,
+                &config,
+                &messages,
+                0.3,
+                &std::sync::atomic::AtomicBool::new(false),
+                &mut |delta| {
+                    output.push_str(delta);
+                    on_partial(output.trim_start());
+                },
+            )
+        }
+        None => crate::ai::openai::chat(client, &config, &messages, 0.3),
+    };
+    result.map_err(|e| e.code("TRANSLATE"))
+}
+
+/// 设置项：翻译条目时把译文保存为关联到源条目的新条目（默认开启）
+const SAVE_LINKED_SETTING: &str = "translate_save_linked";
+/// 设置项：缓存译文（默认开启）
+const CACHE_ENABLED_SETTING: &str = "translate_cache_enabled";
+
+/// 流式翻译进度事件（仅发给调用窗口）
+const STREAM_EVENT: &str = "translate-stream";
+
+#[derive(Clone, serde::Serialize)]
+struct TranslateStreamEvent<'a> {
+    stream_id: u64,
+    /// 目前已译出的完整内容
+    text: &'a str,
+}
+
+/// 前端传入的各翻译服务配置
+#[derive(Default)]
+struct ProviderConfigs {
+    deeplx_endpoint: String,
+    google_api_key: String,
+    baidu_app_id: String,
+    baidu_secret_key: String,
+    openai_endpoint: String,
+    openai_api_key: String,
+    openai_model: String,
+}
+
+impl ProviderConfigs {
+    /// 翻译缓存中的服务标识：OpenAI 按端点与模型、DeepLX 按端点区分，切换后不会命中旧译文
+    fn cache_scope(&self, id: &str) -> String {
+        match id {
+            "openai" => format!("openai|{}|{}", self.openai_endpoint, self.openai_model),
+            "deeplx" => format!("deeplx|{}", self.deeplx_endpoint),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// 基于 HTTP 接口的翻译服务（服务链中的一环）
+struct HttpProvider<'a> {
+    id: &'a str,
+    client: &'a reqwest::blocking::Client,
+    configs: &'a ProviderConfigs,
+}
+
+impl Provider for HttpProvider<'_> {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn translate(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+        on_partial: Option<&mut dyn FnMut(&str)>,
+    ) -> Result<String, String> {
+        let (client, c) = (self.client, self.configs);
+        match self.id {
+            "microsoft" => translate_microsoft(client, text, from, to),
+            "deeplx" => translate_deeplx(client, text, from, to, &c.deeplx_endpoint, on_partial),
+            "google_free" => translate_google_free(client, text, from, to),
+            "google_api" => translate_google_api(client, text, from, to, &c.google_api_key),
+            "baidu" => {
+                translate_baidu(client, text, from, to, &c.baidu_app_id, &c.baidu_secret_key)
+            }
+            "openai" => translate_openai(
+                client,
+                text,
+                from,
+                to,
+                &c.openai_endpoint,
+                &c.openai_api_key,
+                &c.openai_model,
+                on_partial,
+            ),
+            other => Err(format!("TRANSLATE:UNSUPPORTED_PROVIDER:{other}")),
+        }
+    }
 }
 
 /// 翻译文本（Tauri 命令）
+///
+/// 依次尝试主服务与设置中的备用服务（`translate_fallback_providers`），术语表中的术语以占位符
+/// 保护后再请求。结果按 (provider, from, to, 原文语义哈希) 缓存在数据库中，命中时不发起网络请求；
+/// 缓存的是还原术语前的译文，修改术语译法后无需清空缓存。
+/// 传入 `stream_id` 时，OpenAI / DeepLX 的中间结果通过 `translate-stream` 事件推送给调用窗口。
+/// `test` 为 true 时（设置页「测试连接」）只请求主服务且不读写缓存。
 #[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn translate_text(
     state: State<'_, Arc<AppState>>,
+    window: tauri::WebviewWindow,
     text: String,
     from: String,
     to: String,
@@ -337,51 +484,243 @@ pub async fn translate_text(
     openai_endpoint: Option<String>,
     openai_api_key: Option<String>,
     openai_model: Option<String>,
+    stream_id: Option<u64>,
+    source_id: Option<i64>,
+    test: Option<bool>,
 ) -> Result<String, String> {
     ensure_translate_available(&state.db)?;
+    let test = test.unwrap_or(false);
+    let state = state.inner().clone();
     tokio::task::spawn_blocking(move || {
-        let client = build_client(&proxy_mode, &proxy_url)?;
-        match provider.as_str() {
-            "microsoft" => translate_microsoft(&client, &text, &from, &to),
-            "deeplx" => translate_deeplx(
-                &client,
-                &text,
-                &from,
-                &to,
-                &deeplx_endpoint.unwrap_or_default(),
-            ),
-            "google_free" => translate_google_free(&client, &text, &from, &to),
-            "google_api" => translate_google_api(
-                &client,
-                &text,
-                &from,
-                &to,
-                &google_api_key.unwrap_or_default(),
-            ),
-            "baidu" => translate_baidu(
-                &client,
-                &text,
+        let settings = database::SettingsRepository::new(&state.db);
+        // 先查缓存，再按服务链与术语表翻译；返回译文与实际给出结果的服务
+        let translate = || -> Result<(String, String), String> {
+            let chain = if test {
+                vec![provider.clone()]
+            } else {
+                translation::parse_chain(
+                    &provider,
+                    settings
+                        .get(translation::FALLBACK_SETTING)
+                        .ok()
+                        .flatten()
+                        .as_deref(),
+                )
+            };
+            let configs = ProviderConfigs {
+                deeplx_endpoint: deeplx_endpoint.unwrap_or_default(),
+                google_api_key: google_api_key.unwrap_or_default(),
+                baidu_app_id: baidu_app_id.unwrap_or_default(),
+                baidu_secret_key: baidu_secret_key.unwrap_or_default(),
+                openai_endpoint: openai_endpoint.unwrap_or_default(),
+                openai_api_key: openai_api_key.unwrap_or_default(),
+                openai_model: openai_model.unwrap_or_default(),
+            };
+            let glossary = glossary::parse(
+                settings
+                    .get(glossary::GLOSSARY_SETTING)
+                    .ok()
+                    .flatten()
+                    .as_deref(),
+            );
+            let protected = glossary::protect(&glossary, &text, &to);
+
+            let cache = database::TranslationCacheRepository::new(&state.db);
+            let cache_key = (!test && settings.get_bool(CACHE_ENABLED_SETTING, true))
+                .then(|| crate::clipboard::semantic_hash_from_text(&protected.text))
+                .flatten();
+            if let Some(hash) = &cache_key {
+                for id in &chain {
+                    if let Ok(Some(cached)) = cache.get(&configs.cache_scope(id), &from, &to, hash)
+                    {
+                        tracing::debug!("[TRANSLATE] cache hit ({}, {} -> {})", id, from, to);
+                        return Ok((protected.restore(&cached), id.clone()));
+                    }
+                }
+            }
+
+            let mut emit_partial = |partial: &str| {
+                if let Some(stream_id) = stream_id {
+                    let _ = window.emit_to(
+                        window.label(),
+                        STREAM_EVENT,
+                        TranslateStreamEvent {
+                            stream_id,
+                            text: &protected.restore(partial),
+                        },
+                    );
+                }
+            };
+            let on_partial: Option<&mut dyn FnMut(&str)> = if stream_id.is_some() {
+                Some(&mut emit_partial)
+            } else {
+                None
+            };
+
+            let client = build_client(&proxy_mode, &proxy_url)?;
+            let providers: Vec<HttpProvider> = chain
+                .iter()
+                .map(|id| HttpProvider {
+                    id,
+                    client: &client,
+                    configs: &configs,
+                })
+                .collect();
+            let providers: Vec<&dyn Provider> =
+                providers.iter().map(|p| p as &dyn Provider).collect();
+            let outcome = translation::translate_with_fallback(
+                &providers,
+                &translation::HEALTH,
+                &protected.text,
                 &from,
                 &to,
-                &baidu_app_id.unwrap_or_default(),
-                &baidu_secret_key.unwrap_or_default(),
-            ),
-            "openai" => translate_openai(
-                &client,
-                &text,
+                on_partial,
+            )?;
+
+            if let Some(hash) = &cache_key
+                && let Err(e) = cache.put(
+                    &configs.cache_scope(&outcome.provider),
+                    &from,
+                    &to,
+                    hash,
+                    &outcome.text,
+                )
+            {
+                tracing::warn!("[TRANSLATE] failed to write translation cache: {}", e);
+            }
+            Ok((protected.restore(&outcome.text), outcome.provider))
+        };
+        let (translated, provider) = translate()?;
+
+        if let Some(source_id) = source_id
+            && settings.get_bool(SAVE_LINKED_SETTING, true)
+            && let Err(e) = save_linked_translation(
+                &state,
+                window.app_handle(),
+                source_id,
+                &translated,
+                &provider,
                 &from,
                 &to,
-                &openai_endpoint.unwrap_or_default(),
-                &openai_api_key.unwrap_or_default(),
-                &openai_model.unwrap_or_default(),
-            ),
-            other => Err(format!("TRANSLATE:UNSUPPORTED_PROVIDER:{other}")),
+            )
+        {
+            tracing::warn!(
+                "[TRANSLATE] failed to save translation of item {}: {}",
+                source_id,
+                e
+            );
         }
+        Ok(translated)
     })
     .await
     .map_err(|e| format!("TRANSLATE:TASK_FAILED:{e}"))?
 }
 
+/// 把条目的译文保存为新条目（与源条目同组），并关联到源条目
+fn save_linked_translation(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    source_id: i64,
+    translated: &str,
+    provider: &str,
+    from: &str,
+    to: &str,
+) -> Result<(), String> {
+    let repo = database::ClipboardRepository::new(&state.db);
+    let Some(source) = repo.get_by_id(source_id).map_err(|e| e.to_string())? else {
+        return Ok(());
+    };
+    // 译文与原文相同（如已是目标语言）时不保存
+    if translated.trim().is_empty() || source.text_content.as_deref() == Some(translated) {
+        return Ok(());
+    }
+    // 只关联新建的条目：去重命中或并入收集记录时得到的是已有条目，不能改写其来源
+    let Some(captured) = super::save_generated(
+        state,
+        app,
+        Some(translated.to_string()),
+        None,
+        source.group_id,
+    )?
+    .filter(|c| c.inserted) else {
+        return Ok(());
+    };
+    let derivation = serde_json::json!({
+        "kind": "translation",
+        "provider": provider,
+        "from": from,
+        "to": to,
+    });
+    repo.link_derived(captured.id, source_id, &derivation.to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 各翻译服务的健康状况（成功 / 失败次数、最近错误、是否冷却中）
+#[tauri::command]
+pub fn get_translate_provider_health()
+-> std::collections::BTreeMap<String, translation::ProviderHealth> {
+    translation::HEALTH.snapshot()
+}
+
+/// 设置备用翻译服务（主服务失败时按顺序尝试）
+#[tauri::command]
+pub async fn set_translate_fallback_providers(
+    state: State<'_, Arc<AppState>>,
+    providers: Vec<String>,
+) -> Result<(), String> {
+    if let Some(unknown) = providers
+        .iter()
+        .find(|p| !translation::PROVIDERS.contains(&p.as_str()))
+    {
+        return Err(format!("TRANSLATE:UNSUPPORTED_PROVIDER:{unknown}"));
+    }
+    let json = serde_json::to_string(&providers).map_err(|e| e.to_string())?;
+    database::SettingsRepository::new(&state.db)
+        .set(translation::FALLBACK_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// 读取术语表
+#[tauri::command]
+pub async fn get_translate_glossary(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<glossary::GlossaryEntry>, String> {
+    let raw = database::SettingsRepository::new(&state.db)
+        .get(glossary::GLOSSARY_SETTING)
+        .map_err(|e| e.to_string())?;
+    Ok(glossary::parse(raw.as_deref()))
+}
+
+/// 保存术语表
+#[tauri::command]
+pub async fn set_translate_glossary(
+    state: State<'_, Arc<AppState>>,
+    entries: Vec<glossary::GlossaryEntry>,
+) -> Result<(), String> {
+    glossary::validate(&entries)?;
+    let json = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    database::SettingsRepository::new(&state.db)
+        .set(glossary::GLOSSARY_SETTING, &json)
+        .map_err(|e| e.to_string())
+}
+
+/// 翻译缓存条数
+#[tauri::command]
+pub async fn get_translation_cache_count(state: State<'_, Arc<AppState>>) -> Result<i64, String> {
+    database::TranslationCacheRepository::new(&state.db)
+        .count()
+        .map_err(|e| e.to_string())
+}
+
+/// 清空翻译缓存，返回删除条数
+#[tauri::command]
+pub async fn clear_translation_cache(state: State<'_, Arc<AppState>>) -> Result<usize, String> {
+    database::TranslationCacheRepository::new(&state.db)
+        .clear()
+        .map_err(|e| e.to_string())
+}
+
 /// 将文本写入系统剪贴板
 #[tauri::command]
 pub async fn write_text_to_clipboard(
@@ -861,3 +1200,51 @@ pub async fn update_translate_selection_shortcut(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ProviderConfigs, split_paragraph_chunks};
+
+    #[test]
+    fn paragraph_chunks_rejoin_to_original() {
+        let text = "aaaa\n\nbbbb\n\ncccc\n\n\ndddd";
+        for max in [1, 6, 10, 100] {
+            let (chunks, sep) = split_paragraph_chunks(text, max);
+            assert_eq!(chunks.join(sep), text, "max = {max}");
+        }
+        let (chunks, sep) = split_paragraph_chunks(text, 10);
+        assert_eq!(sep, "\n\n");
+        assert_eq!(chunks, ["aaaa\n\nbbbb", "cccc", "\ndddd"]);
+
+        let (chunks, sep) = split_paragraph_chunks("one\r\n\r\ntwo", 1);
+        assert_eq!((chunks, sep), (vec!["one", "two"], "\r\n\r\n"));
+        assert_eq!(split_paragraph_chunks("", 10).0, [""]);
+    }
+
+    #[test]
+    fn cache_scope_separates_endpoints_and_models() {
+        let configs = |endpoint: &str, model: &str| ProviderConfigs {
+            deeplx_endpoint: endpoint.to_string(),
+            google_api_key: String::new(),
+            baidu_app_id: String::new(),
+            baidu_secret_key: String::new(),
+            openai_endpoint: endpoint.to_string(),
+            openai_api_key: "key".to_string(),
+            openai_model: model.to_string(),
+        };
+        let a = configs("https://a", "m1");
+        assert_ne!(
+            a.cache_scope("openai"),
+            configs("https://a", "m2").cache_scope("openai")
+        );
+        assert_ne!(
+            a.cache_scope("openai"),
+            configs("https://b", "m1").cache_scope("openai")
+        );
+        assert_ne!(
+            a.cache_scope("deeplx"),
+            configs("https://b", "m1").cache_scope("deeplx")
+        );
+        assert_eq!(a.cache_scope("microsoft"), "microsoft");
+    }
+}
@@ -192,6 +192,16 @@ pub fn migrate_data(old_path: &PathBuf, new_path: &PathBuf) -> Result<MigrationR
         copy_dir_recursive(&old_staged, &new_staged, &mut result);
     }
 
+    // 迁移 blob 存储（递归）
+    let old_blobs = old_path.join(crate::blobs::DIR_NAME);
+    if old_blobs.is_dir() {
+        copy_dir_recursive(
+            &old_blobs,
+            &new_path.join(crate::blobs::DIR_NAME),
+            &mut result,
+        );
+    }
+
     info!(
         "Migration complete: {} files, {} bytes",
         result.files_copied, result.bytes_copied
@@ -374,6 +374,207 @@ impl Database {
             info!("Migration complete: file_payload column added");
         }
 
+        // 迁移 12: 图片 OCR 文本（供搜索与「复制图片文字」使用）
+        let has_ocr_text: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'ocr_text'",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(false);
+
+        if !has_ocr_text {
+            info!("Migrating database: adding ocr_text column");
+            conn.execute_batch("ALTER TABLE clipboard_items ADD COLUMN ocr_text TEXT;")?;
+            info!("Migration complete: ocr_text column added");
+        }
+
+        // 迁移 13: 文本子类型标签（color/json/code 等，逗号分隔）
+        let has_subtypes: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'subtypes'",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(false);
+
+        if !has_subtypes {
+            info!("Migrating database: adding subtypes column");
+            conn.execute_batch("ALTER TABLE clipboard_items ADD COLUMN subtypes TEXT;")?;
+            info!("Migration complete: subtypes column added");
+        }
+
+        // 迁移 14: 条目过期时间（捕获规则设置的 TTL）
+        let has_expires_at: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'expires_at'",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(false);
+
+        if !has_expires_at {
+            info!("Migrating database: adding expires_at column");
+            conn.execute_batch("ALTER TABLE clipboard_items ADD COLUMN expires_at TEXT;")?;
+            info!("Migration complete: expires_at column added");
+        }
+
+        // 迁移 15: 分组层级与分组级设置（保留天数、最大条数、去重策略、同步开关）
+        let has_groups: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='groups'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if has_groups {
+            for (column, definition) in [
+                (
+                    "parent_id",
+                    "INTEGER REFERENCES groups(id) ON DELETE SET NULL",
+                ),
+                ("retention_days", "INTEGER"),
+                ("max_count", "INTEGER"),
+                ("dedup_strategy", "TEXT"),
+                ("sync_enabled", "INTEGER NOT NULL DEFAULT 1"),
+            ] {
+                let has_column: bool = conn
+                    .query_row(
+                        "SELECT COUNT(*) > 0 FROM pragma_table_info('groups') WHERE name = ?1",
+                        [column],
+                        |row| row.get(0),
+                    )
+                    .unwrap_or(false);
+
+                if !has_column {
+                    info!("Migrating database: adding groups.{} column", column);
+                    conn.execute_batch(&format!(
+                        "ALTER TABLE groups ADD COLUMN {column} {definition};"
+                    ))?;
+                    info!("Migration complete: groups.{} column added", column);
+                }
+            }
+        }
+
+        // 迁移 16: 派生条目（如翻译结果）指向源条目，附带来源元数据
+        for (column, definition) in [
+            (
+                "derived_from",
+                "INTEGER REFERENCES clipboard_items(id) ON DELETE SET NULL",
+            ),
+            ("derivation", "TEXT"),
+        ] {
+            let has_column: bool = conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = ?1",
+                    [column],
+                    |row| row.get(0),
+                )
+                .unwrap_or(false);
+
+            if !has_column {
+                info!("Migrating database: adding {} column", column);
+                conn.execute_batch(&format!(
+                    "ALTER TABLE clipboard_items ADD COLUMN {column} {definition};"
+                ))?;
+                info!("Migration complete: {} column added", column);
+            }
+        }
+
+        // 迁移 17: 文件条目后台 staging 状态
+        let has_staging_state: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'staging_state'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if !has_staging_state {
+            info!("Migrating database: adding staging_state column");
+            conn.execute_batch("ALTER TABLE clipboard_items ADD COLUMN staging_state TEXT;")?;
+            info!("Migration complete: staging_state column added");
+        }
+
+        // 迁移 18: 存储检查标记媒体文件缺失的条目
+        let has_media_missing: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'media_missing'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if !has_media_missing {
+            info!("Migrating database: adding media_missing column");
+            conn.execute_batch(
+                "ALTER TABLE clipboard_items ADD COLUMN media_missing INTEGER NOT NULL DEFAULT 0;",
+            )?;
+            info!("Migration complete: media_missing column added");
+        }
+
+        // 迁移 19: 分组级容量配额（MB，NULL = 沿用全局设置）
+        let has_quota_mb: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('groups') WHERE name = 'quota_mb'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if has_groups && !has_quota_mb {
+            info!("Migrating database: adding groups.quota_mb column");
+            conn.execute_batch("ALTER TABLE groups ADD COLUMN quota_mb INTEGER;")?;
+            info!("Migration complete: groups.quota_mb column added");
+        }
+
+        // 迁移 20: blob 文件名别名表（同一内容以多个文件名存入时全部可查）
+        let has_blobs: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='blobs'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        let has_blob_names: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='blob_names'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if has_blobs && !has_blob_names {
+            info!("Migrating database: adding blob_names table");
+            conn.execute_batch(
+                "DROP INDEX IF EXISTS idx_blobs_name;
+                 CREATE TABLE blob_names (
+                     hash TEXT NOT NULL REFERENCES blobs(hash) ON DELETE CASCADE,
+                     name TEXT NOT NULL,
+                     PRIMARY KEY (hash, name)
+                 );
+                 INSERT OR IGNORE INTO blob_names (hash, name) SELECT hash, name FROM blobs;",
+            )?;
+            info!("Migration complete: blob_names table added");
+        }
+
+        // 迁移 21: 缓存条目文本列字节数，配额与占用统计不再逐行读取内容
+        let has_content_bytes: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('clipboard_items') WHERE name = 'content_bytes'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if !has_content_bytes {
+            info!("Migrating database: adding content_bytes column");
+            conn.execute_batch(
+                "ALTER TABLE clipboard_items ADD COLUMN content_bytes INTEGER NOT NULL DEFAULT 0;
+                 UPDATE clipboard_items SET content_bytes = COALESCE(length(CAST(text_content AS BLOB)), 0)
+                     + COALESCE(length(CAST(html_content AS BLOB)), 0)
+                     + COALESCE(length(CAST(rtf_content AS BLOB)), 0)
+                     + COALESCE(length(CAST(ocr_text AS BLOB)), 0)
+                     + COALESCE(length(CAST(file_payload AS BLOB)), 0);",
+            )?;
+            info!("Migration complete: content_bytes column added");
+        }
+
         Ok(())
     }
 
@@ -1008,6 +1209,16 @@ mod tests {
             )
             .unwrap();
         assert_eq!(null_semantic, 0);
+
+        let group_columns: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('groups') \
+                 WHERE name IN ('parent_id', 'retention_days', 'max_count', 'dedup_strategy', 'sync_enabled')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(group_columns, 5);
     }
 
     #[test]
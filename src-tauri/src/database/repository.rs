@@ -1,7 +1,11 @@
 use super::{ContentType, Database};
-use crate::clipboard::semantic_hash_from_text;
+use crate::clipboard::file_clipboard::{
+    StagedFile, decode_payload, encode_payload, parse_file_paths, staged_paths_from_payload,
+};
+use crate::clipboard::retention::{RetentionPolicy, RetentionScore, UsageStats};
+use crate::clipboard::{classify_text, semantic_hash_from_text};
 use parking_lot::Mutex;
-use rusqlite::{Connection, Row, Transaction, params};
+use rusqlite::{Connection, OptionalExtension, Row, Transaction, params};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::debug;
@@ -36,6 +40,24 @@ pub struct ClipboardItem {
     pub source_app_icon: Option<String>,
     /// 所属分组（NULL = 默认分组，Some(id) = 自定义分组）
     pub group_id: Option<i64>,
+    /// 图片 OCR 识别出的文字（NULL = 尚未识别，空串 = 无可识别文字）
+    pub ocr_text: Option<String>,
+    /// 文本子类型标签（逗号分隔，如 `color`、`code,lang:rust`）
+    pub subtypes: Option<String>,
+    /// 过期时间（捕获规则 TTL，到期自动删除）
+    pub expires_at: Option<String>,
+    /// 源条目 ID（派生条目，如翻译结果；源条目删除后置空）
+    pub derived_from: Option<i64>,
+    /// 派生元数据（JSON，如 `{"kind":"translation","provider":"deeplx","from":"en","to":"zh"}`）
+    pub derivation: Option<String>,
+    /// 文件 staging 状态（`pending` / `partial` / `done` / `failed`；NULL = 无需 staging）
+    pub staging_state: Option<String>,
+    /// 媒体文件缺失（存储检查修复时标记，图片无法恢复）
+    #[serde(default)]
+    pub media_missing: bool,
+    /// 标签名称（查询时由 `TagRepository::attach_tags` 附加；同步按名称跨设备合并）
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
     /// 文件是否有效（查询时计算，不存储）
     #[serde(default, skip_deserializing)]
     pub files_valid: Option<bool>,
@@ -61,6 +83,7 @@ pub struct NewClipboardItem {
     pub source_app_icon: Option<String>,
     /// None = 默认分组，Some(id) = 自定义分组
     pub group_id: Option<i64>,
+    pub subtypes: Option<String>,
 }
 
 impl Default for NewClipboardItem {
@@ -83,6 +106,7 @@ impl Default for NewClipboardItem {
             source_app_name: None,
             source_app_icon: None,
             group_id: None,
+            subtypes: None,
         }
     }
 }
@@ -94,10 +118,66 @@ pub struct QueryOptions {
     pub pinned_only: bool,
     pub favorite_only: bool,
     pub group_id: Option<i64>,
+    /// 子类型过滤（逗号分隔，任一命中即可，如 `color` 或 `code,json`）
+    pub subtype: Option<String>,
+    /// 标签过滤（任一命中即可）；设置后跨分组查询，忽略 `group_id`
+    pub tag_ids: Option<Vec<i64>>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+/// 捕获路由规则：条件全部满足时将新条目放入目标分组，可选置顶 / 设置过期时间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRule {
+    pub id: i64,
+    pub name: String,
+    pub enabled: bool,
+    pub sort_order: i64,
+    /// 来源应用（与应用过滤同语法：子串或 `*`/`?` 通配符）
+    pub source_app: Option<String>,
+    /// 内容正则（匹配文本内容或文件路径）
+    pub content_regex: Option<String>,
+    /// 内容类型（逗号分隔）
+    pub content_types: Option<String>,
+    /// 时间窗口起止（`HH:MM`，可跨午夜）
+    pub time_start: Option<String>,
+    pub time_end: Option<String>,
+    /// 目标分组（None = 保持原分组，仅应用置顶 / TTL）
+    pub target_group_id: Option<i64>,
+    pub pin: bool,
+    /// 存活分钟数（None = 永久）
+    pub ttl_minutes: Option<i64>,
+    pub created_at: String,
+}
+
+/// 新建 / 更新规则时的输入
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureRuleInput {
+    pub name: String,
+    #[serde(default = "default_rule_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub source_app: Option<String>,
+    #[serde(default)]
+    pub content_regex: Option<String>,
+    #[serde(default)]
+    pub content_types: Option<String>,
+    #[serde(default)]
+    pub time_start: Option<String>,
+    #[serde(default)]
+    pub time_end: Option<String>,
+    #[serde(default)]
+    pub target_group_id: Option<i64>,
+    #[serde(default)]
+    pub pin: bool,
+    #[serde(default)]
+    pub ttl_minutes: Option<i64>,
+}
+
+fn default_rule_enabled() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Group {
     pub id: i64,
@@ -106,6 +186,170 @@ pub struct Group {
     pub sort_order: i64,
     pub created_at: String,
     pub item_count: i64,
+    /// 父分组（None = 顶层）
+    pub parent_id: Option<i64>,
+    #[serde(flatten)]
+    pub settings: GroupSettings,
+}
+
+/// 分组级设置，None 表示沿用全局设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupSettings {
+    /// 自动清理天数（0 = 该分组不自动清理）
+    pub retention_days: Option<i64>,
+    /// 最大条目数（0 = 不限制）
+    pub max_count: Option<i64>,
+    /// 去重策略覆盖：ignore / move_to_top / always_new
+    pub dedup_strategy: Option<String>,
+    /// 是否参与 WebDAV 同步
+    pub sync_enabled: bool,
+    /// 容量配额（MB，0 = 不限制）
+    pub quota_mb: Option<i64>,
+}
+
+impl Default for GroupSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: None,
+            max_count: None,
+            dedup_strategy: None,
+            sync_enabled: true,
+            quota_mb: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: String,
+    pub item_count: i64,
+}
+
+/// 文本条目的历史版本（列表不含完整文本）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemRevision {
+    pub id: i64,
+    pub item_id: i64,
+    pub content_type: String,
+    pub preview: String,
+    pub char_count: i64,
+    /// 该版本被编辑替换时的行级差异摘要，如 `+3 -1`
+    pub diff_summary: Option<String>,
+    pub created_at: String,
+}
+
+/// 历史版本的完整内容
+#[derive(Debug, Clone)]
+pub struct RevisionContent {
+    pub item_id: i64,
+    pub content_type: String,
+    pub text_content: String,
+    pub html_content: Option<String>,
+}
+
+/// 字节配额超出时的淘汰顺序（置顶与收藏条目不参与淘汰）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// 最早创建的优先
+    #[default]
+    Oldest,
+    /// 使用次数最少、最久未使用的优先
+    LeastAccessed,
+    /// 占用最大的优先
+    Largest,
+}
+
+impl EvictionPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "oldest" => Some(Self::Oldest),
+            "least_accessed" => Some(Self::LeastAccessed),
+            "largest" => Some(Self::Largest),
+            _ => None,
+        }
+    }
+
+    fn order_by(self) -> &'static str {
+        match self {
+            Self::Oldest => "ORDER BY created_at ASC, id ASC",
+            Self::LeastAccessed => {
+                "ORDER BY access_count ASC, COALESCE(last_accessed_at, created_at) ASC, id ASC"
+            }
+            Self::Largest => "ORDER BY bytes DESC, created_at ASC, id ASC",
+        }
+    }
+}
+
+/// 字节配额的统计范围
+#[derive(Debug, Clone, Copy)]
+pub enum QuotaScope<'a> {
+    /// 全部条目
+    Total,
+    /// 单一内容类型
+    ContentType(&'a str),
+    /// 单一分组（None = 默认分组），分组设置的配额优先
+    Group(Option<i64>),
+}
+
+/// 按内容类型统计的占用
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TypeUsage {
+    pub content_type: String,
+    pub count: i64,
+    pub bytes: i64,
+}
+
+/// 按分组统计的占用（`group_id` 为 None 表示默认分组）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GroupUsage {
+    pub group_id: Option<i64>,
+    pub name: Option<String>,
+    pub count: i64,
+    pub bytes: i64,
+}
+
+/// 条目占用明细：数据库内容与引用的媒体文件分开统计，共享的 blob 按引用数均摊
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageUsage {
+    pub total_bytes: i64,
+    pub content_bytes: i64,
+    pub media_bytes: i64,
+    pub by_type: Vec<TypeUsage>,
+    pub by_group: Vec<GroupUsage>,
+}
+
+/// 条目在数据库中的内容字节数（文本列长度之和，缓存于 `content_bytes` 列，更新时由触发器重算）
+fn content_bytes(columns: &[Option<&str>]) -> i64 {
+    columns.iter().flatten().map(|c| c.len() as i64).sum()
+}
+
+/// 条目引用的 blob 字节数（图片、图标、staging 副本），共享的 blob 按引用数均摊
+const ITEM_MEDIA_BYTES_SQL: &str = "COALESCE((SELECT SUM(b.size / MAX(b.ref_count, 1)) \
+     FROM item_blobs ib JOIN blobs b ON b.hash = ib.hash \
+     WHERE ib.item_id = clipboard_items.id), 0)";
+
+/// 自动清理的候选条目与其保留评分
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionCandidate {
+    pub id: i64,
+    pub content_type: String,
+    pub preview: Option<String>,
+    pub group_id: Option<i64>,
+    pub created_at: String,
+    pub last_accessed_at: Option<String>,
+    pub access_count: i64,
+    pub bytes: i64,
+    pub age_days: f64,
+    pub score: RetentionScore,
+    #[serde(skip)]
+    pub image_path: Option<String>,
+    #[serde(skip)]
+    pub file_payload: Option<String>,
 }
 
 /// 剪贴板条目仓库（读写分离）
@@ -114,6 +358,24 @@ pub struct ClipboardRepository {
     read_conn: Arc<Mutex<Connection>>,
 }
 
+/// 可按分组覆盖的清理上限
+#[derive(Debug, Clone, Copy)]
+enum GroupLimit {
+    RetentionDays,
+    MaxCount,
+    QuotaMb,
+}
+
+impl GroupLimit {
+    fn as_sql(self) -> &'static str {
+        match self {
+            GroupLimit::RetentionDays => "retention_days",
+            GroupLimit::MaxCount => "max_count",
+            GroupLimit::QuotaMb => "quota_mb",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum HashColumn {
     Content,
@@ -163,6 +425,16 @@ impl ConditionBuilder {
         self
     }
 
+    /// Restrict to a view: tagged items across all groups when `tag_ids` is
+    /// non-empty, otherwise the given group.
+    fn scope(mut self, group_id: Option<i64>, tag_ids: &[i64]) -> Self {
+        if tag_ids.is_empty() {
+            return self.group(group_id);
+        }
+        ClipboardRepository::append_tag_condition(tag_ids, &mut self.conditions, &mut self.params);
+        self
+    }
+
     /// Add content_type filter (supports comma-separated multi-type).
     fn content_type(mut self, content_type: Option<&str>) -> Self {
         ClipboardRepository::append_content_type_condition(
@@ -277,8 +549,8 @@ impl ClipboardRepository {
             "INSERT INTO clipboard_items
              (content_type, text_content, html_content, rtf_content, image_path, file_paths, file_payload,
               content_hash, semantic_hash, preview, byte_size, image_width, image_height, sort_order,
-              char_count, source_app_name, source_app_icon, group_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+              char_count, source_app_name, source_app_icon, group_id, subtypes, content_bytes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
             params![
                 item.content_type.as_str(),
                 item.text_content,
@@ -298,10 +570,18 @@ impl ClipboardRepository {
                 item.source_app_name,
                 item.source_app_icon,
                 item.group_id,
+                item.subtypes,
+                content_bytes(&[
+                    item.text_content.as_deref(),
+                    item.html_content.as_deref(),
+                    item.rtf_content.as_deref(),
+                    item.file_payload.as_deref(),
+                ]),
             ],
         )?;
 
         let id = conn.last_insert_rowid();
+        Self::sync_blob_refs(&conn, id)?;
         debug!(
             "Inserted clipboard item with id: {}, sort_order: {}, group_id: {:?}",
             id, new_sort_order, item.group_id
@@ -309,6 +589,21 @@ impl ClipboardRepository {
         Ok(id)
     }
 
+    /// 按 content_hash 或 semantic_hash 查找已有条目（合并导入判重用）
+    pub fn find_by_hashes(
+        &self,
+        content_hash: &str,
+        semantic_hash: &str,
+    ) -> Result<Option<ClipboardItem>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "SELECT * FROM clipboard_items WHERE content_hash = ?1 OR semantic_hash = ?2 LIMIT 1",
+            params![content_hash, semantic_hash],
+            Self::row_to_item,
+        )
+        .optional()
+    }
+
     pub fn exists_by_hash(
         &self,
         hash: &str,
@@ -494,13 +789,13 @@ impl ClipboardRepository {
     const LIST_COLUMNS: &'static str = "id, content_type, NULL AS text_content, NULL AS html_content, NULL AS rtf_content, \
          image_path, file_paths, NULL AS file_payload, content_hash, semantic_hash, preview, byte_size, image_width, image_height, \
          is_pinned, is_favorite, favorite_order, sort_order, created_at, updated_at, access_count, last_accessed_at, char_count, \
-         source_app_name, source_app_icon, group_id";
+         source_app_name, source_app_icon, group_id, NULL AS ocr_text, subtypes, expires_at, derived_from, derivation, staging_state, media_missing";
 
     /// 搜索查询列（含 text_content 用于关键词上下文预览）
     const SEARCH_COLUMNS: &'static str = "id, content_type, text_content, NULL AS html_content, NULL AS rtf_content, \
          image_path, file_paths, NULL AS file_payload, content_hash, semantic_hash, preview, byte_size, image_width, image_height, \
          is_pinned, is_favorite, favorite_order, sort_order, created_at, updated_at, access_count, last_accessed_at, char_count, \
-         source_app_name, source_app_icon, group_id";
+         source_app_name, source_app_icon, group_id, ocr_text, subtypes, expires_at, derived_from, derivation, staging_state, media_missing";
 
     /// 构建通用的 WHERE 条件（content_type / pinned_only / favorite_only / search）
     fn build_filter_conditions(
@@ -513,8 +808,13 @@ impl ClipboardRepository {
         if let Some(ref search) = options.search
             && !search.is_empty()
         {
+            // 派生条目（如译文）的文本也能命中源条目
             conditions.push(
-                "(text_content LIKE ? ESCAPE '\\' OR file_paths LIKE ? ESCAPE '\\')".to_string(),
+                "(text_content LIKE ? ESCAPE '\\' OR file_paths LIKE ? ESCAPE '\\' \
+                 OR ocr_text LIKE ? ESCAPE '\\' \
+                 OR id IN (SELECT derived_from FROM clipboard_items \
+                           WHERE derived_from IS NOT NULL AND text_content LIKE ? ESCAPE '\\'))"
+                    .to_string(),
             );
             let pattern = format!(
                 "%{}%",
@@ -524,6 +824,8 @@ impl ClipboardRepository {
                     .replace('_', "\\_")
             );
             params_vec.push(Box::new(pattern.clone()));
+            params_vec.push(Box::new(pattern.clone()));
+            params_vec.push(Box::new(pattern.clone()));
             params_vec.push(Box::new(pattern));
         }
 
@@ -534,6 +836,12 @@ impl ClipboardRepository {
             &mut params_vec,
         );
 
+        Self::append_subtype_condition(
+            options.subtype.as_deref(),
+            &mut conditions,
+            &mut params_vec,
+        );
+
         if options.pinned_only {
             conditions.push("is_pinned = 1".to_string());
         }
@@ -542,11 +850,16 @@ impl ClipboardRepository {
             conditions.push("is_favorite = 1".to_string());
         }
 
-        // 分组过滤：None = 默认分组（group_id IS NULL），Some(id) = 自定义分组
-        let (group_cond, group_param) = Self::group_condition(options.group_id);
-        conditions.push(group_cond.to_string());
-        if let Some(gid) = group_param {
-            params_vec.push(Box::new(gid));
+        // 标签视图跨分组；否则按分组过滤：None = 默认分组（group_id IS NULL），Some(id) = 自定义分组
+        let tag_ids = options.tag_ids.as_deref().unwrap_or_default();
+        if tag_ids.is_empty() {
+            let (group_cond, group_param) = Self::group_condition(options.group_id);
+            conditions.push(group_cond.to_string());
+            if let Some(gid) = group_param {
+                params_vec.push(Box::new(gid));
+            }
+        } else {
+            Self::append_tag_condition(tag_ids, &mut conditions, &mut params_vec);
         }
 
         (conditions, params_vec)
@@ -589,6 +902,52 @@ impl ClipboardRepository {
         }
     }
 
+    /// 子类型过滤：`subtypes` 为逗号分隔列表，按完整标签匹配（`code` 不会命中 `lang:code`）
+    fn append_subtype_condition(
+        subtype: Option<&str>,
+        conditions: &mut Vec<String>,
+        params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    ) {
+        let Some(raw) = subtype else {
+            return;
+        };
+        let tags: Vec<&str> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        if tags.is_empty() {
+            return;
+        }
+        let clauses: Vec<&str> = tags
+            .iter()
+            .map(|_| "(',' || subtypes || ',') LIKE ? ESCAPE '\\'")
+            .collect();
+        conditions.push(format!("({})", clauses.join(" OR ")));
+        for tag in tags {
+            let escaped = tag
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            params_vec.push(Box::new(format!("%,{escaped},%")));
+        }
+    }
+
+    /// 标签过滤：条目带有任一指定标签
+    fn append_tag_condition(
+        tag_ids: &[i64],
+        conditions: &mut Vec<String>,
+        params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    ) {
+        let placeholders = vec!["?"; tag_ids.len()].join(", ");
+        conditions.push(format!(
+            "id IN (SELECT item_id FROM item_tags WHERE tag_id IN ({placeholders}))"
+        ));
+        for &tag_id in tag_ids {
+            params_vec.push(Box::new(tag_id));
+        }
+    }
+
     /// 将条件拼接到 SQL 语句
     fn append_where(sql: &mut String, conditions: &[String]) {
         if !conditions.is_empty() {
@@ -598,17 +957,28 @@ impl ClipboardRepository {
     }
 
     pub fn list(&self, options: QueryOptions) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
-        let conn = self.read_conn.lock();
-
         let is_searching = options.search.as_ref().is_some_and(|s| !s.is_empty());
         let columns = if is_searching {
             Self::SEARCH_COLUMNS
         } else {
             Self::LIST_COLUMNS
         };
+        self.list_columns(&options, columns)
+    }
+
+    /// 与 `list` 相同的筛选与排序，但返回完整内容（含 HTML / RTF / OCR 文本），用于导出
+    pub fn list_full(&self, options: &QueryOptions) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+        self.list_columns(options, "*")
+    }
 
+    fn list_columns(
+        &self,
+        options: &QueryOptions,
+        columns: &str,
+    ) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
         let mut sql = format!("SELECT {columns} FROM clipboard_items");
-        let (conditions, mut params_vec) = Self::build_filter_conditions(&options);
+        let (conditions, mut params_vec) = Self::build_filter_conditions(options);
         Self::append_where(&mut sql, &conditions);
 
         if options.favorite_only {
@@ -753,46 +1123,49 @@ impl ClipboardRepository {
         Ok((deleted, image_paths, file_payloads))
     }
 
-    /// 获取可清除条目的图片路径（按分组/类型过滤）
+    /// 获取可清除条目的图片路径（按分组或标签/类型过滤）
     pub fn get_clearable_image_paths(
         &self,
         group_id: Option<i64>,
         content_type: Option<&str>,
+        tag_ids: &[i64],
     ) -> Result<Vec<String>, rusqlite::Error> {
         let conn = self.read_conn.lock();
         ConditionBuilder::new()
             .clearable()
-            .group(group_id)
+            .scope(group_id, tag_ids)
             .content_type(content_type)
             .condition("image_path IS NOT NULL")
             .select_strings(&conn, "SELECT image_path FROM clipboard_items", "")
     }
 
-    /// 获取可清除条目的 file_payload（按分组/类型过滤）
+    /// 获取可清除条目的 file_payload（按分组或标签/类型过滤）
     pub fn get_clearable_file_payloads(
         &self,
         group_id: Option<i64>,
         content_type: Option<&str>,
+        tag_ids: &[i64],
     ) -> Result<Vec<String>, rusqlite::Error> {
         let conn = self.read_conn.lock();
         ConditionBuilder::new()
             .clearable()
-            .group(group_id)
+            .scope(group_id, tag_ids)
             .content_type(content_type)
             .condition("file_payload IS NOT NULL")
             .select_strings(&conn, "SELECT file_payload FROM clipboard_items", "")
     }
 
-    /// 清空历史（保留置顶和收藏），按分组/类型过滤
+    /// 清空历史（保留置顶和收藏），按分组或标签/类型过滤
     pub fn clear_history(
         &self,
         group_id: Option<i64>,
         content_type: Option<&str>,
+        tag_ids: &[i64],
     ) -> Result<i64, rusqlite::Error> {
         let conn = self.write_conn.lock();
         ConditionBuilder::new()
             .clearable()
-            .group(group_id)
+            .scope(group_id, tag_ids)
             .content_type(content_type)
             .delete_items(&conn)
     }
@@ -858,53 +1231,229 @@ impl ClipboardRepository {
         Ok(deleted as i64)
     }
 
-    /// 删除 N 天前的非置顶/非收藏条目（按分组），返回 (删除数, 图片路径, file_payload)
+    /// 分组级清理设置覆盖全局值（NULL = 沿用全局；默认分组始终使用全局值）
+    fn group_limit(
+        conn: &Connection,
+        group_id: Option<i64>,
+        limit: GroupLimit,
+        global: i64,
+    ) -> Result<i64, rusqlite::Error> {
+        let Some(group_id) = group_id else {
+            return Ok(global);
+        };
+        let sql = format!("SELECT {} FROM groups WHERE id = ?1", limit.as_sql());
+        let value: Option<i64> = conn
+            .query_row(&sql, [group_id], |row| row.get(0))
+            .optional()?
+            .flatten();
+        Ok(value.unwrap_or(global))
+    }
+
+    /// 按保留评分列出分组内到期的非置顶/非收藏条目（分组设置的保留天数优先，0 = 不清理），旧→新
+    pub fn retention_expired(
+        &self,
+        days: i64,
+        group_id: Option<i64>,
+        policy: &RetentionPolicy,
+    ) -> Result<Vec<RetentionCandidate>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let days = Self::group_limit(&conn, group_id, GroupLimit::RetentionDays, days)?;
+        if days <= 0 {
+            return Ok(vec![]);
+        }
+        let base_days = days as f64;
+
+        // 只对超过最短保留天数的条目评分（同 RetentionPolicy::min_keep_days，大小取 byte_size）
+        let age_sql = "julianday('now', 'localtime') - julianday(created_at)";
+        let cb = ConditionBuilder::new().clearable().group(group_id);
+        let cb = if policy.size_ref_mb > 0.0 {
+            cb.condition_with_param(
+                &format!("{age_sql} > MAX(? / (1.0 + MAX(COALESCE(byte_size, 0), 0) / ?), ?)"),
+                base_days,
+            )
+            .param(policy.size_ref_mb * 1024.0 * 1024.0)
+            .param(base_days.min(1.0))
+        } else {
+            cb.condition_with_param(&format!("{age_sql} > ?"), base_days)
+        };
+        let sql = format!(
+            "SELECT id, content_type, preview, group_id, created_at, last_accessed_at, access_count, \
+             {age_sql}, \
+             julianday('now', 'localtime') - julianday(COALESCE(last_accessed_at, created_at)), \
+             COALESCE(byte_size, 0), image_path, file_payload \
+             FROM clipboard_items{} ORDER BY created_at ASC, id ASC",
+            cb.where_clause()
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(cb.param_refs().as_slice(), |row| {
+            let stats = UsageStats {
+                age_days: row.get(7)?,
+                idle_days: row.get(8)?,
+                access_count: row.get::<_, Option<i64>>(6)?.unwrap_or(0),
+                bytes: row.get(9)?,
+            };
+            Ok(RetentionCandidate {
+                id: row.get(0)?,
+                content_type: row.get(1)?,
+                preview: row.get(2)?,
+                group_id: row.get(3)?,
+                created_at: row.get(4)?,
+                last_accessed_at: row.get(5)?,
+                access_count: stats.access_count,
+                bytes: stats.bytes,
+                age_days: stats.age_days,
+                score: policy.score(base_days, &stats),
+                image_path: row.get(10)?,
+                file_payload: row.get(11)?,
+            })
+        })?;
+        let mut expired = Vec::new();
+        for candidate in rows {
+            let candidate = candidate?;
+            if candidate.score.expired(candidate.age_days) {
+                expired.push(candidate);
+            }
+        }
+        Ok(expired)
+    }
+
+    /// 按保留评分删除到期的非置顶/非收藏条目（分组设置的保留天数优先），返回 (删除数, 图片路径, file_payload)
     pub fn delete_older_than(
         &self,
         days: i64,
         group_id: Option<i64>,
+        policy: &RetentionPolicy,
     ) -> Result<(i64, Vec<String>, Vec<String>), rusqlite::Error> {
+        let expired = self.retention_expired(days, group_id, policy)?;
+        if expired.is_empty() {
+            return Ok((0, vec![], vec![]));
+        }
+        let ids: Vec<i64> = expired.iter().map(|c| c.id).collect();
+        let conn = self.write_conn.lock();
+        // 评分后被置顶/收藏的条目不删除，媒体路径也按实际删除的条目收集
+        let kept: Vec<i64> = {
+            let placeholders = vec!["?"; ids.len()].join(",");
+            let sql = format!(
+                "SELECT id FROM clipboard_items WHERE id IN ({placeholders}) \
+                 AND (is_pinned = 1 OR is_favorite = 1)"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            stmt.query_map(rusqlite::params_from_iter(ids.iter()), |row| row.get(0))?
+                .collect::<Result<_, _>>()?
+        };
+        let deleted = Self::delete_ids(&conn, &ids)?;
+        drop(conn);
+        let mut image_paths = Vec::new();
+        let mut file_payloads = Vec::new();
+        for candidate in expired.into_iter().filter(|c| !kept.contains(&c.id)) {
+            image_paths.extend(candidate.image_path);
+            file_payloads.extend(candidate.file_payload);
+        }
+
+        debug!(
+            "Auto-cleanup: deleted {} expired items (group: {:?})",
+            deleted, group_id
+        );
+        Ok((deleted, image_paths, file_payloads))
+    }
+
+    /// 按 ID 删除条目；选出候选后被置顶/收藏的条目跳过
+    fn delete_ids(conn: &Connection, ids: &[i64]) -> Result<i64, rusqlite::Error> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders = vec!["?"; ids.len()].join(",");
+        Ok(conn.execute(
+            &format!(
+                "DELETE FROM clipboard_items WHERE id IN ({placeholders}) \
+                 AND is_pinned = 0 AND is_favorite = 0"
+            ),
+            rusqlite::params_from_iter(ids.iter()),
+        )? as i64)
+    }
+
+    /// 删除已过期（捕获规则 TTL 到期）的非置顶、非收藏条目，返回 (删除数, 图片路径, file_payload)
+    pub fn delete_expired(&self) -> Result<(i64, Vec<String>, Vec<String>), rusqlite::Error> {
         let conn = self.write_conn.lock();
-        let age_cond = "created_at < datetime('now', 'localtime', '-' || ? || ' days')";
+        let expired_cond = "expires_at IS NOT NULL AND expires_at <= datetime('now', 'localtime')";
 
         let image_paths = ConditionBuilder::new()
-            .clearable()
-            .group(group_id)
+            .condition("is_pinned = 0")
+            .condition("is_favorite = 0")
             .condition("image_path IS NOT NULL")
-            .condition_with_param(age_cond, days)
+            .condition(expired_cond)
             .select_strings(&conn, "SELECT image_path FROM clipboard_items", "")?;
 
         let file_payloads = ConditionBuilder::new()
-            .clearable()
-            .group(group_id)
+            .condition("is_pinned = 0")
+            .condition("is_favorite = 0")
             .condition("file_payload IS NOT NULL")
-            .condition_with_param(age_cond, days)
+            .condition(expired_cond)
             .select_strings(&conn, "SELECT file_payload FROM clipboard_items", "")?;
 
         let deleted = ConditionBuilder::new()
-            .clearable()
-            .group(group_id)
-            .condition_with_param(age_cond, days)
+            .condition("is_pinned = 0")
+            .condition("is_favorite = 0")
+            .condition(expired_cond)
             .delete_items(&conn)?;
 
-        debug!(
-            "Auto-cleanup: deleted {} items older than {} days (group: {:?})",
-            deleted, days, group_id
-        );
+        if deleted > 0 {
+            debug!("Deleted {} expired items", deleted);
+        }
         Ok((deleted, image_paths, file_payloads))
     }
 
-    /// 执行最大数量限制（按分组），返回 (删除数, 图片路径, file_payload)
+    /// 应用捕获规则效果：置顶（只增不减）与过期时间
+    pub fn apply_rule_effects(
+        &self,
+        id: i64,
+        pin: bool,
+        ttl_minutes: Option<i64>,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        if pin {
+            conn.execute(
+                "UPDATE clipboard_items SET is_pinned = 1 WHERE id = ?1",
+                params![id],
+            )?;
+        }
+        if let Some(minutes) = ttl_minutes.filter(|m| *m > 0) {
+            conn.execute(
+                "UPDATE clipboard_items SET expires_at = datetime('now', 'localtime', '+' || ?1 || ' minutes') \
+                 WHERE id = ?2",
+                params![minutes, id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 最近 N 条条目（含文本内容，供规则试运行）
+    pub fn list_recent_with_text(&self, limit: i64) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let sql = format!(
+            "SELECT {} FROM clipboard_items ORDER BY created_at DESC, id DESC LIMIT ?1",
+            Self::SEARCH_COLUMNS
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let items = stmt
+            .query_map(params![limit], Self::row_to_item)?
+            .filter_map(std::result::Result::ok)
+            .collect();
+        Ok(items)
+    }
+
+    /// 执行最大数量限制（按分组，分组设置的最大条数优先），返回 (删除数, 图片路径, file_payload)
     pub fn enforce_max_count(
         &self,
         max_count: i64,
         group_id: Option<i64>,
     ) -> Result<(i64, Vec<String>, Vec<String>), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let max_count = Self::group_limit(&conn, group_id, GroupLimit::MaxCount, max_count)?;
         if max_count <= 0 {
             return Ok((0, vec![], vec![]));
         }
 
-        let conn = self.write_conn.lock();
         let current_count = ConditionBuilder::new()
             .clearable()
             .group(group_id)
@@ -957,53 +1506,497 @@ impl ClipboardRepository {
         Ok((deleted, image_paths, file_payloads))
     }
 
-    /// 去重置顶时刷新 HTML/RTF 字段（Word 等同内容重拷会更新 base64 RTF）
-    pub fn refresh_rich_fields(
-        &self,
-        id: i64,
-        item: &NewClipboardItem,
-    ) -> Result<(), rusqlite::Error> {
-        let conn = self.write_conn.lock();
-        conn.execute(
-            "UPDATE clipboard_items SET text_content = ?1, html_content = ?2, rtf_content = ?3, \
-             byte_size = ?4, preview = ?5, char_count = ?6, \
-             updated_at = datetime('now', 'localtime') WHERE id = ?7",
-            params![
-                item.text_content,
-                item.html_content,
-                item.rtf_content,
-                item.byte_size,
-                item.preview,
-                item.char_count,
-                id,
-            ],
-        )?;
-        debug!("Refreshed rich fields for item {}", id);
-        Ok(())
+    /// 配额范围对应的条件
+    fn quota_scope(builder: ConditionBuilder, scope: QuotaScope) -> ConditionBuilder {
+        match scope {
+            QuotaScope::Total => builder,
+            QuotaScope::ContentType(content_type) => {
+                builder.condition_with_param("content_type = ?", content_type.to_string())
+            }
+            QuotaScope::Group(group_id) => builder.group(group_id),
+        }
     }
 
-    /// 更新文本内容（编辑功能）
-    pub fn update_text_content(&self, id: i64, new_text: &str) -> Result<(), rusqlite::Error> {
+    /// 执行字节配额（分组范围时分组设置的配额优先），超出时按 `policy` 删除非置顶/非收藏条目，
+    /// `keep_id`（刚写入的条目）不参与淘汰。返回 (删除数, 图片路径, file_payload)
+    pub fn enforce_byte_quota(
+        &self,
+        scope: QuotaScope,
+        limit_mb: i64,
+        policy: EvictionPolicy,
+        keep_id: Option<i64>,
+    ) -> Result<(i64, Vec<String>, Vec<String>), rusqlite::Error> {
         let conn = self.write_conn.lock();
-        let preview: String = new_text.chars().take(200).collect();
-        let byte_size = new_text.len() as i64;
-        let char_count = new_text.chars().count() as i64;
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(b"text:");
-        hasher.update(new_text.as_bytes());
-        let content_hash = hasher.finalize().to_hex().to_string();
-        let semantic_hash =
-            semantic_hash_from_text(new_text).unwrap_or_else(|| content_hash.clone());
-
-        // 降级为 text 类型，清除 html/rtf/文件字段
-        conn.execute(
-            "UPDATE clipboard_items SET text_content = ?1, preview = ?2, content_hash = ?3, semantic_hash = ?4, \
-             byte_size = ?5, char_count = ?6, content_type = 'text', \
-             html_content = NULL, rtf_content = NULL, image_path = NULL, file_paths = NULL, file_payload = NULL WHERE id = ?7",
-            params![new_text, preview, content_hash, semantic_hash, byte_size, char_count, id],
+        let limit_mb = match scope {
+            QuotaScope::Group(group_id) => {
+                Self::group_limit(&conn, group_id, GroupLimit::QuotaMb, limit_mb)?
+            }
+            _ => limit_mb,
+        };
+        if limit_mb <= 0 {
+            return Ok((0, vec![], vec![]));
+        }
+        let limit = limit_mb.saturating_mul(1024 * 1024);
+
+        let usage_cb = Self::quota_scope(ConditionBuilder::new(), scope);
+        let usage: i64 = conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM(content_bytes + {ITEM_MEDIA_BYTES_SQL}), 0) \
+                 FROM clipboard_items{}",
+                usage_cb.where_clause()
+            ),
+            usage_cb.param_refs().as_slice(),
+            |row| row.get(0),
         )?;
-        debug!("Updated text content for item {}", id);
-        Ok(())
+        if usage <= limit {
+            return Ok((0, vec![], vec![]));
+        }
+
+        let mut candidate_cb = Self::quota_scope(ConditionBuilder::new(), scope).clearable();
+        if let Some(keep_id) = keep_id {
+            candidate_cb = candidate_cb.condition_with_param("id != ?", keep_id);
+        }
+        let sql = format!(
+            "SELECT id, content_bytes + {ITEM_MEDIA_BYTES_SQL} AS bytes, image_path, file_payload \
+             FROM clipboard_items{} {}",
+            candidate_cb.where_clause(),
+            policy.order_by()
+        );
+        let mut ids = Vec::new();
+        let mut image_paths = Vec::new();
+        let mut file_payloads = Vec::new();
+        {
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query(candidate_cb.param_refs().as_slice())?;
+            let mut excess = usage - limit;
+            while excess > 0 {
+                let Some(row) = rows.next()? else {
+                    break;
+                };
+                ids.push(row.get::<_, i64>(0)?);
+                excess -= row.get::<_, i64>(1)?;
+                if let Some(path) = row.get::<_, Option<String>>(2)? {
+                    image_paths.push(path);
+                }
+                if let Some(payload) = row.get::<_, Option<String>>(3)? {
+                    file_payloads.push(payload);
+                }
+            }
+        }
+        if ids.is_empty() {
+            return Ok((0, vec![], vec![]));
+        }
+
+        let deleted = Self::delete_ids(&conn, &ids)?;
+        debug!(
+            "Enforced byte quota ({:?}, {} MB, {:?}): deleted {} items",
+            scope, limit_mb, policy, deleted
+        );
+        Ok((deleted, image_paths, file_payloads))
+    }
+
+    /// 按内容类型与分组统计条目占用（数据库内容 + 引用的媒体文件）
+    pub fn storage_usage(&self) -> Result<StorageUsage, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut group_names: std::collections::HashMap<i64, String> =
+            std::collections::HashMap::new();
+        {
+            let mut stmt = conn.prepare("SELECT id, name FROM groups")?;
+            for row in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+                let (id, name) = row?;
+                group_names.insert(id, name);
+            }
+        }
+
+        let sql = format!(
+            "SELECT content_type, group_id, COUNT(*), \
+             COALESCE(SUM(content_bytes), 0), COALESCE(SUM({ITEM_MEDIA_BYTES_SQL}), 0) \
+             FROM clipboard_items GROUP BY content_type, group_id"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut usage = StorageUsage::default();
+        for row in rows {
+            let (content_type, group_id, count, content_bytes, media_bytes) = row?;
+            let bytes = content_bytes + media_bytes;
+            usage.content_bytes += content_bytes;
+            usage.media_bytes += media_bytes;
+            usage.total_bytes += bytes;
+
+            match usage
+                .by_type
+                .iter_mut()
+                .find(|u| u.content_type == content_type)
+            {
+                Some(entry) => {
+                    entry.count += count;
+                    entry.bytes += bytes;
+                }
+                None => usage.by_type.push(TypeUsage {
+                    content_type,
+                    count,
+                    bytes,
+                }),
+            }
+            match usage.by_group.iter_mut().find(|u| u.group_id == group_id) {
+                Some(entry) => {
+                    entry.count += count;
+                    entry.bytes += bytes;
+                }
+                None => usage.by_group.push(GroupUsage {
+                    group_id,
+                    name: group_id.and_then(|id| group_names.get(&id).cloned()),
+                    count,
+                    bytes,
+                }),
+            }
+        }
+        usage.by_type.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+        usage.by_group.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+        Ok(usage)
+    }
+
+    /// 去重置顶时刷新 HTML/RTF 字段（Word 等同内容重拷会更新 base64 RTF）
+    pub fn refresh_rich_fields(
+        &self,
+        id: i64,
+        item: &NewClipboardItem,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE clipboard_items SET text_content = ?1, html_content = ?2, rtf_content = ?3, \
+             byte_size = ?4, preview = ?5, char_count = ?6, \
+             updated_at = datetime('now', 'localtime') WHERE id = ?7",
+            params![
+                item.text_content,
+                item.html_content,
+                item.rtf_content,
+                item.byte_size,
+                item.preview,
+                item.char_count,
+                id,
+            ],
+        )?;
+        debug!("Refreshed rich fields for item {}", id);
+        Ok(())
+    }
+
+    /// 用收集模式的合并结果覆盖条目内容并置顶，条目不存在时返回 false
+    pub fn replace_collected_content(
+        &self,
+        id: i64,
+        item: &NewClipboardItem,
+    ) -> Result<bool, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let file_paths_json = item
+            .file_paths
+            .as_ref()
+            .map(|p| serde_json::to_string(p).unwrap_or_default());
+        // 文件副本由后台 staging 补写，保留已 staging 的早期片段文件
+        let mut file_payload = item.file_payload.clone();
+        if let Some(paths) = item.file_paths.as_ref()
+            && let Some(mut payload) = decode_payload(file_payload.as_deref())
+        {
+            let current: Option<Option<String>> = conn
+                .query_row(
+                    "SELECT file_payload FROM clipboard_items WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let previous = decode_payload(current.flatten().as_deref()).unwrap_or_default();
+            for staged in previous.staged {
+                if paths.contains(&staged.original)
+                    && !payload.staged.iter().any(|s| s.original == staged.original)
+                {
+                    payload.staged.push(staged);
+                }
+            }
+            file_payload = Some(encode_payload(&payload));
+        }
+        let affected = conn.execute(
+            "UPDATE clipboard_items SET content_type = ?1, text_content = ?2, html_content = ?3, \
+             rtf_content = NULL, file_paths = ?4, file_payload = ?5, content_hash = ?6, \
+             semantic_hash = ?7, preview = ?8, byte_size = ?9, char_count = ?10, subtypes = ?11, \
+             sort_order = CASE WHEN is_pinned = 0 \
+                 THEN (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM clipboard_items) \
+                 ELSE sort_order END, \
+             updated_at = datetime('now', 'localtime') WHERE id = ?12",
+            params![
+                item.content_type.as_str(),
+                item.text_content,
+                item.html_content,
+                file_paths_json,
+                file_payload,
+                item.content_hash,
+                item.semantic_hash,
+                item.preview,
+                item.byte_size,
+                item.char_count,
+                item.subtypes,
+                id,
+            ],
+        )?;
+        Self::sync_blob_refs(&conn, id)?;
+        debug!("Replaced collected content for item {}", id);
+        Ok(affected > 0)
+    }
+
+    /// 更新文本内容（编辑功能），旧内容存为历史版本
+    ///
+    /// `revision_limit` 为单个条目保留的历史版本数，0 = 不保留
+    pub fn update_text_content(
+        &self,
+        id: i64,
+        new_text: &str,
+        revision_limit: usize,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let tx = conn.unchecked_transaction()?;
+        Self::record_revision(&tx, id, new_text, None, revision_limit)?;
+        // 降级为 text 类型，清除 html/rtf/文件字段
+        Self::write_text_version(&tx, id, ContentType::Text, new_text, None)?;
+        tx.commit()?;
+        debug!("Updated text content for item {}", id);
+        Ok(())
+    }
+
+    /// 将当前内容存为历史版本（内容未变化或条目无文本时跳过），并按上限裁剪旧版本
+    fn record_revision(
+        tx: &Transaction<'_>,
+        id: i64,
+        new_text: &str,
+        new_html: Option<&str>,
+        revision_limit: usize,
+    ) -> Result<(), rusqlite::Error> {
+        if revision_limit == 0 {
+            return Ok(());
+        }
+        let current: Option<(String, Option<String>, Option<String>)> = tx
+            .query_row(
+                "SELECT content_type, text_content, html_content FROM clipboard_items WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let Some((content_type, Some(old_text), old_html)) = current else {
+            return Ok(());
+        };
+        if old_text == new_text && old_html.as_deref() == new_html {
+            return Ok(());
+        }
+        tx.execute(
+            "INSERT INTO item_revisions (item_id, content_type, text_content, html_content, diff_summary)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id,
+                content_type,
+                old_text,
+                old_html,
+                crate::clipboard::text_diff::diff_summary(&old_text, new_text),
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM item_revisions WHERE item_id = ?1 AND id NOT IN (
+                 SELECT id FROM item_revisions WHERE item_id = ?1 ORDER BY id DESC LIMIT ?2)",
+            params![id, revision_limit as i64],
+        )?;
+        Ok(())
+    }
+
+    /// 写入文本版本（同时重算哈希、预览与子类型），清除 rtf/图片/文件字段
+    fn write_text_version(
+        tx: &Transaction<'_>,
+        id: i64,
+        content_type: ContentType,
+        text: &str,
+        html: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        let preview: String = text.chars().take(200).collect();
+        let byte_size = html.map_or(text.len(), str::len) as i64;
+        let char_count = text.chars().count() as i64;
+        let mut hasher = blake3::Hasher::new();
+        match html {
+            Some(html) => {
+                hasher.update(b"html:");
+                hasher.update(html.as_bytes());
+            }
+            None => {
+                hasher.update(b"text:");
+                hasher.update(text.as_bytes());
+            }
+        }
+        let content_hash = hasher.finalize().to_hex().to_string();
+        let semantic_hash = semantic_hash_from_text(text).unwrap_or_else(|| content_hash.clone());
+        let subtypes = classify_text(text);
+
+        tx.execute(
+            "UPDATE clipboard_items SET text_content = ?1, preview = ?2, content_hash = ?3, semantic_hash = ?4, \
+             byte_size = ?5, char_count = ?6, content_type = ?7, subtypes = ?8, html_content = ?9, \
+             rtf_content = NULL, image_path = NULL, file_paths = NULL, file_payload = NULL WHERE id = ?10",
+            params![
+                text,
+                preview,
+                content_hash,
+                semantic_hash,
+                byte_size,
+                char_count,
+                content_type.as_str(),
+                subtypes,
+                html,
+                id
+            ],
+        )?;
+        Self::sync_blob_refs(tx, id)?;
+        Ok(())
+    }
+
+    /// 列出条目的历史版本（新→旧），不含完整文本
+    pub fn list_revisions(&self, item_id: i64) -> Result<Vec<ItemRevision>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, item_id, content_type, substr(text_content, 1, 200), length(text_content), \
+             diff_summary, created_at FROM item_revisions WHERE item_id = ?1 ORDER BY id DESC",
+        )?;
+        stmt.query_map([item_id], |row| {
+            Ok(ItemRevision {
+                id: row.get(0)?,
+                item_id: row.get(1)?,
+                content_type: row.get(2)?,
+                preview: row.get(3)?,
+                char_count: row.get(4)?,
+                diff_summary: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect()
+    }
+
+    /// 获取历史版本的完整内容
+    pub fn get_revision_content(
+        &self,
+        revision_id: i64,
+    ) -> Result<Option<RevisionContent>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        Self::query_revision_content(&conn, revision_id)
+    }
+
+    fn query_revision_content(
+        conn: &Connection,
+        revision_id: i64,
+    ) -> Result<Option<RevisionContent>, rusqlite::Error> {
+        conn.query_row(
+            "SELECT item_id, content_type, text_content, html_content FROM item_revisions WHERE id = ?1",
+            [revision_id],
+            |row| {
+                Ok(RevisionContent {
+                    item_id: row.get(0)?,
+                    content_type: row.get(1)?,
+                    text_content: row.get(2)?,
+                    html_content: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// 恢复历史版本（当前内容先存为新版本），返回条目 ID；版本不存在时返回 None
+    pub fn restore_revision(
+        &self,
+        revision_id: i64,
+        revision_limit: usize,
+    ) -> Result<Option<i64>, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let tx = conn.unchecked_transaction()?;
+        let Some(RevisionContent {
+            item_id,
+            content_type,
+            text_content: text,
+            html_content: html,
+        }) = Self::query_revision_content(&tx, revision_id)?
+        else {
+            return Ok(None);
+        };
+        // 仅恢复文本与 HTML；RTF 等其他格式不随版本保存，按纯文本恢复
+        let (content_type, html) = match (content_type.as_str(), html) {
+            ("html", Some(html)) => (ContentType::Html, Some(html)),
+            ("url", _) => (ContentType::Url, None),
+            _ => (ContentType::Text, None),
+        };
+        // 裁剪会保留最新的 revision_limit 个版本，被恢复的版本可能随之移除
+        Self::record_revision(&tx, item_id, &text, html.as_deref(), revision_limit)?;
+        Self::write_text_version(&tx, item_id, content_type, &text, html.as_deref())?;
+        tx.commit()?;
+        debug!("Restored revision {} for item {}", revision_id, item_id);
+        Ok(Some(item_id))
+    }
+
+    /// 写入图片 OCR 结果（空串表示已识别但无文字，避免重复回填）
+    pub fn update_ocr_text(&self, id: i64, text: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE clipboard_items SET ocr_text = ?1 WHERE id = ?2 AND content_type = 'image'",
+            params![text, id],
+        )?;
+        debug!(
+            "Updated OCR text for item {} ({} chars)",
+            id,
+            text.chars().count()
+        );
+        Ok(())
+    }
+
+    /// 将条目标记为 `source_id` 的派生条目（如译文）
+    ///
+    /// 已派生自其它条目的不会被改写（去重命中了同文本的其它译文），返回是否已关联。
+    pub fn link_derived(
+        &self,
+        id: i64,
+        source_id: i64,
+        derivation: &str,
+    ) -> Result<bool, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let changed = conn.execute(
+            "UPDATE clipboard_items SET derived_from = ?2, derivation = ?3 \
+             WHERE id = ?1 AND id != ?2 AND (derived_from IS NULL OR derived_from = ?2)",
+            params![id, source_id, derivation],
+        )?;
+        debug!("Linked item {} as derived from {}", id, source_id);
+        Ok(changed > 0)
+    }
+
+    /// 源条目的派生条目，新到旧排列
+    pub fn list_derived(&self, source_id: i64) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM clipboard_items WHERE derived_from = ?1 ORDER BY sort_order DESC",
+        )?;
+        stmt.query_map(params![source_id], Self::row_to_item)?
+            .collect()
+    }
+
+    /// 获取尚未 OCR 的图片条目 (id, image_path)，按新到旧排列，供回填使用
+    pub fn list_images_missing_ocr(&self) -> Result<Vec<(i64, String)>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, image_path FROM clipboard_items \
+             WHERE content_type = 'image' AND image_path IS NOT NULL AND ocr_text IS NULL \
+             ORDER BY created_at DESC, id DESC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(std::result::Result::ok)
+            .collect();
+        Ok(rows)
     }
 
     /// 将条目移到非置顶区最顶部（粘贴后置顶功能）。
@@ -1115,7 +2108,7 @@ impl ClipboardRepository {
         Ok(())
     }
 
-    fn row_to_item(row: &Row) -> Result<ClipboardItem, rusqlite::Error> {
+    pub(crate) fn row_to_item(row: &Row) -> Result<ClipboardItem, rusqlite::Error> {
         Ok(ClipboardItem {
             id: row.get("id")?,
             content_type: row.get("content_type")?,
@@ -1143,6 +2136,14 @@ impl ClipboardRepository {
             source_app_name: row.get("source_app_name")?,
             source_app_icon: row.get("source_app_icon")?,
             group_id: row.get("group_id")?,
+            ocr_text: row.get("ocr_text")?,
+            subtypes: row.get("subtypes")?,
+            expires_at: row.get("expires_at")?,
+            derived_from: row.get("derived_from")?,
+            derivation: row.get("derivation")?,
+            staging_state: row.get("staging_state")?,
+            media_missing: row.get("media_missing")?,
+            tags: None,        // 按需由 TagRepository 附加
             files_valid: None, // 查询时计算
         })
     }
@@ -1182,8 +2183,11 @@ impl ClipboardRepository {
         }
 
         let conn = self.read_conn.lock();
+        // 关闭同步的分组不参与同步
         let sql = format!(
-            "SELECT * FROM clipboard_items WHERE {} ORDER BY created_at DESC",
+            "SELECT * FROM clipboard_items WHERE ({}) \
+             AND (group_id IS NULL OR group_id NOT IN (SELECT id FROM groups WHERE sync_enabled = 0)) \
+             ORDER BY created_at DESC",
             clauses.join(" OR ")
         );
         let mut stmt = conn.prepare(&sql)?;
@@ -1199,7 +2203,8 @@ impl ClipboardRepository {
         let conn = self.read_conn.lock();
         let mut stmt = conn.prepare(
             "SELECT * FROM clipboard_items \
-             WHERE content_type IN ('image','files') OR source_app_icon IS NOT NULL",
+             WHERE content_type IN ('image','files') OR image_path IS NOT NULL \
+                 OR source_app_icon IS NOT NULL",
         )?;
         let items = stmt
             .query_map([], Self::row_to_item)?
@@ -1222,29 +2227,197 @@ impl ClipboardRepository {
              WHERE id = ?4",
             params![image_path, file_payload, source_app_icon, id],
         )?;
+        Self::sync_blob_refs(&conn, id)?;
+        Ok(())
+    }
+
+    /// 标记或清除条目的媒体文件缺失状态
+    pub fn set_media_missing(&self, id: i64, missing: bool) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE clipboard_items SET media_missing = ?1 WHERE id = ?2",
+            params![missing, id],
+        )?;
+        Ok(())
+    }
+
+    /// 按条目当前的媒体路径重建 `item_blobs` 引用（存储检查修复用）
+    pub fn resync_blob_refs(&self, id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        Self::sync_blob_refs(&conn, id)
+    }
+
+    /// 设置文件 staging 状态
+    pub fn set_staging_state(&self, id: i64, state: Option<&str>) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE clipboard_items SET staging_state = ?1 WHERE id = ?2",
+            params![state, id],
+        )?;
+        Ok(())
+    }
+
+    /// 把后台 staging 结果合并进 `file_payload` 并更新状态；
+    /// 条目已删除或已不是文件条目（如被编辑为文本）时返回 false
+    pub fn apply_staged_files(
+        &self,
+        id: i64,
+        staged: &[StagedFile],
+        state: &str,
+    ) -> Result<bool, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let tx = conn.unchecked_transaction()?;
+        let current: Option<Option<String>> = tx
+            .query_row(
+                "SELECT file_payload FROM clipboard_items WHERE id = ?1 AND content_type = 'files'",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(current) = current else {
+            return Ok(false);
+        };
+        let mut payload = decode_payload(current.as_deref()).unwrap_or_default();
+        for file in staged {
+            match payload
+                .staged
+                .iter_mut()
+                .find(|existing| existing.original == file.original)
+            {
+                Some(existing) => *existing = file.clone(),
+                None => payload.staged.push(file.clone()),
+            }
+        }
+        tx.execute(
+            "UPDATE clipboard_items SET file_payload = ?1, staging_state = ?2 WHERE id = ?3",
+            params![encode_payload(&payload), state, id],
+        )?;
+        Self::sync_blob_refs(&tx, id)?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// 仍处于 `pending` 状态的文件条目（上次退出时未完成 staging）：(id, 文件路径)
+    pub fn list_pending_staging(&self) -> Result<Vec<(i64, Vec<String>)>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, file_paths FROM clipboard_items \
+             WHERE staging_state = 'pending' AND content_type = 'files' ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let paths: Option<String> = row.get(1)?;
+            Ok((row.get(0)?, parse_file_paths(paths.as_deref())))
+        })?;
+        rows.collect()
+    }
+
+    /// 按条目当前的媒体路径同步 blob 引用（`item_blobs`），引用计数由触发器维护
+    fn sync_blob_refs(conn: &Connection, id: i64) -> Result<(), rusqlite::Error> {
+        let row: Option<(Option<String>, Option<String>, Option<String>)> = conn
+            .query_row(
+                "SELECT image_path, source_app_icon, file_payload FROM clipboard_items WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let Some((image_path, icon, payload)) = row else {
+            return Ok(());
+        };
+        let mut refs: Vec<(String, &str, String)> = Vec::new();
+        let staged = staged_paths_from_payload(payload.as_deref());
+        let paths = image_path
+            .map(|p| (p, "image"))
+            .into_iter()
+            .chain(icon.map(|p| (p, "icon")))
+            .chain(staged.into_iter().map(|p| (p, "file")));
+        for (path, kind) in paths {
+            if let Some(hash) = crate::blobs::hash_from_path(&path)
+                && !refs.iter().any(|(h, _, _)| h == hash)
+            {
+                let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+                refs.push((hash.to_string(), kind, name.to_string()));
+            }
+        }
+
+        let existing: Vec<String> = {
+            let mut stmt = conn.prepare_cached("SELECT hash FROM item_blobs WHERE item_id = ?1")?;
+            stmt.query_map(params![id], |row| row.get(0))?
+                .collect::<Result<_, _>>()?
+        };
+        for hash in existing
+            .iter()
+            .filter(|h| !refs.iter().any(|(r, _, _)| r == *h))
+        {
+            conn.execute(
+                "DELETE FROM item_blobs WHERE item_id = ?1 AND hash = ?2",
+                params![id, hash],
+            )?;
+        }
+        for (hash, kind, name) in refs.iter().filter(|(h, _, _)| !existing.contains(h)) {
+            // 同步下载等途径写入的文件可能尚未登记，补一条占位记录
+            conn.execute(
+                "INSERT OR IGNORE INTO blobs (hash, kind, name) VALUES (?1, ?2, ?3)",
+                params![hash, kind, name],
+            )?;
+            conn.execute(
+                "INSERT OR IGNORE INTO blob_names (hash, name) VALUES (?1, ?2)",
+                params![hash, name],
+            )?;
+            conn.execute(
+                "INSERT INTO item_blobs (item_id, hash) VALUES (?1, ?2)",
+                params![id, hash],
+            )?;
+        }
         Ok(())
     }
 
     /// 导入同步条目（基于 content_hash 去重，已存在则跳过）
     pub fn import_sync_items(&self, items: &[ClipboardItem]) -> Result<usize, rusqlite::Error> {
+        self.import_items(items, false)
+    }
+
+    /// 合并导入备份条目（content_hash 或 semantic_hash 已存在则只合并标签），返回新增数
+    pub fn merge_items(&self, items: &[ClipboardItem]) -> Result<usize, rusqlite::Error> {
+        self.import_items(items, true)
+    }
+
+    /// 按 content_hash（`match_semantic` 时也按 semantic_hash）去重后插入条目，保留元数据并合并标签
+    fn import_items(
+        &self,
+        items: &[ClipboardItem],
+        match_semantic: bool,
+    ) -> Result<usize, rusqlite::Error> {
         let mut conn = self.write_conn.lock();
         let mut count = 0usize;
 
         let tx = conn.transaction()?;
         {
-            let mut exists_stmt =
-                tx.prepare_cached("SELECT 1 FROM clipboard_items WHERE content_hash = ?1 LIMIT 1")?;
+            let mut existing_stmt = tx.prepare_cached(if match_semantic {
+                "SELECT id FROM clipboard_items WHERE content_hash = ?1 OR semantic_hash = ?2 LIMIT 1"
+            } else {
+                "SELECT id FROM clipboard_items WHERE content_hash = ?1 LIMIT 1"
+            })?;
             let mut insert_stmt = tx.prepare_cached(
                 "INSERT INTO clipboard_items
                  (content_type, text_content, html_content, rtf_content, image_path, file_paths, file_payload,
                   content_hash, semantic_hash, preview, byte_size, image_width, image_height,
                   is_pinned, is_favorite, sort_order, created_at, updated_at,
-                  access_count, last_accessed_at, char_count, source_app_name, source_app_icon, group_id)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)"
+                  access_count, last_accessed_at, char_count, source_app_name, source_app_icon, group_id, ocr_text, subtypes,
+                  content_bytes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)"
             )?;
             for item in items {
-                let exists = exists_stmt.exists(params![item.content_hash])?;
-                if exists {
+                let existing: Option<i64> = if match_semantic {
+                    existing_stmt.query_row(params![item.content_hash, item.semantic_hash], |row| {
+                        row.get(0)
+                    })
+                } else {
+                    existing_stmt.query_row(params![item.content_hash], |row| row.get(0))
+                }
+                .optional()?;
+                if let Some(id) = existing {
+                    // 已存在的条目只合并标签
+                    TagRepository::link_names(&tx, id, item.tags.as_deref().unwrap_or_default())?;
                     continue;
                 }
                 insert_stmt.execute(params![
@@ -1272,7 +2445,19 @@ impl ClipboardRepository {
                     item.source_app_name,
                     item.source_app_icon,
                     item.group_id,
+                    item.ocr_text,
+                    item.subtypes,
+                    content_bytes(&[
+                        item.text_content.as_deref(),
+                        item.html_content.as_deref(),
+                        item.rtf_content.as_deref(),
+                        item.ocr_text.as_deref(),
+                        item.file_payload.as_deref(),
+                    ]),
                 ])?;
+                let id = tx.last_insert_rowid();
+                Self::sync_blob_refs(&tx, id)?;
+                TagRepository::link_names(&tx, id, item.tags.as_deref().unwrap_or_default())?;
                 count += 1;
             }
 
@@ -1482,28 +2667,39 @@ impl GroupRepository {
         }
     }
 
+    fn row_to_group(row: &Row) -> Result<Group, rusqlite::Error> {
+        Ok(Group {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            color: row.get(2)?,
+            sort_order: row.get(3)?,
+            created_at: row.get(4)?,
+            item_count: row.get(5)?,
+            parent_id: row.get(6)?,
+            settings: GroupSettings {
+                retention_days: row.get(7)?,
+                max_count: row.get(8)?,
+                dedup_strategy: row.get(9)?,
+                sync_enabled: row.get(10)?,
+                quota_mb: row.get(11)?,
+            },
+        })
+    }
+
     /// 列出所有分组（含每个分组的条目数）
     pub fn list_with_count(&self) -> Result<Vec<Group>, rusqlite::Error> {
         let conn = self.read_conn.lock();
         let mut stmt = conn.prepare(
             "SELECT g.id, g.name, g.color, g.sort_order, g.created_at, \
-             COUNT(ci.id) AS item_count \
+             COUNT(ci.id) AS item_count, g.parent_id, g.retention_days, g.max_count, \
+             g.dedup_strategy, g.sync_enabled, g.quota_mb \
              FROM groups g \
              LEFT JOIN clipboard_items ci ON ci.group_id = g.id \
              GROUP BY g.id \
              ORDER BY g.sort_order ASC, g.created_at ASC",
         )?;
         let groups = stmt
-            .query_map([], |row| {
-                Ok(Group {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    color: row.get(2)?,
-                    sort_order: row.get(3)?,
-                    created_at: row.get(4)?,
-                    item_count: row.get(5)?,
-                })
-            })?
+            .query_map([], Self::row_to_group)?
             .filter_map(std::result::Result::ok)
             .collect();
         Ok(groups)
@@ -1511,35 +2707,69 @@ impl GroupRepository {
 
     /// 创建新分组，返回完整分组对象
     pub fn create(&self, name: &str, color: Option<&str>) -> Result<Group, rusqlite::Error> {
+        self.create_in(name, color, None)
+    }
+
+    /// 在父分组下创建分组（None = 顶层）；父分组与分组在同一事务中写入，不会留下孤立的顶层分组
+    pub fn create_in(
+        &self,
+        name: &str,
+        color: Option<&str>,
+        parent_id: Option<i64>,
+    ) -> Result<Group, rusqlite::Error> {
         let conn = self.write_conn.lock();
-        let max_sort: i64 = conn
+        let tx = conn.unchecked_transaction()?;
+        let max_sort: i64 = tx
             .query_row(
                 "SELECT COALESCE(MAX(sort_order), -1) FROM groups",
                 [],
                 |row| row.get(0),
             )
             .unwrap_or(-1);
-        conn.execute(
-            "INSERT INTO groups (name, color, sort_order) VALUES (?1, ?2, ?3)",
-            params![name, color, max_sort + 1],
+        tx.execute(
+            "INSERT INTO groups (name, color, sort_order, parent_id) VALUES (?1, ?2, ?3, ?4)",
+            params![name, color, max_sort + 1, parent_id],
         )?;
-        let id = conn.last_insert_rowid();
-        let group = conn.query_row(
-            "SELECT g.id, g.name, g.color, g.sort_order, g.created_at, 0 AS item_count FROM groups g WHERE g.id = ?1",
+        let id = tx.last_insert_rowid();
+        let group = tx.query_row(
+            "SELECT g.id, g.name, g.color, g.sort_order, g.created_at, 0 AS item_count, \
+             g.parent_id, g.retention_days, g.max_count, g.dedup_strategy, g.sync_enabled, g.quota_mb \
+             FROM groups g WHERE g.id = ?1",
             params![id],
-            |row| Ok(Group {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                color: row.get(2)?,
-                sort_order: row.get(3)?,
-                created_at: row.get(4)?,
-                item_count: row.get(5)?,
-            }),
+            Self::row_to_group,
         )?;
-        debug!("Created group: id={}, name={}", id, name);
+        tx.commit()?;
+        debug!(
+            "Created group: id={}, name={}, parent={:?}",
+            id, name, parent_id
+        );
         Ok(group)
     }
 
+    /// 按名称查找分组 ID（名称唯一）
+    pub fn find_id_by_name(&self, name: &str) -> Result<Option<i64>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        match conn.query_row(
+            "SELECT id FROM groups WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        ) {
+            Ok(id) => Ok(Some(id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 分组是否存在
+    pub fn exists(&self, id: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "SELECT COUNT(*) > 0 FROM groups WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+    }
+
     /// 重命名分组
     pub fn rename(&self, id: i64, name: &str) -> Result<(), rusqlite::Error> {
         let conn = self.write_conn.lock();
@@ -1562,15 +2792,97 @@ impl GroupRepository {
         Ok(())
     }
 
-    /// 删除分组（ON DELETE CASCADE 自动删除该分组的所有 clipboard_items）
-    pub fn delete(&self, id: i64) -> Result<(), rusqlite::Error> {
+    /// 删除分组，条目移至 `move_items_to`（None = 默认分组）；子分组上移一级。
+    /// 移至其他分组时，指向该分组的捕获规则随之改指目标分组，否则级联删除
+    pub fn delete(&self, id: i64, move_items_to: Option<i64>) -> Result<(), rusqlite::Error> {
         let conn = self.write_conn.lock();
-        conn.execute("DELETE FROM groups WHERE id = ?1", params![id])?;
-        debug!("Deleted group {}", id);
+        let tx = conn.unchecked_transaction()?;
+        let moved = tx.execute(
+            "UPDATE clipboard_items SET group_id = ?1 WHERE group_id = ?2",
+            params![move_items_to, id],
+        )?;
+        tx.execute(
+            "UPDATE groups SET parent_id = (SELECT parent_id FROM groups WHERE id = ?1) \
+             WHERE parent_id = ?1",
+            params![id],
+        )?;
+        if let Some(target) = move_items_to {
+            tx.execute(
+                "UPDATE capture_rules SET target_group_id = ?1 WHERE target_group_id = ?2",
+                params![target, id],
+            )?;
+        }
+        tx.execute("DELETE FROM groups WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        debug!(
+            "Deleted group {} (moved {} items to {:?})",
+            id, moved, move_items_to
+        );
+        Ok(())
+    }
+
+    /// 设置父分组（None = 顶层）
+    pub fn set_parent(&self, id: i64, parent_id: Option<i64>) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE groups SET parent_id = ?1 WHERE id = ?2",
+            params![parent_id, id],
+        )?;
+        debug!("Moved group {} under {:?}", id, parent_id);
+        Ok(())
+    }
+
+    /// `candidate` 是否为 `id` 自身或其子孙分组（用于阻止循环嵌套）
+    pub fn is_self_or_descendant(&self, id: i64, candidate: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "WITH RECURSIVE ancestors(id) AS ( \
+                 SELECT ?1 \
+                 UNION SELECT g.parent_id FROM groups g JOIN ancestors a ON g.id = a.id \
+                 WHERE g.parent_id IS NOT NULL \
+             ) SELECT COUNT(*) > 0 FROM ancestors WHERE id = ?2",
+            params![candidate, id],
+            |row| row.get(0),
+        )
+    }
+
+    /// 更新分组级设置
+    pub fn update_settings(
+        &self,
+        id: i64,
+        settings: &GroupSettings,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE groups SET retention_days = ?1, max_count = ?2, dedup_strategy = ?3, \
+             sync_enabled = ?4, quota_mb = ?5 WHERE id = ?6",
+            params![
+                settings.retention_days,
+                settings.max_count,
+                settings.dedup_strategy,
+                settings.sync_enabled,
+                settings.quota_mb,
+                id
+            ],
+        )?;
+        debug!("Updated settings of group {}: {:?}", id, settings);
         Ok(())
     }
 
-    /// 删除所有自定义分组（及其关联条目通过 ON DELETE CASCADE 一并删除）
+    /// 分组的去重策略覆盖（未设置时返回 None）
+    pub fn dedup_strategy(&self, id: i64) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "SELECT dedup_strategy FROM groups WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map(Option::flatten)
+    }
+
+    /// 删除所有自定义分组，用于重置数据（调用前条目已清空）。
+    /// 与 `delete` 不同，这里不迁移条目：残留条目会被外键级联删除，需保留条目时应逐个调用 `delete`
     pub fn delete_all(&self) -> Result<(), rusqlite::Error> {
         let conn = self.write_conn.lock();
         conn.execute("DELETE FROM groups", [])?;
@@ -1594,6 +2906,594 @@ impl GroupRepository {
     }
 }
 
+/// 标签仓库（条目与标签多对多，与单一分组归属相互独立）
+pub struct TagRepository {
+    write_conn: Arc<Mutex<Connection>>,
+    read_conn: Arc<Mutex<Connection>>,
+}
+
+impl TagRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            write_conn: db.write_connection(),
+            read_conn: db.read_connection(),
+        }
+    }
+
+    fn row_to_tag(row: &Row) -> Result<Tag, rusqlite::Error> {
+        Ok(Tag {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            color: row.get(2)?,
+            created_at: row.get(3)?,
+            item_count: row.get(4)?,
+        })
+    }
+
+    /// 列出所有标签（含每个标签的条目数）
+    pub fn list_with_count(&self) -> Result<Vec<Tag>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, t.color, t.created_at, COUNT(it.item_id) AS item_count \
+             FROM tags t \
+             LEFT JOIN item_tags it ON it.tag_id = t.id \
+             GROUP BY t.id \
+             ORDER BY t.name COLLATE NOCASE ASC",
+        )?;
+        let tags = stmt
+            .query_map([], Self::row_to_tag)?
+            .filter_map(std::result::Result::ok)
+            .collect();
+        Ok(tags)
+    }
+
+    /// 创建新标签，返回完整标签对象
+    pub fn create(&self, name: &str, color: Option<&str>) -> Result<Tag, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "INSERT INTO tags (name, color) VALUES (?1, ?2)",
+            params![name, color],
+        )?;
+        let id = conn.last_insert_rowid();
+        let tag = conn.query_row(
+            "SELECT id, name, color, created_at, 0 AS item_count FROM tags WHERE id = ?1",
+            params![id],
+            Self::row_to_tag,
+        )?;
+        debug!("Created tag: id={}, name={}", id, name);
+        Ok(tag)
+    }
+
+    /// 重命名标签
+    pub fn rename(&self, id: i64, name: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![name, id])?;
+        debug!("Renamed tag {} to {}", id, name);
+        Ok(())
+    }
+
+    /// 更新标签颜色
+    pub fn update_color(&self, id: i64, color: Option<&str>) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE tags SET color = ?1 WHERE id = ?2",
+            params![color, id],
+        )?;
+        debug!("Updated color of tag {} to {:?}", id, color);
+        Ok(())
+    }
+
+    /// 删除标签（仅解除关联，不删除条目）
+    pub fn delete(&self, id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+        debug!("Deleted tag {}", id);
+        Ok(())
+    }
+
+    /// 批量为条目添加标签，返回新增的关联数（已存在的关联与无效 ID 忽略）
+    pub fn tag_items(&self, item_ids: &[i64], tag_ids: &[i64]) -> Result<usize, rusqlite::Error> {
+        let mut conn = self.write_conn.lock();
+        let tx = conn.transaction()?;
+        let mut added = 0;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO item_tags (item_id, tag_id) \
+                 SELECT ?1, ?2 \
+                 WHERE EXISTS (SELECT 1 FROM clipboard_items WHERE id = ?1) \
+                   AND EXISTS (SELECT 1 FROM tags WHERE id = ?2)",
+            )?;
+            for &item_id in item_ids {
+                for &tag_id in tag_ids {
+                    added += stmt.execute(params![item_id, tag_id])?;
+                }
+            }
+        }
+        tx.commit()?;
+        debug!(
+            "Tagged {} items with {:?}: {} new links",
+            item_ids.len(),
+            tag_ids,
+            added
+        );
+        Ok(added)
+    }
+
+    /// 批量移除条目的标签，返回移除的关联数
+    pub fn untag_items(&self, item_ids: &[i64], tag_ids: &[i64]) -> Result<usize, rusqlite::Error> {
+        let mut conn = self.write_conn.lock();
+        let tx = conn.transaction()?;
+        let mut removed = 0;
+        {
+            let mut stmt =
+                tx.prepare_cached("DELETE FROM item_tags WHERE item_id = ?1 AND tag_id = ?2")?;
+            for &item_id in item_ids {
+                for &tag_id in tag_ids {
+                    removed += stmt.execute(params![item_id, tag_id])?;
+                }
+            }
+        }
+        tx.commit()?;
+        debug!(
+            "Untagged {} items from {:?}: {} links removed",
+            item_ids.len(),
+            tag_ids,
+            removed
+        );
+        Ok(removed)
+    }
+
+    /// 为条目附加标签名称（单次查询）
+    pub fn attach_tags(&self, items: &mut [ClipboardItem]) -> Result<(), rusqlite::Error> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let ids = serde_json::to_string(&items.iter().map(|i| i.id).collect::<Vec<_>>())
+            .unwrap_or_else(|_| "[]".to_string());
+        let mut by_item: std::collections::HashMap<i64, Vec<String>> =
+            std::collections::HashMap::new();
+        {
+            let conn = self.read_conn.lock();
+            let mut stmt = conn.prepare_cached(
+                "SELECT it.item_id, t.name FROM item_tags it \
+                 JOIN tags t ON t.id = it.tag_id \
+                 WHERE it.item_id IN (SELECT value FROM json_each(?1)) \
+                 ORDER BY t.name COLLATE NOCASE ASC",
+            )?;
+            let rows = stmt.query_map(params![ids], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for (item_id, name) in rows.filter_map(std::result::Result::ok) {
+                by_item.entry(item_id).or_default().push(name);
+            }
+        }
+        for item in items {
+            item.tags = Some(by_item.remove(&item.id).unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    /// 按名称为条目添加标签（标签不存在时创建），用于捕获钩子追加的标签
+    pub fn tag_item_by_names(&self, item_id: i64, names: &[String]) -> Result<(), rusqlite::Error> {
+        let mut conn = self.write_conn.lock();
+        let tx = conn.transaction()?;
+        Self::link_names(&tx, item_id, names)?;
+        tx.commit()?;
+        debug!("Tagged item {} by name: {:?}", item_id, names);
+        Ok(())
+    }
+
+    /// 按名称为条目关联标签（标签不存在时创建），用于同步导入
+    fn link_names(
+        tx: &Transaction<'_>,
+        item_id: i64,
+        names: &[String],
+    ) -> Result<(), rusqlite::Error> {
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            tx.execute(
+                "INSERT OR IGNORE INTO tags (name) VALUES (?1)",
+                params![name],
+            )?;
+            tx.execute(
+                "INSERT OR IGNORE INTO item_tags (item_id, tag_id) \
+                 SELECT ?1, id FROM tags WHERE name = ?2",
+                params![item_id, name],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 捕获路由规则仓库
+pub struct RuleRepository {
+    write_conn: Arc<Mutex<Connection>>,
+    read_conn: Arc<Mutex<Connection>>,
+}
+
+impl RuleRepository {
+    const COLUMNS: &'static str = "id, name, enabled, sort_order, source_app, content_regex, content_types, \
+         time_start, time_end, target_group_id, pin, ttl_minutes, created_at";
+
+    pub fn new(db: &Database) -> Self {
+        Self {
+            write_conn: db.write_connection(),
+            read_conn: db.read_connection(),
+        }
+    }
+
+    fn row_to_rule(row: &Row) -> Result<CaptureRule, rusqlite::Error> {
+        Ok(CaptureRule {
+            id: row.get("id")?,
+            name: row.get("name")?,
+            enabled: row.get("enabled")?,
+            sort_order: row.get("sort_order")?,
+            source_app: row.get("source_app")?,
+            content_regex: row.get("content_regex")?,
+            content_types: row.get("content_types")?,
+            time_start: row.get("time_start")?,
+            time_end: row.get("time_end")?,
+            target_group_id: row.get("target_group_id")?,
+            pin: row.get("pin")?,
+            ttl_minutes: row.get("ttl_minutes")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+
+    /// 按执行顺序列出规则
+    pub fn list(&self) -> Result<Vec<CaptureRule>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let sql = format!(
+            "SELECT {} FROM capture_rules ORDER BY sort_order ASC, id ASC",
+            Self::COLUMNS
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rules = stmt
+            .query_map([], Self::row_to_rule)?
+            .filter_map(std::result::Result::ok)
+            .collect();
+        Ok(rules)
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<CaptureRule>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let sql = format!("SELECT {} FROM capture_rules WHERE id = ?1", Self::COLUMNS);
+        match conn.query_row(&sql, params![id], Self::row_to_rule) {
+            Ok(rule) => Ok(Some(rule)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 新建规则（追加到末尾），返回完整规则
+    pub fn create(&self, input: &CaptureRuleInput) -> Result<CaptureRule, rusqlite::Error> {
+        let id = {
+            let conn = self.write_conn.lock();
+            let max_sort: i64 = conn
+                .query_row(
+                    "SELECT COALESCE(MAX(sort_order), 0) FROM capture_rules",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0);
+            conn.execute(
+                "INSERT INTO capture_rules (name, enabled, sort_order, source_app, content_regex, content_types, \
+                 time_start, time_end, target_group_id, pin, ttl_minutes) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    input.name,
+                    input.enabled,
+                    max_sort + 1,
+                    input.source_app,
+                    input.content_regex,
+                    input.content_types,
+                    input.time_start,
+                    input.time_end,
+                    input.target_group_id,
+                    input.pin,
+                    input.ttl_minutes,
+                ],
+            )?;
+            conn.last_insert_rowid()
+        };
+        debug!("Created capture rule {} ({})", id, input.name);
+        self.get(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)
+    }
+
+    pub fn update(&self, id: i64, input: &CaptureRuleInput) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "UPDATE capture_rules SET name = ?1, enabled = ?2, source_app = ?3, content_regex = ?4, \
+             content_types = ?5, time_start = ?6, time_end = ?7, target_group_id = ?8, pin = ?9, \
+             ttl_minutes = ?10 WHERE id = ?11",
+            params![
+                input.name,
+                input.enabled,
+                input.source_app,
+                input.content_regex,
+                input.content_types,
+                input.time_start,
+                input.time_end,
+                input.target_group_id,
+                input.pin,
+                input.ttl_minutes,
+                id,
+            ],
+        )?;
+        debug!("Updated capture rule {}", id);
+        Ok(())
+    }
+
+    pub fn delete(&self, id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute("DELETE FROM capture_rules WHERE id = ?1", params![id])?;
+        debug!("Deleted capture rule {}", id);
+        Ok(())
+    }
+
+    /// 按给定 ID 顺序重排规则
+    pub fn reorder(&self, ids: &[i64]) -> Result<(), rusqlite::Error> {
+        let mut conn = self.write_conn.lock();
+        let tx = conn.transaction()?;
+        for (index, id) in ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE capture_rules SET sort_order = ?1 WHERE id = ?2",
+                params![index as i64 + 1, id],
+            )?;
+        }
+        tx.commit()?;
+        debug!("Reordered {} capture rules", ids.len());
+        Ok(())
+    }
+}
+
+/// 翻译缓存：键为 (provider, 源语言, 目标语言, 原文语义哈希)
+pub struct TranslationCacheRepository {
+    write_conn: Arc<Mutex<Connection>>,
+    read_conn: Arc<Mutex<Connection>>,
+}
+
+impl TranslationCacheRepository {
+    /// 缓存条数上限，超出时淘汰最久未使用的记录
+    pub const MAX_ENTRIES: i64 = 5000;
+
+    pub fn new(db: &Database) -> Self {
+        Self {
+            write_conn: db.write_connection(),
+            read_conn: db.read_connection(),
+        }
+    }
+
+    /// 查询缓存译文；命中时刷新最近使用时间
+    pub fn get(
+        &self,
+        provider: &str,
+        from: &str,
+        to: &str,
+        semantic_hash: &str,
+    ) -> Result<Option<String>, rusqlite::Error> {
+        let translated: Option<String> = {
+            let conn = self.read_conn.lock();
+            conn.query_row(
+                "SELECT translated_text FROM translation_cache \
+                 WHERE provider = ?1 AND source_lang = ?2 AND target_lang = ?3 AND semantic_hash = ?4",
+                params![provider, from, to, semantic_hash],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+        if translated.is_some() {
+            let conn = self.write_conn.lock();
+            conn.execute(
+                "UPDATE translation_cache SET last_used_at = datetime('now', 'localtime') \
+                 WHERE provider = ?1 AND source_lang = ?2 AND target_lang = ?3 AND semantic_hash = ?4",
+                params![provider, from, to, semantic_hash],
+            )?;
+        }
+        Ok(translated)
+    }
+
+    /// 写入译文（已存在则覆盖），并按上限淘汰旧记录
+    pub fn put(
+        &self,
+        provider: &str,
+        from: &str,
+        to: &str,
+        semantic_hash: &str,
+        translated: &str,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO translation_cache \
+             (provider, source_lang, target_lang, semantic_hash, translated_text) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![provider, from, to, semantic_hash, translated],
+        )?;
+        let evicted = conn.execute(
+            "DELETE FROM translation_cache WHERE rowid IN ( \
+                 SELECT rowid FROM translation_cache ORDER BY last_used_at DESC, rowid DESC \
+                 LIMIT -1 OFFSET ?1)",
+            params![Self::MAX_ENTRIES],
+        )?;
+        if evicted > 0 {
+            debug!("Evicted {} translation cache entries", evicted);
+        }
+        Ok(())
+    }
+
+    pub fn count(&self) -> Result<i64, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row("SELECT COUNT(*) FROM translation_cache", [], |row| {
+            row.get(0)
+        })
+    }
+
+    /// 清空缓存，返回删除条数
+    pub fn clear(&self) -> Result<usize, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        conn.execute("DELETE FROM translation_cache", [])
+    }
+}
+
+/// `blobs` 表记录
+#[derive(Debug, Clone)]
+pub struct BlobRecord {
+    pub hash: String,
+    pub kind: String,
+    pub name: String,
+    pub size: u64,
+    pub ref_count: i64,
+    pub recent: bool,
+}
+
+/// blob 存储元数据；引用关系见 `item_blobs`
+pub struct BlobRepository {
+    write_conn: Arc<Mutex<Connection>>,
+    read_conn: Arc<Mutex<Connection>>,
+}
+
+impl BlobRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            write_conn: db.write_connection(),
+            read_conn: db.read_connection(),
+        }
+    }
+
+    /// 登记 blob 并记录文件名别名（已存在时保留首次登记的主文件名）；
+    /// 未被引用的 blob 重新计时，避免在写入期间被清理
+    pub fn register(
+        &self,
+        hash: &str,
+        kind: &str,
+        name: &str,
+        size: u64,
+    ) -> Result<(), rusqlite::Error> {
+        let mut conn = self.write_conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO blobs (hash, kind, name, size) VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT(hash) DO UPDATE SET size = excluded.size, released_at = NULL, \
+                 created_at = CASE WHEN ref_count <= 0 THEN excluded.created_at ELSE created_at END",
+            params![hash, kind, name, size as i64],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO blob_names (hash, name) VALUES (?1, ?2)",
+            params![hash, name],
+        )?;
+        tx.commit()
+    }
+
+    /// 按文件名（任一别名）查找 blob 哈希
+    pub fn find_by_name(&self, kind: &str, name: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "SELECT b.hash FROM blob_names n JOIN blobs b ON b.hash = n.hash \
+             WHERE b.kind = ?1 AND n.name = ?2 LIMIT 1",
+            params![kind, name],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// 可清理的 blob：引用已全部释放，或存入后超过宽限期仍未被引用
+    pub fn list_sweepable(&self, grace_minutes: i64) -> Result<Vec<String>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT hash FROM blobs WHERE ref_count <= 0 AND (released_at IS NOT NULL \
+                 OR created_at < datetime('now', 'localtime', '-' || ?1 || ' minutes'))",
+        )?;
+        stmt.query_map(params![grace_minutes], |row| row.get(0))?
+            .collect()
+    }
+
+    /// 删除未被引用的 blob 记录，条件同 [`Self::list_sweepable`]；
+    /// 期间重新被引用或重新存入（仍在宽限期内）时返回 false
+    pub fn delete_unreferenced(
+        &self,
+        hash: &str,
+        grace_minutes: i64,
+    ) -> Result<bool, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let deleted = conn.execute(
+            "DELETE FROM blobs WHERE hash = ?1 AND ref_count <= 0 \
+             AND (released_at IS NOT NULL \
+                 OR created_at < datetime('now', 'localtime', '-' || ?2 || ' minutes')) \
+             AND NOT EXISTS (SELECT 1 FROM item_blobs WHERE hash = ?1)",
+            params![hash, grace_minutes],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    pub fn ref_count(&self, hash: &str) -> Result<Option<i64>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        conn.query_row(
+            "SELECT ref_count FROM blobs WHERE hash = ?1",
+            params![hash],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// 全部 blob 记录；`recent` 表示仍在存入后的宽限期内
+    pub fn list_all(&self, grace_minutes: i64) -> Result<Vec<BlobRecord>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT hash, kind, name, size, ref_count, \
+                 created_at >= datetime('now', 'localtime', '-' || ?1 || ' minutes') \
+             FROM blobs",
+        )?;
+        stmt.query_map(params![grace_minutes], |row| {
+            Ok(BlobRecord {
+                hash: row.get(0)?,
+                kind: row.get(1)?,
+                name: row.get(2)?,
+                size: row.get::<_, i64>(3)?.max(0) as u64,
+                ref_count: row.get(4)?,
+                recent: row.get::<_, Option<bool>>(5)?.unwrap_or(false),
+            })
+        })?
+        .collect()
+    }
+
+    /// 引用该 blob 的条目 ID
+    pub fn referencing_items(&self, hash: &str) -> Result<Vec<i64>, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let mut stmt = conn.prepare("SELECT item_id FROM item_blobs WHERE hash = ?1")?;
+        stmt.query_map(params![hash], |row| row.get(0))?.collect()
+    }
+
+    /// 引用数与 `item_blobs` 不一致的记录数
+    pub fn count_ref_drift(&self) -> Result<usize, rusqlite::Error> {
+        let conn = self.read_conn.lock();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM blobs b \
+             WHERE b.ref_count != (SELECT COUNT(*) FROM item_blobs ib WHERE ib.hash = b.hash)",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// 按 `item_blobs` 重新计算引用数，返回修正的记录数；归零的记录标记为已释放
+    pub fn fix_ref_counts(&self) -> Result<usize, rusqlite::Error> {
+        let conn = self.write_conn.lock();
+        let fixed = conn.execute(
+            "UPDATE blobs SET \
+                 ref_count = (SELECT COUNT(*) FROM item_blobs ib WHERE ib.hash = blobs.hash), \
+                 released_at = CASE \
+                     WHEN (SELECT COUNT(*) FROM item_blobs ib WHERE ib.hash = blobs.hash) = 0 \
+                     THEN COALESCE(released_at, datetime('now', 'localtime')) ELSE NULL END \
+             WHERE ref_count != (SELECT COUNT(*) FROM item_blobs ib WHERE ib.hash = blobs.hash)",
+            [],
+        )?;
+        Ok(fixed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2007,7 +3907,7 @@ mod tests {
         repo.toggle_pin(id2).unwrap();
         repo.toggle_favorite(id3).unwrap();
 
-        let deleted = repo.clear_history(None, None).unwrap();
+        let deleted = repo.clear_history(None, None, &[]).unwrap();
         assert_eq!(deleted, 1);
         assert!(repo.get_by_id(id1).unwrap().is_none());
         assert!(repo.get_by_id(id2).unwrap().is_some());
@@ -2071,13 +3971,244 @@ mod tests {
         let db = temp_db();
         let repo = ClipboardRepository::new(&db);
         let id = repo.insert(make_text_item("original text")).unwrap();
-        repo.update_text_content(id, "updated text").unwrap();
+        repo.update_text_content(id, "updated text", 10).unwrap();
         let item = repo.get_by_id(id).unwrap().unwrap();
         assert_eq!(item.text_content.as_deref(), Some("updated text"));
         assert_eq!(item.content_type, "text");
         assert_eq!(item.char_count, Some(12));
     }
 
+    #[test]
+    fn edits_keep_revisions_up_to_limit() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("v1")).unwrap();
+        repo.update_text_content(id, "v2", 2).unwrap();
+        repo.update_text_content(id, "v2", 2).unwrap();
+        let revisions = repo.list_revisions(id).unwrap();
+        assert_eq!(revisions.len(), 1, "unchanged text must not add a revision");
+        assert_eq!(revisions[0].preview, "v1");
+        assert_eq!(revisions[0].diff_summary.as_deref(), Some("+1 -1"));
+
+        repo.update_text_content(id, "v3", 2).unwrap();
+        repo.update_text_content(id, "v4", 2).unwrap();
+        let previews: Vec<String> = repo
+            .list_revisions(id)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.preview)
+            .collect();
+        assert_eq!(previews, vec!["v3", "v2"]);
+
+        repo.update_text_content(id, "v5", 0).unwrap();
+        assert_eq!(repo.list_revisions(id).unwrap().len(), 2);
+
+        repo.delete(id).unwrap();
+        assert!(repo.list_revisions(id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn restore_revision_saves_current_version() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let mut item = make_text_item("plain");
+        item.content_type = ContentType::Html;
+        item.html_content = Some("<b>plain</b>".into());
+        let id = repo.insert(item).unwrap();
+        repo.update_text_content(id, "edited", 10).unwrap();
+        assert_eq!(repo.get_by_id(id).unwrap().unwrap().content_type, "text");
+
+        let original = repo.list_revisions(id).unwrap()[0].id;
+        assert_eq!(repo.restore_revision(original, 10).unwrap(), Some(id));
+        let restored = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(restored.content_type, "html");
+        assert_eq!(restored.text_content.as_deref(), Some("plain"));
+        assert_eq!(restored.html_content.as_deref(), Some("<b>plain</b>"));
+
+        let revisions = repo.list_revisions(id).unwrap();
+        assert_eq!(revisions[0].preview, "edited");
+        assert_eq!(repo.restore_revision(9999, 10).unwrap(), None);
+    }
+
+    #[test]
+    fn replace_collected_content_updates_and_bumps() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("first")).unwrap();
+        let other = repo.insert(make_text_item("other")).unwrap();
+
+        let mut combined = make_text_item("first\nsecond");
+        combined.content_type = ContentType::Html;
+        combined.html_content = Some("first<br><b>second</b>".into());
+        assert!(repo.replace_collected_content(id, &combined).unwrap());
+
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.content_type, "html");
+        assert_eq!(item.text_content.as_deref(), Some("first\nsecond"));
+        assert_eq!(item.content_hash, combined.content_hash);
+        let other_sort = repo.get_by_id(other).unwrap().unwrap().sort_order;
+        assert!(item.sort_order > other_sort);
+
+        repo.delete(id).unwrap();
+        assert!(!repo.replace_collected_content(id, &combined).unwrap());
+    }
+
+    fn make_image_item(label: &str) -> NewClipboardItem {
+        NewClipboardItem {
+            content_type: ContentType::Image,
+            content_hash: format!("img_{label}"),
+            semantic_hash: format!("img_{label}"),
+            image_path: Some(format!("/fake/{label}.png")),
+            preview: Some("[图片]".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ocr_text_is_searchable() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_image_item("screenshot")).unwrap();
+        repo.insert(make_text_item("unrelated")).unwrap();
+
+        repo.update_ocr_text(id, "Invoice #4711 total").unwrap();
+        let items = repo
+            .list(QueryOptions {
+                search: Some("4711".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, id);
+        assert_eq!(items[0].ocr_text.as_deref(), Some("Invoice #4711 total"));
+
+        // 列表查询不携带 OCR 文本
+        let listed = repo.list(QueryOptions::default()).unwrap();
+        assert!(listed.iter().all(|i| i.ocr_text.is_none()));
+    }
+
+    #[test]
+    fn derived_items_link_to_source_and_are_searchable() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let source = repo.insert(make_text_item("Guten Morgen")).unwrap();
+        let other = repo.insert(make_text_item("Gute Nacht")).unwrap();
+        let translation = repo.insert(make_text_item("Good morning")).unwrap();
+        let meta = r#"{"kind":"translation","provider":"deeplx","from":"de","to":"en"}"#;
+
+        assert!(repo.link_derived(translation, source, meta).unwrap());
+        assert!(!repo.link_derived(translation, other, meta).unwrap());
+        assert!(!repo.link_derived(source, source, meta).unwrap());
+
+        let derived = repo.list_derived(source).unwrap();
+        assert_eq!(derived.len(), 1);
+        assert_eq!(derived[0].id, translation);
+        assert_eq!(derived[0].derivation.as_deref(), Some(meta));
+        assert!(repo.list_derived(other).unwrap().is_empty());
+
+        // 按译文搜索同时命中译文条目与源条目
+        let mut ids: Vec<i64> = repo
+            .list(QueryOptions {
+                search: Some("morning".to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, [source, translation]);
+
+        // 删除源条目后译文保留，关联置空
+        repo.delete(source).unwrap();
+        let item = repo.get_by_id(translation).unwrap().unwrap();
+        assert_eq!(item.derived_from, None);
+    }
+
+    #[test]
+    fn list_images_missing_ocr_skips_recognized() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let pending = repo.insert(make_image_item("pending")).unwrap();
+        let done = repo.insert(make_image_item("done")).unwrap();
+        let empty = repo.insert(make_image_item("empty")).unwrap();
+        repo.insert(make_text_item("not an image")).unwrap();
+
+        repo.update_ocr_text(done, "hello").unwrap();
+        repo.update_ocr_text(empty, "").unwrap();
+
+        let missing = repo.list_images_missing_ocr().unwrap();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].0, pending);
+        assert_eq!(missing[0].1, "/fake/pending.png");
+    }
+
+    #[test]
+    fn update_ocr_text_ignores_non_image_items() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("plain")).unwrap();
+        repo.update_ocr_text(id, "should not stick").unwrap();
+        assert!(repo.get_by_id(id).unwrap().unwrap().ocr_text.is_none());
+    }
+
+    #[test]
+    fn subtype_filter_matches_whole_tags() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let color = repo
+            .insert(NewClipboardItem {
+                subtypes: Some("color".to_string()),
+                ..make_text_item("#ff0000")
+            })
+            .unwrap();
+        let code = repo
+            .insert(NewClipboardItem {
+                subtypes: Some("code,lang:rust".to_string()),
+                ..make_text_item("fn main() {}")
+            })
+            .unwrap();
+        repo.insert(make_text_item("plain")).unwrap();
+
+        let only = |subtype: &str| -> Vec<i64> {
+            repo.list(QueryOptions {
+                subtype: Some(subtype.to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect()
+        };
+        assert_eq!(only("color"), vec![color]);
+        assert_eq!(only("code"), vec![code]);
+        assert_eq!(only("lang:rust"), vec![code]);
+        assert!(only("lang").is_empty());
+        assert_eq!(only("color, code").len(), 2);
+
+        let count = repo
+            .count(QueryOptions {
+                subtype: Some("color".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn update_text_content_reclassifies_subtypes() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("hello")).unwrap();
+        repo.update_text_content(id, "#00ff00", 10).unwrap();
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.subtypes.as_deref(), Some("color"));
+
+        repo.update_text_content(id, "just words", 10).unwrap();
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert!(item.subtypes.is_none());
+    }
+
     #[test]
     fn get_by_position() {
         let db = temp_db();
@@ -2113,6 +4244,48 @@ mod tests {
         assert_eq!(deleted, 0);
     }
 
+    #[test]
+    fn apply_rule_effects_pins_and_expires() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("rule_effects")).unwrap();
+        repo.apply_rule_effects(id, true, Some(30)).unwrap();
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert!(item.is_pinned);
+        assert!(item.expires_at.is_some());
+    }
+
+    #[test]
+    fn delete_expired_skips_pinned_favorites_and_future_items() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let expired = repo.insert(make_text_item("expired")).unwrap();
+        let favorite = repo.insert(make_text_item("expired_favorite")).unwrap();
+        let pinned = repo.insert(make_text_item("expired_pinned")).unwrap();
+        let future = repo.insert(make_text_item("not_yet")).unwrap();
+        repo.toggle_favorite(favorite).unwrap();
+        // 规则同时设置置顶与 TTL 时，置顶条目不因过期被删除
+        repo.apply_rule_effects(pinned, true, Some(60)).unwrap();
+        repo.apply_rule_effects(future, false, Some(60)).unwrap();
+        {
+            let conn = db.write_connection();
+            let conn = conn.lock();
+            conn.execute(
+                "UPDATE clipboard_items SET expires_at = datetime('now', 'localtime', '-1 minutes') \
+                 WHERE id IN (?1, ?2, ?3)",
+                params![expired, favorite, pinned],
+            )
+            .unwrap();
+        }
+
+        let (deleted, _, _) = repo.delete_expired().unwrap();
+        assert_eq!(deleted, 1);
+        assert!(repo.get_by_id(expired).unwrap().is_none());
+        assert!(repo.get_by_id(favorite).unwrap().is_some());
+        assert!(repo.get_by_id(pinned).unwrap().is_some());
+        assert!(repo.get_by_id(future).unwrap().is_some());
+    }
+
     // ==================== SettingsRepository ====================
 
     #[test]
@@ -2172,57 +4345,294 @@ mod tests {
     }
 
     #[test]
-    fn settings_get_multiple() {
+    fn settings_get_multiple() {
+        let db = temp_db();
+        let repo = SettingsRepository::new(&db);
+        repo.set("a", "1").unwrap();
+        repo.set("b", "2").unwrap();
+        let result = repo.get_multiple(&["a", "b", "c"]).unwrap();
+        assert_eq!(result.get("a").unwrap(), "1");
+        assert_eq!(result.get("b").unwrap(), "2");
+        assert!(!result.contains_key("c"));
+    }
+
+    #[test]
+    fn settings_get_all() {
+        let db = temp_db();
+        let repo = SettingsRepository::new(&db);
+        let all = repo.get_all().unwrap();
+        assert!(all.contains_key("global_shortcut"));
+    }
+
+    #[test]
+    fn settings_clear_all() {
+        let db = temp_db();
+        let repo = SettingsRepository::new(&db);
+        repo.set("extra", "val").unwrap();
+        repo.clear_all().unwrap();
+        assert!(repo.get_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn settings_upsert() {
+        let db = temp_db();
+        let repo = SettingsRepository::new(&db);
+        repo.set("upsert_key", "first").unwrap();
+        repo.set("upsert_key", "second").unwrap();
+        assert_eq!(repo.get("upsert_key").unwrap(), Some("second".to_string()));
+    }
+
+    // ==================== GroupRepository ====================
+
+    #[test]
+    fn group_create_and_list() {
+        let db = temp_db();
+        let repo = GroupRepository::new(&db);
+        let group = repo.create("Test Group", Some("#ff0000")).unwrap();
+        assert_eq!(group.name, "Test Group");
+        assert_eq!(group.color.as_deref(), Some("#ff0000"));
+        assert_eq!(group.item_count, 0);
+
+        let groups = repo.list_with_count().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Test Group");
+    }
+
+    #[test]
+    fn group_find_by_name_and_exists() {
+        let db = temp_db();
+        let repo = GroupRepository::new(&db);
+        let group = repo.create("Work", None).unwrap();
+        assert_eq!(repo.find_id_by_name("Work").unwrap(), Some(group.id));
+        assert_eq!(repo.find_id_by_name("work").unwrap(), None);
+        assert!(repo.exists(group.id).unwrap());
+        assert!(!repo.exists(group.id + 100).unwrap());
+    }
+
+    // ==================== TagRepository ====================
+
+    #[test]
+    fn tag_items_in_bulk_and_count() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let tags = TagRepository::new(&db);
+        let a = repo.insert(make_text_item("tag_a")).unwrap();
+        let b = repo.insert(make_text_item("tag_b")).unwrap();
+        let work = tags.create("work", Some("#f00")).unwrap();
+        let snippet = tags.create("snippet", None).unwrap();
+
+        assert_eq!(tags.tag_items(&[a, b], &[work.id, snippet.id]).unwrap(), 4);
+        // 重复关联与不存在的条目/标签被忽略
+        assert_eq!(tags.tag_items(&[a, 9999], &[work.id, 9999]).unwrap(), 0);
+        assert_eq!(tags.untag_items(&[b], &[snippet.id]).unwrap(), 1);
+
+        let counts: Vec<(String, i64)> = tags
+            .list_with_count()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.name, t.item_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("snippet".to_string(), 1), ("work".to_string(), 2)]
+        );
+
+        repo.delete(a).unwrap();
+        tags.delete(snippet.id).unwrap();
+        let remaining = tags.list_with_count().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].item_count, 1);
+    }
+
+    #[test]
+    fn tag_filter_spans_groups() {
         let db = temp_db();
-        let repo = SettingsRepository::new(&db);
-        repo.set("a", "1").unwrap();
-        repo.set("b", "2").unwrap();
-        let result = repo.get_multiple(&["a", "b", "c"]).unwrap();
-        assert_eq!(result.get("a").unwrap(), "1");
-        assert_eq!(result.get("b").unwrap(), "2");
-        assert!(!result.contains_key("c"));
+        let repo = ClipboardRepository::new(&db);
+        let tags = TagRepository::new(&db);
+        let group = GroupRepository::new(&db).create("G", None).unwrap();
+        let default_item = repo.insert(make_text_item("default_tagged")).unwrap();
+        let group_item = repo
+            .insert(NewClipboardItem {
+                group_id: Some(group.id),
+                ..make_text_item("group_tagged")
+            })
+            .unwrap();
+        repo.insert(make_text_item("untagged")).unwrap();
+        let tag = tags.create("topic", None).unwrap();
+        tags.tag_items(&[default_item, group_item], &[tag.id])
+            .unwrap();
+
+        let options = QueryOptions {
+            tag_ids: Some(vec![tag.id]),
+            ..Default::default()
+        };
+        let mut ids: Vec<i64> = repo
+            .list(options.clone())
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![default_item, group_item]);
+        assert_eq!(repo.count(options).unwrap(), 2);
+
+        let deleted = repo.clear_history(None, None, &[tag.id]).unwrap();
+        assert_eq!(deleted, 2);
+        assert_eq!(repo.count(QueryOptions::default()).unwrap(), 1);
     }
 
     #[test]
-    fn settings_get_all() {
+    fn tag_item_by_names_creates_missing_tags() {
         let db = temp_db();
-        let repo = SettingsRepository::new(&db);
-        let all = repo.get_all().unwrap();
-        assert!(all.contains_key("global_shortcut"));
+        let repo = ClipboardRepository::new(&db);
+        let tags = TagRepository::new(&db);
+        let id = repo.insert(make_text_item("hooked")).unwrap();
+        tags.create("ticket", None).unwrap();
+
+        tags.tag_item_by_names(
+            id,
+            &["ticket".to_string(), " new ".to_string(), String::new()],
+        )
+        .unwrap();
+        let mut items = vec![repo.get_by_id(id).unwrap().unwrap()];
+        tags.attach_tags(&mut items).unwrap();
+        assert_eq!(
+            items[0].tags,
+            Some(vec!["new".to_string(), "ticket".to_string()])
+        );
+        assert_eq!(tags.list_with_count().unwrap().len(), 2);
     }
 
     #[test]
-    fn settings_clear_all() {
+    fn attach_tags_and_sync_import_merge_by_name() {
         let db = temp_db();
-        let repo = SettingsRepository::new(&db);
-        repo.set("extra", "val").unwrap();
-        repo.clear_all().unwrap();
-        assert!(repo.get_all().unwrap().is_empty());
+        let repo = ClipboardRepository::new(&db);
+        let tags = TagRepository::new(&db);
+        let id = repo.insert(make_text_item("synced")).unwrap();
+        let tag = tags.create("alpha", None).unwrap();
+        tags.tag_items(&[id], &[tag.id]).unwrap();
+
+        let mut items = vec![repo.get_by_id(id).unwrap().unwrap()];
+        tags.attach_tags(&mut items).unwrap();
+        assert_eq!(items[0].tags, Some(vec!["alpha".to_string()]));
+
+        // 另一台设备：同内容条目合并标签，新条目按名称建立标签
+        let other = temp_db();
+        let other_repo = ClipboardRepository::new(&other);
+        let other_tags = TagRepository::new(&other);
+        let local = other_repo.insert(make_text_item("synced")).unwrap();
+        let mut incoming = items[0].clone();
+        incoming.tags = Some(vec!["alpha".to_string(), "beta".to_string()]);
+        let new_hash = blake3::hash(b"text:fresh").to_hex().to_string();
+        let fresh = ClipboardItem {
+            content_hash: new_hash.clone(),
+            semantic_hash: new_hash,
+            text_content: Some("fresh".to_string()),
+            tags: Some(vec!["beta".to_string()]),
+            ..items[0].clone()
+        };
+        assert_eq!(other_repo.import_sync_items(&[incoming, fresh]).unwrap(), 1);
+
+        let mut local_items = vec![other_repo.get_by_id(local).unwrap().unwrap()];
+        other_tags.attach_tags(&mut local_items).unwrap();
+        assert_eq!(
+            local_items[0].tags,
+            Some(vec!["alpha".to_string(), "beta".to_string()])
+        );
+        let counts: Vec<i64> = other_tags
+            .list_with_count()
+            .unwrap()
+            .iter()
+            .map(|t| t.item_count)
+            .collect();
+        assert_eq!(counts, vec![1, 2]);
     }
 
     #[test]
-    fn settings_upsert() {
+    fn merge_items_dedups_by_semantic_hash() {
         let db = temp_db();
-        let repo = SettingsRepository::new(&db);
-        repo.set("upsert_key", "first").unwrap();
-        repo.set("upsert_key", "second").unwrap();
-        assert_eq!(repo.get("upsert_key").unwrap(), Some("second".to_string()));
+        let repo = ClipboardRepository::new(&db);
+        let mut existing = make_text_item("hello");
+        existing.semantic_hash = "semantic".to_string();
+        repo.insert(existing).unwrap();
+        let item = repo
+            .find_by_hashes("missing", "semantic")
+            .unwrap()
+            .expect("found by semantic hash");
+
+        // 内容哈希不同但语义相同（如仅格式不同）的条目：同步导入会新增，合并导入跳过
+        let variant = ClipboardItem {
+            content_hash: "other".to_string(),
+            ..item
+        };
+        assert_eq!(repo.merge_items(std::slice::from_ref(&variant)).unwrap(), 0);
+        assert_eq!(repo.import_sync_items(&[variant]).unwrap(), 1);
     }
 
-    // ==================== GroupRepository ====================
+    // ==================== RuleRepository ====================
+
+    fn rule_input(name: &str) -> CaptureRuleInput {
+        CaptureRuleInput {
+            name: name.to_string(),
+            enabled: true,
+            content_regex: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
 
     #[test]
-    fn group_create_and_list() {
+    fn rule_crud_and_reorder() {
         let db = temp_db();
-        let repo = GroupRepository::new(&db);
-        let group = repo.create("Test Group", Some("#ff0000")).unwrap();
-        assert_eq!(group.name, "Test Group");
-        assert_eq!(group.color.as_deref(), Some("#ff0000"));
-        assert_eq!(group.item_count, 0);
+        let repo = RuleRepository::new(&db);
+        let group = GroupRepository::new(&db).create("Routed", None).unwrap();
+
+        let first = repo.create(&rule_input("first")).unwrap();
+        let second = repo
+            .create(&CaptureRuleInput {
+                target_group_id: Some(group.id),
+                pin: true,
+                ttl_minutes: Some(15),
+                ..rule_input("second")
+            })
+            .unwrap();
+        assert!(second.sort_order > first.sort_order);
+        assert_eq!(second.target_group_id, Some(group.id));
+        assert!(second.pin);
+
+        repo.update(
+            first.id,
+            &CaptureRuleInput {
+                enabled: false,
+                ..rule_input("renamed")
+            },
+        )
+        .unwrap();
+        let updated = repo.get(first.id).unwrap().unwrap();
+        assert_eq!(updated.name, "renamed");
+        assert!(!updated.enabled);
 
-        let groups = repo.list_with_count().unwrap();
-        assert_eq!(groups.len(), 1);
-        assert_eq!(groups[0].name, "Test Group");
+        repo.reorder(&[second.id, first.id]).unwrap();
+        let ids: Vec<i64> = repo.list().unwrap().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![second.id, first.id]);
+
+        repo.delete(first.id).unwrap();
+        assert!(repo.get(first.id).unwrap().is_none());
+        assert_eq!(repo.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rules_are_removed_with_their_group() {
+        let db = temp_db();
+        let repo = RuleRepository::new(&db);
+        let groups = GroupRepository::new(&db);
+        let group = groups.create("Temp", None).unwrap();
+        repo.create(&CaptureRuleInput {
+            target_group_id: Some(group.id),
+            ..rule_input("temp")
+        })
+        .unwrap();
+        groups.delete(group.id, None).unwrap();
+        assert!(repo.list().unwrap().is_empty());
     }
 
     #[test]
@@ -2251,23 +4661,142 @@ mod tests {
         let db = temp_db();
         let repo = GroupRepository::new(&db);
         let group = repo.create("To Delete", None).unwrap();
-        repo.delete(group.id).unwrap();
+        repo.delete(group.id, None).unwrap();
         assert!(repo.list_with_count().unwrap().is_empty());
     }
 
     #[test]
-    fn group_delete_cascades_items() {
+    fn group_delete_moves_items_to_default() {
         let db = temp_db();
         let group_repo = GroupRepository::new(&db);
-        let group = group_repo.create("Cascade Group", None).unwrap();
+        let group = group_repo.create("Preserve Group", None).unwrap();
 
         let clip_repo = ClipboardRepository::new(&db);
         let mut item = make_text_item("grouped_item");
         item.group_id = Some(group.id);
         let id = clip_repo.insert(item).unwrap();
 
-        group_repo.delete(group.id).unwrap();
-        assert!(clip_repo.get_by_id(id).unwrap().is_none());
+        group_repo.delete(group.id, None).unwrap();
+        let item = clip_repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.group_id, None);
+    }
+
+    #[test]
+    fn group_delete_moves_items_rules_and_children() {
+        let db = temp_db();
+        let group_repo = GroupRepository::new(&db);
+        let parent = group_repo.create("Parent", None).unwrap();
+        let doomed = group_repo.create("Doomed", None).unwrap();
+        let child = group_repo.create("Child", None).unwrap();
+        let target = group_repo.create("Target", None).unwrap();
+        group_repo.set_parent(doomed.id, Some(parent.id)).unwrap();
+        group_repo.set_parent(child.id, Some(doomed.id)).unwrap();
+
+        let clip_repo = ClipboardRepository::new(&db);
+        let mut item = make_text_item("moved_item");
+        item.group_id = Some(doomed.id);
+        let id = clip_repo.insert(item).unwrap();
+        let rules = RuleRepository::new(&db);
+        rules
+            .create(&CaptureRuleInput {
+                target_group_id: Some(doomed.id),
+                ..rule_input("follow")
+            })
+            .unwrap();
+
+        group_repo.delete(doomed.id, Some(target.id)).unwrap();
+        assert_eq!(
+            clip_repo.get_by_id(id).unwrap().unwrap().group_id,
+            Some(target.id)
+        );
+        assert_eq!(rules.list().unwrap()[0].target_group_id, Some(target.id));
+        let groups = group_repo.list_with_count().unwrap();
+        let child = groups.iter().find(|g| g.id == child.id).unwrap();
+        assert_eq!(child.parent_id, Some(parent.id));
+    }
+
+    #[test]
+    fn group_nesting_detects_cycles() {
+        let db = temp_db();
+        let repo = GroupRepository::new(&db);
+        let a = repo.create("A", None).unwrap();
+        let b = repo.create("B", None).unwrap();
+        let c = repo.create("C", None).unwrap();
+        repo.set_parent(b.id, Some(a.id)).unwrap();
+        repo.set_parent(c.id, Some(b.id)).unwrap();
+
+        assert!(repo.is_self_or_descendant(a.id, a.id).unwrap());
+        assert!(repo.is_self_or_descendant(a.id, c.id).unwrap());
+        assert!(!repo.is_self_or_descendant(c.id, a.id).unwrap());
+
+        let d = repo.create_in("D", None, Some(c.id)).unwrap();
+        assert_eq!(d.parent_id, Some(c.id));
+        assert!(repo.is_self_or_descendant(a.id, d.id).unwrap());
+        // 父分组不存在时整体失败，不留下顶层分组
+        assert!(repo.create_in("Orphan", None, Some(9999)).is_err());
+        assert_eq!(repo.find_id_by_name("Orphan").unwrap(), None);
+    }
+
+    #[test]
+    fn group_settings_override_cleanup_limits() {
+        let db = temp_db();
+        let group_repo = GroupRepository::new(&db);
+        let group = group_repo.create("Limited", None).unwrap();
+        assert_eq!(group.settings, GroupSettings::default());
+
+        let clip_repo = ClipboardRepository::new(&db);
+        for i in 0..4 {
+            let mut item = make_text_item(&format!("limited_{i}"));
+            item.group_id = Some(group.id);
+            clip_repo.insert(item).unwrap();
+        }
+        // 未设置分组上限时沿用全局值
+        assert_eq!(
+            clip_repo.enforce_max_count(10, Some(group.id)).unwrap().0,
+            0
+        );
+
+        let settings = GroupSettings {
+            max_count: Some(2),
+            retention_days: Some(0),
+            dedup_strategy: Some("always_new".into()),
+            sync_enabled: false,
+            quota_mb: None,
+        };
+        group_repo.update_settings(group.id, &settings).unwrap();
+        assert_eq!(
+            clip_repo.enforce_max_count(10, Some(group.id)).unwrap().0,
+            2
+        );
+        // 保留天数 0 = 该分组不自动清理（即使全局设置为 1 天）
+        clip_repo
+            .write_conn
+            .lock()
+            .execute(
+                "UPDATE clipboard_items SET created_at = datetime('now', 'localtime', '-5 days')",
+                [],
+            )
+            .unwrap();
+        assert_eq!(
+            clip_repo
+                .delete_older_than(1, Some(group.id), &RetentionPolicy::default())
+                .unwrap()
+                .0,
+            0
+        );
+
+        assert_eq!(
+            group_repo.dedup_strategy(group.id).unwrap().as_deref(),
+            Some("always_new")
+        );
+        let listed = group_repo.list_with_count().unwrap();
+        assert_eq!(listed[0].settings, settings);
+        assert!(
+            clip_repo
+                .query_items_for_sync(true, None, None)
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
@@ -2327,4 +4856,418 @@ mod tests {
         let groups = group_repo.list_with_count().unwrap();
         assert_eq!(groups[0].item_count, 2);
     }
+
+    #[test]
+    fn translation_cache_roundtrip() {
+        let db = temp_db();
+        let repo = TranslationCacheRepository::new(&db);
+        assert_eq!(repo.get("openai", "en", "zh", "h1").unwrap(), None);
+
+        repo.put("openai", "en", "zh", "h1", "你好").unwrap();
+        repo.put("deeplx", "en", "zh", "h1", "您好").unwrap();
+        assert_eq!(
+            repo.get("openai", "en", "zh", "h1").unwrap().as_deref(),
+            Some("你好")
+        );
+        assert_eq!(
+            repo.get("deeplx", "en", "zh", "h1").unwrap().as_deref(),
+            Some("您好")
+        );
+        assert_eq!(repo.get("openai", "en", "ja", "h1").unwrap(), None);
+
+        repo.put("openai", "en", "zh", "h1", "嗨").unwrap();
+        assert_eq!(
+            repo.get("openai", "en", "zh", "h1").unwrap().as_deref(),
+            Some("嗨")
+        );
+        assert_eq!(repo.count().unwrap(), 2);
+        assert_eq!(repo.clear().unwrap(), 2);
+        assert_eq!(repo.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn blob_register_keeps_first_name_and_records_aliases() {
+        let db = temp_db();
+        let blobs = BlobRepository::new(&db);
+        let hash = "ef".to_string() + &"3".repeat(62);
+        blobs.register(&hash, "icon", "a.png", 10).unwrap();
+        blobs.register(&hash, "icon", "b.png", 10).unwrap();
+
+        let records = blobs.list_all(60).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name, "a.png");
+        assert_eq!(
+            blobs.find_by_name("icon", "a.png").unwrap(),
+            Some(hash.clone())
+        );
+        assert_eq!(
+            blobs.find_by_name("icon", "b.png").unwrap(),
+            Some(hash.clone())
+        );
+        assert_eq!(blobs.find_by_name("image", "b.png").unwrap(), None);
+
+        // 刚存入、尚未被引用的 blob 仍在宽限期内，不会被删除
+        assert!(!blobs.delete_unreferenced(&hash, 60).unwrap());
+    }
+
+    #[test]
+    fn blob_refs_follow_item_media_paths() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let blobs = BlobRepository::new(&db);
+        let hash = "ab".to_string() + &"1".repeat(62);
+        let path = format!("/data/blobs/ab/{hash}/image.png");
+        blobs.register(&hash, "image", "image.png", 3).unwrap();
+
+        let mut item = make_image_item("shared");
+        item.image_path = Some(path.clone());
+        let first = repo.insert(item).unwrap();
+        let mut item = make_image_item("shared copy");
+        item.image_path = Some(path.clone());
+        let second = repo.insert(item).unwrap();
+        assert_eq!(blobs.ref_count(&hash).unwrap(), Some(2));
+
+        // 路径改为非 blob 路径时释放引用
+        repo.update_item_media_paths(first, Some("/fake/old.png"), None, None)
+            .unwrap();
+        assert_eq!(blobs.ref_count(&hash).unwrap(), Some(1));
+        assert!(blobs.list_sweepable(60).unwrap().is_empty());
+        assert!(!blobs.delete_unreferenced(&hash, 60).unwrap());
+
+        // 删除条目级联释放
+        repo.delete(second).unwrap();
+        assert_eq!(blobs.ref_count(&hash).unwrap(), Some(0));
+        assert_eq!(blobs.list_sweepable(60).unwrap(), vec![hash.clone()]);
+        assert!(blobs.delete_unreferenced(&hash, 60).unwrap());
+        assert_eq!(blobs.ref_count(&hash).unwrap(), None);
+
+        // 未登记的 blob 路径补建占位记录
+        let other = "cd".to_string() + &"2".repeat(62);
+        repo.update_item_media_paths(
+            first,
+            None,
+            None,
+            Some(&format!("C:\\data\\blobs\\cd\\{other}\\key.png")),
+        )
+        .unwrap();
+        assert_eq!(blobs.ref_count(&other).unwrap(), Some(1));
+        assert_eq!(blobs.find_by_name("icon", "key.png").unwrap(), Some(other));
+    }
+
+    #[test]
+    fn staged_files_are_merged_into_payload() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let staged = |original: &str| StagedFile {
+            original: original.to_string(),
+            staged: format!("/staged/{original}"),
+            size: 1,
+            snapshot: None,
+        };
+        let id = repo
+            .insert(NewClipboardItem {
+                content_type: ContentType::Files,
+                file_paths: Some(vec!["/a".into(), "/b".into()]),
+                file_payload: Some(r#"{"hdrop_b64":"AA=="}"#.into()),
+                content_hash: "files_ab".into(),
+                semantic_hash: "files_ab".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        repo.set_staging_state(id, Some("pending")).unwrap();
+        assert_eq!(
+            repo.list_pending_staging().unwrap(),
+            vec![(id, vec!["/a".to_string(), "/b".to_string()])]
+        );
+
+        assert!(
+            repo.apply_staged_files(id, &[staged("/a")], "partial")
+                .unwrap()
+        );
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        assert_eq!(item.staging_state.as_deref(), Some("partial"));
+        let payload = decode_payload(item.file_payload.as_deref()).unwrap();
+        assert_eq!(payload.hdrop_b64.as_deref(), Some("AA=="));
+        assert_eq!(payload.staged, vec![staged("/a")]);
+        assert!(repo.list_pending_staging().unwrap().is_empty());
+
+        // 收集模式覆盖内容时保留已 staging 的早期文件
+        let merged = NewClipboardItem {
+            content_type: ContentType::Files,
+            file_paths: Some(vec!["/a".into(), "/b".into(), "/c".into()]),
+            file_payload: Some(encode_payload(&Default::default())),
+            content_hash: "files_abc".into(),
+            semantic_hash: "files_abc".into(),
+            ..Default::default()
+        };
+        assert!(repo.replace_collected_content(id, &merged).unwrap());
+        assert!(
+            repo.apply_staged_files(id, &[staged("/c")], "done")
+                .unwrap()
+        );
+        let item = repo.get_by_id(id).unwrap().unwrap();
+        let payload = decode_payload(item.file_payload.as_deref()).unwrap();
+        assert_eq!(payload.staged, vec![staged("/a"), staged("/c")]);
+
+        // 条目被编辑为文本后丢弃迟到的 staging 结果
+        repo.update_text_content(id, "now text", 0).unwrap();
+        assert!(
+            !repo
+                .apply_staged_files(id, &[staged("/b")], "done")
+                .unwrap()
+        );
+        assert!(!repo.apply_staged_files(9999, &[], "failed").unwrap());
+    }
+
+    #[test]
+    fn byte_quota_evicts_by_policy_and_spares_pinned() {
+        let insert = |repo: &ClipboardRepository, label: &str, kb: usize| {
+            repo.insert(make_text_item(&format!("{label}{}", "x".repeat(kb * 1024))))
+                .unwrap()
+        };
+
+        // oldest：置顶条目与刚写入的条目不参与淘汰
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let a = insert(&repo, "a", 600);
+        let b = insert(&repo, "b", 500);
+        let c = insert(&repo, "c", 300);
+        repo.toggle_pin(a).unwrap();
+        assert_eq!(
+            repo.enforce_byte_quota(QuotaScope::Total, 2, EvictionPolicy::Oldest, None)
+                .unwrap()
+                .0,
+            0
+        );
+        let (deleted, _, _) = repo
+            .enforce_byte_quota(QuotaScope::Total, 1, EvictionPolicy::Oldest, Some(c))
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(repo.get_by_id(b).unwrap().is_none());
+        assert!(repo.get_by_id(a).unwrap().is_some());
+        assert!(repo.get_by_id(c).unwrap().is_some());
+
+        // largest：只删除最大的一条即可回到上限内
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let a = insert(&repo, "a", 300);
+        let b = insert(&repo, "b", 700);
+        let c = insert(&repo, "c", 400);
+        repo.enforce_byte_quota(QuotaScope::Total, 1, EvictionPolicy::Largest, None)
+            .unwrap();
+        assert!(repo.get_by_id(b).unwrap().is_none());
+        assert!(repo.get_by_id(a).unwrap().is_some());
+        assert!(repo.get_by_id(c).unwrap().is_some());
+
+        // least_accessed：使用过的条目保留到最后
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let a = insert(&repo, "a", 300);
+        let b = insert(&repo, "b", 700);
+        let c = insert(&repo, "c", 400);
+        repo.write_conn
+            .lock()
+            .execute(
+                "UPDATE clipboard_items SET access_count = 5 WHERE id = ?1",
+                params![b],
+            )
+            .unwrap();
+        let (deleted, _, _) = repo
+            .enforce_byte_quota(QuotaScope::Total, 1, EvictionPolicy::LeastAccessed, None)
+            .unwrap();
+        assert_eq!(deleted, 2);
+        assert!(repo.get_by_id(a).unwrap().is_none());
+        assert!(repo.get_by_id(c).unwrap().is_none());
+        assert!(repo.get_by_id(b).unwrap().is_some());
+    }
+
+    #[test]
+    fn content_bytes_follow_text_edits_and_ocr() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let id = repo.insert(make_text_item("héllo")).unwrap();
+        assert_eq!(repo.storage_usage().unwrap().content_bytes, 6);
+
+        repo.update_text_content(id, "hi", 0).unwrap();
+        assert_eq!(repo.storage_usage().unwrap().content_bytes, 2);
+
+        let image = repo.insert(make_image_item("shot")).unwrap();
+        repo.update_ocr_text(image, "ocr").unwrap();
+        assert_eq!(repo.storage_usage().unwrap().content_bytes, 5);
+    }
+
+    #[test]
+    fn byte_quota_scopes_and_usage_breakdown() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let group_repo = GroupRepository::new(&db);
+        let group = group_repo.create("Big", None).unwrap();
+        let text = |label: &str| format!("{label}{}", "x".repeat(600 * 1024));
+
+        for label in ["g1", "g2"] {
+            let mut item = make_text_item(&text(label));
+            item.group_id = Some(group.id);
+            repo.insert(item).unwrap();
+        }
+        repo.insert(make_text_item(&text("d"))).unwrap();
+
+        // 两张图片共享同一个 4MB blob，各计一半
+        let blobs = BlobRepository::new(&db);
+        let hash = "ef".to_string() + &"3".repeat(62);
+        blobs
+            .register(&hash, "image", "shot.png", 4 * 1024 * 1024)
+            .unwrap();
+        for label in ["shot", "shot again"] {
+            let mut item = make_image_item(label);
+            item.image_path = Some(format!("/data/blobs/ef/{hash}/shot.png"));
+            repo.insert(item).unwrap();
+        }
+
+        let usage = repo.storage_usage().unwrap();
+        assert_eq!(usage.media_bytes, 4 * 1024 * 1024);
+        assert_eq!(usage.total_bytes, usage.content_bytes + usage.media_bytes);
+        assert_eq!(usage.by_type[0].content_type, "image");
+        assert_eq!(usage.by_type[0].count, 2);
+        let text_usage = usage
+            .by_type
+            .iter()
+            .find(|u| u.content_type == "text")
+            .unwrap();
+        assert_eq!(text_usage.count, 3);
+        let group_usage = usage
+            .by_group
+            .iter()
+            .find(|u| u.group_id == Some(group.id))
+            .unwrap();
+        assert_eq!(group_usage.name.as_deref(), Some("Big"));
+        assert_eq!(group_usage.count, 2);
+
+        // 全局分组配额为 0 时分组设置的配额生效，默认分组不受影响
+        let settings = GroupSettings {
+            quota_mb: Some(1),
+            ..Default::default()
+        };
+        group_repo.update_settings(group.id, &settings).unwrap();
+        assert_eq!(
+            repo.enforce_byte_quota(QuotaScope::Group(None), 0, EvictionPolicy::Oldest, None)
+                .unwrap()
+                .0,
+            0
+        );
+        assert_eq!(
+            repo.enforce_byte_quota(
+                QuotaScope::Group(Some(group.id)),
+                0,
+                EvictionPolicy::Oldest,
+                None
+            )
+            .unwrap()
+            .0,
+            1
+        );
+
+        // 类型配额只统计该类型
+        assert_eq!(
+            repo.enforce_byte_quota(
+                QuotaScope::ContentType("text"),
+                1,
+                EvictionPolicy::Oldest,
+                None
+            )
+            .unwrap()
+            .0,
+            1
+        );
+        assert_eq!(
+            repo.enforce_byte_quota(
+                QuotaScope::ContentType("image"),
+                5,
+                EvictionPolicy::Oldest,
+                None
+            )
+            .unwrap()
+            .0,
+            0
+        );
+        // 共享 blob 只有在最后一个引用删除后才释放，两条都会被淘汰
+        let (deleted, image_paths, _) = repo
+            .enforce_byte_quota(
+                QuotaScope::ContentType("image"),
+                1,
+                EvictionPolicy::Oldest,
+                None,
+            )
+            .unwrap();
+        assert_eq!(deleted, 2);
+        assert_eq!(image_paths.len(), 2);
+    }
+
+    #[test]
+    fn retention_keeps_frequently_used_items() {
+        let db = temp_db();
+        let repo = ClipboardRepository::new(&db);
+        let unused = repo.insert(make_text_item("unused")).unwrap();
+        let daily = repo.insert(make_text_item("pasted daily")).unwrap();
+        let once = repo.insert(make_text_item("pasted once")).unwrap();
+        let pinned = repo.insert(make_text_item("pinned")).unwrap();
+        let fresh = repo.insert(make_text_item("fresh")).unwrap();
+        repo.toggle_pin(pinned).unwrap();
+        let conn = repo.write_conn.lock();
+        conn.execute(
+            "UPDATE clipboard_items SET created_at = datetime('now', 'localtime', '-40 days') \
+             WHERE id != ?1",
+            params![fresh],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE clipboard_items SET access_count = 60, \
+             last_accessed_at = datetime('now', 'localtime', '-1 hours') WHERE id = ?1",
+            params![daily],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE clipboard_items SET access_count = 1, \
+             last_accessed_at = datetime('now', 'localtime', '-2 days') WHERE id = ?1",
+            params![once],
+        )
+        .unwrap();
+        drop(conn);
+
+        let policy = RetentionPolicy::default();
+        let expired = repo.retention_expired(30, None, &policy).unwrap();
+        assert_eq!(
+            expired.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![unused]
+        );
+        assert_eq!(expired[0].access_count, 0);
+        assert!(expired[0].age_days > 39.0);
+
+        // 不衰减、不保护时，用过一次的条目保留期翻倍，仍在保留期内
+        let strict = RetentionPolicy {
+            half_life_days: 0.0,
+            protect_uses: 0.0,
+            size_ref_mb: 0.0,
+        };
+        let ids: Vec<i64> = repo
+            .retention_expired(30, None, &strict)
+            .unwrap()
+            .iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, vec![unused]);
+        assert_eq!(repo.retention_expired(15, None, &strict).unwrap().len(), 2);
+
+        // 评分后被收藏的条目不删除
+        repo.toggle_favorite(unused).unwrap();
+        let conn = repo.write_conn.lock();
+        assert_eq!(ClipboardRepository::delete_ids(&conn, &ids).unwrap(), 0);
+        drop(conn);
+        repo.toggle_favorite(unused).unwrap();
+
+        assert_eq!(repo.delete_older_than(30, None, &policy).unwrap().0, 1);
+        assert!(repo.get_by_id(unused).unwrap().is_none());
+        for id in [daily, once, pinned, fresh] {
+            assert!(repo.get_by_id(id).unwrap().is_some());
+        }
+    }
 }
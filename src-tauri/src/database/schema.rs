@@ -1,11 +1,18 @@
 pub const SCHEMA_SQL: &str = r#"
 -- Custom groups table (must be created before clipboard_items due to FK)
+-- parent_id builds a folder hierarchy; per-group settings NULL => inherit the global setting
 CREATE TABLE IF NOT EXISTS groups (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     name TEXT NOT NULL UNIQUE,
     color TEXT,
     sort_order INTEGER DEFAULT 0,
-    created_at TEXT DEFAULT (datetime('now', 'localtime'))
+    created_at TEXT DEFAULT (datetime('now', 'localtime')),
+    parent_id INTEGER REFERENCES groups(id) ON DELETE SET NULL,
+    retention_days INTEGER,
+    max_count INTEGER,
+    dedup_strategy TEXT,
+    sync_enabled INTEGER NOT NULL DEFAULT 1,
+    quota_mb INTEGER
 );
 
 -- Clipboard items table
@@ -37,9 +44,112 @@ CREATE TABLE IF NOT EXISTS clipboard_items (
     char_count INTEGER,
     source_app_name TEXT,
     source_app_icon TEXT,
-    group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE
+    group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
+    ocr_text TEXT,
+    subtypes TEXT,
+    expires_at TEXT,
+    derived_from INTEGER REFERENCES clipboard_items(id) ON DELETE SET NULL,
+    derivation TEXT,
+    staging_state TEXT,
+    media_missing INTEGER NOT NULL DEFAULT 0,
+    content_bytes INTEGER NOT NULL DEFAULT 0
 );
 
+-- Capture routing rules (evaluated in order for each new capture)
+-- target_group_id IS NULL => keep the group the item would go to anyway
+CREATE TABLE IF NOT EXISTS capture_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    enabled INTEGER DEFAULT 1,
+    sort_order INTEGER DEFAULT 0,
+    source_app TEXT,
+    content_regex TEXT,
+    content_types TEXT,
+    time_start TEXT,
+    time_end TEXT,
+    target_group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
+    pin INTEGER DEFAULT 0,
+    ttl_minutes INTEGER,
+    created_at TEXT DEFAULT (datetime('now', 'localtime'))
+);
+
+-- Tags (many-to-many labels, independent of single-group membership)
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    color TEXT,
+    created_at TEXT DEFAULT (datetime('now', 'localtime'))
+);
+
+CREATE TABLE IF NOT EXISTS item_tags (
+    item_id INTEGER NOT NULL REFERENCES clipboard_items(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (item_id, tag_id)
+) WITHOUT ROWID;
+
+-- Prior versions of edited text items
+CREATE TABLE IF NOT EXISTS item_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    item_id INTEGER NOT NULL REFERENCES clipboard_items(id) ON DELETE CASCADE,
+    content_type TEXT NOT NULL,
+    text_content TEXT NOT NULL,
+    html_content TEXT,
+    diff_summary TEXT,
+    created_at TEXT DEFAULT (datetime('now', 'localtime'))
+);
+
+-- Translation results keyed by provider, language pair and normalized source text
+CREATE TABLE IF NOT EXISTS translation_cache (
+    provider TEXT NOT NULL,
+    source_lang TEXT NOT NULL,
+    target_lang TEXT NOT NULL,
+    semantic_hash TEXT NOT NULL,
+    translated_text TEXT NOT NULL,
+    created_at TEXT DEFAULT (datetime('now', 'localtime')),
+    last_used_at TEXT DEFAULT (datetime('now', 'localtime')),
+    PRIMARY KEY (provider, source_lang, target_lang, semantic_hash)
+);
+
+-- Content-addressed blob store (images, icons, staged files), keyed by BLAKE3
+CREATE TABLE IF NOT EXISTS blobs (
+    hash TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL,
+    size INTEGER NOT NULL DEFAULT 0,
+    ref_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT DEFAULT (datetime('now', 'localtime')),
+    released_at TEXT
+);
+
+-- File names stored in each blob directory (the same content may be saved under several names)
+CREATE TABLE IF NOT EXISTS blob_names (
+    hash TEXT NOT NULL REFERENCES blobs(hash) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    PRIMARY KEY (hash, name)
+);
+
+-- Item -> blob references; ref_count is maintained by triggers below
+CREATE TABLE IF NOT EXISTS item_blobs (
+    item_id INTEGER NOT NULL REFERENCES clipboard_items(id) ON DELETE CASCADE,
+    hash TEXT NOT NULL REFERENCES blobs(hash),
+    PRIMARY KEY (item_id, hash)
+);
+
+CREATE TRIGGER IF NOT EXISTS item_blobs_ref_insert
+AFTER INSERT ON item_blobs
+BEGIN
+    UPDATE blobs SET ref_count = ref_count + 1, released_at = NULL
+    WHERE hash = new.hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS item_blobs_ref_delete
+AFTER DELETE ON item_blobs
+BEGIN
+    UPDATE blobs SET ref_count = ref_count - 1,
+        released_at = CASE WHEN ref_count - 1 <= 0 THEN datetime('now', 'localtime') ELSE released_at END
+    WHERE hash = old.hash;
+END;
+
 -- Settings table
 CREATE TABLE IF NOT EXISTS settings (
     key TEXT PRIMARY KEY,
@@ -47,6 +157,18 @@ CREATE TABLE IF NOT EXISTS settings (
     updated_at TEXT DEFAULT (datetime('now', 'localtime'))
 );
 
+-- Cached byte size of the text columns (byte quotas and storage usage); inserts set it directly
+CREATE TRIGGER IF NOT EXISTS clipboard_items_content_bytes
+AFTER UPDATE OF text_content, html_content, rtf_content, ocr_text, file_payload ON clipboard_items
+BEGIN
+    UPDATE clipboard_items SET content_bytes = COALESCE(length(CAST(new.text_content AS BLOB)), 0)
+        + COALESCE(length(CAST(new.html_content AS BLOB)), 0)
+        + COALESCE(length(CAST(new.rtf_content AS BLOB)), 0)
+        + COALESCE(length(CAST(new.ocr_text AS BLOB)), 0)
+        + COALESCE(length(CAST(new.file_payload AS BLOB)), 0)
+    WHERE id = new.id;
+END;
+
 -- Update timestamp trigger
 CREATE TRIGGER IF NOT EXISTS clipboard_items_update_timestamp 
 AFTER UPDATE ON clipboard_items
@@ -69,6 +191,13 @@ CREATE INDEX IF NOT EXISTS idx_clipboard_access ON clipboard_items(access_count
 CREATE INDEX IF NOT EXISTS idx_clipboard_favorite_order ON clipboard_items(favorite_order DESC) WHERE is_favorite = 1;
 CREATE INDEX IF NOT EXISTS idx_clipboard_sort_order ON clipboard_items(sort_order DESC);
 CREATE INDEX IF NOT EXISTS idx_clipboard_group ON clipboard_items(group_id);
+CREATE INDEX IF NOT EXISTS idx_clipboard_expires ON clipboard_items(expires_at) WHERE expires_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_clipboard_derived_from ON clipboard_items(derived_from) WHERE derived_from IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag_id);
+CREATE INDEX IF NOT EXISTS idx_item_revisions_item ON item_revisions(item_id, id DESC);
+CREATE INDEX IF NOT EXISTS idx_translation_cache_used ON translation_cache(last_used_at);
+CREATE INDEX IF NOT EXISTS idx_item_blobs_hash ON item_blobs(hash);
+CREATE INDEX IF NOT EXISTS idx_blob_names_name ON blob_names(name);
 
 -- Insert default settings
 INSERT OR IGNORE INTO settings (key, value) VALUES
@@ -82,7 +211,8 @@ INSERT OR IGNORE INTO settings (key, value) VALUES
     ('theme', 'system'),
     ('language', 'zh-CN'),
     ('auto_cleanup_days', '30'),
-    ('paste_key', 'ctrl_v');
+    ('paste_key', 'ctrl_v'),
+    ('ocr_enabled', 'true');
 "#;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,93 @@
+//! Clipy：历史记录保存在 Realm 数据库中，无法直接读取；这里导入「片段」编辑器导出的 XML
+//! （`<folders><folder><title/><snippets><snippet><title/><content/>…`），文件夹名称作为分组。
+
+use super::ExternalClip;
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use std::path::Path;
+
+pub fn read(path: &Path) -> Result<Vec<ExternalClip>, String> {
+    let xml = std::fs::read_to_string(path).map_err(|e| format!("读取 Clipy 片段文件失败: {e}"))?;
+    parse(&xml)
+}
+
+fn xml_err(e: impl std::fmt::Display) -> String {
+    format!("解析 Clipy 片段文件失败: {e}")
+}
+
+fn parse(xml: &str) -> Result<Vec<ExternalClip>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<String> = Vec::new();
+    let mut folder = None;
+    let mut content = None;
+    let mut buf = String::new();
+    let mut clips = Vec::new();
+    let mut saw_folders = false;
+
+    loop {
+        match reader.read_event().map_err(xml_err)? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                saw_folders |= name == "folders";
+                stack.push(name);
+                buf.clear();
+            }
+            Event::End(_) => {
+                let parent = stack.len().checked_sub(2).map(|i| stack[i].as_str());
+                match (parent, stack.last().map(String::as_str)) {
+                    (Some("folder"), Some("title")) => folder = Some(buf.trim().to_string()),
+                    (Some("snippet"), Some("content")) => content = Some(buf.clone()),
+                    (_, Some("snippet")) => clips.push(ExternalClip {
+                        text: content.take(),
+                        group: folder.clone().filter(|f| !f.is_empty()),
+                        ..Default::default()
+                    }),
+                    (_, Some("folder")) => folder = None,
+                    _ => {}
+                }
+                stack.pop();
+                buf.clear();
+            }
+            Event::Text(e) => buf.push_str(&e.xml10_content().map_err(xml_err)?),
+            Event::CData(e) => buf.push_str(&e.decode().map_err(xml_err)?),
+            Event::GeneralRef(e) => {
+                if let Some(ch) = e.resolve_char_ref().map_err(xml_err)? {
+                    buf.push(ch);
+                } else if let Some(value) = resolve_predefined_entity(&e.decode().map_err(xml_err)?)
+                {
+                    buf.push_str(value);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if !saw_folders {
+        return Err(xml_err("缺少 <folders> 根元素，不是 Clipy 片段导出文件"));
+    }
+    Ok(clips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_clipy_snippets_fixture() {
+        let clips = parse(include_str!("fixtures/clipy_snippets.xml")).unwrap();
+        assert_eq!(clips.len(), 3);
+
+        assert_eq!(
+            clips[0].text.as_deref(),
+            Some("Hi & welcome,\n  see you <soon> ✓")
+        );
+        assert_eq!(clips[0].group.as_deref(), Some("Mail"));
+        // 空片段没有可导入的内容，交给导入报告记录跳过
+        assert!(!clips[1].has_content());
+        assert_eq!(clips[2].text.as_deref(), Some("if (a < b) { return; }"));
+        assert_eq!(clips[2].group.as_deref(), Some("Code"));
+
+        assert!(parse("<items><item/></items>").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! CopyQ：每个标签页保存为配置目录下的 `copyq_tab_<名称>.dat`（QDataStream，大端序）。
+//! 文件内容为条目数 + 每个条目的「MIME → 数据」表，新版格式（版本标记 -2）可按条目压缩数据并缩写 MIME。
+//! CopyQ 不记录复制时间，条目按标签页中的顺序（最新在前）导入；标签页名称作为分组。
+
+use super::{ExternalClip, file_url_to_path, utf8_string};
+use base64::Engine;
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+use std::path::Path;
+
+const TAB_PREFIX: &str = "copyq_tab_";
+/// 默认的剪贴板标签页，不作为分组
+const CLIPBOARD_TAB: &str = "clipboard";
+const MIME_PREFIX: &str = "application/x-copyq-";
+
+/// `path` 可以是单个标签页文件，也可以是 CopyQ 配置目录（读取其中所有标签页）
+pub fn read(path: &Path) -> Result<Vec<ExternalClip>, String> {
+    if !path.is_dir() {
+        return read_tab(path);
+    }
+    let mut tabs: Vec<_> = std::fs::read_dir(path)
+        .map_err(|e| format!("读取 CopyQ 配置目录失败: {e}"))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(TAB_PREFIX) && n.ends_with(".dat"))
+        })
+        .collect();
+    if tabs.is_empty() {
+        return Err("目录中没有 CopyQ 标签页文件（copyq_tab_*.dat）".to_string());
+    }
+    tabs.sort();
+    let mut clips = Vec::new();
+    for tab in tabs {
+        clips.extend(read_tab(&tab)?);
+    }
+    Ok(clips)
+}
+
+fn read_tab(path: &Path) -> Result<Vec<ExternalClip>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("读取 CopyQ 标签页失败: {e}"))?;
+    let group = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.strip_prefix(TAB_PREFIX))
+        .map(tab_name)
+        .filter(|name| !name.is_empty() && name != CLIPBOARD_TAB);
+    let mut stream = Stream::new(&data);
+    let count = stream.u32().ok_or("CopyQ 标签页为空")?;
+    // 加密标签页以 "CopyQ…" 字符串开头，条目数会远大于文件长度
+    if count as usize > data.len() / 4 {
+        let header = Stream::new(&data).qstring().unwrap_or_default();
+        return Err(if header.starts_with("CopyQ") {
+            "暂不支持导入加密的 CopyQ 标签页".to_string()
+        } else {
+            "不是有效的 CopyQ 标签页文件".to_string()
+        });
+    }
+    let mut clips = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let entries = read_item(&mut stream).ok_or("CopyQ 标签页数据不完整")?;
+        let mut clip = ExternalClip {
+            group: group.clone(),
+            ..Default::default()
+        };
+        for (mime, bytes) in entries {
+            apply_mime(&mut clip, &mime, bytes);
+        }
+        clips.push(clip);
+    }
+    Ok(clips)
+}
+
+/// 文件名中的标签页名称：Base64（`/` 写作 `-`），旧版直接使用名称；去掉快捷键标记 `&`
+fn tab_name(encoded: &str) -> String {
+    let name = base64::engine::general_purpose::STANDARD
+        .decode(encoded.replace('-', "/"))
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| encoded.to_string());
+    name.replace('&', "")
+}
+
+fn read_item(stream: &mut Stream) -> Option<Vec<(String, Vec<u8>)>> {
+    let marker = stream.i32()?;
+    let mut entries = Vec::new();
+    if marker == -2 {
+        for _ in 0..stream.u32()? {
+            let mime = expand_mime(&stream.qstring()?);
+            let compressed = stream.bool()?;
+            let bytes = stream.bytes()?;
+            let bytes = if compressed {
+                uncompress(&bytes)?
+            } else {
+                bytes
+            };
+            entries.push((mime, bytes));
+        }
+    } else {
+        // 旧格式：首个整数即 MIME 数量，数据一律经过 qCompress
+        for _ in 0..u32::try_from(marker).ok()? {
+            let mime = stream.qstring()?;
+            let bytes = stream.bytes()?;
+            let bytes = if bytes.is_empty() {
+                bytes
+            } else {
+                uncompress(&bytes)?
+            };
+            entries.push((mime, bytes));
+        }
+    }
+    Some(entries)
+}
+
+/// 新格式用首字符缩写常见的 MIME 前缀
+fn expand_mime(mime: &str) -> String {
+    let Some(first) = mime.chars().next() else {
+        return String::new();
+    };
+    let rest = &mime[first.len_utf8()..];
+    match first {
+        '0' => rest.to_string(),
+        '1' => format!("{MIME_PREFIX}{rest}"),
+        '2' => format!("text/{rest}"),
+        '3' => format!("application/{rest}"),
+        '4' => format!("image/{rest}"),
+        _ => mime.to_string(),
+    }
+}
+
+/// qUncompress：4 字节大端原始长度 + zlib 数据
+fn uncompress(data: &[u8]) -> Option<Vec<u8>> {
+    let expected = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let mut out = Vec::with_capacity(expected.min(64 * 1024 * 1024));
+    ZlibDecoder::new(&data[4..]).read_to_end(&mut out).ok()?;
+    Some(out)
+}
+
+fn apply_mime(clip: &mut ExternalClip, mime: &str, bytes: Vec<u8>) {
+    match mime {
+        "text/plain" | "text/plain;charset=utf-8" => clip.text = Some(utf8_string(&bytes)),
+        "text/html" => clip.html = Some(utf8_string(&bytes)),
+        "text/rtf" | "application/rtf" => clip.rtf = Some(bytes),
+        "text/uri-list" => {
+            let uris = utf8_string(&bytes);
+            let files: Option<Vec<_>> = uris
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(file_url_to_path)
+                .collect();
+            match files {
+                Some(files) if !files.is_empty() => clip.files = files,
+                _ => {
+                    if clip.text.is_none() {
+                        clip.text = Some(uris.trim().to_string());
+                    }
+                }
+            }
+        }
+        "image/png" => clip.image = Some(bytes),
+        "image/bmp" | "image/tiff" => {
+            if clip.image.is_none() {
+                clip.image = Some(bytes);
+            }
+        }
+        "application/x-copyq-tags" => {
+            clip.tags = utf8_string(&bytes)
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        // 其余 CopyQ 内部元数据（备注、来源窗口等）不导入
+        m if m.starts_with(MIME_PREFIX) => {}
+        other => clip.unsupported.push(other.to_string()),
+    }
+}
+
+/// QDataStream 读取（大端序）
+struct Stream<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Stream<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        Some(self.take(1)?[0] != 0)
+    }
+
+    /// QByteArray：长度 + 字节，`0xFFFFFFFF` 表示空
+    fn bytes(&mut self) -> Option<Vec<u8>> {
+        match self.u32()? {
+            u32::MAX => Some(Vec::new()),
+            len => Some(self.take(len as usize)?.to_vec()),
+        }
+    }
+
+    /// QString：字节长度 + UTF-16BE
+    fn qstring(&mut self) -> Option<String> {
+        let bytes = self.bytes()?;
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        Some(String::from_utf16_lossy(&units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::tests::red_pixel_png;
+
+    const FIXTURE: &[u8] = include_bytes!("fixtures/copyq_tab.dat");
+
+    #[test]
+    fn reads_copyq_tab_fixture() {
+        let dir = std::env::temp_dir().join(format!(
+            "ec_import_copyq_{}_{}",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        // “&Notes” 标签页
+        let encoded = base64::engine::general_purpose::STANDARD.encode("&Notes");
+        std::fs::write(dir.join(format!("copyq_tab_{encoded}.dat")), FIXTURE).unwrap();
+        std::fs::write(dir.join("copyq.conf"), "").unwrap();
+
+        let clips = read(&dir).unwrap();
+        assert_eq!(clips.len(), 5);
+        assert!(clips.iter().all(|c| c.group.as_deref() == Some("Notes")));
+
+        assert_eq!(clips[0].text.as_deref(), Some("CopyQ text"));
+        assert_eq!(clips[0].tags, vec!["work".to_string(), "todo".to_string()]);
+        // 压缩数据与缩写的 MIME
+        assert_eq!(clips[1].html.as_deref(), Some("<i>it</i>"));
+        assert_eq!(clips[1].text.as_deref(), Some("it"));
+        let image = image::load_from_memory(clips[2].image.as_deref().unwrap()).unwrap();
+        let expected = image::load_from_memory(&red_pixel_png()).unwrap();
+        assert_eq!(image.to_rgb8(), expected.to_rgb8());
+        // 旧格式条目
+        assert_eq!(clips[3].files, vec!["/tmp/a b.txt".to_string()]);
+        assert!(!clips[4].has_content());
+        assert_eq!(
+            clips[4].unsupported,
+            vec!["application/x-special".to_string()]
+        );
+
+        // 默认剪贴板标签页不作为分组
+        let tab = dir.join("copyq_tab_Y2xpcGJvYXJk.dat");
+        std::fs::write(&tab, FIXTURE).unwrap();
+        assert!(read(&tab).unwrap().iter().all(|c| c.group.is_none()));
+        assert!(read(&dir.join("copyq.conf")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,180 @@
+//! Ditto：只读打开 `Ditto.db`。`Main` 表为条目与分组（`bIsGroup = 1` 的行是分组，`mText` 为名称），
+//! `Data` 表按剪贴板格式名保存每个条目的原始数据，`lDate` 为 Unix 秒数。
+
+use super::{
+    ExternalClip, dib_to_bmp, has_column, html_fragment, local_from_unix, open_read_only,
+    utf8_string, utf16le_string,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub fn read(path: &Path) -> Result<Vec<ExternalClip>, String> {
+    let conn = open_read_only(path)?;
+    let err = |e: rusqlite::Error| format!("读取 Ditto 数据库失败: {e}");
+
+    let groups: HashMap<i64, String> = conn
+        .prepare("SELECT lID, mText FROM Main WHERE bIsGroup = 1")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect()
+        })
+        .map_err(err)?;
+
+    let mut formats: HashMap<i64, Vec<(String, Vec<u8>)>> = HashMap::new();
+    {
+        let mut stmt = conn
+            .prepare("SELECT lParentID, strClipBoardFormat, ooData FROM Data ORDER BY lID")
+            .map_err(err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?.unwrap_or_default(),
+                ))
+            })
+            .map_err(err)?;
+        for row in rows {
+            let (id, format, data) = row.map_err(err)?;
+            formats.entry(id).or_default().push((format, data));
+        }
+    }
+
+    // “永不自动删除”对应收藏
+    let never_delete = if has_column(&conn, "Main", "lDontAutoDelete") {
+        "lDontAutoDelete"
+    } else {
+        "0"
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT lID, lDate, mText, lParentID, {never_delete} FROM Main \
+             WHERE bIsGroup = 0 ORDER BY lDate, lID"
+        ))
+        .map_err(err)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+            ))
+        })
+        .map_err(err)?;
+
+    let mut clips = Vec::new();
+    for row in rows {
+        let (id, date, title, parent, never_delete) = row.map_err(err)?;
+        let mut clip = ExternalClip {
+            created_at: date.and_then(local_from_unix),
+            group: parent.and_then(|p| groups.get(&p).cloned()),
+            favorite: never_delete.unwrap_or(0) > 0,
+            ..Default::default()
+        };
+        for (format, data) in formats.remove(&id).unwrap_or_default() {
+            apply_format(&mut clip, &format, &data);
+        }
+        // 没有格式数据的旧条目只剩列表中显示的文本
+        if !clip.has_content() {
+            clip.text = title.filter(|t| !t.is_empty());
+        }
+        clips.push(clip);
+    }
+    Ok(clips)
+}
+
+fn apply_format(clip: &mut ExternalClip, format: &str, data: &[u8]) {
+    match format {
+        "CF_UNICODETEXT" => clip.text = Some(utf16le_string(data)),
+        "CF_TEXT" | "CF_OEMTEXT" if clip.text.is_none() => clip.text = Some(utf8_string(data)),
+        "HTML Format" => clip.html = Some(html_fragment(&utf8_string(data))),
+        "Rich Text Format" => clip.rtf = Some(data.to_vec()),
+        "PNG" => clip.image = Some(data.to_vec()),
+        "CF_DIB" | "CF_DIBV5" if clip.image.is_none() => clip.image = dib_to_bmp(data),
+        "CF_HDROP" => {
+            clip.files = parse_hdrop(data);
+            clip.hdrop = Some(data.to_vec());
+        }
+        "CF_TEXT" | "CF_OEMTEXT" | "CF_DIB" | "CF_DIBV5" | "CF_LOCALE" => {}
+        other => clip.unsupported.push(other.to_string()),
+    }
+}
+
+/// 解析 DROPFILES：`pFiles` 偏移处为 NUL 分隔、双 NUL 结尾的路径列表，`fWide` 标记 UTF-16
+fn parse_hdrop(data: &[u8]) -> Vec<String> {
+    let u32_at = |at: usize| {
+        data.get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let (Some(offset), Some(wide)) = (u32_at(0), u32_at(16)) else {
+        return Vec::new();
+    };
+    let Some(list) = data.get(offset as usize..) else {
+        return Vec::new();
+    };
+    let text = if wide != 0 {
+        let units: Vec<u16> = list
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(list).into_owned()
+    };
+    text.split('\0')
+        .take_while(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn reads_ditto_fixture() {
+        let dir = std::env::temp_dir().join(format!(
+            "ec_import_ditto_{}_{}",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Ditto.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(include_str!("fixtures/ditto.sql"))
+            .unwrap();
+
+        let clips = read(&path).unwrap();
+        assert_eq!(clips.len(), 5);
+
+        let text = &clips[0];
+        assert_eq!(text.text.as_deref(), Some("Hello Ditto ✓"));
+        assert_eq!(text.created_at, local_from_unix(1_700_000_000));
+        assert_eq!(text.group.as_deref(), Some("Snippets"));
+        assert!(text.favorite);
+
+        let html = &clips[1];
+        assert_eq!(html.html.as_deref(), Some("<b>bold</b>"));
+        assert_eq!(html.text.as_deref(), Some("bold"));
+        assert!(html.rtf.as_deref().unwrap().starts_with(b"{\\rtf1"));
+        assert!(html.group.is_none());
+
+        let image = &clips[2];
+        assert!(image.text.is_none());
+        let (_, width, height) = super::super::to_png(image.image.as_deref().unwrap()).unwrap();
+        assert_eq!((width, height), (2, 1));
+
+        let files = &clips[3];
+        assert_eq!(files.files, vec![r"C:\Temp\a.txt", r"C:\Temp\b 文件.txt"]);
+
+        // 只有 Ditto 私有格式的条目：退回列表文本，并记录未识别的格式
+        let legacy = &clips[4];
+        assert_eq!(legacy.text.as_deref(), Some("legacy title"));
+        assert_eq!(legacy.unsupported, vec!["Ditto Private".to_string()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,35 @@
+-- Ditto.db 的最小结构（省略与导入无关的列）、一个分组与 5 条记录：
+-- 分组中的收藏文本、HTML+RTF、CF_DIB 图片、CF_HDROP 文件、只有私有格式的旧条目
+CREATE TABLE Main (
+    lID INTEGER PRIMARY KEY AUTOINCREMENT,
+    lDate INTEGER,
+    mText TEXT,
+    lShortCut INTEGER,
+    lDontAutoDelete INTEGER,
+    CRC INTEGER,
+    bIsGroup INTEGER,
+    lParentID INTEGER,
+    QuickPasteText TEXT
+);
+CREATE TABLE Data (
+    lID INTEGER PRIMARY KEY AUTOINCREMENT,
+    lParentID INTEGER,
+    strClipBoardFormat TEXT,
+    ooData BLOB
+);
+INSERT INTO Main (lID, lDate, mText, lDontAutoDelete, bIsGroup, lParentID) VALUES
+    (1, 1699990000, 'Snippets', 0, 1, -1),
+    (2, 1700000000, 'Hello Ditto ✓', 1700000000, 0, 1),
+    (3, 1700000100, 'bold', 0, 0, -1),
+    (4, 1700000200, 'CF_DIB', 0, 0, -1),
+    (5, 1700000300, 'C:\Temp\a.txt', 0, 0, -1),
+    (6, 1700000400, 'legacy title', 0, 0, -1);
+INSERT INTO Data (lParentID, strClipBoardFormat, ooData) VALUES
+    (2, 'CF_UNICODETEXT', X'480065006c006c006f00200044006900740074006f00200013270000'),
+    (2, 'CF_LOCALE', X'04080000'),
+    (3, 'HTML Format', X'56657273696f6e3a302e390d0a537461727448544d4c3a303030303030303039370d0a456e6448544d4c3a303030303030303137320d0a5374617274467261676d656e743a303030303030303133310d0a456e64467261676d656e743a303030303030303134320d0a3c68746d6c3e3c626f64793e3c212d2d5374617274467261676d656e742d2d3e3c623e626f6c643c2f623e3c212d2d456e64467261676d656e742d2d3e3c2f626f64793e3c2f68746d6c3e00'),
+    (3, 'Rich Text Format', X'7b5c727466315c616e736920626f6c647d00'),
+    (3, 'CF_UNICODETEXT', X'62006f006c0064000000'),
+    (4, 'CF_DIB', X'28000000020000000100000001001800000000000800000000000000000000000000000000000000ff000000ff000000'),
+    (5, 'CF_HDROP', X'140000000000000000000000000000000100000043003a005c00540065006d0070005c0061002e00740078007400000043003a005c00540065006d0070005c00620020008765f64e2e0074007800740000000000'),
+    (6, 'Ditto Private', X'00010203');
@@ -0,0 +1,34 @@
+-- Maccy Storage.sqlite（Core Data）的最小结构与 4 条记录：
+-- 置顶的文本、HTML+文本、PNG 图片、文件 URL；时间为 2001-01-01 起的秒数
+CREATE TABLE ZHISTORYITEM (
+    Z_PK INTEGER PRIMARY KEY,
+    Z_ENT INTEGER,
+    Z_OPT INTEGER,
+    ZNUMBEROFCOPIES INTEGER,
+    ZFIRSTCOPIEDAT TIMESTAMP,
+    ZLASTCOPIEDAT TIMESTAMP,
+    ZAPPLICATION VARCHAR,
+    ZPIN VARCHAR,
+    ZTITLE VARCHAR
+);
+CREATE TABLE ZHISTORYITEMCONTENT (
+    Z_PK INTEGER PRIMARY KEY,
+    Z_ENT INTEGER,
+    Z_OPT INTEGER,
+    ZITEM INTEGER,
+    ZTYPE VARCHAR,
+    ZVALUE BLOB
+);
+INSERT INTO ZHISTORYITEM (Z_PK, ZNUMBEROFCOPIES, ZFIRSTCOPIEDAT, ZLASTCOPIEDAT, ZAPPLICATION, ZPIN, ZTITLE) VALUES
+    (1, 3, 721692800.0, 721692900.5, 'com.apple.Safari', 'b', 'Hello Maccy'),
+    (2, 1, 721693000.0, 721693000.0, 'com.apple.TextEdit', NULL, 'rich'),
+    (3, 1, 721693100.0, NULL, 'com.apple.Preview', NULL, ''),
+    (4, 2, 721693200.0, 721693300.0, 'com.apple.finder', NULL, 'report.pdf');
+INSERT INTO ZHISTORYITEMCONTENT (ZITEM, ZTYPE, ZVALUE) VALUES
+    (1, 'public.utf8-plain-text', X'48656c6c6f204d61636379'),
+    (1, 'org.p0deje.Maccy.fromMaccy', X''),
+    (2, 'public.html', X'3c703e726963683c2f703e'),
+    (2, 'public.utf8-plain-text', X'72696368'),
+    (3, 'public.png', X'89504e470d0a1a0a0000000d4948445200000001000000010802000000907753de0000000c49444154789c63f8cfc0000003010100c9fe92ef0000000049454e44ae426082'),
+    (4, 'public.file-url', X'66696c653a2f2f2f55736572732f6d652f7265706f72742e706466'),
+    (4, 'com.apple.finder.noderef', X'00');
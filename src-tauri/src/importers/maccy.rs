@@ -0,0 +1,157 @@
+//! Maccy：只读打开 `Storage.sqlite`（Core Data）。`ZHISTORYITEM` 为条目（复制次数、时间、来源应用、
+//! 置顶快捷键），`ZHISTORYITEMCONTENT` 按 UTI 类型保存各格式的数据。时间为 2001-01-01 起的秒数。
+
+use super::{
+    ExternalClip, file_url_to_path, has_column, local_from_unix, open_read_only, utf8_string,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Core Data 时间起点（2001-01-01 UTC）的 Unix 秒数
+const APPLE_EPOCH_OFFSET: f64 = 978_307_200.0;
+/// Maccy 自身写入的标记类型
+const MACCY_PREFIX: &str = "org.p0deje.Maccy";
+
+pub fn read(path: &Path) -> Result<Vec<ExternalClip>, String> {
+    let conn = open_read_only(path)?;
+    let err = |e: rusqlite::Error| format!("读取 Maccy 数据库失败: {e}");
+
+    let mut contents: HashMap<i64, Vec<(String, Vec<u8>)>> = HashMap::new();
+    {
+        let mut stmt = conn
+            .prepare("SELECT ZITEM, ZTYPE, ZVALUE FROM ZHISTORYITEMCONTENT ORDER BY Z_PK")
+            .map_err(err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, Option<i64>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?.unwrap_or_default(),
+                ))
+            })
+            .map_err(err)?;
+        for row in rows {
+            if let (Some(item), Some(kind), value) = row.map_err(err)? {
+                contents.entry(item).or_default().push((kind, value));
+            }
+        }
+    }
+
+    // 以最近一次复制的时间排序与计时，与 Maccy 列表一致
+    let last_copied = if has_column(&conn, "ZHISTORYITEM", "ZLASTCOPIEDAT") {
+        "COALESCE(ZLASTCOPIEDAT, ZFIRSTCOPIEDAT)"
+    } else {
+        "ZFIRSTCOPIEDAT"
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT Z_PK, {last_copied}, ZNUMBEROFCOPIES, ZAPPLICATION, ZPIN, ZTITLE \
+             FROM ZHISTORYITEM ORDER BY {last_copied}, Z_PK"
+        ))
+        .map_err(err)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })
+        .map_err(err)?;
+
+    let mut clips = Vec::new();
+    for row in rows {
+        let (id, copied_at, copies, application, pin, title) = row.map_err(err)?;
+        let mut clip = ExternalClip {
+            created_at: copied_at
+                .and_then(|secs| local_from_unix((secs + APPLE_EPOCH_OFFSET) as i64)),
+            use_count: copies.unwrap_or(0).max(0),
+            source_app: application.filter(|a| !a.is_empty()),
+            pinned: pin.is_some_and(|p| !p.is_empty()),
+            ..Default::default()
+        };
+        for (kind, value) in contents.remove(&id).unwrap_or_default() {
+            apply_type(&mut clip, &kind, value);
+        }
+        if !clip.has_content() {
+            clip.text = title.filter(|t| !t.trim().is_empty());
+        }
+        clips.push(clip);
+    }
+    Ok(clips)
+}
+
+fn apply_type(clip: &mut ExternalClip, kind: &str, value: Vec<u8>) {
+    match kind {
+        "public.utf8-plain-text" | "public.plain-text" => clip.text = Some(utf8_string(&value)),
+        "public.html" => clip.html = Some(utf8_string(&value)),
+        "public.rtf" => clip.rtf = Some(value),
+        "public.png" => clip.image = Some(value),
+        "public.tiff" => {
+            if clip.image.is_none() {
+                clip.image = Some(value);
+            }
+        }
+        "public.file-url" => {
+            if let Some(path) = file_url_to_path(&utf8_string(&value)) {
+                clip.files.push(path);
+            }
+        }
+        k if k.starts_with(MACCY_PREFIX) => {}
+        other => clip.unsupported.push(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn reads_maccy_fixture() {
+        let dir = std::env::temp_dir().join(format!(
+            "ec_import_maccy_{}_{}",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Storage.sqlite");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(include_str!("fixtures/maccy.sql"))
+            .unwrap();
+
+        let clips = read(&path).unwrap();
+        assert_eq!(clips.len(), 4);
+
+        let text = &clips[0];
+        assert_eq!(text.text.as_deref(), Some("Hello Maccy"));
+        assert_eq!(text.created_at, local_from_unix(1_700_000_100));
+        assert!(text.pinned);
+        assert_eq!(text.use_count, 3);
+        assert_eq!(text.source_app.as_deref(), Some("com.apple.Safari"));
+        assert!(text.unsupported.is_empty());
+
+        let rich = &clips[1];
+        assert_eq!(rich.html.as_deref(), Some("<p>rich</p>"));
+        assert_eq!(rich.text.as_deref(), Some("rich"));
+        assert!(!rich.pinned);
+
+        // 没有最近复制时间的条目使用首次复制时间
+        let image = &clips[2];
+        assert_eq!(image.created_at, local_from_unix(1_700_000_300));
+        assert!(image.image.as_deref().unwrap().starts_with(b"\x89PNG"));
+        assert!(image.text.is_none());
+
+        let file = &clips[3];
+        assert_eq!(file.files, vec!["/Users/me/report.pdf".to_string()]);
+        assert_eq!(
+            file.unsupported,
+            vec!["com.apple.finder.noderef".to_string()]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
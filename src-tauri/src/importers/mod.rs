@@ -0,0 +1,782 @@
+//! 从其他剪贴板工具迁移历史：Windows 剪贴板历史、Ditto、CopyQ、Maccy、Clipy。
+//!
+//! 各来源先解析为 [`ExternalClip`]（保留原始格式数据、时间与分组），再按捕获时相同的规则
+//! 转换为 `NewClipboardItem`（类型优先级、哈希、预览、子类型），图片存入 blob 存储，
+//! 按 content_hash / semantic_hash 去重后写入，保留原始时间。
+
+mod clipy;
+mod copyq;
+mod ditto;
+mod maccy;
+mod windows_history;
+
+use crate::blobs::{self, BlobKind, BlobStore};
+use crate::clipboard::rtf_storage::encode_rtf_for_storage;
+use crate::clipboard::{
+    ClipboardHandler, HashInput, IMAGE_HASH_PREFIX, canonical_url_text, classify_text,
+    compute_semantic_hash, content_hash, file_clipboard, is_url, semantic_hash_from_text,
+};
+use crate::database::{
+    ClipboardItem, ClipboardRepository, ContentType, Database, GroupRepository, NewClipboardItem,
+};
+use base64::Engine;
+use blake3::Hasher;
+use chrono::{DateTime, Local, TimeZone};
+use image::{ImageFormat, ImageReader};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::path::Path;
+use tracing::info;
+
+/// 导入来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    /// Windows 剪贴板历史（Win+V），通过系统接口读取，无需选择文件
+    WindowsHistory,
+    /// Ditto 的 `Ditto.db`
+    Ditto,
+    /// CopyQ 的标签页数据文件 `copyq_tab_*.dat`（或其所在的配置目录）
+    #[serde(rename = "copyq")]
+    CopyQ,
+    /// Maccy 的 `Storage.sqlite`
+    Maccy,
+    /// Clipy 导出的片段 XML
+    Clipy,
+}
+
+impl ImportSource {
+    /// 是否需要选择文件（Windows 剪贴板历史直接读取系统接口）
+    pub fn needs_path(self) -> bool {
+        self != Self::WindowsHistory
+    }
+
+    /// 解析来源数据
+    pub fn read(self, path: Option<&Path>) -> Result<Vec<ExternalClip>, String> {
+        let path = || path.ok_or_else(|| "请选择要导入的文件".to_string());
+        match self {
+            Self::WindowsHistory => windows_history::read(),
+            Self::Ditto => ditto::read(path()?),
+            Self::CopyQ => copyq::read(path()?),
+            Self::Maccy => maccy::read(path()?),
+            Self::Clipy => clipy::read(path()?),
+        }
+    }
+}
+
+/// 从其他工具读出的一条记录
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalClip {
+    pub text: Option<String>,
+    /// HTML 片段（已从 CF_HTML 中提取）
+    pub html: Option<String>,
+    /// RTF 原始字节
+    pub rtf: Option<Vec<u8>>,
+    /// 编码后的图片（PNG / BMP / TIFF），非 PNG 时转换为 PNG
+    pub image: Option<Vec<u8>>,
+    pub files: Vec<String>,
+    /// 原始 CF_HDROP（来源保存了时与捕获时一样计入哈希并写入 payload）
+    pub hdrop: Option<Vec<u8>>,
+    /// 原始复制时间（来源未记录时按列表顺序生成）
+    pub created_at: Option<DateTime<Local>>,
+    pub pinned: bool,
+    pub favorite: bool,
+    pub use_count: i64,
+    /// 分组名称（不存在时新建）
+    pub group: Option<String>,
+    pub tags: Vec<String>,
+    pub source_app: Option<String>,
+    /// 未识别的格式（没有可导入内容时写入跳过原因）
+    pub unsupported: Vec<String>,
+}
+
+impl ExternalClip {
+    /// 是否有可导入的内容
+    pub fn has_content(&self) -> bool {
+        self.text.as_deref().is_some_and(|t| !t.trim().is_empty())
+            || self.html.is_some()
+            || self.rtf.is_some()
+            || self.image.is_some()
+            || !self.files.is_empty()
+    }
+}
+
+/// 被跳过的记录
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SkippedClip {
+    /// 在来源中的序号（从 0 开始）
+    pub index: usize,
+    /// `unsupported`（没有可导入的格式）/ `invalid_image`（图片无法解码）/ `store_failed`（图片保存失败）
+    pub reason: &'static str,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub source: ImportSource,
+    pub total: usize,
+    pub imported: usize,
+    /// 当前已存在（或来源中重复）而跳过的记录数
+    pub duplicates: usize,
+    pub groups_created: Vec<String>,
+    pub skipped: Vec<SkippedClip>,
+}
+
+/// 把解析出的记录写入数据库，每处理一条回调一次 `(已处理, 总数)`
+pub fn import(
+    db: &Database,
+    store: &BlobStore,
+    source: ImportSource,
+    clips: Vec<ExternalClip>,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<ImportReport, String> {
+    let repo = ClipboardRepository::new(db);
+    let mut groups = GroupResolver::new(db)?;
+    let total = clips.len();
+    let mut report = ImportReport {
+        source,
+        total,
+        imported: 0,
+        duplicates: 0,
+        groups_created: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let now = Local::now();
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+
+    for (index, clip) in clips.into_iter().enumerate() {
+        crate::backup::check_cancelled()?;
+        on_progress(index, total);
+        let skip = |reason, detail| SkippedClip {
+            index,
+            reason,
+            detail,
+        };
+        let (mut item, png) = match build_item(&clip) {
+            Ok(built) => built,
+            Err((reason, detail)) => {
+                report.skipped.push(skip(reason, detail));
+                continue;
+            }
+        };
+        let exists = repo
+            .find_by_hashes(&item.content_hash, &item.semantic_hash)
+            .map_err(|e| e.to_string())?
+            .is_some();
+        if exists || seen.contains(&item.content_hash) || seen.contains(&item.semantic_hash) {
+            report.duplicates += 1;
+            continue;
+        }
+        if let Some(png) = png {
+            match store.put_bytes(&png, blobs::IMAGE_NAME, BlobKind::Image) {
+                Ok(blob) => item.image_path = Some(blob.path.to_string_lossy().to_string()),
+                Err(e) => {
+                    report
+                        .skipped
+                        .push(skip("store_failed", Some(e.to_string())));
+                    continue;
+                }
+            }
+        }
+        seen.insert(item.content_hash.clone());
+        seen.insert(item.semantic_hash.clone());
+        item.group_id = match clip.group.as_deref() {
+            Some(name) => groups.resolve(name, &mut report.groups_created)?,
+            None => None,
+        };
+        // 来源未记录时间时按列表顺序（越靠前越新）生成，保持原有排序
+        let created_at = clip
+            .created_at
+            .unwrap_or_else(|| now - chrono::Duration::seconds(index as i64));
+        items.push(into_clipboard_item(item, &clip, created_at));
+    }
+
+    report.imported = repo.merge_items(&items).map_err(|e| e.to_string())?;
+    report.duplicates += items.len() - report.imported;
+    on_progress(total, total);
+    info!(
+        "Imported {} items from {:?}: {} duplicates, {} skipped",
+        report.imported,
+        source,
+        report.duplicates,
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+/// 分组名称 → ID，首次用到时创建
+struct GroupResolver {
+    repo: GroupRepository,
+    ids: HashMap<String, i64>,
+}
+
+impl GroupResolver {
+    fn new(db: &Database) -> Result<Self, String> {
+        let repo = GroupRepository::new(db);
+        let ids = repo
+            .list_with_count()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|g| (g.name, g.id))
+            .collect();
+        Ok(Self { repo, ids })
+    }
+
+    fn resolve(&mut self, name: &str, created: &mut Vec<String>) -> Result<Option<i64>, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(None);
+        }
+        if let Some(id) = self.ids.get(name) {
+            return Ok(Some(*id));
+        }
+        let group = self
+            .repo
+            .create(name, None)
+            .map_err(|e| format!("创建分组 {name} 失败: {e}"))?;
+        self.ids.insert(name.to_string(), group.id);
+        created.push(name.to_string());
+        Ok(Some(group.id))
+    }
+}
+
+type SkipReason = (&'static str, Option<String>);
+
+/// 按捕获时的优先级（文件 → HTML → RTF → 文本 → 图片）生成条目；图片以 PNG 字节返回，去重后再存储
+fn build_item(clip: &ExternalClip) -> Result<(NewClipboardItem, Option<Vec<u8>>), SkipReason> {
+    let text = clip.text.as_deref().filter(|t| !t.trim().is_empty());
+    let mut item = if !clip.files.is_empty() {
+        files_item(&clip.files, clip.hdrop.as_deref())
+    } else if let Some(html) = clip.html.as_deref().filter(|h| !h.trim().is_empty()) {
+        html_item(html, text, clip.rtf.as_deref())
+    } else if let Some(rtf) = clip.rtf.as_deref().filter(|r| !r.is_empty()) {
+        rtf_item(rtf, text)
+    } else if let Some(text) = text {
+        text_item(text)
+    } else if let Some(image) = clip.image.as_deref() {
+        return image_item(image).map(|(item, png)| (item, Some(png)));
+    } else {
+        let detail = (!clip.unsupported.is_empty()).then(|| clip.unsupported.join(", "));
+        return Err(("unsupported", detail));
+    };
+    if matches!(
+        item.content_type,
+        ContentType::Text | ContentType::Html | ContentType::Rtf
+    ) {
+        item.subtypes = item.text_content.as_deref().and_then(classify_text);
+    }
+    item.source_app_name.clone_from(&clip.source_app);
+    Ok((item, None))
+}
+
+fn image_hash(png: &[u8]) -> String {
+    let mut hasher = Hasher::new();
+    hasher.update(IMAGE_HASH_PREFIX);
+    hasher.update(png);
+    hasher.finalize().to_hex().to_string()
+}
+
+fn text_item(text: &str) -> NewClipboardItem {
+    let content_hash = content_hash(HashInput::Text(text));
+    let (content_type, text) = match canonical_url_text(text) {
+        Some(canonical) => (ContentType::Url, canonical),
+        None => (ContentType::Text, text),
+    };
+    let semantic_hash = if is_url(text) {
+        content_hash.clone()
+    } else {
+        semantic_hash_from_text(text).unwrap_or_else(|| content_hash.clone())
+    };
+    NewClipboardItem {
+        content_type,
+        text_content: Some(text.to_string()),
+        preview: Some(ClipboardHandler::create_preview(text)),
+        byte_size: text.len() as i64,
+        char_count: Some(text.chars().count() as i64),
+        content_hash,
+        semantic_hash,
+        ..Default::default()
+    }
+}
+
+fn html_item(html: &str, text: Option<&str>, rtf: Option<&[u8]>) -> NewClipboardItem {
+    let content_hash = content_hash(HashInput::Html(html));
+    NewClipboardItem {
+        content_type: ContentType::Html,
+        text_content: text.map(str::to_string),
+        html_content: Some(html.to_string()),
+        rtf_content: rtf.filter(|r| !r.is_empty()).map(encode_rtf_for_storage),
+        semantic_hash: compute_semantic_hash("html", text, &content_hash),
+        content_hash,
+        preview: Some(ClipboardHandler::create_preview(text.unwrap_or(html))),
+        byte_size: html.len() as i64,
+        char_count: text.map(|t| t.chars().count() as i64),
+        ..Default::default()
+    }
+}
+
+fn rtf_item(rtf: &[u8], text: Option<&str>) -> NewClipboardItem {
+    let rtf_content = encode_rtf_for_storage(rtf);
+    let content_hash = content_hash(HashInput::Rtf(&rtf_content));
+    NewClipboardItem {
+        content_type: ContentType::Rtf,
+        text_content: text.map(str::to_string),
+        byte_size: rtf_content.len() as i64,
+        rtf_content: Some(rtf_content),
+        semantic_hash: compute_semantic_hash("rtf", text, &content_hash),
+        content_hash,
+        preview: Some(text.map_or_else(
+            || "[RTF Content]".to_string(),
+            ClipboardHandler::create_preview,
+        )),
+        char_count: text.map(|t| t.chars().count() as i64),
+        ..Default::default()
+    }
+}
+
+fn files_item(paths: &[String], hdrop: Option<&[u8]>) -> NewClipboardItem {
+    let content_hash = content_hash(HashInput::Files {
+        paths,
+        descriptor_names: &[],
+        hdrop,
+    });
+    let byte_size = paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok().filter(|m| m.is_file()))
+        .map(|m| m.len() as i64)
+        .sum();
+    NewClipboardItem {
+        content_type: ContentType::Files,
+        file_paths: Some(paths.to_vec()),
+        file_payload: hdrop.map(|raw| {
+            file_clipboard::encode_payload(&file_clipboard::FilePayload {
+                hdrop_b64: Some(base64::engine::general_purpose::STANDARD.encode(raw)),
+                ..Default::default()
+            })
+        }),
+        preview: Some(file_clipboard::file_entry_preview(paths, &[])),
+        semantic_hash: content_hash.clone(),
+        content_hash,
+        byte_size,
+        ..Default::default()
+    }
+}
+
+fn image_item(data: &[u8]) -> Result<(NewClipboardItem, Vec<u8>), SkipReason> {
+    let (png, width, height) = to_png(data).map_err(|e| ("invalid_image", Some(e.to_string())))?;
+    let content_hash = image_hash(&png);
+    let item = NewClipboardItem {
+        content_type: ContentType::Image,
+        semantic_hash: content_hash.clone(),
+        content_hash,
+        preview: Some("[图片]".to_string()),
+        byte_size: png.len() as i64,
+        image_width: Some(i64::from(width)),
+        image_height: Some(i64::from(height)),
+        ..Default::default()
+    };
+    Ok((item, png))
+}
+
+/// 统一转换为 PNG，返回 `(PNG 字节, 宽, 高)`；已是 PNG 时原样保留
+fn to_png(data: &[u8]) -> image::ImageResult<(Vec<u8>, u32, u32)> {
+    let format = image::guess_format(data)?;
+    if format == ImageFormat::Png {
+        let (width, height) =
+            ImageReader::with_format(Cursor::new(data), format).into_dimensions()?;
+        return Ok((data.to_vec(), width, height));
+    }
+    let image = image::load_from_memory_with_format(data, format)?;
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok((png, image.width(), image.height()))
+}
+
+fn into_clipboard_item(
+    item: NewClipboardItem,
+    clip: &ExternalClip,
+    created_at: DateTime<Local>,
+) -> ClipboardItem {
+    let created_at = created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+    ClipboardItem {
+        id: 0,
+        content_type: item.content_type.as_str().to_string(),
+        text_content: item.text_content,
+        html_content: item.html_content,
+        rtf_content: item.rtf_content,
+        image_path: item.image_path,
+        file_paths: item
+            .file_paths
+            .map(|paths| serde_json::to_string(&paths).unwrap_or_default()),
+        file_payload: item.file_payload,
+        content_hash: item.content_hash,
+        semantic_hash: item.semantic_hash,
+        preview: item.preview,
+        byte_size: item.byte_size,
+        image_width: item.image_width,
+        image_height: item.image_height,
+        is_pinned: clip.pinned,
+        is_favorite: clip.favorite,
+        favorite_order: 0,
+        sort_order: 0,
+        updated_at: created_at.clone(),
+        created_at,
+        access_count: clip.use_count,
+        last_accessed_at: None,
+        char_count: item.char_count,
+        source_app_name: item.source_app_name,
+        source_app_icon: None,
+        group_id: item.group_id,
+        ocr_text: None,
+        subtypes: item.subtypes,
+        expires_at: None,
+        derived_from: None,
+        derivation: None,
+        staging_state: None,
+        media_missing: false,
+        tags: (!clip.tags.is_empty()).then(|| clip.tags.clone()),
+        files_valid: None,
+    }
+}
+
+// ==================== 各来源共用的解析工具 ====================
+
+/// 只读打开来源数据库（来源工具可能仍在运行，不修改其文件）
+fn open_read_only(path: &Path) -> Result<Connection, String> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("打开数据库失败: {e}"))
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+    conn.prepare(&format!("PRAGMA table_info({table})"))
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .is_ok_and(|columns| columns.iter().any(|c| c.eq_ignore_ascii_case(column)))
+}
+
+/// Unix 秒数 → 本地时间
+fn local_from_unix(secs: i64) -> Option<DateTime<Local>> {
+    Local.timestamp_opt(secs, 0).single()
+}
+
+/// UTF-16LE 字节（如 CF_UNICODETEXT）→ 字符串，去掉结尾的 NUL
+fn utf16le_string(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    let end = units.iter().position(|&u| u == 0).unwrap_or(units.len());
+    String::from_utf16_lossy(&units[..end])
+}
+
+/// UTF-8 字节 → 字符串，去掉结尾的 NUL
+fn utf8_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// 从 CF_HTML（`Version:…StartFragment:…` 头 + 完整文档）中取出片段；不是 CF_HTML 时原样返回
+fn html_fragment(raw: &str) -> String {
+    const START: &str = "<!--StartFragment-->";
+    const END: &str = "<!--EndFragment-->";
+    if let (Some(start), Some(end)) = (raw.find(START), raw.find(END))
+        && start < end
+    {
+        return raw[start + START.len()..end].to_string();
+    }
+    let offset = |key: &str| {
+        let rest = &raw[raw.find(key)? + key.len()..];
+        let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<usize>().ok()
+    };
+    match (offset("StartFragment:"), offset("EndFragment:")) {
+        (Some(start), Some(end))
+            if start < end && raw.get(start..end).is_some() && raw.starts_with("Version:") =>
+        {
+            raw[start..end].to_string()
+        }
+        _ => raw.to_string(),
+    }
+}
+
+/// `file://` URL → 本地路径（含百分号解码与 Windows 盘符）
+fn file_url_to_path(url: &str) -> Option<String> {
+    let rest = url.trim().strip_prefix("file://")?;
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let decoded = urlencoding::decode(rest).ok()?.into_owned();
+    let bytes = decoded.as_bytes();
+    // file:///C:/dir → C:/dir
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[2] == b':' && bytes[1].is_ascii_alphabetic() {
+        return Some(decoded[1..].to_string());
+    }
+    (!decoded.is_empty()).then_some(decoded)
+}
+
+/// CF_DIB（BITMAPINFOHEADER + 像素）补上 BMP 文件头，交给 BMP 解码器
+fn dib_to_bmp(dib: &[u8]) -> Option<Vec<u8>> {
+    const FILE_HEADER_LEN: u32 = 14;
+    const BI_BITFIELDS: u32 = 3;
+    let u32_at = |at: usize| Some(u32::from_le_bytes(dib.get(at..at + 4)?.try_into().ok()?));
+    let header_len = u32_at(0)?;
+    if header_len < 12 || dib.len() < header_len as usize {
+        return None;
+    }
+    let bit_count = u16::from_le_bytes(dib.get(14..16)?.try_into().ok()?);
+    let (compression, colors_used) = if header_len >= 40 {
+        (u32_at(16)?, u32_at(32)?)
+    } else {
+        (0, 0)
+    };
+    let palette = match (colors_used, bit_count) {
+        (0, 1 | 4 | 8) => 1u32 << bit_count,
+        (n, _) => n,
+    };
+    // BITMAPINFOHEADER 后紧跟三个颜色掩码（V4/V5 头已包含掩码）
+    let masks = if compression == BI_BITFIELDS && header_len == 40 {
+        12
+    } else {
+        0
+    };
+    let offset = FILE_HEADER_LEN + header_len + palette * 4 + masks;
+    let mut bmp = Vec::with_capacity(dib.len() + FILE_HEADER_LEN as usize);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(dib.len() as u32 + FILE_HEADER_LEN).to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&offset.to_le_bytes());
+    bmp.extend_from_slice(dib);
+    Some(bmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    /// 1×1 红色 PNG
+    pub(super) fn red_pixel_png() -> Vec<u8> {
+        let mut png = Vec::new();
+        image::RgbImage::from_pixel(1, 1, image::Rgb([255, 0, 0]))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn extracts_html_fragment() {
+        let body = "<html><body><!--StartFragment--><b>hi</b><!--EndFragment--></body></html>";
+        let header = "Version:0.9\r\nStartHTML:0000000000\r\nEndHTML:0000000000\r\n";
+        assert_eq!(html_fragment(&format!("{header}{body}")), "<b>hi</b>");
+
+        // 头部 63 字节，片段为 `<p>` 之后的 hello
+        let raw = format!(
+            "Version:0.9\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n<p>hello</p>",
+            66, 71
+        );
+        assert_eq!(html_fragment(&raw), "hello");
+        assert_eq!(html_fragment("<p>plain</p>"), "<p>plain</p>");
+    }
+
+    #[test]
+    fn converts_file_urls() {
+        assert_eq!(
+            file_url_to_path("file:///Users/me/My%20Doc.txt").as_deref(),
+            Some("/Users/me/My Doc.txt")
+        );
+        assert_eq!(
+            file_url_to_path("file:///C:/Temp/a.txt").as_deref(),
+            Some("C:/Temp/a.txt")
+        );
+        assert_eq!(
+            file_url_to_path("file://localhost/tmp/x").as_deref(),
+            Some("/tmp/x")
+        );
+        assert_eq!(file_url_to_path("https://example.com"), None);
+    }
+
+    #[test]
+    fn converts_dib_to_png() {
+        // 2×1、24 位、自下而上的 DIB：蓝、绿两个像素（每行补齐到 4 字节）
+        let mut dib = Vec::new();
+        dib.extend_from_slice(&40u32.to_le_bytes());
+        dib.extend_from_slice(&2i32.to_le_bytes());
+        dib.extend_from_slice(&1i32.to_le_bytes());
+        dib.extend_from_slice(&1u16.to_le_bytes());
+        dib.extend_from_slice(&24u16.to_le_bytes());
+        dib.extend_from_slice(&[0; 24]);
+        dib.extend_from_slice(&[255, 0, 0, 0, 255, 0, 0, 0]);
+
+        let (png, width, height) = to_png(&dib_to_bmp(&dib).unwrap()).unwrap();
+        assert_eq!((width, height), (2, 1));
+        let decoded = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(decoded.get_pixel(0, 0).0, [0, 0, 255]);
+        assert_eq!(decoded.get_pixel(1, 0).0, [0, 255, 0]);
+        assert!(dib_to_bmp(&[1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn builds_items_with_capture_priority_and_hashes() {
+        let url = ExternalClip {
+            text: Some("  https://example.com/a  ".into()),
+            ..Default::default()
+        };
+        let (item, png) = build_item(&url).unwrap();
+        assert!(png.is_none());
+        assert_eq!(item.content_type, ContentType::Url);
+        assert_eq!(item.text_content.as_deref(), Some("https://example.com/a"));
+
+        let rich = ExternalClip {
+            text: Some("bold".into()),
+            html: Some("<b>bold</b>".into()),
+            image: Some(red_pixel_png()),
+            ..Default::default()
+        };
+        let (item, png) = build_item(&rich).unwrap();
+        assert!(png.is_none());
+        assert_eq!(item.content_type, ContentType::Html);
+        // 语义哈希与同文本的纯文本条目一致，跨格式去重
+        let (plain, _) = build_item(&ExternalClip {
+            text: Some("bold".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(item.semantic_hash, plain.semantic_hash);
+        assert_ne!(item.content_hash, plain.content_hash);
+
+        let picture = ExternalClip {
+            image: Some(red_pixel_png()),
+            ..Default::default()
+        };
+        let (item, png) = build_item(&picture).unwrap();
+        assert_eq!(item.content_type, ContentType::Image);
+        assert_eq!((item.image_width, item.image_height), (Some(1), Some(1)));
+        assert_eq!(png, Some(red_pixel_png()));
+
+        // 原始 CF_HDROP 与捕获时一样计入哈希并写入 payload
+        let files = ExternalClip {
+            files: vec![r"C:\Temp\a.txt".into()],
+            hdrop: Some(vec![20, 0, 0, 0]),
+            ..Default::default()
+        };
+        let (item, _) = build_item(&files).unwrap();
+        assert_eq!(
+            item.content_hash,
+            content_hash(HashInput::Files {
+                paths: &files.files,
+                descriptor_names: &[],
+                hdrop: Some(&[20, 0, 0, 0]),
+            })
+        );
+        let payload = file_clipboard::decode_payload(item.file_payload.as_deref()).unwrap();
+        assert_eq!(payload.hdrop_b64.as_deref(), Some("FAAAAA=="));
+        let (bare, _) = build_item(&ExternalClip {
+            hdrop: None,
+            ..files
+        })
+        .unwrap();
+        assert_ne!(bare.content_hash, item.content_hash);
+        assert!(bare.file_payload.is_none());
+
+        let broken = ExternalClip {
+            image: Some(b"\x89PNG\r\n\x1a\nbroken".to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(build_item(&broken).unwrap_err().0, "invalid_image");
+        let empty = ExternalClip {
+            unsupported: vec!["CF_LOCALE".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            build_item(&empty).unwrap_err(),
+            ("unsupported", Some("CF_LOCALE".to_string()))
+        );
+    }
+
+    #[test]
+    fn imports_with_dedup_groups_timestamps_and_skip_report() {
+        let dir = temp_dir("run");
+        let db = Database::new(dir.join("clipboard.db")).unwrap();
+        let store = BlobStore::new(&db, dir.join(blobs::DIR_NAME));
+        let repo = ClipboardRepository::new(&db);
+        let existing = text_item("already here");
+        repo.insert(existing).unwrap();
+
+        let at = local_from_unix;
+        let clips = vec![
+            ExternalClip {
+                text: Some("first".into()),
+                created_at: at(1_600_000_000),
+                group: Some("Work".into()),
+                pinned: true,
+                tags: vec!["todo".into()],
+                ..Default::default()
+            },
+            ExternalClip {
+                text: Some("already here".into()),
+                ..Default::default()
+            },
+            ExternalClip {
+                image: Some(red_pixel_png()),
+                created_at: at(1_600_000_100),
+                ..Default::default()
+            },
+            ExternalClip {
+                text: Some("first".into()),
+                group: Some("Other".into()),
+                ..Default::default()
+            },
+            ExternalClip {
+                unsupported: vec!["CF_LOCALE".into()],
+                ..Default::default()
+            },
+        ];
+        let mut progress = Vec::new();
+        let report = import(&db, &store, ImportSource::Ditto, clips, |done, total| {
+            progress.push((done, total))
+        })
+        .unwrap();
+
+        assert_eq!(report.total, 5);
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(report.groups_created, vec!["Work".to_string()]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].index, 4);
+        assert_eq!(progress.last(), Some(&(5, 5)));
+
+        let work = GroupRepository::new(&db)
+            .list_with_count()
+            .unwrap()
+            .into_iter()
+            .find(|g| g.name == "Work")
+            .unwrap();
+        let first = repo
+            .find_by_hashes(&text_item("first").content_hash, "")
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.group_id, Some(work.id));
+        assert!(first.is_pinned);
+        assert_eq!(
+            first.created_at,
+            at(1_600_000_000)
+                .unwrap()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        );
+        let image = repo
+            .find_by_hashes(&image_hash(&red_pixel_png()), "")
+            .unwrap()
+            .unwrap();
+        let image_path = image.image_path.unwrap();
+        assert!(blobs::hash_from_path(&image_path).is_some());
+        assert_eq!(std::fs::read(&image_path).unwrap(), red_pixel_png());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
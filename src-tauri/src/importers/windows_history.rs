@@ -0,0 +1,256 @@
+//! Windows 剪贴板历史（Win+V）：通过 WinRT `Clipboard.GetHistoryItemsAsync` 读取，
+//! 需要在系统设置中开启剪贴板历史。系统接口不提供固定状态，条目按时间戳导入。
+
+// 非 Windows 平台上格式映射只在测试中使用
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use super::ExternalClip;
+
+/// 历史条目中可导入的格式（未包含时返回 `None` / 空列表）。
+/// 由 WinRT `DataPackageView` 实现，测试中用固定数据实现
+trait HistoryContent {
+    fn text(&self) -> Result<Option<String>, String>;
+    /// CF_HTML 原文（含 `StartFragment` 等头部）
+    fn html_format(&self) -> Result<Option<String>, String>;
+    fn rtf(&self) -> Result<Option<String>, String>;
+    fn storage_paths(&self) -> Result<Vec<String>, String>;
+    /// 位图流的全部字节（通常为 BMP 或 PNG 编码）
+    fn bitmap(&self) -> Result<Option<Vec<u8>>, String>;
+}
+
+/// 把一条历史记录转换为 [`ExternalClip`]；`ticks` 为 FILETIME 时间戳
+fn clip_from(ticks: Option<i64>, content: Result<impl HistoryContent, String>) -> ExternalClip {
+    let mut clip = ExternalClip {
+        created_at: ticks.and_then(local_from_filetime),
+        ..Default::default()
+    };
+    // 单条读取失败只记入跳过原因，不中断整体导入
+    if let Err(e) = content.and_then(|content| read_content(&content, &mut clip)) {
+        clip.unsupported.push(e);
+    }
+    clip
+}
+
+fn read_content(content: &impl HistoryContent, clip: &mut ExternalClip) -> Result<(), String> {
+    clip.text = content.text()?;
+    clip.html = content.html_format()?.map(|raw| super::html_fragment(&raw));
+    clip.rtf = content.rtf()?.map(String::into_bytes);
+    clip.files = content.storage_paths()?;
+    clip.image = content.bitmap()?;
+    Ok(())
+}
+
+/// FILETIME（1601-01-01 起的 100ns 间隔）→ 本地时间
+fn local_from_filetime(ticks: i64) -> Option<chrono::DateTime<chrono::Local>> {
+    const UNIX_EPOCH_TICKS: i64 = 116_444_736_000_000_000;
+    super::local_from_unix((ticks - UNIX_EPOCH_TICKS) / 10_000_000)
+}
+
+#[cfg(not(windows))]
+pub fn read() -> Result<Vec<ExternalClip>, String> {
+    Err("仅支持在 Windows 上读取系统剪贴板历史".to_string())
+}
+
+#[cfg(windows)]
+pub fn read() -> Result<Vec<ExternalClip>, String> {
+    use windows::ApplicationModel::DataTransfer::{Clipboard, ClipboardHistoryItemsResultStatus};
+
+    let err = |e: windows::core::Error| format!("读取 Windows 剪贴板历史失败: {e}");
+    let result = Clipboard::GetHistoryItemsAsync()
+        .and_then(|op| op.get())
+        .map_err(err)?;
+    match result.Status().map_err(err)? {
+        ClipboardHistoryItemsResultStatus::Success => {}
+        ClipboardHistoryItemsResultStatus::ClipboardHistoryDisabled => {
+            return Err("系统剪贴板历史未开启（设置 → 系统 → 剪贴板）".to_string());
+        }
+        _ => return Err("没有访问系统剪贴板历史的权限".to_string()),
+    }
+
+    let items = result.Items().map_err(err)?;
+    let mut clips = Vec::new();
+    for index in 0..items.Size().map_err(err)? {
+        let item = items.GetAt(index).map_err(err)?;
+        let ticks = item.Timestamp().ok().map(|t| t.UniversalTime);
+        clips.push(clip_from(ticks, item.Content().map_err(|e| e.message())));
+    }
+    Ok(clips)
+}
+
+#[cfg(windows)]
+impl HistoryContent for windows::ApplicationModel::DataTransfer::DataPackageView {
+    fn text(&self) -> Result<Option<String>, String> {
+        use windows::ApplicationModel::DataTransfer::StandardDataFormats;
+        winrt(|| {
+            if !contains(self, StandardDataFormats::Text()) {
+                return Ok(None);
+            }
+            Ok(Some(self.GetTextAsync()?.get()?.to_string_lossy()))
+        })
+    }
+
+    fn html_format(&self) -> Result<Option<String>, String> {
+        use windows::ApplicationModel::DataTransfer::StandardDataFormats;
+        winrt(|| {
+            if !contains(self, StandardDataFormats::Html()) {
+                return Ok(None);
+            }
+            Ok(Some(self.GetHtmlFormatAsync()?.get()?.to_string_lossy()))
+        })
+    }
+
+    fn rtf(&self) -> Result<Option<String>, String> {
+        use windows::ApplicationModel::DataTransfer::StandardDataFormats;
+        winrt(|| {
+            if !contains(self, StandardDataFormats::Rtf()) {
+                return Ok(None);
+            }
+            Ok(Some(self.GetRtfAsync()?.get()?.to_string_lossy()))
+        })
+    }
+
+    fn storage_paths(&self) -> Result<Vec<String>, String> {
+        use windows::ApplicationModel::DataTransfer::StandardDataFormats;
+        winrt(|| {
+            if !contains(self, StandardDataFormats::StorageItems()) {
+                return Ok(Vec::new());
+            }
+            let items = self.GetStorageItemsAsync()?.get()?;
+            (0..items.Size()?)
+                .map(|index| -> windows::core::Result<String> {
+                    Ok(items.GetAt(index)?.Path()?.to_string_lossy())
+                })
+                .collect()
+        })
+    }
+
+    fn bitmap(&self) -> Result<Option<Vec<u8>>, String> {
+        use windows::ApplicationModel::DataTransfer::StandardDataFormats;
+        use windows::Storage::Streams::{DataReader, IInputStream};
+        use windows::core::Interface;
+        winrt(|| {
+            if !contains(self, StandardDataFormats::Bitmap()) {
+                return Ok(None);
+            }
+            let stream = self.GetBitmapAsync()?.get()?.OpenReadAsync()?.get()?;
+            let size = u32::try_from(stream.Size()?).unwrap_or(u32::MAX);
+            let reader = DataReader::CreateDataReader(&stream.cast::<IInputStream>()?)?;
+            let loaded = reader.LoadAsync(size)?.get()?;
+            let mut bytes = vec![0; loaded as usize];
+            reader.ReadBytes(&mut bytes)?;
+            Ok(Some(bytes))
+        })
+    }
+}
+
+#[cfg(windows)]
+fn contains(
+    view: &windows::ApplicationModel::DataTransfer::DataPackageView,
+    format: windows::core::Result<windows::core::HSTRING>,
+) -> bool {
+    format.and_then(|f| view.Contains(&f)).unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn winrt<T>(read: impl FnOnce() -> windows::core::Result<T>) -> Result<T, String> {
+    read().map_err(|e| e.message())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 固定的历史条目；`failing` 为 true 时读取位图失败
+    #[derive(Default)]
+    struct Fixture {
+        text: Option<&'static str>,
+        html: Option<&'static str>,
+        rtf: Option<&'static str>,
+        paths: Vec<&'static str>,
+        bitmap: Option<Vec<u8>>,
+        failing: bool,
+    }
+
+    impl HistoryContent for Fixture {
+        fn text(&self) -> Result<Option<String>, String> {
+            Ok(self.text.map(str::to_string))
+        }
+
+        fn html_format(&self) -> Result<Option<String>, String> {
+            Ok(self.html.map(str::to_string))
+        }
+
+        fn rtf(&self) -> Result<Option<String>, String> {
+            Ok(self.rtf.map(str::to_string))
+        }
+
+        fn storage_paths(&self) -> Result<Vec<String>, String> {
+            Ok(self.paths.iter().map(|p| p.to_string()).collect())
+        }
+
+        fn bitmap(&self) -> Result<Option<Vec<u8>>, String> {
+            if self.failing {
+                return Err("bitmap unavailable".to_string());
+            }
+            Ok(self.bitmap.clone())
+        }
+    }
+
+    const TICKS_2020: i64 = 116_444_736_000_000_000 + 1_600_000_000 * 10_000_000;
+
+    #[test]
+    fn maps_history_formats_to_clip() {
+        let clip = clip_from(
+            Some(TICKS_2020),
+            Ok(Fixture {
+                text: Some("bold"),
+                html: Some(
+                    "Version:0.9\r\nStartHTML:0\r\n<html><body><!--StartFragment--><b>bold</b>\
+                     <!--EndFragment--></body></html>",
+                ),
+                rtf: Some(r"{\rtf1 \b bold\b0}"),
+                bitmap: Some(vec![1, 2, 3]),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            clip.created_at,
+            super::super::local_from_unix(1_600_000_000)
+        );
+        assert_eq!(clip.text.as_deref(), Some("bold"));
+        assert_eq!(clip.html.as_deref(), Some("<b>bold</b>"));
+        assert_eq!(clip.rtf.as_deref(), Some(br"{\rtf1 \b bold\b0}".as_slice()));
+        assert_eq!(clip.image, Some(vec![1, 2, 3]));
+        assert!(clip.unsupported.is_empty());
+
+        let files = clip_from(
+            None,
+            Ok(Fixture {
+                paths: vec![r"C:\Temp\a.txt", r"C:\Temp\b.txt"],
+                ..Default::default()
+            }),
+        );
+        assert_eq!(files.files, vec![r"C:\Temp\a.txt", r"C:\Temp\b.txt"]);
+        assert!(files.created_at.is_none());
+        assert!(files.has_content());
+    }
+
+    #[test]
+    fn read_errors_become_skip_reasons() {
+        // 已读出的格式保留，错误记入跳过原因
+        let partial = clip_from(
+            Some(TICKS_2020),
+            Ok(Fixture {
+                text: Some("kept"),
+                failing: true,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(partial.text.as_deref(), Some("kept"));
+        assert_eq!(partial.unsupported, vec!["bitmap unavailable"]);
+
+        let unreadable = clip_from(None, Err::<Fixture, _>("access denied".to_string()));
+        assert!(!unreadable.has_content());
+        assert_eq!(unreadable.unsupported, vec!["access denied"]);
+    }
+}
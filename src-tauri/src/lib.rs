@@ -1,17 +1,27 @@
 mod admin_launch;
+mod ai;
+mod api;
+mod backup;
+mod blobs;
 mod clipboard;
 mod commands;
 mod config;
 mod database;
 mod file_preview_limits;
 mod hotkey;
+mod importers;
 mod input_monitor;
 mod keyboard_hook;
 mod main_thread;
+mod ocr;
+mod plugins;
 mod positioning;
 mod proxy;
 mod shortcut;
 mod task_scheduler;
+#[cfg(test)]
+mod test_support;
+mod translation;
 mod tray;
 mod updater;
 mod utils;
@@ -132,6 +142,8 @@ pub fn toggle_shortcuts_disabled(app: &tauri::AppHandle) -> bool {
         unregister_shortcut_list(app, &CURRENT_QUICK_PASTE_SHORTCUTS.read());
         unregister_shortcut_list(app, &CURRENT_FAVORITE_PASTE_SHORTCUTS.read());
         commands::translate::unregister_translate_selection_shortcut(app);
+        commands::paste_queue::unregister_paste_queue_shortcut(app);
+        commands::collect::unregister_collect_shortcut(app);
         tracing::info!("All shortcuts disabled (except Win+V)");
     } else {
         if let Some(sc) = parse_shortcut(&get_current_shortcut()) {
@@ -142,6 +154,8 @@ pub fn toggle_shortcuts_disabled(app: &tauri::AppHandle) -> bool {
         let fav_shortcuts = CURRENT_FAVORITE_PASTE_SHORTCUTS.read().clone();
         apply_paste_shortcuts(app, &fav_shortcuts, PasteKind::Favorite);
         commands::translate::register_translate_selection_shortcut(app);
+        commands::paste_queue::register_paste_queue_shortcut(app);
+        commands::collect::register_collect_shortcut(app);
         tracing::info!("All shortcuts re-enabled");
     }
     disabled
@@ -813,6 +827,17 @@ pub fn run() {
                 e.to_string()
             })?;
 
+            // 旧版 images/icons/staged 目录迁入 blob 存储，后台执行不阻塞启动
+            let blob_store = blobs::init(&db, &config.get_data_dir());
+            {
+                let db = db.clone();
+                let data_dir = config.get_data_dir();
+                std::thread::spawn(move || {
+                    blob_store.migrate_legacy(&db, &data_dir);
+                    blob_store.sweep();
+                });
+            }
+
             let monitor = ClipboardMonitor::new();
             monitor.init(&db, images_path);
 
@@ -851,7 +876,12 @@ pub fn run() {
 
             let saved_shortcut = settings_repo.get_or("global_shortcut", "Alt+C");
 
+            ocr::init(app.handle(), state.db.clone());
+            clipboard::staging::init(app.handle(), state.db.clone());
+            commands::storage::start_scheduled_check(app.handle().clone(), state.db.clone());
+            commands::rules::start_expiry_timer(app.handle().clone(), state.db.clone());
             state.monitor.start(app.handle().clone());
+            commands::api::apply_settings(&state, app.handle());
             app.manage(state);
 
             let _ = tray::setup_tray(app.handle());
@@ -987,6 +1017,8 @@ pub fn run() {
                 if settings.get_bool("plugin_translate_enabled", false) {
                     commands::translate::register_translate_selection_shortcut(app.handle());
                 }
+                commands::paste_queue::register_paste_queue_shortcut(app.handle());
+                commands::collect::register_collect_shortcut(app.handle());
             }
 
             {
@@ -1060,6 +1092,11 @@ pub fn run() {
             commands::data_transfer::migrate_data_to_path,
             commands::data_transfer::export_data,
             commands::data_transfer::import_data,
+            commands::data_transfer::pick_backup_file,
+            commands::data_transfer::cancel_data_transfer,
+            commands::data_transfer::merge_import_data,
+            commands::data_transfer::import_from_app,
+            commands::data_transfer::export_items,
             commands::data_transfer::restart_app,
             commands::window::show_window,
             commands::window::hide_window,
@@ -1106,6 +1143,10 @@ pub fn run() {
             commands::window::install_update,
             commands::clipboard::get_clipboard_items,
             commands::clipboard::get_clipboard_item,
+            commands::clipboard::get_derived_items,
+            commands::clipboard::cancel_file_staging,
+            commands::clipboard::retry_file_staging,
+            commands::clipboard::get_active_file_staging,
             commands::clipboard::get_clipboard_count,
             commands::clipboard::toggle_pin,
             commands::clipboard::toggle_favorite,
@@ -1119,9 +1160,16 @@ pub fn run() {
             commands::clipboard::copy_to_clipboard,
             commands::clipboard::paste_content,
             commands::clipboard::paste_content_as_plain,
+            commands::clipboard::paste_image_text,
             commands::clipboard::paste_text_direct,
             commands::clipboard::merge_paste_content,
             commands::clipboard::update_text_content,
+            commands::revisions::list_item_revisions,
+            commands::revisions::diff_item_revisions,
+            commands::revisions::restore_item_revision,
+            commands::storage::check_storage,
+            commands::storage::get_storage_check_status,
+            commands::storage::preview_retention_cleanup,
             commands::settings::get_running_apps,
             commands::settings::get_setting,
             commands::settings::set_setting,
@@ -1158,12 +1206,64 @@ pub fn run() {
             commands::groups::rename_group,
             commands::groups::update_group_color,
             commands::groups::delete_group,
+            commands::groups::set_group_parent,
+            commands::groups::update_group_settings,
             commands::groups::move_item_to_group,
+            commands::rules::list_capture_rules,
+            commands::rules::create_capture_rule,
+            commands::rules::update_capture_rule,
+            commands::rules::delete_capture_rule,
+            commands::rules::reorder_capture_rules,
+            commands::rules::test_capture_rule,
+            commands::tags::get_tags,
+            commands::tags::create_tag,
+            commands::tags::rename_tag,
+            commands::tags::update_tag_color,
+            commands::tags::delete_tag,
+            commands::tags::tag_items,
+            commands::tags::untag_items,
+            commands::paste_queue::get_paste_queue,
+            commands::paste_queue::paste_queue_add,
+            commands::paste_queue::paste_queue_remove,
+            commands::paste_queue::paste_queue_clear,
+            commands::paste_queue::paste_queue_set_mode,
+            commands::paste_queue::paste_queue_next,
+            commands::paste_queue::update_paste_queue_shortcut,
+            commands::api::get_api_status,
+            commands::api::set_api_enabled,
+            commands::api::update_api_bind,
+            commands::api::regenerate_api_token,
+            commands::plugins::list_plugins,
+            commands::plugins::set_plugin_enabled,
+            commands::plugins::open_plugins_dir,
+            commands::plugins::list_item_actions,
+            commands::plugins::run_plugin_action,
+            commands::ai::list_ai_templates,
+            commands::ai::save_ai_templates,
+            commands::ai::reset_ai_templates,
+            commands::ai::run_ai_action,
+            commands::ai::cancel_ai_action,
+            commands::ai::get_ai_run,
+            commands::ai::save_ai_result,
+            commands::collect::get_collect_state,
+            commands::collect::start_collect,
+            commands::collect::finish_collect,
+            commands::collect::toggle_collect,
+            commands::collect::update_collect_shortcut,
+            commands::ocr::is_ocr_available,
+            commands::ocr::ocr_backfill_images,
+            commands::ocr::cancel_ocr_backfill,
             commands::sync::webdav_enable_plugin,
             commands::sync::webdav_test_connection,
             commands::sync::webdav_upload,
             commands::sync::webdav_download,
             commands::translate::translate_text,
+            commands::translate::get_translation_cache_count,
+            commands::translate::clear_translation_cache,
+            commands::translate::get_translate_provider_health,
+            commands::translate::set_translate_fallback_providers,
+            commands::translate::get_translate_glossary,
+            commands::translate::set_translate_glossary,
             commands::translate::write_text_to_clipboard,
             commands::translate::get_pending_translate_text,
             commands::translate::open_translate_result_window,
@@ -0,0 +1,256 @@
+//! 图片 OCR 模块（离线、纯 CPU）
+//!
+//! 基于 `ocrs` + `rten` 推理，模型随安装包分发于资源目录的 `ocr/`（源码中为 `resources/ocr/models/`）。
+//! 图片条目入库后由后台 `ocr-worker` 线程异步识别，结果写入 `ocr_text` 列，
+//! 供搜索与「复制图片文字」使用；另提供历史图片回填（带进度与取消）。
+
+use crate::database::{ClipboardRepository, Database};
+use ocrs::{ImageSource, OcrEngine, OcrEngineParams};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{debug, info, warn};
+
+const DETECTION_MODEL: &str = "text-detection.rten";
+const RECOGNITION_MODEL: &str = "text-recognition.rten";
+
+/// 超过该像素数的图片跳过识别，避免超大截图长时间占用 CPU
+const MAX_OCR_PIXELS: u64 = 40_000_000;
+
+/// 运行期上下文（`init` 时写入）
+struct OcrContext {
+    app: AppHandle,
+    db: Database,
+    model_dir: PathBuf,
+}
+
+static CONTEXT: OnceLock<OcrContext> = OnceLock::new();
+
+/// 引擎懒加载：首次识别时加载模型，失败结果也缓存，避免反复读盘
+static ENGINE: OnceLock<Result<OcrEngine, String>> = OnceLock::new();
+
+/// 回填取消标志
+static BACKFILL_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 回填互斥，防止同时运行多个回填任务
+static BACKFILL_LOCK: Mutex<()> = Mutex::new(());
+
+/// 初始化 OCR 上下文（应用启动时调用一次）
+pub fn init(app: &AppHandle, db: Database) {
+    let model_dir = app
+        .path()
+        .resource_dir()
+        .map(|dir| dir.join("ocr"))
+        .unwrap_or_else(|_| PathBuf::from("resources/ocr/models"));
+    debug!("OCR model dir: {:?}", model_dir);
+    let _ = CONTEXT.set(OcrContext {
+        app: app.clone(),
+        db,
+        model_dir,
+    });
+}
+
+/// 模型文件是否就绪
+pub fn is_available() -> bool {
+    CONTEXT.get().is_some_and(|ctx| {
+        ctx.model_dir.join(DETECTION_MODEL).is_file()
+            && ctx.model_dir.join(RECOGNITION_MODEL).is_file()
+    })
+}
+
+fn engine() -> Result<&'static OcrEngine, String> {
+    let ctx = CONTEXT.get().ok_or("OCR not initialized")?;
+    ENGINE
+        .get_or_init(|| load_engine(&ctx.model_dir))
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+fn load_engine(model_dir: &Path) -> Result<OcrEngine, String> {
+    let started = Instant::now();
+    let detection_model = rten::Model::load_file(model_dir.join(DETECTION_MODEL))
+        .map_err(|e| format!("Failed to load OCR detection model: {e}"))?;
+    let recognition_model = rten::Model::load_file(model_dir.join(RECOGNITION_MODEL))
+        .map_err(|e| format!("Failed to load OCR recognition model: {e}"))?;
+    let engine = OcrEngine::new(OcrEngineParams {
+        detection_model: Some(detection_model),
+        recognition_model: Some(recognition_model),
+        ..Default::default()
+    })
+    .map_err(|e| format!("Failed to create OCR engine: {e}"))?;
+    info!("OCR engine loaded in {:?}", started.elapsed());
+    Ok(engine)
+}
+
+/// 识别单张图片文件，返回规范化后的文本（可能为空串）
+pub fn recognize_file(path: &Path) -> Result<String, String> {
+    let engine = engine()?;
+    // 先只读取尺寸，超限时不解码整张图片
+    let (width, height) =
+        image::image_dimensions(path).map_err(|e| format!("Failed to open image: {e}"))?;
+    if u64::from(width) * u64::from(height) > MAX_OCR_PIXELS {
+        return Err(format!("Image too large for OCR: {width}x{height}"));
+    }
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {e}"))?
+        .into_rgb8();
+    let source = ImageSource::from_bytes(img.as_raw(), img.dimensions())
+        .map_err(|e| format!("Invalid image for OCR: {e}"))?;
+    let input = engine
+        .prepare_input(source)
+        .map_err(|e| format!("OCR preprocessing failed: {e}"))?;
+    let text = engine
+        .get_text(&input)
+        .map_err(|e| format!("OCR recognition failed: {e}"))?;
+    Ok(normalize_ocr_text(&text))
+}
+
+/// 去掉每行首尾空白与空行，统一换行符
+fn normalize_ocr_text(raw: &str) -> String {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 识别并写库；成功后通知前端刷新该条目
+fn recognize_and_store(ctx: &OcrContext, item_id: i64, image_path: &str) -> Result<(), String> {
+    let started = Instant::now();
+    let text = recognize_file(Path::new(image_path))?;
+    ClipboardRepository::new(&ctx.db)
+        .update_ocr_text(item_id, &text)
+        .map_err(|e| e.to_string())?;
+    debug!(
+        "OCR item {} done in {:?} ({} chars)",
+        item_id,
+        started.elapsed(),
+        text.chars().count()
+    );
+    let _ = ctx.app.emit("clipboard-ocr-updated", item_id);
+    Ok(())
+}
+
+// ============ 后台识别队列 ============
+
+static OCR_TX: LazyLock<std::sync::mpsc::Sender<(i64, String)>> = LazyLock::new(|| {
+    let (tx, rx) = std::sync::mpsc::channel::<(i64, String)>();
+    if let Err(e) = std::thread::Builder::new()
+        .name("ocr-worker".into())
+        .spawn(move || {
+            while let Ok((item_id, image_path)) = rx.recv() {
+                let Some(ctx) = CONTEXT.get() else {
+                    continue;
+                };
+                if let Err(e) = recognize_and_store(ctx, item_id, &image_path) {
+                    warn!("OCR failed for item {}: {}", item_id, e);
+                }
+            }
+        })
+    {
+        tracing::error!("Failed to spawn ocr-worker thread: {}", e);
+    }
+    tx
+});
+
+/// 将新入库的图片条目加入后台识别队列（模型缺失时静默跳过）
+pub fn enqueue(item_id: i64, image_path: String) {
+    if !is_available() {
+        return;
+    }
+    let _ = OCR_TX.send((item_id, image_path));
+}
+
+// ============ 历史回填 ============
+
+/// 回填结果摘要
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BackfillSummary {
+    pub total: usize,
+    pub recognized: usize,
+    pub failed: usize,
+    pub cancelled: bool,
+}
+
+/// 取消正在进行的回填
+pub fn cancel_backfill() {
+    BACKFILL_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// 为所有尚未识别的历史图片执行 OCR（阻塞，需在 blocking 线程调用）
+///
+/// 进度通过 `ocr-backfill-progress` 事件上报：`{ done, total }`。
+pub fn backfill(app: &AppHandle) -> Result<BackfillSummary, String> {
+    let ctx = CONTEXT.get().ok_or("OCR not initialized")?;
+    if !is_available() {
+        return Err("OCR models are not installed".to_string());
+    }
+    let _guard = BACKFILL_LOCK
+        .try_lock()
+        .ok_or("OCR backfill is already running")?;
+    BACKFILL_CANCELLED.store(false, Ordering::SeqCst);
+
+    let pending = ClipboardRepository::new(&ctx.db)
+        .list_images_missing_ocr()
+        .map_err(|e| e.to_string())?;
+    let mut summary = BackfillSummary {
+        total: pending.len(),
+        ..Default::default()
+    };
+    info!("OCR backfill started: {} images", summary.total);
+
+    let emit_progress = |done: usize| {
+        let _ = app.emit(
+            "ocr-backfill-progress",
+            serde_json::json!({ "done": done, "total": summary.total }),
+        );
+    };
+    emit_progress(0);
+
+    let mut last_emit = Instant::now();
+    for (index, (item_id, image_path)) in pending.iter().enumerate() {
+        if BACKFILL_CANCELLED.load(Ordering::SeqCst) {
+            summary.cancelled = true;
+            break;
+        }
+        match recognize_and_store(ctx, *item_id, image_path) {
+            Ok(()) => summary.recognized += 1,
+            Err(e) => {
+                debug!("OCR backfill skipped item {}: {}", item_id, e);
+                summary.failed += 1;
+            }
+        }
+        // 节流：每 100ms 最多发送一次进度事件
+        if last_emit.elapsed() >= Duration::from_millis(100) || index + 1 == summary.total {
+            emit_progress(index + 1);
+            last_emit = Instant::now();
+        }
+    }
+
+    info!(
+        "OCR backfill finished: {}/{} recognized, {} failed, cancelled={}",
+        summary.recognized, summary.total, summary.failed, summary.cancelled
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_trims_lines_and_drops_blanks() {
+        let raw = "  Hello World  \r\n\n   \n\tsecond line\n";
+        assert_eq!(normalize_ocr_text(raw), "Hello World\nsecond line");
+    }
+
+    #[test]
+    fn normalize_empty_input() {
+        assert_eq!(normalize_ocr_text(""), "");
+        assert_eq!(normalize_ocr_text(" \n \n"), "");
+    }
+}
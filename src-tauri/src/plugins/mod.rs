@@ -0,0 +1,511 @@
+//! 外部插件：为条目提供自定义操作（如缩短链接、上传图片、格式化 SQL）
+//!
+//! 每个插件位于数据目录 `plugins/<目录>/plugin.json`，清单声明启动命令与操作列表：
+//!
+//! ```json
+//! {
+//!   "id": "sql-format", "name": "SQL 格式化", "version": "1.0.0",
+//!   "command": "python", "args": ["main.py"], "timeout_ms": 10000,
+//!   "actions": [
+//!     { "id": "format", "title": "格式化 SQL", "content_types": ["text"], "subtypes": ["code"], "output": "clipboard" }
+//!   ]
+//! }
+//! ```
+//!
+//! 调用协议（stdio JSON-RPC 2.0）：每次执行启动一次插件进程，stdin 写入一行请求
+//! `{"jsonrpc":"2.0","id":1,"method":"action.run","params":{"version":1,"action":"format","item":{...}}}`，
+//! 插件在 stdout 输出响应 `{"jsonrpc":"2.0","id":1,"result":{"text":"...","html":"..."}}`
+//! 或 `{"error":{"code":1,"message":"..."}}`。日志请写 stderr。
+//!
+//! 进程在插件目录下运行，沙箱限制与捕获钩子相同（见 [`crate::clipboard::hooks::run_sandboxed`]）。
+
+use crate::clipboard::hooks::{SandboxedCommand, run_sandboxed};
+use crate::database::ClipboardItem;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// 清单文件名
+pub const MANIFEST_FILE: &str = "plugin.json";
+/// 设置项：已禁用插件 ID 列表（JSON 数组）
+pub const DISABLED_PLUGINS_SETTING: &str = "plugins_disabled";
+/// 调用协议版本
+pub const PROTOCOL_VERSION: u32 = 1;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+const MAX_TIMEOUT_MS: u64 = 60_000;
+/// 单个插件声明的操作数上限
+const MAX_ACTIONS: usize = 32;
+
+/// 操作结果的去向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionOutput {
+    /// 保存为新条目（默认）
+    #[default]
+    NewItem,
+    /// 写入系统剪贴板
+    Clipboard,
+}
+
+/// 插件声明的单个操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginAction {
+    pub id: String,
+    pub title: String,
+    /// 适用的内容类型（text/html/rtf/image/files/url），空 = 全部
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    /// 适用的文本子类型（如 `url`、`code`），任一命中即可，空 = 不限
+    #[serde(default)]
+    pub subtypes: Vec<String>,
+    #[serde(default)]
+    pub output: ActionOutput,
+}
+
+impl PluginAction {
+    /// 操作是否适用于该条目
+    pub fn applies_to(&self, item: &ClipboardItem) -> bool {
+        let type_ok =
+            self.content_types.is_empty() || self.content_types.contains(&item.content_type);
+        let subtype_ok = self.subtypes.is_empty()
+            || item.subtypes.as_deref().is_some_and(|subtypes| {
+                subtypes
+                    .split(',')
+                    .any(|s| self.subtypes.iter().any(|wanted| wanted == s.trim()))
+            });
+        type_ok && subtype_ok
+    }
+}
+
+/// 插件清单（`plugin.json`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// 启动命令：含路径分隔符时相对插件目录解析，否则从 PATH 查找
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    pub actions: Vec<PluginAction>,
+}
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+impl PluginManifest {
+    fn validate(&self) -> Result<(), String> {
+        let valid_id = |id: &str| {
+            !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        if !valid_id(&self.id) {
+            return Err(format!("invalid plugin id '{}'", self.id));
+        }
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        if self.command.trim().is_empty() {
+            return Err("command must not be empty".to_string());
+        }
+        if self.actions.is_empty() || self.actions.len() > MAX_ACTIONS {
+            return Err(format!("plugin must declare 1-{MAX_ACTIONS} actions"));
+        }
+        let mut seen = HashSet::new();
+        for action in &self.actions {
+            if !valid_id(&action.id) || !seen.insert(action.id.as_str()) {
+                return Err(format!("invalid or duplicate action id '{}'", action.id));
+            }
+            if action.title.trim().is_empty() {
+                return Err(format!("action '{}' has no title", action.id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 已加载的插件
+#[derive(Debug, Clone, Serialize)]
+pub struct Plugin {
+    #[serde(flatten)]
+    pub manifest: PluginManifest,
+    /// 插件目录
+    pub dir: PathBuf,
+    pub enabled: bool,
+}
+
+impl Plugin {
+    pub fn action(&self, action_id: &str) -> Option<&PluginAction> {
+        self.manifest.actions.iter().find(|a| a.id == action_id)
+    }
+
+    fn command(&self) -> String {
+        let command = self.manifest.command.trim();
+        if command.contains(['/', '\\']) && Path::new(command).is_relative() {
+            self.dir.join(command).to_string_lossy().into_owned()
+        } else {
+            command.to_string()
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.manifest.timeout_ms.clamp(1, MAX_TIMEOUT_MS))
+    }
+}
+
+/// 解析已禁用插件列表
+pub fn parse_disabled(raw: Option<&str>) -> HashSet<String> {
+    raw.and_then(|raw| serde_json::from_str::<Vec<String>>(raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
+/// 扫描插件目录；清单缺失或无效的子目录记录日志后跳过，ID 重复时保留先出现的
+pub fn discover(plugins_dir: &Path, disabled: &HashSet<String>) -> Vec<Plugin> {
+    let Ok(entries) = std::fs::read_dir(plugins_dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for dir in dirs {
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+            .map_err(|e| e.to_string())
+            .and_then(|raw| serde_json::from_str::<PluginManifest>(&raw).map_err(|e| e.to_string()))
+            .and_then(|manifest| manifest.validate().map(|()| manifest));
+        match manifest {
+            Ok(manifest) if plugins.iter().any(|p| p.manifest.id == manifest.id) => {
+                warn!(
+                    "Duplicate plugin id '{}' in {:?}, skipped",
+                    manifest.id, dir
+                );
+            }
+            Ok(manifest) => plugins.push(Plugin {
+                enabled: !disabled.contains(&manifest.id),
+                manifest,
+                dir,
+            }),
+            Err(e) => warn!("Invalid plugin manifest in {:?}: {}", dir, e),
+        }
+    }
+    plugins
+}
+
+/// 条目可用的操作（仅启用的插件）
+pub fn actions_for<'a>(
+    plugins: &'a [Plugin],
+    item: &'a ClipboardItem,
+) -> impl Iterator<Item = (&'a Plugin, &'a PluginAction)> {
+    plugins
+        .iter()
+        .filter(|p| p.enabled)
+        .flat_map(|p| p.manifest.actions.iter().map(move |a| (p, a)))
+        .filter(|(_, a)| a.applies_to(item))
+}
+
+/// 传给插件的条目描述
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginItem {
+    pub id: i64,
+    pub content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_paths: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtypes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_app_name: Option<String>,
+}
+
+impl From<&ClipboardItem> for PluginItem {
+    fn from(item: &ClipboardItem) -> Self {
+        Self {
+            id: item.id,
+            content_type: item.content_type.clone(),
+            text: item.text_content.clone(),
+            html: item.html_content.clone(),
+            image_path: item.image_path.clone(),
+            file_paths: item
+                .file_paths
+                .as_deref()
+                .and_then(|raw| serde_json::from_str(raw).ok()),
+            subtypes: item.subtypes.clone(),
+            source_app_name: item.source_app_name.clone(),
+        }
+    }
+}
+
+/// 插件返回了空内容
+pub const EMPTY_RESULT: &str = "插件未返回内容";
+
+/// 插件返回的内容
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ActionResult {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub html: Option<String>,
+}
+
+impl ActionResult {
+    /// 文本与 HTML 均为空（或只有空白）
+    pub fn is_empty(&self) -> bool {
+        let blank = |s: &Option<String>| s.as_deref().is_none_or(|s| s.trim().is_empty());
+        blank(&self.text) && blank(&self.html)
+    }
+}
+
+/// 执行插件操作
+pub fn run_action(
+    plugin: &Plugin,
+    action_id: &str,
+    item: &ClipboardItem,
+) -> Result<ActionResult, String> {
+    if plugin.action(action_id).is_none() {
+        return Err(format!(
+            "插件 {} 不支持操作 {}",
+            plugin.manifest.id, action_id
+        ));
+    }
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "action.run",
+        "params": {
+            "version": PROTOCOL_VERSION,
+            "action": action_id,
+            "item": PluginItem::from(item),
+        },
+    });
+    let mut payload = serde_json::to_vec(&request).map_err(|e| e.to_string())?;
+    payload.push(b'\n');
+
+    let stdout = run_sandboxed(
+        &SandboxedCommand {
+            command: &plugin.command(),
+            args: &plugin.manifest.args,
+            current_dir: &plugin.dir,
+            marker_env: "ELEGANT_CLIPBOARD_PLUGIN",
+            timeout: plugin.timeout(),
+        },
+        &payload,
+    )
+    .map_err(|e| format!("插件 {} 执行失败: {e}", plugin.manifest.id))?;
+    let result = parse_response(&stdout)?;
+    debug!(
+        "Plugin '{}' action '{}' returned {} text bytes",
+        plugin.manifest.id,
+        action_id,
+        result.text.as_deref().map_or(0, str::len)
+    );
+    Ok(result)
+}
+
+fn parse_response(stdout: &[u8]) -> Result<ActionResult, String> {
+    #[derive(Deserialize)]
+    struct RpcError {
+        #[serde(default)]
+        code: i64,
+        message: String,
+    }
+    #[derive(Deserialize)]
+    struct RpcResponse {
+        #[serde(default)]
+        result: Option<ActionResult>,
+        #[serde(default)]
+        error: Option<RpcError>,
+    }
+
+    let text = std::str::from_utf8(stdout).map_err(|e| format!("插件输出不是 UTF-8: {e}"))?;
+    // 以最后一个非空行为响应，容忍插件在此之前输出的杂项
+    let line = text
+        .lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .ok_or("插件没有输出")?;
+    let response: RpcResponse =
+        serde_json::from_str(line.trim()).map_err(|e| format!("插件响应无效: {e}"))?;
+    if let Some(error) = response.error {
+        return Err(format!("插件返回错误 ({}): {}", error.code, error.message));
+    }
+    let result = response.result.ok_or("插件响应缺少 result")?;
+    if result.is_empty() {
+        return Err(EMPTY_RESULT.to_string());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn write_plugin(root: &Path, dir: &str, manifest: &str) -> PathBuf {
+        let dir = root.join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        dir
+    }
+
+    fn item(content_type: &str, text: &str, subtypes: Option<&str>) -> ClipboardItem {
+        ClipboardItem {
+            id: 1,
+            content_type: content_type.to_string(),
+            text_content: Some(text.to_string()),
+            html_content: None,
+            rtf_content: None,
+            image_path: None,
+            file_paths: None,
+            file_payload: None,
+            content_hash: String::new(),
+            semantic_hash: String::new(),
+            preview: None,
+            byte_size: 0,
+            image_width: None,
+            image_height: None,
+            is_pinned: false,
+            is_favorite: false,
+            favorite_order: 0,
+            sort_order: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+            access_count: 0,
+            last_accessed_at: None,
+            char_count: None,
+            source_app_name: None,
+            source_app_icon: None,
+            group_id: None,
+            ocr_text: None,
+            subtypes: subtypes.map(str::to_string),
+            expires_at: None,
+            derived_from: None,
+            derivation: None,
+            staging_state: None,
+            media_missing: false,
+            tags: None,
+            files_valid: None,
+        }
+    }
+
+    const URL_PLUGIN: &str = r#"{
+        "id": "shortener", "name": "Shortener", "command": "sh", "args": ["run.sh"],
+        "actions": [
+            { "id": "shorten", "title": "Shorten URL", "content_types": ["text", "url"], "subtypes": ["url"] },
+            { "id": "upper", "title": "Upper", "content_types": ["text"], "output": "clipboard" }
+        ]
+    }"#;
+
+    #[test]
+    fn discover_validates_and_dedups_manifests() {
+        let root = temp_dir("discover");
+        write_plugin(&root, "a", URL_PLUGIN);
+        write_plugin(&root, "b", URL_PLUGIN);
+        write_plugin(
+            &root,
+            "c",
+            r#"{"id":"bad id","name":"x","command":"sh","actions":[]}"#,
+        );
+        write_plugin(&root, "d", "{broken");
+        std::fs::create_dir_all(root.join("no-manifest")).unwrap();
+
+        let plugins = discover(&root, &HashSet::new());
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].manifest.id, "shortener");
+        assert_eq!(plugins[0].dir, root.join("a"));
+        assert_eq!(plugins[0].manifest.timeout_ms, DEFAULT_TIMEOUT_MS);
+        assert_eq!(
+            plugins[0].action("upper").unwrap().output,
+            ActionOutput::Clipboard
+        );
+
+        let disabled = parse_disabled(Some(r#"["shortener"]"#));
+        assert!(!discover(&root, &disabled)[0].enabled);
+        assert!(discover(&root.join("missing"), &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn actions_match_content_type_and_subtype() {
+        let root = temp_dir("match");
+        write_plugin(&root, "a", URL_PLUGIN);
+        let mut plugins = discover(&root, &HashSet::new());
+
+        let ids = |plugins: &[Plugin], item: &ClipboardItem| {
+            actions_for(plugins, item)
+                .map(|(_, a)| a.id.clone())
+                .collect::<Vec<_>>()
+        };
+        let url = item("text", "https://example.com", Some("url"));
+        assert_eq!(ids(&plugins, &url), vec!["shorten", "upper"]);
+        let code = item("text", "fn main() {}", Some("code,lang:rust"));
+        assert_eq!(ids(&plugins, &code), vec!["upper"]);
+        assert!(ids(&plugins, &item("image", "", None)).is_empty());
+
+        plugins[0].enabled = false;
+        assert!(ids(&plugins, &url).is_empty());
+    }
+
+    #[test]
+    fn parse_response_handles_results_and_errors() {
+        let ok = parse_response(
+            b"log line\n{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"text\":\"x\"}}\n",
+        );
+        assert_eq!(ok.unwrap().text.as_deref(), Some("x"));
+        let err =
+            parse_response(br#"{"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"nope"}}"#);
+        assert!(err.unwrap_err().contains("nope"));
+        assert!(parse_response(br#"{"result":{}}"#).is_err());
+        assert!(parse_response(br#"{"result":{"text":" \n","html":""}}"#).is_err());
+        assert!(parse_response(b"").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_action_speaks_json_rpc_over_stdio() {
+        let root = temp_dir("run");
+        let dir = write_plugin(&root, "a", URL_PLUGIN);
+        // 读取请求中的 action 与文本，原样回显为大写
+        std::fs::write(
+            dir.join("run.sh"),
+            r#"req=$(cat)
+case "$req" in
+  *'"method":"action.run"'*'"action":"upper"'*) ;;
+  *) echo '{"jsonrpc":"2.0","id":1,"error":{"code":1,"message":"bad request"}}'; exit 0 ;;
+esac
+text=$(printf '%s' "$req" | sed 's/.*"text":"\([^"]*\)".*/\1/' | tr a-z A-Z)
+[ "$ELEGANT_CLIPBOARD_PLUGIN" = 1 ] || exit 9
+printf '{"jsonrpc":"2.0","id":1,"result":{"text":"%s"}}\n' "$text"
+"#,
+        )
+        .unwrap();
+        let plugins = discover(&root, &HashSet::new());
+        let plugin = &plugins[0];
+
+        let result = run_action(plugin, "upper", &item("text", "hello", None)).unwrap();
+        assert_eq!(result.text.as_deref(), Some("HELLO"));
+        let err = run_action(plugin, "shorten", &item("text", "x", Some("url"))).unwrap_err();
+        assert!(err.contains("bad request"), "{err}");
+        assert!(run_action(plugin, "missing", &item("text", "x", None)).is_err());
+    }
+}
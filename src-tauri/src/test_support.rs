@@ -0,0 +1,29 @@
+//! 单元测试共用的辅助函数。
+
+use crate::database::{ContentType, NewClipboardItem};
+use std::path::PathBuf;
+
+/// 创建唯一的临时目录（`ec_<name>_<pid>_<uuid>`），调用方负责清理
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "ec_{name}_{}_{}",
+        std::process::id(),
+        uuid::Uuid::new_v4().simple()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// 以 `label` 为文本、预览与哈希来源的条目
+pub(crate) fn item(content_type: ContentType, label: &str) -> NewClipboardItem {
+    let hash = blake3::hash(label.as_bytes()).to_hex().to_string();
+    NewClipboardItem {
+        content_type,
+        text_content: Some(label.to_string()),
+        preview: Some(label.to_string()),
+        content_hash: hash.clone(),
+        semantic_hash: hash,
+        byte_size: label.len() as i64,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,227 @@
+//! 翻译术语表：强制指定术语的译法，对所有翻译服务生效。
+//!
+//! 请求前把原文中的术语替换为占位符 `__GLS<n>__`，翻译完成后再把占位符还原为指定译文。
+//! 部分服务会在占位符内插入空格或改变大小写，还原时按宽松格式匹配。
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// 设置项：术语表（JSON 数组）
+pub const GLOSSARY_SETTING: &str = "translate_glossary";
+
+const MAX_ENTRIES: usize = 500;
+
+static PLACEHOLDER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)_{1,2}\s?GLS\s?(\d+)\s?_{1,2}").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    /// 原文术语
+    pub source: String,
+    /// 强制译文
+    pub target: String,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// 仅在翻译到该语言时生效，空 = 所有目标语言
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+pub fn validate(entries: &[GlossaryEntry]) -> Result<(), String> {
+    if entries.len() > MAX_ENTRIES {
+        return Err(format!("术语数量超过上限 {MAX_ENTRIES}"));
+    }
+    let mut seen = HashSet::new();
+    for entry in entries {
+        let source = entry.source.trim();
+        if source.is_empty() {
+            return Err("术语原文不能为空".to_string());
+        }
+        let key = (
+            if entry.case_sensitive {
+                source.to_string()
+            } else {
+                source.to_lowercase()
+            },
+            entry.to.clone(),
+        );
+        if !seen.insert(key) {
+            return Err(format!("术语重复: {source}"));
+        }
+    }
+    Ok(())
+}
+
+/// 解析设置中的术语表；内容无效时视为空表
+pub fn parse(raw: Option<&str>) -> Vec<GlossaryEntry> {
+    raw.filter(|s| !s.trim().is_empty())
+        .and_then(|s| serde_json::from_str::<Vec<GlossaryEntry>>(s).ok())
+        .filter(|entries| validate(entries).is_ok())
+        .unwrap_or_default()
+}
+
+/// 替换术语后的原文，及各占位符对应的译文
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Protected {
+    pub text: String,
+    targets: Vec<String>,
+}
+
+impl Protected {
+    /// 未命中任何术语
+    pub fn is_unchanged(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// 把译文中的占位符还原为术语译文（也用于流式的中间结果）
+    pub fn restore(&self, translated: &str) -> String {
+        if self.targets.is_empty() {
+            return translated.to_string();
+        }
+        PLACEHOLDER_RE
+            .replace_all(translated, |caps: &regex::Captures| {
+                caps[1]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| self.targets.get(n))
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+}
+
+fn term_pattern(term: &str, case_sensitive: bool) -> String {
+    let escaped = regex::escape(term);
+    // 以字母数字开头 / 结尾的术语按整词匹配，避免 "cat" 命中 "category"
+    let start = if term.starts_with(|c: char| c.is_alphanumeric()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if term.ends_with(|c: char| c.is_alphanumeric()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let flags = if case_sensitive { "" } else { "(?i)" };
+    format!("(?:{flags}{start}{escaped}{end})")
+}
+
+/// 用占位符替换原文中的术语；较长的术语优先匹配
+pub fn protect(entries: &[GlossaryEntry], text: &str, to: &str) -> Protected {
+    let mut applicable: Vec<&GlossaryEntry> = entries
+        .iter()
+        .filter(|e| !e.source.trim().is_empty())
+        .filter(|e| e.to.as_deref().is_none_or(|lang| lang == to))
+        .collect();
+    if applicable.is_empty() {
+        return Protected {
+            text: text.to_string(),
+            targets: Vec::new(),
+        };
+    }
+    applicable.sort_by_key(|e| std::cmp::Reverse(e.source.trim().chars().count()));
+    let pattern = applicable
+        .iter()
+        .map(|e| format!("({})", term_pattern(e.source.trim(), e.case_sensitive)))
+        .collect::<Vec<_>>()
+        .join("|");
+    let Ok(re) = Regex::new(&pattern) else {
+        return Protected {
+            text: text.to_string(),
+            targets: Vec::new(),
+        };
+    };
+
+    // 占位符编号按首次出现顺序分配，同一术语复用同一编号
+    let mut targets = Vec::new();
+    let mut slots: Vec<Option<usize>> = vec![None; applicable.len()];
+    let protected = re.replace_all(text, |caps: &regex::Captures| {
+        let entry_index = (1..caps.len())
+            .find(|&i| caps.get(i).is_some())
+            .map_or(0, |i| i - 1);
+        let slot = *slots[entry_index].get_or_insert_with(|| {
+            targets.push(applicable[entry_index].target.clone());
+            targets.len() - 1
+        });
+        format!("__GLS{slot}__")
+    });
+    Protected {
+        text: protected.into_owned(),
+        targets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: &str, target: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            source: source.into(),
+            target: target.into(),
+            case_sensitive: false,
+            to: None,
+        }
+    }
+
+    #[test]
+    fn protects_and_restores_terms() {
+        let entries = [
+            entry("Tauri", "Tauri"),
+            entry("clipboard", "剪贴板"),
+            entry("clipboard history", "剪贴板历史"),
+        ];
+        let protected = protect(
+            &entries,
+            "Clipboard history in Tauri; every clipboard entry.",
+            "zh",
+        );
+        assert_eq!(
+            protected.text,
+            "__GLS0__ in __GLS1__; every __GLS2__ entry."
+        );
+        // 模拟服务在占位符中插入空格 / 改变大小写
+        assert_eq!(
+            protected.restore("__GLS1__ 中的 __gls 0__；每个 __GLS2__ 条目。"),
+            "Tauri 中的 剪贴板历史；每个 剪贴板 条目。"
+        );
+    }
+
+    #[test]
+    fn respects_word_boundaries_case_and_language() {
+        let mut case_sensitive = entry("Go", "Go 语言");
+        case_sensitive.case_sensitive = true;
+        let mut japanese_only = entry("cat", "猫");
+        japanese_only.to = Some("ja".into());
+        let entries = [case_sensitive, japanese_only, entry("C++", "C++")];
+
+        let protected = protect(&entries, "Go go category cat C++", "zh");
+        assert_eq!(protected.text, "__GLS0__ go category cat __GLS1__");
+        let protected = protect(&entries, "category cat", "ja");
+        assert_eq!(protected.text, "category __GLS0__");
+        assert_eq!(protected.restore("カテゴリー __GLS0__"), "カテゴリー 猫");
+
+        let untouched = protect(&entries, "nothing here", "zh");
+        assert!(untouched.is_unchanged());
+        assert_eq!(untouched.restore("没有 __GLS0__"), "没有 __GLS0__");
+    }
+
+    #[test]
+    fn parse_and_validate() {
+        assert!(parse(None).is_empty());
+        assert!(parse(Some("{}")).is_empty());
+        assert_eq!(
+            parse(Some(r#"[{"source":"Rust","target":"Rust"}]"#)),
+            [entry("Rust", "Rust")]
+        );
+        assert!(validate(&[entry("a", "b"), entry("A", "c")]).is_err());
+        assert!(validate(&[entry(" ", "b")]).is_err());
+        let mut cased = entry("A", "c");
+        cased.case_sensitive = true;
+        assert!(validate(&[entry("a", "b"), cased]).is_ok());
+    }
+}
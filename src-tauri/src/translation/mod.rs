@@ -0,0 +1,329 @@
+//! 翻译服务链：按顺序尝试多个翻译服务，失败（含超时）时切换到下一个，并记录各服务健康状况。
+//! 具体服务的 HTTP 实现见 `commands::translate`，术语表见 [`glossary`]。
+//!
+//! 连续失败 [`COOLDOWN_AFTER_FAILURES`] 次的服务进入冷却期，冷却期内排到链尾，
+//! 仅在其它服务都失败时才会尝试。
+
+pub mod glossary;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// 设置项：备用翻译服务 ID 列表（JSON 数组，主服务之后依次尝试）
+pub const FALLBACK_SETTING: &str = "translate_fallback_providers";
+/// 支持的翻译服务
+pub const PROVIDERS: &[&str] = &[
+    "microsoft",
+    "google_free",
+    "google_api",
+    "baidu",
+    "deeplx",
+    "openai",
+];
+
+const COOLDOWN_AFTER_FAILURES: u32 = 3;
+const COOLDOWN: Duration = Duration::from_secs(60);
+
+/// 单个翻译服务
+pub trait Provider {
+    fn id(&self) -> &str;
+
+    /// 翻译文本；`on_partial` 存在时可推送已译出的内容（流式）
+    fn translate(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+        on_partial: Option<&mut dyn FnMut(&str)>,
+    ) -> Result<String, String>;
+}
+
+/// 翻译服务健康状况
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProviderHealth {
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_latency_ms: Option<u64>,
+    /// 是否处于冷却期（快照时计算）
+    pub cooling_down: bool,
+    #[serde(skip)]
+    last_failure: Option<Instant>,
+}
+
+impl ProviderHealth {
+    fn is_cooling_down(&self, now: Instant) -> bool {
+        self.consecutive_failures >= COOLDOWN_AFTER_FAILURES
+            && self
+                .last_failure
+                .is_some_and(|at| now.duration_since(at) < COOLDOWN)
+    }
+}
+
+#[derive(Default)]
+pub struct HealthTracker {
+    providers: Mutex<HashMap<String, ProviderHealth>>,
+}
+
+/// 进程内共享的健康记录
+pub static HEALTH: LazyLock<HealthTracker> = LazyLock::new(HealthTracker::default);
+
+impl HealthTracker {
+    pub fn record_success(&self, id: &str, latency: Duration) {
+        let mut providers = self.providers.lock();
+        let health = providers.entry(id.to_string()).or_default();
+        health.successes += 1;
+        health.consecutive_failures = 0;
+        health.last_latency_ms = Some(latency.as_millis() as u64);
+    }
+
+    pub fn record_failure(&self, id: &str, error: &str) {
+        self.record_failure_at(id, error, Instant::now());
+    }
+
+    fn record_failure_at(&self, id: &str, error: &str, at: Instant) {
+        let mut providers = self.providers.lock();
+        let health = providers.entry(id.to_string()).or_default();
+        health.failures += 1;
+        health.consecutive_failures += 1;
+        health.last_error = Some(error.to_string());
+        health.last_failure = Some(at);
+    }
+
+    pub fn is_cooling_down(&self, id: &str, now: Instant) -> bool {
+        self.providers
+            .lock()
+            .get(id)
+            .is_some_and(|h| h.is_cooling_down(now))
+    }
+
+    pub fn snapshot(&self) -> BTreeMap<String, ProviderHealth> {
+        let now = Instant::now();
+        self.providers
+            .lock()
+            .iter()
+            .map(|(id, health)| {
+                let mut health = health.clone();
+                health.cooling_down = health.is_cooling_down(now);
+                (id.clone(), health)
+            })
+            .collect()
+    }
+
+    pub fn reset(&self) {
+        self.providers.lock().clear();
+    }
+}
+
+/// 组装服务链：主服务在前，备用服务按配置顺序追加（去重、忽略未知 ID）
+pub fn parse_chain(primary: &str, fallbacks: Option<&str>) -> Vec<String> {
+    let fallbacks: Vec<String> = fallbacks
+        .and_then(|raw| serde_json::from_str(raw).ok())
+        .unwrap_or_default();
+    let mut chain = vec![primary.to_string()];
+    for id in fallbacks {
+        if PROVIDERS.contains(&id.as_str()) && !chain.contains(&id) {
+            chain.push(id);
+        }
+    }
+    chain
+}
+
+/// 服务链的执行结果
+#[derive(Debug)]
+pub struct ChainOutcome {
+    pub text: String,
+    /// 实际给出结果的服务
+    pub provider: String,
+}
+
+/// 依次尝试服务链，返回第一个成功的结果；全部失败时返回首个被尝试服务的错误
+/// （保持与单服务调用相同的错误码，便于前端本地化）。
+pub fn translate_with_fallback(
+    providers: &[&dyn Provider],
+    health: &HealthTracker,
+    text: &str,
+    from: &str,
+    to: &str,
+    mut on_partial: Option<&mut dyn FnMut(&str)>,
+) -> Result<ChainOutcome, String> {
+    let now = Instant::now();
+    let (healthy, cooling): (Vec<&dyn Provider>, Vec<&dyn Provider>) = providers
+        .iter()
+        .copied()
+        .partition(|p| !health.is_cooling_down(p.id(), now));
+
+    let mut first_error = None;
+    for provider in healthy.into_iter().chain(cooling) {
+        let started = Instant::now();
+        // 显式重新借用：`as_deref_mut` 无法缩短 trait 对象的生命周期
+        let partial: Option<&mut dyn FnMut(&str)> = match on_partial {
+            Some(ref mut f) => Some(&mut **f),
+            None => None,
+        };
+        match provider.translate(text, from, to, partial) {
+            Ok(translated) => {
+                health.record_success(provider.id(), started.elapsed());
+                if first_error.is_some() {
+                    info!("[TRANSLATE] fell back to provider {}", provider.id());
+                }
+                return Ok(ChainOutcome {
+                    text: translated,
+                    provider: provider.id().to_string(),
+                });
+            }
+            Err(e) => {
+                warn!("[TRANSLATE] provider {} failed: {}", provider.id(), e);
+                health.record_failure(provider.id(), &e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "TRANSLATE:NO_PROVIDER".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct MockProvider {
+        id: &'static str,
+        result: Result<&'static str, &'static str>,
+        calls: Cell<u32>,
+    }
+
+    impl MockProvider {
+        fn ok(id: &'static str, text: &'static str) -> Self {
+            Self {
+                id,
+                result: Ok(text),
+                calls: Cell::new(0),
+            }
+        }
+
+        fn failing(id: &'static str, error: &'static str) -> Self {
+            Self {
+                id,
+                result: Err(error),
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl Provider for MockProvider {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn translate(
+            &self,
+            text: &str,
+            _from: &str,
+            _to: &str,
+            on_partial: Option<&mut dyn FnMut(&str)>,
+        ) -> Result<String, String> {
+            self.calls.set(self.calls.get() + 1);
+            let translated = self.result.map_err(str::to_string)?;
+            if let Some(on_partial) = on_partial {
+                on_partial(&translated[..1]);
+            }
+            Ok(format!("{translated}:{text}"))
+        }
+    }
+
+    #[test]
+    fn falls_back_on_failure() {
+        let health = HealthTracker::default();
+        let microsoft = MockProvider::failing("microsoft", "TRANSLATE:TOKEN_FAILED:timeout");
+        let google = MockProvider::ok("google_free", "G");
+        let deeplx = MockProvider::ok("deeplx", "D");
+        let mut partials = Vec::new();
+        let outcome = translate_with_fallback(
+            &[&microsoft, &google, &deeplx],
+            &health,
+            "hi",
+            "en",
+            "zh",
+            Some(&mut |p: &str| partials.push(p.to_string())),
+        )
+        .unwrap();
+        assert_eq!(outcome.text, "G:hi");
+        assert_eq!(outcome.provider, "google_free");
+        assert_eq!(partials, ["G"]);
+        assert_eq!(deeplx.calls.get(), 0);
+
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot["microsoft"].failures, 1);
+        assert_eq!(
+            snapshot["microsoft"].last_error.as_deref(),
+            Some("TRANSLATE:TOKEN_FAILED:timeout")
+        );
+        assert_eq!(snapshot["google_free"].successes, 1);
+        assert!(snapshot["google_free"].last_latency_ms.is_some());
+    }
+
+    #[test]
+    fn all_failing_returns_first_error() {
+        let health = HealthTracker::default();
+        let a = MockProvider::failing("microsoft", "TRANSLATE:TOKEN_FAILED:x");
+        let b = MockProvider::failing("deeplx", "TRANSLATE:INVALID_CONFIG:DEEPLX_ENDPOINT");
+        let err = translate_with_fallback(&[&a, &b], &health, "hi", "en", "zh", None).unwrap_err();
+        assert_eq!(err, "TRANSLATE:TOKEN_FAILED:x");
+        assert_eq!((a.calls.get(), b.calls.get()), (1, 1));
+        assert_eq!(
+            translate_with_fallback(&[], &health, "hi", "en", "zh", None).unwrap_err(),
+            "TRANSLATE:NO_PROVIDER"
+        );
+    }
+
+    #[test]
+    fn unhealthy_provider_moves_to_end_until_cooldown_expires() {
+        let health = HealthTracker::default();
+        for _ in 0..COOLDOWN_AFTER_FAILURES {
+            health.record_failure("microsoft", "TRANSLATE:TOKEN_FAILED:x");
+        }
+        let microsoft = MockProvider::ok("microsoft", "M");
+        let google = MockProvider::ok("google_free", "G");
+        let outcome =
+            translate_with_fallback(&[&microsoft, &google], &health, "hi", "en", "zh", None)
+                .unwrap();
+        assert_eq!(outcome.provider, "google_free");
+        assert_eq!(microsoft.calls.get(), 0);
+        assert!(health.snapshot()["microsoft"].cooling_down);
+
+        // 冷却期内若其它服务都失败，仍会尝试冷却中的服务
+        let google = MockProvider::failing("google_free", "TRANSLATE:REQUEST_FAILED:x");
+        let outcome =
+            translate_with_fallback(&[&microsoft, &google], &health, "hi", "en", "zh", None)
+                .unwrap();
+        assert_eq!(outcome.provider, "microsoft");
+        assert_eq!(health.snapshot()["microsoft"].consecutive_failures, 0);
+
+        // 冷却期过后恢复原顺序
+        let health = HealthTracker::default();
+        let long_ago = Instant::now() - COOLDOWN - Duration::from_secs(1);
+        for _ in 0..COOLDOWN_AFTER_FAILURES {
+            health.record_failure_at("microsoft", "x", long_ago);
+        }
+        assert!(!health.is_cooling_down("microsoft", Instant::now()));
+    }
+
+    #[test]
+    fn chain_dedups_and_ignores_unknown_providers() {
+        assert_eq!(parse_chain("microsoft", None), ["microsoft"]);
+        assert_eq!(
+            parse_chain(
+                "microsoft",
+                Some(r#"["google_free","microsoft","nope","deeplx","google_free"]"#)
+            ),
+            ["microsoft", "google_free", "deeplx"]
+        );
+        assert_eq!(parse_chain("openai", Some("not json")), ["openai"]);
+    }
+}
@@ -19,6 +19,7 @@ const MAIN_TRAY_ID: &str = "main-tray";
 struct TrayMenuItems {
     pause_item: MenuItem<tauri::Wry>,
     shortcut_item: MenuItem<tauri::Wry>,
+    collect_item: MenuItem<tauri::Wry>,
     settings_item: MenuItem<tauri::Wry>,
     check_update_item: MenuItem<tauri::Wry>,
     restart_item: MenuItem<tauri::Wry>,
@@ -61,6 +62,7 @@ fn apply_tray_menu_labels(app: &AppHandle, items: &TrayMenuItems, i18n: &TrayI18
     } else {
         &i18n.disable_shortcuts
     });
+    let _ = items.collect_item.set_text(collect_label(&i18n));
     let _ = items.settings_item.set_text(&i18n.settings);
     let _ = items.check_update_item.set_text(&i18n.check_update);
     let _ = items.restart_item.set_text(&i18n.restart);
@@ -76,6 +78,23 @@ fn apply_tray_menu_labels(app: &AppHandle, items: &TrayMenuItems, i18n: &TrayI18
     }
 }
 
+fn collect_label(i18n: &TrayI18n) -> &str {
+    if crate::clipboard::collect::is_active() {
+        &i18n.stop_collect
+    } else {
+        &i18n.start_collect
+    }
+}
+
+/// 收集模式切换后刷新托盘菜单文案（托盘、快捷键、前端均可切换）
+pub(crate) fn refresh_collect_label() {
+    let guard = TRAY_STATE.lock();
+    if let Some((items, loc)) = guard.as_ref() {
+        let i18n = TrayI18n::from_locale(loc);
+        let _ = items.collect_item.set_text(collect_label(&i18n));
+    }
+}
+
 /// 初始化系统托盘图标和菜单
 pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let icon_data = include_bytes!("../../icons/icon.png");
@@ -96,6 +115,13 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         true,
         None::<&str>,
     )?;
+    let collect_item = MenuItem::with_id(
+        app,
+        "toggle_collect",
+        collect_label(&i18n),
+        true,
+        None::<&str>,
+    )?;
     let separator1 = PredefinedMenuItem::separator(app)?;
     let settings_item = MenuItem::with_id(app, "settings", &i18n.settings, true, None::<&str>)?;
     let check_update_item =
@@ -109,6 +135,7 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         &[
             &pause_item,
             &shortcut_item,
+            &collect_item,
             &separator1,
             &settings_item,
             &check_update_item,
@@ -123,6 +150,7 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         TrayMenuItems {
             pause_item: pause_item.clone(),
             shortcut_item: shortcut_item.clone(),
+            collect_item: collect_item.clone(),
             settings_item: settings_item.clone(),
             check_update_item: check_update_item.clone(),
             restart_item: restart_item.clone(),
@@ -173,6 +201,18 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
                         });
                     }
                 }
+                "toggle_collect" => {
+                    if let Some(state) = app.try_state::<Arc<AppState>>() {
+                        let state = state.inner().clone();
+                        let app = app.clone();
+                        // 结束收集需写剪贴板并等待监控恢复，不阻塞菜单事件线程
+                        std::thread::spawn(move || {
+                            if let Err(e) = crate::commands::collect::toggle(&state, &app) {
+                                tracing::warn!("Failed to toggle collect mode: {}", e);
+                            }
+                        });
+                    }
+                }
                 _ => handle_menu_event(app, id),
             }
         })
@@ -7,6 +7,8 @@ pub struct TrayI18n {
     pub resume_monitor: String,
     pub disable_shortcuts: String,
     pub restore_shortcuts: String,
+    pub start_collect: String,
+    pub stop_collect: String,
     pub settings: String,
     pub check_update: String,
     pub restart: String,
@@ -23,6 +25,8 @@ impl TrayI18n {
                 resume_monitor: "Resume Monitoring".into(),
                 disable_shortcuts: "Disable Shortcuts".into(),
                 restore_shortcuts: "Restore Shortcuts".into(),
+                start_collect: "Start Collecting".into(),
+                stop_collect: "Finish Collecting".into(),
                 settings: "Settings".into(),
                 check_update: "Check for Updates".into(),
                 restart: "Restart".into(),
@@ -34,6 +38,8 @@ impl TrayI18n {
                 resume_monitor: "恢復監控".into(),
                 disable_shortcuts: "停用快捷鍵".into(),
                 restore_shortcuts: "恢復快捷鍵".into(),
+                start_collect: "開始收集".into(),
+                stop_collect: "結束收集".into(),
                 settings: "設定".into(),
                 check_update: "檢查更新".into(),
                 restart: "重新啟動".into(),
@@ -50,6 +56,8 @@ impl TrayI18n {
             resume_monitor: "恢复监控".into(),
             disable_shortcuts: "禁用快捷键".into(),
             restore_shortcuts: "恢复快捷键".into(),
+            start_collect: "开始收集".into(),
+            stop_collect: "结束收集".into(),
             settings: "设置".into(),
             check_update: "检查更新".into(),
             restart: "重启程序".into(),
@@ -68,6 +76,7 @@ mod tests {
         let i18n = TrayI18n::from_locale("en");
         assert_eq!(i18n.settings, "Settings");
         assert_eq!(i18n.pause_monitor, "Pause Monitoring");
+        assert_eq!(i18n.stop_collect, "Finish Collecting");
     }
 
     #[test]
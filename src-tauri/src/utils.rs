@@ -9,9 +9,24 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// 转义 HTML 文本中的 `& < > "`（用于拼接 HTML 片段与导出页面）
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
-    use super::format_size;
+    use super::{escape_html, format_size};
 
     #[test]
     fn zero_bytes() {
@@ -37,4 +52,13 @@ mod tests {
     fn fractional_kb() {
         assert_eq!(format_size(1536), "1.5 KB");
     }
+
+    #[test]
+    fn escapes_html_special_chars() {
+        assert_eq!(
+            escape_html(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain 文本"), "plain 文本");
+    }
 }
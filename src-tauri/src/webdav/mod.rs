@@ -116,43 +116,35 @@ fn safe_media_file_name(name: &str) -> Option<String> {
 /// 写入位置完全由本机数据目录 + 内容 hash 构造，与来源设备的路径无关，
 /// 因此跨设备同步不再依赖两端目录一致。
 pub fn local_media_target(entry: &MediaEntry, data_dir: &Path) -> Option<PathBuf> {
-    if entry.hash.is_empty() {
+    // 哈希作为 blob 目录名，只接受 BLAKE3 十六进制串
+    if entry.hash.len() != 64 || !entry.hash.bytes().all(|b| b.is_ascii_hexdigit()) {
         return None;
     }
-    match entry.media_type.as_str() {
+    let name = match entry.media_type.as_str() {
         "image" => {
             let ext = if entry.ext.is_empty() {
                 "png"
             } else {
                 &entry.ext
             };
-            Some(
-                data_dir
-                    .join("images")
-                    .join(format!("{}.{ext}", entry.hash)),
-            )
+            safe_media_file_name(&format!("image.{ext}"))?
         }
         // 图标保留原文件名（{cache_key}.png），使本机后续捕获能命中图标缓存
-        "icon" => {
-            let name = file_name_from_any_path(&entry.local_path)?;
-            Some(data_dir.join("icons").join(name))
-        }
+        "icon" => file_name_from_any_path(&entry.local_path)?,
         "file" => {
-            let name = if entry.file_name.is_empty() {
+            if entry.file_name.is_empty() {
                 file_name_from_any_path(&entry.local_path)?
             } else {
                 safe_media_file_name(&entry.file_name)?
-            };
-            let prefix = &entry.hash[..16.min(entry.hash.len())];
-            Some(
-                data_dir
-                    .join("staged")
-                    .join("webdav")
-                    .join(format!("{prefix}_{name}")),
-            )
+            }
         }
-        _ => None,
-    }
+        _ => return None,
+    };
+    Some(crate::blobs::object_path(
+        &data_dir.join(crate::blobs::DIR_NAME),
+        &entry.hash,
+        &crate::blobs::safe_name(&name),
+    ))
 }
 
 /// 获取或创建设备唯一标识（存储在 settings 表中）
@@ -179,6 +171,17 @@ fn file_hash_from_path(path: &Path) -> Result<(String, u64), String> {
     Ok((hasher.finalize().to_hex().to_string(), bytes))
 }
 
+/// blob 存储中的文件直接取路径中的哈希，其余文件计算 BLAKE3
+fn media_hash(path: &Path) -> Result<(String, u64), String> {
+    if let Some(hash) = crate::blobs::hash_from_path(&path.to_string_lossy()) {
+        let len = std::fs::metadata(path)
+            .map_err(|e| format!("读取文件失败 {}: {}", path.display(), e))?
+            .len();
+        return Ok((hash.to_string(), len));
+    }
+    file_hash_from_path(path)
+}
+
 fn file_len_if_within_limit(path: &Path, max_bytes: i64) -> Option<u64> {
     let len = std::fs::metadata(path).ok()?.len();
     if max_bytes >= 0 && len > max_bytes as u64 {
@@ -320,9 +323,14 @@ pub fn query_sync_items(
     options: &SyncOptions,
 ) -> Result<Vec<crate::database::ClipboardItem>, String> {
     let (include_text, image_max, files_max) = sync_query_limits(options);
-    crate::database::ClipboardRepository::new(db)
+    let mut items = crate::database::ClipboardRepository::new(db)
         .query_items_for_sync(include_text, image_max, files_max)
-        .map_err(|e| format!("查询条目失败: {e}"))
+        .map_err(|e| format!("查询条目失败: {e}"))?;
+    // 标签按名称随条目同步，导入端按名称合并
+    crate::database::TagRepository::new(db)
+        .attach_tags(&mut items)
+        .map_err(|e| format!("查询标签失败: {e}"))?;
+    Ok(items)
 }
 
 /// 导出同步 ZIP（设置 + 条目元数据 + 媒体映射表，不含二进制文件）
@@ -508,7 +516,7 @@ pub fn build_media_map(
                     images_dir.join(img_path)
                 };
                 if let Some(size) = file_len_if_within_limit(&full_path, max_image_bytes)
-                    && let Ok((hash, _)) = file_hash_from_path(&full_path)
+                    && let Ok((hash, _)) = media_hash(&full_path)
                 {
                     seen_hashes.insert(hash.clone());
                     let ext = full_path
@@ -546,7 +554,7 @@ pub fn build_media_map(
                     icons_dir.join(icon_path)
                 };
                 if full_path.is_file()
-                    && let Ok((hash, size)) = file_hash_from_path(&full_path)
+                    && let Ok((hash, size)) = media_hash(&full_path)
                     && seen_hashes.insert(hash.clone())
                 {
                     let ext = full_path
@@ -606,7 +614,7 @@ pub fn build_media_map(
                     paths.iter().zip(resolved.iter()).zip(file_checks.iter())
                 {
                     let p = Path::new(resolved_path);
-                    match file_hash_from_path(p) {
+                    match media_hash(p) {
                         Ok((hash, _)) => {
                             let ext = p
                                 .extension()
@@ -750,7 +758,7 @@ pub fn upload_media_files(
     Ok((uploaded, skipped, total_bytes))
 }
 
-/// 下载缺失的媒体文件（按内容 hash 落地到本机数据目录，与来源设备路径无关）
+/// 下载缺失的媒体文件（校验内容 hash 后存入本机 blob 存储，与来源设备路径无关）
 /// 返回下载数量
 pub fn download_missing_media(
     config: &WebDavConfig,
@@ -760,6 +768,7 @@ pub fn download_missing_media(
     let client = build_client(config)?;
     let auth = basic_auth(&config.username, &config.password);
     let base_url = normalize_url(&config.url, &config.remote_dir);
+    let store = crate::blobs::global().ok_or("blob 存储未初始化")?;
 
     let mut downloaded = 0usize;
 
@@ -786,29 +795,21 @@ pub fn download_missing_media(
             .map_err(|e| format!("下载 {remote_path} 失败: {e}"))?;
 
         if resp.status().is_success() {
-            if let Some(parent) = local_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            let tmp_path = local_path.with_extension(format!(
-                "{}.download",
-                local_path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("tmp")
-            ));
+            let name = local_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let Some(kind) = crate::blobs::BlobKind::parse(&entry.media_type) else {
+                continue;
+            };
+            // 经 blob 存储写入：校验 BLAKE3 哈希并登记，内容不符的下载被丢弃
             let mut body = resp;
-            match std::fs::File::create(&tmp_path)
-                .and_then(|mut file| std::io::copy(&mut body, &mut file).map(|_| ()))
-                .and_then(|_| std::fs::rename(&tmp_path, &local_path))
-            {
-                Ok(()) => {
+            match store.put_verified(&mut body, &entry.hash, &name, kind) {
+                Ok(blob) => {
                     downloaded += 1;
-                    info!("媒体下载: {} -> {}", remote_path, local_path.display());
-                }
-                Err(e) => {
-                    let _ = std::fs::remove_file(&tmp_path);
-                    warn!("媒体下载写入失败 {}: {}", local_path.display(), e);
+                    info!("媒体下载: {} -> {}", remote_path, blob.path.display());
                 }
+                Err(e) => warn!("媒体下载写入失败 {}: {}", local_path.display(), e),
             }
         }
     }
@@ -861,6 +862,11 @@ pub fn import_sync_data(
                 "webdav_proxy_mode",
                 "webdav_proxy_url",
                 "device_id",
+                // 本地 API 令牌按安装生成，不跨设备同步
+                "api_enabled",
+                "api_port",
+                "api_socket_path",
+                "api_token",
             ]
             .into_iter()
             .collect();
@@ -999,6 +1005,7 @@ pub fn rewrite_item_media_paths(
                             original: path.clone(),
                             staged: target_str,
                             size: entry.size,
+                            snapshot: None,
                         });
                     payload_changed = true;
                 }
@@ -2005,51 +2012,56 @@ mod tests {
         assert_eq!(image, Some(i64::MAX));
     }
 
+    fn blob_hash(fill: char) -> String {
+        std::iter::repeat_n(fill, 64).collect()
+    }
+
+    fn blob_target(data_dir: &str, hash: &str, name: &str) -> PathBuf {
+        crate::blobs::object_path(&Path::new(data_dir).join("blobs"), hash, name)
+    }
+
     #[test]
-    fn media_target_image_uses_hash_in_local_images_dir() {
-        let entry = media_entry("image", "C:\\Users\\A\\data\\images\\old.png", "abc123");
+    fn media_target_image_uses_hash_in_blob_store() {
+        let hash = blob_hash('a');
+        let entry = media_entry("image", "C:\\Users\\A\\data\\images\\old.png", &hash);
         let target = local_media_target(&entry, Path::new("D:\\data")).unwrap();
-        assert_eq!(
-            target,
-            Path::new("D:\\data").join("images").join("abc123.png")
-        );
+        assert_eq!(target, blob_target("D:\\data", &hash, "image.png"));
     }
 
     #[test]
     fn media_target_icon_keeps_cache_filename() {
-        let entry = media_entry("icon", "C:\\Users\\A\\data\\icons\\key123.png", "h1");
+        let hash = blob_hash('b');
+        let entry = media_entry("icon", "C:\\Users\\A\\data\\icons\\key123.png", &hash);
         let target = local_media_target(&entry, Path::new("D:\\data")).unwrap();
-        assert_eq!(
-            target,
-            Path::new("D:\\data").join("icons").join("key123.png")
-        );
+        assert_eq!(target, blob_target("D:\\data", &hash, "key123.png"));
     }
 
     #[test]
-    fn media_target_file_goes_to_staged_webdav() {
-        let mut entry = media_entry("file", "D:\\Downloads\\report.zip", "aabbccddeeff00112233");
+    fn media_target_file_keeps_file_name() {
+        let hash = blob_hash('c');
+        let mut entry = media_entry("file", "D:\\Downloads\\report.zip", &hash);
         entry.file_name = "report.zip".to_string();
         let target = local_media_target(&entry, Path::new("E:\\app")).unwrap();
+        assert_eq!(target, blob_target("E:\\app", &hash, "report.zip"));
         assert_eq!(
-            target,
-            Path::new("E:\\app")
-                .join("staged")
-                .join("webdav")
-                .join("aabbccddeeff0011_report.zip")
+            crate::blobs::hash_from_path(&target.to_string_lossy()),
+            Some(hash.as_str())
         );
     }
 
     #[test]
     fn media_target_file_falls_back_to_local_path_name() {
-        let entry = media_entry("file", "D:\\Downloads\\notes.txt", "hash1234");
+        let entry = media_entry("file", "D:\\Downloads\\notes.txt", &blob_hash('d'));
         let target = local_media_target(&entry, Path::new("E:\\app")).unwrap();
-        assert!(target.to_string_lossy().ends_with("hash1234_notes.txt"));
+        assert!(target.to_string_lossy().ends_with("notes.txt"));
     }
 
     #[test]
-    fn media_target_rejects_empty_hash() {
+    fn media_target_rejects_empty_or_invalid_hash() {
         let entry = media_entry("image", "a.png", "");
         assert!(local_media_target(&entry, Path::new("D:\\data")).is_none());
+        let entry = media_entry("image", "a.png", "..");
+        assert!(local_media_target(&entry, Path::new("D:\\data")).is_none());
     }
 
     #[test]
@@ -2098,16 +2110,25 @@ mod tests {
             source_app_name: None,
             source_app_icon: None,
             group_id: None,
+            ocr_text: None,
+            subtypes: None,
+            expires_at: None,
+            derived_from: None,
+            derivation: None,
+            staging_state: None,
+            media_missing: false,
+            tags: None,
             files_valid: None,
         }
     }
 
     #[test]
     fn rewrite_image_path_to_local_target() {
+        let hash = blob_hash('1');
         let map = vec![media_entry(
             "image",
             "C:\\other-device\\images\\x.png",
-            "hash1",
+            &hash,
         )];
         let index = build_media_index(&map);
         let mut item = test_item("image");
@@ -2117,13 +2138,14 @@ mod tests {
         assert!(changed);
         assert_eq!(
             item.image_path.as_deref().map(std::path::PathBuf::from),
-            Some(Path::new("D:\\data").join("images").join("hash1.png"))
+            Some(blob_target("D:\\data", &hash, "image.png"))
         );
     }
 
     #[test]
     fn rewrite_files_payload_adds_staged_entry() {
-        let mut entry = media_entry("file", "C:\\other-device\\doc.pdf", "hash2");
+        let hash = blob_hash('2');
+        let mut entry = media_entry("file", "C:\\other-device\\doc.pdf", &hash);
         entry.ext = "pdf".into();
         entry.file_name = "doc.pdf".into();
         entry.size = 42;
@@ -2141,8 +2163,11 @@ mod tests {
         assert_eq!(payload.staged.len(), 1);
         assert_eq!(payload.staged[0].original, "C:\\other-device\\doc.pdf");
         assert_eq!(payload.staged[0].size, 42);
-        assert!(payload.staged[0].staged.contains("staged"));
-        assert!(payload.staged[0].staged.ends_with("hash2_doc.pdf"));
+        assert_eq!(
+            crate::blobs::hash_from_path(&payload.staged[0].staged),
+            Some(hash.as_str())
+        );
+        assert!(payload.staged[0].staged.ends_with("doc.pdf"));
     }
 
     #[test]
@@ -2172,7 +2197,7 @@ mod tests {
 
     #[test]
     fn media_target_file_rejects_traversal_in_file_name() {
-        let mut entry = media_entry("file", "D:\\Downloads\\report.zip", "aabbccddeeff00112233");
+        let mut entry = media_entry("file", "D:\\Downloads\\report.zip", &blob_hash('e'));
         entry.file_name = "..\\..\\evil.exe".to_string();
         assert!(local_media_target(&entry, Path::new("E:\\app")).is_none());
     }
@@ -20,6 +20,7 @@ import debounce from "lodash.debounce";
 import { useShallow } from "zustand/react/shallow";
 import { ClipboardList } from "@/components/ClipboardList";
 import { Onboarding } from "@/components/Onboarding";
+import { PasteQueueBar } from "@/components/PasteQueueBar";
 import { Button } from "@/components/ui/button";
 import {
   Dialog,
@@ -30,6 +31,13 @@ import {
   DialogTitle,
 } from "@/components/ui/dialog";
 import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import { showToast } from "@/components/ui/toast";
 import {
   Tooltip,
@@ -47,6 +55,7 @@ import { filterToolbarButtonsForWebDAV } from "@/lib/webdav-availability";
 import { useClipboardStore } from "@/stores/clipboard";
 import { useGroupStore } from "@/stores/groups";
 import type { Group } from "@/stores/groups";
+import { initPasteQueue, usePasteQueue } from "@/stores/paste-queue";
 import type { ToolbarButton } from "@/stores/ui-settings";
 import { isUISettingsInitialized, useUISettings, whenUISettingsReady } from "@/stores/ui-settings";
 import { initWebDAVSyncListeners } from "@/stores/webdav-sync";
@@ -77,6 +86,8 @@ function App() {
   const [renameName, setRenameName] = useState("");
   const [deleteGroupDialogOpen, setDeleteGroupDialogOpen] = useState(false);
   const [deleteGroupTarget, setDeleteGroupTarget] = useState<Group | null>(null);
+  /** 被删除分组中条目的去向（null = 默认分组） */
+  const [deleteGroupMoveTo, setDeleteGroupMoveTo] = useState<number | null>(null);
 
   const { searchQuery, selectedGroup, selectedGroupId, setSearchQuery, setSelectedGroup, setSelectedGroupId, fetchItems, clearHistory, refresh, resetView, itemCount } = useClipboardStore(
     useShallow((s) => ({
@@ -142,6 +153,28 @@ function App() {
     fetchGroups();
   }, []);
 
+  // 粘贴队列状态由后端推送（快捷键出队也会更新）
+  useEffect(() => {
+    void initPasteQueue();
+  }, []);
+
+  // 合并导入备份后刷新分组与列表
+  useEffect(() => {
+    const unlisten = listen("history-imported", () => {
+      fetchGroups();
+      refresh();
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, [fetchGroups, refresh]);
+
+  // 本地 API 新建分组
+  useEffect(() => {
+    const unlisten = listen("groups-changed", () => {
+      fetchGroups();
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, [fetchGroups]);
+
   // 同步文件预览大小限制（与 settings max_image_size_kb 一致）
   useEffect(() => {
     void syncFilePreviewLimitsFromSettings();
@@ -242,13 +275,14 @@ function App() {
 
   const requestDeleteGroup = (group: Group) => {
     setDeleteGroupTarget(group);
+    setDeleteGroupMoveTo(null);
     setDeleteGroupDialogOpen(true);
   };
 
   const confirmDeleteGroup = async () => {
     if (!deleteGroupTarget) return;
     const group = deleteGroupTarget;
-    await deleteGroup(group.id);
+    await deleteGroup(group.id, deleteGroupMoveTo);
     if (selectedGroupId === group.id) {
       setSelectedGroupId(null);
     }
@@ -645,6 +679,16 @@ function App() {
             >
               {t("app.batchMergePaste")}
             </button>
+            <button
+              onClick={() => {
+                void usePasteQueue.getState().add(Array.from(selectedIds));
+                setBatchMode(false);
+              }}
+              disabled={selectedIds.size === 0}
+              className="text-xs px-2 py-1 rounded-md bg-primary-subtle text-primary hover:bg-primary-subtle-hover transition-surface disabled:opacity-40 disabled:cursor-not-allowed"
+            >
+              {t("clipboard.pasteQueue.batchAdd")}
+            </button>
             <button
               onClick={() => setBatchDeleteDialogOpen(true)}
               disabled={selectedIds.size === 0}
@@ -662,6 +706,8 @@ function App() {
         </div>
       )}
 
+      <PasteQueueBar />
+
       {/* 剪贴板列表 */}
       <div className="flex-1 overflow-hidden">
         <ClipboardList searchInputRef={inputRef} />
@@ -925,6 +971,23 @@ function App() {
               )}
             </DialogDescription>
           </DialogHeader>
+          <div className="space-y-1.5">
+            <label className="text-xs text-muted-foreground">{t("groups.deleteGroupMoveTo")}</label>
+            <Select
+              value={deleteGroupMoveTo === null ? "default" : String(deleteGroupMoveTo)}
+              onValueChange={(v) => setDeleteGroupMoveTo(v === "default" ? null : Number(v))}
+            >
+              <SelectTrigger className="h-8 text-xs"><SelectValue /></SelectTrigger>
+              <SelectContent>
+                <SelectItem value="default">{t("groups.defaultGroupFull")}</SelectItem>
+                {groups
+                  .filter((g) => g.id !== deleteGroupTarget?.id)
+                  .map((g) => (
+                    <SelectItem key={g.id} value={String(g.id)}>{g.name}</SelectItem>
+                  ))}
+              </SelectContent>
+            </Select>
+          </div>
           <DialogFooter>
             <Button variant="outline" onClick={() => setDeleteGroupDialogOpen(false)}>
               {t("common.cancel")}
@@ -19,7 +19,7 @@ import { createLeaseManager } from "@/lib/lease-manager";
 import { logError } from "@/lib/logger";
 import { getPreviewPresentation } from "@/lib/preview-presentation";
 import { cn } from "@/lib/utils";
-import { useClipboardStore } from "@/stores/clipboard";
+import { type ClipboardItem, useClipboardStore } from "@/stores/clipboard";
 import { useUISettings } from "@/stores/ui-settings";
 
 // ============ 卡片底栏 ============
@@ -597,6 +597,8 @@ interface ImageCardProps {
   sourceAppIcon?: string | null;
   imageWidth?: number | null;
   imageHeight?: number | null;
+  /** 存储检查已标记文件缺失，不再尝试加载 */
+  mediaMissing?: boolean;
 }
 
 export const ImageCard = memo(function ImageCard({
@@ -609,6 +611,7 @@ export const ImageCard = memo(function ImageCard({
   sourceAppIcon,
   imageWidth,
   imageHeight,
+  mediaMissing,
 }: ImageCardProps) {
   const { t } = useTranslation();
   const [error, setError] = useState(false);
@@ -617,11 +620,13 @@ export const ImageCard = memo(function ImageCard({
 
   return (
     <div className="flex-1 min-w-0 px-3 py-2.5">
-      {error ? (
+      {error || mediaMissing ? (
         <div className="relative w-full h-32 rounded-md overflow-hidden bg-muted-surface-faint flex items-center justify-center">
           <div className="text-center">
             <Warning16Regular className="w-6 h-6 text-muted-foreground/40 mx-auto mb-1" />
-            <p className="text-xs text-muted-foreground/60">{t("cardContent.imageLoadFailed")}</p>
+            <p className="text-xs text-muted-foreground/60">
+              {t(mediaMissing ? "cardContent.imageMissing" : "cardContent.imageLoadFailed")}
+            </p>
           </div>
         </div>
       ) : (
@@ -768,6 +773,35 @@ interface FileContentProps {
   byteSize?: number;
   /** 后端标记文件超过阈值，前端据此跳过图片预览 */
   tooLarge?: boolean;
+  /** 文件后台复制状态 */
+  stagingState?: ClipboardItem["staging_state"];
+  /** 文件后台复制进度百分比（复制中时有值） */
+  stagingPercent?: number;
+}
+
+function StagingLabel({
+  state,
+  percent,
+}: {
+  state?: ClipboardItem["staging_state"];
+  percent?: number;
+}) {
+  const { t } = useTranslation();
+  if (state === "pending") {
+    return (
+      <span className="ml-1.5 text-xs font-normal text-muted-foreground/70">
+        {t("cardContent.staging", { percent: percent ?? 0 })}
+      </span>
+    );
+  }
+  if (state === "partial" || state === "failed") {
+    return (
+      <span className="ml-1.5 text-xs font-normal text-destructive/80">
+        {t(state === "partial" ? "cardContent.stagingPartial" : "cardContent.stagingFailed")}
+      </span>
+    );
+  }
+  return null;
 }
 
 export const FileContent = memo(function FileContent({
@@ -782,6 +816,8 @@ export const FileContent = memo(function FileContent({
   sourceAppIcon,
   byteSize,
   tooLarge: backendTooLarge,
+  stagingState,
+  stagingPercent,
 }: FileContentProps) {
   const { t } = useTranslation();
   const isMultiple = filePaths.length > 1;
@@ -842,6 +878,7 @@ export const FileContent = memo(function FileContent({
                 {filesInvalid && (
                   <span className="ml-1.5 text-xs font-normal">{t("cardContent.invalid")}</span>
                 )}
+                {!filesInvalid && <StagingLabel state={stagingState} percent={stagingPercent} />}
               </p>
               <p
                 className={cn(
@@ -876,6 +913,7 @@ export const FileContent = memo(function FileContent({
                 {skipImagePreview && !filesInvalid && (
                   <span className="ml-1.5 text-xs font-normal text-muted-foreground/70">{t("cardContent.fileTooLarge")}</span>
                 )}
+                {!filesInvalid && <StagingLabel state={stagingState} percent={stagingPercent} />}
               </p>
               <p
                 className={cn(
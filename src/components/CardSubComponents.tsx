@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import {
   Pin16Regular,
   Pin16Filled,
@@ -12,6 +12,7 @@ import {
   Warning16Regular,
   ChevronDown16Regular,
 } from "@fluentui/react-icons";
+import { invoke } from "@tauri-apps/api/core";
 import { Button } from "@/components/ui/button";
 import {
   ContextMenuItem,
@@ -29,6 +30,8 @@ import {
   TooltipTrigger,
 } from "@/components/ui/tooltip";
 import { useTranslation } from "@/i18n";
+import { showToast } from "@/components/ui/toast";
+import { logError } from "@/lib/logger";
 import { cn } from "@/lib/utils";
 import type { ClipboardItem } from "@/stores/clipboard";
 
@@ -126,6 +129,32 @@ export const FileDetailsDialog = ({
   );
 };
 
+// ============ 内联折叠菜单行 ============
+
+/** 右键菜单中可展开的分区标题（点击不关闭菜单） */
+function ExpandableMenuRow({
+  label,
+  expanded,
+  onToggle,
+}: {
+  label: string;
+  expanded: boolean;
+  onToggle: () => void;
+}) {
+  return (
+    <div
+      role="menuitem"
+      onClick={(e) => { e.preventDefault(); e.stopPropagation(); onToggle(); }}
+      className="flex cursor-default select-none items-center rounded-md px-2 py-1.5 text-sm outline-none focus:bg-accent focus:text-accent-foreground hover:bg-accent hover:text-accent-foreground"
+    >
+      <span>{label}</span>
+      <ChevronDown16Regular
+        className={cn("ml-auto h-4 w-4 transition-transform duration-150", expanded && "rotate-180")}
+      />
+    </div>
+  );
+}
+
 // ============ 移动到分组（内联折叠） ============
 
 export function MoveToGroupSection({
@@ -148,16 +177,11 @@ export function MoveToGroupSection({
   return (
     <>
       <ContextMenuSeparator />
-      <div
-        role="menuitem"
-        onClick={(e) => { e.preventDefault(); e.stopPropagation(); setExpanded((v) => !v); }}
-        className="flex cursor-default select-none items-center rounded-md px-2 py-1.5 text-sm outline-none focus:bg-accent focus:text-accent-foreground hover:bg-accent hover:text-accent-foreground"
-      >
-        <span>{t("groups.moveToGroup")}</span>
-        <ChevronDown16Regular
-          className={cn("ml-auto h-4 w-4 transition-transform duration-150", expanded && "rotate-180")}
-        />
-      </div>
+      <ExpandableMenuRow
+        label={t("groups.moveToGroup")}
+        expanded={expanded}
+        onToggle={() => setExpanded((v) => !v)}
+      />
       {expanded && (
         <>
           {showDefault && (
@@ -176,6 +200,118 @@ export function MoveToGroupSection({
   );
 }
 
+// ============ 插件操作（内联折叠） ============
+
+interface ItemAction {
+  plugin_id: string;
+  plugin_name: string;
+  action_id: string;
+  title: string;
+  output: "new_item" | "clipboard";
+}
+
+interface PluginRunResult {
+  output: "new_item" | "clipboard";
+  item_id: number | null;
+}
+
+export function PluginActionsSection({ itemId }: { itemId: number }) {
+  const { t } = useTranslation();
+  const [expanded, setExpanded] = useState(false);
+  const [actions, setActions] = useState<ItemAction[]>([]);
+
+  // 菜单内容仅在打开时挂载，每次打开重新读取（插件可能被安装或禁用）
+  useEffect(() => {
+    let cancelled = false;
+    invoke<ItemAction[]>("list_item_actions", { itemId })
+      .then((list) => { if (!cancelled) setActions(list); })
+      .catch((error) => logError("Failed to list plugin actions:", error));
+    return () => { cancelled = true; };
+  }, [itemId]);
+
+  if (actions.length === 0) return null;
+
+  const runAction = async (action: ItemAction) => {
+    try {
+      const result = await invoke<PluginRunResult>("run_plugin_action", {
+        itemId,
+        pluginId: action.plugin_id,
+        actionId: action.action_id,
+      });
+      showToast(
+        result.output === "clipboard"
+          ? t("clipboard.contextMenu.pluginCopied")
+          : t("clipboard.contextMenu.pluginSaved"),
+        "success",
+      );
+    } catch (error) {
+      showToast(t("clipboard.contextMenu.pluginFailed", { error: String(error) }));
+    }
+  };
+
+  return (
+    <>
+      <ContextMenuSeparator />
+      <ExpandableMenuRow
+        label={t("clipboard.contextMenu.pluginActions")}
+        expanded={expanded}
+        onToggle={() => setExpanded((v) => !v)}
+      />
+      {expanded && actions.map((action) => (
+        <ContextMenuItem
+          className="pl-6"
+          key={`${action.plugin_id}/${action.action_id}`}
+          title={action.plugin_name}
+          onClick={() => runAction(action)}
+        >
+          {action.title}
+        </ContextMenuItem>
+      ))}
+    </>
+  );
+}
+
+// ============ AI 操作（内联折叠） ============
+
+export function AiActionsSection({ itemId }: { itemId: number }) {
+  const { t } = useTranslation();
+  const [expanded, setExpanded] = useState(false);
+  const [templates, setTemplates] = useState<AiTemplate[]>([]);
+
+  const toggle = () => {
+    setExpanded((v) => !v);
+    if (!expanded) {
+      listAiTemplates()
+        .then(setTemplates)
+        .catch((error) => showToast(t("clipboard.contextMenu.aiFailed", { error: String(error) })));
+    }
+  };
+
+  const run = async (template: AiTemplate) => {
+    try {
+      await runAiAction([itemId], template.id);
+    } catch (error) {
+      showToast(t("clipboard.contextMenu.aiFailed", { error: String(error) }));
+    }
+  };
+
+  return (
+    <>
+      <ContextMenuSeparator />
+      <ExpandableMenuRow
+        label={t("clipboard.contextMenu.aiActions")}
+        expanded={expanded}
+        onToggle={toggle}
+      />
+      {expanded && templates.map((template) => (
+        <ContextMenuItem className="pl-6" key={template.id} onClick={() => run(template)}>
+          {template.name}
+        </ContextMenuItem>
+      ))}
+    </>
+  );
+}
+
 // ============ 操作工具栏 ============
 
 interface ActionToolbarProps {
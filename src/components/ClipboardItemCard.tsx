@@ -12,6 +12,9 @@ import {
   Translate16Regular,
   CheckmarkCircle16Filled,
   Circle16Regular,
+  ArrowClockwise16Regular,
+  Dismiss16Regular,
+  Add16Regular,
 } from "@fluentui/react-icons";
 import { invoke } from "@tauri-apps/api/core";
 import { emitTo, listen } from "@tauri-apps/api/event";
@@ -24,8 +27,10 @@ import {
 } from "@/components/CardContentRenderers";
 import {
   ActionToolbar,
+  AiActionsSection,
   FileDetailsDialog,
   MoveToGroupSection,
+  PluginActionsSection,
   type FileListItem,
   type ContextMenuItemConfig,
 } from "@/components/CardSubComponents";
@@ -67,10 +72,12 @@ import {
 import { createLeaseManager } from "@/lib/lease-manager";
 import { logError } from "@/lib/logger";
 import { getPreviewPresentation } from "@/lib/preview-presentation";
-import { translateText } from "@/lib/translate";
+import { getLinkedTranslation, translateText } from "@/lib/translate";
 import { cn } from "@/lib/utils";
 import { useClipboardStore, ClipboardItem } from "@/stores/clipboard";
 import { useGroupStore } from "@/stores/groups";
+import { usePasteQueue } from "@/stores/paste-queue";
+import { useOcrAvailable } from "@/stores/plugin-availability";
 import { useUISettings } from "@/stores/ui-settings";
 
 // ============ 类型定义 ============
@@ -164,6 +171,8 @@ const arePropsEqual = (
     item.char_count === nextItem.char_count &&
     item.image_path === nextItem.image_path &&
     item.files_valid === nextItem.files_valid &&
+    item.staging_state === nextItem.staging_state &&
+    item.media_missing === nextItem.media_missing &&
     item.preview === nextItem.preview &&
     item.source_app_name === nextItem.source_app_name &&
     item.source_app_icon === nextItem.source_app_icon
@@ -184,6 +193,7 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
   );
   const batchMode = useClipboardStore((s) => s.batchMode);
   const isSelected = useClipboardStore((s) => s.selectedIds.has(item.id));
+  const stagingPercent = useClipboardStore((s) => s.stagingProgress[item.id]);
   const toggleSelect = useClipboardStore((s) => s.toggleSelect);
   const keyboardNavEnabled = useUISettings((s) => s.keyboardNavigation);
   const isActive = isActiveIndex && keyboardNavEnabled;
@@ -230,9 +240,11 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
     copyToClipboard,
     pasteContent,
     pasteAsPlainText,
+    pasteImageText,
   } = clipboardActions();
 
   const translateAvailable = useTranslateAvailable();
+  const ocrAvailable = useOcrAvailable();
   const [translateStatus, setTranslateStatus] = useState<"idle" | "loading" | "done" | "error">("idle");
   const [translatedText, setTranslatedText] = useState("");
 
@@ -244,6 +256,7 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
     useShallow((s) => ({ groups: s.groups, moveItemToGroup: s.moveItemToGroup })),
   );
   const selectedGroupId = useClipboardStore((s) => s.selectedGroupId);
+  const addToPasteQueue = usePasteQueue((s) => s.add);
   const textPreviewTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const textPreviewVisibleRef = useRef(false);
   const textPreviewAnchorRef = useRef<HTMLDivElement | null>(null);
@@ -615,14 +628,21 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
     try {
       const text = await resolveTextPreviewContent();
       if (!text.trim()) { setTranslateStatus("idle"); return; }
-      const result = await translateText(text);
+      // 已有关联译文时直接显示，右键菜单的“翻译”强制重新翻译
+      const linked = forceRedo ? null : await getLinkedTranslation(item.id, text).catch(() => null);
+      if (linked) {
+        setTranslatedText(linked);
+        setTranslateStatus("done");
+        return;
+      }
+      const result = await translateText(text, undefined, item.id);
       setTranslatedText(result);
       setTranslateStatus("done");
     } catch (error) {
       setTranslatedText(String(error));
       setTranslateStatus("error");
     }
-  }, [resolveTextPreviewContent]);
+  }, [resolveTextPreviewContent, item.id]);
 
   const handleTranslateClick = useCallback((e: React.MouseEvent) => {
     e.stopPropagation();
@@ -679,6 +699,14 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
     }
   };
 
+  const invokeStaging = async (command: "cancel_file_staging" | "retry_file_staging") => {
+    try {
+      await invoke(command, { id: item.id });
+    } catch (error) {
+      logError(`Failed to ${command}:`, error);
+    }
+  };
+
   const handleShowDetails = async () => {
     if (filePaths.length === 0) return;
     try {
@@ -825,6 +853,7 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
               sourceAppIcon={effectiveSourceIcon}
               imageWidth={item.image_width}
               imageHeight={item.image_height}
+              mediaMissing={item.media_missing}
             />
           ) : item.content_type === "files" ? (
             <FileContent
@@ -839,6 +868,8 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
               sourceAppIcon={effectiveSourceIcon}
               byteSize={item.byte_size}
               tooLarge={previewTooLarge}
+              stagingState={item.staging_state}
+              stagingPercent={stagingPercent}
             />
           ) : (
             <div
@@ -931,6 +962,7 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
         { icon: Copy16Regular, label: t("clipboard.contextMenu.copy"), onClick: handleCopyCtxMenu },
         ...(translateAvailable ? [{ icon: Translate16Regular, label: t("clipboard.contextMenu.translate"), onClick: () => triggerTranslate(true) }] : []),
         { icon: Edit16Regular, label: t("clipboard.contextMenu.edit"), onClick: handleEdit },
+        { icon: Add16Regular, label: t("clipboard.contextMenu.addToPasteQueue"), onClick: () => void addToPasteQueue([item.id]) },
         { icon: Delete16Regular, label: t("clipboard.contextMenu.delete"), onClick: () => deleteItem(item.id), destructive: true, separator: true },
       ];
     }
@@ -941,6 +973,12 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
         { icon: FolderOpen16Regular, label: t("clipboard.contextMenu.showInExplorer"), onClick: handleShowInExplorer, disabled: filesInvalid },
         { icon: ArrowDownload16Regular, label: t("clipboard.contextMenu.saveAs"), onClick: handleSaveAs, disabled: filesInvalid },
         { icon: Info16Regular, label: t("clipboard.contextMenu.viewDetails"), onClick: handleShowDetails, disabled: filesInvalid },
+        ...(item.staging_state === "pending"
+          ? [{ icon: Dismiss16Regular, label: t("clipboard.contextMenu.cancelStaging"), onClick: () => invokeStaging("cancel_file_staging") }]
+          : item.staging_state === "partial" || item.staging_state === "failed"
+            ? [{ icon: ArrowClockwise16Regular, label: t("clipboard.contextMenu.retryStaging"), onClick: () => invokeStaging("retry_file_staging") }]
+            : []),
+        { icon: Add16Regular, label: t("clipboard.contextMenu.addToPasteQueue"), onClick: () => void addToPasteQueue([item.id]) },
         { icon: Delete16Regular, label: t("clipboard.contextMenu.delete"), onClick: () => deleteItem(item.id), destructive: true, separator: true },
       ];
     }
@@ -948,8 +986,10 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
       return [
         { icon: ClipboardPaste16Regular, label: t("clipboard.contextMenu.paste"), onClick: () => pasteContent(item.id) },
         { icon: Copy16Regular, label: t("clipboard.contextMenu.copy"), onClick: handleCopyCtxMenu },
+        ...(ocrAvailable ? [{ icon: TextDescription16Regular, label: t("clipboard.contextMenu.pasteImageText"), onClick: () => pasteImageText(item.id) }] : []),
         { icon: FolderOpen16Regular, label: t("clipboard.contextMenu.showInExplorer"), onClick: handleShowImageInExplorer },
         { icon: ArrowDownload16Regular, label: t("clipboard.contextMenu.saveAs"), onClick: handleSaveAs },
+        { icon: Add16Regular, label: t("clipboard.contextMenu.addToPasteQueue"), onClick: () => void addToPasteQueue([item.id]) },
         { icon: Delete16Regular, label: t("clipboard.contextMenu.delete"), onClick: () => deleteItem(item.id), destructive: true, separator: true },
       ];
     }
@@ -982,6 +1022,8 @@ export const ClipboardItemCard = memo(function ClipboardItemCard({
               selectedGroupId={selectedGroupId}
               moveItemToGroup={moveItemToGroup}
             />
+            {TEXT_LIKE_TYPES.has(item.content_type) && <AiActionsSection itemId={item.id} />}
+            <PluginActionsSection itemId={item.id} />
           </ContextMenuContent>
         </ContextMenu>
         {item.content_type === "files" && (
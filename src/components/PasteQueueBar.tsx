@@ -0,0 +1,71 @@
+import { useState } from "react";
+import { ChevronDown16Regular, ChevronUp16Regular, Dismiss16Regular } from "@fluentui/react-icons";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { useTranslation } from "@/i18n";
+import { usePasteQueue, type PasteQueueMode } from "@/stores/paste-queue";
+
+/** 粘贴队列非空时显示在列表上方：下一项预览、模式切换与逐项移除 */
+export function PasteQueueBar() {
+  const { t } = useTranslation();
+  const { mode, items, remove, clear, setMode, pasteNext } = usePasteQueue();
+  const [expanded, setExpanded] = useState(false);
+
+  if (items.length === 0) return null;
+
+  const label = (preview: string | null, contentType: string) => preview || `[${contentType}]`;
+
+  return (
+    <div className="shrink-0 px-3 py-1.5 bg-primary-faint border-b border-primary-subtle">
+      <div className="flex items-center justify-between gap-2">
+        <button
+          onClick={() => setExpanded(!expanded)}
+          className="flex items-center gap-1 min-w-0 text-xs text-muted-foreground hover:text-foreground transition-surface"
+        >
+          {expanded ? <ChevronUp16Regular className="w-3.5 h-3.5 shrink-0" /> : <ChevronDown16Regular className="w-3.5 h-3.5 shrink-0" />}
+          <span className="shrink-0">{t("clipboard.pasteQueue.title", { count: items.length })}</span>
+          <span className="truncate text-muted-foreground/60">
+            {t("clipboard.pasteQueue.nextHint", { preview: label(items[0].preview, items[0].content_type) })}
+          </span>
+        </button>
+        <div className="flex items-center gap-1 shrink-0">
+          <Select value={mode} onValueChange={(v) => void setMode(v as PasteQueueMode)}>
+            <SelectTrigger className="h-6 w-[110px] text-xs"><SelectValue /></SelectTrigger>
+            <SelectContent>
+              <SelectItem value="fifo">{t("clipboard.pasteQueue.fifo")}</SelectItem>
+              <SelectItem value="lifo">{t("clipboard.pasteQueue.lifo")}</SelectItem>
+            </SelectContent>
+          </Select>
+          <button
+            onClick={() => void pasteNext()}
+            className="text-xs px-2 py-1 rounded-md bg-primary-subtle text-primary hover:bg-primary-subtle-hover transition-surface"
+          >
+            {t("clipboard.pasteQueue.next")}
+          </button>
+          <button
+            onClick={() => void clear()}
+            className="text-xs px-2 py-1 rounded-md hover:bg-accent transition-surface text-muted-foreground hover:text-foreground"
+          >
+            {t("clipboard.pasteQueue.clear")}
+          </button>
+        </div>
+      </div>
+      {expanded && (
+        <ol className="mt-1.5 max-h-32 overflow-y-auto space-y-0.5">
+          {items.map((entry, index) => (
+            <li key={`${entry.id}-${index}`} className="flex items-center gap-2 text-xs">
+              <span className="w-4 shrink-0 text-right text-muted-foreground/60">{index + 1}</span>
+              <span className="flex-1 truncate">{label(entry.preview, entry.content_type)}</span>
+              <button
+                onClick={() => void remove(entry.id)}
+                aria-label={t("clipboard.pasteQueue.remove")}
+                className="shrink-0 p-0.5 rounded text-muted-foreground hover:text-foreground hover:bg-accent transition-surface"
+              >
+                <Dismiss16Regular className="w-3 h-3" />
+              </button>
+            </li>
+          ))}
+        </ol>
+      )}
+    </div>
+  );
+}
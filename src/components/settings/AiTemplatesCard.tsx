@@ -0,0 +1,167 @@
+import { useEffect, useState } from "react";
+import { Add16Regular, Delete16Regular } from "@fluentui/react-icons";
+import { SettingsCard, SettingsCardHeader } from "@/components/settings/SettingSection";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { listAiTemplates, newTemplateId, resetAiTemplates, saveAiTemplates, type AiTemplate } from "@/lib/ai";
+
+const textareaClass =
+  "w-full min-h-[64px] rounded-md border border-input bg-transparent px-3 py-2 text-xs focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring";
+
+export function AiTemplatesCard() {
+  const { t } = useTranslation();
+  const [templates, setTemplates] = useState<AiTemplate[] | null>(null);
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  const load = (list: AiTemplate[]) => {
+    setTemplates(list);
+    setSelectedId(list[0]?.id ?? null);
+    setDirty(false);
+  };
+
+  useEffect(() => {
+    listAiTemplates()
+      .then(load)
+      .catch((error) => showToast(t("settings.aiTemplates.loadFailed", { error: String(error) })));
+  }, [t]);
+
+  if (!templates) return null;
+
+  const selected = templates.find((tpl) => tpl.id === selectedId) ?? null;
+
+  const update = (patch: Partial<AiTemplate>) => {
+    setTemplates(templates.map((tpl) => (tpl.id === selectedId ? { ...tpl, ...patch } : tpl)));
+    setDirty(true);
+  };
+
+  const add = () => {
+    const template: AiTemplate = {
+      id: newTemplateId(),
+      name: t("settings.aiTemplates.newName"),
+      prompt: "{{text}}",
+    };
+    setTemplates([...templates, template]);
+    setSelectedId(template.id);
+    setDirty(true);
+  };
+
+  const remove = () => {
+    const rest = templates.filter((tpl) => tpl.id !== selectedId);
+    setTemplates(rest);
+    setSelectedId(rest[0]?.id ?? null);
+    setDirty(true);
+  };
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      await saveAiTemplates(templates);
+      setDirty(false);
+      showToast(t("settings.aiTemplates.saved"), "success");
+    } catch (error) {
+      showToast(t("settings.aiTemplates.saveFailed", { error: String(error) }));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const reset = async () => {
+    setSaving(true);
+    try {
+      load(await resetAiTemplates());
+    } catch (error) {
+      showToast(t("settings.aiTemplates.saveFailed", { error: String(error) }));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.aiTemplates.title")}
+        description={t("settings.aiTemplates.desc")}
+        action={
+          <div className="flex gap-2">
+            <Button variant="outline" size="sm" className="h-7 text-xs" disabled={saving} onClick={reset}>
+              {t("settings.aiTemplates.reset")}
+            </Button>
+            <Button size="sm" className="h-7 text-xs" disabled={saving || !dirty} onClick={save}>
+              {t("settings.aiTemplates.save")}
+            </Button>
+          </div>
+        }
+      />
+      <div className="flex gap-3">
+        <div className="w-36 shrink-0 space-y-1">
+          {templates.map((tpl) => (
+            <button
+              key={tpl.id}
+              type="button"
+              className={`w-full truncate rounded px-2 py-1 text-left text-xs ${
+                tpl.id === selectedId ? "bg-accent" : "hover:bg-accent/50"
+              }`}
+              onClick={() => setSelectedId(tpl.id)}
+            >
+              {tpl.name || tpl.id}
+            </button>
+          ))}
+          <Button variant="ghost" size="sm" className="h-7 w-full justify-start text-xs" onClick={add}>
+            <Add16Regular className="w-4 h-4 mr-1" />
+            {t("settings.aiTemplates.add")}
+          </Button>
+        </div>
+        {selected && (
+          <div className="min-w-0 flex-1 space-y-2">
+            <div className="space-y-1">
+              <Label className="text-xs">{t("settings.aiTemplates.name")}</Label>
+              <Input className="h-8 text-xs" value={selected.name} onChange={(e) => update({ name: e.target.value })} />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-xs">{t("settings.aiTemplates.system")}</Label>
+              <textarea
+                className={textareaClass}
+                value={selected.system ?? ""}
+                onChange={(e) => update({ system: e.target.value || undefined })}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label className="text-xs">{t("settings.aiTemplates.prompt")}</Label>
+              <textarea
+                className={textareaClass}
+                value={selected.prompt}
+                onChange={(e) => update({ prompt: e.target.value })}
+              />
+              <p className="text-xs text-muted-foreground">
+                <code>{"{{text}}"}</code> {t("settings.aiTemplates.promptHint")}
+              </p>
+            </div>
+            <div className="flex items-end justify-between gap-2">
+              <div className="space-y-1">
+                <Label className="text-xs">{t("settings.aiTemplates.temperature")}</Label>
+                <Input
+                  className="h-8 w-24 text-xs"
+                  type="number"
+                  min={0}
+                  max={2}
+                  step={0.1}
+                  value={selected.temperature ?? ""}
+                  onChange={(e) => update({ temperature: e.target.value === "" ? undefined : Number(e.target.value) })}
+                />
+              </div>
+              <Button variant="ghost" size="sm" className="h-7 text-xs text-destructive" onClick={remove}>
+                <Delete16Regular className="w-4 h-4 mr-1" />
+                {t("settings.aiTemplates.delete")}
+              </Button>
+            </div>
+          </div>
+        )}
+      </div>
+    </SettingsCard>
+  );
+}
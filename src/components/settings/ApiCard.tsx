@@ -0,0 +1,107 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { SettingsCard, SettingsCardHeader, SettingRow } from "@/components/settings/SettingSection";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+
+interface ApiStatus {
+  enabled: boolean;
+  running: boolean;
+  endpoint: string | null;
+  port: number;
+  socket_path: string | null;
+  token: string;
+  error: string | null;
+}
+
+export function ApiCard() {
+  const { t } = useTranslation();
+  const [status, setStatus] = useState<ApiStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    invoke<ApiStatus>("get_api_status")
+      .then(setStatus)
+      .catch((error) => logError("Failed to load API status:", error));
+  }, []);
+
+  const run = async (command: string, args?: Record<string, unknown>) => {
+    setBusy(true);
+    try {
+      setStatus(await invoke<ApiStatus>(command, args));
+    } catch (error) {
+      showToast(t("settings.api.failed", { error: String(error) }));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const copyToken = async () => {
+    if (!status) return;
+    try {
+      await invoke("write_text_to_clipboard", { text: status.token, record: false });
+      showToast(t("settings.api.tokenCopied"), "success");
+    } catch (error) {
+      logError("Failed to copy API token:", error);
+    }
+  };
+
+  const statusText = () => {
+    if (!status?.enabled) return t("settings.api.stopped");
+    if (status.error) return t("settings.api.error", { error: status.error });
+    return status.endpoint
+      ? t("settings.api.running", { endpoint: status.endpoint })
+      : t("settings.api.stopped");
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader title={t("settings.api.title")} description={t("settings.api.desc")} />
+      <div className="space-y-3">
+        <SettingRow
+          title={t("settings.api.enable")}
+          description={statusText()}
+          action={
+            <Switch
+              checked={!!status?.enabled}
+              disabled={!status || busy}
+              onCheckedChange={(v) => void run("set_api_enabled", { enabled: v })}
+              aria-label={t("settings.api.enable")}
+            />
+          }
+        />
+        <SettingRow
+          title={t("settings.api.token")}
+          description={
+            <span className="font-mono break-all select-text">{status?.token ?? ""}</span>
+          }
+          action={
+            <div className="flex gap-2">
+              <Button
+                variant="outline"
+                size="sm"
+                className="h-7 text-xs"
+                disabled={!status}
+                onClick={() => void copyToken()}
+              >
+                {t("settings.api.copy")}
+              </Button>
+              <Button
+                variant="outline"
+                size="sm"
+                className="h-7 text-xs"
+                disabled={!status || busy}
+                onClick={() => void run("regenerate_api_token")}
+              >
+                {t("settings.api.regenerate")}
+              </Button>
+            </div>
+          }
+        />
+      </div>
+    </SettingsCard>
+  );
+}
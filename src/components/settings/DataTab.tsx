@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback, useMemo } from "react";
-import { Folder16Regular, Open16Regular, ArrowSync16Regular, ArrowDownload16Regular, ArrowUpload16Regular, Delete16Regular, ArrowCounterclockwise16Regular, ArrowClockwise16Regular } from "@fluentui/react-icons";
+import { Folder16Regular, Open16Regular, ArrowSync16Regular, ArrowDownload16Regular, ArrowUpload16Regular, Delete16Regular, ArrowCounterclockwise16Regular, ArrowClockwise16Regular, ShieldCheckmark16Regular, Wrench16Regular } from "@fluentui/react-icons";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { SettingsCard, SettingsCardHeader } from "@/components/settings/SettingSection";
 import { Button } from "@/components/ui/button";
 import {
@@ -13,7 +14,15 @@ import {
 } from "@/components/ui/dialog";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import { Slider } from "@/components/ui/slider";
+import { Switch } from "@/components/ui/switch";
 import {
   Tooltip,
   TooltipContent,
@@ -21,6 +30,7 @@ import {
 } from "@/components/ui/tooltip";
 import { useTranslation } from "@/i18n";
 import { logError } from "@/lib/logger";
+import type { Group } from "@/stores/groups";
 
 function isUserCancelled(error: unknown): boolean {
   const msg = String(error).toLowerCase();
@@ -59,7 +69,17 @@ interface DataSizeInfo {
   images_count: number;
   staged_size?: number;
   staged_count?: number;
+  blobs_size?: number;
+  blobs_count?: number;
   total_size: number;
+  /** 配额统计口径的条目占用（数据库内容 + 引用的媒体文件） */
+  usage?: {
+    total_bytes: number;
+    content_bytes: number;
+    media_bytes: number;
+    by_type: { content_type: string; count: number; bytes: number }[];
+    by_group: { group_id: number | null; name: string | null; count: number; bytes: number }[];
+  };
 }
 
 function formatDataSize(bytes: number): string {
@@ -71,6 +91,7 @@ function formatDataSize(bytes: number): string {
 
 type DedupStrategy = "move_to_top" | "ignore" | "always_new";
 type TextDedupMode = "semantic" | "strict";
+type FolderSnapshotMode = "off" | "copy" | "archive";
 
 const dedupKeys: { value: DedupStrategy; labelKey: string; descKey: string }[] = [
   { value: "move_to_top", labelKey: "settings.data.dedupMoveToTop", descKey: "settings.data.dedupMoveToTopDesc" },
@@ -81,6 +102,17 @@ const textDedupKeys: { value: TextDedupMode; labelKey: string; descKey: string }
   { value: "semantic", labelKey: "settings.data.textDedupSemantic", descKey: "settings.data.textDedupSemanticDesc" },
   { value: "strict", labelKey: "settings.data.textDedupStrict", descKey: "settings.data.textDedupStrictDesc" },
 ];
+const folderSnapshotKeys: { value: FolderSnapshotMode; labelKey: string; descKey: string }[] = [
+  { value: "off", labelKey: "settings.data.folderSnapshotOff", descKey: "settings.data.folderSnapshotOffDesc" },
+  { value: "copy", labelKey: "settings.data.folderSnapshotCopy", descKey: "settings.data.folderSnapshotCopyDesc" },
+  { value: "archive", labelKey: "settings.data.folderSnapshotArchive", descKey: "settings.data.folderSnapshotArchiveDesc" },
+];
+const FOLDER_SNAPSHOT_DEFAULTS = {
+  folder_snapshot_max_mb: "200",
+  folder_snapshot_max_entries: "5000",
+  folder_snapshot_ignore: "node_modules, .git, target",
+};
+type FolderSnapshotLimitKey = keyof typeof FOLDER_SNAPSHOT_DEFAULTS;
 
 interface DedupStrategyCardProps {
   strategy: DedupStrategy;
@@ -226,6 +258,1161 @@ function TextDedupModeCard({ dedupStrategy }: { dedupStrategy: DedupStrategy })
     </SettingsCard>
   );
 }
+function FolderSnapshotCard() {
+  const { t } = useTranslation();
+  const options = useMemo(
+    () => folderSnapshotKeys.map((k) => ({ value: k.value, label: t(k.labelKey), desc: t(k.descKey) })),
+    [t],
+  );
+  const [mode, setMode] = useState<FolderSnapshotMode>("off");
+  const [limits, setLimits] = useState<Record<FolderSnapshotLimitKey, string>>(FOLDER_SNAPSHOT_DEFAULTS);
+  const activeIndex = Math.max(0, options.findIndex((opt) => opt.value === mode));
+
+  useEffect(() => {
+    const keys = ["folder_snapshot_mode", ...Object.keys(FOLDER_SNAPSHOT_DEFAULTS)];
+    invoke<Record<string, string>>("get_settings_batch", { keys }).then((values) => {
+      const saved = values["folder_snapshot_mode"];
+      setMode(saved === "copy" || saved === "archive" ? saved : "off");
+      setLimits({
+        folder_snapshot_max_mb: values["folder_snapshot_max_mb"] ?? FOLDER_SNAPSHOT_DEFAULTS.folder_snapshot_max_mb,
+        folder_snapshot_max_entries: values["folder_snapshot_max_entries"] ?? FOLDER_SNAPSHOT_DEFAULTS.folder_snapshot_max_entries,
+        folder_snapshot_ignore: values["folder_snapshot_ignore"] ?? FOLDER_SNAPSHOT_DEFAULTS.folder_snapshot_ignore,
+      });
+    }).catch((error) => {
+      logError("Failed to load folder snapshot settings:", error);
+    });
+  }, []);
+
+  const saveSetting = async (key: string, value: string) => {
+    try {
+      await invoke("set_setting", { key, value });
+    } catch (error) {
+      logError(`Failed to save ${key}:`, error);
+    }
+  };
+
+  const handleModeChange = (value: FolderSnapshotMode) => {
+    setMode(value);
+    void saveSetting("folder_snapshot_mode", value);
+  };
+
+  const limitField = (key: FolderSnapshotLimitKey, label: string, numeric: boolean) => (
+    <div className="space-y-1.5">
+      <Label htmlFor={key} className="text-xs">{label}</Label>
+      <Input
+        id={key}
+        value={limits[key]}
+        inputMode={numeric ? "numeric" : undefined}
+        onChange={(e) => {
+          const value = numeric ? e.target.value.replace(/[^0-9]/g, "") : e.target.value;
+          setLimits((prev) => ({ ...prev, [key]: value }));
+        }}
+        onBlur={() => { void saveSetting(key, limits[key]); }}
+        className="h-8 text-sm"
+      />
+    </div>
+  );
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.folderSnapshotTitle")}
+        description={t("settings.data.folderSnapshotDesc")}
+      />
+      <div
+        role="radiogroup"
+        aria-label={t("settings.data.folderSnapshotTitle")}
+        className="relative rounded-md border bg-muted-surface-subtle p-1"
+      >
+        <div className="relative grid grid-cols-3">
+          <div
+            aria-hidden
+            className="absolute inset-y-0 left-0 w-1/3 rounded-md bg-primary elevation-control will-change-transform transition-transform duration-200 ease-out"
+            style={{ transform: `translateX(${activeIndex * 100}%)` }}
+          />
+          {options.map((opt) => {
+            const isActive = mode === opt.value;
+            return (
+              <button
+                key={opt.value}
+                type="button"
+                role="radio"
+                aria-checked={isActive}
+                onClick={() => handleModeChange(opt.value)}
+                className={`relative z-1 rounded-md px-2.5 py-1.5 text-xs font-medium transition-surface ${
+                  isActive
+                    ? "text-primary-foreground"
+                    : "text-foreground/80 hover:text-foreground"
+                }`}
+              >
+                {opt.label}
+              </button>
+            );
+          })}
+        </div>
+      </div>
+      <p className="text-xs text-muted-foreground mt-2">
+        {options.find((o) => o.value === mode)?.desc}
+      </p>
+      {mode !== "off" && (
+        <div className="space-y-3 mt-4">
+          <div className="grid grid-cols-2 gap-3">
+            {limitField("folder_snapshot_max_mb", t("settings.data.folderSnapshotMaxMb"), true)}
+            {limitField("folder_snapshot_max_entries", t("settings.data.folderSnapshotMaxEntries"), true)}
+          </div>
+          {limitField("folder_snapshot_ignore", t("settings.data.folderSnapshotIgnore"), false)}
+          <p className="text-xs text-muted-foreground">
+            {t("settings.data.folderSnapshotIgnoreHint")}
+          </p>
+        </div>
+      )}
+    </SettingsCard>
+  );
+}
+
+interface CleanupPreview {
+  base_days: number;
+  items: {
+    id: number;
+    content_type: string;
+    preview: string | null;
+    created_at: string;
+    access_count: number;
+    bytes: number;
+    age_days: number;
+    score: { effective_uses: number; keep_days: number; protected: boolean };
+  }[];
+  total_bytes: number;
+}
+
+const RETENTION_DEFAULTS = {
+  retention_half_life_days: "14",
+  retention_protect_uses: "5",
+  retention_size_ref_mb: "0",
+};
+type RetentionKey = keyof typeof RETENTION_DEFAULTS;
+
+const CLEANUP_PREVIEW_LIMIT = 50;
+
+function RetentionCard() {
+  const { t } = useTranslation();
+  const [values, setValues] = useState<Record<RetentionKey, string>>(RETENTION_DEFAULTS);
+  const [preview, setPreview] = useState<CleanupPreview | null>(null);
+  const [previewLoading, setPreviewLoading] = useState(false);
+
+  useEffect(() => {
+    invoke<Record<string, string>>("get_settings_batch", { keys: Object.keys(RETENTION_DEFAULTS) })
+      .then((saved) => {
+        setValues({
+          retention_half_life_days: saved["retention_half_life_days"] ?? RETENTION_DEFAULTS.retention_half_life_days,
+          retention_protect_uses: saved["retention_protect_uses"] ?? RETENTION_DEFAULTS.retention_protect_uses,
+          retention_size_ref_mb: saved["retention_size_ref_mb"] ?? RETENTION_DEFAULTS.retention_size_ref_mb,
+        });
+      })
+      .catch((error) => logError("Failed to load retention settings:", error));
+  }, []);
+
+  const saveSetting = async (key: RetentionKey) => {
+    try {
+      await invoke("set_setting", { key, value: values[key] || "0" });
+      setPreview(null);
+    } catch (error) {
+      logError(`Failed to save ${key}:`, error);
+    }
+  };
+
+  const loadPreview = async () => {
+    setPreviewLoading(true);
+    try {
+      setPreview(await invoke<CleanupPreview>("preview_retention_cleanup"));
+    } catch (error) {
+      logError("Failed to preview cleanup:", error);
+    }
+    setPreviewLoading(false);
+  };
+
+  const field = (key: RetentionKey, label: string) => (
+    <div className="space-y-1.5">
+      <Label htmlFor={key} className="text-xs">{label}</Label>
+      <Input
+        id={key}
+        value={values[key]}
+        inputMode="decimal"
+        onChange={(e) => {
+          const value = e.target.value.replace(/[^0-9.]/g, "");
+          setValues((prev) => ({ ...prev, [key]: value }));
+        }}
+        onBlur={() => { void saveSetting(key); }}
+        className="h-8 text-sm"
+      />
+    </div>
+  );
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.retentionTitle")}
+        description={t("settings.data.retentionDesc")}
+      />
+      <div className="space-y-3">
+        <div className="grid grid-cols-3 gap-3">
+          {field("retention_half_life_days", t("settings.data.retentionHalfLife"))}
+          {field("retention_protect_uses", t("settings.data.retentionProtectUses"))}
+          {field("retention_size_ref_mb", t("settings.data.retentionSizeRef"))}
+        </div>
+        <p className="text-xs text-muted-foreground">{t("settings.data.retentionHint")}</p>
+        <Button variant="outline" size="sm" disabled={previewLoading} onClick={() => void loadPreview()}>
+          {t("settings.data.retentionPreview")}
+        </Button>
+        {preview && (
+          preview.items.length === 0 ? (
+            <p className="text-xs text-muted-foreground">{t("settings.data.retentionPreviewEmpty")}</p>
+          ) : (
+            <div className="space-y-1.5">
+              <p className="text-xs">
+                {t("settings.data.retentionPreviewSummary", {
+                  count: preview.items.length,
+                  size: formatDataSize(preview.total_bytes),
+                })}
+              </p>
+              <div className="max-h-48 overflow-y-auto rounded-md border divide-y text-xs">
+                {preview.items.slice(0, CLEANUP_PREVIEW_LIMIT).map((item) => (
+                  <div key={item.id} className="flex items-center gap-2 px-2 py-1">
+                    <span className="shrink-0 text-muted-foreground">{t(`contentType.${item.content_type}`)}</span>
+                    <span className="flex-1 min-w-0 truncate">{item.preview ?? ""}</span>
+                    <span className="shrink-0 tabular-nums text-muted-foreground">
+                      {t("settings.data.retentionPreviewItem", {
+                        age: Math.floor(item.age_days),
+                        keep: Math.floor(item.score.keep_days),
+                        uses: item.access_count,
+                      })}
+                    </span>
+                  </div>
+                ))}
+              </div>
+              {preview.items.length > CLEANUP_PREVIEW_LIMIT && (
+                <p className="text-xs text-muted-foreground">
+                  {t("settings.data.retentionPreviewMore", { count: preview.items.length - CLEANUP_PREVIEW_LIMIT })}
+                </p>
+              )}
+            </div>
+          )
+        )}
+      </div>
+    </SettingsCard>
+  );
+}
+
+type EvictionPolicy = "oldest" | "least_accessed" | "largest";
+
+const evictionPolicyKeys: { value: EvictionPolicy; labelKey: string; descKey: string }[] = [
+  { value: "oldest", labelKey: "settings.data.quotaPolicyOldest", descKey: "settings.data.quotaPolicyOldestDesc" },
+  { value: "least_accessed", labelKey: "settings.data.quotaPolicyLeastAccessed", descKey: "settings.data.quotaPolicyLeastAccessedDesc" },
+  { value: "largest", labelKey: "settings.data.quotaPolicyLargest", descKey: "settings.data.quotaPolicyLargestDesc" },
+];
+
+const QUOTA_CONTENT_TYPES = ["image", "files", "text", "html", "rtf", "url"] as const;
+const QUOTA_LIMIT_KEYS = [
+  "quota_total_mb",
+  "quota_group_mb",
+  ...QUOTA_CONTENT_TYPES.map((type) => `quota_${type}_mb`),
+];
+
+function QuotaCard() {
+  const { t } = useTranslation();
+  const options = useMemo(
+    () => evictionPolicyKeys.map((k) => ({ value: k.value, label: t(k.labelKey), desc: t(k.descKey) })),
+    [t],
+  );
+  const [policy, setPolicy] = useState<EvictionPolicy>("oldest");
+  const [limits, setLimits] = useState<Record<string, string>>({});
+  const activeIndex = Math.max(0, options.findIndex((opt) => opt.value === policy));
+
+  useEffect(() => {
+    invoke<Record<string, string>>("get_settings_batch", {
+      keys: ["quota_eviction_policy", ...QUOTA_LIMIT_KEYS],
+    }).then((values) => {
+      const saved = values["quota_eviction_policy"];
+      setPolicy(saved === "least_accessed" || saved === "largest" ? saved : "oldest");
+      setLimits(Object.fromEntries(QUOTA_LIMIT_KEYS.map((key) => [key, values[key] ?? "0"])));
+    }).catch((error) => {
+      logError("Failed to load quota settings:", error);
+    });
+  }, []);
+
+  const saveSetting = async (key: string, value: string) => {
+    try {
+      await invoke("set_setting", { key, value });
+    } catch (error) {
+      logError(`Failed to save ${key}:`, error);
+    }
+  };
+
+  const handlePolicyChange = (value: EvictionPolicy) => {
+    setPolicy(value);
+    void saveSetting("quota_eviction_policy", value);
+  };
+
+  const limitField = (key: string, label: string) => (
+    <div className="space-y-1.5" key={key}>
+      <Label htmlFor={key} className="text-xs">{label}</Label>
+      <Input
+        id={key}
+        value={limits[key] ?? "0"}
+        inputMode="numeric"
+        onChange={(e) => {
+          const value = e.target.value.replace(/[^0-9]/g, "");
+          setLimits((prev) => ({ ...prev, [key]: value }));
+        }}
+        onBlur={() => { void saveSetting(key, limits[key] || "0"); }}
+        className="h-8 text-sm"
+      />
+    </div>
+  );
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.quotaTitle")}
+        description={t("settings.data.quotaDesc")}
+      />
+      <div className="space-y-3">
+        <div className="grid grid-cols-2 gap-3">
+          {limitField("quota_total_mb", t("settings.data.quotaTotal"))}
+          {limitField("quota_group_mb", t("settings.data.quotaGroup"))}
+        </div>
+        <div>
+          <p className="text-xs font-medium mb-1.5">{t("settings.data.quotaByType")}</p>
+          <div className="grid grid-cols-3 gap-3">
+            {QUOTA_CONTENT_TYPES.map((type) => limitField(`quota_${type}_mb`, t(`contentType.${type}`)))}
+          </div>
+        </div>
+        <p className="text-xs text-muted-foreground">{t("settings.data.quotaHint")}</p>
+        <div
+          role="radiogroup"
+          aria-label={t("settings.data.quotaPolicy")}
+          className="relative rounded-md border bg-muted-surface-subtle p-1"
+        >
+          <div className="relative grid grid-cols-3">
+            <div
+              aria-hidden
+              className="absolute inset-y-0 left-0 w-1/3 rounded-md bg-primary elevation-control will-change-transform transition-transform duration-200 ease-out"
+              style={{ transform: `translateX(${activeIndex * 100}%)` }}
+            />
+            {options.map((opt) => {
+              const isActive = policy === opt.value;
+              return (
+                <button
+                  key={opt.value}
+                  type="button"
+                  role="radio"
+                  aria-checked={isActive}
+                  onClick={() => handlePolicyChange(opt.value)}
+                  className={`relative z-1 rounded-md px-2.5 py-1.5 text-xs font-medium transition-surface ${
+                    isActive
+                      ? "text-primary-foreground"
+                      : "text-foreground/80 hover:text-foreground"
+                  }`}
+                >
+                  {opt.label}
+                </button>
+              );
+            })}
+          </div>
+        </div>
+        <p className="text-xs text-muted-foreground">
+          {options.find((o) => o.value === policy)?.desc}
+        </p>
+      </div>
+    </SettingsCard>
+  );
+}
+
+interface StorageCheckReport {
+  checked_at: string;
+  duration_ms: number;
+  objects: number;
+  items: number;
+  orphans: { path: string; size: number; reason: string }[];
+  orphan_bytes: number;
+  dangling: { item_id: number; field: string; path: string }[];
+  mismatches: { hash: string; path: string; actual: string }[];
+  ref_count_drift: number;
+  hashes_verified: boolean;
+  repair: {
+    orphans_deleted: number;
+    bytes_freed: number;
+    rehashed: number;
+    refs_cleared: number;
+    items_marked_missing: number;
+    ref_counts_fixed: number;
+  } | null;
+}
+
+interface StorageCheckProgress {
+  phase: "items" | "objects" | "legacy" | "repair";
+  done: number;
+  total: number;
+}
+
+const STORAGE_CHECK_INTERVALS = ["0", "1", "7", "30"] as const;
+
+function StorageCheckCard({ onRepaired }: { onRepaired: () => void }) {
+  const { t } = useTranslation();
+  const [report, setReport] = useState<StorageCheckReport | null>(null);
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<StorageCheckProgress | null>(null);
+  const [verifyHashes, setVerifyHashes] = useState(false);
+  const [intervalDays, setIntervalDays] = useState("0");
+  const [autoRepair, setAutoRepair] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<{ running: boolean; last_report: StorageCheckReport | null }>("get_storage_check_status")
+      .then((status) => {
+        setRunning(status.running);
+        setReport(status.last_report);
+      })
+      .catch((e) => logError("Failed to load storage check status:", e));
+    invoke<Record<string, string>>("get_settings_batch", {
+      keys: ["storage_check_interval_days", "storage_check_auto_repair"],
+    }).then((values) => {
+      setIntervalDays(values["storage_check_interval_days"] ?? "0");
+      setAutoRepair(values["storage_check_auto_repair"] === "true");
+    }).catch((e) => logError("Failed to load storage check settings:", e));
+
+    // 定时检查在后台运行时同样更新进度与结果
+    const unlistenProgress = listen<StorageCheckProgress>("storage-check-progress", (event) => {
+      setRunning(true);
+      setProgress(event.payload);
+    });
+    const unlistenFinished = listen<StorageCheckReport>("storage-check-finished", (event) => {
+      setRunning(false);
+      setProgress(null);
+      setReport(event.payload);
+    });
+    return () => {
+      unlistenProgress.then((fn) => fn());
+      unlistenFinished.then((fn) => fn());
+    };
+  }, []);
+
+  const runCheck = async (repair: boolean) => {
+    setRunning(true);
+    setError(null);
+    try {
+      const result = await invoke<StorageCheckReport>("check_storage", { repair, verifyHashes });
+      setReport(result);
+      if (repair) onRepaired();
+    } catch (e) {
+      logError("Storage check failed:", e);
+      setError(String(e));
+    }
+    setRunning(false);
+    setProgress(null);
+  };
+
+  const saveSetting = async (key: string, value: string) => {
+    try {
+      await invoke("set_setting", { key, value });
+    } catch (e) {
+      logError(`Failed to save ${key}:`, e);
+    }
+  };
+
+  const problemCount = report
+    ? report.orphans.length + report.dangling.length + report.mismatches.length + report.ref_count_drift
+    : 0;
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.storageCheckTitle")}
+        description={t("settings.data.storageCheckDesc")}
+      />
+      <div className="space-y-3">
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <Label className="text-xs">{t("settings.data.storageCheckVerify")}</Label>
+            <p className="text-xs text-muted-foreground">{t("settings.data.storageCheckVerifyDesc")}</p>
+          </div>
+          <Switch checked={verifyHashes} onCheckedChange={setVerifyHashes} disabled={running} />
+        </div>
+        <div className="flex items-center gap-2">
+          <Button variant="outline" size="sm" disabled={running} onClick={() => void runCheck(false)}>
+            <ShieldCheckmark16Regular className="w-4 h-4 mr-1.5" />
+            {t("settings.data.storageCheckRun")}
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            disabled={running || !report || problemCount === 0}
+            onClick={() => void runCheck(true)}
+          >
+            <Wrench16Regular className="w-4 h-4 mr-1.5" />
+            {t("settings.data.storageCheckRepair")}
+          </Button>
+          {running && (
+            <span className="text-xs text-muted-foreground">
+              {progress
+                ? t("settings.data.storageCheckProgress", {
+                    phase: t(`settings.data.storageCheckPhase.${progress.phase}`),
+                    done: progress.done,
+                    total: progress.total,
+                  })
+                : t("settings.data.storageCheckRunning")}
+            </span>
+          )}
+        </div>
+        {error && <p className="text-xs text-destructive">{error}</p>}
+        {report && !running && (
+          <div className="rounded-md bg-muted-surface-subtle px-3 py-2 space-y-1 text-xs">
+            <p className="text-muted-foreground">
+              {t("settings.data.storageCheckSummary", {
+                time: new Date(report.checked_at).toLocaleString(),
+                items: report.items,
+                objects: report.objects,
+              })}
+            </p>
+            {problemCount === 0 ? (
+              <p>{t("settings.data.storageCheckClean")}</p>
+            ) : (
+              <ul className="space-y-0.5">
+                {report.orphans.length > 0 && (
+                  <li>{t("settings.data.storageCheckOrphans", { count: report.orphans.length, size: formatDataSize(report.orphan_bytes) })}</li>
+                )}
+                {report.dangling.length > 0 && (
+                  <li>{t("settings.data.storageCheckDangling", { count: report.dangling.length })}</li>
+                )}
+                {report.mismatches.length > 0 && (
+                  <li>{t("settings.data.storageCheckMismatches", { count: report.mismatches.length })}</li>
+                )}
+                {report.ref_count_drift > 0 && (
+                  <li>{t("settings.data.storageCheckRefDrift", { count: report.ref_count_drift })}</li>
+                )}
+              </ul>
+            )}
+            {report.repair && (
+              <p className="text-muted-foreground">
+                {t("settings.data.storageCheckRepaired", {
+                  deleted: report.repair.orphans_deleted,
+                  size: formatDataSize(report.repair.bytes_freed),
+                  rehashed: report.repair.rehashed,
+                  missing: report.repair.items_marked_missing,
+                })}
+              </p>
+            )}
+          </div>
+        )}
+        <div className="flex items-center justify-between pt-1">
+          <Label className="text-xs">{t("settings.data.storageCheckSchedule")}</Label>
+          <Select
+            value={intervalDays}
+            onValueChange={(v) => {
+              setIntervalDays(v);
+              void saveSetting("storage_check_interval_days", v);
+            }}
+          >
+            <SelectTrigger className="w-[140px] h-8 text-xs"><SelectValue /></SelectTrigger>
+            <SelectContent>
+              {STORAGE_CHECK_INTERVALS.map((days) => (
+                <SelectItem key={days} value={days}>
+                  {t(`settings.data.storageCheckInterval.${days}`)}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        {intervalDays !== "0" && (
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <Label className="text-xs">{t("settings.data.storageCheckAutoRepair")}</Label>
+              <p className="text-xs text-muted-foreground">{t("settings.data.storageCheckAutoRepairDesc")}</p>
+            </div>
+            <Switch
+              checked={autoRepair}
+              onCheckedChange={(checked) => {
+                setAutoRepair(checked);
+                void saveSetting("storage_check_auto_repair", String(checked));
+              }}
+            />
+          </div>
+        )}
+      </div>
+    </SettingsCard>
+  );
+}
+
+const OPEN_EXPORT_FORMATS = ["jsonl", "markdown", "html"] as const;
+type OpenExportFormat = (typeof OPEN_EXPORT_FORMATS)[number];
+
+/** 列表视图的查询条件（对应后端 `QueryOptions`） */
+interface QueryOptions {
+  search?: string;
+  content_type?: string;
+  pinned_only?: boolean;
+  favorite_only?: boolean;
+}
+
+/** 开放格式导出：JSON Lines / Markdown / HTML，按列表视图的筛选条件导出 */
+function OpenExportCard() {
+  const { t } = useTranslation();
+  const [format, setFormat] = useState<OpenExportFormat>("markdown");
+  const [groups, setGroups] = useState<Group[]>([]);
+  const [selectedGroups, setSelectedGroups] = useState<Set<number | null>>(new Set([null]));
+  const [types, setTypes] = useState<Set<string>>(new Set(EXPORT_CONTENT_TYPES));
+  const [search, setSearch] = useState("");
+  const [favoritesOnly, setFavoritesOnly] = useState(false);
+  const [pinnedOnly, setPinnedOnly] = useState(false);
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<TransferProgress | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<Group[]>("get_groups").then((list) => {
+      setGroups(list);
+      setSelectedGroups(new Set([null, ...list.map((g) => g.id)]));
+    }).catch((e) => logError("Failed to load groups:", e));
+    const unlisten = listen<TransferProgress>("data-transfer-progress", (event) => {
+      if (event.payload.phase === "items") setProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const toggle = <T,>(set: Set<T>, value: T) => {
+    const next = new Set(set);
+    if (next.has(value)) next.delete(value);
+    else next.add(value);
+    return next;
+  };
+
+  const runExport = async () => {
+    if (selectedGroups.size === 0 || types.size === 0) {
+      setMessage(t("settings.data.exportNothingSelected"));
+      return;
+    }
+    const options: QueryOptions = {
+      search: search.trim() || undefined,
+      content_type: types.size < EXPORT_CONTENT_TYPES.length ? [...types].join(",") : undefined,
+      favorite_only: favoritesOnly,
+      pinned_only: pinnedOnly,
+    };
+    setRunning(true);
+    setMessage(null);
+    try {
+      setMessage(await invoke<string>("export_items", { format, options, groupIds: [...selectedGroups] }));
+    } catch (e) {
+      if (!isUserCancelled(e)) setMessage(t("settings.data.exportFailed", { error: String(e) }));
+    } finally {
+      setRunning(false);
+      setProgress(null);
+    }
+  };
+
+  const chip = (key: string, label: string, active: boolean, onClick: () => void) => (
+    <Button
+      key={key}
+      type="button"
+      size="sm"
+      variant={active ? "default" : "outline"}
+      onClick={onClick}
+      disabled={running}
+      className="h-7 px-2.5"
+    >
+      {label}
+    </Button>
+  );
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.openExportTitle")}
+        description={t("settings.data.openExportDesc")}
+      />
+      <div className="space-y-3">
+        <div className="grid grid-cols-3 gap-2">
+          {OPEN_EXPORT_FORMATS.map((f) =>
+            chip(f, t(`settings.data.openExportFormat.${f}`), format === f, () => setFormat(f)),
+          )}
+        </div>
+        <p className="text-xs text-muted-foreground">{t(`settings.data.openExportFormatDesc.${format}`)}</p>
+        <div className="space-y-1.5">
+          <Label className="text-xs">{t("settings.data.exportGroups")}</Label>
+          <div className="flex flex-wrap gap-1.5">
+            {chip("default", t("groups.defaultGroupFull"), selectedGroups.has(null), () =>
+              setSelectedGroups((prev) => toggle(prev, null)),
+            )}
+            {groups.map((g) =>
+              chip(String(g.id), g.name, selectedGroups.has(g.id), () =>
+                setSelectedGroups((prev) => toggle(prev, g.id)),
+              ),
+            )}
+          </div>
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-xs">{t("settings.data.exportContentTypes")}</Label>
+          <div className="flex flex-wrap gap-1.5">
+            {EXPORT_CONTENT_TYPES.map((type) =>
+              chip(type, t(`contentType.${type}`), types.has(type), () =>
+                setTypes((prev) => toggle(prev, type)),
+              ),
+            )}
+          </div>
+        </div>
+        <div className="space-y-1.5">
+          <Label htmlFor="open-export-search" className="text-xs">{t("settings.data.openExportSearch")}</Label>
+          <Input
+            id="open-export-search"
+            value={search}
+            onChange={(e) => setSearch(e.target.value)}
+            disabled={running}
+            className="h-8 text-sm"
+          />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label className="text-xs">{t("settings.data.exportFavoritesOnly")}</Label>
+          <Switch checked={favoritesOnly} onCheckedChange={setFavoritesOnly} disabled={running} />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label className="text-xs">{t("settings.data.openExportPinnedOnly")}</Label>
+          <Switch checked={pinnedOnly} onCheckedChange={setPinnedOnly} disabled={running} />
+        </div>
+        <div className="flex items-center gap-2">
+          <Button variant="outline" size="sm" disabled={running} onClick={() => void runExport()}>
+            <ArrowUpload16Regular className="w-4 h-4 mr-1.5" />
+            {running ? t("settings.data.exporting") : t("settings.data.exportStart")}
+          </Button>
+          {running && progress && (
+            <>
+              <span className="text-xs text-muted-foreground">
+                {t("settings.data.transferProgress", {
+                  phase: t("settings.data.transferPhase.items"),
+                  percent: progress.total > 0 ? Math.floor((progress.done / progress.total) * 100) : 0,
+                })}
+              </span>
+              <Button variant="ghost" size="sm" onClick={() => void invoke("cancel_data_transfer")}>
+                {t("common.cancel")}
+              </Button>
+            </>
+          )}
+        </div>
+        {message && (
+          <p className={`text-xs ${isErrorMessage(message) ? "text-destructive" : "text-muted-foreground"}`}>
+            {message}
+          </p>
+        )}
+      </div>
+    </SettingsCard>
+  );
+}
+
+const EXTERNAL_IMPORT_SOURCES = ["windows_history", "ditto", "copyq", "maccy", "clipy"] as const;
+type ExternalImportSource = (typeof EXTERNAL_IMPORT_SOURCES)[number];
+
+interface ExternalImportReport {
+  source: ExternalImportSource;
+  total: number;
+  imported: number;
+  duplicates: number;
+  groups_created: string[];
+  skipped: { index: number; reason: "unsupported" | "invalid_image" | "store_failed"; detail: string | null }[];
+}
+
+const EXTERNAL_SKIP_PREVIEW_LIMIT = 20;
+
+/** 从其他剪贴板工具迁移历史 */
+function ExternalImportCard() {
+  const { t } = useTranslation();
+  const [source, setSource] = useState<ExternalImportSource>("windows_history");
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<TransferProgress | null>(null);
+  const [report, setReport] = useState<ExternalImportReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<TransferProgress>("data-transfer-progress", (event) => {
+      if (event.payload.phase === "external") setProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const runImport = async () => {
+    setRunning(true);
+    setError(null);
+    setReport(null);
+    try {
+      setReport(await invoke<ExternalImportReport>("import_from_app", { source }));
+    } catch (e) {
+      if (!isUserCancelled(e)) setError(String(e));
+    } finally {
+      setRunning(false);
+      setProgress(null);
+    }
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.data.externalImportTitle")}
+        description={t("settings.data.externalImportDesc")}
+      />
+      <div className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Select value={source} onValueChange={(v) => setSource(v as ExternalImportSource)} disabled={running}>
+            <SelectTrigger className="flex-1 h-8 text-xs"><SelectValue /></SelectTrigger>
+            <SelectContent>
+              {EXTERNAL_IMPORT_SOURCES.map((s) => (
+                <SelectItem key={s} value={s}>{t(`settings.data.externalImportSource.${s}`)}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Button variant="outline" size="sm" disabled={running} onClick={() => void runImport()}>
+            <ArrowDownload16Regular className="w-4 h-4 mr-1.5" />
+            {running ? t("settings.data.importing") : t("settings.data.externalImportRun")}
+          </Button>
+        </div>
+        <p className="text-xs text-muted-foreground">{t(`settings.data.externalImportHint.${source}`)}</p>
+        {running && progress && (
+          <div className="flex items-center justify-between gap-2">
+            <span className="text-xs text-muted-foreground">
+              {t("settings.data.transferProgress", {
+                phase: t("settings.data.transferPhase.external"),
+                percent: progress.total > 0 ? Math.floor((progress.done / progress.total) * 100) : 0,
+              })}
+            </span>
+            <Button variant="ghost" size="sm" onClick={() => void invoke("cancel_data_transfer")}>
+              {t("common.cancel")}
+            </Button>
+          </div>
+        )}
+        {error && <p className="text-xs text-destructive">{error}</p>}
+        {report && (
+          <div className="rounded-md bg-muted-surface-subtle px-3 py-2 space-y-1 text-xs">
+            <p>
+              {t("settings.data.externalImportSummary", {
+                total: report.total,
+                imported: report.imported,
+                duplicates: report.duplicates,
+                skipped: report.skipped.length,
+              })}
+            </p>
+            {report.groups_created.length > 0 && (
+              <p className="text-muted-foreground">
+                {t("settings.data.mergeGroupsCreated", { groups: report.groups_created.join(", ") })}
+              </p>
+            )}
+            {report.skipped.length > 0 && (
+              <ul className="max-h-32 overflow-y-auto space-y-0.5 text-muted-foreground">
+                {report.skipped.slice(0, EXTERNAL_SKIP_PREVIEW_LIMIT).map((s) => (
+                  <li key={s.index} className="truncate">
+                    #{s.index + 1} {t(`settings.data.externalSkipReason.${s.reason}`)}
+                    {s.detail && `: ${s.detail}`}
+                  </li>
+                ))}
+              </ul>
+            )}
+          </div>
+        )}
+      </div>
+    </SettingsCard>
+  );
+}
+
+const EXPORT_CONTENT_TYPES = ["text", "url", "html", "rtf", "image", "files"] as const;
+
+/** 导出范围，字段缺省表示不限制（对应后端 `ExportFilter`） */
+interface ExportFilter {
+  /** null = 默认分组 */
+  group_ids?: (number | null)[];
+  date_from?: string;
+  date_to?: string;
+  content_types?: string[];
+  favorites_only?: boolean;
+}
+
+interface ExportRequest {
+  filter: ExportFilter | null;
+  password: string | null;
+}
+
+interface TransferProgress {
+  phase: "export" | "encrypt" | "decrypt" | "import" | "external" | "items";
+  done: number;
+  total: number;
+}
+
+interface MergeConflict {
+  kind: "other_group" | "state_differs" | "media_missing" | "group_color";
+  backup_id: number;
+  preview: string | null;
+}
+
+interface MergeReport {
+  dry_run: boolean;
+  backup_items: number;
+  imported: number;
+  duplicates: number;
+  skipped: number;
+  groups_created: string[];
+  groups_matched: string[];
+  media_files: number;
+  media_bytes: number;
+  conflicts: MergeConflict[];
+}
+
+/** 已解析的备份（合并预览完成，等待用户选择合并或替换） */
+interface PendingImport {
+  path: string;
+  password: string | null;
+  report: MergeReport;
+}
+
+const MERGE_CONFLICT_PREVIEW_LIMIT = 20;
+
+interface ImportModeDialogProps {
+  pending: PendingImport | null;
+  onCancel: () => void;
+  onMerge: () => void;
+  onReplace: () => void;
+}
+
+/** 导入方式选择：展示合并预览（新增、重复、跳过、分组与冲突），可合并或整体替换 */
+function ImportModeDialog({ pending, onCancel, onMerge, onReplace }: ImportModeDialogProps) {
+  const { t } = useTranslation();
+  const report = pending?.report;
+  const conflicts = report?.conflicts.slice(0, MERGE_CONFLICT_PREVIEW_LIMIT) ?? [];
+
+  return (
+    <Dialog open={pending !== null} onOpenChange={(open) => { if (!open) onCancel(); }}>
+      <DialogContent className="max-w-md" showCloseButton={false}>
+        <DialogHeader>
+          <DialogTitle>{t("settings.data.importModeTitle")}</DialogTitle>
+          <DialogDescription>{t("settings.data.importModeDesc")}</DialogDescription>
+        </DialogHeader>
+        {report && (
+          <div className="space-y-2 text-xs">
+            <p>
+              {t("settings.data.mergeSummary", {
+                total: report.backup_items,
+                imported: report.imported,
+                duplicates: report.duplicates,
+                skipped: report.skipped,
+              })}
+            </p>
+            {report.media_files > 0 && (
+              <p className="text-muted-foreground">
+                {t("settings.data.mergeMedia", {
+                  count: report.media_files,
+                  size: formatDataSize(report.media_bytes),
+                })}
+              </p>
+            )}
+            {report.groups_created.length > 0 && (
+              <p className="text-muted-foreground">
+                {t("settings.data.mergeGroupsCreated", { groups: report.groups_created.join(", ") })}
+              </p>
+            )}
+            {report.groups_matched.length > 0 && (
+              <p className="text-muted-foreground">
+                {t("settings.data.mergeGroupsMatched", { groups: report.groups_matched.join(", ") })}
+              </p>
+            )}
+            {conflicts.length > 0 && (
+              <div className="space-y-1">
+                <p className="font-medium">
+                  {t("settings.data.mergeConflicts", { count: report.conflicts.length })}
+                </p>
+                <ul className="max-h-40 overflow-y-auto space-y-0.5 text-muted-foreground">
+                  {conflicts.map((conflict) => (
+                    <li key={`${conflict.kind}-${conflict.backup_id}`} className="truncate">
+                      {t(`settings.data.mergeConflictKind.${conflict.kind}`)}
+                      {conflict.preview ? `: ${conflict.preview}` : ""}
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+          </div>
+        )}
+        <DialogFooter>
+          <Button variant="outline" onClick={onCancel}>
+            {t("common.cancel")}
+          </Button>
+          <Button variant="outline" onClick={onReplace}>
+            {t("settings.data.importReplace")}
+          </Button>
+          <Button onClick={onMerge} disabled={!report || report.imported === 0}>
+            {t("settings.data.importMerge")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+interface ExportOptionsDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onConfirm: (request: ExportRequest) => void;
+}
+
+function ExportOptionsDialog({ open, onOpenChange, onConfirm }: ExportOptionsDialogProps) {
+  const { t } = useTranslation();
+  const [custom, setCustom] = useState(false);
+  const [groups, setGroups] = useState<Group[]>([]);
+  const [selectedGroups, setSelectedGroups] = useState<Set<number | null>>(new Set([null]));
+  const [dateFrom, setDateFrom] = useState("");
+  const [dateTo, setDateTo] = useState("");
+  const [types, setTypes] = useState<Set<string>>(new Set(EXPORT_CONTENT_TYPES));
+  const [favoritesOnly, setFavoritesOnly] = useState(false);
+  const [encrypt, setEncrypt] = useState(false);
+  const [password, setPassword] = useState("");
+  const [passwordConfirm, setPasswordConfirm] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!open) return;
+    setError(null);
+    invoke<Group[]>("get_groups").then((list) => {
+      setGroups(list);
+      setSelectedGroups(new Set([null, ...list.map((g) => g.id)]));
+    }).catch((e) => logError("Failed to load groups:", e));
+  }, [open]);
+
+  const toggle = <T,>(set: Set<T>, value: T) => {
+    const next = new Set(set);
+    if (next.has(value)) next.delete(value);
+    else next.add(value);
+    return next;
+  };
+
+  const buildFilter = (): ExportFilter | null => {
+    if (!custom) return null;
+    const filter: ExportFilter = {};
+    if (selectedGroups.size < groups.length + 1) filter.group_ids = [...selectedGroups];
+    if (types.size < EXPORT_CONTENT_TYPES.length) filter.content_types = [...types];
+    if (dateFrom) filter.date_from = dateFrom;
+    if (dateTo) filter.date_to = dateTo;
+    if (favoritesOnly) filter.favorites_only = true;
+    return Object.keys(filter).length > 0 ? filter : null;
+  };
+
+  const handleConfirm = () => {
+    if (custom && (selectedGroups.size === 0 || types.size === 0)) {
+      setError(t("settings.data.exportNothingSelected"));
+      return;
+    }
+    if (encrypt && (!password || password !== passwordConfirm)) {
+      setError(t("settings.data.exportPasswordMismatch"));
+      return;
+    }
+    onConfirm({ filter: buildFilter(), password: encrypt ? password : null });
+    setPassword("");
+    setPasswordConfirm("");
+  };
+
+  const chip = (key: string, label: string, active: boolean, onClick: () => void) => (
+    <Button
+      key={key}
+      type="button"
+      size="sm"
+      variant={active ? "default" : "outline"}
+      onClick={onClick}
+      className="h-7 px-2.5"
+    >
+      {label}
+    </Button>
+  );
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-md" showCloseButton={false}>
+        <DialogHeader>
+          <DialogTitle>{t("settings.data.exportOptionsTitle")}</DialogTitle>
+          <DialogDescription>{t("settings.data.exportOptionsDesc")}</DialogDescription>
+        </DialogHeader>
+        <div className="space-y-3 py-2">
+          <div className="grid grid-cols-2 gap-2">
+            {chip("all", t("settings.data.exportScopeAll"), !custom, () => setCustom(false))}
+            {chip("custom", t("settings.data.exportScopeCustom"), custom, () => setCustom(true))}
+          </div>
+          {custom && (
+            <>
+              <div className="space-y-1.5">
+                <Label className="text-xs">{t("settings.data.exportGroups")}</Label>
+                <div className="flex flex-wrap gap-1.5">
+                  {chip("default", t("groups.defaultGroupFull"), selectedGroups.has(null), () =>
+                    setSelectedGroups((prev) => toggle(prev, null)),
+                  )}
+                  {groups.map((g) =>
+                    chip(String(g.id), g.name, selectedGroups.has(g.id), () =>
+                      setSelectedGroups((prev) => toggle(prev, g.id)),
+                    ),
+                  )}
+                </div>
+              </div>
+              <div className="space-y-1.5">
+                <Label className="text-xs">{t("settings.data.exportContentTypes")}</Label>
+                <div className="flex flex-wrap gap-1.5">
+                  {EXPORT_CONTENT_TYPES.map((type) =>
+                    chip(type, t(`contentType.${type}`), types.has(type), () =>
+                      setTypes((prev) => toggle(prev, type)),
+                    ),
+                  )}
+                </div>
+              </div>
+              <div className="grid grid-cols-2 gap-3">
+                <div className="space-y-1.5">
+                  <Label htmlFor="export-date-from" className="text-xs">{t("settings.data.exportDateFrom")}</Label>
+                  <Input id="export-date-from" type="date" value={dateFrom} onChange={(e) => setDateFrom(e.target.value)} className="h-8 text-sm" />
+                </div>
+                <div className="space-y-1.5">
+                  <Label htmlFor="export-date-to" className="text-xs">{t("settings.data.exportDateTo")}</Label>
+                  <Input id="export-date-to" type="date" value={dateTo} onChange={(e) => setDateTo(e.target.value)} className="h-8 text-sm" />
+                </div>
+              </div>
+              <div className="flex items-center justify-between">
+                <Label className="text-xs">{t("settings.data.exportFavoritesOnly")}</Label>
+                <Switch checked={favoritesOnly} onCheckedChange={setFavoritesOnly} />
+              </div>
+            </>
+          )}
+          <div className="flex items-center justify-between gap-4">
+            <div className="space-y-0.5">
+              <Label className="text-xs">{t("settings.data.exportEncrypt")}</Label>
+              <p className="text-xs text-muted-foreground">{t("settings.data.exportEncryptDesc")}</p>
+            </div>
+            <Switch checked={encrypt} onCheckedChange={setEncrypt} />
+          </div>
+          {encrypt && (
+            <div className="grid grid-cols-2 gap-3">
+              <div className="space-y-1.5">
+                <Label htmlFor="export-password" className="text-xs">{t("settings.data.exportPassword")}</Label>
+                <Input id="export-password" type="password" value={password} onChange={(e) => setPassword(e.target.value)} className="h-8 text-sm" />
+              </div>
+              <div className="space-y-1.5">
+                <Label htmlFor="export-password-confirm" className="text-xs">{t("settings.data.exportPasswordConfirm")}</Label>
+                <Input id="export-password-confirm" type="password" value={passwordConfirm} onChange={(e) => setPasswordConfirm(e.target.value)} className="h-8 text-sm" />
+              </div>
+            </div>
+          )}
+          {error && <p className="text-xs text-destructive">{error}</p>}
+        </div>
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            {t("common.cancel")}
+          </Button>
+          <Button onClick={handleConfirm}>{t("settings.data.exportStart")}</Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
 function formatKB(kb: number, fractionDigits = 1, unlimitedLabel: string): string {
   if (kb === 0) return unlimitedLabel;
   if (kb >= 1024) return `${(kb / 1024).toFixed(fractionDigits)} MB`;
@@ -257,6 +1444,11 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
   const [exporting, setExporting] = useState(false);
   const [importing, setImporting] = useState(false);
   const [exportImportMsg, setExportImportMsg] = useState<string | null>(null);
+  const [exportDialogOpen, setExportDialogOpen] = useState(false);
+  const [transferProgress, setTransferProgress] = useState<TransferProgress | null>(null);
+  const [importPath, setImportPath] = useState<string | null>(null);
+  const [importPassword, setImportPassword] = useState("");
+  const [pendingImport, setPendingImport] = useState<PendingImport | null>(null);
   const [dedupStrategy, setDedupStrategy] = useState<DedupStrategy>("move_to_top");
 
   // 数据清理
@@ -421,11 +1613,21 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
     }
   };
 
-  const handleExport = async () => {
+  useEffect(() => {
+    const unlisten = listen<TransferProgress>("data-transfer-progress", (event) => {
+      setTransferProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleExport = async ({ filter, password }: ExportRequest) => {
+    setExportDialogOpen(false);
     setExporting(true);
     setExportImportMsg(null);
     try {
-      const msg = await invoke<string>("export_data");
+      const msg = await invoke<string>("export_data", { filter, password });
       setExportImportMsg(msg);
     } catch (error) {
       if (!isUserCancelled(error)) {
@@ -433,14 +1635,15 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
       }
     } finally {
       setExporting(false);
+      setTransferProgress(null);
     }
   };
 
-  const handleImport = async () => {
+  const runImport = async (path: string, password: string | null) => {
     setImporting(true);
     setExportImportMsg(null);
     try {
-      const msg = await invoke<string>("import_data");
+      const msg = await invoke<string>("import_data", { path, password });
       setExportImportMsg(msg);
       // 导入成功后重启应用
       await invoke("restart_app");
@@ -450,9 +1653,71 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
       }
     } finally {
       setImporting(false);
+      setTransferProgress(null);
     }
   };
 
+  /** 解析备份并预览合并结果，再由用户选择合并或替换 */
+  const previewImport = async (path: string, password: string | null) => {
+    setImporting(true);
+    setExportImportMsg(null);
+    try {
+      const report = await invoke<MergeReport>("merge_import_data", { path, password, dryRun: true });
+      setPendingImport({ path, password, report });
+    } catch (error) {
+      if (!isUserCancelled(error)) {
+        setExportImportMsg(t("settings.data.importFailed", { error: String(error) }));
+      }
+    } finally {
+      setImporting(false);
+      setTransferProgress(null);
+    }
+  };
+
+  const runMerge = async ({ path, password }: PendingImport) => {
+    setPendingImport(null);
+    setImporting(true);
+    try {
+      const report = await invoke<MergeReport>("merge_import_data", { path, password, dryRun: false });
+      setExportImportMsg(t("settings.data.mergeDone", {
+        imported: report.imported,
+        duplicates: report.duplicates,
+        skipped: report.skipped,
+      }));
+    } catch (error) {
+      if (!isUserCancelled(error)) {
+        setExportImportMsg(t("settings.data.importFailed", { error: String(error) }));
+      }
+    } finally {
+      setImporting(false);
+      setTransferProgress(null);
+    }
+  };
+
+  const handleImport = async () => {
+    setExportImportMsg(null);
+    try {
+      const file = await invoke<{ path: string; encrypted: boolean } | null>("pick_backup_file");
+      if (!file) return;
+      if (file.encrypted) {
+        // 加密备份：先询问密码
+        setImportPassword("");
+        setImportPath(file.path);
+        return;
+      }
+      await previewImport(file.path, null);
+    } catch (error) {
+      setExportImportMsg(t("settings.data.importFailed", { error: String(error) }));
+    }
+  };
+
+  const confirmImportPassword = () => {
+    const path = importPath;
+    if (!path || !importPassword) return;
+    setImportPath(null);
+    void previewImport(path, importPassword);
+  };
+
   const openDataFolder = async () => {
     try {
       await invoke("open_data_folder");
@@ -509,9 +1774,37 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
                 <p className="text-xs text-muted-foreground">{t("settings.data.database")}</p>
               </div>
               <div className="text-center p-2 rounded-md bg-muted-surface">
-                <p className="text-sm font-medium tabular-nums">{formatDataSize(dataSize.images_size)}</p>
-                <p className="text-xs text-muted-foreground">{t("common.imagesCount", { count: dataSize.images_count })}</p>
+                <p className="text-sm font-medium tabular-nums">
+                  {formatDataSize(dataSize.images_size + (dataSize.staged_size ?? 0) + (dataSize.blobs_size ?? 0))}
+                </p>
+                <p className="text-xs text-muted-foreground">
+                  {t("settings.data.mediaFilesCount", {
+                    count: dataSize.images_count + (dataSize.staged_count ?? 0) + (dataSize.blobs_count ?? 0),
+                  })}
+                </p>
               </div>
+              {dataSize.usage && dataSize.usage.by_type.length > 0 && (
+                <div className="col-span-3 grid grid-cols-2 gap-3 text-xs">
+                  <div className="space-y-1">
+                    <p className="font-medium">{t("settings.data.usageByType")}</p>
+                    {dataSize.usage.by_type.map((u) => (
+                      <div key={u.content_type} className="flex justify-between text-muted-foreground">
+                        <span>{t(`contentType.${u.content_type}`)} ({u.count})</span>
+                        <span className="tabular-nums">{formatDataSize(u.bytes)}</span>
+                      </div>
+                    ))}
+                  </div>
+                  <div className="space-y-1">
+                    <p className="font-medium">{t("settings.data.usageByGroup")}</p>
+                    {dataSize.usage.by_group.map((u) => (
+                      <div key={u.group_id ?? "default"} className="flex justify-between text-muted-foreground">
+                        <span className="truncate">{u.name ?? t("groups.defaultGroupFull")} ({u.count})</span>
+                        <span className="tabular-nums">{formatDataSize(u.bytes)}</span>
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
             </div>
           ) : (
             <p className="text-xs text-muted-foreground">{t("settings.data.statsRefreshHint")}</p>
@@ -575,7 +1868,7 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
             <Button
               variant="outline"
               size="sm"
-              onClick={handleExport}
+              onClick={() => setExportDialogOpen(true)}
               disabled={exporting || importing}
               className="flex-1"
             >
@@ -593,6 +1886,21 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
               {importing ? t("settings.data.importing") : t("settings.data.importData")}
             </Button>
           </div>
+          {(exporting || importing) && transferProgress && (
+            <div className="flex items-center justify-between gap-2 mt-2">
+              <span className="text-xs text-muted-foreground">
+                {t("settings.data.transferProgress", {
+                  phase: t(`settings.data.transferPhase.${transferProgress.phase}`),
+                  percent: transferProgress.total > 0
+                    ? Math.floor((transferProgress.done / transferProgress.total) * 100)
+                    : 0,
+                })}
+              </span>
+              <Button variant="ghost" size="sm" onClick={() => void invoke("cancel_data_transfer")}>
+                {t("common.cancel")}
+              </Button>
+            </div>
+          )}
           {exportImportMsg && (
             <p className={`text-xs mt-2 ${isErrorMessage(exportImportMsg) ? "text-destructive" : "text-muted-foreground"}`}>
               {exportImportMsg}
@@ -600,6 +1908,9 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
           )}
         </SettingsCard>
 
+        <OpenExportCard />
+        <ExternalImportCard />
+
         {/* Data Cleanup Card */}
         <SettingsCard>
           <SettingsCardHeader
@@ -665,6 +1976,9 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
         {/* Dedup Strategy Card */}
         <DedupStrategyCard strategy={dedupStrategy} onChange={handleDedupStrategyChange} />
         <TextDedupModeCard dedupStrategy={dedupStrategy} />
+        <FolderSnapshotCard />
+        <QuotaCard />
+        <StorageCheckCard onRepaired={() => void refreshDataSize()} />
 
         {/* History Limit Card */}
         <SettingsCard>
@@ -751,8 +2065,54 @@ export function DataTab({ settings, onSettingsChange }: DataTabProps) {
             </div>
           </div>
         </SettingsCard>
+        <RetentionCard />
       </div>
 
+      {/* Export Options Dialog */}
+      <ExportOptionsDialog
+        open={exportDialogOpen}
+        onOpenChange={setExportDialogOpen}
+        onConfirm={(request) => void handleExport(request)}
+      />
+
+      {/* Import Mode Dialog */}
+      <ImportModeDialog
+        pending={pendingImport}
+        onCancel={() => setPendingImport(null)}
+        onMerge={() => { if (pendingImport) void runMerge(pendingImport); }}
+        onReplace={() => {
+          if (!pendingImport) return;
+          setPendingImport(null);
+          void runImport(pendingImport.path, pendingImport.password);
+        }}
+      />
+
+      {/* Import Password Dialog */}
+      <Dialog open={importPath !== null} onOpenChange={(open) => { if (!open) setImportPath(null); }}>
+        <DialogContent className="max-w-sm" showCloseButton={false}>
+          <DialogHeader>
+            <DialogTitle>{t("settings.data.importPasswordTitle")}</DialogTitle>
+            <DialogDescription>{t("settings.data.importPasswordDesc")}</DialogDescription>
+          </DialogHeader>
+          <Input
+            type="password"
+            autoFocus
+            value={importPassword}
+            onChange={(e) => setImportPassword(e.target.value)}
+            onKeyDown={(e) => { if (e.key === "Enter") confirmImportPassword(); }}
+            className="h-8 text-sm"
+          />
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setImportPath(null)}>
+              {t("common.cancel")}
+            </Button>
+            <Button onClick={confirmImportPassword} disabled={!importPassword}>
+              {t("settings.data.importData")}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
       {/* Data Cleanup Confirmation Dialog */}
       <Dialog open={cleanDialogAction !== null} onOpenChange={(open) => { if (!open && !cleanLoading) setCleanDialogAction(null); }}>
         <DialogContent className="max-w-md" showCloseButton={false}>
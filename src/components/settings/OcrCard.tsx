@@ -0,0 +1,127 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { SettingsCard, SettingsCardHeader, SettingRow } from "@/components/settings/SettingSection";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+import { useOcrAvailable } from "@/stores/plugin-availability";
+
+interface BackfillProgress {
+  done: number;
+  total: number;
+}
+
+interface BackfillSummary {
+  total: number;
+  recognized: number;
+  failed: number;
+  cancelled: boolean;
+}
+
+export function OcrCard() {
+  const { t } = useTranslation();
+  const available = useOcrAvailable();
+  const [enabled, setEnabled] = useState(true);
+  const [progress, setProgress] = useState<BackfillProgress | null>(null);
+
+  useEffect(() => {
+    invoke<string | null>("get_setting", { key: "ocr_enabled" })
+      .then((value) => setEnabled(value !== "false"))
+      .catch((error) => logError("Failed to load OCR setting:", error));
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<BackfillProgress>("ocr-backfill-progress", (event) => {
+      setProgress(event.payload);
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const toggle = async (value: boolean) => {
+    setEnabled(value);
+    try {
+      await invoke("set_setting", { key: "ocr_enabled", value: String(value) });
+    } catch (error) {
+      setEnabled(!value);
+      logError("Failed to save OCR setting:", error);
+    }
+  };
+
+  const backfill = async () => {
+    setProgress({ done: 0, total: 0 });
+    try {
+      const summary = await invoke<BackfillSummary>("ocr_backfill_images");
+      showToast(
+        t(summary.cancelled ? "settings.ocr.backfillCancelled" : "settings.ocr.backfillDone", {
+          recognized: summary.recognized,
+          failed: summary.failed,
+        }),
+        "success",
+      );
+    } catch (error) {
+      showToast(t("settings.ocr.backfillFailed", { error: String(error) }));
+    } finally {
+      setProgress(null);
+    }
+  };
+
+  const running = progress !== null;
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.ocr.title")}
+        description={available ? t("settings.ocr.desc") : t("settings.ocr.unavailable")}
+      />
+      <div className="space-y-3">
+        <SettingRow
+          title={t("settings.ocr.enable")}
+          description={t("settings.ocr.enableDesc")}
+          action={
+            <Switch
+              checked={enabled}
+              disabled={!available}
+              onCheckedChange={(v) => void toggle(v)}
+              aria-label={t("settings.ocr.enable")}
+            />
+          }
+        />
+        <SettingRow
+          title={t("settings.ocr.backfill")}
+          description={
+            progress && progress.total > 0
+              ? t("settings.ocr.backfillProgress", { done: progress.done, total: progress.total })
+              : t("settings.ocr.backfillDesc")
+          }
+          action={
+            running ? (
+              <Button
+                variant="outline"
+                size="sm"
+                className="h-7 text-xs"
+                onClick={() => void invoke("cancel_ocr_backfill")}
+              >
+                {t("settings.ocr.cancel")}
+              </Button>
+            ) : (
+              <Button
+                variant="outline"
+                size="sm"
+                className="h-7 text-xs"
+                disabled={!available || !enabled}
+                onClick={() => void backfill()}
+              >
+                {t("settings.ocr.backfillStart")}
+              </Button>
+            )
+          }
+        />
+      </div>
+    </SettingsCard>
+  );
+}
@@ -1,5 +1,8 @@
 import { useMemo } from "react";
 import { ArrowSync16Regular, Translate16Regular } from "@fluentui/react-icons";
+import { AiTemplatesCard } from "@/components/settings/AiTemplatesCard";
+import { ApiCard } from "@/components/settings/ApiCard";
+import { OcrCard } from "@/components/settings/OcrCard";
 import { SettingRow, SettingSection } from "@/components/settings/SettingSection";
 import { Switch } from "@/components/ui/switch";
 import { useTranslation } from "@/i18n";
@@ -55,6 +58,9 @@ export function PluginsTab({ enabledMap, onToggle }: PluginsTabProps) {
           </SettingSection>
         );
       })}
+      <OcrCard />
+      <AiTemplatesCard />
+      <ApiCard />
     </div>
   );
 }
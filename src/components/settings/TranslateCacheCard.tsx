@@ -0,0 +1,77 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { SettingsCard, SettingsCardHeader, SettingRow } from "@/components/settings/SettingSection";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+
+export function TranslateCacheCard() {
+  const { t } = useTranslation();
+  const [enabled, setEnabled] = useState(true);
+  const [count, setCount] = useState<number | null>(null);
+
+  useEffect(() => {
+    invoke<string | null>("get_setting", { key: "translate_cache_enabled" })
+      .then((value) => setEnabled(value !== "false"))
+      .catch((error) => logError("Failed to load translation cache setting:", error));
+    invoke<number>("get_translation_cache_count")
+      .then(setCount)
+      .catch((error) => logError("Failed to count translation cache:", error));
+  }, []);
+
+  const toggle = async (value: boolean) => {
+    setEnabled(value);
+    try {
+      await invoke("set_setting", { key: "translate_cache_enabled", value: String(value) });
+    } catch (error) {
+      setEnabled(!value);
+      logError("Failed to save translation cache setting:", error);
+    }
+  };
+
+  const clear = async () => {
+    try {
+      const removed = await invoke<number>("clear_translation_cache");
+      setCount(0);
+      showToast(t("settings.translate.cacheCleared", { count: removed }), "success");
+    } catch (error) {
+      showToast(t("settings.translate.cacheClearFailed", { error: String(error) }));
+    }
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader title={t("settings.translate.cacheTitle")} />
+      <div className="space-y-3">
+        <SettingRow
+          title={t("settings.translate.cacheEnable")}
+          description={t("settings.translate.cacheEnableDesc")}
+          action={
+            <Switch
+              checked={enabled}
+              onCheckedChange={(v) => void toggle(v)}
+              aria-label={t("settings.translate.cacheEnable")}
+            />
+          }
+        />
+        <SettingRow
+          title={t("settings.translate.cacheClear")}
+          description={t("settings.translate.cacheCount", { count: count ?? "…" })}
+          action={
+            <Button
+              variant="outline"
+              size="sm"
+              className="h-7 text-xs"
+              disabled={!count}
+              onClick={() => void clear()}
+            >
+              {t("settings.translate.cacheClearAction")}
+            </Button>
+          }
+        />
+      </div>
+    </SettingsCard>
+  );
+}
@@ -0,0 +1,155 @@
+import { useCallback, useEffect, useState } from "react";
+import { ArrowClockwise16Regular, ChevronDown16Regular, ChevronUp16Regular, Dismiss16Regular } from "@fluentui/react-icons";
+import { invoke } from "@tauri-apps/api/core";
+import { SettingsCard, SettingsCardHeader } from "@/components/settings/SettingSection";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+import { getProviderOptions } from "@/lib/translate";
+import { useTranslateSettings } from "@/stores/translate-settings";
+
+/** 后端 `ProviderHealth`（本次运行期间的统计） */
+interface ProviderHealth {
+  successes: number;
+  failures: number;
+  consecutive_failures: number;
+  last_error: string | null;
+  last_latency_ms: number | null;
+  cooling_down: boolean;
+}
+
+export function TranslateFallbackCard() {
+  const { t } = useTranslation();
+  const provider = useTranslateSettings((s) => s.provider);
+  const [fallbacks, setFallbacks] = useState<string[]>([]);
+  const [health, setHealth] = useState<Record<string, ProviderHealth>>({});
+  const options = getProviderOptions();
+  const labelOf = (id: string) => options.find((o) => o.value === id)?.label ?? id;
+
+  const refreshHealth = useCallback(() => {
+    invoke<Record<string, ProviderHealth>>("get_translate_provider_health")
+      .then(setHealth)
+      .catch((error) => logError("Failed to load provider health:", error));
+  }, []);
+
+  useEffect(() => {
+    invoke<string | null>("get_setting", { key: "translate_fallback_providers" })
+      .then((raw) => {
+        const parsed: unknown = raw ? JSON.parse(raw) : [];
+        setFallbacks(Array.isArray(parsed) ? parsed.filter((p): p is string => typeof p === "string") : []);
+      })
+      .catch((error) => logError("Failed to load fallback providers:", error));
+    refreshHealth();
+  }, [refreshHealth]);
+
+  const save = async (next: string[]) => {
+    const previous = fallbacks;
+    setFallbacks(next);
+    try {
+      await invoke("set_translate_fallback_providers", { providers: next });
+    } catch (error) {
+      setFallbacks(previous);
+      showToast(t("settings.translate.fallbackSaveFailed", { error: String(error) }));
+    }
+  };
+
+  // 主服务不会作为备用重复请求
+  const secondary = fallbacks.filter((id) => id !== provider);
+  const chain = [provider, ...secondary];
+
+  const move = (index: number, delta: number) => {
+    const next = [...secondary];
+    [next[index], next[index + delta]] = [next[index + delta], next[index]];
+    void save(next);
+  };
+  const addable = options.filter((o) => !chain.includes(o.value));
+
+  const healthText = (id: string) => {
+    const h = health[id];
+    if (!h || h.successes + h.failures === 0) return t("settings.translate.healthUnused");
+    const parts = [t("settings.translate.healthCounts", { ok: h.successes, failed: h.failures })];
+    if (h.last_latency_ms !== null) parts.push(`${h.last_latency_ms} ms`);
+    if (h.cooling_down) parts.push(t("settings.translate.healthCooling"));
+    return parts.join(" · ");
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.translate.fallbackTitle")}
+        description={t("settings.translate.fallbackDesc")}
+      />
+      <div className="space-y-1">
+        {chain.map((id, index) => {
+          const fallbackIndex = index - 1;
+          return (
+            <div key={id} className="flex items-center gap-2 rounded-md px-2 py-1.5 hover:bg-accent/50">
+              <span className="w-4 shrink-0 text-right text-xs text-muted-foreground/60">{index + 1}</span>
+              <div className="flex-1 min-w-0">
+                <p className="text-xs">
+                  {labelOf(id)}
+                  {index === 0 && (
+                    <span className="ml-1.5 text-muted-foreground">{t("settings.translate.fallbackPrimary")}</span>
+                  )}
+                </p>
+                <p
+                  className={`text-xs truncate ${health[id]?.cooling_down ? "text-destructive" : "text-muted-foreground"}`}
+                  title={health[id]?.last_error ?? undefined}
+                >
+                  {healthText(id)}
+                </p>
+              </div>
+              {index > 0 && (
+                <div className="flex items-center shrink-0 text-muted-foreground">
+                  <button
+                    className="p-0.5 rounded hover:text-foreground hover:bg-accent disabled:opacity-30"
+                    disabled={fallbackIndex === 0}
+                    onClick={() => move(fallbackIndex, -1)}
+                    aria-label={t("settings.translate.fallbackUp")}
+                  >
+                    <ChevronUp16Regular className="w-3.5 h-3.5" />
+                  </button>
+                  <button
+                    className="p-0.5 rounded hover:text-foreground hover:bg-accent disabled:opacity-30"
+                    disabled={fallbackIndex === secondary.length - 1}
+                    onClick={() => move(fallbackIndex, 1)}
+                    aria-label={t("settings.translate.fallbackDown")}
+                  >
+                    <ChevronDown16Regular className="w-3.5 h-3.5" />
+                  </button>
+                  <button
+                    className="p-0.5 rounded hover:text-foreground hover:bg-accent"
+                    onClick={() => void save(secondary.filter((p) => p !== id))}
+                    aria-label={t("settings.translate.fallbackRemove")}
+                  >
+                    <Dismiss16Regular className="w-3.5 h-3.5" />
+                  </button>
+                </div>
+              )}
+            </div>
+          );
+        })}
+        <div className="flex items-center justify-between pt-2">
+          <Select value="" onValueChange={(id) => void save([...secondary, id])}>
+            <SelectTrigger className="w-[180px] h-8 text-xs" disabled={addable.length === 0}>
+              <SelectValue placeholder={t("settings.translate.fallbackAdd")} />
+            </SelectTrigger>
+            <SelectContent>
+              {addable.map((opt) => (
+                <SelectItem key={opt.value} value={opt.value}>{opt.label}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <button
+            className="flex items-center gap-1 text-xs text-muted-foreground hover:text-foreground transition-surface"
+            onClick={refreshHealth}
+          >
+            <ArrowClockwise16Regular className="w-3.5 h-3.5" />
+            {t("settings.translate.healthRefresh")}
+          </button>
+        </div>
+      </div>
+    </SettingsCard>
+  );
+}
@@ -0,0 +1,127 @@
+import { useEffect, useState } from "react";
+import { Add16Regular, Dismiss16Regular } from "@fluentui/react-icons";
+import { invoke } from "@tauri-apps/api/core";
+import { SettingsCard, SettingsCardHeader } from "@/components/settings/SettingSection";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { Switch } from "@/components/ui/switch";
+import { showToast } from "@/components/ui/toast";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+import { getLanguages } from "@/lib/translate";
+
+/** 后端 `GlossaryEntry` */
+interface GlossaryEntry {
+  source: string;
+  target: string;
+  case_sensitive: boolean;
+  /** 仅在翻译到该语言时生效，省略 = 所有目标语言 */
+  to?: string | null;
+}
+
+/** Select 不接受空值，用占位值表示「所有语言」 */
+const ANY_LANGUAGE = "__any__";
+
+export function TranslateGlossaryCard() {
+  const { t } = useTranslation();
+  const [entries, setEntries] = useState<GlossaryEntry[]>([]);
+  const [dirty, setDirty] = useState(false);
+
+  useEffect(() => {
+    invoke<GlossaryEntry[]>("get_translate_glossary")
+      .then(setEntries)
+      .catch((error) => logError("Failed to load glossary:", error));
+  }, []);
+
+  const update = (index: number, patch: Partial<GlossaryEntry>) => {
+    setEntries((prev) => prev.map((entry, i) => (i === index ? { ...entry, ...patch } : entry)));
+    setDirty(true);
+  };
+
+  const removeAt = (index: number) => {
+    setEntries((prev) => prev.filter((_, i) => i !== index));
+    setDirty(true);
+  };
+
+  const add = () => {
+    setEntries((prev) => [...prev, { source: "", target: "", case_sensitive: false }]);
+    setDirty(true);
+  };
+
+  const save = async () => {
+    // 空行视为未填写，直接丢弃
+    const cleaned = entries
+      .filter((entry) => entry.source.trim() || entry.target.trim())
+      .map((entry) => ({ ...entry, to: entry.to || undefined }));
+    try {
+      await invoke("set_translate_glossary", { entries: cleaned });
+      setEntries(cleaned);
+      setDirty(false);
+      showToast(t("settings.translate.glossarySaved"), "success");
+    } catch (error) {
+      showToast(t("settings.translate.glossarySaveFailed", { error: String(error) }));
+    }
+  };
+
+  return (
+    <SettingsCard>
+      <SettingsCardHeader
+        title={t("settings.translate.glossaryTitle")}
+        description={t("settings.translate.glossaryDesc")}
+      />
+      <div className="space-y-2">
+        {entries.map((entry, index) => (
+          <div key={index} className="flex items-center gap-2">
+            <Input
+              className="h-8 text-xs flex-1"
+              placeholder={t("settings.translate.glossarySource")}
+              value={entry.source}
+              onChange={(e) => update(index, { source: e.target.value })}
+            />
+            <Input
+              className="h-8 text-xs flex-1"
+              placeholder={t("settings.translate.glossaryTarget")}
+              value={entry.target}
+              onChange={(e) => update(index, { target: e.target.value })}
+            />
+            <Select
+              value={entry.to || ANY_LANGUAGE}
+              onValueChange={(v) => update(index, { to: v === ANY_LANGUAGE ? null : v })}
+            >
+              <SelectTrigger className="w-[110px] h-8 text-xs"><SelectValue /></SelectTrigger>
+              <SelectContent>
+                <SelectItem value={ANY_LANGUAGE}>{t("settings.translate.glossaryAnyLanguage")}</SelectItem>
+                {getLanguages().map((lang) => (
+                  <SelectItem key={lang.value} value={lang.value}>{lang.label}</SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Switch
+              checked={entry.case_sensitive}
+              onCheckedChange={(v) => update(index, { case_sensitive: v })}
+              aria-label={t("settings.translate.glossaryCaseSensitive")}
+              title={t("settings.translate.glossaryCaseSensitive")}
+            />
+            <button
+              className="p-0.5 rounded text-muted-foreground hover:text-foreground hover:bg-accent shrink-0"
+              onClick={() => removeAt(index)}
+              aria-label={t("settings.translate.glossaryRemove")}
+            >
+              <Dismiss16Regular className="w-3.5 h-3.5" />
+            </button>
+          </div>
+        ))}
+        <div className="flex items-center justify-between pt-1">
+          <Button variant="outline" size="sm" className="h-7 text-xs" onClick={add}>
+            <Add16Regular className="w-3.5 h-3.5 mr-1" />
+            {t("settings.translate.glossaryAdd")}
+          </Button>
+          <Button size="sm" className="h-7 text-xs" disabled={!dirty} onClick={() => void save()}>
+            {t("common.save")}
+          </Button>
+        </div>
+      </div>
+    </SettingsCard>
+  );
+}
@@ -2,6 +2,9 @@ import { useState, useEffect, useCallback, useRef } from "react";
 import { Eye16Regular, EyeOff16Regular } from "@fluentui/react-icons";
 import { invoke } from "@tauri-apps/api/core";
 import { SettingsCard, SettingsCardHeader } from "@/components/settings/SettingSection";
+import { TranslateCacheCard } from "@/components/settings/TranslateCacheCard";
+import { TranslateFallbackCard } from "@/components/settings/TranslateFallbackCard";
+import { TranslateGlossaryCard } from "@/components/settings/TranslateGlossaryCard";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
@@ -10,7 +13,7 @@ import { Switch } from "@/components/ui/switch";
 import { useTranslation } from "@/i18n";
 import { logError } from "@/lib/logger";
 import { KEY_CODE_MAP } from "@/lib/shortcut-helpers";
-import { getProviderOptions, getLanguages, translateText } from "@/lib/translate";
+import { getProviderOptions, getLanguages, testTranslateConnection } from "@/lib/translate";
 import { useTranslateSettings, type TranslateProvider, type LanguageMode } from "@/stores/translate-settings";
 
 export function TranslateTab() {
@@ -18,6 +21,7 @@ export function TranslateTab() {
   const {
     enabled, setEnabled,
     recordTranslation, setRecordTranslation,
+    saveLinked, setSaveLinked,
     provider, setProvider,
     languageMode, setLanguageMode,
     sourceLanguage, setSourceLanguage,
@@ -168,6 +172,15 @@ export function TranslateTab() {
             <Switch checked={recordTranslation} onCheckedChange={setRecordTranslation} />
           </div>
         )}
+        {enabled && (
+          <div className="flex items-center justify-between pt-4 mt-1">
+            <div className="space-y-0.5">
+              <Label className="text-xs">{t("settings.translate.saveLinked")}</Label>
+              <p className="text-xs text-muted-foreground">{t("settings.translate.saveLinkedDesc")}</p>
+            </div>
+            <Switch checked={saveLinked} onCheckedChange={setSaveLinked} />
+          </div>
+        )}
       </SettingsCard>
 
       {enabled && (
@@ -315,7 +328,7 @@ export function TranslateTab() {
                   onClick={async () => {
                     setTesting(true); setTestResult(null);
                     try {
-                      const result = await translateText("Hello");
+                      const result = await testTranslateConnection();
                       setTestResult({ ok: true, msg: t("settings.translate.testSuccess", { result }) });
                     } catch (error) {
                       setTestResult({ ok: false, msg: String(error) });
@@ -332,6 +345,12 @@ export function TranslateTab() {
             </div>
           </SettingsCard>
 
+          <TranslateFallbackCard />
+
+          <TranslateGlossaryCard />
+
+          <TranslateCacheCard />
+
           {/* 语言设置 */}
           <SettingsCard>
             <SettingsCardHeader
@@ -41,13 +41,37 @@ export const extended = {
       saveAs: "Save as",
       viewDetails: "View details",
       removeRule: "Remove {{rule}}",
+      cancelStaging: "Cancel file copy",
+      retryStaging: "Retry file copy",
+      pasteImageText: "Paste text in image",
+      pluginActions: "Plugin actions",
+      pluginSaved: "Result saved as a new item",
+      pluginCopied: "Result copied to clipboard",
+      pluginFailed: "Plugin action failed: {{error}}",
+      aiActions: "AI actions",
+      addToPasteQueue: "Add to paste queue",
+      aiFailed: "AI action failed: {{error}}",
+    },
+    pasteQueue: {
+      title: "Paste queue ({{count}})",
+      next: "Paste next",
+      nextHint: "Next: {{preview}}",
+      fifo: "First in, first out",
+      lifo: "Last in, first out",
+      clear: "Clear",
+      remove: "Remove from queue",
+      batchAdd: "Add to queue",
     },
   },
   cardContent: {
     imageLoadFailed: "Failed to load image",
+    imageMissing: "Image file is missing",
     fileCount: "{{count}} files",
     invalid: "(invalid)",
     fileTooLarge: "(file too large)",
+    staging: "(copying {{percent}}%)",
+    stagingPartial: "(partially copied)",
+    stagingFailed: "(copy failed)",
   },
   textEditor: {
     title: "Edit text",
@@ -71,6 +95,20 @@ export const extended = {
     charCount: "{{count}} chars",
     retranslate: "Retranslate",
   },
+  aiResult: {
+    title: "AI",
+    titleWithTemplate: "AI · {{name}}",
+    waiting: "Waiting for result...",
+    generating: "Generating...",
+    result: "Result",
+    copy: "Copy",
+    copied: "Copied",
+    cancel: "Stop",
+    cancelled: "Stopped",
+    save: "Save as item",
+    saved: "Saved",
+    sourceInfo: "{{count}} items · {{chars}} chars",
+  },
   scrollTop: {
     backToTop: "Back to top",
   },
@@ -201,13 +239,93 @@ export const extended = {
       migrateHint: "Changing path migrates data and restarts the app",
       restoreDefault: "Restore default",
       backupTitle: "Backup",
-      backupDesc: "Export or import clipboard data (ZIP)",
+      backupDesc: "Export or import clipboard data (ZIP, optionally encrypted)",
       exporting: "Exporting...",
       exportData: "Export",
       importing: "Importing...",
       importData: "Import",
       exportFailed: "Export failed: {{error}}",
       importFailed: "Import failed: {{error}}",
+      exportOptionsTitle: "Export data",
+      exportOptionsDesc: "Choose what to export. Media files referenced by the exported items are included.",
+      exportScopeAll: "Everything",
+      exportScopeCustom: "Selected items",
+      exportGroups: "Groups",
+      exportDateFrom: "Created from",
+      exportDateTo: "Created until",
+      exportContentTypes: "Content types",
+      exportFavoritesOnly: "Favorites only",
+      exportEncrypt: "Protect with password",
+      exportEncryptDesc: "The backup is encrypted with AES-256; it cannot be restored without the password",
+      exportPassword: "Password",
+      exportPasswordConfirm: "Confirm password",
+      exportPasswordMismatch: "Passwords do not match",
+      exportNothingSelected: "Select at least one group and one content type",
+      exportStart: "Export",
+      openExportTitle: "Export as open formats",
+      openExportDesc: "Export items readable without the app, filtered like the history list",
+      openExportFormat: {
+        jsonl: "JSON Lines",
+        markdown: "Markdown",
+        html: "HTML archive",
+      },
+      openExportFormatDesc: {
+        jsonl: "One item per line; images and icons are copied to a _media folder next to the file and referenced by relative path.",
+        markdown: "One notebook per group in a new folder, with images in media/.",
+        html: "A single self-contained page with inline image thumbnails.",
+      },
+      openExportSearch: "Contains text",
+      openExportPinnedOnly: "Pinned only",
+      transferProgress: "{{phase}}… {{percent}}%",
+      transferPhase: {
+        export: "Writing backup",
+        encrypt: "Encrypting",
+        decrypt: "Decrypting",
+        import: "Extracting and verifying",
+        external: "Importing from another app",
+        items: "Exporting items",
+      },
+      importPasswordTitle: "Encrypted backup",
+      importPasswordDesc: "Enter the password that was used when exporting this backup",
+      importModeTitle: "Import backup",
+      importModeDesc: "Merge adds items you don't have yet to your current history. Replace swaps the whole database and restarts the app.",
+      importMerge: "Merge",
+      importReplace: "Replace",
+      mergeSummary: "{{total}} items in backup: {{imported}} new, {{duplicates}} already present, {{skipped}} skipped",
+      mergeMedia: "{{count}} media files ({{size}}) will be copied",
+      mergeGroupsCreated: "New groups: {{groups}}",
+      mergeGroupsMatched: "Matched by name: {{groups}}",
+      mergeConflicts: "{{count}} conflicts (current data is kept)",
+      mergeConflictKind: {
+        other_group: "In another group",
+        state_differs: "Favorite / pin differs",
+        media_missing: "Image missing, skipped",
+        group_color: "Group color differs",
+      },
+      mergeDone: "Merge complete: {{imported}} added, {{duplicates}} already present, {{skipped}} skipped",
+      externalImportTitle: "Import from other clipboard managers",
+      externalImportDesc: "Migrate history from Windows clipboard history, Ditto, CopyQ, Maccy or Clipy. Existing items are skipped.",
+      externalImportRun: "Import",
+      externalImportSource: {
+        windows_history: "Windows clipboard history",
+        ditto: "Ditto",
+        copyq: "CopyQ",
+        maccy: "Maccy",
+        clipy: "Clipy",
+      },
+      externalImportHint: {
+        windows_history: "Reads the Win+V history directly; clipboard history must be enabled in system settings.",
+        ditto: "Choose Ditto.db (in Ditto's data folder, shown under Options → Database path).",
+        copyq: "Choose a copyq_tab_*.dat file from CopyQ's config folder; the tab name becomes the group.",
+        maccy: "Choose Storage.sqlite from ~/Library/Containers/org.p0deje.Maccy/Data/Library/Application Support/Maccy.",
+        clipy: "Clipy's history can't be read; export snippets as XML from the snippet editor and choose that file.",
+      },
+      externalImportSummary: "{{total}} items read: {{imported}} added, {{duplicates}} already present, {{skipped}} skipped",
+      externalSkipReason: {
+        unsupported: "No importable format",
+        invalid_image: "Image could not be decoded",
+        store_failed: "Failed to save image",
+      },
       cleanupTitle: "Data cleanup",
       cleanupDesc: "Clean up and reset app data",
       clearHistory: "Clear clipboard history",
@@ -236,6 +354,64 @@ export const extended = {
       textDedupStrict: "Strict",
       textDedupStrictDesc: "Exact match only",
       textDedupDisabled: "Always new is selected; text dedup mode has no effect.",
+      folderSnapshotTitle: "Folder snapshots",
+      folderSnapshotDesc: "Keep a copy of copied folders so they can still be pasted after the original is moved or deleted",
+      folderSnapshotOff: "Off",
+      folderSnapshotOffDesc: "Only the folder path is recorded",
+      folderSnapshotCopy: "Copy",
+      folderSnapshotCopyDesc: "Copy the folder tree file by file; pastes instantly",
+      folderSnapshotArchive: "Archive",
+      folderSnapshotArchiveDesc: "Store the folder as a compressed ZIP; unpacked on first paste",
+      folderSnapshotMaxMb: "Max folder size (MB)",
+      folderSnapshotMaxEntries: "Max files and subfolders",
+      folderSnapshotIgnore: "Ignored names",
+      folderSnapshotIgnoreHint: "Comma-separated; *.ext matches by suffix. Folders over the limits are not snapshotted.",
+      quotaTitle: "Storage quotas",
+      quotaDesc: "Limit history by size instead of item count; entries and their images or file copies both count",
+      quotaTotal: "Total limit (MB)",
+      quotaGroup: "Per-group limit (MB)",
+      quotaByType: "Per content type (MB)",
+      quotaHint: "0 = no limit. A group's own quota overrides the per-group limit. Pinned and favorite items are never removed.",
+      quotaPolicy: "When over quota",
+      quotaPolicyOldest: "Oldest",
+      quotaPolicyOldestDesc: "Remove the oldest entries first",
+      quotaPolicyLeastAccessed: "Least used",
+      quotaPolicyLeastAccessedDesc: "Remove the entries pasted least often, then the ones unused longest",
+      quotaPolicyLargest: "Largest",
+      quotaPolicyLargestDesc: "Remove the largest entries first",
+      mediaFilesCount: "Media ({{count}} files)",
+      usageByType: "By type",
+      usageByGroup: "By group",
+      storageCheckTitle: "Storage check",
+      storageCheckDesc: "Compare media files with history entries to find orphaned files, missing files and corrupted copies",
+      storageCheckVerify: "Verify file contents",
+      storageCheckVerifyDesc: "Re-hash every stored file to detect corruption (slower)",
+      storageCheckRun: "Check",
+      storageCheckRepair: "Repair",
+      storageCheckRunning: "Checking...",
+      storageCheckProgress: "{{phase}} {{done}}/{{total}}",
+      storageCheckPhase: {
+        items: "Checking entries",
+        objects: "Checking stored files",
+        legacy: "Checking legacy folders",
+        repair: "Repairing",
+      },
+      storageCheckSummary: "Last check {{time}}: {{items}} entries, {{objects}} stored files",
+      storageCheckClean: "No problems found",
+      storageCheckOrphans: "{{count}} orphaned file(s), {{size}}",
+      storageCheckDangling: "{{count}} reference(s) to missing files",
+      storageCheckMismatches: "{{count}} corrupted file(s)",
+      storageCheckRefDrift: "{{count}} incorrect reference count(s)",
+      storageCheckRepaired: "Repaired: deleted {{deleted}} file(s) ({{size}}), re-hashed {{rehashed}}, marked {{missing}} item(s) as missing",
+      storageCheckSchedule: "Scheduled check",
+      storageCheckInterval: {
+        "0": "Off",
+        "1": "Daily",
+        "7": "Weekly",
+        "30": "Monthly",
+      },
+      storageCheckAutoRepair: "Repair automatically",
+      storageCheckAutoRepairDesc: "Delete orphaned files and fix references after scheduled checks",
       historyTitle: "History",
       historyDesc: "Configure history storage limits",
       maxHistory: "Max history count",
@@ -245,7 +421,18 @@ export const extended = {
       maxImageSize: "Max image size",
       maxImageSizeHint: "Images larger than this are not recorded; 0 = unlimited",
       autoCleanup: "Auto cleanup days",
-      autoCleanupHint: "Delete history older than this many days; 0 = disabled",
+      autoCleanupHint: "Base retention for history; frequently used items are kept longer. 0 = disabled",
+      retentionTitle: "Smart retention",
+      retentionDesc: "Keep items you paste often longer than the auto-cleanup period",
+      retentionHalfLife: "Usage half-life (days)",
+      retentionProtectUses: "Protect at uses",
+      retentionSizeRef: "Size reference (MB)",
+      retentionHint: "Past uses fade by half every half-life. Each recent use extends retention by another cleanup period; items at the protect threshold are never auto-deleted. With a size reference, larger items expire sooner. 0 disables decay, protection or size.",
+      retentionPreview: "Preview next cleanup",
+      retentionPreviewEmpty: "The next cleanup will not delete anything",
+      retentionPreviewSummary: "The next cleanup will delete {{count}} item(s), {{size}}",
+      retentionPreviewItem: "{{age}}d old, keep {{keep}}d, {{uses}} uses",
+      retentionPreviewMore: "and {{count}} more",
       migrationDestHasData: "Destination already has data",
       migrationTitle: "Migrate data",
       migrationDestDesc: "Destination has clipboard data. Choose which copy to keep.",
@@ -373,6 +560,51 @@ export const extended = {
       translateDesc: "Microsoft, Google, Baidu, DeepLX, OpenAI and more; translate items and selections",
       toggleAria: "Toggle {{name}}",
     },
+    ocr: {
+      title: "Image text recognition (OCR)",
+      desc: "Recognize text in image clips offline so they can be searched and copied as text",
+      unavailable: "OCR models are not included in this build",
+      enable: "Recognize new images",
+      enableDesc: "Run OCR in the background for each copied image",
+      backfill: "Recognize existing images",
+      backfillDesc: "Run OCR for images in history that have not been recognized yet",
+      backfillStart: "Start",
+      backfillProgress: "Recognizing {{done}} / {{total}}…",
+      cancel: "Cancel",
+      backfillDone: "Recognized {{recognized}} images ({{failed}} failed)",
+      backfillCancelled: "Stopped after {{recognized}} images ({{failed}} failed)",
+      backfillFailed: "OCR failed: {{error}}",
+    },
+    api: {
+      title: "Local scripting API",
+      desc: "Let launchers and editor plugins on this machine read and add clipboard items (see docs/local-api.md)",
+      enable: "Enable API",
+      running: "Listening on {{endpoint}}",
+      stopped: "Not running",
+      error: "Failed to start: {{error}}",
+      token: "Access token",
+      copy: "Copy",
+      tokenCopied: "Token copied",
+      regenerate: "Regenerate",
+      failed: "Failed to update API: {{error}}",
+    },
+    aiTemplates: {
+      title: "AI templates",
+      desc: "Prompt templates listed under AI actions in the item context menu",
+      add: "Add",
+      delete: "Delete",
+      save: "Save",
+      reset: "Restore defaults",
+      name: "Name",
+      system: "System prompt (optional)",
+      prompt: "Prompt",
+      promptHint: "is replaced with the item text; without it the text is appended",
+      temperature: "Temperature (0–2, optional)",
+      newName: "New template",
+      saved: "Templates saved",
+      saveFailed: "Failed to save templates: {{error}}",
+      loadFailed: "Failed to load templates: {{error}}",
+    },
     translate: {
       entryTitle: "Item translation",
       entryDesc: "Show translate in toolbar and context menu; results appear below the item",
@@ -380,6 +612,38 @@ export const extended = {
       enableEntryDesc: "Translate text clipboard items",
       recordOnCopy: "Record when copying translation",
       recordOnCopyDesc: "Copying translation adds a new history entry",
+      saveLinked: "Save translations of clips",
+      saveLinkedDesc: "Translating a clip saves the result as a linked entry, searchable from the original",
+      cacheTitle: "Translation cache",
+      cacheEnable: "Cache translations",
+      cacheEnableDesc: "Reuse earlier results for the same text, language pair and service instead of sending a new request",
+      cacheClear: "Cached translations",
+      cacheCount: "{{count}} entries",
+      cacheClearAction: "Clear",
+      cacheCleared: "Removed {{count}} cached translations",
+      cacheClearFailed: "Failed to clear cache: {{error}}",
+      fallbackTitle: "Fallback providers",
+      fallbackDesc: "When the current provider fails, the fallbacks are tried in order. Providers that keep failing are skipped for a while",
+      fallbackPrimary: "(current)",
+      fallbackUp: "Move up",
+      fallbackDown: "Move down",
+      fallbackRemove: "Remove",
+      fallbackAdd: "Add fallback...",
+      fallbackSaveFailed: "Failed to save fallback providers: {{error}}",
+      healthUnused: "Not used yet",
+      healthCounts: "{{ok}} ok / {{failed}} failed",
+      healthCooling: "Cooling down",
+      healthRefresh: "Refresh",
+      glossaryTitle: "Glossary",
+      glossaryDesc: "Terms that are always translated the same way. Leave the language as All to apply to every target language",
+      glossarySource: "Source term",
+      glossaryTarget: "Translation",
+      glossaryAnyLanguage: "All",
+      glossaryCaseSensitive: "Case sensitive",
+      glossaryRemove: "Remove",
+      glossaryAdd: "Add term",
+      glossarySaved: "Glossary saved",
+      glossarySaveFailed: "Failed to save glossary: {{error}}",
       providerTitle: "Translation provider",
       provider: "Service",
       providerDesc: "Choose translation provider",
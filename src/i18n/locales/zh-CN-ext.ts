@@ -41,13 +41,37 @@ export const extended = {
       saveAs: "另存为",
       viewDetails: "查看详细信息",
       removeRule: "移除 {{rule}}",
+      cancelStaging: "取消复制文件",
+      retryStaging: "重新复制文件",
+      pasteImageText: "粘贴图片中的文字",
+      pluginActions: "插件操作",
+      pluginSaved: "结果已保存为新条目",
+      pluginCopied: "结果已复制到剪贴板",
+      pluginFailed: "插件操作失败：{{error}}",
+      aiActions: "AI 操作",
+      addToPasteQueue: "加入粘贴队列",
+      aiFailed: "AI 操作失败：{{error}}",
+    },
+    pasteQueue: {
+      title: "粘贴队列（{{count}}）",
+      next: "粘贴下一项",
+      nextHint: "下一项：{{preview}}",
+      fifo: "先进先出",
+      lifo: "后进先出",
+      clear: "清空",
+      remove: "移出队列",
+      batchAdd: "加入队列",
     },
   },
   cardContent: {
     imageLoadFailed: "图片加载失败",
+    imageMissing: "图片文件已丢失",
     fileCount: "{{count}} 个文件",
     invalid: "(已失效)",
     fileTooLarge: "(文件过大)",
+    staging: "(复制中 {{percent}}%)",
+    stagingPartial: "(部分已复制)",
+    stagingFailed: "(复制失败)",
   },
   textEditor: {
     title: "编辑文本",
@@ -71,6 +95,20 @@ export const extended = {
     charCount: "{{count}} 字符",
     retranslate: "重新翻译",
   },
+  aiResult: {
+    title: "AI",
+    titleWithTemplate: "AI · {{name}}",
+    waiting: "等待结果...",
+    generating: "生成中...",
+    result: "结果",
+    copy: "复制",
+    copied: "已复制",
+    cancel: "停止",
+    cancelled: "已停止",
+    save: "保存为条目",
+    saved: "已保存",
+    sourceInfo: "{{count}} 个条目 · {{chars}} 字符",
+  },
   scrollTop: {
     backToTop: "回到顶部",
   },
@@ -201,13 +239,93 @@ export const extended = {
       migrateHint: "修改路径将迁移数据并重启应用",
       restoreDefault: "恢复默认",
       backupTitle: "数据备份",
-      backupDesc: "导出或导入剪贴板数据（ZIP 格式）",
+      backupDesc: "导出或导入剪贴板数据（ZIP 格式，可加密）",
       exporting: "导出中...",
       exportData: "导出数据",
       importing: "导入中...",
       importData: "导入数据",
       exportFailed: "导出失败: {{error}}",
       importFailed: "导入失败: {{error}}",
+      exportOptionsTitle: "导出数据",
+      exportOptionsDesc: "选择导出范围，导出条目引用的媒体文件会一并导出",
+      exportScopeAll: "全部数据",
+      exportScopeCustom: "按条件导出",
+      exportGroups: "分组",
+      exportDateFrom: "创建时间起",
+      exportDateTo: "创建时间止",
+      exportContentTypes: "内容类型",
+      exportFavoritesOnly: "仅收藏",
+      exportEncrypt: "设置密码",
+      exportEncryptDesc: "使用 AES-256 加密备份，忘记密码将无法恢复",
+      exportPassword: "密码",
+      exportPasswordConfirm: "确认密码",
+      exportPasswordMismatch: "两次输入的密码不一致",
+      exportNothingSelected: "请至少选择一个分组和一种内容类型",
+      exportStart: "导出",
+      openExportTitle: "导出为开放格式",
+      openExportDesc: "导出不依赖本应用即可阅读的文件，筛选方式与历史列表相同",
+      openExportFormat: {
+        jsonl: "JSON Lines",
+        markdown: "Markdown",
+        html: "HTML 归档",
+      },
+      openExportFormatDesc: {
+        jsonl: "每行一条记录，图片与图标复制到文件旁的 _media 目录并以相对路径引用",
+        markdown: "在新建的文件夹中为每个分组生成一篇笔记，图片位于 media/",
+        html: "单个网页文件，图片以缩略图内嵌，无需其他文件",
+      },
+      openExportSearch: "包含文本",
+      openExportPinnedOnly: "仅置顶",
+      transferProgress: "{{phase}}… {{percent}}%",
+      transferPhase: {
+        export: "正在写入备份",
+        encrypt: "正在加密",
+        decrypt: "正在解密",
+        import: "正在解压并校验",
+        external: "正在导入其他工具的数据",
+        items: "正在导出条目",
+      },
+      importPasswordTitle: "加密备份",
+      importPasswordDesc: "请输入导出该备份时设置的密码",
+      importModeTitle: "导入备份",
+      importModeDesc: "合并会把当前没有的条目追加到现有历史；替换会用备份覆盖整个数据库并重启应用。",
+      importMerge: "合并",
+      importReplace: "替换",
+      mergeSummary: "备份共 {{total}} 条：新增 {{imported}} 条，已存在 {{duplicates}} 条，跳过 {{skipped}} 条",
+      mergeMedia: "将复制 {{count}} 个媒体文件（{{size}}）",
+      mergeGroupsCreated: "新建分组：{{groups}}",
+      mergeGroupsMatched: "按名称对应：{{groups}}",
+      mergeConflicts: "{{count}} 处冲突（保留当前数据）",
+      mergeConflictKind: {
+        other_group: "位于其他分组",
+        state_differs: "收藏 / 置顶状态不同",
+        media_missing: "图片缺失，已跳过",
+        group_color: "分组颜色不同",
+      },
+      mergeDone: "合并完成：新增 {{imported}} 条，已存在 {{duplicates}} 条，跳过 {{skipped}} 条",
+      externalImportTitle: "从其他剪贴板工具导入",
+      externalImportDesc: "迁移 Windows 剪贴板历史、Ditto、CopyQ、Maccy 或 Clipy 的记录，已存在的条目会被跳过",
+      externalImportRun: "导入",
+      externalImportSource: {
+        windows_history: "Windows 剪贴板历史",
+        ditto: "Ditto",
+        copyq: "CopyQ",
+        maccy: "Maccy",
+        clipy: "Clipy",
+      },
+      externalImportHint: {
+        windows_history: "直接读取 Win+V 历史，需要在系统设置中开启剪贴板历史",
+        ditto: "选择 Ditto.db（位于 Ditto 数据目录，可在 选项 → 数据库路径 中查看）",
+        copyq: "选择 CopyQ 配置目录中的 copyq_tab_*.dat，标签页名称作为分组",
+        maccy: "选择 ~/Library/Containers/org.p0deje.Maccy/Data/Library/Application Support/Maccy 下的 Storage.sqlite",
+        clipy: "Clipy 的历史无法读取，请在片段编辑器中导出 XML 后选择该文件",
+      },
+      externalImportSummary: "共读取 {{total}} 条：新增 {{imported}} 条，已存在 {{duplicates}} 条，跳过 {{skipped}} 条",
+      externalSkipReason: {
+        unsupported: "没有可导入的格式",
+        invalid_image: "图片无法解码",
+        store_failed: "图片保存失败",
+      },
       cleanupTitle: "数据清理",
       cleanupDesc: "清理和重置应用数据",
       clearHistory: "清空剪贴板历史",
@@ -236,6 +354,64 @@ export const extended = {
       textDedupStrict: "严格去重",
       textDedupStrictDesc: "内容完全一致才视为重复",
       textDedupDisabled: "当前为「总是新建」，文本去重模式不会生效。",
+      folderSnapshotTitle: "文件夹快照",
+      folderSnapshotDesc: "为复制的文件夹保留副本，原文件夹移动或删除后仍可粘贴",
+      folderSnapshotOff: "关闭",
+      folderSnapshotOffDesc: "只记录文件夹路径",
+      folderSnapshotCopy: "复制",
+      folderSnapshotCopyDesc: "逐文件复制目录树，粘贴时直接使用",
+      folderSnapshotArchive: "压缩包",
+      folderSnapshotArchiveDesc: "压缩为 ZIP 保存，首次粘贴时解压",
+      folderSnapshotMaxMb: "文件夹大小上限 (MB)",
+      folderSnapshotMaxEntries: "文件与子文件夹数量上限",
+      folderSnapshotIgnore: "忽略的名称",
+      folderSnapshotIgnoreHint: "逗号分隔；*.扩展名 按后缀匹配。超出上限的文件夹不创建快照。",
+      quotaTitle: "容量配额",
+      quotaDesc: "按占用大小而非条数限制历史记录，条目内容与其图片、文件副本都计入",
+      quotaTotal: "总容量上限 (MB)",
+      quotaGroup: "每个分组上限 (MB)",
+      quotaByType: "按内容类型 (MB)",
+      quotaHint: "0 = 不限制。分组自身设置的配额优先于每个分组上限。置顶与收藏的条目不会被删除。",
+      quotaPolicy: "超出配额时",
+      quotaPolicyOldest: "最早",
+      quotaPolicyOldestDesc: "优先删除最早的条目",
+      quotaPolicyLeastAccessed: "最少使用",
+      quotaPolicyLeastAccessedDesc: "优先删除粘贴次数最少、最久未使用的条目",
+      quotaPolicyLargest: "最大",
+      quotaPolicyLargestDesc: "优先删除占用最大的条目",
+      mediaFilesCount: "媒体文件（{{count}} 个）",
+      usageByType: "按类型",
+      usageByGroup: "按分组",
+      storageCheckTitle: "存储检查",
+      storageCheckDesc: "比对媒体文件与历史条目，查找孤立文件、丢失的文件与损坏的副本",
+      storageCheckVerify: "校验文件内容",
+      storageCheckVerifyDesc: "重新计算所有已存文件的哈希以发现损坏（较慢）",
+      storageCheckRun: "检查",
+      storageCheckRepair: "修复",
+      storageCheckRunning: "检查中...",
+      storageCheckProgress: "{{phase}} {{done}}/{{total}}",
+      storageCheckPhase: {
+        items: "检查条目",
+        objects: "检查已存文件",
+        legacy: "检查旧版目录",
+        repair: "修复中",
+      },
+      storageCheckSummary: "上次检查 {{time}}：{{items}} 个条目，{{objects}} 个已存文件",
+      storageCheckClean: "未发现问题",
+      storageCheckOrphans: "{{count}} 个孤立文件，共 {{size}}",
+      storageCheckDangling: "{{count}} 处引用的文件已丢失",
+      storageCheckMismatches: "{{count}} 个文件已损坏",
+      storageCheckRefDrift: "{{count}} 个引用计数不正确",
+      storageCheckRepaired: "已修复：删除 {{deleted}} 个文件（{{size}}），重新校验 {{rehashed}} 个，标记 {{missing}} 个条目文件丢失",
+      storageCheckSchedule: "定时检查",
+      storageCheckInterval: {
+        "0": "关闭",
+        "1": "每天",
+        "7": "每周",
+        "30": "每月",
+      },
+      storageCheckAutoRepair: "自动修复",
+      storageCheckAutoRepairDesc: "定时检查后删除孤立文件并修正引用",
       historyTitle: "历史记录",
       historyDesc: "配置历史记录的存储限制",
       maxHistory: "最大历史记录数",
@@ -245,7 +421,18 @@ export const extended = {
       maxImageSize: "单张图片最大大小",
       maxImageSizeHint: "超过该大小的图片不会被记录，可避免从 NAS 等远程位置复制超大图导致卡顿，设为 0 表示无限制",
       autoCleanup: "自动清理天数",
-      autoCleanupHint: "自动删除超过指定天数的历史记录，设为 0 表示不自动清理",
+      autoCleanupHint: "历史记录的基础保留天数，常用条目会保留更久；设为 0 表示不自动清理",
+      retentionTitle: "智能保留",
+      retentionDesc: "经常粘贴的条目保留得比自动清理天数更久",
+      retentionHalfLife: "使用次数半衰期（天）",
+      retentionProtectUses: "保护阈值（次）",
+      retentionSizeRef: "大小参考值 (MB)",
+      retentionHint: "过去的使用次数每经过一个半衰期减半；每次近期使用再延长一个清理周期，达到保护阈值的条目不会被自动清理。设置大小参考值后，越大的条目越早清理。设为 0 表示不衰减、不保护或不考虑大小。",
+      retentionPreview: "预览下次清理",
+      retentionPreviewEmpty: "下次清理不会删除任何条目",
+      retentionPreviewSummary: "下次清理将删除 {{count}} 个条目，共 {{size}}",
+      retentionPreviewItem: "已存 {{age}} 天，保留 {{keep}} 天，使用 {{uses}} 次",
+      retentionPreviewMore: "另有 {{count}} 个",
       migrationDestHasData: "目标位置已有数据",
       migrationTitle: "迁移数据",
       migrationDestDesc: "新位置已存在剪贴板数据，请选择保留哪一份数据。",
@@ -373,6 +560,51 @@ export const extended = {
       translateDesc: "支持微软、谷歌、百度、DeepLX、OpenAI 等多种翻译服务，可翻译剪贴板条目及选中文字",
       toggleAria: "{{name}}开关",
     },
+    ocr: {
+      title: "图片文字识别（OCR）",
+      desc: "离线识别图片条目中的文字，可搜索并复制为文本",
+      unavailable: "当前版本未包含 OCR 模型",
+      enable: "识别新图片",
+      enableDesc: "复制图片后在后台识别文字",
+      backfill: "识别历史图片",
+      backfillDesc: "为历史记录中尚未识别的图片执行 OCR",
+      backfillStart: "开始",
+      backfillProgress: "正在识别 {{done}} / {{total}}…",
+      cancel: "取消",
+      backfillDone: "已识别 {{recognized}} 张图片（失败 {{failed}} 张）",
+      backfillCancelled: "已停止，识别了 {{recognized}} 张图片（失败 {{failed}} 张）",
+      backfillFailed: "OCR 失败：{{error}}",
+    },
+    api: {
+      title: "本地脚本 API",
+      desc: "允许本机的启动器、编辑器插件读取和新增剪贴板条目（见 docs/local-api.md）",
+      enable: "开启 API",
+      running: "正在监听 {{endpoint}}",
+      stopped: "未运行",
+      error: "启动失败：{{error}}",
+      token: "访问令牌",
+      copy: "复制",
+      tokenCopied: "令牌已复制",
+      regenerate: "重新生成",
+      failed: "更新 API 设置失败：{{error}}",
+    },
+    aiTemplates: {
+      title: "AI 模板",
+      desc: "条目右键菜单“AI 操作”中列出的提示词模板",
+      add: "新增",
+      delete: "删除",
+      save: "保存",
+      reset: "恢复默认",
+      name: "名称",
+      system: "系统提示词（可选）",
+      prompt: "提示词",
+      promptHint: "替换为条目文本；不含占位符时文本追加在末尾",
+      temperature: "温度（0–2，可选）",
+      newName: "新模板",
+      saved: "模板已保存",
+      saveFailed: "保存模板失败：{{error}}",
+      loadFailed: "加载模板失败：{{error}}",
+    },
     translate: {
       entryTitle: "条目翻译",
       entryDesc: "开启后，每个条目的工具栏和右键菜单中将出现翻译选项，翻译结果会显示在条目下方",
@@ -380,6 +612,38 @@ export const extended = {
       enableEntryDesc: "开启后可对剪贴板文本条目进行翻译",
       recordOnCopy: "复制翻译时记录条目",
       recordOnCopyDesc: "开启后复制翻译结果时会作为新条目记录到剪贴板历史",
+      saveLinked: "保存条目译文",
+      saveLinkedDesc: "翻译条目时将译文保存为关联条目，可通过译文搜索到原条目",
+      cacheTitle: "翻译缓存",
+      cacheEnable: "缓存译文",
+      cacheEnableDesc: "相同文本、语言与翻译服务直接复用之前的结果，不再发起请求",
+      cacheClear: "已缓存的译文",
+      cacheCount: "{{count}} 条",
+      cacheClearAction: "清空",
+      cacheCleared: "已清除 {{count}} 条缓存译文",
+      cacheClearFailed: "清空缓存失败：{{error}}",
+      fallbackTitle: "备用渠道",
+      fallbackDesc: "当前渠道失败时按顺序尝试备用渠道，连续失败的渠道会暂时跳过",
+      fallbackPrimary: "（当前）",
+      fallbackUp: "上移",
+      fallbackDown: "下移",
+      fallbackRemove: "移除",
+      fallbackAdd: "添加备用渠道...",
+      fallbackSaveFailed: "保存备用渠道失败：{{error}}",
+      healthUnused: "尚未使用",
+      healthCounts: "成功 {{ok}} / 失败 {{failed}}",
+      healthCooling: "冷却中",
+      healthRefresh: "刷新",
+      glossaryTitle: "术语表",
+      glossaryDesc: "始终按固定译法翻译的术语，语言选「全部」时对所有目标语言生效",
+      glossarySource: "原文术语",
+      glossaryTarget: "译文",
+      glossaryAnyLanguage: "全部",
+      glossaryCaseSensitive: "区分大小写",
+      glossaryRemove: "删除",
+      glossaryAdd: "添加术语",
+      glossarySaved: "术语表已保存",
+      glossarySaveFailed: "保存术语表失败：{{error}}",
       providerTitle: "翻译渠道",
       provider: "翻译服务",
       providerDesc: "选择用于翻译的服务提供者",
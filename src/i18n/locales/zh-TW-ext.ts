@@ -41,13 +41,37 @@ export const extended = {
       saveAs: "另存新檔",
       viewDetails: "檢視詳細資訊",
       removeRule: "移除 {{rule}}",
+      cancelStaging: "取消複製檔案",
+      retryStaging: "重新複製檔案",
+      pasteImageText: "貼上圖片中的文字",
+      pluginActions: "外掛操作",
+      pluginSaved: "結果已儲存為新條目",
+      pluginCopied: "結果已複製到剪貼簿",
+      pluginFailed: "外掛操作失敗：{{error}}",
+      aiActions: "AI 操作",
+      addToPasteQueue: "加入貼上佇列",
+      aiFailed: "AI 操作失敗：{{error}}",
+    },
+    pasteQueue: {
+      title: "貼上佇列（{{count}}）",
+      next: "貼上下一項",
+      nextHint: "下一項：{{preview}}",
+      fifo: "先進先出",
+      lifo: "後進先出",
+      clear: "清除",
+      remove: "移出佇列",
+      batchAdd: "加入佇列",
     },
   },
   cardContent: {
     imageLoadFailed: "圖片載入失敗",
+    imageMissing: "圖片檔案已遺失",
     fileCount: "{{count}} 個檔案",
     invalid: "(已失效)",
     fileTooLarge: "(檔案過大)",
+    staging: "(複製中 {{percent}}%)",
+    stagingPartial: "(部分已複製)",
+    stagingFailed: "(複製失敗)",
   },
   textEditor: {
     title: "編輯文字",
@@ -71,6 +95,20 @@ export const extended = {
     charCount: "{{count}} 字元",
     retranslate: "重新翻譯",
   },
+  aiResult: {
+    title: "AI",
+    titleWithTemplate: "AI · {{name}}",
+    waiting: "等待結果...",
+    generating: "生成中...",
+    result: "結果",
+    copy: "複製",
+    copied: "已複製",
+    cancel: "停止",
+    cancelled: "已停止",
+    save: "儲存為條目",
+    saved: "已儲存",
+    sourceInfo: "{{count}} 個條目 · {{chars}} 字元",
+  },
   scrollTop: {
     backToTop: "回到頂部",
   },
@@ -201,13 +239,93 @@ export const extended = {
       migrateHint: "修改路徑將遷移資料並重新啟動應用程式",
       restoreDefault: "恢復預設",
       backupTitle: "資料備份",
-      backupDesc: "匯出或匯入剪貼簿資料（ZIP 格式）",
+      backupDesc: "匯出或匯入剪貼簿資料（ZIP 格式，可加密）",
       exporting: "匯出中...",
       exportData: "匯出資料",
       importing: "匯入中...",
       importData: "匯入資料",
       exportFailed: "匯出失敗: {{error}}",
       importFailed: "匯入失敗: {{error}}",
+      exportOptionsTitle: "匯出資料",
+      exportOptionsDesc: "選擇匯出範圍，匯出項目引用的媒體檔案會一併匯出",
+      exportScopeAll: "全部資料",
+      exportScopeCustom: "依條件匯出",
+      exportGroups: "分組",
+      exportDateFrom: "建立時間起",
+      exportDateTo: "建立時間止",
+      exportContentTypes: "內容類型",
+      exportFavoritesOnly: "僅收藏",
+      exportEncrypt: "設定密碼",
+      exportEncryptDesc: "使用 AES-256 加密備份，忘記密碼將無法還原",
+      exportPassword: "密碼",
+      exportPasswordConfirm: "確認密碼",
+      exportPasswordMismatch: "兩次輸入的密碼不一致",
+      exportNothingSelected: "請至少選擇一個分組和一種內容類型",
+      exportStart: "匯出",
+      openExportTitle: "匯出為開放格式",
+      openExportDesc: "匯出不依賴本應用程式即可閱讀的檔案，篩選方式與歷史記錄列表相同",
+      openExportFormat: {
+        jsonl: "JSON Lines",
+        markdown: "Markdown",
+        html: "HTML 封存",
+      },
+      openExportFormatDesc: {
+        jsonl: "每行一筆記錄，圖片與圖示複製到檔案旁的 _media 目錄並以相對路徑參照",
+        markdown: "在新建的資料夾中為每個分組產生一篇筆記，圖片位於 media/",
+        html: "單一網頁檔案，圖片以縮圖內嵌，無需其他檔案",
+      },
+      openExportSearch: "包含文字",
+      openExportPinnedOnly: "僅置頂",
+      transferProgress: "{{phase}}… {{percent}}%",
+      transferPhase: {
+        export: "正在寫入備份",
+        encrypt: "正在加密",
+        decrypt: "正在解密",
+        import: "正在解壓並校驗",
+        external: "正在匯入其他工具的資料",
+        items: "正在匯出項目",
+      },
+      importPasswordTitle: "加密備份",
+      importPasswordDesc: "請輸入匯出該備份時設定的密碼",
+      importModeTitle: "匯入備份",
+      importModeDesc: "合併會把目前沒有的項目追加到現有歷史；取代會用備份覆蓋整個資料庫並重新啟動應用程式。",
+      importMerge: "合併",
+      importReplace: "取代",
+      mergeSummary: "備份共 {{total}} 筆：新增 {{imported}} 筆，已存在 {{duplicates}} 筆，略過 {{skipped}} 筆",
+      mergeMedia: "將複製 {{count}} 個媒體檔案（{{size}}）",
+      mergeGroupsCreated: "新建分組：{{groups}}",
+      mergeGroupsMatched: "依名稱對應：{{groups}}",
+      mergeConflicts: "{{count}} 處衝突（保留目前資料）",
+      mergeConflictKind: {
+        other_group: "位於其他分組",
+        state_differs: "收藏 / 置頂狀態不同",
+        media_missing: "圖片遺失，已略過",
+        group_color: "分組顏色不同",
+      },
+      mergeDone: "合併完成：新增 {{imported}} 筆，已存在 {{duplicates}} 筆，略過 {{skipped}} 筆",
+      externalImportTitle: "從其他剪貼簿工具匯入",
+      externalImportDesc: "遷移 Windows 剪貼簿歷程記錄、Ditto、CopyQ、Maccy 或 Clipy 的記錄，已存在的項目會被略過",
+      externalImportRun: "匯入",
+      externalImportSource: {
+        windows_history: "Windows 剪貼簿歷程記錄",
+        ditto: "Ditto",
+        copyq: "CopyQ",
+        maccy: "Maccy",
+        clipy: "Clipy",
+      },
+      externalImportHint: {
+        windows_history: "直接讀取 Win+V 歷程記錄，需要在系統設定中開啟剪貼簿歷程記錄",
+        ditto: "選擇 Ditto.db（位於 Ditto 資料目錄，可在 選項 → 資料庫路徑 中查看）",
+        copyq: "選擇 CopyQ 設定目錄中的 copyq_tab_*.dat，分頁名稱作為群組",
+        maccy: "選擇 ~/Library/Containers/org.p0deje.Maccy/Data/Library/Application Support/Maccy 下的 Storage.sqlite",
+        clipy: "Clipy 的歷程記錄無法讀取，請在片段編輯器中匯出 XML 後選擇該檔案",
+      },
+      externalImportSummary: "共讀取 {{total}} 筆：新增 {{imported}} 筆，已存在 {{duplicates}} 筆，略過 {{skipped}} 筆",
+      externalSkipReason: {
+        unsupported: "沒有可匯入的格式",
+        invalid_image: "圖片無法解碼",
+        store_failed: "圖片儲存失敗",
+      },
       cleanupTitle: "資料清理",
       cleanupDesc: "清理和重設應用程式資料",
       clearHistory: "清空剪貼簿歷史",
@@ -236,6 +354,64 @@ export const extended = {
       textDedupStrict: "嚴格去重",
       textDedupStrictDesc: "內容完全一致才視為重複",
       textDedupDisabled: "目前為「總是新建」，文字去重模式不會生效。",
+      folderSnapshotTitle: "資料夾快照",
+      folderSnapshotDesc: "為複製的資料夾保留副本，原資料夾移動或刪除後仍可貼上",
+      folderSnapshotOff: "關閉",
+      folderSnapshotOffDesc: "只記錄資料夾路徑",
+      folderSnapshotCopy: "複製",
+      folderSnapshotCopyDesc: "逐檔複製目錄樹，貼上時直接使用",
+      folderSnapshotArchive: "壓縮檔",
+      folderSnapshotArchiveDesc: "壓縮為 ZIP 儲存，首次貼上時解壓縮",
+      folderSnapshotMaxMb: "資料夾大小上限 (MB)",
+      folderSnapshotMaxEntries: "檔案與子資料夾數量上限",
+      folderSnapshotIgnore: "忽略的名稱",
+      folderSnapshotIgnoreHint: "逗號分隔；*.副檔名 依後綴比對。超出上限的資料夾不建立快照。",
+      quotaTitle: "容量配額",
+      quotaDesc: "依佔用大小而非筆數限制歷史紀錄，條目內容與其圖片、檔案副本都計入",
+      quotaTotal: "總容量上限 (MB)",
+      quotaGroup: "每個分組上限 (MB)",
+      quotaByType: "依內容類型 (MB)",
+      quotaHint: "0 = 不限制。分組自身設定的配額優先於每個分組上限。釘選與收藏的條目不會被刪除。",
+      quotaPolicy: "超出配額時",
+      quotaPolicyOldest: "最早",
+      quotaPolicyOldestDesc: "優先刪除最早的條目",
+      quotaPolicyLeastAccessed: "最少使用",
+      quotaPolicyLeastAccessedDesc: "優先刪除貼上次數最少、最久未使用的條目",
+      quotaPolicyLargest: "最大",
+      quotaPolicyLargestDesc: "優先刪除佔用最大的條目",
+      mediaFilesCount: "媒體檔案（{{count}} 個）",
+      usageByType: "依類型",
+      usageByGroup: "依分組",
+      storageCheckTitle: "儲存檢查",
+      storageCheckDesc: "比對媒體檔案與歷史條目，找出孤立檔案、遺失的檔案與損壞的副本",
+      storageCheckVerify: "驗證檔案內容",
+      storageCheckVerifyDesc: "重新計算所有已存檔案的雜湊以發現損壞（較慢）",
+      storageCheckRun: "檢查",
+      storageCheckRepair: "修復",
+      storageCheckRunning: "檢查中...",
+      storageCheckProgress: "{{phase}} {{done}}/{{total}}",
+      storageCheckPhase: {
+        items: "檢查條目",
+        objects: "檢查已存檔案",
+        legacy: "檢查舊版目錄",
+        repair: "修復中",
+      },
+      storageCheckSummary: "上次檢查 {{time}}：{{items}} 個條目，{{objects}} 個已存檔案",
+      storageCheckClean: "未發現問題",
+      storageCheckOrphans: "{{count}} 個孤立檔案，共 {{size}}",
+      storageCheckDangling: "{{count}} 處參照的檔案已遺失",
+      storageCheckMismatches: "{{count}} 個檔案已損壞",
+      storageCheckRefDrift: "{{count}} 個參照計數不正確",
+      storageCheckRepaired: "已修復：刪除 {{deleted}} 個檔案（{{size}}），重新驗證 {{rehashed}} 個，標記 {{missing}} 個條目檔案遺失",
+      storageCheckSchedule: "定時檢查",
+      storageCheckInterval: {
+        "0": "關閉",
+        "1": "每天",
+        "7": "每週",
+        "30": "每月",
+      },
+      storageCheckAutoRepair: "自動修復",
+      storageCheckAutoRepairDesc: "定時檢查後刪除孤立檔案並修正參照",
       historyTitle: "歷史記錄",
       historyDesc: "設定歷史記錄的儲存限制",
       maxHistory: "最大歷史記錄數",
@@ -245,7 +421,18 @@ export const extended = {
       maxImageSize: "單張圖片最大大小",
       maxImageSizeHint: "超過該大小的圖片不會被記錄，可避免從 NAS 等遠端位置複製超大圖導致卡頓，設為 0 表示無限制",
       autoCleanup: "自動清理天數",
-      autoCleanupHint: "自動刪除超過指定天數的歷史記錄，設為 0 表示不自動清理",
+      autoCleanupHint: "歷史記錄的基礎保留天數，常用條目會保留更久；設為 0 表示不自動清理",
+      retentionTitle: "智慧保留",
+      retentionDesc: "經常貼上的條目保留得比自動清理天數更久",
+      retentionHalfLife: "使用次數半衰期（天）",
+      retentionProtectUses: "保護門檻（次）",
+      retentionSizeRef: "大小參考值 (MB)",
+      retentionHint: "過去的使用次數每經過一個半衰期減半；每次近期使用再延長一個清理週期，達到保護門檻的條目不會被自動清理。設定大小參考值後，越大的條目越早清理。設為 0 表示不衰減、不保護或不考慮大小。",
+      retentionPreview: "預覽下次清理",
+      retentionPreviewEmpty: "下次清理不會刪除任何條目",
+      retentionPreviewSummary: "下次清理將刪除 {{count}} 個條目，共 {{size}}",
+      retentionPreviewItem: "已存 {{age}} 天，保留 {{keep}} 天，使用 {{uses}} 次",
+      retentionPreviewMore: "另有 {{count}} 個",
       migrationDestHasData: "目標位置已有資料",
       migrationTitle: "遷移資料",
       migrationDestDesc: "新位置已存在剪貼簿資料，請選擇保留哪一份資料。",
@@ -373,6 +560,51 @@ export const extended = {
       translateDesc: "支援微軟、谷歌、百度、DeepLX、OpenAI 等多種翻譯服務，可翻譯剪貼簿項目及選取文字",
       toggleAria: "{{name}}開關",
     },
+    ocr: {
+      title: "圖片文字辨識（OCR）",
+      desc: "離線辨識圖片項目中的文字，可搜尋並複製為文字",
+      unavailable: "目前版本未包含 OCR 模型",
+      enable: "辨識新圖片",
+      enableDesc: "複製圖片後在背景辨識文字",
+      backfill: "辨識歷史圖片",
+      backfillDesc: "為歷史記錄中尚未辨識的圖片執行 OCR",
+      backfillStart: "開始",
+      backfillProgress: "正在辨識 {{done}} / {{total}}…",
+      cancel: "取消",
+      backfillDone: "已辨識 {{recognized}} 張圖片（失敗 {{failed}} 張）",
+      backfillCancelled: "已停止，辨識了 {{recognized}} 張圖片（失敗 {{failed}} 張）",
+      backfillFailed: "OCR 失敗：{{error}}",
+    },
+    api: {
+      title: "本機腳本 API",
+      desc: "允許本機的啟動器、編輯器外掛讀取和新增剪貼簿項目（見 docs/local-api.md）",
+      enable: "開啟 API",
+      running: "正在監聽 {{endpoint}}",
+      stopped: "未執行",
+      error: "啟動失敗：{{error}}",
+      token: "存取權杖",
+      copy: "複製",
+      tokenCopied: "權杖已複製",
+      regenerate: "重新產生",
+      failed: "更新 API 設定失敗：{{error}}",
+    },
+    aiTemplates: {
+      title: "AI 範本",
+      desc: "條目右鍵選單「AI 操作」中列出的提示詞範本",
+      add: "新增",
+      delete: "刪除",
+      save: "儲存",
+      reset: "恢復預設",
+      name: "名稱",
+      system: "系統提示詞（選填）",
+      prompt: "提示詞",
+      promptHint: "會替換為條目文字；不含預留位置時文字附加在末尾",
+      temperature: "溫度（0–2，選填）",
+      newName: "新範本",
+      saved: "範本已儲存",
+      saveFailed: "儲存範本失敗：{{error}}",
+      loadFailed: "載入範本失敗：{{error}}",
+    },
     translate: {
       entryTitle: "項目翻譯",
       entryDesc: "開啟後，每個項目的工具列和右鍵選單中將出現翻譯選項，翻譯結果會顯示在項目下方",
@@ -380,6 +612,38 @@ export const extended = {
       enableEntryDesc: "開啟後可對剪貼簿文字項目進行翻譯",
       recordOnCopy: "複製翻譯時記錄項目",
       recordOnCopyDesc: "開啟後複製翻譯結果時會作為新項目記錄到剪貼簿歷史",
+      saveLinked: "儲存項目譯文",
+      saveLinkedDesc: "翻譯項目時將譯文儲存為關聯項目，可透過譯文搜尋到原項目",
+      cacheTitle: "翻譯快取",
+      cacheEnable: "快取譯文",
+      cacheEnableDesc: "相同文字、語言與翻譯服務直接沿用之前的結果，不再發出請求",
+      cacheClear: "已快取的譯文",
+      cacheCount: "{{count}} 筆",
+      cacheClearAction: "清除",
+      cacheCleared: "已清除 {{count}} 筆快取譯文",
+      cacheClearFailed: "清除快取失敗：{{error}}",
+      fallbackTitle: "備用渠道",
+      fallbackDesc: "目前渠道失敗時依序嘗試備用渠道，連續失敗的渠道會暫時略過",
+      fallbackPrimary: "（目前）",
+      fallbackUp: "上移",
+      fallbackDown: "下移",
+      fallbackRemove: "移除",
+      fallbackAdd: "新增備用渠道...",
+      fallbackSaveFailed: "儲存備用渠道失敗：{{error}}",
+      healthUnused: "尚未使用",
+      healthCounts: "成功 {{ok}} / 失敗 {{failed}}",
+      healthCooling: "冷卻中",
+      healthRefresh: "重新整理",
+      glossaryTitle: "術語表",
+      glossaryDesc: "一律以固定譯法翻譯的術語，語言選「全部」時對所有目標語言生效",
+      glossarySource: "原文術語",
+      glossaryTarget: "譯文",
+      glossaryAnyLanguage: "全部",
+      glossaryCaseSensitive: "區分大小寫",
+      glossaryRemove: "刪除",
+      glossaryAdd: "新增術語",
+      glossarySaved: "術語表已儲存",
+      glossarySaveFailed: "儲存術語表失敗：{{error}}",
       providerTitle: "翻譯渠道",
       provider: "翻譯服務",
       providerDesc: "選擇用於翻譯的服務提供者",
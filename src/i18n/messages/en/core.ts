@@ -63,7 +63,8 @@ export const core = {
     editGroup: "Edit group",
     deleteGroup: "Delete group",
     groupNamePlaceholder: "Group name",
-    deleteGroupConfirm: "Delete group \"{{name}}\"? All clipboard items in this group will be deleted permanently.",
+    deleteGroupConfirm: "Delete group \"{{name}}\"? Its items are moved to the group chosen below and its subgroups move up one level.",
+    deleteGroupMoveTo: "Move items to",
     deleteGroupItemCount: "Items in group: {{count}}",
     moveToGroup: "Move to group",
   },
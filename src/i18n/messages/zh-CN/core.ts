@@ -63,7 +63,8 @@ export const core = {
     editGroup: "编辑分组",
     deleteGroup: "删除分组",
     groupNamePlaceholder: "分组名称",
-    deleteGroupConfirm: "确定要删除分组「{{name}}」吗？该分组下的所有剪贴板记录将被同时删除（不可撤销）。",
+    deleteGroupConfirm: "确定要删除分组「{{name}}」吗？该分组下的剪贴板记录将移至下方选择的分组，子分组上移一级。",
+    deleteGroupMoveTo: "条目移至",
     deleteGroupItemCount: "当前分组条目数：{{count}}",
     moveToGroup: "移动到分组",
   },
@@ -63,7 +63,8 @@ export const core = {
     editGroup: "編輯分組",
     deleteGroup: "刪除分組",
     groupNamePlaceholder: "分組名稱",
-    deleteGroupConfirm: "確定要刪除分組「{{name}}」嗎？該分組下的所有剪貼簿記錄將被同時刪除（不可撤銷）。",
+    deleteGroupConfirm: "確定要刪除分組「{{name}}」嗎？該分組下的剪貼簿記錄將移至下方選擇的分組，子分組上移一層。",
+    deleteGroupMoveTo: "項目移至",
     deleteGroupItemCount: "目前分組條目數：{{count}}",
     moveToGroup: "移動到分組",
   },
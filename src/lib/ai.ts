@@ -0,0 +1,32 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** 提示词模板（与后端 `ai::AiTemplate` 一致） */
+export interface AiTemplate {
+  id: string;
+  name: string;
+  system?: string;
+  prompt: string;
+  temperature?: number;
+}
+
+export function listAiTemplates(): Promise<AiTemplate[]> {
+  return invoke<AiTemplate[]>("list_ai_templates");
+}
+
+export function saveAiTemplates(templates: AiTemplate[]): Promise<void> {
+  return invoke("save_ai_templates", { templates });
+}
+
+export function resetAiTemplates(): Promise<AiTemplate[]> {
+  return invoke<AiTemplate[]>("reset_ai_templates");
+}
+
+/** 对条目运行模板，结果在 AI 结果窗口中流式显示；返回 run_id */
+export function runAiAction(itemIds: number[], templateId: string): Promise<number> {
+  return invoke<number>("run_ai_action", { itemIds, templateId });
+}
+
+/** 新模板 ID：仅含字母、数字、`-`、`_`（后端校验规则） */
+export function newTemplateId(): string {
+  return `custom-${Date.now().toString(36)}`;
+}
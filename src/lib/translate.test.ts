@@ -1,5 +1,6 @@
 import { describe, it, expect, vi, beforeEach } from "vitest";
-import { translateText, getLanguages, getProviderOptions } from "./translate";
+import { invoke } from "@tauri-apps/api/core";
+import { translateText, testTranslateConnection, getLanguages, getProviderOptions } from "./translate";
 
 vi.mock("@tauri-apps/api/core", () => ({
   invoke: vi.fn((command: string, args?: Record<string, unknown>) => {
@@ -118,5 +119,12 @@ describe("translate", () => {
       const result = await translateText("Hello");
       expect(result).toBe("Translated text");
     });
+
+    it("bypasses cache and fallbacks when testing the connection", async () => {
+      await translateText("Hello");
+      expect(invoke).toHaveBeenLastCalledWith("translate_text", expect.objectContaining({ test: false }));
+      await testTranslateConnection();
+      expect(invoke).toHaveBeenLastCalledWith("translate_text", expect.objectContaining({ test: true }));
+    });
   });
 });
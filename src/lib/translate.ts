@@ -1,6 +1,8 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { t } from "@/i18n";
 import { logError } from "@/lib/logger";
+import type { ClipboardItem } from "@/stores/clipboard";
 import { useTranslateSettings, type TranslateProvider } from "@/stores/translate-settings";
 
 export function getLanguages() {
@@ -76,13 +78,44 @@ function localizeTranslateError(error: unknown): string {
   return localized !== i18nKey ? localized : error;
 }
 
-export async function translateText(text: string): Promise<string> {
+let nextStreamId = 1;
+
+/**
+ * 翻译文本。传入 `onPartial` 时，支持流式的服务（OpenAI / DeepLX）会持续回调已译出的内容；
+ * 命中翻译缓存时直接返回结果。传入 `sourceId` 时译文会保存为关联到该条目的新条目。
+ */
+export async function translateText(
+  text: string,
+  onPartial?: (partial: string) => void,
+  sourceId?: number,
+): Promise<string> {
+  return requestTranslation(text, onPartial, sourceId, false);
+}
+
+/** 测试当前翻译服务：只请求主服务，不读写翻译缓存 */
+export async function testTranslateConnection(): Promise<string> {
+  return requestTranslation("Hello", undefined, undefined, true);
+}
+
+async function requestTranslation(
+  text: string,
+  onPartial: ((partial: string) => void) | undefined,
+  sourceId: number | undefined,
+  test: boolean,
+): Promise<string> {
   const settings = useTranslateSettings.getState();
   if (!settings.enabled) throw new Error(t("translate.errors.FEATURE_DISABLED"));
 
   const { from, to } = resolveLanguages(text);
+  const streamId = onPartial ? nextStreamId++ : null;
+  let unlisten: UnlistenFn | null = null;
 
   try {
+    if (onPartial) {
+      unlisten = await listen<{ stream_id: number; text: string }>("translate-stream", (event) => {
+        if (event.payload.stream_id === streamId) onPartial(event.payload.text);
+      });
+    }
     return await invoke<string>("translate_text", {
       text,
       from,
@@ -97,11 +130,32 @@ export async function translateText(text: string): Promise<string> {
       openaiEndpoint: settings.openaiEndpoint || null,
       openaiApiKey: settings.openaiApiKey || null,
       openaiModel: settings.openaiModel || null,
+      streamId,
+      sourceId: sourceId ?? null,
+      test,
     });
   } catch (error) {
     logError("翻译失败:", error);
     throw new Error(localizeTranslateError(error));
+  } finally {
+    unlisten?.();
+  }
+}
+
+/** 条目已保存的译文（目标语言与当前设置一致的最新一条），没有时返回 null */
+export async function getLinkedTranslation(sourceId: number, text: string): Promise<string | null> {
+  const { to } = resolveLanguages(text);
+  const derived = await invoke<ClipboardItem[]>("get_derived_items", { id: sourceId });
+  for (const item of derived) {
+    if (!item.text_content || !item.derivation) continue;
+    try {
+      const meta = JSON.parse(item.derivation) as { kind?: string; to?: string };
+      if (meta.kind === "translation" && meta.to === to) return item.text_content;
+    } catch {
+      // 无法解析的元数据视为其它派生类型
+    }
   }
+  return null;
 }
 
 export function getProviderOptions(): { value: TranslateProvider; label: string; needsConfig: boolean }[] {
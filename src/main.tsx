@@ -17,6 +17,9 @@ const TextEditor = lazy(() =>
 const TranslateResult = lazy(() =>
   import("./pages/TranslateResult").then((m) => ({ default: m.TranslateResult })),
 );
+const AiResult = lazy(() =>
+  import("./pages/AiResult").then((m) => ({ default: m.AiResult })),
+);
 
 // 禁用右键菜单
 document.addEventListener("contextmenu", (e) => {
@@ -64,6 +67,13 @@ function Router() {
       </Suspense>
     );
   }
+  if (path === "/ai-result") {
+    return (
+      <Suspense fallback={<RouteFallback />}>
+        <AiResult />
+      </Suspense>
+    );
+  }
 
   return <App />;
 }
@@ -0,0 +1,139 @@
+import { useState, useEffect, useCallback } from "react";
+import { Sparkle16Regular, Copy16Regular } from "@fluentui/react-icons";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { Button } from "@/components/ui/button";
+import { Card } from "@/components/ui/card";
+import { WindowTitleBar } from "@/components/WindowTitleBar";
+import { useTranslation } from "@/i18n";
+import { logError } from "@/lib/logger";
+import { initTheme } from "@/lib/theme-applier";
+import { cn } from "@/lib/utils";
+
+interface AiRun {
+  run_id: number;
+  template_id: string;
+  template_name: string;
+  source_ids: number[];
+  source_chars: number;
+  text: string;
+  status: "running" | "done" | "failed" | "cancelled";
+  error: string | null;
+  item_id: number | null;
+}
+
+export function AiResult() {
+  const { t } = useTranslation();
+  const [run, setRun] = useState<AiRun | null>(null);
+  const [themeReady, setThemeReady] = useState(false);
+  const [copied, setCopied] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    void initTheme();
+    setThemeReady(true);
+  }, []);
+
+  useEffect(() => {
+    // 先监听再取快照，避免错过挂载期间的进度；旧操作的事件按 run_id 丢弃
+    const unlisten = listen<AiRun>("ai-action-update", (event) => {
+      setRun((prev) => (prev && event.payload.run_id < prev.run_id ? prev : event.payload));
+    });
+    invoke<AiRun | null>("get_ai_run")
+      .then((snapshot) => {
+        if (snapshot) setRun((prev) => (prev && prev.run_id >= snapshot.run_id ? prev : snapshot));
+      })
+      .catch((error) => logError("Failed to load AI run:", error));
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") getCurrentWindow().close();
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, []);
+
+  const running = run?.status === "running";
+
+  const handleCancel = useCallback(async () => {
+    if (!run) return;
+    try {
+      await invoke("cancel_ai_action", { runId: run.run_id });
+    } catch (error) { logError("取消 AI 操作失败:", error); }
+  }, [run]);
+
+  const handleCopy = useCallback(async () => {
+    if (!run?.text) return;
+    try {
+      await invoke("write_text_to_clipboard", { text: run.text, record: false });
+      setCopied(true);
+      setTimeout(() => setCopied(false), 1500);
+    } catch (error) { logError("复制失败:", error); }
+  }, [run]);
+
+  const handleSave = useCallback(async () => {
+    if (!run) return;
+    setSaving(true);
+    try {
+      await invoke("save_ai_result", { runId: run.run_id });
+    } catch (error) {
+      logError("保存 AI 结果失败:", error);
+    } finally {
+      setSaving(false);
+    }
+  }, [run]);
+
+  return (
+    <div className={cn("h-screen flex flex-col bg-page-shell overflow-hidden p-3 gap-3", !themeReady ? "opacity-0 **:transition-none!" : "opacity-100")}>
+      <WindowTitleBar
+        icon={<Sparkle16Regular className="w-5 h-5 text-muted-foreground" />}
+        title={run ? t("aiResult.titleWithTemplate", { name: run.template_name }) : t("aiResult.title")}
+      />
+
+      <Card className="flex-1 overflow-hidden flex flex-col min-h-0">
+        <div className="flex items-center justify-between px-4 pt-3 pb-1">
+          <span className="text-xs font-medium text-muted-foreground">
+            {running ? t("aiResult.generating") : t("aiResult.result")}
+          </span>
+          {run?.text && (
+            <Button variant="ghost" size="sm" className="h-6 px-2 text-xs" onClick={handleCopy}>
+              <Copy16Regular className="w-3 h-3 mr-1" />
+              {copied ? t("aiResult.copied") : t("aiResult.copy")}
+            </Button>
+          )}
+        </div>
+        <div className="flex-1 overflow-auto px-4 pb-3">
+          {!run && <p className="text-sm text-muted-foreground">{t("aiResult.waiting")}</p>}
+          {run?.text && (
+            <p className="text-sm leading-relaxed whitespace-pre-wrap cursor-text select-text ui-font">{run.text}</p>
+          )}
+          {run?.status === "cancelled" && <p className="text-sm text-muted-foreground">{t("aiResult.cancelled")}</p>}
+          {run?.error && <p className="text-sm text-destructive">{run.error}</p>}
+        </div>
+        <div className="shrink-0 flex items-center justify-between px-4 py-2 border-t">
+          <span className="text-xs text-muted-foreground">
+            {run ? t("aiResult.sourceInfo", { count: run.source_ids.length, chars: run.source_chars }) : ""}
+          </span>
+          {running ? (
+            <Button variant="ghost" size="sm" className="h-7 px-2 text-xs" onClick={handleCancel}>
+              {t("aiResult.cancel")}
+            </Button>
+          ) : run?.status === "done" && (
+            <Button
+              variant="ghost"
+              size="sm"
+              className="h-7 px-2 text-xs text-primary hover:text-primary hover:bg-primary-subtle"
+              onClick={handleSave}
+              disabled={saving || run.item_id !== null || !run.text}
+            >
+              {run.item_id !== null ? t("aiResult.saved") : t("aiResult.save")}
+            </Button>
+          )}
+        </div>
+      </Card>
+    </div>
+  );
+}
@@ -44,7 +44,9 @@ export function TranslateResult() {
     setTranslateError("");
     setTranslatedText("");
     try {
-      const result = await translateText(sourceText);
+      const result = await translateText(sourceText, (partial) => {
+        if (reqId === requestIdRef.current) setTranslatedText(partial);
+      });
       if (reqId !== requestIdRef.current) return; // 过期请求，丢弃结果
       setTranslatedText(result);
     } catch (error) {
@@ -174,7 +176,7 @@ export function TranslateResult() {
           )}
         </div>
         <div className="flex-1 overflow-auto px-4 pb-3">
-          {translating && <p className="text-sm text-muted-foreground">{t("translateResult.translatingProgress")}</p>}
+          {translating && !translatedText && <p className="text-sm text-muted-foreground">{t("translateResult.translatingProgress")}</p>}
           {translatedText && (
             <p className="text-sm leading-relaxed whitespace-pre-wrap cursor-text select-text ui-font">{translatedText}</p>
           )}
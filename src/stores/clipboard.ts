@@ -38,10 +38,39 @@ export interface ClipboardItem {
   source_app_icon: string | null;
   /** 所属自定义分组 ID（null = 默认分组） */
   group_id: number | null;
+  /** 图片 OCR 文字（null = 未识别；列表查询不返回） */
+  ocr_text?: string | null;
+  /** 文本子类型标签，逗号分隔（如 "color"、"code,lang:rust"） */
+  subtypes: string | null;
+  /** 捕获规则设置的过期时间（null = 永久） */
+  expires_at: string | null;
+  /** 源条目 ID（派生条目，如译文；null = 非派生） */
+  derived_from: number | null;
+  /** 派生元数据 JSON（如 {"kind":"translation","provider":"deeplx","from":"en","to":"zh"}） */
+  derivation: string | null;
+  /** 文件后台复制状态（null = 无需复制） */
+  staging_state: "pending" | "partial" | "done" | "failed" | null;
+  /** 存储检查发现媒体文件缺失 */
+  media_missing: boolean;
+  /** 标签名称（列表与详情查询时附加） */
+  tags?: string[] | null;
   /** 所有文件是否存在（仅 files 类型，查询时计算） */
   files_valid?: boolean;
 }
 
+interface StagingProgressEvent {
+  id: number;
+  file_index: number;
+  file_count: number;
+  bytes_done: number;
+  bytes_total: number;
+}
+
+interface StagingUpdatedEvent {
+  id: number;
+  state: ClipboardItem["staging_state"];
+}
+
 interface ClipboardState {
   items: ClipboardItem[];
   isLoading: boolean;
@@ -55,6 +84,8 @@ interface ClipboardState {
   _fetchId: number;
   /** 视图重置计数（滚动到顶部等） */
   _resetToken: number;
+  /** 文件后台复制进度（条目 ID → 百分比） */
+  stagingProgress: Record<number, number>;
 
   // 操作
   fetchItems: (options?: {
@@ -75,6 +106,8 @@ interface ClipboardState {
   copyToClipboard: (id: number) => Promise<void>;
   pasteContent: (id: number) => Promise<void>;
   pasteAsPlainText: (id: number) => Promise<void>;
+  /** 粘贴图片条目中 OCR 识别出的文字 */
+  pasteImageText: (id: number) => Promise<void>;
   /** 清空当前分组历史，返回删除条数；失败返回 null */
   clearHistory: (contentType?: string | null) => Promise<number | null>;
   refresh: () => Promise<void>;
@@ -98,7 +131,7 @@ interface ClipboardState {
 async function doPaste(
   get: () => ClipboardState,
   id: number,
-  command: "paste_content" | "paste_content_as_plain",
+  command: "paste_content" | "paste_content_as_plain" | "paste_image_text",
 ) {
   try {
     cancelPendingFocusRestore();
@@ -121,6 +154,7 @@ export const useClipboardStore = create<ClipboardState>((set, get) => ({
   activeIndex: -1,
   _fetchId: 0,
   _resetToken: 0,
+  stagingProgress: {},
 
   fetchItems: async (options = {}) => {
     const state = get();
@@ -245,6 +279,10 @@ export const useClipboardStore = create<ClipboardState>((set, get) => ({
     await doPaste(get, id, "paste_content_as_plain");
   },
 
+  pasteImageText: async (id: number) => {
+    await doPaste(get, id, "paste_image_text");
+  },
+
   // contentType=null 时后端 Option<String> 为 None，清除所有类型（正确行为）
   clearHistory: async (contentType = null) => {
     try {
@@ -317,9 +355,45 @@ export const useClipboardStore = create<ClipboardState>((set, get) => ({
       playCopySound("immediate");
       void debouncedCaptureUpdate(id);
     });
+
+    const unlistenStagingProgress = await listen<StagingProgressEvent>(
+      "file-staging-progress",
+      (event) => {
+        const { id, bytes_done, bytes_total } = event.payload;
+        const percent = bytes_total > 0 ? Math.floor((bytes_done / bytes_total) * 100) : 0;
+        set((s) => ({ stagingProgress: { ...s.stagingProgress, [id]: percent } }));
+      },
+    );
+    const unlistenStagingUpdated = await listen<StagingUpdatedEvent>(
+      "file-staging-updated",
+      (event) => {
+        const { id, state } = event.payload;
+        set((s) => {
+          const { [id]: _, ...stagingProgress } = s.stagingProgress;
+          return {
+            stagingProgress,
+            items: s.items.map((item) =>
+              item.id === id ? { ...item, staging_state: state } : item,
+            ),
+          };
+        });
+      },
+    );
+    // 捕获规则 TTL 到期的条目由后端定时删除
+    const unlistenExpired = await listen("clipboard-items-expired", () => {
+      void get().refresh();
+    });
+    // 本地 API 置顶 / 移动 / 删除条目
+    const unlistenChanged = await listen("clipboard-items-changed", () => {
+      void get().refresh();
+    });
     return () => {
       unlistenPasteSound();
       unlisten();
+      unlistenStagingProgress();
+      unlistenStagingUpdated();
+      unlistenExpired();
+      unlistenChanged();
     };
   },
 
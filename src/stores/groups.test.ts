@@ -102,6 +102,14 @@ describe("group store", () => {
       await useGroupStore.getState().deleteGroup(1);
       expect(useGroupStore.getState().groups).toHaveLength(1);
     });
+
+    it("moves item count to the target group and refreshes items", async () => {
+      await useGroupStore.getState().fetchGroups();
+      await useGroupStore.getState().deleteGroup(1, 2);
+      const state = useGroupStore.getState();
+      expect(state.groups.find((g) => g.id === 2)?.item_count).toBe(8);
+      expect(mockFetchItems).toHaveBeenCalled();
+    });
   });
 
   describe("moveItemToGroup", () => {
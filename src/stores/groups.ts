@@ -10,17 +10,38 @@ export interface Group {
   sort_order: number;
   created_at: string;
   item_count: number;
+  /** 父分组（null = 顶层） */
+  parent_id: number | null;
+  /** 以下为分组级设置，null 表示沿用全局设置 */
+  retention_days: number | null;
+  max_count: number | null;
+  dedup_strategy: "ignore" | "move_to_top" | "always_new" | null;
+  sync_enabled: boolean;
+  /** 容量配额（MB，0 = 不限制） */
+  quota_mb: number | null;
 }
 
+export type GroupSettings = Pick<
+  Group,
+  "retention_days" | "max_count" | "dedup_strategy" | "sync_enabled" | "quota_mb"
+>;
+
 interface GroupState {
   groups: Group[];
   isLoading: boolean;
 
   fetchGroups: () => Promise<void>;
-  createGroup: (name: string, color?: string) => Promise<Group | null>;
+  createGroup: (
+    name: string,
+    color?: string,
+    parentId?: number | null,
+  ) => Promise<Group | null>;
   renameGroup: (id: number, name: string) => Promise<void>;
   updateGroupColor: (id: number, color: string | null) => Promise<void>;
-  deleteGroup: (id: number) => Promise<void>;
+  /** 删除分组，条目移至 moveItemsTo（null = 默认分组） */
+  deleteGroup: (id: number, moveItemsTo?: number | null) => Promise<void>;
+  setGroupParent: (id: number, parentId: number | null) => Promise<void>;
+  updateGroupSettings: (id: number, settings: GroupSettings) => Promise<void>;
   moveItemToGroup: (itemId: number, groupId: number | null) => Promise<void>;
 }
 
@@ -39,11 +60,12 @@ export const useGroupStore = create<GroupState>((set, get) => ({
     }
   },
 
-  createGroup: async (name, color) => {
+  createGroup: async (name, color, parentId) => {
     try {
       const group = await invoke<Group>("create_group", {
         name,
         color: color ?? null,
+        parentId: parentId ?? null,
       });
       set((state) => ({ groups: [...state.groups, group] }));
       return group;
@@ -75,14 +97,60 @@ export const useGroupStore = create<GroupState>((set, get) => ({
     }
   },
 
-  deleteGroup: async (id) => {
+  deleteGroup: async (id, moveItemsTo) => {
+    try {
+      await invoke("delete_group", { id, moveItemsTo: moveItemsTo ?? null });
+      set((state) => {
+        const deleted = state.groups.find((g) => g.id === id);
+        return {
+          groups: state.groups
+            .filter((g) => g.id !== id)
+            .map((g) => {
+              // 子分组上移一级，条目计数并入目标分组
+              let next = g;
+              if (g.parent_id === id) {
+                next = { ...next, parent_id: deleted?.parent_id ?? null };
+              }
+              if (g.id === moveItemsTo && deleted) {
+                next = {
+                  ...next,
+                  item_count: g.item_count + deleted.item_count,
+                };
+              }
+              return next;
+            }),
+        };
+      });
+      // 条目已移至其他分组，刷新列表
+      useClipboardStore.getState().fetchItems();
+    } catch (error) {
+      logError("Failed to delete group:", error);
+    }
+  },
+
+  setGroupParent: async (id, parentId) => {
     try {
-      await invoke("delete_group", { id });
+      await invoke("set_group_parent", { id, parentId });
       set((state) => ({
-        groups: state.groups.filter((g) => g.id !== id),
+        groups: state.groups.map((g) =>
+          g.id === id ? { ...g, parent_id: parentId } : g,
+        ),
       }));
     } catch (error) {
-      logError("Failed to delete group:", error);
+      logError("Failed to set group parent:", error);
+    }
+  },
+
+  updateGroupSettings: async (id, settings) => {
+    try {
+      await invoke("update_group_settings", { id, settings });
+      set((state) => ({
+        groups: state.groups.map((g) =>
+          g.id === id ? { ...g, ...settings } : g,
+        ),
+      }));
+    } catch (error) {
+      logError("Failed to update group settings:", error);
     }
   },
 
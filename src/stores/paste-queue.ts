@@ -0,0 +1,68 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { create } from "zustand";
+import { logError } from "@/lib/logger";
+
+export type PasteQueueMode = "fifo" | "lifo";
+
+export interface PasteQueueEntry {
+  id: number;
+  content_type: string;
+  preview: string | null;
+}
+
+/** 后端 `PasteQueueState`，`items` 按粘贴顺序排列 */
+interface PasteQueueSnapshot {
+  mode: PasteQueueMode;
+  items: PasteQueueEntry[];
+}
+
+interface PasteQueueState extends PasteQueueSnapshot {
+  add: (ids: number[]) => Promise<void>;
+  remove: (id: number) => Promise<void>;
+  clear: () => Promise<void>;
+  setMode: (mode: PasteQueueMode) => Promise<void>;
+  pasteNext: () => Promise<void>;
+}
+
+export const usePasteQueue = create<PasteQueueState>((set) => {
+  // 命令返回最新快照；其他窗口 / 快捷键的变化由 paste-queue-changed 事件同步
+  const run = async (command: string, args?: Record<string, unknown>) => {
+    try {
+      set(await invoke<PasteQueueSnapshot>(command, args));
+    } catch (error) {
+      logError(`Failed to ${command}:`, error);
+    }
+  };
+
+  return {
+    mode: "fifo",
+    items: [],
+    add: (ids) => run("paste_queue_add", { ids }),
+    remove: (id) => run("paste_queue_remove", { id }),
+    clear: () => run("paste_queue_clear"),
+    setMode: (mode) => run("paste_queue_set_mode", { mode }),
+    pasteNext: async () => {
+      try {
+        await invoke("paste_queue_next");
+      } catch (error) {
+        logError("Failed to paste next queue item:", error);
+      }
+    },
+  };
+});
+
+let initialized = false;
+
+export async function initPasteQueue() {
+  if (initialized) return;
+  initialized = true;
+  try {
+    await listen<PasteQueueSnapshot>("paste-queue-changed", (event) => {
+      usePasteQueue.setState(event.payload);
+    });
+    usePasteQueue.setState(await invoke<PasteQueueSnapshot>("get_paste_queue"));
+  } catch (error) {
+    logError("Failed to init paste queue:", error);
+  }
+}
@@ -1,3 +1,4 @@
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { create } from "zustand";
 import { logError } from "@/lib/logger";
@@ -13,22 +14,26 @@ import {
 type PluginAvailabilityState = {
   webdavAvailable: boolean;
   translateAvailable: boolean;
+  /** OCR 模型随安装包分发，运行期间不会变化 */
+  ocrAvailable: boolean;
   refresh: () => Promise<void>;
 };
 
 export const usePluginAvailability = create<PluginAvailabilityState>((set) => ({
   webdavAvailable: false,
   translateAvailable: false,
+  ocrAvailable: false,
   refresh: async () => {
     try {
-      const [webdavAvailable, translateAvailable] = await Promise.all([
+      const [webdavAvailable, translateAvailable, ocrAvailable] = await Promise.all([
         fetchWebDAVAvailable(),
         fetchTranslateAvailable(),
+        invoke<boolean>("is_ocr_available"),
       ]);
-      set({ webdavAvailable, translateAvailable });
+      set({ webdavAvailable, translateAvailable, ocrAvailable });
     } catch (error) {
       logError("Failed to refresh plugin availability:", error);
-      set({ webdavAvailable: false, translateAvailable: false });
+      set({ webdavAvailable: false, translateAvailable: false, ocrAvailable: false });
     }
   },
 }));
@@ -64,3 +69,7 @@ export function useWebDAVAvailable() {
 export function useTranslateAvailable() {
   return usePluginAvailability((state) => state.translateAvailable);
 }
+
+export function useOcrAvailable() {
+  return usePluginAvailability((state) => state.ocrAvailable);
+}
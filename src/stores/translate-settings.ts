@@ -12,6 +12,7 @@ export type LanguageMode = "auto" | "manual";
 export interface TranslateSettings {
   enabled: boolean;
   recordTranslation: boolean;
+  saveLinked: boolean;
   provider: TranslateProvider;
   languageMode: LanguageMode;
   sourceLanguage: string;
@@ -34,6 +35,7 @@ interface TranslateSettingsStore extends TranslateSettings {
   loadSettings: () => Promise<void>;
   setEnabled: (enabled: boolean) => void;
   setRecordTranslation: (record: boolean) => void;
+  setSaveLinked: (save: boolean) => void;
   setProvider: (provider: TranslateProvider) => void;
   setLanguageMode: (mode: LanguageMode) => void;
   setSourceLanguage: (lang: string) => void;
@@ -54,6 +56,7 @@ interface TranslateSettingsStore extends TranslateSettings {
 const DEFAULT_TRANSLATE_SETTINGS: TranslateSettings = {
   enabled: false,
   recordTranslation: false,
+  saveLinked: true,
   provider: "microsoft",
   languageMode: "auto",
   sourceLanguage: "",
@@ -81,6 +84,7 @@ const TRANSLATE_SETTINGS_KEYS = Object.keys(DEFAULT_TRANSLATE_SETTINGS) as (keyo
 const FIELD_TO_DB_KEY: Record<keyof TranslateSettings, string> = {
   enabled: "translate_enabled",
   recordTranslation: "translate_record_translation",
+  saveLinked: "translate_save_linked",
   provider: "translate_provider",
   languageMode: "translate_language_mode",
   sourceLanguage: "translate_source_language",
@@ -187,6 +191,7 @@ export const useTranslateSettings = create<TranslateSettingsStore>((set, get) =>
 
     setEnabled: makeSetter("enabled"),
     setRecordTranslation: makeSetter("recordTranslation"),
+    setSaveLinked: makeSetter("saveLinked"),
     setProvider: makeSetter("provider"),
     setLanguageMode: makeSetter("languageMode"),
     setSourceLanguage: makeSetter("sourceLanguage"),